@@ -3,6 +3,33 @@ use clap_complete::Shell;
 
 #[derive(Parser)]
 pub struct Cli {
+    #[clap(
+        long = "quiet",
+        short = 'q',
+        global = true,
+        help = "Print only a machine-readable result for sync, new, fixup and prune-branches (nothing when there is nothing to report)"
+    )]
+    pub quiet: bool,
+    #[clap(
+        long = "verbose",
+        global = true,
+        help = "Show the failing git command, its exit status and full stderr when something goes wrong"
+    )]
+    pub verbose: bool,
+    #[clap(
+        long = "jobs",
+        short = 'j',
+        global = true,
+        value_name = "N",
+        help = "Git processes and worker threads to run at once (default: git-x.jobs, else one per CPU)"
+    )]
+    pub jobs: Option<usize>,
+    #[clap(
+        long = "offline",
+        global = true,
+        help = "Skip fetches, pushes, ls-remote probes and gh calls, working from what was last fetched (default: GIT_X_OFFLINE or git-x.offline)"
+    )]
+    pub offline: bool,
     #[clap(subcommand)]
     pub command: Commands,
 }
@@ -14,6 +41,11 @@ pub enum Commands {
         #[clap(help = "New name for the current branch")]
         new_name: String,
     },
+    #[clap(about = "Create a branch at a detached HEAD and switch to it")]
+    Attach {
+        #[clap(help = "Name of the branch to create")]
+        name: String,
+    },
     #[clap(about = "Delete merged local branches (except protected ones)")]
     PruneBranches {
         #[clap(
@@ -24,76 +56,970 @@ pub enum Commands {
         except: Option<String>,
         #[clap(long = "dry-run", help = "Show which branches would be deleted without actually deleting them", action = clap::ArgAction::SetTrue)]
         dry_run: bool,
+        #[clap(short = 'y', long = "yes", help = "Delete without asking, even many branches in a non-interactive run", action = clap::ArgAction::SetTrue)]
+        yes: bool,
+        #[clap(
+            long = "older-than",
+            value_name = "AGE",
+            help = "Only branches whose last commit is older than this, e.g. 30d, 2w, 6m or 1y"
+        )]
+        older_than: Option<String>,
+        #[clap(
+            long = "allow-unmerged",
+            requires = "older_than",
+            help = "Also delete old unmerged branches, after confirming and archiving each as a tag"
+        )]
+        allow_unmerged: bool,
+    },
+    #[clap(
+        name = "branch-deps",
+        about = "Show which branches are built on which, to plan clean-up order"
+    )]
+    BranchDeps,
+    #[clap(
+        name = "merge-queue",
+        about = "Simulate merging branches onto the default branch in order and find the first conflict"
+    )]
+    MergeQueue {
+        #[clap(help = "Branches to merge, in order")]
+        branches: Vec<String>,
+        #[clap(
+            long = "base",
+            help = "Branch to merge onto (default: the default branch)"
+        )]
+        base: Option<String>,
+        #[clap(
+            long = "prs",
+            help = "Also queue open pull requests against the base, oldest first (needs gh)"
+        )]
+        prs: bool,
+    },
+    #[clap(
+        name = "move",
+        about = "Move a branch's commits onto another base (git rebase --onto, with a preview)"
+    )]
+    Move {
+        #[clap(help = "Branch whose commits to move (default: the current branch, or pick one)")]
+        branch: Option<String>,
+        #[clap(
+            long = "onto",
+            help = "New base for the commits (picked interactively if omitted)"
+        )]
+        onto: Option<String>,
+        #[clap(
+            long = "from",
+            help = "Old base: the commits after it are moved (default: the commits on no other branch)"
+        )]
+        from: Option<String>,
+        #[clap(long = "dry-run", help = "Show the resulting history without moving anything", action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+    },
+    #[clap(about = "Check out what a branch looked like at a point in time")]
+    At {
+        #[clap(help = "When, e.g. \"yesterday 17:00\", \"friday 18:00\" or \"2025-07-04 09:30\"")]
+        when: String,
+        #[clap(help = "Branch or ref to look at (default: the current branch)")]
+        reference: Option<String>,
+        #[clap(
+            long = "create",
+            value_name = "NAME",
+            num_args = 0..=1,
+            default_missing_value = "",
+            conflicts_with = "print",
+            help = "Create a branch there instead of detaching HEAD (default name: at/<ref>-<time>)"
+        )]
+        create: Option<String>,
+        #[clap(long = "print", help = "Only print the commit hash")]
+        print: bool,
+        #[clap(
+            long = "commit-dates",
+            help = "Go by committer dates on the first-parent history instead of the reflog"
+        )]
+        commit_dates: bool,
+    },
+    #[clap(about = "Check out pull requests, forks included, and clean them up afterwards")]
+    Pr {
+        #[clap(subcommand)]
+        action: PrAction,
     },
     #[clap(about = "Show a high-level overview of the current repo")]
-    Info,
+    Info {
+        #[clap(long = "json", help = "Output as JSON", action = clap::ArgAction::SetTrue)]
+        json: bool,
+    },
     #[clap(about = "Pretty Git log with branches, remotes, and HEADs")]
-    Graph,
+    Graph {
+        #[clap(
+            long = "first-parent",
+            help = "Follow only the first parent of merges: the mainline, without feature branch commits (default branch unless --branch is given)"
+        )]
+        first_parent: bool,
+        #[clap(
+            long = "merges-only",
+            help = "List only the merges on the mainline, with their pull request titles"
+        )]
+        merges_only: bool,
+        #[clap(
+            long = "branch",
+            short = 'b',
+            value_name = "BRANCH",
+            help = "Show this branch instead of every ref (repeatable)"
+        )]
+        branches: Vec<String>,
+    },
     #[clap(about = "Colorized Git log with branches, remotes, and HEADs")]
-    ColorGraph,
+    ColorGraph {
+        #[clap(
+            long = "first-parent",
+            help = "Follow only the first parent of merges: the mainline, without feature branch commits (default branch unless --branch is given)"
+        )]
+        first_parent: bool,
+        #[clap(
+            long = "merges-only",
+            help = "List only the merges on the mainline, with their pull request titles"
+        )]
+        merges_only: bool,
+        #[clap(
+            long = "branch",
+            short = 'b',
+            value_name = "BRANCH",
+            help = "Show this branch instead of every ref (repeatable)"
+        )]
+        branches: Vec<String>,
+    },
     #[clap(about = "Check repository health and show potential issues")]
-    Health,
-    #[clap(about = "Show commits since a reference (e.g., cb676ec, origin/main)")]
+    Health {
+        #[clap(long = "json", help = "Output one JSON record per check", action = clap::ArgAction::SetTrue)]
+        json: bool,
+        #[clap(
+            long = "fast",
+            conflicts_with = "deep",
+            help = "Skip the scans of history, every file and every branch"
+        )]
+        fast: bool,
+        #[clap(
+            long = "deep",
+            help = "Check every tracked file and scan all history for secrets"
+        )]
+        deep: bool,
+        #[clap(
+            long = "no-cache",
+            help = "Run every check again instead of reusing results for HEAD"
+        )]
+        no_cache: bool,
+    },
+    #[clap(about = "Show commits since a reference (e.g., cb676ec, origin/main, last-tag) or time")]
     Since {
-        #[clap(help = "Reference point")]
-        reference: String,
+        #[clap(help = "Reference point (default: upstream, then latest tag, then last fetch)")]
+        reference: Option<String>,
+        #[clap(long = "copy", help = "Also copy the output to the clipboard")]
+        copy: bool,
     },
     #[clap(about = "Undo the last commit (without losing changes)")]
     Undo,
+    #[clap(about = "Commit everything as a WIP commit that lint and hooks skip")]
+    Wip {
+        #[clap(help = "Summary after 'WIP: ' (default: 'work in progress on <branch>')")]
+        message: Option<String>,
+    },
+    #[clap(about = "Uncommit the latest WIP commit back into the working tree")]
+    Unwip,
+    #[clap(
+        name = "undo-file",
+        about = "List and restore checkpoints of uncommitted changes to a file"
+    )]
+    UndoFile {
+        #[clap(help = "File to show checkpoints for (default: list every file with checkpoints)")]
+        path: Option<String>,
+        #[clap(
+            long = "restore",
+            value_name = "ID",
+            help = "Write this checkpoint back to the file"
+        )]
+        restore: Option<u64>,
+    },
+    #[clap(
+        about = "Archive branches as tags instead of deleting them",
+        args_conflicts_with_subcommands = true
+    )]
+    Archive {
+        #[clap(subcommand)]
+        action: Option<ArchiveAction>,
+        #[clap(help = "Branches to archive as archive/<branch>-<date> tags")]
+        branches: Vec<String>,
+        #[clap(long = "remote", help = "Also push the archive tag and delete the branch on its remote", action = clap::ArgAction::SetTrue)]
+        remote: bool,
+        #[clap(long = "dry-run", help = "Show which tags would be created without changing anything", action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+    },
     #[clap(about = "Delete all fully merged local branches (except protected ones)")]
     CleanBranches {
         #[clap(long = "dry-run", help = "Prints the branches it would delete instead of actually deleting them", action = clap::ArgAction::SetTrue)]
         dry_run: bool,
+        #[clap(long = "gone", help = "Delete branches whose upstream was deleted on the remote instead of merged branches", action = clap::ArgAction::SetTrue)]
+        gone: bool,
+        #[clap(short = 'y', long = "yes", help = "Delete without asking, even many branches in a non-interactive run", action = clap::ArgAction::SetTrue)]
+        yes: bool,
+    },
+    #[clap(about = "Show what’s different between this branch and another (default: main)")]
+    What {
+        #[clap(long = "target", help = "Branch to compare to")]
+        target: Option<String>,
+        #[clap(
+            long = "package",
+            value_name = "NAME",
+            help = "Only count commits touching this workspace package (see `git x packages`)"
+        )]
+        package: Option<String>,
+        #[clap(long = "copy", help = "Also copy the output to the clipboard")]
+        copy: bool,
+    },
+    #[clap(about = "List the packages of a monorepo (Cargo, npm/pnpm/yarn workspaces, Go modules)")]
+    Packages,
+    #[clap(
+        name = "branch-diff",
+        about = "List commits only on this branch and only on another, side by side"
+    )]
+    BranchDiff {
+        #[clap(help = "Branch or ref to compare with")]
+        target: String,
+    },
+    #[clap(
+        name = "diff-stat",
+        about = "Show a directory-level roll-up of changes between two refs"
+    )]
+    DiffStat {
+        #[clap(help = "Base ref")]
+        from: String,
+        #[clap(help = "Target ref (default: HEAD)")]
+        to: Option<String>,
+        #[clap(
+            long = "depth",
+            default_value = "1",
+            help = "Number of directory levels to group by"
+        )]
+        depth: usize,
+        #[clap(long = "json", help = "Output as JSON", action = clap::ArgAction::SetTrue)]
+        json: bool,
+    },
+    #[clap(
+        name = "audit-merges",
+        about = "Audit force-pushes and history rewrites of protected branches"
+    )]
+    AuditMerges {
+        #[clap(long = "since", help = "Start of the window (default: 90 days ago)")]
+        since: Option<String>,
+        #[clap(
+            long = "branch",
+            value_name = "PATTERN",
+            help = "Branch to audit, repeatable (default: protected branches, else the default branch)"
+        )]
+        branches: Vec<String>,
+        #[clap(long = "json", help = "Output as JSON", conflicts_with = "csv", action = clap::ArgAction::SetTrue)]
+        json: bool,
+        #[clap(long = "csv", help = "Output as CSV", action = clap::ArgAction::SetTrue)]
+        csv: bool,
+    },
+    #[clap(about = "Show who changes each directory most over a period")]
+    Ownership {
+        #[clap(long = "since", help = "Start of the period (default: 6 months ago)")]
+        since: Option<String>,
+        #[clap(
+            long = "depth",
+            default_value = "1",
+            help = "Number of directory levels to group by"
+        )]
+        depth: usize,
+        #[clap(
+            long = "top",
+            default_value = "3",
+            help = "Number of contributors to name per directory"
+        )]
+        top: usize,
+        #[clap(long = "json", help = "Output as JSON", conflicts_with = "csv", action = clap::ArgAction::SetTrue)]
+        json: bool,
+        #[clap(long = "csv", help = "Output as CSV", action = clap::ArgAction::SetTrue)]
+        csv: bool,
+    },
+    #[clap(
+        name = "export-report",
+        about = "Combine summary, health, contributors, technical debt and large files into one report"
+    )]
+    ExportReport {
+        #[clap(
+            long = "format",
+            value_parser = ["markdown", "md", "html"],
+            help = "Report format (default: from the --output extension, else markdown)"
+        )]
+        format: Option<String>,
+        #[clap(
+            short = 'o',
+            long = "output",
+            help = "Write the report to this file instead of stdout"
+        )]
+        output: Option<String>,
+    },
+    #[clap(
+        name = "log-json",
+        about = "Export commits as newline-delimited JSON, resumable with a cursor"
+    )]
+    LogJson {
+        #[clap(help = "Range or revisions to export (default: HEAD)")]
+        range: Option<String>,
+        #[clap(long = "since", help = "Only commits after this date")]
+        since: Option<String>,
+        #[clap(long = "until", help = "Only commits before this date")]
+        until: Option<String>,
+        #[clap(
+            short = 'n',
+            long = "limit",
+            help = "Stop after this many commits and print the cursor to resume from"
+        )]
+        limit: Option<usize>,
+        #[clap(
+            long = "cursor",
+            conflicts_with_all = ["range", "since", "until"],
+            help = "Resume an export from the cursor of its last record"
+        )]
+        cursor: Option<String>,
+        #[clap(long = "no-files", help = "Leave out per-file line counts (faster)")]
+        no_files: bool,
+    },
+    #[clap(
+        about = "Score the current branch's diff against review-size guidelines and suggest splits"
+    )]
+    ReviewSize {
+        #[clap(
+            long = "target",
+            help = "Branch the PR would merge into (default: the default branch)"
+        )]
+        target: Option<String>,
+        #[clap(long = "json", help = "Output the score as JSON, e.g. for a bot")]
+        json: bool,
+    },
+    #[clap(about = "Review a commit range or branch commit-by-commit in the terminal")]
+    Review {
+        #[clap(help = "Range (e.g. main..feature) or branch to review")]
+        target: String,
+        #[clap(
+            long = "base",
+            help = "Base to compare a branch against (default: main)"
+        )]
+        base: Option<String>,
+        #[clap(
+            short = 'o',
+            long = "output",
+            help = "Review summary file (default: git-x-review.md)"
+        )]
+        output: Option<String>,
+    },
+    #[clap(
+        name = "gone-files",
+        about = "Find when files were deleted, by whom, and restore them"
+    )]
+    GoneFiles {
+        #[clap(help = "Fuzzy path to search for (lists all deleted files when omitted)")]
+        query: Option<String>,
+        #[clap(
+            long = "restore",
+            help = "Restore the matching file from the last commit that contained it"
+        )]
+        restore: bool,
+        #[clap(
+            long = "limit",
+            default_value = "20",
+            help = "Maximum number of files to list"
+        )]
+        limit: usize,
+    },
+    #[clap(
+        name = "find-merge",
+        about = "Find the merge that brought a commit into the default branch"
+    )]
+    FindMerge {
+        #[clap(help = "Commit to look for")]
+        commit: String,
+        #[clap(
+            long = "into",
+            value_name = "BRANCH",
+            help = "Branch the commit was merged into (default: the default branch)"
+        )]
+        into: Option<String>,
+        #[clap(
+            long = "pr",
+            help = "Also look up the pull request with the GitHub CLI (gh)"
+        )]
+        pr: bool,
+        #[clap(long = "copy", help = "Also copy the output to the clipboard")]
+        copy: bool,
+    },
+    #[clap(about = "Check commit-graph, fsmonitor and other performance settings")]
+    Perf {
+        #[clap(long = "apply", help = "Apply the recommended config changes")]
+        apply: bool,
+    },
+    #[clap(about = "Show a calendar heatmap of commit activity")]
+    Heatmap {
+        #[clap(long = "since", help = "Start of the period (default: 1 year ago)")]
+        since: Option<String>,
+        #[clap(long = "author", help = "Only count commits by this author")]
+        author: Option<String>,
+        #[clap(long = "branch", help = "Count commits on this branch instead of HEAD")]
+        branch: Option<String>,
+        #[clap(
+            long = "all",
+            conflicts_with = "branch",
+            help = "Count commits on every branch"
+        )]
+        all: bool,
+        #[clap(help = "Only count commits touching this path")]
+        path: Option<String>,
+    },
+    #[clap(
+        name = "files-changed-together",
+        about = "Find files that frequently change in the same commits (change coupling)"
+    )]
+    FilesChangedTogether {
+        #[clap(long = "since", help = "Start of the period (default: all history)")]
+        since: Option<String>,
+        #[clap(long = "limit", help = "Pairs to show (default: 15)")]
+        limit: Option<usize>,
+        #[clap(
+            long = "min-shared",
+            help = "Shared commits a pair needs to be reported (default: 3)"
+        )]
+        min_shared: Option<usize>,
+        #[clap(
+            long = "max-files",
+            help = "Skip commits touching more files than this (default: 30)"
+        )]
+        max_files: Option<usize>,
+        #[clap(help = "Only show files coupled with this one")]
+        path: Option<String>,
+    },
+    #[clap(
+        name = "commit-sizes",
+        about = "Histogram of commit sizes, flagging giant, vendored and generated commits"
+    )]
+    CommitSizes {
+        #[clap(help = "Commits to measure, e.g. main..feature (default: HEAD)")]
+        range: Option<String>,
+        #[clap(long = "since", help = "Only measure commits since this date")]
+        since: Option<String>,
+        #[clap(
+            long = "by",
+            default_value = "lines",
+            value_parser = ["lines", "files"],
+            help = "Bucket commits by lines or files changed"
+        )]
+        by: String,
+        #[clap(
+            long = "giant-lines",
+            help = "Flag commits changing more lines than this (default: 1000)"
+        )]
+        giant_lines: Option<usize>,
+        #[clap(
+            long = "giant-files",
+            help = "Flag commits changing more files than this (default: 100)"
+        )]
+        giant_files: Option<usize>,
+        #[clap(long = "limit", help = "Flagged commits to show (default: 20)")]
+        limit: Option<usize>,
+    },
+    #[clap(about = "Show a short, changelog-style summary of recent commits")]
+    Summary {
+        #[clap(
+            long = "since",
+            help = "Start of the period, e.g. \"yesterday\", \"2w\", \"3 months\", \"2025-07-01\" or \"last-tag\""
+        )]
+        since: Option<String>,
+        #[clap(
+            long = "exclude-merges",
+            help = "Leave merge commits out of the summary"
+        )]
+        exclude_merges: bool,
+        #[clap(
+            long = "author",
+            value_name = "PATTERN",
+            help = "Only count commits by matching authors (repeatable, same as git log --author)"
+        )]
+        authors: Vec<String>,
+        #[clap(
+            long = "team",
+            help = "Only count commits by a team defined in git-x.team.<name> (repeatable)"
+        )]
+        teams: Vec<String>,
+        #[clap(long = "by-author", help = "Break each day down per author")]
+        by_author: bool,
+        #[clap(
+            long = "package",
+            value_name = "NAME",
+            help = "Only count commits touching this workspace package (see `git x packages`)"
+        )]
+        package: Option<String>,
+        #[clap(
+            long = "include-generated",
+            help = "Count generated and vendored files (lockfiles, bundles, linguist-generated/-vendored)"
+        )]
+        include_generated: bool,
+        #[clap(
+            long = "json",
+            conflicts_with = "by_author",
+            help = "Output the repository overview, including lines of code per language, as JSON"
+        )]
+        json: bool,
+        #[clap(long = "copy", help = "Also copy the output to the clipboard")]
+        copy: bool,
+    },
+    #[clap(about = "Report commits since the previous working day, per team and author")]
+    Standup {
+        #[clap(
+            long = "since",
+            help = "Start of the window (default: the start of the previous working day)"
+        )]
+        since: Option<String>,
+        #[clap(
+            long = "team",
+            num_args = 0..,
+            value_name = "NAME",
+            help = "Group by teams from git-x.team.<name>; without names, every configured team"
+        )]
+        teams: Option<Vec<String>>,
+        #[clap(long = "exclude-merges", help = "Leave merge commits out")]
+        exclude_merges: bool,
+        #[clap(
+            long = "author",
+            value_name = "PATTERN",
+            help = "Only include matching authors (repeatable, same as git log --author)"
+        )]
+        authors: Vec<String>,
+    },
+    #[clap(about = "Sync current branch with upstream (fetch + rebase)")]
+    Sync {
+        #[clap(long = "merge", help = "Use merge instead of rebase", action = clap::ArgAction::SetTrue)]
+        merge: bool,
+        #[clap(
+            long = "autostash",
+            help = "Stash local changes before syncing and restore them afterwards"
+        )]
+        autostash: bool,
+    },
+    #[clap(about = "Create and switch to a new branch")]
+    New {
+        #[clap(help = "Name of the new branch")]
+        branch_name: String,
+        #[clap(
+            long = "from",
+            help = "Base branch to create from (default: current branch)"
+        )]
+        from: Option<String>,
+        #[clap(
+            long = "fresh",
+            help = "Fetch the base's upstream first and warn if the base is behind it"
+        )]
+        fresh: bool,
+        #[clap(
+            long = "max-behind",
+            value_name = "N",
+            help = "Commits the base may be behind before warning (implies --fresh)"
+        )]
+        max_behind: Option<u32>,
+        #[clap(
+            long = "update",
+            help = "Fast-forward a stale base without asking (implies --fresh)"
+        )]
+        update: bool,
+
+        #[clap(
+            long = "no-hooks",
+            help = "Don't run the switch hooks from .git-x.toml"
+        )]
+        no_hooks: bool,
+    },
+    #[clap(about = "Find largest files in repository history")]
+    LargeFiles {
+        #[clap(long = "limit", default_value = "10", help = "Number of files to show")]
+        limit: usize,
+        #[clap(
+            long = "threshold",
+            help = "Minimum file size in MB (default: show all)"
+        )]
+        threshold: Option<f64>,
+        #[clap(
+            long = "include-generated",
+            help = "List generated and vendored files (lockfiles, bundles, linguist-generated/-vendored)"
+        )]
+        include_generated: bool,
+    },
+    #[clap(about = "Create fixup commits for easier interactive rebasing")]
+    Fixup {
+        #[clap(
+            help = "Commit hash to create fixup for",
+            required_unless_present = "auto"
+        )]
+        commit_hash: Option<String>,
+        #[clap(long = "rebase", help = "Automatically rebase with autosquash after creating fixup", action = clap::ArgAction::SetTrue)]
+        rebase: bool,
+        #[clap(
+            long = "auto",
+            conflicts_with = "commit_hash",
+            help = "Split staged hunks into fixups for the commits that last touched them (via blame)"
+        )]
+        auto: bool,
+    },
+    #[clap(about = "Reword an unpushed commit's message without a manual interactive rebase")]
+    Reword {
+        #[clap(help = "Commit to reword")]
+        commit: String,
+        #[clap(short = 'm', long = "message", help = "New commit message")]
+        message: String,
+    },
+    #[clap(about = "Squash the last N commits (or a range) into one without an interactive rebase")]
+    Squash {
+        #[clap(
+            help = "Number of commits to squash, or a range ending at HEAD (e.g. abc123..HEAD)"
+        )]
+        target: String,
+        #[clap(
+            short = 'm',
+            long = "message",
+            help = "Message for the squashed commit (default: combine the original messages)"
+        )]
+        message: Option<String>,
+    },
+    #[clap(about = "Create a hotfix branch from the latest release and prepare the merge back")]
+    Hotfix {
+        #[clap(help = "Hotfix name (branch will be hotfix/<name>)")]
+        name: String,
+        #[clap(help = "Commits to cherry-pick onto the hotfix branch")]
+        commits: Vec<String>,
+        #[clap(
+            long = "from",
+            help = "Base ref (default: latest release tag, then production branch)"
+        )]
+        from: Option<String>,
+    },
+    #[clap(
+        name = "fix-locks",
+        about = "Detect and clean up stale lock files and interrupted operations"
+    )]
+    FixLocks {
+        #[clap(long = "dry-run", help = "Show what would be cleaned up without changing anything", action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+        #[clap(long = "abort", help = "Also abort an in-progress merge, rebase, cherry-pick or revert", action = clap::ArgAction::SetTrue)]
+        abort: bool,
+    },
+    #[clap(about = "Generate and apply email-formatted patch series")]
+    Patch {
+        #[clap(subcommand)]
+        action: PatchAction,
+    },
+    #[clap(about = "Import the history of a directory of another local repository")]
+    ImportCommits {
+        #[clap(help = "Path to the repository to import from")]
+        source: std::path::PathBuf,
+        #[clap(
+            long = "path",
+            help = "Directory of the source repository to import (default: all of it)"
+        )]
+        path: Option<String>,
+        #[clap(
+            long = "into",
+            help = "Directory to import into (default: --path, else the source repository's name)"
+        )]
+        into: Option<String>,
+        #[clap(
+            long = "from",
+            default_value = "HEAD",
+            help = "Branch or tag of the source repository to import"
+        )]
+        from: String,
+        #[clap(long = "dry-run", help = "List the commits that would be created", action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+    },
+    #[clap(about = "Revert a merge commit, recording it so the branch can be merged again")]
+    RevertMerge {
+        #[clap(help = "Merge commit to revert")]
+        commit: String,
+        #[clap(
+            long,
+            short = 'm',
+            value_name = "N",
+            help = "Parent to keep, counting from 1 (default: the one on this branch's first-parent history)"
+        )]
+        mainline: Option<usize>,
+    },
+    #[clap(about = "Bring back a branch whose merge was reverted with revert-merge")]
+    ReMerge {
+        #[clap(help = "Branch to bring back (lists the reverted merges when omitted)")]
+        branch: Option<String>,
+    },
+    #[clap(about = "Rebase helpers")]
+    Rebase {
+        #[clap(subcommand)]
+        action: RebaseAction,
+    },
+    #[clap(about = "Advanced stash management with branch integration")]
+    StashBranch {
+        #[clap(subcommand)]
+        action: StashBranchAction,
+    },
+    #[clap(about = "Inspect Git LFS usage")]
+    Lfs {
+        #[clap(subcommand)]
+        action: LfsAction,
+    },
+    #[clap(
+        name = "line-endings",
+        about = "Detect mixed or committed CRLF line endings and missing .gitattributes eol settings"
+    )]
+    LineEndings {
+        #[clap(long = "fix", help = "Add recommended eol attributes and renormalize tracked files", action = clap::ArgAction::SetTrue)]
+        fix: bool,
+    },
+    #[clap(about = "Advisory locks on files that can't be merged, such as binary assets")]
+    Lock {
+        #[clap(subcommand)]
+        action: LockAction,
+    },
+    #[clap(about = "Show the effective git identity, signing key and credential helper")]
+    Whoami,
+    #[clap(
+        name = "show-config",
+        about = "Show the effective git-x and git configuration and where each value comes from"
+    )]
+    ShowConfig {
+        #[clap(long = "json", help = "Output as JSON", action = clap::ArgAction::SetTrue)]
+        json: bool,
+    },
+    #[clap(
+        name = "config-check",
+        about = "Check git config against a team policy file and apply it"
+    )]
+    ConfigCheck {
+        #[clap(
+            long = "policy",
+            value_name = "FILE",
+            help = "Policy in git config syntax (default: .git-x-policy in the repository root)"
+        )]
+        policy: Option<String>,
+        #[clap(long = "apply", help = "Write the required values where they drift")]
+        apply: bool,
+        #[clap(
+            long = "local",
+            requires = "apply",
+            help = "Apply unset settings to this repository instead of the global config"
+        )]
+        local: bool,
+    },
+    #[clap(about = "Install a commit-msg hook that enforces Conventional Commits")]
+    InitHooks {
+        #[clap(long, help = "Replace an existing commit-msg hook, keeping a backup")]
+        force: bool,
+        #[clap(
+            long,
+            value_name = "FILE",
+            hide = true,
+            help = "Check a commit message file (run by the installed hook)"
+        )]
+        check: Option<std::path::PathBuf>,
+    },
+    #[clap(about = "Start commit messages from a template chosen by the staged paths")]
+    CommitTemplate {
+        #[clap(
+            long,
+            help = "Install a prepare-commit-msg hook that applies the template"
+        )]
+        install: bool,
+        #[clap(
+            long,
+            requires = "install",
+            help = "Replace an existing prepare-commit-msg hook, keeping a backup"
+        )]
+        force: bool,
+        #[clap(
+            long,
+            value_name = "FILE",
+            hide = true,
+            help = "Fill a commit message file (run by the installed hook)"
+        )]
+        fill: Option<std::path::PathBuf>,
+        #[clap(
+            long,
+            hide = true,
+            default_value = "",
+            help = "Where the message came from, as git passes it to the hook"
+        )]
+        source: String,
+    },
+    #[clap(about = "List commits by the tickets their messages reference, or open a ticket")]
+    Tickets {
+        #[clap(
+            help = "Commits to scan (default: the current branch's commits not on the default branch)"
+        )]
+        range: Option<String>,
+        #[clap(
+            long,
+            value_name = "ID",
+            conflicts_with_all = ["range", "install_hook"],
+            help = "Open the ticket in the browser, using git-x.tickets.url"
+        )]
+        open: Option<String>,
+        #[clap(
+            long,
+            conflicts_with = "range",
+            help = "Install a commit-msg hook that warns when a message references no ticket"
+        )]
+        install_hook: bool,
+        #[clap(
+            long,
+            requires = "install_hook",
+            help = "Replace an existing commit-msg hook, keeping a backup"
+        )]
+        force: bool,
+        #[clap(
+            long,
+            value_name = "FILE",
+            hide = true,
+            help = "Check a commit message file (run by the installed hook)"
+        )]
+        check: Option<std::path::PathBuf>,
+    },
+    #[clap(about = "Run repository checks locally, as CI or a git hook would")]
+    Hooks {
+        #[clap(subcommand)]
+        action: HooksAction,
+    },
+    #[clap(about = "Guard branches locally against direct commits and pushes")]
+    Protect {
+        #[clap(
+            help = "Branches to protect, `*` matching any characters (lists them when omitted)"
+        )]
+        branches: Vec<String>,
+        #[clap(long, help = "Stop protecting the given branches")]
+        remove: bool,
+        #[clap(
+            long,
+            value_parser = ["commit", "push"],
+            hide = true,
+            help = "Check a commit or push (run by the installed hooks)"
+        )]
+        check: Option<String>,
     },
-    #[clap(about = "Show what’s different between this branch and another (default: main)")]
-    What {
-        #[clap(long = "target", help = "Branch to compare to")]
+    #[clap(
+        about = "Check the current branch is ready to merge: rebased, no conflicts, linted, CI green, no TODOs or secrets added"
+    )]
+    Ready {
+        #[clap(
+            long = "target",
+            help = "Branch the work will merge into (default: the default branch)"
+        )]
         target: Option<String>,
+        #[clap(
+            long = "skip",
+            value_name = "CHECK",
+            value_parser = ["rebased", "conflicts", "lint", "ci", "todos", "secrets"],
+            help = "Leave out a check (repeatable)"
+        )]
+        skip: Vec<String>,
+        #[clap(long = "no-fetch", help = "Compare against the target as last fetched")]
+        no_fetch: bool,
     },
-    #[clap(about = "Show a short, changelog-style summary of recent commits")]
-    Summary {
+    #[clap(about = "Scan staged changes for secrets before committing")]
+    ScanStaged {
         #[clap(
-            long = "since",
-            help = "Accepts flexible formats like \"yesterday\", \"3 days ago\", \"2025-07-01\", etc. (same as git log --since)"
+            long = "fail-on",
+            value_parser = ["low", "medium", "high"],
+            help = "Lowest severity that fails the scan (default: git-x.scan.failOn or medium)"
         )]
-        since: Option<String>,
+        fail_on: Option<String>,
+        #[clap(
+            long = "install-hook",
+            help = "Install a pre-commit hook that runs the scan"
+        )]
+        install_hook: bool,
     },
-    #[clap(about = "Sync current branch with upstream (fetch + rebase)")]
-    Sync {
-        #[clap(long = "merge", help = "Use merge instead of rebase", action = clap::ArgAction::SetTrue)]
-        merge: bool,
+    #[clap(
+        name = "rm-secrets",
+        about = "Purge files from every commit in history, with a backup and push steps"
+    )]
+    RmSecrets {
+        #[clap(
+            required = true,
+            help = "Files or patterns to purge, `*` matching any characters; patterns without `/` match file names anywhere"
+        )]
+        patterns: Vec<String>,
+        #[clap(long = "dry-run", help = "Show what would be rewritten without changing anything", action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+        #[clap(long = "internal", help = "Use the built-in path removal even when git-filter-repo is installed", action = clap::ArgAction::SetTrue)]
+        internal: bool,
     },
-    #[clap(about = "Create and switch to a new branch")]
-    New {
-        #[clap(help = "Name of the new branch")]
-        branch_name: String,
+    #[clap(about = "Mirror every ref to a backup remote for disaster recovery")]
+    Mirror {
+        #[clap(subcommand)]
+        action: MirrorAction,
+    },
+    #[clap(about = "Connect a fork to the repository it was forked from")]
+    ForkSetup {
+        #[clap(help = "URL of the original repository (default: the fork's parent on GitHub)")]
+        upstream: Option<String>,
         #[clap(
-            long = "from",
-            help = "Base branch to create from (default: current branch)"
+            long = "branch",
+            help = "Branch sync-fork fast-forwards (default: upstream's default branch)"
         )]
-        from: Option<String>,
+        branch: Option<String>,
+        #[clap(long = "dry-run", help = "Show the changes without making them", action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
     },
-    #[clap(about = "Find largest files in repository history")]
-    LargeFiles {
-        #[clap(long = "limit", default_value = "10", help = "Number of files to show")]
-        limit: usize,
+    #[clap(about = "Manage remotes across one or many repositories")]
+    Remotes {
+        #[clap(subcommand)]
+        action: RemotesAction,
+    },
+    #[clap(about = "Find repositories with uncommitted, untracked, unpushed or stashed work")]
+    Uncommitted {
         #[clap(
-            long = "threshold",
-            help = "Minimum file size in MB (default: show all)"
+            help = "Directories to search (default: git-x.repos.root, else the current directory)"
         )]
-        threshold: Option<f64>,
+        roots: Vec<std::path::PathBuf>,
+        #[clap(
+            long = "depth",
+            help = "Directory levels to search below each root (default: 3)"
+        )]
+        depth: Option<usize>,
+        #[clap(long = "all", help = "List clean repositories too", action = clap::ArgAction::SetTrue)]
+        all: bool,
+        #[clap(long = "json", help = "Output as JSON", conflicts_with = "csv", action = clap::ArgAction::SetTrue)]
+        json: bool,
+        #[clap(long = "csv", help = "Output as CSV", action = clap::ArgAction::SetTrue)]
+        csv: bool,
     },
-    #[clap(about = "Create fixup commits for easier interactive rebasing")]
-    Fixup {
-        #[clap(help = "Commit hash to create fixup for")]
-        commit_hash: String,
-        #[clap(long = "rebase", help = "Automatically rebase with autosquash after creating fixup", action = clap::ArgAction::SetTrue)]
-        rebase: bool,
+    #[clap(about = "Manage the repositories git-x knows about on this machine")]
+    Repos {
+        #[clap(subcommand)]
+        action: ReposAction,
     },
-    #[clap(about = "Advanced stash management with branch integration")]
-    StashBranch {
+    #[clap(
+        about = "Print the path of a known repository by fuzzy name, for cd \"$(git x goto name)\""
+    )]
+    Goto {
+        #[clap(help = "Repository name, part of it, or the end of its path")]
+        name: String,
+    },
+    #[clap(about = "Show and edit which parts of the tree are checked out (sparse checkout)")]
+    Sparse {
         #[clap(subcommand)]
-        action: StashBranchAction,
+        action: SparseAction,
+    },
+    #[clap(about = "Diagnose common problems")]
+    Doctor {
+        #[clap(subcommand)]
+        action: DoctorAction,
+    },
+    #[clap(about = "Show CI status for the current branch")]
+    Ci {
+        #[clap(subcommand)]
+        action: CiAction,
     },
     #[clap(about = "Manage upstream branch relationships")]
     Upstream {
@@ -101,11 +1027,127 @@ pub enum Commands {
         action: UpstreamAction,
     },
     #[clap(about = "Interactive picker for recent branches")]
-    SwitchRecent,
+    SwitchRecent {
+        #[clap(
+            long = "no-hooks",
+            help = "Don't run the switch hooks from .git-x.toml"
+        )]
+        no_hooks: bool,
+    },
+    #[clap(
+        about = "Switch branches, stashing WIP and restoring what was stashed on the destination"
+    )]
+    Jump {
+        #[clap(help = "Branch to switch to, or `-` for the previous one")]
+        branch: String,
+        #[clap(short = 'u', long = "include-untracked", help = "Stash untracked files too", action = clap::ArgAction::SetTrue)]
+        include_untracked: bool,
+        #[clap(long = "no-restore", help = "Leave WIP stashed for the destination in the stash", action = clap::ArgAction::SetTrue)]
+        no_restore: bool,
+
+        #[clap(
+            long = "no-hooks",
+            help = "Don't run the switch hooks from .git-x.toml"
+        )]
+        no_hooks: bool,
+    },
+    #[clap(
+        visible_alias = "notes",
+        about = "Attach reminders to branches, shown when git-x switches to them"
+    )]
+    BranchNote {
+        #[clap(subcommand)]
+        action: BranchNoteAction,
+    },
     #[clap(about = "Show contributor statistics for the repository")]
-    Contributors,
+    Contributors {
+        #[clap(
+            long = "since",
+            help = "Only count commits after this time (e.g. '1y', '2025-07-01', 'last-release')"
+        )]
+        since: Option<String>,
+        #[clap(long = "until", help = "Only count commits before this time")]
+        until: Option<String>,
+        #[clap(
+            long = "limit",
+            help = "Show at most this many contributors (default: 20, or everyone when exporting)"
+        )]
+        limit: Option<usize>,
+        #[clap(
+            long = "offset",
+            default_value_t = 0,
+            help = "Skip this many top contributors, to page through the list"
+        )]
+        offset: usize,
+        #[clap(
+            long = "fast",
+            help = "Count with git shortlog, without first and last commit dates (for huge repositories)"
+        )]
+        fast: bool,
+        #[clap(help = "Only count commits touching these paths")]
+        paths: Vec<String>,
+        #[clap(
+            long = "package",
+            value_name = "NAME",
+            help = "Only count commits touching this workspace package (see `git x packages`)"
+        )]
+        package: Option<String>,
+        #[clap(
+            long = "csv",
+            help = "Export statistics as CSV",
+            conflicts_with = "tsv",
+            action = clap::ArgAction::SetTrue
+        )]
+        csv: bool,
+        #[clap(
+            long = "tsv",
+            help = "Export statistics as TSV",
+            action = clap::ArgAction::SetTrue
+        )]
+        tsv: bool,
+    },
+    #[cfg(feature = "changed-apis")]
+    #[clap(
+        name = "changed-apis",
+        about = "List public Rust items added, removed or changed between two refs"
+    )]
+    ChangedApis {
+        #[clap(help = "Ref to compare from (default: the latest tag)")]
+        from: Option<String>,
+        #[clap(help = "Ref to compare to (default: HEAD)")]
+        to: Option<String>,
+    },
+    #[cfg(feature = "serve")]
+    #[clap(about = "Serve a local, read-only dashboard of info, summary, health and contributors")]
+    Serve {
+        #[clap(
+            long = "port",
+            default_value_t = 8080,
+            help = "Port to listen on (localhost only)"
+        )]
+        port: u16,
+        #[clap(
+            long = "refresh",
+            default_value_t = 30,
+            help = "Seconds between dashboard refreshes"
+        )]
+        refresh: u64,
+    },
     #[clap(about = "Analyze code complexity and technical debt metrics")]
-    TechnicalDebt,
+    TechnicalDebt {
+        #[clap(
+            long = "lockfile-months",
+            help = "Flag lockfiles not updated in this many months (default: 6)"
+        )]
+        lockfile_months: Option<u32>,
+        #[clap(long = "outdated", help = "Count outdated dependencies with cargo-outdated, npm, go or pip when installed", action = clap::ArgAction::SetTrue)]
+        outdated: bool,
+        #[clap(
+            long = "include-generated",
+            help = "Analyze generated and vendored files (lockfiles, bundles, linguist-generated/-vendored)"
+        )]
+        include_generated: bool,
+    },
     #[clap(about = "Simplified bisect workflow")]
     Bisect {
         #[clap(subcommand)]
@@ -119,6 +1161,28 @@ pub enum Commands {
         #[clap(help = "Shell to install completion for")]
         shell: Shell,
     },
+    #[clap(name = "__complete", hide = true)]
+    Complete {
+        #[clap(
+            allow_hyphen_values = true,
+            help = "Words after `git-x`, ending with the one being completed"
+        )]
+        words: Vec<String>,
+    },
+    #[clap(about = "Install, list and remove git aliases for git-x commands")]
+    Alias {
+        #[clap(subcommand)]
+        action: AliasAction,
+    },
+    #[clap(about = "Configure git and git-x for a new machine")]
+    Setup {
+        #[clap(
+            long = "yes",
+            short = 'y',
+            help = "Accept suggested values without prompting"
+        )]
+        yes: bool,
+    },
 }
 
 #[derive(clap::Subcommand)]
@@ -139,6 +1203,15 @@ pub enum StashBranchAction {
         older_than: Option<String>,
         #[clap(long = "dry-run", help = "Show what would be cleaned without doing it", action = clap::ArgAction::SetTrue)]
         dry_run: bool,
+        #[clap(long = "mine", help = "Only clean stashes git-x created (checkpoints, autostashes)", action = clap::ArgAction::SetTrue)]
+        mine: bool,
+        #[clap(short = 'y', long = "yes", help = "Delete without asking, even many stashes in a non-interactive run", action = clap::ArgAction::SetTrue)]
+        yes: bool,
+    },
+    #[clap(about = "List stashes with the operation behind the ones git-x created")]
+    List {
+        #[clap(long = "mine", help = "Only list stashes git-x created", action = clap::ArgAction::SetTrue)]
+        mine: bool,
     },
     #[clap(about = "Apply stashes from a specific branch")]
     ApplyByBranch {
@@ -146,9 +1219,21 @@ pub enum StashBranchAction {
         branch_name: String,
         #[clap(long = "list", help = "List stashes instead of applying", action = clap::ArgAction::SetTrue)]
         list_only: bool,
+        #[clap(long = "pop", help = "Drop each stash once it applied cleanly", conflicts_with = "list_only", action = clap::ArgAction::SetTrue)]
+        pop: bool,
+        #[clap(long = "newest-only", help = "Only the branch's most recent stash", action = clap::ArgAction::SetTrue)]
+        newest_only: bool,
     },
     #[clap(about = "Interactive stash management with fuzzy search")]
-    Interactive,
+    Interactive {
+        #[clap(
+            long = "sort",
+            default_value = "age",
+            value_parser = ["age", "branch"],
+            help = "Order stashes by age (newest first) or by originating branch"
+        )]
+        sort: String,
+    },
     #[clap(about = "Export stashes to patch files")]
     Export {
         #[clap(help = "Output directory for patch files")]
@@ -161,6 +1246,94 @@ pub enum StashBranchAction {
     },
 }
 
+#[derive(clap::Subcommand)]
+pub enum ArchiveAction {
+    #[clap(about = "List archived branches")]
+    List,
+    #[clap(about = "Restore an archived branch")]
+    Restore {
+        #[clap(help = "Archive tag, or a branch name to restore its latest archive")]
+        name: String,
+        #[clap(long = "as", help = "Restore under a different branch name")]
+        as_branch: Option<String>,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum PatchAction {
+    #[clap(about = "Write a patch series with cover letter from a range")]
+    Send {
+        #[clap(help = "Range to export (e.g. main..feature, a ref, or a number of commits)")]
+        range: String,
+        #[clap(
+            short = 'o',
+            long = "output-dir",
+            default_value = "patches",
+            help = "Directory to write patches to"
+        )]
+        output_dir: String,
+        #[clap(
+            long = "onto",
+            help = "Branch to check the series applies onto (default: the range's base)"
+        )]
+        onto: Option<String>,
+    },
+    #[clap(about = "Apply a patch series from a directory or mbox")]
+    Apply {
+        #[clap(help = "Directory of .patch files or an mbox file")]
+        source: String,
+        #[clap(long = "no-3way", help = "Disable the 3-way merge fallback", action = clap::ArgAction::SetTrue)]
+        no_3way: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum BranchNoteAction {
+    #[clap(about = "Attach a note to a branch, replacing its current one")]
+    Set {
+        #[clap(help = "The reminder, e.g. \"waiting on review feedback for the API change\"")]
+        message: String,
+        #[clap(long = "branch", help = "Branch to note (default: current branch)")]
+        branch: Option<String>,
+        #[clap(
+            long = "expires",
+            value_name = "WHEN",
+            help = "Stop showing the note after a duration (12h, 3d, 2w) or on a date (2026-11-01)"
+        )]
+        expires: Option<String>,
+    },
+    #[clap(about = "Show a branch's note")]
+    Show {
+        #[clap(help = "Branch (default: current branch)")]
+        branch: Option<String>,
+    },
+    #[clap(about = "Remove a branch's note")]
+    Clear {
+        #[clap(help = "Branch (default: current branch)")]
+        branch: Option<String>,
+        #[clap(
+            long = "expired",
+            conflicts_with = "branch",
+            help = "Remove every expired note instead"
+        )]
+        expired: bool,
+    },
+    #[clap(about = "List the notes on all branches")]
+    List {
+        #[clap(long = "all", help = "Include expired notes")]
+        all: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum RebaseAction {
+    #[clap(
+        name = "continue-smart",
+        about = "Skip stopped commits already upstream (by patch id, squashed or not) and continue once conflicts are resolved"
+    )]
+    ContinueSmart,
+}
+
 #[derive(clap::Subcommand)]
 pub enum UpstreamAction {
     #[clap(about = "Set upstream for current branch")]
@@ -175,20 +1348,305 @@ pub enum UpstreamAction {
         #[clap(long = "dry-run", help = "Show what would be synced without doing it", action = clap::ArgAction::SetTrue
         )]
         dry_run: bool,
-        #[clap(long = "merge", help = "Use merge instead of rebase", action = clap::ArgAction::SetTrue
+        #[clap(long = "merge", help = "Accepted for compatibility; sync-all only fast-forwards", action = clap::ArgAction::SetTrue
         )]
         merge: bool,
     },
+    #[clap(about = "Track same-named remote branches for every branch without an upstream")]
+    Adopt {
+        #[clap(long = "remote", help = "Only match branches on this remote")]
+        remote: Option<String>,
+        #[clap(
+            long = "exclude",
+            value_name = "BRANCH",
+            help = "Leave this branch alone (repeatable)"
+        )]
+        exclude: Vec<String>,
+        #[clap(long = "dry-run", help = "Show the matches without setting anything", action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum LfsAction {
+    #[clap(about = "Show LFS patterns, large binaries outside LFS and pending transfers")]
+    Status,
+}
+
+#[derive(clap::Subcommand)]
+pub enum LockAction {
+    #[clap(about = "Lock files so others know not to change them")]
+    Take {
+        #[clap(required = true, help = "Files to lock")]
+        paths: Vec<String>,
+    },
+    #[clap(about = "Release locks (default: all of yours)")]
+    Release {
+        #[clap(help = "Files to unlock")]
+        paths: Vec<String>,
+        #[clap(long = "force", help = "Break locks held by someone else", action = clap::ArgAction::SetTrue)]
+        force: bool,
+    },
+    #[clap(about = "List locked files and who holds them")]
+    Status,
+    #[clap(
+        hide = true,
+        about = "Warn about changes to files others have locked (run by the installed hooks)"
+    )]
+    Check {
+        #[clap(value_parser = ["commit", "push"])]
+        check: String,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum HooksAction {
+    #[clap(
+        name = "run-ci",
+        about = "Run the checks from .git-x/ci.toml in parallel, with a pass/fail summary"
+    )]
+    RunCi {
+        #[clap(help = "Checks to run (default: every check in the manifest)")]
+        checks: Vec<String>,
+        #[clap(
+            long = "manifest",
+            value_name = "FILE",
+            help = "Manifest to read (default: git-x.hooks.ciManifest or .git-x/ci.toml)"
+        )]
+        manifest: Option<std::path::PathBuf>,
+        #[clap(
+            long = "install-hook",
+            conflicts_with_all = ["checks", "manifest"],
+            help = "Install a pre-push hook that runs the checks"
+        )]
+        install_hook: bool,
+    },
+    #[clap(about = "Run the switch hooks from .git-x.toml for a branch, or list them")]
+    Switch {
+        #[clap(help = "Branch whose hooks to run (default: current branch)")]
+        branch: Option<String>,
+        #[clap(long = "dry-run", help = "List the commands instead of running them")]
+        dry_run: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum MirrorAction {
+    #[clap(about = "Push all branches, tags, notes and other refs, then verify")]
+    Push {
+        #[clap(help = "Backup remote (default: git-x.mirror.remote or 'backup')")]
+        remote: Option<String>,
+    },
+    #[clap(about = "Check the backup remote has exactly the local refs")]
+    Verify {
+        #[clap(help = "Backup remote (default: git-x.mirror.remote or 'backup')")]
+        remote: Option<String>,
+    },
+    #[clap(about = "Register this repository for scheduled mirroring")]
+    Schedule {
+        #[clap(help = "Backup remote (default: git-x.mirror.remote or 'backup')")]
+        remote: Option<String>,
+        #[clap(
+            long = "unregister",
+            help = "Stop scheduled mirroring of this repository"
+        )]
+        unregister: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum PrAction {
+    #[clap(
+        about = "Fetch a pull request into pr/<number>, set up pushing to the contributor's fork, and switch to it"
+    )]
+    Checkout {
+        #[clap(help = "Pull request number")]
+        number: u64,
+        #[clap(
+            long = "remote",
+            help = "Remote the pull request was opened against (default: upstream, then origin)"
+        )]
+        remote: Option<String>,
+    },
+    #[clap(about = "Delete pr/<number> branches whose pull request is closed or merged")]
+    Clean {
+        #[clap(long = "all", help = "Delete every pull request branch, open or not")]
+        all: bool,
+        #[clap(long = "dry-run", help = "Show what would be deleted without deleting")]
+        dry_run: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum SparseAction {
+    #[clap(
+        about = "Show the sparse-checkout patterns, how much is checked out and any partial clone"
+    )]
+    Status,
+    #[clap(about = "Check out more directories, enabling sparse checkout (cone mode) if needed")]
+    Add {
+        #[clap(
+            required = true,
+            help = "Directories to check out (patterns with --no-cone)"
+        )]
+        paths: Vec<String>,
+        #[clap(
+            long = "no-cone",
+            help = "Use gitignore-style patterns instead of directories when enabling"
+        )]
+        no_cone: bool,
+    },
+    #[clap(about = "Check out the whole tree again")]
+    Disable,
+}
+
+#[derive(clap::Subcommand)]
+pub enum ReposAction {
+    #[clap(about = "Register repositories (default: the current one)")]
+    Add {
+        #[clap(help = "Paths inside the repositories to register")]
+        paths: Vec<std::path::PathBuf>,
+    },
+    #[clap(about = "Register every repository found under some directories")]
+    Scan {
+        #[clap(
+            help = "Directories to search (default: git-x.repos.root, else the current directory)"
+        )]
+        roots: Vec<std::path::PathBuf>,
+        #[clap(
+            long = "depth",
+            help = "Directory levels to search below each root (default: 3)"
+        )]
+        depth: Option<usize>,
+    },
+    #[clap(about = "Forget a repository")]
+    Remove {
+        #[clap(help = "Repository name or the end of its path")]
+        name: String,
+    },
+    #[clap(about = "List known repositories")]
+    List {
+        #[clap(long = "json", help = "Output as JSON", action = clap::ArgAction::SetTrue)]
+        json: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum DoctorAction {
+    #[clap(
+        about = "Check each remote can be reached: DNS, SSH authentication, ls-remote timing, credential helpers and proxies"
+    )]
+    Connection {
+        #[clap(help = "Only check this remote (default: all remotes)")]
+        remote: Option<String>,
+        #[clap(
+            long = "timeout",
+            default_value_t = 10,
+            help = "Seconds before a check gives up"
+        )]
+        timeout: u64,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum RemotesAction {
+    #[clap(about = "Rewrite remote URLs by prefix, e.g. after moving to another host")]
+    Migrate {
+        #[clap(long = "from", help = "URL prefix to replace, e.g. git@gitlab.com:org")]
+        from: String,
+        #[clap(long = "to", help = "Replacement prefix, e.g. git@github.com:org")]
+        to: String,
+        #[clap(long = "dry-run", help = "Show the changes without applying them")]
+        dry_run: bool,
+        #[clap(
+            long = "no-verify",
+            help = "Skip checking that rewritten remotes are reachable"
+        )]
+        no_verify: bool,
+        #[clap(help = "Repositories to update (default: the current one)")]
+        repos: Vec<String>,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum AliasAction {
+    #[clap(about = "Add recommended aliases (e.g. git xr → git x switch-recent) or your own")]
+    Install {
+        #[clap(help = "Recommended alias names or name=command pairs (default: all recommended)")]
+        aliases: Vec<String>,
+        #[clap(long = "local", conflicts_with = "system", help = "Write to this repository's config instead of the global one", action = clap::ArgAction::SetTrue)]
+        local: bool,
+        #[clap(long = "system", help = "Write to the system-wide config instead of the global one", action = clap::ArgAction::SetTrue)]
+        system: bool,
+        #[clap(long = "force", help = "Replace aliases that are already set to something else", action = clap::ArgAction::SetTrue)]
+        force: bool,
+    },
+    #[clap(about = "List aliases that run git-x commands")]
+    List {
+        #[clap(long = "all", help = "Include aliases that don't run git-x", action = clap::ArgAction::SetTrue)]
+        all: bool,
+    },
+    #[clap(about = "Remove aliases (default: every git-x alias in the scope)")]
+    Remove {
+        #[clap(help = "Alias names to remove")]
+        names: Vec<String>,
+        #[clap(long = "local", conflicts_with = "system", help = "Remove from this repository's config instead of the global one", action = clap::ArgAction::SetTrue)]
+        local: bool,
+        #[clap(long = "system", help = "Remove from the system-wide config instead of the global one", action = clap::ArgAction::SetTrue)]
+        system: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum CiAction {
+    #[clap(about = "Show workflow runs and job results for HEAD")]
+    Status {
+        #[clap(long = "watch", help = "Poll until every run has finished")]
+        watch: bool,
+        #[clap(
+            long = "interval",
+            default_value = "10",
+            help = "Seconds between polls in --watch mode"
+        )]
+        interval: u64,
+    },
 }
 
 #[derive(clap::Subcommand)]
 pub enum BisectAction {
     #[clap(about = "Start bisect session")]
     Start {
-        #[clap(help = "Good commit reference")]
-        good: String,
-        #[clap(help = "Bad commit reference")]
-        bad: String,
+        #[clap(
+            help = "Good commit reference (with --auto-good: the bad commit)",
+            required_unless_present = "auto_good"
+        )]
+        good: Option<String>,
+        #[clap(
+            help = "Bad commit reference",
+            required_unless_present = "auto_good",
+            conflicts_with = "auto_good"
+        )]
+        bad: Option<String>,
+        #[clap(
+            long = "auto-good",
+            help = "Find the good commit: where the branch forked, the latest tag, or the last commit --test passes on",
+            action = clap::ArgAction::SetTrue
+        )]
+        auto_good: bool,
+        #[clap(
+            long = "test",
+            value_name = "command",
+            // Without --auto-good both commits are required, so this rules --test out there
+            conflicts_with = "bad",
+            help = "Shell command that passes on good commits and fails on bad ones (with --auto-good)"
+        )]
+        test: Option<String>,
+        #[clap(
+            long = "max-tests",
+            default_value = "8",
+            help = "Most times to run --test while searching"
+        )]
+        max_tests: usize,
     },
     #[clap(about = "Mark current commit as good")]
     Good,
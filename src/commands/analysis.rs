@@ -1,9 +1,26 @@
-use crate::Result;
+use crate::commands::commit::RevertMergeCommand;
+use crate::core::config::Config;
+use crate::core::dependencies::{DEFAULT_LOCKFILE_MONTHS, Dependencies, DependencyManifest};
+use crate::core::diff_renderer::DiffRenderer;
+use crate::core::export::{ExportFormat, ExportValue, Exporter};
+use crate::core::generated::GeneratedFiles;
+pub use crate::core::generated::is_generated_file;
 use crate::core::git::AsyncGitOperations;
+use crate::core::i18n::{Message, t};
+use crate::core::loc::{LinesOfCode, thousands};
+use crate::core::network::Network;
+use crate::core::packages::{Package, Packages};
+use crate::core::period::TimeSpec;
+use crate::core::report::{Report, ReportFormat};
+use crate::core::secrets::glob_match;
+use crate::core::sparse::SparseCheckout;
 use crate::core::traits::*;
 use crate::core::{git::*, output::*};
-use chrono::{NaiveDate, Utc};
-use std::collections::{BTreeMap, HashMap};
+use crate::domain::RefName;
+use crate::{GitXError, Result};
+use chrono::{Datelike, NaiveDate, Utc, Weekday};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
 
 /// Analysis and reporting commands grouped together
 pub struct AnalysisCommands;
@@ -14,6 +31,11 @@ impl AnalysisCommands {
         SummaryCommand::new(since).execute()
     }
 
+    /// Commits since the previous working day, per author
+    pub fn standup(since: Option<String>) -> Result<String> {
+        StandupCommand::new(since).execute()
+    }
+
     /// Show repository graph
     pub fn graph(colored: bool) -> Result<String> {
         if colored {
@@ -45,53 +67,281 @@ impl AnalysisCommands {
 
     /// Analyze what changed between branches
     pub fn what(target: Option<String>) -> Result<String> {
-        WhatCommand::new(target).execute()
+        WhatCommand::new(target.map(|t| t.parse()).transpose()?).execute()
+    }
+
+    /// List the commits that differ between the current branch and another
+    pub fn branch_diff(target: String) -> Result<String> {
+        BranchDiffCommand::new(target.parse()?).execute()
+    }
+
+    /// Directory-level roll-up of changes between two refs
+    pub fn diff_stat(from: String, to: Option<String>, depth: usize) -> Result<String> {
+        DiffStatCommand::new(from, to, depth).execute()
+    }
+
+    /// Who changes each directory most over a period
+    pub fn ownership(since: Option<String>, depth: usize) -> Result<String> {
+        OwnershipCommand::new()
+            .with_since(since)
+            .with_depth(depth)
+            .execute()
+    }
+
+    /// Consolidated Markdown or HTML report, optionally written to a file
+    pub fn export_report(format: Option<ReportFormat>, output: Option<String>) -> Result<String> {
+        ExportReportCommand::new()
+            .with_format(format)
+            .with_output(output)
+            .execute()
+    }
+
+    /// Find deleted files, optionally restoring one
+    pub fn gone_files(query: Option<String>, restore: bool, limit: usize) -> Result<String> {
+        GoneFilesCommand::new(query, restore, limit).execute()
+    }
+
+    /// Check performance features, optionally applying the recommendations
+    pub fn perf(apply: bool) -> Result<String> {
+        PerfCommand::new(apply).execute()
+    }
+
+    /// Calendar heatmap of commit activity
+    pub fn heatmap(since: Option<String>, author: Option<String>) -> Result<String> {
+        HeatmapCommand::new()
+            .with_since(since)
+            .with_author(author)
+            .execute()
+    }
+
+    /// Walk a range commit-by-commit and write a review summary
+    pub fn review(target: String, base: Option<String>, output: Option<String>) -> Result<String> {
+        ReviewCommand::new(target, base, output).execute()
+    }
+}
+
+/// Which commits a summary counts
+///
+/// Teams are groups of authors defined in config, e.g.
+/// `git config --add git-x.team.backend "alice@example.com"`.
+#[derive(Debug, Clone, Default)]
+pub struct SummaryFilter {
+    pub exclude_merges: bool,
+    /// `git log --author` patterns
+    pub authors: Vec<String>,
+    /// Team names resolved through `git-x.team.<name>`
+    pub teams: Vec<String>,
+    /// Break the daily summary down per author
+    pub by_author: bool,
+    /// Only commits and files in this workspace package
+    pub package: Option<Package>,
+    /// Count generated and vendored files in the file and language totals
+    pub include_generated: bool,
+}
+
+impl SummaryFilter {
+    /// Authors configured for a team
+    pub fn team_members(team: &str) -> Result<Vec<String>> {
+        let members = crate::core::config::Config::get_list(&format!("team.{team}"));
+        if members.is_empty() {
+            return Err(GitXError::Other(format!(
+                "Team '{team}' is not defined (set git-x.team.{team} in git config)"
+            )));
+        }
+        Ok(members)
+    }
+
+    /// Extra arguments for `git log`, `rev-list` and `shortlog`
+    pub fn git_args(&self) -> Result<Vec<String>> {
+        let mut args = Vec::new();
+        if self.exclude_merges {
+            args.push("--no-merges".to_string());
+        }
+        for author in &self.authors {
+            args.push(format!("--author={author}"));
+        }
+        for team in &self.teams {
+            for member in Self::team_members(team)? {
+                args.push(format!("--author={member}"));
+            }
+        }
+        Ok(args)
+    }
+
+    /// Pathspec for the end of the `git log`, `rev-list` and `shortlog` command line
+    pub fn pathspec(&self) -> Vec<String> {
+        match &self.package {
+            Some(package) => vec!["--".to_string(), package.path.clone()],
+            None => Vec::new(),
+        }
+    }
+
+    /// Human-readable description of the active filters, if any
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if self.exclude_merges {
+            parts.push("no merges".to_string());
+        }
+        if !self.authors.is_empty() {
+            parts.push(format!("authors: {}", self.authors.join(", ")));
+        }
+        if !self.teams.is_empty() {
+            parts.push(format!("teams: {}", self.teams.join(", ")));
+        }
+        if let Some(package) = &self.package {
+            parts.push(format!("package: {}", package.label()));
+        }
+        (!parts.is_empty()).then(|| parts.join("; "))
     }
 }
 
+/// Period `summary` covers when no `--since` is given
+pub const DEFAULT_SUMMARY_SINCE: &str = "1 month ago";
+
+/// `--since` argument for git and the label shown for a summary period
+fn summary_period(since: Option<&str>) -> Result<(String, String)> {
+    let since = TimeSpec::parse(since.unwrap_or(DEFAULT_SUMMARY_SINCE))?;
+    Ok((since.git_arg(), since.to_string()))
+}
+
 /// Command to generate repository summary
 pub struct SummaryCommand {
     since: Option<String>,
+    filter: SummaryFilter,
 }
 
 impl SummaryCommand {
     pub fn new(since: Option<String>) -> Self {
-        Self { since }
+        Self {
+            since,
+            filter: SummaryFilter::default(),
+        }
+    }
+
+    pub fn with_filter(mut self, filter: SummaryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Whether to show the per-day commit listing instead of the overview
+    fn is_detailed(&self) -> bool {
+        self.since.is_some() || self.filter.by_author
     }
 
     fn get_commit_stats(&self) -> Result<CommitStats> {
-        let since_arg = self.since.as_deref().unwrap_or("1 month ago");
-        let args = if self.since.is_some() {
-            vec!["rev-list", "--count", "--since", since_arg, "HEAD"]
+        let (since_arg, period) = summary_period(self.since.as_deref())?;
+        let mut args = if self.since.is_some() {
+            vec!["rev-list", "--count", "--since", &since_arg]
         } else {
-            vec!["rev-list", "--count", "HEAD"]
+            vec!["rev-list", "--count"]
         };
+        let filter_args = self.filter.git_args()?;
+        args.extend(filter_args.iter().map(String::as_str));
+        args.push("HEAD");
+        let pathspec = self.filter.pathspec();
+        args.extend(pathspec.iter().map(String::as_str));
 
         let count_output = GitOperations::run(&args)?;
         let total_commits: u32 = count_output.trim().parse().unwrap_or(0);
 
         Ok(CommitStats {
             total_commits,
-            period: since_arg.to_string(),
+            period,
         })
     }
 
     fn get_detailed_commit_summary(&self) -> Result<String> {
-        let since_arg = self.since.as_deref().unwrap_or("1 month ago");
-        let git_log_output = GitOperations::run(&[
+        let (since_arg, period) = summary_period(self.since.as_deref())?;
+        let mut args = vec![
             "log",
             "--since",
-            since_arg,
+            &since_arg,
             "--pretty=format:%h|%ad|%s|%an|%cr",
             "--date=short",
-        ])?;
+        ];
+        let filter_args = self.filter.git_args()?;
+        args.extend(filter_args.iter().map(String::as_str));
+        let pathspec = self.filter.pathspec();
+        args.extend(pathspec.iter().map(String::as_str));
+        let git_log_output = GitOperations::run(&args)?;
+
+        self.render_detailed_summary(&period, &git_log_output)
+    }
+
+    /// Render `git log` output for the detailed summary, honouring the filter
+    fn render_detailed_summary(&self, since: &str, log: &str) -> Result<String> {
+        if log.trim().is_empty() {
+            return Ok(Message::new("summary.no_commits")
+                .arg("since", since)
+                .to_string());
+        }
+
+        let mut result = if self.filter.by_author {
+            self.format_author_breakdown(since, log)
+        } else {
+            self.format_commit_summary(since, &self.parse_git_log_output(log))
+        };
+        if let Some(filters) = self.filter.describe() {
+            result.push_str(&format!(
+                "\n{}\n",
+                Message::new("summary.filters").arg("filters", filters)
+            ));
+        }
+        Ok(result)
+    }
+
+    /// Commits grouped by day, then by author (busiest first)
+    fn format_author_breakdown(&self, since: &str, stdout: &str) -> String {
+        let mut grouped: BTreeMap<NaiveDate, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+        for line in stdout.lines() {
+            let parts: Vec<&str> = line.splitn(5, '|').collect();
+            if parts.len() != 5 {
+                continue;
+            }
+            let Some(date) = self.parse_commit_date(parts[1]) else {
+                continue;
+            };
+            let message = parts[2].trim();
+            let entry = format!(
+                "   - {} {} ({})",
+                self.get_commit_emoji(message),
+                message,
+                parts[4]
+            );
+            grouped
+                .entry(date)
+                .or_default()
+                .entry(parts[3].to_string())
+                .or_default()
+                .push(entry);
+        }
+
+        let mut result = format!(
+            "{}\n",
+            Message::new("summary.since_by_author").arg("since", since)
+        );
+        result.push_str(&"=".repeat(50));
+        result.push('\n');
 
-        if git_log_output.trim().is_empty() {
-            return Ok(format!("📅 No commits found since {since_arg}"));
+        for (date, authors) in grouped.iter().rev() {
+            result.push_str(&format!("\n📆 {date}\n"));
+            let mut authors: Vec<_> = authors.iter().collect();
+            authors.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+            for (author, commits) in authors {
+                result.push_str(&format!(
+                    "{}\n",
+                    Message::new("summary.author")
+                        .arg("author", author)
+                        .arg("count", commits.len())
+                ));
+                for commit in commits {
+                    result.push_str(commit);
+                    result.push('\n');
+                }
+            }
         }
 
-        let grouped = self.parse_git_log_output(&git_log_output);
-        Ok(self.format_commit_summary(since_arg, &grouped))
+        result
     }
 
     fn parse_git_log_output(&self, stdout: &str) -> BTreeMap<NaiveDate, Vec<String>> {
@@ -117,7 +367,9 @@ impl SummaryCommand {
         let entry = format!(" - {} {}", self.get_commit_emoji(message), message.trim());
         let author = parts[3];
         let time = parts[4];
-        let meta = format!("(by {author}, {time})");
+        let meta = Message::new("summary.by")
+            .arg("author", author)
+            .arg("time", time);
         Some((date, format!("{entry} {meta}")))
     }
 
@@ -132,7 +384,7 @@ impl SummaryCommand {
         since: &str,
         grouped: &BTreeMap<NaiveDate, Vec<String>>,
     ) -> String {
-        let mut result = format!("📅 Commit Summary since {since}:\n");
+        let mut result = format!("{}\n", Message::new("summary.since").arg("since", since));
         result.push_str(&"=".repeat(50));
         result.push('\n');
 
@@ -179,8 +431,14 @@ impl SummaryCommand {
     }
 
     fn get_author_stats(&self) -> Result<Vec<AuthorStats>> {
-        let since_arg = self.since.as_deref().unwrap_or("1 month ago");
-        let args = vec!["shortlog", "-sn", "--since", since_arg];
+        let (since_arg, _) = summary_period(self.since.as_deref())?;
+        let mut args = vec!["shortlog", "-sn", "--since", &since_arg];
+        let filter_args = self.filter.git_args()?;
+        args.extend(filter_args.iter().map(String::as_str));
+        // Without a revision shortlog reads stdin when it isn't a terminal
+        args.push("HEAD");
+        let pathspec = self.filter.pathspec();
+        args.extend(pathspec.iter().map(String::as_str));
 
         let output = GitOperations::run(&args)?;
         let mut authors = Vec::new();
@@ -200,34 +458,21 @@ impl SummaryCommand {
     }
 
     fn get_file_stats(&self) -> Result<FileStats> {
-        let output = GitOperations::run(&["ls-files"])?;
-        let total_files = output.lines().count();
-
-        // Get lines of code (rough estimate)
-        let mut total_lines = 0;
-        if let Ok(wc_output) = GitOperations::run(&["ls-files", "-z"]) {
-            // This is a simplified version - in practice you'd want better file type detection
-            total_lines = wc_output.split('\0').count();
-        }
-
-        Ok(FileStats {
-            total_files,
-            _total_lines: total_lines,
-        })
+        FileStats::collect(self.filter.package.as_ref(), self.filter.include_generated)
     }
 }
 
 impl Command for SummaryCommand {
     fn execute(&self) -> Result<String> {
         // If a specific since parameter is provided, show detailed commit summary
-        if self.since.is_some() {
+        if self.is_detailed() {
             return self.get_detailed_commit_summary();
         }
 
         // Otherwise show repository summary
         let mut output = BufferedOutput::new();
 
-        output.add_line("📊 Repository Summary".to_string());
+        output.add_line(t("summary.title"));
         output.add_line("=".repeat(50));
 
         // Repository name
@@ -236,76 +481,66 @@ impl Command for SummaryCommand {
                 .file_name()
                 .map(|s| s.to_string_lossy().to_string())
                 .unwrap_or_else(|| "Unknown".to_string());
-            output.add_line(format!("🗂️  Repository: {}", Format::bold(&repo_name)));
+            output.add_line(
+                Message::new("repository")
+                    .arg("name", Format::bold(&repo_name))
+                    .to_string(),
+            );
         }
 
         // Current branch info
         let (current_branch, upstream, ahead, behind) = GitOperations::branch_info_optimized()?;
-        output.add_line(format!(
-            "📍 Current branch: {}",
-            Format::bold(&current_branch)
-        ));
+        output.add_line(current_branch_line(&current_branch)?);
 
         if let Some(upstream_branch) = upstream {
-            if ahead > 0 || behind > 0 {
-                output.add_line(format!(
-                    "🔗 Upstream: {upstream_branch} ({ahead} ahead, {behind} behind)"
-                ));
-            } else {
-                output.add_line(format!("🔗 Upstream: {upstream_branch} (up to date)"));
-            }
+            output.add_line(upstream_line(&upstream_branch, ahead, behind));
         }
 
         // Commit statistics
         match self.get_commit_stats() {
             Ok(stats) => {
-                output.add_line(format!(
-                    "📈 Commits ({}): {}",
-                    stats.period, stats.total_commits
-                ));
+                output.add_line(
+                    Message::new("summary.commits")
+                        .arg("period", &stats.period)
+                        .arg("count", stats.total_commits)
+                        .to_string(),
+                );
             }
             Err(_) => {
-                output.add_line("📈 Commits: Unable to retrieve".to_string());
+                output.add_line(t("summary.commits_failed"));
             }
         }
 
         // Author statistics
         match self.get_author_stats() {
             Ok(authors) => {
-                if !authors.is_empty() {
-                    output.add_line(format!(
-                        "👥 Top contributors ({}): ",
-                        self.since.as_deref().unwrap_or("all time")
-                    ));
-                    for (i, author) in authors.iter().take(5).enumerate() {
-                        let prefix = match i {
-                            0 => "🥇",
-                            1 => "🥈",
-                            2 => "🥉",
-                            _ => "👤",
-                        };
-                        output.add_line(format!(
-                            "   {} {} ({} commits)",
-                            prefix, author.name, author.commits
-                        ));
-                    }
-                }
+                output.add_lines(contributor_lines(self.since.as_deref(), &authors));
             }
             Err(_) => {
-                output.add_line("👥 Contributors: Unable to retrieve".to_string());
+                output.add_line(t("summary.contributors_failed"));
             }
         }
 
         // File statistics
         match self.get_file_stats() {
             Ok(stats) => {
-                output.add_line(format!("📁 Files: {} total", stats.total_files));
+                for line in stats.lines() {
+                    output.add_line(line);
+                }
             }
             Err(_) => {
-                output.add_line("📁 Files: Unable to retrieve".to_string());
+                output.add_line(t("summary.files_failed"));
             }
         }
 
+        if let Some(filters) = self.filter.describe() {
+            output.add_line(
+                Message::new("summary.filters")
+                    .arg("filters", filters)
+                    .to_string(),
+            );
+        }
+
         Ok(output.content())
     }
 
@@ -323,16 +558,33 @@ impl GitCommand for SummaryCommand {}
 /// Async parallel version of SummaryCommand
 pub struct AsyncSummaryCommand {
     since: Option<String>,
+    filter: SummaryFilter,
+    json: bool,
 }
 
 impl AsyncSummaryCommand {
     pub fn new(since: Option<String>) -> Self {
-        Self { since }
+        Self {
+            since,
+            filter: SummaryFilter::default(),
+            json: false,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: SummaryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Print the repository overview as one JSON object, even with `--since`
+    pub fn with_json(mut self) -> Self {
+        self.json = true;
+        self
     }
 
     pub async fn execute_parallel(&self) -> Result<String> {
         // If a specific since parameter is provided, show detailed commit summary
-        if self.since.is_some() {
+        if !self.json && (self.since.is_some() || self.filter.by_author) {
             return self.get_detailed_commit_summary_async().await;
         }
 
@@ -351,106 +603,155 @@ impl AsyncSummaryCommand {
             self.get_file_stats_async(),
         )?;
 
-        let mut output = BufferedOutput::new();
-
-        output.add_line("📊 Repository Summary".to_string());
-        output.add_line("=".repeat(50));
-
         // Repository name
         let repo_name = std::path::Path::new(&repo_root_result)
             .file_name()
             .map(|s| s.to_string_lossy().to_string())
             .unwrap_or_else(|| "Unknown".to_string());
-        output.add_line(format!("🗂️  Repository: {}", Format::bold(&repo_name)));
+
+        if self.json {
+            let (branch, upstream, ahead, behind) = branch_info_result;
+            return Ok(ExportValue::record(vec![
+                ("repository", ExportValue::from(repo_name)),
+                ("branch", ExportValue::from(branch)),
+                (
+                    "upstream",
+                    upstream.map_or(ExportValue::Null, ExportValue::from),
+                ),
+                ("ahead", ahead.into()),
+                ("behind", behind.into()),
+                ("period", ExportValue::from(commit_stats_result.period)),
+                ("commits", commit_stats_result.total_commits.into()),
+                (
+                    "contributors",
+                    ExportValue::List(
+                        author_stats_result
+                            .iter()
+                            .map(|author| {
+                                ExportValue::record(vec![
+                                    ("name", ExportValue::from(author.name.as_str())),
+                                    ("commits", author.commits.into()),
+                                ])
+                            })
+                            .collect(),
+                    ),
+                ),
+                ("files", file_stats_result.total_files.into()),
+                ("binary_files", file_stats_result.loc.binary_files.into()),
+                ("generated_files", file_stats_result.generated_files.into()),
+                ("lines", file_stats_result.loc.total_lines().into()),
+                ("code", file_stats_result.loc.total_code().into()),
+                ("languages", file_stats_result.loc.to_value()),
+                (
+                    "filters",
+                    self.filter
+                        .describe()
+                        .map_or(ExportValue::Null, ExportValue::from),
+                ),
+            ])
+            .to_json());
+        }
+
+        let mut output = BufferedOutput::new();
+
+        output.add_line(t("summary.title"));
+        output.add_line("=".repeat(50));
+        output.add_line(
+            Message::new("repository")
+                .arg("name", Format::bold(&repo_name))
+                .to_string(),
+        );
 
         // Current branch info
         let (current_branch, upstream, ahead, behind) = branch_info_result;
-        output.add_line(format!(
-            "📍 Current branch: {}",
-            Format::bold(&current_branch)
-        ));
+        output.add_line(current_branch_line(&current_branch)?);
 
         if let Some(upstream_branch) = upstream {
-            if ahead > 0 || behind > 0 {
-                output.add_line(format!(
-                    "🔗 Upstream: {upstream_branch} ({ahead} ahead, {behind} behind)"
-                ));
-            } else {
-                output.add_line(format!("🔗 Upstream: {upstream_branch} (up to date)"));
-            }
+            output.add_line(upstream_line(&upstream_branch, ahead, behind));
         }
 
         // Commit statistics
-        output.add_line(format!(
-            "📈 Commits ({}): {}",
-            commit_stats_result.period, commit_stats_result.total_commits
-        ));
+        output.add_line(
+            Message::new("summary.commits")
+                .arg("period", &commit_stats_result.period)
+                .arg("count", commit_stats_result.total_commits)
+                .to_string(),
+        );
 
         // Author statistics
-        if !author_stats_result.is_empty() {
-            output.add_line(format!(
-                "👥 Top contributors ({}): ",
-                self.since.as_deref().unwrap_or("all time")
-            ));
-            for (i, author) in author_stats_result.iter().take(5).enumerate() {
-                let prefix = match i {
-                    0 => "🥇",
-                    1 => "🥈",
-                    2 => "🥉",
-                    _ => "👤",
-                };
-                output.add_line(format!(
-                    "   {} {} ({} commits)",
-                    prefix, author.name, author.commits
-                ));
-            }
-        }
+        output.add_lines(contributor_lines(
+            self.since.as_deref(),
+            &author_stats_result,
+        ));
 
         // File statistics
-        output.add_line(format!("📁 Files: {} total", file_stats_result.total_files));
+        for line in file_stats_result.lines() {
+            output.add_line(line);
+        }
+
+        if let Some(filters) = self.filter.describe() {
+            output.add_line(
+                Message::new("summary.filters")
+                    .arg("filters", filters)
+                    .to_string(),
+            );
+        }
 
         Ok(output.content())
     }
 
     async fn get_detailed_commit_summary_async(&self) -> Result<String> {
-        let since_arg = self.since.as_deref().unwrap_or("1 month ago");
-        let git_log_output = AsyncGitOperations::run(&[
+        let (since_arg, period) = summary_period(self.since.as_deref())?;
+        let mut args = vec![
             "log",
             "--since",
-            since_arg,
+            &since_arg,
             "--pretty=format:%h|%ad|%s|%an|%cr",
             "--date=short",
-        ])
-        .await?;
-
-        if git_log_output.trim().is_empty() {
-            return Ok(format!("📅 No commits found since {since_arg}"));
-        }
-
-        let grouped = self.parse_git_log_output(&git_log_output);
-        Ok(self.format_commit_summary(since_arg, &grouped))
+        ];
+        let filter_args = self.filter.git_args()?;
+        args.extend(filter_args.iter().map(String::as_str));
+        let pathspec = self.filter.pathspec();
+        args.extend(pathspec.iter().map(String::as_str));
+        let git_log_output = AsyncGitOperations::run(&args).await?;
+
+        // Rendering is shared with the synchronous command
+        SummaryCommand::new(self.since.clone())
+            .with_filter(self.filter.clone())
+            .render_detailed_summary(&period, &git_log_output)
     }
 
     async fn get_commit_stats_async(&self) -> Result<CommitStats> {
-        let since_arg = self.since.as_deref().unwrap_or("1 month ago");
-        let args = if self.since.is_some() {
-            vec!["rev-list", "--count", "--since", since_arg, "HEAD"]
+        let (since_arg, period) = summary_period(self.since.as_deref())?;
+        let mut args = if self.since.is_some() {
+            vec!["rev-list", "--count", "--since", &since_arg]
         } else {
-            vec!["rev-list", "--count", "HEAD"]
+            vec!["rev-list", "--count"]
         };
+        let filter_args = self.filter.git_args()?;
+        args.extend(filter_args.iter().map(String::as_str));
+        args.push("HEAD");
+        let pathspec = self.filter.pathspec();
+        args.extend(pathspec.iter().map(String::as_str));
 
         let count_output = AsyncGitOperations::run(&args).await?;
         let total_commits: u32 = count_output.trim().parse().unwrap_or(0);
 
         Ok(CommitStats {
             total_commits,
-            period: since_arg.to_string(),
+            period,
         })
     }
 
     async fn get_author_stats_async(&self) -> Result<Vec<AuthorStats>> {
-        let since_arg = self.since.as_deref().unwrap_or("1 month ago");
-        let args = vec!["shortlog", "-sn", "--since", since_arg];
+        let (since_arg, _) = summary_period(self.since.as_deref())?;
+        let mut args = vec!["shortlog", "-sn", "--since", &since_arg];
+        let filter_args = self.filter.git_args()?;
+        args.extend(filter_args.iter().map(String::as_str));
+        // Without a revision shortlog reads stdin when it isn't a terminal (e.g. under serve)
+        args.push("HEAD");
+        let pathspec = self.filter.pathspec();
+        args.extend(pathspec.iter().map(String::as_str));
 
         let output = AsyncGitOperations::run(&args).await?;
         let mut authors = Vec::new();
@@ -470,140 +771,449 @@ impl AsyncSummaryCommand {
     }
 
     async fn get_file_stats_async(&self) -> Result<FileStats> {
-        let (output, wc_output) = tokio::try_join!(
-            AsyncGitOperations::run(&["ls-files"]),
-            AsyncGitOperations::run(&["ls-files", "-z"])
-        )?;
+        // Reading every file is blocking work, spread over rayon's pool
+        let package = self.filter.package.clone();
+        let include_generated = self.filter.include_generated;
+        tokio::task::spawn_blocking(move || FileStats::collect(package.as_ref(), include_generated))
+            .await?
+    }
+}
 
-        let total_files = output.lines().count();
-        let total_lines = wc_output.split('\0').count();
+/// A team name with its members from `git-x.team.<name>`
+pub type Team = (String, Vec<String>);
+
+/// One commit in a standup report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StandupCommit {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    /// Relative commit time, e.g. `3 hours ago`
+    pub when: String,
+    pub subject: String,
+}
 
-        Ok(FileStats {
-            total_files,
-            _total_lines: total_lines,
+impl StandupCommit {
+    /// `git log` format read by [`StandupCommit::parse`]
+    pub const FORMAT: &'static str = "--format=%h%x1f%an%x1f%ae%x1f%cr%x1f%s";
+
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\x1f');
+        Some(Self {
+            hash: fields.next()?.to_string(),
+            author: fields.next()?.to_string(),
+            email: fields.next()?.to_string(),
+            when: fields.next()?.to_string(),
+            subject: fields.next()?.trim().to_string(),
         })
     }
 
-    // Reuse existing helper methods
-    fn parse_git_log_output(
-        &self,
-        stdout: &str,
-    ) -> std::collections::BTreeMap<chrono::NaiveDate, Vec<String>> {
-        use std::collections::BTreeMap;
-        let mut grouped: BTreeMap<chrono::NaiveDate, Vec<String>> = BTreeMap::new();
+    /// Whether a team entry (a name, an email or part of either) is this commit's author
+    pub fn is_by(&self, member: &str) -> bool {
+        format!("{} <{}>", self.author, self.email)
+            .to_lowercase()
+            .contains(&member.to_lowercase())
+    }
+}
 
-        for line in stdout.lines() {
-            if let Some((date, formatted_commit)) = self.parse_commit_line(line) {
-                grouped.entry(date).or_default().push(formatted_commit);
-            }
+/// Commits since the previous working day, per author or per team and then per author
+pub struct StandupCommand {
+    since: Option<String>,
+    filter: SummaryFilter,
+    /// `None` lists authors only; an empty list groups by every configured team
+    teams: Option<Vec<String>>,
+}
+
+impl StandupCommand {
+    pub fn new(since: Option<String>) -> Self {
+        Self {
+            since,
+            filter: SummaryFilter::default(),
+            teams: None,
         }
+    }
 
-        grouped
+    /// Merge and author filtering; the filter's own teams are ignored
+    pub fn with_filter(mut self, filter: SummaryFilter) -> Self {
+        self.filter = filter;
+        self
     }
 
-    fn parse_commit_line(&self, line: &str) -> Option<(chrono::NaiveDate, String)> {
-        let parts: Vec<&str> = line.splitn(5, '|').collect();
-        if parts.len() != 5 {
-            return None;
-        }
+    pub fn with_teams(mut self, teams: Option<Vec<String>>) -> Self {
+        self.teams = teams;
+        self
+    }
 
-        let date = self.parse_commit_date(parts[1])?;
-        let message = parts[2];
-        let entry = format!(" - {} {}", self.get_commit_emoji(message), message.trim());
-        let author = parts[3];
-        let time = parts[4];
-        let meta = format!("(by {author}, {time})");
-        Some((date, format!("{entry} {meta}")))
+    /// The previous working day, so Monday's standup covers Friday
+    pub fn previous_working_day(today: NaiveDate) -> NaiveDate {
+        let days = match today.weekday() {
+            Weekday::Mon => 3,
+            Weekday::Sun => 2,
+            _ => 1,
+        };
+        today - chrono::Duration::days(days)
     }
 
-    fn parse_commit_date(&self, date_str: &str) -> Option<chrono::NaiveDate> {
-        use chrono::{NaiveDate, Utc};
-        NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-            .ok()
-            .or_else(|| Some(Utc::now().date_naive()))
+    /// `--since` cutoff and its label, defaulting to the start of the previous working day
+    fn since(&self) -> Result<(TimeSpec, String)> {
+        if let Some(since) = &self.since {
+            let spec = TimeSpec::parse(since)?;
+            let label = spec.to_string();
+            return Ok((spec, label));
+        }
+        let day = Self::previous_working_day(chrono::Local::now().date_naive());
+        // A bare date is local midnight
+        let spec = TimeSpec::parse(&day.format("%Y-%m-%d").to_string())?;
+        Ok((spec, day.format("%a %Y-%m-%d").to_string()))
     }
 
-    fn format_commit_summary(
-        &self,
-        since: &str,
-        grouped: &std::collections::BTreeMap<chrono::NaiveDate, Vec<String>>,
-    ) -> String {
-        let mut result = format!("📅 Commit Summary since {since}:\n");
+    /// Teams to group by with their members, in the order requested
+    fn resolve_teams(&self) -> Result<Option<Vec<Team>>> {
+        let Some(requested) = &self.teams else {
+            return Ok(None);
+        };
+        let names = if requested.is_empty() {
+            let names = Config::names("team.");
+            if names.is_empty() {
+                return Err(GitXError::Other(
+                    "No teams are defined (set git-x.team.<name> in git config)".to_string(),
+                ));
+            }
+            names
+        } else {
+            requested.clone()
+        };
+        names
+            .into_iter()
+            .map(|team| Ok((team.clone(), SummaryFilter::team_members(&team)?)))
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    }
+
+    /// Standup text for already-fetched commits, newest first
+    ///
+    /// With all teams (no names requested), authors outside every team get
+    /// their own group so nobody's work goes missing.
+    pub fn render(&self, since: &str, commits: &[StandupCommit], teams: Option<&[Team]>) -> String {
+        if commits.is_empty() {
+            return format!("📅 No commits since {since}");
+        }
+
+        let mut result = format!("🗓️  Standup since {since}\n");
         result.push_str(&"=".repeat(50));
         result.push('\n');
 
-        for (date, commits) in grouped.iter().rev() {
-            result.push_str(&format!("\n📆 {date}\n"));
-            for commit in commits {
-                result.push_str(commit);
-                result.push('\n');
+        match teams {
+            None => Self::push_authors(&mut result, commits.iter().collect()),
+            Some(teams) => {
+                for (team, members) in teams {
+                    let team_commits: Vec<&StandupCommit> = commits
+                        .iter()
+                        .filter(|commit| members.iter().any(|member| commit.is_by(member)))
+                        .collect();
+                    Self::push_team(&mut result, team, team_commits);
+                }
+                if self.teams.as_ref().is_some_and(|names| names.is_empty()) {
+                    let unassigned: Vec<&StandupCommit> = commits
+                        .iter()
+                        .filter(|commit| {
+                            !teams
+                                .iter()
+                                .flat_map(|(_, members)| members)
+                                .any(|member| commit.is_by(member))
+                        })
+                        .collect();
+                    if !unassigned.is_empty() {
+                        Self::push_team(&mut result, "Not in a team", unassigned);
+                    }
+                }
             }
         }
 
+        let authors: HashSet<&str> = commits.iter().map(|c| c.author.as_str()).collect();
+        result.push_str(&format!(
+            "\n📊 {} commits by {} authors\n",
+            commits.len(),
+            authors.len()
+        ));
+        if let Some(filters) = self.filter.describe() {
+            result.push_str(&format!("🔎 Filters: {filters}\n"));
+        }
         result
     }
 
-    fn get_commit_emoji(&self, message: &str) -> &'static str {
-        // Use case-insensitive matching without allocation
-        let msg_bytes = message.as_bytes();
-        if msg_bytes.windows(3).any(|w| w.eq_ignore_ascii_case(b"fix"))
-            || msg_bytes.windows(3).any(|w| w.eq_ignore_ascii_case(b"bug"))
-        {
-            "🐛"
-        } else if msg_bytes
-            .windows(4)
-            .any(|w| w.eq_ignore_ascii_case(b"feat"))
-            || msg_bytes.windows(3).any(|w| w.eq_ignore_ascii_case(b"add"))
-        {
-            "✨"
-        } else if msg_bytes
-            .windows(6)
-            .any(|w| w.eq_ignore_ascii_case(b"remove"))
-            || msg_bytes
-                .windows(6)
-                .any(|w| w.eq_ignore_ascii_case(b"delete"))
-        {
-            "🔥"
-        } else if msg_bytes
-            .windows(8)
-            .any(|w| w.eq_ignore_ascii_case(b"refactor"))
-        {
-            "🛠"
-        } else {
-            "🔹"
+    fn push_team(result: &mut String, team: &str, commits: Vec<&StandupCommit>) {
+        if commits.is_empty() {
+            result.push_str(&format!("\n🏷️  {team}: no commits\n"));
+            return;
         }
+        let authors: HashSet<&str> = commits.iter().map(|c| c.author.as_str()).collect();
+        result.push_str(&format!(
+            "\n🏷️  {team} ({} commits, {} authors)\n",
+            commits.len(),
+            authors.len()
+        ));
+        Self::push_authors(result, commits);
     }
-}
-
-/// Command to show colored commit graph
-pub struct ColorGraphCommand;
-
-impl Default for ColorGraphCommand {
-    fn default() -> Self {
-        Self::new()
-    }
-}
 
-impl ColorGraphCommand {
-    pub fn new() -> Self {
-        Self
+    /// Busiest authors first, each with their commits
+    fn push_authors(result: &mut String, commits: Vec<&StandupCommit>) {
+        let mut by_author: BTreeMap<&str, Vec<&StandupCommit>> = BTreeMap::new();
+        for commit in commits {
+            by_author.entry(&commit.author).or_default().push(commit);
+        }
+        let mut authors: Vec<_> = by_author.into_iter().collect();
+        authors.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+        for (author, commits) in authors {
+            result.push_str(&format!(" 👤 {author} ({} commits)\n", commits.len()));
+            for commit in commits {
+                result.push_str(&format!(
+                    "   - {} {} ({})\n",
+                    commit.hash, commit.subject, commit.when
+                ));
+            }
+        }
     }
 }
 
-impl Command for ColorGraphCommand {
+impl Command for StandupCommand {
     fn execute(&self) -> Result<String> {
-        GitOperations::run(&[
-            "log",
-            "--graph",
-            "--pretty=format:%C(auto)%h%d %s %C(black)%C(bold)%cr",
-            "--abbrev-commit",
-            "--all",
-            "-20", // Limit to recent commits for better performance
-        ])
+        let teams = self.resolve_teams()?;
+        let (since, label) = self.since()?;
+        let mut args = vec![
+            "log".to_string(),
+            "--branches".to_string(),
+            "--remotes".to_string(),
+            "HEAD".to_string(),
+            format!("--since={}", since.git_arg()),
+            StandupCommit::FORMAT.to_string(),
+        ];
+        if self.filter.exclude_merges {
+            args.push("--no-merges".to_string());
+        }
+        for author in &self.filter.authors {
+            args.push(format!("--author={author}"));
+        }
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let log = GitOperations::run(&args)?;
+        let commits: Vec<StandupCommit> = log.lines().filter_map(StandupCommit::parse).collect();
+
+        Ok(self.render(&label, &commits, teams.as_deref()))
     }
 
     fn name(&self) -> &'static str {
-        "color-graph"
+        "standup"
+    }
+
+    fn description(&self) -> &'static str {
+        "Report commits since the previous working day, per team and author"
+    }
+}
+
+impl GitCommand for StandupCommand {}
+
+/// Which history the graph commands show
+#[derive(Debug, Clone, Default)]
+pub struct GraphOptions {
+    /// Follow only the first parent of each merge: the mainline, one entry per merge
+    pub first_parent: bool,
+    /// List only the merges on the mainline, with their pull request titles
+    pub merges_only: bool,
+    /// Branches to show instead of every ref
+    pub branches: Vec<RefName>,
+}
+
+impl GraphOptions {
+    /// Most recent commits (or merges) shown
+    pub const LIMIT: usize = 20;
+
+    /// The chosen branches; the default branch for a mainline view; else every ref
+    fn revisions(&self) -> Vec<String> {
+        if !self.branches.is_empty() {
+            return self.branches.iter().map(|b| b.to_string()).collect();
+        }
+        if self.first_parent || self.merges_only {
+            return vec![GitOperations::default_branch().unwrap_or_else(|| "HEAD".to_string())];
+        }
+        vec!["--all".to_string()]
+    }
+
+    /// `git log --graph` with the given format options
+    fn graph(&self, format: &[&str]) -> Result<String> {
+        let limit = format!("-{}", Self::LIMIT);
+        let revisions = self.revisions();
+        let mut args = vec!["log", "--graph"];
+        args.extend(format);
+        if self.first_parent {
+            args.push("--first-parent");
+        }
+        args.push(&limit);
+        args.extend(revisions.iter().map(String::as_str));
+        args.push("--");
+        GitOperations::run(&args)
+    }
+
+    /// The merges on the first-parent history, newest first
+    fn merges(&self) -> Result<Vec<MergeEntry>> {
+        let limit = format!("-{}", Self::LIMIT);
+        let revisions = self.revisions();
+        let mut args = vec![
+            "log",
+            "--merges",
+            "--first-parent",
+            "--date=short",
+            "--format=%h%x1f%ad%x1f%s%x1f%b%x1e",
+            &limit,
+        ];
+        args.extend(revisions.iter().map(String::as_str));
+        args.push("--");
+        let log = GitOperations::run(&args)?;
+        Ok(log
+            .split('\x1e')
+            .filter_map(|record| {
+                let mut fields = record.trim_start_matches('\n').splitn(4, '\x1f');
+                let hash = fields.next().filter(|hash| !hash.is_empty())?;
+                let date = fields.next()?;
+                let subject = fields.next()?;
+                Some(MergeEntry::parse(
+                    hash,
+                    date,
+                    subject,
+                    fields.next().unwrap_or_default(),
+                ))
+            })
+            .collect())
+    }
+
+    /// The `--merges-only` listing, with hashes highlighted when `colored`
+    fn render_merges(&self, colored: bool) -> Result<String> {
+        let merges = self.merges()?;
+        let revisions = self.revisions().join(", ");
+        if merges.is_empty() {
+            return Ok(format!(
+                "✅ No merges on the first-parent history of {revisions}"
+            ));
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🚂 Merges into {revisions} (first parent, newest first):"
+        ));
+        for merge in &merges {
+            let hash = if colored {
+                Format::colored(&merge.hash, console::Color::Yellow)
+            } else {
+                merge.hash.clone()
+            };
+            output.add_line(format!("{hash} {} {}", merge.date, merge.describe()));
+        }
+        Ok(output.content())
+    }
+}
+
+/// A merge commit, with the pull or merge request it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeEntry {
+    pub hash: String,
+    pub date: String,
+    /// `#12` for a GitHub pull request, `!34` for a GitLab merge request
+    pub request: Option<String>,
+    /// The request's title, else the merge subject
+    pub title: String,
+    /// The merged branch, when the subject names it
+    pub branch: Option<String>,
+}
+
+impl MergeEntry {
+    /// Read a merge message as GitHub, GitLab or plain git wrote it
+    ///
+    /// GitHub puts the pull request title on the first body line under
+    /// `Merge pull request #12 from owner/branch`; GitLab puts it under
+    /// `Merge branch 'branch' into 'main'` and ends with `See merge request group/project!34`.
+    pub fn parse(hash: &str, date: &str, subject: &str, body: &str) -> Self {
+        let digits =
+            |text: &str| -> String { text.chars().take_while(char::is_ascii_digit).collect() };
+        let github = subject
+            .strip_prefix("Merge pull request #")
+            .map(digits)
+            .filter(|number| !number.is_empty())
+            .map(|number| format!("#{number}"));
+        let gitlab = body
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("See merge request "))
+            .and_then(|reference| reference.rsplit_once('!'))
+            .map(|(_, number)| digits(number))
+            .filter(|number| !number.is_empty())
+            .map(|number| format!("!{number}"));
+        let title = body
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with("See merge request "))
+            .filter(|_| github.is_some() || gitlab.is_some())
+            .unwrap_or(subject);
+
+        Self {
+            hash: hash.to_string(),
+            date: date.to_string(),
+            request: github.or(gitlab),
+            title: title.to_string(),
+            branch: RevertMergeCommand::merged_branch(subject),
+        }
+    }
+
+    /// `#12 Add login form (feature/login)`, leaving out what isn't known
+    pub fn describe(&self) -> String {
+        let mut text = match &self.request {
+            Some(request) => format!("{request} {}", self.title),
+            None => self.title.clone(),
+        };
+        if let Some(branch) = &self.branch
+            && !self.title.contains(branch.as_str())
+        {
+            text.push_str(&format!(" ({branch})"));
+        }
+        text
+    }
+}
+
+/// Command to show colored commit graph
+pub struct ColorGraphCommand {
+    options: GraphOptions,
+}
+
+impl Default for ColorGraphCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColorGraphCommand {
+    pub fn new() -> Self {
+        Self {
+            options: GraphOptions::default(),
+        }
+    }
+
+    /// Which history to show
+    pub fn with_options(mut self, options: GraphOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl Command for ColorGraphCommand {
+    fn execute(&self) -> Result<String> {
+        if self.options.merges_only {
+            return self.options.render_merges(true);
+        }
+        self.options.graph(&[
+            "--pretty=format:%C(auto)%h%d %s %C(black)%C(bold)%cr",
+            "--abbrev-commit",
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "color-graph"
     }
 
     fn description(&self) -> &'static str {
@@ -614,7 +1224,9 @@ impl Command for ColorGraphCommand {
 impl GitCommand for ColorGraphCommand {}
 
 /// Command to show simple commit graph
-pub struct GraphCommand;
+pub struct GraphCommand {
+    options: GraphOptions,
+}
 
 impl Default for GraphCommand {
     fn default() -> Self {
@@ -624,13 +1236,24 @@ impl Default for GraphCommand {
 
 impl GraphCommand {
     pub fn new() -> Self {
-        Self
+        Self {
+            options: GraphOptions::default(),
+        }
+    }
+
+    /// Which history to show
+    pub fn with_options(mut self, options: GraphOptions) -> Self {
+        self.options = options;
+        self
     }
 }
 
 impl Command for GraphCommand {
     fn execute(&self) -> Result<String> {
-        GitOperations::run(&["log", "--graph", "--oneline", "--all", "-20"])
+        if self.options.merges_only {
+            return self.options.render_merges(false);
+        }
+        self.options.graph(&["--oneline"])
     }
 
     fn name(&self) -> &'static str {
@@ -647,15 +1270,27 @@ impl GitCommand for GraphCommand {}
 /// Command to show contributors
 pub struct ContributorsCommand {
     since: Option<String>,
+    format: Option<ExportFormat>,
 }
 
 impl ContributorsCommand {
     pub fn new(since: Option<String>) -> Self {
-        Self { since }
+        Self {
+            since,
+            format: None,
+        }
+    }
+
+    /// Render as CSV/TSV/JSON instead of the human-readable report
+    pub fn with_format(mut self, format: Option<ExportFormat>) -> Self {
+        self.format = format;
+        self
     }
 
     fn get_detailed_contributors(&self) -> Result<Vec<ContributorStats>> {
-        let args = if let Some(ref since) = self.since {
+        let since = self.since.as_deref().map(TimeSpec::parse).transpose()?;
+        let since_arg = since.map(|since| since.git_arg());
+        let args = if let Some(since) = &since_arg {
             vec![
                 "log",
                 "--all",
@@ -707,7 +1342,7 @@ impl ContributorsCommand {
         }
 
         let mut sorted_contributors: Vec<ContributorStats> = contributors.into_values().collect();
-        sorted_contributors.sort_by(|a, b| b.commit_count.cmp(&a.commit_count));
+        sorted_contributors.sort_by_key(|c| std::cmp::Reverse(c.commit_count));
 
         Ok(sorted_contributors)
     }
@@ -717,6 +1352,10 @@ impl Command for ContributorsCommand {
     fn execute(&self) -> Result<String> {
         let contributors = self.get_detailed_contributors()?;
 
+        if let Some(format) = self.format {
+            return Ok(export_contributors(&contributors, format));
+        }
+
         if contributors.is_empty() {
             return Ok("📊 No contributors found in this repository".to_string());
         }
@@ -770,38 +1409,177 @@ impl Command for ContributorsCommand {
 
 impl GitCommand for ContributorsCommand {}
 
+impl MultiFormat for ContributorsCommand {
+    fn supported_formats(&self) -> Vec<&'static str> {
+        let mut formats = vec!["text"];
+        formats.extend(ExportFormat::names());
+        formats
+    }
+
+    fn execute_with_format(&self, format: &str) -> Result<String> {
+        if format == "text" {
+            return ContributorsCommand::new(self.since.clone()).execute();
+        }
+
+        let format = format.parse::<ExportFormat>()?;
+        Ok(export_contributors(
+            &self.get_detailed_contributors()?,
+            format,
+        ))
+    }
+}
+
+/// Contributors shown in the text report unless `--limit` says otherwise
+pub const DEFAULT_CONTRIBUTORS_LIMIT: usize = 20;
+
 /// Parallel version of ContributorsCommand using multi-threading
 pub struct ParallelContributorsCommand {
     since: Option<String>,
+    until: Option<String>,
+    paths: Vec<String>,
+    limit: Option<usize>,
+    offset: usize,
+    fast: bool,
+    format: Option<ExportFormat>,
 }
 
 impl ParallelContributorsCommand {
     pub fn new(since: Option<String>) -> Self {
-        Self { since }
+        Self {
+            since,
+            until: None,
+            paths: Vec::new(),
+            limit: None,
+            offset: 0,
+            fast: false,
+            format: None,
+        }
     }
 
-    pub fn execute_parallel(&self) -> Result<String> {
-        use rayon::prelude::*;
-        use std::collections::HashMap;
+    /// Only count commits up to this date
+    pub fn with_until(mut self, until: Option<String>) -> Self {
+        self.until = until;
+        self
+    }
 
-        let args = if let Some(ref since) = self.since {
-            vec![
-                "log",
-                "--all",
-                "--format=%ae|%an|%ad",
-                "--date=short",
-                "--since",
-                since,
-            ]
-        } else {
-            vec!["log", "--all", "--format=%ae|%an|%ad", "--date=short"]
+    /// Only count commits touching these paths
+    pub fn with_paths(mut self, paths: Vec<String>) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Show at most `limit` contributors; the text report defaults to
+    /// [`DEFAULT_CONTRIBUTORS_LIMIT`] and exports to everyone
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Skip the `offset` highest-ranked contributors, to page through the rest
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Count commits with `git shortlog -sne`, without first and last commit dates
+    ///
+    /// Much faster on repositories with thousands of contributors, since git
+    /// does the grouping instead of printing a line per commit.
+    pub fn with_fast(mut self) -> Self {
+        self.fast = true;
+        self
+    }
+
+    /// Render as CSV/TSV/JSON instead of the human-readable report
+    pub fn with_format(mut self, format: Option<ExportFormat>) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// `--since` and `--until`, resolved
+    fn period(&self) -> Result<(Option<TimeSpec>, Option<TimeSpec>)> {
+        let resolve = |text: &Option<String>| text.as_deref().map(TimeSpec::parse).transpose();
+        Ok((resolve(&self.since)?, resolve(&self.until)?))
+    }
+
+    /// Revision and path arguments shared by `git log` and `git shortlog`
+    fn revision_args(&self) -> Result<Vec<String>> {
+        let mut args = vec!["--all".to_string()];
+        let (since, until) = self.period()?;
+        if let Some(since) = since {
+            args.extend(["--since".to_string(), since.git_arg()]);
+        }
+        if let Some(until) = until {
+            args.extend(["--until".to_string(), until.git_arg()]);
+        }
+        if !self.paths.is_empty() {
+            args.push("--".to_string());
+            args.extend(self.paths.iter().cloned());
+        }
+        Ok(args)
+    }
+
+    /// Period and paths the report covers, e.g. `1 month ago to 1 week ago in src/`
+    fn scope(&self) -> Result<String> {
+        let period = match self.period()? {
+            (None, None) => "all time".to_string(),
+            (Some(since), None) => since.to_string(),
+            (None, Some(until)) => format!("until {until}"),
+            (Some(since), Some(until)) => format!("{since} to {until}"),
         };
+        Ok(if self.paths.is_empty() {
+            period
+        } else {
+            format!("{period} in {}", self.paths.join(", "))
+        })
+    }
+
+    /// Contributors from `git shortlog -sne` lines (`   12\tName <email>`)
+    pub fn parse_shortlog(output: &str) -> Vec<(String, String, usize)> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let (count, author) = line.trim().split_once('\t')?;
+                let count = count.trim().parse().ok()?;
+                let (name, email) = match author.rsplit_once(" <") {
+                    Some((name, email)) => (name, email.trim_end_matches('>')),
+                    None => (author, ""),
+                };
+                Some((name.trim().to_string(), email.to_string(), count))
+            })
+            .collect()
+    }
 
+    fn shortlog_contributors(&self) -> Result<Vec<ContributorStats>> {
+        let mut args = vec!["shortlog".to_string(), "-sne".to_string()];
+        args.extend(self.revision_args()?);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
         let output = GitOperations::run(&args)?;
 
-        if output.trim().is_empty() {
-            return Ok("No commits found".to_string());
-        }
+        Ok(Self::parse_shortlog(&output)
+            .into_iter()
+            .map(|(name, email, commit_count)| ContributorStats {
+                name,
+                email,
+                commit_count,
+                first_commit: String::new(),
+                last_commit: String::new(),
+            })
+            .collect())
+    }
+
+    fn log_contributors(&self) -> Result<Vec<ContributorStats>> {
+        use rayon::prelude::*;
+        use std::collections::HashMap;
+
+        let mut args = vec![
+            "log".to_string(),
+            "--format=%ae|%an|%ad".to_string(),
+            "--date=short".to_string(),
+        ];
+        args.extend(self.revision_args()?);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = GitOperations::run(&args)?;
 
         // Split lines and process in parallel
         let lines: Vec<&str> = output.lines().collect();
@@ -864,18 +1642,54 @@ impl ParallelContributorsCommand {
                 acc
             });
 
-        // Sort by commit count
-        let mut sorted_contributors: Vec<_> = contributors.into_values().collect();
-        sorted_contributors.sort_by(|a, b| b.commit_count.cmp(&a.commit_count));
+        Ok(contributors.into_values().collect())
+    }
+
+    pub fn execute_parallel(&self) -> Result<String> {
+        let mut contributors = if self.fast {
+            self.shortlog_contributors()?
+        } else {
+            self.log_contributors()?
+        };
+
+        if contributors.is_empty() {
+            return Ok(match self.format {
+                Some(format) => export_contributors(&[], format),
+                None => "No commits found".to_string(),
+            });
+        }
+
+        // Sort by commit count, then name so pages are stable
+        contributors.sort_by(|a, b| {
+            b.commit_count
+                .cmp(&a.commit_count)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let total = contributors.len();
+
+        // Exports include every contributor unless a limit is given
+        let limit = match (self.limit, self.format) {
+            (Some(limit), _) => limit,
+            (None, Some(_)) => usize::MAX,
+            (None, None) => DEFAULT_CONTRIBUTORS_LIMIT,
+        };
+        let page: Vec<ContributorStats> = contributors
+            .into_iter()
+            .skip(self.offset)
+            .take(limit)
+            .collect();
+
+        if let Some(format) = self.format {
+            return Ok(export_contributors(&page, format));
+        }
 
         // Format output
         let mut output = BufferedOutput::new();
-        let period = self.since.as_deref().unwrap_or("all time");
-        output.add_line(format!("👥 Contributors ({period})"));
+        output.add_line(format!("👥 Contributors ({})", self.scope()?));
         output.add_line("=".repeat(50));
 
-        for (i, contributor) in sorted_contributors.iter().take(20).enumerate() {
-            let rank = match i {
+        for (i, contributor) in page.iter().enumerate() {
+            let rank = match self.offset + i {
                 0 => "🥇",
                 1 => "🥈",
                 2 => "🥉",
@@ -887,9 +1701,21 @@ impl ParallelContributorsCommand {
                 rank, contributor.name, contributor.commit_count
             ));
 
+            if self.fast {
+                output.add_line(format!("   📧 {}", contributor.email));
+            } else {
+                output.add_line(format!(
+                    "   📧 {} | 📅 {} to {}",
+                    contributor.email, contributor.first_commit, contributor.last_commit
+                ));
+            }
+        }
+
+        let shown = self.offset + page.len();
+        if shown < total {
             output.add_line(format!(
-                "   📧 {} | 📅 {} to {}",
-                contributor.email, contributor.first_commit, contributor.last_commit
+                "… {} more of {total} contributors (use --offset {shown} for the next page)",
+                total - shown
             ));
         }
 
@@ -897,18 +1723,67 @@ impl ParallelContributorsCommand {
     }
 }
 
-/// Command to analyze technical debt
-pub struct TechnicalDebtCommand;
+/// How the dependency part of the technical-debt report is gathered
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DependencyCheck {
+    /// Lockfile age that counts as stale; `git-x.debt.lockfileMonths` or 6 when unset
+    pub lockfile_months: Option<u32>,
+    /// Ask each ecosystem's tool for outdated dependencies
+    pub outdated: bool,
+}
 
-impl Default for TechnicalDebtCommand {
-    fn default() -> Self {
-        Self::new()
+impl DependencyCheck {
+    fn max_age_days(&self) -> i64 {
+        let months = self
+            .lockfile_months
+            .or_else(|| Config::get("debt.lockfileMonths").and_then(|v| v.parse().ok()))
+            .unwrap_or(DEFAULT_LOCKFILE_MONTHS);
+        i64::from(months) * 30
+    }
+
+    /// Report lines for every manifest found, empty when there are none
+    fn report(&self) -> (Vec<String>, bool) {
+        let Ok(manifests) = Dependencies::find(self.outdated) else {
+            return (Vec::new(), false);
+        };
+        Self::render(&manifests, self.max_age_days())
     }
+
+    fn render(manifests: &[DependencyManifest], max_age_days: i64) -> (Vec<String>, bool) {
+        if manifests.is_empty() {
+            return (Vec::new(), false);
+        }
+        let mut lines = vec![format!(
+            "🧩 Dependencies (lockfiles older than {} months are flagged):",
+            max_age_days / 30
+        )];
+        lines.extend(manifests.iter().map(|m| m.summary(max_age_days)));
+        let needs_attention = manifests.iter().any(|m| m.needs_attention(max_age_days));
+        (lines, needs_attention)
+    }
+}
+
+/// Command to analyze technical debt
+#[derive(Default)]
+pub struct TechnicalDebtCommand {
+    dependencies: DependencyCheck,
+    include_generated: bool,
 }
 
 impl TechnicalDebtCommand {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_dependency_check(mut self, dependencies: DependencyCheck) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Analyze generated and vendored files too
+    pub fn with_include_generated(mut self) -> Self {
+        self.include_generated = true;
+        self
     }
 
     fn analyze_file_churn(&self) -> Result<Vec<FileChurn>> {
@@ -932,8 +1807,9 @@ impl TechnicalDebtCommand {
             .into_iter()
             .map(|(file, changes)| FileChurn { file, changes })
             .collect();
+        exclude_generated_churn(self.include_generated, &mut churns)?;
 
-        churns.sort_by(|a, b| b.changes.cmp(&a.changes));
+        churns.sort_by_key(|c| std::cmp::Reverse(c.changes));
         churns.truncate(10); // Top 10 most changed files
 
         Ok(churns)
@@ -957,6 +1833,7 @@ impl TechnicalDebtCommand {
             }
         }
 
+        exclude_generated_files(self.include_generated, &mut large_files)?;
         large_files.sort_by(|a, b| b.size_mb.partial_cmp(&a.size_mb).unwrap());
         large_files.truncate(10);
 
@@ -970,6 +1847,9 @@ impl Command for TechnicalDebtCommand {
 
         output.add_line("🔧 Technical Debt Analysis".to_string());
         output.add_line("=".repeat(50));
+        if let Some(warning) = SparseCheckout::current_scan_warning() {
+            output.add_line(warning);
+        }
 
         // File churn analysis
         match self.analyze_file_churn() {
@@ -998,6 +1878,12 @@ impl Command for TechnicalDebtCommand {
             }
         }
 
+        let (dependency_lines, _) = self.dependencies.report();
+        if !dependency_lines.is_empty() {
+            output.add_line("".to_string());
+            output.add_lines(dependency_lines);
+        }
+
         Ok(output.content())
     }
 
@@ -1013,39 +1899,58 @@ impl Command for TechnicalDebtCommand {
 impl GitCommand for TechnicalDebtCommand {}
 
 /// Parallel version of TechnicalDebtCommand using multi-threading
-pub struct ParallelTechnicalDebtCommand;
-
-impl Default for ParallelTechnicalDebtCommand {
-    fn default() -> Self {
-        Self::new()
-    }
+#[derive(Default)]
+pub struct ParallelTechnicalDebtCommand {
+    dependencies: DependencyCheck,
+    include_generated: bool,
 }
 
 impl ParallelTechnicalDebtCommand {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_dependency_check(mut self, dependencies: DependencyCheck) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Analyze generated and vendored files too
+    pub fn with_include_generated(mut self) -> Self {
+        self.include_generated = true;
+        self
     }
 
     pub fn execute_parallel(&self) -> Result<String> {
         // Run multiple analysis types in parallel
-        let ((file_churn_result, large_files_result), old_files_result) = rayon::join(
-            || {
-                rayon::join(
-                    || self.analyze_file_churn_parallel(),
-                    || self.analyze_large_files_parallel(),
-                )
-            },
-            || self.analyze_old_files_parallel(),
-        );
+        let (((file_churn_result, large_files_result), old_files_result), dependencies) =
+            rayon::join(
+                || {
+                    rayon::join(
+                        || {
+                            rayon::join(
+                                || self.analyze_file_churn_parallel(),
+                                || self.analyze_large_files_parallel(),
+                            )
+                        },
+                        || self.analyze_old_files_parallel(),
+                    )
+                },
+                || self.dependencies.report(),
+            );
+        let (dependency_lines, stale_dependencies) = dependencies;
 
-        let file_churn = file_churn_result?;
-        let large_files = large_files_result?;
-        let old_files = old_files_result?;
+        let (file_churn, churn_excluded) = file_churn_result?;
+        let (large_files, large_excluded) = large_files_result?;
+        let (old_files, old_excluded) = old_files_result?;
 
         let mut output = BufferedOutput::new();
 
         output.add_line("🔧 Technical Debt Analysis".to_string());
         output.add_line("=".repeat(40));
+        if let Some(warning) = SparseCheckout::current_scan_warning() {
+            output.add_line(warning);
+        }
 
         // File churn analysis
         if !file_churn.is_empty() {
@@ -1071,14 +1976,31 @@ impl ParallelTechnicalDebtCommand {
             }
         }
 
-        if file_churn.is_empty() && large_files.is_empty() && old_files.is_empty() {
+        if !dependency_lines.is_empty() {
+            output.add_line(String::new());
+            output.add_lines(dependency_lines);
+        }
+
+        if churn_excluded + large_excluded + old_excluded > 0 {
+            output.add_line(
+                "\n🙈 Generated and vendored files left out (--include-generated to analyze them)"
+                    .to_string(),
+            );
+        }
+
+        if file_churn.is_empty()
+            && large_files.is_empty()
+            && old_files.is_empty()
+            && !stale_dependencies
+        {
             output.add_line("✅ No significant technical debt detected".to_string());
         }
 
         Ok(output.content())
     }
 
-    fn analyze_file_churn_parallel(&self) -> Result<Vec<FileChurn>> {
+    /// Files with significant churn, and how many generated or vendored ones were left out
+    fn analyze_file_churn_parallel(&self) -> Result<(Vec<FileChurn>, usize)> {
         use rayon::prelude::*;
         use std::collections::HashMap;
 
@@ -1121,13 +2043,14 @@ impl ParallelTechnicalDebtCommand {
             .map(|(file, changes)| FileChurn { file, changes })
             .collect();
 
-        churns.sort_by(|a, b| b.changes.cmp(&a.changes));
+        churns.sort_by_key(|c| std::cmp::Reverse(c.changes));
         churns.retain(|churn| churn.changes > 5); // Only show files with significant churn
+        let excluded = exclude_generated_churn(self.include_generated, &mut churns)?;
 
-        Ok(churns)
+        Ok((churns, excluded))
     }
 
-    fn analyze_large_files_parallel(&self) -> Result<Vec<LargeFile>> {
+    fn analyze_large_files_parallel(&self) -> Result<(Vec<LargeFile>, usize)> {
         use rayon::prelude::*;
 
         let output = GitOperations::run(&["ls-files"])?;
@@ -1154,16 +2077,19 @@ impl ParallelTechnicalDebtCommand {
             .collect();
 
         let mut sorted_files = large_files;
+        let excluded = exclude_generated_files(self.include_generated, &mut sorted_files)?;
         sorted_files.sort_by(|a, b| b.size_mb.partial_cmp(&a.size_mb).unwrap());
 
-        Ok(sorted_files)
+        Ok((sorted_files, excluded))
     }
 
-    fn analyze_old_files_parallel(&self) -> Result<Vec<String>> {
+    fn analyze_old_files_parallel(&self) -> Result<(Vec<String>, usize)> {
         use rayon::prelude::*;
 
         let output = GitOperations::run(&["ls-files"])?;
-        let files: Vec<&str> = output.lines().collect();
+        let mut files: Vec<&str> = output.lines().collect();
+        let generated = generated_among(self.include_generated, Path::new("."), &files)?;
+        files.retain(|file| !generated.contains(*file));
 
         // Get files not modified in last 6 months
         let old_files: Vec<String> = files
@@ -1184,7 +2110,7 @@ impl ParallelTechnicalDebtCommand {
             })
             .collect();
 
-        Ok(old_files)
+        Ok((old_files, generated.len()))
     }
 }
 
@@ -1192,6 +2118,7 @@ impl ParallelTechnicalDebtCommand {
 pub struct LargeFilesCommand {
     threshold_mb: Option<f64>,
     limit: Option<usize>,
+    include_generated: bool,
 }
 
 impl LargeFilesCommand {
@@ -1199,8 +2126,15 @@ impl LargeFilesCommand {
         Self {
             threshold_mb,
             limit,
+            include_generated: false,
         }
     }
+
+    /// List generated and vendored files too
+    pub fn with_include_generated(mut self) -> Self {
+        self.include_generated = true;
+        self
+    }
 }
 
 impl Command for LargeFilesCommand {
@@ -1223,22 +2157,11 @@ impl Command for LargeFilesCommand {
             }
         }
 
+        let excluded = exclude_generated_files(self.include_generated, &mut large_files)?;
         large_files.sort_by(|a, b| b.size_mb.partial_cmp(&a.size_mb).unwrap());
         large_files.truncate(limit);
 
-        if large_files.is_empty() {
-            return Ok(format!("No files larger than {threshold:.1}MB found"));
-        }
-
-        let mut result = format!("📦 Files larger than {threshold:.1}MB:\n");
-        result.push_str(&"=".repeat(40));
-        result.push('\n');
-
-        for file in large_files {
-            result.push_str(&format!("🗃️  {} ({:.2} MB)\n", file.path, file.size_mb));
-        }
-
-        Ok(result)
+        Ok(render_large_files(&large_files, threshold, excluded))
     }
 
     fn name(&self) -> &'static str {
@@ -1256,6 +2179,7 @@ impl GitCommand for LargeFilesCommand {}
 pub struct ParallelLargeFilesCommand {
     threshold_mb: Option<f64>,
     limit: Option<usize>,
+    include_generated: bool,
 }
 
 impl ParallelLargeFilesCommand {
@@ -1263,9 +2187,16 @@ impl ParallelLargeFilesCommand {
         Self {
             threshold_mb,
             limit,
+            include_generated: false,
         }
     }
 
+    /// List generated and vendored files too
+    pub fn with_include_generated(mut self) -> Self {
+        self.include_generated = true;
+        self
+    }
+
     pub fn execute_parallel(&self) -> Result<String> {
         use rayon::prelude::*;
         let threshold = self.threshold_mb.unwrap_or(1.0);
@@ -1296,56 +2227,171 @@ impl ParallelLargeFilesCommand {
 
         // Sort and limit results
         let mut sorted_files = large_files;
+        let excluded = exclude_generated_files(self.include_generated, &mut sorted_files)?;
         sorted_files.sort_by(|a, b| b.size_mb.partial_cmp(&a.size_mb).unwrap());
         sorted_files.truncate(limit);
 
-        if sorted_files.is_empty() {
-            return Ok(format!("No files larger than {threshold:.1}MB found"));
-        }
+        Ok(render_large_files(&sorted_files, threshold, excluded))
+    }
+}
 
-        let mut result = format!("📦 Files larger than {threshold:.1}MB:\n");
-        result.push_str(&"=".repeat(40));
-        result.push('\n');
+/// The large-files report, noting how many generated or vendored files were left out
+fn render_large_files(files: &[LargeFile], threshold: f64, excluded: usize) -> String {
+    // Files outside a sparse checkout aren't on disk to measure
+    let warning = SparseCheckout::current_scan_warning()
+        .map(|warning| format!("{warning}\n"))
+        .unwrap_or_default();
+    let note = if excluded > 0 {
+        format!(
+            "\n🙈 {excluded} generated or vendored file(s) left out (--include-generated to show them)"
+        )
+    } else {
+        String::new()
+    };
 
-        for file in sorted_files {
-            result.push_str(&format!("🗃️  {} ({:.2} MB)\n", file.path, file.size_mb));
-        }
+    if files.is_empty() {
+        return format!("{warning}No files larger than {threshold:.1}MB found{note}");
+    }
 
-        Ok(result)
+    let mut result = format!("{warning}📦 Files larger than {threshold:.1}MB:\n");
+    result.push_str(&"=".repeat(40));
+    result.push('\n');
+
+    for file in files {
+        result.push_str(&format!("🗃️  {} ({:.2} MB)\n", file.path, file.size_mb));
     }
+    result.push_str(note.trim_start());
+
+    result
 }
 
-/// Command to show commits since a certain time or reference
-pub struct SinceCommand {
-    reference: String,
+/// Generated and vendored files among `paths` (relative to `dir`), none when they're included
+fn generated_among(include: bool, dir: &Path, paths: &[&str]) -> Result<HashSet<String>> {
+    if include {
+        return Ok(HashSet::new());
+    }
+    GeneratedFiles::load().detect(dir, paths)
 }
 
-impl SinceCommand {
-    pub fn new(reference: String) -> Self {
-        Self { reference }
+/// Drop generated and vendored files from tracked files found in the current directory
+fn exclude_generated_files(include: bool, files: &mut Vec<LargeFile>) -> Result<usize> {
+    let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+    let generated = generated_among(include, Path::new("."), &paths)?;
+    files.retain(|file| !generated.contains(&file.path));
+    Ok(generated.len())
+}
+
+/// Drop generated and vendored files from churn, whose paths are relative to the root
+fn exclude_generated_churn(include: bool, churns: &mut Vec<FileChurn>) -> Result<usize> {
+    if include {
+        return Ok(0);
     }
+    let root = GitOperations::repo_root()?;
+    let paths: Vec<&str> = churns.iter().map(|churn| churn.file.as_str()).collect();
+    let generated = generated_among(include, Path::new(&root), &paths)?;
+    churns.retain(|churn| !generated.contains(&churn.file));
+    Ok(generated.len())
 }
 
-impl Command for SinceCommand {
-    fn execute(&self) -> Result<String> {
-        // First try as a git reference (commit hash, branch, tag)
-        let log_range = format!("{}..HEAD", self.reference);
-        if let Ok(output) = GitOperations::run(&["log", &log_range, "--pretty=format:- %h %s"]) {
-            if !output.trim().is_empty() {
-                return Ok(format!("🔍 Commits since {}:\n{}", self.reference, output));
-            } else {
-                return Ok(format!("✅ No new commits since {}", self.reference));
-            }
-        }
+/// Reference `since` falls back to when none is given
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SinceDefault {
+    /// The current branch's upstream, e.g. `origin/main`
+    Upstream(String),
+    /// The most recent tag reachable from HEAD
+    Tag(String),
+    /// When the repository was last fetched
+    LastFetch(chrono::DateTime<Utc>),
+}
 
-        // If that fails, try as a time specification
-        let output = GitOperations::run(&["log", "--oneline", "--since", &self.reference])?;
+impl SinceDefault {
+    /// Upstream, then latest tag, then last fetch
+    pub fn detect() -> Result<Self> {
+        if let Ok(upstream) = GitOperations::upstream_branch() {
+            return Ok(Self::Upstream(upstream));
+        }
+        if let Ok(tag) = GitOperations::run(&["describe", "--tags", "--abbrev=0"]) {
+            return Ok(Self::Tag(tag));
+        }
+        let fetch_head = GitOperations::run(&["rev-parse", "--git-path", "FETCH_HEAD"])?;
+        if let Ok(modified) = std::fs::metadata(fetch_head).and_then(|meta| meta.modified()) {
+            return Ok(Self::LastFetch(modified.into()));
+        }
+        Err(GitXError::GitCommand(
+            "No upstream, tag or previous fetch to compare against; pass a reference, e.g. 'git x since origin/main'"
+                .to_string(),
+        ))
+    }
 
-        if output.trim().is_empty() {
-            return Ok(format!("✅ No commits found since '{}'", self.reference));
+    /// What to hand to `git log`: a ref, or an ISO 8601 time for `--since`
+    pub fn spec(&self) -> String {
+        match self {
+            Self::Upstream(reference) | Self::Tag(reference) => reference.clone(),
+            Self::LastFetch(time) => time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
         }
+    }
 
-        let mut result = format!("📅 Commits since '{}':\n", self.reference);
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Upstream(reference) => format!("upstream '{reference}'"),
+            Self::Tag(tag) => format!("latest tag '{tag}'"),
+            Self::LastFetch(time) => format!(
+                "last fetch ({})",
+                time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            ),
+        }
+    }
+}
+
+/// Command to show commits since a certain time or reference
+pub struct SinceCommand {
+    /// `None` picks a [`SinceDefault`]
+    reference: Option<String>,
+}
+
+impl SinceCommand {
+    pub fn new(reference: String) -> Self {
+        Self {
+            reference: Some(reference),
+        }
+    }
+
+    /// Compare against the upstream, latest tag or last fetch, whichever exists first
+    pub fn with_default_reference() -> Self {
+        Self { reference: None }
+    }
+
+    fn commits_since(reference: &str) -> Result<String> {
+        // `last-tag` and `last-release` name a tag, so compare against it like any other
+        if matches!(reference, "last-tag" | "last-release") {
+            let tag = TimeSpec::parse(reference)?;
+            return Self::commits_since(tag.reference().unwrap_or(reference));
+        }
+
+        // First try as a git reference (commit hash, branch, tag)
+        let log_range = format!("{reference}..HEAD");
+        if let Ok(output) = GitOperations::run(&["log", &log_range, "--pretty=format:- %h %s"]) {
+            if !output.trim().is_empty() {
+                return Ok(format!("🔍 Commits since {reference}:\n{output}"));
+            } else {
+                return Ok(format!("✅ No new commits since {reference}"));
+            }
+        }
+
+        // If that fails, try as a time specification
+        let since = TimeSpec::parse(reference).map_err(|_| {
+            GitXError::Parse(format!(
+                "'{reference}' is neither a reference nor a time; pass a commit, branch or tag, \
+                 an age (2w, 3 months), a date (2025-07-01), 'last-tag' or 'last-release'"
+            ))
+        })?;
+        let output = GitOperations::run(&["log", "--oneline", "--since", &since.git_arg()])?;
+
+        if output.trim().is_empty() {
+            return Ok(format!("✅ No commits found since '{since}'"));
+        }
+
+        let mut result = format!("📅 Commits since '{since}':\n");
         result.push_str(&"=".repeat(50));
         result.push('\n');
 
@@ -1355,6 +2401,21 @@ impl Command for SinceCommand {
 
         Ok(result)
     }
+}
+
+impl Command for SinceCommand {
+    fn execute(&self) -> Result<String> {
+        if let Some(reference) = &self.reference {
+            return Self::commits_since(reference);
+        }
+
+        let default = SinceDefault::detect()?;
+        Ok(format!(
+            "💡 No reference given, using the {}\n{}",
+            default.describe(),
+            Self::commits_since(&default.spec())?
+        ))
+    }
 
     fn name(&self) -> &'static str {
         "since"
@@ -1369,16 +2430,31 @@ impl GitCommand for SinceCommand {}
 
 /// Command to analyze what changed between branches
 pub struct WhatCommand {
-    target: Option<String>,
+    target: Option<RefName>,
+    package: Option<Package>,
 }
 
 impl WhatCommand {
-    pub fn new(target: Option<String>) -> Self {
-        Self { target }
+    pub fn new(target: Option<RefName>) -> Self {
+        Self {
+            target,
+            package: None,
+        }
+    }
+
+    /// Only count commits and changes in this workspace package
+    pub fn with_package(mut self, package: Package) -> Self {
+        self.package = Some(package);
+        self
     }
 
-    fn get_default_target(&self) -> String {
-        "main".to_string()
+    fn get_default_target(&self) -> Result<String> {
+        GitOperations::default_branch().ok_or_else(|| {
+            GitXError::Other(
+                "No default branch found; pass the branch to compare against, e.g. 'git x what develop'"
+                    .to_string(),
+            )
+        })
     }
 
     fn format_branch_comparison(&self, current: &str, target: &str) -> String {
@@ -1408,21 +2484,38 @@ impl WhatCommand {
     }
 
     fn git_status_to_symbol(&self, status: &str) -> &'static str {
-        match status {
+        // Renames and copies carry a similarity score, e.g. `R087`
+        match status.get(..1).unwrap_or_default() {
             "A" => "➕",
             "M" => "🔄",
             "D" => "➖",
+            "R" => "🚚",
+            "C" => "📋",
+            "T" => "🔧",
             _ => "❓",
         }
     }
 
-    fn format_diff_line(&self, line: &str) -> Option<String> {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() >= 2 {
-            let symbol = self.git_status_to_symbol(parts[0]);
-            Some(format!(" {} {}", symbol, parts[1]))
-        } else {
-            None
+    /// Format one `git diff --name-status` line
+    ///
+    /// Fields are tab-separated so paths with spaces survive; renames and
+    /// copies list both paths and are shown as `old → new`.
+    pub fn format_diff_line(&self, line: &str) -> Option<String> {
+        let parts: Vec<&str> = line.split('\t').collect();
+        let symbol = self.git_status_to_symbol(parts.first()?);
+        match parts.as_slice() {
+            [status, from, to] => {
+                let similarity = status
+                    .get(1..)
+                    .and_then(|score| score.parse::<u32>().ok())
+                    .filter(|score| *score < 100);
+                Some(match similarity {
+                    Some(score) => format!(" {symbol} {from} → {to} ({score}%)"),
+                    None => format!(" {symbol} {from} → {to}"),
+                })
+            }
+            [_, path] => Some(format!(" {symbol} {path}")),
+            _ => None,
         }
     }
 }
@@ -1431,22 +2524,25 @@ impl Command for WhatCommand {
     fn execute(&self) -> Result<String> {
         let target_branch = self
             .target
-            .clone()
-            .unwrap_or_else(|| self.get_default_target());
+            .as_ref()
+            .map(|target| Ok(target.to_string()))
+            .unwrap_or_else(|| self.get_default_target())?;
 
-        // Get current branch name
-        let current_branch = GitOperations::current_branch()?;
+        let current_branch = GitOperations::require_branch("what")?;
 
         let mut output = Vec::new();
         output.push(self.format_branch_comparison(&current_branch, &target_branch));
+        let range = self.format_rev_list_range(&target_branch, &current_branch);
+        let mut pathspec = Vec::new();
+        if let Some(package) = &self.package {
+            output.push(format!("📦 Package: {}", package.label()));
+            pathspec.extend(["--", package.path.as_str()]);
+        }
 
         // Get ahead/behind commit counts
-        let rev_list_output = GitOperations::run(&[
-            "rev-list",
-            "--left-right",
-            "--count",
-            &self.format_rev_list_range(&target_branch, &current_branch),
-        ])?;
+        let mut args = vec!["rev-list", "--left-right", "--count", range.as_str()];
+        args.extend(&pathspec);
+        let rev_list_output = GitOperations::run(&args)?;
 
         let (ahead, behind) = self.parse_commit_counts(&rev_list_output);
         let (ahead_msg, behind_msg) = self.format_commit_counts(&ahead, &behind);
@@ -1454,11 +2550,9 @@ impl Command for WhatCommand {
         output.push(behind_msg);
 
         // Get diff summary
-        let diff_output = GitOperations::run(&[
-            "diff",
-            "--name-status",
-            &self.format_rev_list_range(&target_branch, &current_branch),
-        ])?;
+        let mut args = vec!["diff", "--name-status", "-M", "-C", range.as_str()];
+        args.extend(&pathspec);
+        let diff_output = GitOperations::run(&args)?;
 
         if !diff_output.trim().is_empty() {
             output.push("📝 Changes:".to_string());
@@ -1485,42 +2579,4125 @@ impl Command for WhatCommand {
 
 impl GitCommand for WhatCommand {}
 
-// Supporting data structures
-#[derive(Debug)]
-struct CommitStats {
-    total_commits: u32,
-    period: String,
+/// Command listing the workspace packages `--package` accepts
+pub struct PackagesCommand;
+
+impl Default for PackagesCommand {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-#[derive(Debug)]
-struct AuthorStats {
-    name: String,
-    commits: u32,
+impl PackagesCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// One aligned line per package: name, directory and ecosystem
+    pub fn render(packages: &[Package]) -> String {
+        if packages.is_empty() {
+            return "📦 No workspace packages found (Cargo and npm/pnpm/yarn workspaces and Go modules are recognised)".to_string();
+        }
+
+        let name_width = packages.iter().map(|p| p.name.len()).max().unwrap_or(0);
+        let path_width = packages.iter().map(|p| p.path.len()).max().unwrap_or(0);
+        let mut output = BufferedOutput::new();
+        output.add_line(format!("📦 {} workspace package(s):", packages.len()));
+        for package in packages {
+            output.add_line(format!(
+                "  {:<name_width$}  {:<path_width$}  {}",
+                package.name,
+                package.path,
+                package.ecosystem.label()
+            ));
+        }
+        output.add_line(
+            "💡 Scope summary, contributors and what to one with --package <name>".to_string(),
+        );
+        output.content()
+    }
 }
 
-#[derive(Debug)]
-struct FileStats {
-    total_files: usize,
-    _total_lines: usize,
+impl Command for PackagesCommand {
+    fn execute(&self) -> Result<String> {
+        Ok(Self::render(&Packages::detect()?))
+    }
+
+    fn name(&self) -> &'static str {
+        "packages"
+    }
+
+    fn description(&self) -> &'static str {
+        "List the workspace packages of a monorepo"
+    }
 }
 
-#[derive(Debug)]
-struct FileChurn {
-    file: String,
-    changes: u32,
+impl GitCommand for PackagesCommand {}
+
+/// One commit in a `branch-diff` list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideCommit {
+    /// `true` when the commit is only on the current branch
+    pub outgoing: bool,
+    pub hash: String,
+    pub subject: String,
+    pub author: String,
+    pub age: String,
 }
 
-#[derive(Debug)]
-struct LargeFile {
-    path: String,
-    size_mb: f64,
+impl SideCommit {
+    /// `git log --left-right` format read by [`SideCommit::parse`]
+    pub const FORMAT: &'static str = "%m%x1f%h%x1f%s%x1f%an%x1f%cr";
+
+    /// Parse one line of [`SideCommit::FORMAT`]; `<` is the target side, `>` ours
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\x1f');
+        let outgoing = match fields.next()? {
+            ">" => true,
+            "<" => false,
+            _ => return None,
+        };
+        Some(Self {
+            outgoing,
+            hash: fields.next()?.to_string(),
+            subject: fields.next()?.to_string(),
+            author: fields.next()?.to_string(),
+            age: fields.next()?.to_string(),
+        })
+    }
 }
 
-#[derive(Debug, Clone)]
-struct ContributorStats {
-    name: String,
-    email: String,
-    commit_count: usize,
-    first_commit: String,
-    last_commit: String,
+/// Command listing the commits that differ between the current branch and another
+pub struct BranchDiffCommand {
+    target: RefName,
+}
+
+impl BranchDiffCommand {
+    pub fn new(target: RefName) -> Self {
+        Self { target }
+    }
+
+    fn push_section(output: &mut BufferedOutput, heading: String, commits: &[&SideCommit]) {
+        output.add_line(heading);
+        if commits.is_empty() {
+            output.add_line("   (none)".to_string());
+        }
+        for commit in commits {
+            output.add_line(format!(
+                "   {} {} ({}, {})",
+                Format::bold(&commit.hash),
+                commit.subject,
+                commit.author,
+                commit.age
+            ));
+        }
+    }
+}
+
+impl Command for BranchDiffCommand {
+    fn execute(&self) -> Result<String> {
+        let current = GitOperations::require_branch("branch-diff")?;
+        let target = self.target.as_str();
+        if !GitOperations::commit_exists(target)? {
+            return Err(GitXError::GitCommand(format!("'{target}' does not exist")));
+        }
+
+        let log = GitOperations::run(&[
+            "log",
+            "--left-right",
+            &format!("--format={}", SideCommit::FORMAT),
+            &format!("{target}...HEAD"),
+        ])?;
+        let commits: Vec<SideCommit> = log.lines().filter_map(SideCommit::parse).collect();
+        let (outgoing, incoming): (Vec<&SideCommit>, Vec<&SideCommit>) =
+            commits.iter().partition(|commit| commit.outgoing);
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🔀 {} vs {}",
+            Format::bold(&current),
+            Format::bold(target)
+        ));
+        if commits.is_empty() {
+            output.add_line("✅ No differences, both point at the same history".to_string());
+            return Ok(output.content());
+        }
+
+        Self::push_section(
+            &mut output,
+            format!(
+                "📤 Outgoing: {} commit(s) only on {current}",
+                outgoing.len()
+            ),
+            &outgoing,
+        );
+        output.add_line(String::new());
+        Self::push_section(
+            &mut output,
+            format!("📥 Incoming: {} commit(s) only on {target}", incoming.len()),
+            &incoming,
+        );
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "branch-diff"
+    }
+
+    fn description(&self) -> &'static str {
+        "List commits only on the current branch and only on another"
+    }
+}
+
+impl GitCommand for BranchDiffCommand {}
+
+/// Command to roll up diff statistics by directory
+pub struct DiffStatCommand {
+    from: String,
+    to: Option<String>,
+    depth: usize,
+    format: Option<ExportFormat>,
+}
+
+impl DiffStatCommand {
+    pub fn new(from: String, to: Option<String>, depth: usize) -> Self {
+        Self {
+            from,
+            to,
+            depth: depth.max(1),
+            format: None,
+        }
+    }
+
+    /// Render as CSV/TSV/JSON instead of the human-readable table
+    pub fn with_format(mut self, format: Option<ExportFormat>) -> Self {
+        self.format = format;
+        self
+    }
+
+    fn range(&self) -> String {
+        format!("{}..{}", self.from, self.to.as_deref().unwrap_or("HEAD"))
+    }
+
+    fn collect(&self) -> Result<Vec<DirectoryStat>> {
+        let output = GitOperations::run(&["diff", "--numstat", "--no-renames", &self.range()])?;
+
+        let mut directories: BTreeMap<String, DirectoryStat> = BTreeMap::new();
+        for line in output.lines() {
+            let mut parts = line.splitn(3, '\t');
+            let (Some(added), Some(deleted), Some(path)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+
+            let directory = directory_at_depth(path, self.depth);
+            let stat = directories
+                .entry(directory.clone())
+                .or_insert_with(|| DirectoryStat {
+                    directory,
+                    ..Default::default()
+                });
+            stat.files += 1;
+            // Binary files report "-" for both counts
+            match (added.parse::<usize>(), deleted.parse::<usize>()) {
+                (Ok(added), Ok(deleted)) => {
+                    stat.insertions += added;
+                    stat.deletions += deleted;
+                }
+                _ => stat.binary_files += 1,
+            }
+        }
+
+        let mut stats: Vec<DirectoryStat> = directories.into_values().collect();
+        stats.sort_by_key(|s| std::cmp::Reverse(s.insertions + s.deletions));
+        Ok(stats)
+    }
+
+    fn export(stats: &[DirectoryStat], format: ExportFormat) -> String {
+        let mut exporter = Exporter::new(&[
+            "directory",
+            "files",
+            "insertions",
+            "deletions",
+            "binary_files",
+        ]);
+        for stat in stats {
+            exporter.add_row(vec![
+                stat.directory.as_str().into(),
+                stat.files.into(),
+                stat.insertions.into(),
+                stat.deletions.into(),
+                stat.binary_files.into(),
+            ]);
+        }
+        exporter.render(format)
+    }
+}
+
+impl Command for DiffStatCommand {
+    fn execute(&self) -> Result<String> {
+        let stats = self.collect()?;
+
+        if let Some(format) = self.format {
+            return Ok(Self::export(&stats, format));
+        }
+
+        let range = self.range();
+        if stats.is_empty() {
+            return Ok(format!("✅ No changes in {range}"));
+        }
+
+        let files: usize = stats.iter().map(|s| s.files).sum();
+        let insertions: usize = stats.iter().map(|s| s.insertions).sum();
+        let deletions: usize = stats.iter().map(|s| s.deletions).sum();
+
+        let mut table = TableFormatter::new(vec![
+            "Directory".to_string(),
+            "Files".to_string(),
+            "+".to_string(),
+            "-".to_string(),
+        ]);
+        for stat in &stats {
+            let files = if stat.binary_files > 0 {
+                format!("{} ({} binary)", stat.files, stat.binary_files)
+            } else {
+                stat.files.to_string()
+            };
+            table.add_row(vec![
+                format!("{}/", stat.directory.trim_end_matches('/')),
+                files,
+                stat.insertions.to_string(),
+                stat.deletions.to_string(),
+            ]);
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "📊 Diff stat {} ({files} files, +{insertions} -{deletions}, depth {})",
+            Format::bold(&range),
+            self.depth
+        ));
+        output.add_line(table.render().trim_end().to_string());
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "diff-stat"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show a directory-level roll-up of changes between two refs"
+    }
+}
+
+impl GitCommand for DiffStatCommand {}
+
+impl MultiFormat for DiffStatCommand {
+    fn supported_formats(&self) -> Vec<&'static str> {
+        let mut formats = vec!["text"];
+        formats.extend(ExportFormat::names());
+        formats
+    }
+
+    fn execute_with_format(&self, format: &str) -> Result<String> {
+        if format == "text" {
+            return DiffStatCommand::new(self.from.clone(), self.to.clone(), self.depth).execute();
+        }
+
+        let format = format.parse::<ExportFormat>()?;
+        Ok(Self::export(&self.collect()?, format))
+    }
+}
+
+/// Period covered by `ownership` when no `--since` is given
+pub const DEFAULT_OWNERSHIP_SINCE: &str = "6 months ago";
+
+/// Contributors named per directory when `--top` isn't given
+pub const DEFAULT_OWNERSHIP_TOP: usize = 3;
+
+/// Changes to one directory over a period, split by author
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryOwnership {
+    pub directory: String,
+    /// Files changed, counted once per commit
+    pub changes: usize,
+    /// Authors and their changes, most active first
+    pub owners: Vec<(String, usize)>,
+}
+
+impl DirectoryOwnership {
+    /// Log format whose records are an author line followed by the files it touched
+    pub const FORMAT: &'static str = "%x1e%aN";
+
+    /// Group `git log --name-only --format=FORMAT` output by directory and author
+    pub fn from_log(log: &str, depth: usize) -> Vec<Self> {
+        let mut directories: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        for record in log.split('\x1e') {
+            let mut lines = record.lines();
+            let Some(author) = lines.next().map(str::trim).filter(|a| !a.is_empty()) else {
+                continue;
+            };
+            for path in lines.map(str::trim).filter(|p| !p.is_empty()) {
+                *directories
+                    .entry(directory_at_depth(path, depth))
+                    .or_default()
+                    .entry(author.to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        let mut ownership: Vec<Self> = directories
+            .into_iter()
+            .map(|(directory, authors)| {
+                let mut owners: Vec<(String, usize)> = authors.into_iter().collect();
+                owners.sort_by_key(|(_, changes)| std::cmp::Reverse(*changes));
+                Self {
+                    directory,
+                    changes: owners.iter().map(|(_, changes)| changes).sum(),
+                    owners,
+                }
+            })
+            .collect();
+        ownership.sort_by_key(|o| std::cmp::Reverse(o.changes));
+        ownership
+    }
+
+    /// Share of this directory's changes, as a whole percentage
+    pub fn share(&self, changes: usize) -> usize {
+        if self.changes == 0 {
+            return 0;
+        }
+        ((changes as f64 / self.changes as f64) * 100.0).round() as usize
+    }
+
+    /// The `top` owners as `68% Alice, 20% Bob`, noting how many others there are
+    pub fn owners_summary(&self, top: usize) -> String {
+        let mut parts: Vec<String> = self
+            .owners
+            .iter()
+            .take(top)
+            .map(|(author, changes)| format!("{}% {author}", self.share(*changes)))
+            .collect();
+        let others = self.owners.len().saturating_sub(top);
+        if others > 0 {
+            parts.push(format!("+{others} more"));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Command that maps directories to the people who change them most
+pub struct OwnershipCommand {
+    since: Option<String>,
+    depth: usize,
+    top: usize,
+    format: Option<ExportFormat>,
+}
+
+impl Default for OwnershipCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OwnershipCommand {
+    pub fn new() -> Self {
+        Self {
+            since: None,
+            depth: 1,
+            top: DEFAULT_OWNERSHIP_TOP,
+            format: None,
+        }
+    }
+
+    /// Start of the period, in any format [`TimeSpec`] accepts
+    pub fn with_since(mut self, since: Option<String>) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Number of directory levels to group by
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// How many contributors to name per directory
+    pub fn with_top(mut self, top: usize) -> Self {
+        self.top = top.max(1);
+        self
+    }
+
+    /// Render as CSV/TSV/JSON instead of the human-readable table
+    pub fn with_format(mut self, format: Option<ExportFormat>) -> Self {
+        self.format = format;
+        self
+    }
+
+    fn since(&self) -> &str {
+        self.since.as_deref().unwrap_or(DEFAULT_OWNERSHIP_SINCE)
+    }
+
+    fn collect(&self) -> Result<Vec<DirectoryOwnership>> {
+        let log = GitOperations::run(&[
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "--no-merges",
+            "--name-only",
+            &format!("--format={}", DirectoryOwnership::FORMAT),
+            &format!("--since={}", TimeSpec::parse(self.since())?.git_arg()),
+        ])?;
+        Ok(DirectoryOwnership::from_log(&log, self.depth))
+    }
+
+    /// One row per directory and author
+    fn export(ownership: &[DirectoryOwnership], format: ExportFormat) -> String {
+        let mut exporter = Exporter::new(&["directory", "author", "changes", "percentage"]);
+        for directory in ownership {
+            for (author, changes) in &directory.owners {
+                exporter.add_row(vec![
+                    directory.directory.as_str().into(),
+                    author.as_str().into(),
+                    (*changes).into(),
+                    directory.share(*changes).into(),
+                ]);
+            }
+        }
+        exporter.render(format)
+    }
+}
+
+impl Command for OwnershipCommand {
+    fn execute(&self) -> Result<String> {
+        let ownership = self.collect()?;
+
+        if let Some(format) = self.format {
+            return Ok(Self::export(&ownership, format));
+        }
+
+        let since = self.since();
+        if ownership.is_empty() {
+            return Ok(format!("👥 No changes since {since}"));
+        }
+
+        let total: usize = ownership.iter().map(|o| o.changes).sum();
+        let labels: Vec<String> = ownership
+            .iter()
+            .map(|o| format!("{}/", o.directory.trim_end_matches('/')))
+            .collect();
+        let width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "👥 Directory ownership since {since} ({total} changes, depth {})",
+            self.depth
+        ));
+        for (directory, label) in ownership.iter().zip(&labels) {
+            let padding = " ".repeat(width - label.chars().count());
+            output.add_line(format!(
+                "   {}{padding} — {} — {} changes",
+                Format::bold(label),
+                directory.owners_summary(self.top),
+                directory.changes
+            ));
+        }
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "ownership"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show who changes each directory most over a period"
+    }
+}
+
+impl GitCommand for OwnershipCommand {}
+
+/// Command that runs the reporting commands and combines them into one document
+pub struct ExportReportCommand {
+    format: Option<ReportFormat>,
+    output: Option<String>,
+}
+
+impl Default for ExportReportCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExportReportCommand {
+    pub fn new() -> Self {
+        Self {
+            format: None,
+            output: None,
+        }
+    }
+
+    /// Document format; defaults to the output file's extension, else Markdown
+    pub fn with_format(mut self, format: Option<ReportFormat>) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Write the report to this file instead of returning it
+    pub fn with_output(mut self, output: Option<String>) -> Self {
+        self.output = output;
+        self
+    }
+
+    fn format(&self) -> ReportFormat {
+        self.format
+            .or_else(|| self.output.as_deref().and_then(ReportFormat::from_path))
+            .unwrap_or(ReportFormat::Markdown)
+    }
+
+    /// Run every section; one that fails is reported in place instead of aborting
+    pub fn build() -> Result<Report> {
+        use crate::commands::repository::HealthCommand;
+
+        let root = GitOperations::repo_root()?;
+        let repo_name = std::path::Path::new(&root)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(root.clone());
+        let head = GitOperations::run(&["rev-parse", "--short", "HEAD"]).unwrap_or_default();
+        let branch = GitOperations::current_branch().unwrap_or_default();
+
+        let mut report = Report::new(format!("Repository report: {repo_name}"));
+        report.add_intro(format!(
+            "Generated {} on {branch} at {head} by git-x {}.",
+            chrono::Local::now().format("%Y-%m-%d %H:%M"),
+            env!("CARGO_PKG_VERSION")
+        ));
+
+        let sections: [(&str, &dyn Command); 5] = [
+            ("Summary", &SummaryCommand::new(None)),
+            ("Health", &HealthCommand::new()),
+            ("Contributors", &ContributorsCommand::new(None)),
+            ("Technical debt", &TechnicalDebtCommand::new()),
+            ("Large files", &LargeFilesCommand::new(None, None)),
+        ];
+        for (title, command) in sections {
+            let body = command
+                .execute()
+                .unwrap_or_else(|e| format!("⚠️  Not available: {e}"));
+            report.add_section(title, &body);
+        }
+        Ok(report)
+    }
+}
+
+impl Command for ExportReportCommand {
+    fn execute(&self) -> Result<String> {
+        let report = Self::build()?;
+        let format = self.format();
+        let rendered = report.render(format);
+
+        let Some(path) = &self.output else {
+            return Ok(rendered.trim_end().to_string());
+        };
+        std::fs::write(path, rendered)?;
+        Ok(format!(
+            "📝 Report written to {path} ({} sections, {format})",
+            report.sections.len()
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "export-report"
+    }
+
+    fn description(&self) -> &'static str {
+        "Combine summary, health, contributors, technical debt and large files into one report"
+    }
+}
+
+impl GitCommand for ExportReportCommand {}
+
+/// Lines added and removed in one file of a commit; `None` for binary files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStat {
+    pub path: String,
+    pub additions: Option<i64>,
+    pub deletions: Option<i64>,
+}
+
+/// One commit as `log-json` exports it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedCommit {
+    pub hash: String,
+    pub parents: Vec<String>,
+    pub author: (String, String, String),
+    pub committer: (String, String, String),
+    pub subject: String,
+    pub body: String,
+    pub files: Vec<FileStat>,
+}
+
+impl LoggedCommit {
+    /// `git log` format read by [`LoggedCommit::parse`]; each commit starts with `\x1e`
+    pub const FORMAT: &'static str =
+        "--format=%x1e%H%x1f%P%x1f%an%x1f%ae%x1f%aI%x1f%cn%x1f%ce%x1f%cI%x1f%s%x1f%b%x1f";
+
+    /// One record of [`LoggedCommit::FORMAT`] output, followed by any `--numstat` lines
+    pub fn parse(record: &str) -> Option<Self> {
+        let fields: Vec<&str> = record.splitn(11, '\x1f').collect();
+        let [
+            hash,
+            parents,
+            an,
+            ae,
+            ad,
+            cn,
+            ce,
+            cd,
+            subject,
+            body,
+            numstat,
+        ] = fields[..]
+        else {
+            return None;
+        };
+        let person = |name: &str, email: &str, date: &str| {
+            (name.to_string(), email.to_string(), date.to_string())
+        };
+        let files = numstat
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                let additions = parts.next()?;
+                let deletions = parts.next()?;
+                Some(FileStat {
+                    path: parts.next()?.to_string(),
+                    additions: additions.parse().ok(),
+                    deletions: deletions.parse().ok(),
+                })
+            })
+            .collect();
+
+        Some(Self {
+            hash: hash.trim().to_string(),
+            parents: parents.split_whitespace().map(str::to_string).collect(),
+            author: person(an, ae, ad),
+            committer: person(cn, ce, cd),
+            subject: subject.to_string(),
+            body: body.trim().to_string(),
+            files,
+        })
+    }
+
+    /// The JSON object written for this commit, with the cursor resuming after it
+    pub fn to_value(&self, cursor: &str, files: bool) -> ExportValue {
+        let person = |(name, email, date): &(String, String, String)| {
+            ExportValue::record(vec![
+                ("name", ExportValue::from(name.as_str())),
+                ("email", ExportValue::from(email.as_str())),
+                ("date", ExportValue::from(date.as_str())),
+            ])
+        };
+        let count = |value: Option<i64>| value.map_or(ExportValue::Null, ExportValue::from);
+
+        let mut fields = vec![
+            ("hash", ExportValue::from(self.hash.as_str())),
+            (
+                "parents",
+                ExportValue::List(
+                    self.parents
+                        .iter()
+                        .map(|parent| ExportValue::from(parent.as_str()))
+                        .collect(),
+                ),
+            ),
+            ("author", person(&self.author)),
+            ("committer", person(&self.committer)),
+            ("subject", ExportValue::from(self.subject.as_str())),
+            ("body", ExportValue::from(self.body.as_str())),
+        ];
+        if files {
+            fields.push((
+                "files",
+                ExportValue::List(
+                    self.files
+                        .iter()
+                        .map(|file| {
+                            ExportValue::record(vec![
+                                ("path", ExportValue::from(file.path.as_str())),
+                                ("additions", count(file.additions)),
+                                ("deletions", count(file.deletions)),
+                                ("binary", ExportValue::from(file.additions.is_none())),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ));
+            let total = |stat: fn(&FileStat) -> Option<i64>| {
+                self.files.iter().filter_map(stat).sum::<i64>()
+            };
+            fields.push((
+                "stats",
+                ExportValue::record(vec![
+                    ("files", self.files.len().into()),
+                    ("additions", total(|file| file.additions).into()),
+                    ("deletions", total(|file| file.deletions).into()),
+                ]),
+            ));
+        }
+        fields.push(("cursor", ExportValue::from(cursor)));
+        ExportValue::record(fields)
+    }
+}
+
+/// Where an export stopped: how many commits were written from a pinned set of revisions
+///
+/// Rendered as `<offset>:<revision>,<revision>...`, with the range and dates resolved
+/// to commit hashes and unix times so new commits don't shift a resumed export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogCursor {
+    pub offset: usize,
+    pub revisions: Vec<String>,
+}
+
+impl LogCursor {
+    /// Pin `range` (default `HEAD`) and the date limits, as `git rev-parse` resolves them
+    pub fn resolve(range: Option<&str>, since: Option<&str>, until: Option<&str>) -> Result<Self> {
+        let mut args = vec!["rev-parse".to_string()];
+        args.extend(
+            range
+                .unwrap_or("HEAD")
+                .split_whitespace()
+                .map(str::to_string),
+        );
+        if let Some(since) = since {
+            args.push(format!("--since={}", TimeSpec::parse(since)?.git_arg()));
+        }
+        if let Some(until) = until {
+            args.push(format!("--until={}", TimeSpec::parse(until)?.git_arg()));
+        }
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let revisions: Vec<String> = GitOperations::run(&args)?
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let cursor = Self {
+            offset: 0,
+            revisions,
+        };
+        cursor.validate()?;
+        Ok(cursor)
+    }
+
+    /// The cursor `n` commits further on
+    pub fn advance(&self, n: usize) -> Self {
+        Self {
+            offset: self.offset + n,
+            revisions: self.revisions.clone(),
+        }
+    }
+
+    /// Only commit hashes, `^` exclusions and age limits, so a cursor can't inject options
+    fn validate(&self) -> Result<()> {
+        let is_hash = |text: &str| text.len() >= 7 && text.chars().all(|c| c.is_ascii_hexdigit());
+        let valid = |revision: &str| {
+            is_hash(revision.strip_prefix('^').unwrap_or(revision))
+                || revision
+                    .strip_prefix("--max-age=")
+                    .or_else(|| revision.strip_prefix("--min-age="))
+                    .is_some_and(|time| time.parse::<i64>().is_ok())
+        };
+        match self.revisions.iter().find(|revision| !valid(revision)) {
+            Some(revision) => Err(GitXError::Parse(format!(
+                "Cannot export from '{revision}': not a commit range"
+            ))),
+            None if !self.revisions.iter().any(|revision| is_hash(revision)) => Err(
+                GitXError::Parse("The range does not name any commit to export".to_string()),
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::str::FromStr for LogCursor {
+    type Err = GitXError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || GitXError::Parse(format!("Invalid cursor '{s}'"));
+        let (offset, revisions) = s.split_once(':').ok_or_else(invalid)?;
+        let cursor = Self {
+            offset: offset.parse().map_err(|_| invalid())?,
+            revisions: revisions.split(',').map(str::to_string).collect(),
+        };
+        cursor.validate().map_err(|_| invalid())?;
+        Ok(cursor)
+    }
+}
+
+impl std::fmt::Display for LogCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.offset, self.revisions.join(","))
+    }
+}
+
+/// Command that exports commits as newline-delimited JSON
+pub struct LogJsonCommand {
+    range: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+    limit: Option<usize>,
+    cursor: Option<LogCursor>,
+    files: bool,
+}
+
+impl LogJsonCommand {
+    pub fn new(range: Option<String>) -> Self {
+        Self {
+            range,
+            since: None,
+            until: None,
+            limit: None,
+            cursor: None,
+            files: true,
+        }
+    }
+
+    pub fn with_since(mut self, since: Option<String>) -> Self {
+        self.since = since;
+        self
+    }
+
+    pub fn with_until(mut self, until: Option<String>) -> Self {
+        self.until = until;
+        self
+    }
+
+    /// Stop after `limit` commits, reporting the cursor to continue from
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Resume an earlier export instead of resolving the range
+    pub fn with_cursor(mut self, cursor: LogCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Leave out the per-file line counts, which is much faster on large histories
+    pub fn without_files(mut self) -> Self {
+        self.files = false;
+        self
+    }
+}
+
+impl StreamingCommand for LogJsonCommand {
+    fn stream(&self, sink: &mut dyn OutputSink) -> Result<()> {
+        let start = match &self.cursor {
+            Some(cursor) => cursor.clone(),
+            None => LogCursor::resolve(
+                self.range.as_deref(),
+                self.since.as_deref(),
+                self.until.as_deref(),
+            )?,
+        };
+
+        let mut args = vec![
+            "-c".to_string(),
+            "core.quotePath=false".to_string(),
+            "log".to_string(),
+            LoggedCommit::FORMAT.to_string(),
+            format!("--skip={}", start.offset),
+        ];
+        if let Some(limit) = self.limit {
+            // One more than asked for tells whether anything is left
+            args.push(format!("--max-count={}", limit + 1));
+        }
+        if self.files {
+            args.extend(
+                ["--numstat", "--no-renames", "--diff-merges=first-parent"].map(String::from),
+            );
+        }
+        args.extend(start.revisions.iter().cloned());
+        args.push("--".to_string());
+
+        // Streamed from git, so huge histories never sit in memory
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let mut exported = 0;
+        let mut more = false;
+        GitOperations::run_streaming(&args, 0x1e, |record| {
+            let Some(commit) = LoggedCommit::parse(&String::from_utf8_lossy(record)) else {
+                return Ok(true);
+            };
+            if self.limit.is_some_and(|limit| exported == limit) {
+                more = true;
+                return Ok(false);
+            }
+            exported += 1;
+            let cursor = start.advance(exported).to_string();
+            sink.line(commit.to_value(&cursor, self.files).to_json());
+            Ok(true)
+        })?;
+
+        if more {
+            eprintln!(
+                "⏭️  More commits remain; continue with --cursor '{}'",
+                start.advance(exported)
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Command for LogJsonCommand {
+    fn execute(&self) -> Result<String> {
+        self.capture()
+    }
+
+    fn name(&self) -> &'static str {
+        "log-json"
+    }
+
+    fn description(&self) -> &'static str {
+        "Export commits as newline-delimited JSON"
+    }
+}
+
+impl GitCommand for LogJsonCommand {}
+
+/// A single hunk of a commit's diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffHunk {
+    pub file: String,
+    pub header: String,
+    pub lines: Vec<String>,
+}
+
+impl DiffHunk {
+    /// Split unified diff output into hunks, tracking the file each belongs to
+    pub fn parse_all(diff: &str) -> Vec<DiffHunk> {
+        let mut hunks = Vec::new();
+        let mut file = String::new();
+        let mut current: Option<DiffHunk> = None;
+
+        for line in diff.lines() {
+            if line.starts_with("diff --git ") {
+                hunks.extend(current.take());
+                file = line
+                    .rsplit_once(" b/")
+                    .map(|(_, path)| path.to_string())
+                    .unwrap_or_default();
+            } else if let Some(path) = line.strip_prefix("+++ b/") {
+                file = path.to_string();
+            } else if line.starts_with("@@") {
+                hunks.extend(current.take());
+                current = Some(DiffHunk {
+                    file: file.clone(),
+                    header: line.to_string(),
+                    lines: Vec::new(),
+                });
+            } else if let Some(hunk) = current.as_mut() {
+                hunk.lines.push(line.to_string());
+            }
+        }
+        hunks.extend(current);
+
+        hunks
+    }
+
+    /// One-line label used when picking hunks to flag
+    pub fn label(&self) -> String {
+        format!("{} {}", self.file, self.header)
+    }
+}
+
+/// Outcome of reviewing a single commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewVerdict {
+    Accepted,
+    Flagged,
+    Skipped,
+}
+
+impl ReviewVerdict {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ReviewVerdict::Accepted => "✅",
+            ReviewVerdict::Flagged => "🚩",
+            ReviewVerdict::Skipped => "⏭️",
+        }
+    }
+}
+
+/// Review state for one commit
+#[derive(Debug, Clone)]
+pub struct CommitReview {
+    pub hash: String,
+    pub subject: String,
+    pub verdict: ReviewVerdict,
+    pub flagged_hunks: Vec<DiffHunk>,
+    pub notes: Vec<String>,
+}
+
+impl CommitReview {
+    pub fn new(hash: &str, subject: &str) -> Self {
+        Self {
+            hash: hash.to_string(),
+            subject: subject.to_string(),
+            verdict: ReviewVerdict::Skipped,
+            flagged_hunks: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+}
+
+/// Collected results of a review session, rendered as Markdown
+#[derive(Debug, Clone)]
+pub struct ReviewSummary {
+    pub range: String,
+    pub total_commits: usize,
+    pub reviews: Vec<CommitReview>,
+}
+
+impl ReviewSummary {
+    pub fn new(range: &str, total_commits: usize) -> Self {
+        Self {
+            range: range.to_string(),
+            total_commits,
+            reviews: Vec::new(),
+        }
+    }
+
+    pub fn count(&self, verdict: ReviewVerdict) -> usize {
+        self.reviews.iter().filter(|r| r.verdict == verdict).count()
+    }
+
+    /// Render the summary file: counts first, then flagged hunks and notes per commit
+    pub fn render_markdown(&self) -> String {
+        let mut out = vec![
+            format!("# Review of {}", self.range),
+            String::new(),
+            format!(
+                "Reviewed {} of {} commits: {} accepted, {} flagged, {} skipped",
+                self.reviews.len(),
+                self.total_commits,
+                self.count(ReviewVerdict::Accepted),
+                self.count(ReviewVerdict::Flagged),
+                self.count(ReviewVerdict::Skipped),
+            ),
+        ];
+
+        for review in &self.reviews {
+            out.push(String::new());
+            out.push(format!(
+                "## {} {} {}",
+                review.verdict.symbol(),
+                short_sha(&review.hash),
+                review.subject
+            ));
+
+            for note in &review.notes {
+                out.push(format!("- {note}"));
+            }
+
+            for hunk in &review.flagged_hunks {
+                out.push(String::new());
+                out.push(format!("`{}`", hunk.file));
+                out.push(String::new());
+                out.push("```diff".to_string());
+                out.push(hunk.header.clone());
+                out.extend(hunk.lines.iter().cloned());
+                out.push("```".to_string());
+            }
+        }
+
+        out.join("\n") + "\n"
+    }
+}
+
+/// Command to review a range commit-by-commit in the terminal
+pub struct ReviewCommand {
+    target: String,
+    base: Option<String>,
+    output: Option<String>,
+}
+
+impl ReviewCommand {
+    pub fn new(target: String, base: Option<String>, output: Option<String>) -> Self {
+        Self {
+            target,
+            base,
+            output,
+        }
+    }
+
+    /// Resolve the target to a range; a bare branch is reviewed against the base
+    pub fn range(&self) -> String {
+        if self.target.contains("..") {
+            self.target.clone()
+        } else {
+            format!(
+                "{}..{}",
+                self.base.as_deref().unwrap_or("main"),
+                self.target
+            )
+        }
+    }
+
+    fn output_path(&self) -> String {
+        self.output
+            .clone()
+            .unwrap_or_else(|| "git-x-review.md".to_string())
+    }
+
+    /// Commits in the range, oldest first, as (hash, subject)
+    fn commits(range: &str) -> Result<Vec<(String, String)>> {
+        let output = GitOperations::run(&[
+            "log",
+            "--reverse",
+            "--no-merges",
+            "--format=%H%x09%s",
+            range,
+        ])?;
+        Ok(output
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(hash, subject)| (hash.to_string(), subject.to_string()))
+            .collect())
+    }
+
+    /// Let the reviewer act on one commit; the flag is set when they finish early
+    fn review_commit(
+        hash: &str,
+        subject: &str,
+        position: usize,
+        total: usize,
+    ) -> Result<(CommitReview, bool)> {
+        use crate::core::interactive::Interactive;
+
+        println!("\n🔍 Commit {position}/{total}");
+        DiffRenderer::configured().show(&["show", "--stat", "--patch", hash])?;
+
+        let hunks = DiffHunk::parse_all(&GitOperations::run(&["show", "--format=", hash])?);
+        let mut review = CommitReview::new(hash, subject);
+        let actions = [
+            "✅ Accept",
+            "🚩 Flag hunks",
+            "📝 Add note",
+            "⏭️ Skip",
+            "🛑 Finish review",
+        ];
+
+        loop {
+            let action = Interactive::fuzzy_select(&actions, "Review action", Some(0))?;
+            match action {
+                "✅ Accept" => {
+                    review.verdict = ReviewVerdict::Accepted;
+                    return Ok((review, false));
+                }
+                "🚩 Flag hunks" => {
+                    let labels: Vec<String> = hunks.iter().map(DiffHunk::label).collect();
+                    if !labels.is_empty() {
+                        let selected = Interactive::multi_select(&labels, "Hunks to flag")?;
+                        review
+                            .flagged_hunks
+                            .extend(selected.into_iter().map(|i| hunks[i].clone()));
+                    }
+                    review.verdict = ReviewVerdict::Flagged;
+                    if Interactive::confirm("Add a note?", false)? {
+                        review
+                            .notes
+                            .push(Interactive::text_input("Note", None, None)?);
+                    }
+                    return Ok((review, false));
+                }
+                "📝 Add note" => {
+                    review
+                        .notes
+                        .push(Interactive::text_input("Note", None, None)?);
+                }
+                "⏭️ Skip" => return Ok((review, false)),
+                _ => return Ok((review, true)),
+            }
+        }
+    }
+}
+
+impl Command for ReviewCommand {
+    fn execute(&self) -> Result<String> {
+        use crate::GitXError;
+        use crate::core::interactive::Interactive;
+
+        let range = self.range();
+        let commits = Self::commits(&range)?;
+        if commits.is_empty() {
+            return Ok(format!("✅ No commits to review in {range}"));
+        }
+
+        if !Interactive::is_interactive() {
+            return Err(GitXError::GitCommand(
+                "Review needs an interactive terminal".to_string(),
+            ));
+        }
+
+        let mut summary = ReviewSummary::new(&range, commits.len());
+        for (i, (hash, subject)) in commits.iter().enumerate() {
+            let (review, finished) = Self::review_commit(hash, subject, i + 1, commits.len())?;
+            // Keep notes taken on the commit the reviewer stopped at
+            if !finished || !review.notes.is_empty() {
+                summary.reviews.push(review);
+            }
+            if finished {
+                break;
+            }
+        }
+
+        let path = self.output_path();
+        std::fs::write(&path, summary.render_markdown())?;
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "📋 Reviewed {} of {} commits in {}: {} accepted, {} flagged",
+            summary.reviews.len(),
+            summary.total_commits,
+            Format::bold(&range),
+            summary.count(ReviewVerdict::Accepted),
+            summary.count(ReviewVerdict::Flagged),
+        ));
+        output.add_line(format!("📝 Summary written to {path}"));
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "review"
+    }
+
+    fn description(&self) -> &'static str {
+        "Review a commit range commit-by-commit in the terminal"
+    }
+}
+
+impl GitCommand for ReviewCommand {}
+
+/// Review-size guidelines a branch's diff is scored against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReviewLimits {
+    pub max_files: usize,
+    pub max_lines: usize,
+    /// Packages, or top-level directories in a repository without packages
+    pub max_packages: usize,
+    /// Changed test lines per changed code line
+    pub min_test_ratio: f64,
+}
+
+impl Default for ReviewLimits {
+    fn default() -> Self {
+        Self {
+            max_files: 20,
+            max_lines: 400,
+            max_packages: 3,
+            min_test_ratio: 0.3,
+        }
+    }
+}
+
+impl ReviewLimits {
+    /// Limits from `git-x.reviewSize.*`, falling back to the defaults
+    pub fn load() -> Self {
+        let defaults = Self::default();
+        let get = |key: &str| Config::get(&format!("reviewSize.{key}"));
+        Self {
+            max_files: get("maxFiles")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(defaults.max_files),
+            max_lines: get("maxLines")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(defaults.max_lines),
+            max_packages: get("maxPackages")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(defaults.max_packages),
+            min_test_ratio: get("minTestRatio")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(defaults.min_test_ratio),
+        }
+    }
+}
+
+/// Whether a path holds tests: a test directory, or a test file by naming convention
+pub fn is_test_file(path: &str) -> bool {
+    const TEST_DIRS: &[&str] = &["test", "tests", "__tests__", "spec", "specs", "testdata"];
+    let mut parts: Vec<&str> = path.split('/').collect();
+    let file_name = parts.pop().unwrap_or(path);
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    parts.iter().any(|dir| TEST_DIRS.contains(dir))
+        || stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_spec")
+        || stem.ends_with("Test")
+        || stem.ends_with("Tests")
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+}
+
+fn is_docs_file(path: &str) -> bool {
+    [".md", ".mdx", ".rst", ".adoc", ".txt"]
+        .iter()
+        .any(|extension| path.to_lowercase().ends_with(extension))
+}
+
+/// Lines changed in one file, from `--numstat -z`; binary files count as no lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub path: String,
+    pub added: usize,
+    pub deleted: usize,
+}
+
+impl ChangedFile {
+    pub fn lines(&self) -> usize {
+        self.added + self.deleted
+    }
+
+    /// Parse `git diff --numstat -z` output, where a rename is `added\tdeleted\t\0old\0new\0`
+    pub fn parse_numstat(output: &str) -> Vec<Self> {
+        let mut files = Vec::new();
+        let mut fields = output.split('\0');
+        while let Some(entry) = fields.next() {
+            let mut columns = entry.trim_start_matches('\n').splitn(3, '\t');
+            let (Some(added), Some(deleted), Some(path)) =
+                (columns.next(), columns.next(), columns.next())
+            else {
+                continue;
+            };
+            let path = if path.is_empty() {
+                // Renamed: skip the old path, keep the new one
+                fields.next();
+                fields.next().unwrap_or_default()
+            } else {
+                path
+            };
+            files.push(Self {
+                path: path.to_string(),
+                added: added.parse().unwrap_or(0),
+                deleted: deleted.parse().unwrap_or(0),
+            });
+        }
+        files
+    }
+}
+
+/// Changes to one package or directory, for splitting a branch by area
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewArea {
+    pub name: String,
+    pub files: usize,
+    pub lines: usize,
+}
+
+/// A run of consecutive commits that would make a reviewable PR on its own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSplit {
+    pub first: String,
+    pub last: String,
+    pub commits: usize,
+    pub lines: usize,
+}
+
+/// Command to score the current branch's diff against review-size guidelines
+pub struct ReviewSizeCommand {
+    target: Option<String>,
+    limits: ReviewLimits,
+    json: bool,
+}
+
+impl ReviewSizeCommand {
+    pub fn new(target: Option<String>) -> Self {
+        Self {
+            target,
+            limits: ReviewLimits::load(),
+            json: false,
+        }
+    }
+
+    pub fn with_limits(mut self, limits: ReviewLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Print the score as JSON, for bots
+    pub fn with_json(mut self) -> Self {
+        self.json = true;
+        self
+    }
+
+    /// `S` up to a quarter of the line limit, `M` up to half, `L` up to the limit, else `XL`
+    pub fn size_label(lines: usize, max_lines: usize) -> &'static str {
+        match lines {
+            lines if lines * 4 <= max_lines => "S",
+            lines if lines * 2 <= max_lines => "M",
+            lines if lines <= max_lines => "L",
+            _ => "XL",
+        }
+    }
+
+    /// The package a path is in, or its top-level directory without one; each level of
+    /// `depth` adds the next directory below that
+    pub fn area(path: &str, packages: &[Package], depth: usize) -> String {
+        let package = packages
+            .iter()
+            .filter(|package| path.starts_with(&format!("{}/", package.path)))
+            .max_by_key(|package| package.path.len());
+        let (mut parts, rest, depth) = match package {
+            Some(package) => (
+                vec![package.path.as_str()],
+                &path[package.path.len() + 1..],
+                depth,
+            ),
+            None => (Vec::new(), path, depth + 1),
+        };
+        let dirs: Vec<&str> = rest.split('/').collect();
+        parts.extend(dirs[..dirs.len() - 1].iter().take(depth));
+        if parts.is_empty() {
+            ".".to_string()
+        } else {
+            parts.join("/")
+        }
+    }
+
+    /// Changes grouped by area, largest first; a single area is broken down one
+    /// directory deeper so the branch can still be split
+    pub fn areas(files: &[ChangedFile], packages: &[Package]) -> Vec<ReviewArea> {
+        let group = |depth: usize| {
+            let mut areas: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+            for file in files {
+                let entry = areas
+                    .entry(Self::area(&file.path, packages, depth))
+                    .or_default();
+                entry.0 += 1;
+                entry.1 += file.lines();
+            }
+            areas
+        };
+        let mut areas = group(0);
+        if areas.len() == 1 {
+            areas = group(1);
+        }
+        let mut areas: Vec<ReviewArea> = areas
+            .into_iter()
+            .map(|(name, (files, lines))| ReviewArea { name, files, lines })
+            .collect();
+        areas.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.name.cmp(&b.name)));
+        areas
+    }
+
+    /// Cut the commits, oldest first with their changed lines, into runs that stay
+    /// within `max_lines`; a commit over the limit makes a run of its own
+    pub fn split_commits(commits: &[(String, usize)], max_lines: usize) -> Vec<CommitSplit> {
+        let mut splits: Vec<CommitSplit> = Vec::new();
+        for (commit, lines) in commits {
+            match splits.last_mut() {
+                Some(split) if split.lines + lines <= max_lines => {
+                    split.last = commit.clone();
+                    split.commits += 1;
+                    split.lines += lines;
+                }
+                _ => splits.push(CommitSplit {
+                    first: commit.clone(),
+                    last: commit.clone(),
+                    commits: 1,
+                    lines: *lines,
+                }),
+            }
+        }
+        splits
+    }
+
+    fn target(&self) -> Result<String> {
+        match &self.target {
+            Some(target) => Ok(target.clone()),
+            None => GitOperations::default_branch().ok_or_else(|| {
+                GitXError::GitCommand("Couldn't tell the default branch; pass --target".to_string())
+            }),
+        }
+    }
+
+    /// Changed lines of each commit since `base`, oldest first, leaving out `excluded` files
+    fn commit_lines(base: &str, excluded: &HashSet<String>) -> Result<Vec<(String, usize)>> {
+        let commits = GitOperations::run(&[
+            "rev-list",
+            "--reverse",
+            "--no-merges",
+            &format!("{base}..HEAD"),
+        ])?;
+        commits
+            .lines()
+            .map(|commit| {
+                let numstat =
+                    GitOperations::run(&["show", "--numstat", "-z", "-M", "--format=", commit])?;
+                let lines = ChangedFile::parse_numstat(&numstat)
+                    .iter()
+                    .filter(|file| !excluded.contains(&file.path))
+                    .map(ChangedFile::lines)
+                    .sum();
+                Ok((commit.to_string(), lines))
+            })
+            .collect()
+    }
+}
+
+impl Command for ReviewSizeCommand {
+    fn execute(&self) -> Result<String> {
+        let target = self.target()?;
+        let base = GitOperations::run(&["merge-base", "HEAD", &target]).map_err(|_| {
+            GitXError::GitCommand(format!("No common history between HEAD and '{target}'"))
+        })?;
+        let branch = GitOperations::current_branch().unwrap_or_else(|_| "HEAD".to_string());
+        let root = GitOperations::repo_root()?;
+
+        let numstat = GitOperations::run(&["diff", "--numstat", "-z", "-M", &base, "HEAD"])?;
+        let changed = ChangedFile::parse_numstat(&numstat);
+        let paths: Vec<&str> = changed.iter().map(|file| file.path.as_str()).collect();
+        let excluded = GeneratedFiles::load().detect(Path::new(&root), &paths)?;
+        let files: Vec<ChangedFile> = changed
+            .iter()
+            .filter(|file| !excluded.contains(&file.path))
+            .cloned()
+            .collect();
+
+        let limits = self.limits;
+        let added: usize = files.iter().map(|file| file.added).sum();
+        let deleted: usize = files.iter().map(|file| file.deleted).sum();
+        let lines = added + deleted;
+        let test_lines: usize = files
+            .iter()
+            .filter(|file| is_test_file(&file.path))
+            .map(ChangedFile::lines)
+            .sum();
+        // Documentation needs no tests
+        let code_lines: usize = files
+            .iter()
+            .filter(|file| !is_test_file(&file.path) && !is_docs_file(&file.path))
+            .map(ChangedFile::lines)
+            .sum();
+        let test_ratio = (code_lines > 0).then(|| test_lines as f64 / code_lines as f64);
+
+        let packages = Packages::detect().unwrap_or_default();
+        let (area_kind, area_kinds) = if packages.is_empty() {
+            ("directory", "Directories")
+        } else {
+            ("package", "Packages")
+        };
+        let touched: BTreeSet<String> = files
+            .iter()
+            .map(|file| Self::area(&file.path, &packages, 0))
+            .collect();
+
+        let over_files = files.len() > limits.max_files;
+        let over_lines = lines > limits.max_lines;
+        let over_packages = touched.len() > limits.max_packages;
+        let too_large = over_files || over_lines || over_packages;
+        let few_tests = test_ratio.is_some_and(|ratio| ratio < limits.min_test_ratio);
+        let size = Self::size_label(lines, limits.max_lines);
+
+        let areas = Self::areas(&files, &packages);
+        let by_area = if too_large && areas.len() > 1 {
+            areas
+        } else {
+            Vec::new()
+        };
+        let by_commit = if too_large {
+            let splits =
+                Self::split_commits(&Self::commit_lines(&base, &excluded)?, limits.max_lines);
+            if splits.len() > 1 { splits } else { Vec::new() }
+        } else {
+            Vec::new()
+        };
+
+        if self.json {
+            let area = |area: &ReviewArea| {
+                ExportValue::record(vec![
+                    ("name", ExportValue::from(area.name.as_str())),
+                    ("files", area.files.into()),
+                    ("lines", area.lines.into()),
+                ])
+            };
+            return Ok(ExportValue::record(vec![
+                ("branch", ExportValue::from(branch)),
+                ("target", ExportValue::from(target)),
+                ("base", ExportValue::from(base)),
+                ("size", ExportValue::from(size)),
+                ("too_large", too_large.into()),
+                ("files", files.len().into()),
+                ("lines", lines.into()),
+                ("added", added.into()),
+                ("deleted", deleted.into()),
+                ("areas", touched.len().into()),
+                ("area_kind", ExportValue::from(area_kind)),
+                (
+                    "test_ratio",
+                    test_ratio.map_or(ExportValue::Null, |ratio| {
+                        ExportValue::from((ratio * 100.0).round() / 100.0)
+                    }),
+                ),
+                ("generated_files", excluded.len().into()),
+                (
+                    "limits",
+                    ExportValue::record(vec![
+                        ("max_files", limits.max_files.into()),
+                        ("max_lines", limits.max_lines.into()),
+                        ("max_packages", limits.max_packages.into()),
+                        ("min_test_ratio", limits.min_test_ratio.into()),
+                    ]),
+                ),
+                (
+                    "split_by_area",
+                    ExportValue::List(by_area.iter().map(area).collect()),
+                ),
+                (
+                    "split_by_commit",
+                    ExportValue::List(
+                        by_commit
+                            .iter()
+                            .map(|split| {
+                                ExportValue::record(vec![
+                                    ("first", ExportValue::from(split.first.as_str())),
+                                    ("last", ExportValue::from(split.last.as_str())),
+                                    ("commits", split.commits.into()),
+                                    ("lines", split.lines.into()),
+                                ])
+                            })
+                            .collect(),
+                    ),
+                ),
+            ])
+            .to_json());
+        }
+
+        if files.is_empty() {
+            return Ok(format!(
+                "✅ Nothing to review: '{branch}' changes no files against {target}"
+            ));
+        }
+
+        let row = |label: &str, value: String, over: bool| {
+            let flag = if over { "  ⚠️" } else { "" };
+            format!("   {:<13}{value}{flag}", format!("{label}:"))
+        };
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "📏 Review size of '{branch}' against {target}: {}",
+            Format::bold(size)
+        ));
+        output.add_line(row(
+            "Files",
+            format!("{} (limit {})", thousands(files.len()), limits.max_files),
+            over_files,
+        ));
+        output.add_line(row(
+            "Lines",
+            format!(
+                "{} (+{} -{}, limit {})",
+                thousands(lines),
+                thousands(added),
+                thousands(deleted),
+                limits.max_lines
+            ),
+            over_lines,
+        ));
+        output.add_line(row(
+            area_kinds,
+            format!("{} (limit {})", touched.len(), limits.max_packages),
+            over_packages,
+        ));
+        output.add_line(match test_ratio {
+            Some(ratio) => row(
+                "Tests",
+                format!(
+                    "{:.0}% of the code lines changed (at least {:.0}%)",
+                    ratio * 100.0,
+                    limits.min_test_ratio * 100.0
+                ),
+                few_tests,
+            ),
+            None => row("Tests", "no code changed".to_string(), false),
+        });
+        if !excluded.is_empty() {
+            output.add_line(format!(
+                "🙈 {} generated or vendored file(s) not counted",
+                excluded.len()
+            ));
+        }
+
+        output.add_line(String::new());
+        output.add_line(if too_large {
+            "⚠️  Too large to review comfortably; consider splitting it".to_string()
+        } else {
+            "✅ Within the review-size guidelines".to_string()
+        });
+        if !by_area.is_empty() {
+            output.add_line(format!("✂️  Split by {area_kind}:"));
+            let width = by_area
+                .iter()
+                .map(|area| area.name.len())
+                .max()
+                .unwrap_or(0);
+            for area in &by_area {
+                output.add_line(format!(
+                    "   {:width$}  {} file(s), {} lines",
+                    area.name,
+                    area.files,
+                    thousands(area.lines)
+                ));
+            }
+        }
+        if !by_commit.is_empty() {
+            output.add_line("✂️  Split by commit:".to_string());
+            for (i, split) in by_commit.iter().enumerate() {
+                let short = |commit: &str| short_sha(commit).to_string();
+                let range = if split.commits == 1 {
+                    short(&split.first)
+                } else {
+                    format!("{}^..{}", short(&split.first), short(&split.last))
+                };
+                output.add_line(format!(
+                    "   {}. {range}  {} commit(s), {} lines",
+                    i + 1,
+                    split.commits,
+                    thousands(split.lines)
+                ));
+            }
+        }
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "review-size"
+    }
+
+    fn description(&self) -> &'static str {
+        "Score the current branch's diff against review-size guidelines"
+    }
+}
+
+impl GitCommand for ReviewSizeCommand {}
+
+/// A file that no longer exists, with the commit that deleted it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedFile {
+    pub path: String,
+    pub commit: String,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+}
+
+impl DeletedFile {
+    /// Parse `git log --diff-filter=D --name-only` output in the format used by
+    /// `GoneFilesCommand`, keeping only the most recent deletion of each path
+    pub fn parse_log(output: &str) -> Vec<DeletedFile> {
+        let mut seen = std::collections::HashSet::new();
+        let mut files = Vec::new();
+
+        for record in output.split('\x1e') {
+            let mut lines = record.lines();
+            let Some(header) = lines.next() else {
+                continue;
+            };
+            let fields: Vec<&str> = header.splitn(4, '\t').collect();
+            let [commit, author, date, subject] = fields.as_slice() else {
+                continue;
+            };
+
+            for path in lines.map(str::trim).filter(|line| !line.is_empty()) {
+                if seen.insert(path.to_string()) {
+                    files.push(DeletedFile {
+                        path: path.to_string(),
+                        commit: commit.to_string(),
+                        author: author.to_string(),
+                        date: date.to_string(),
+                        subject: subject.to_string(),
+                    });
+                }
+            }
+        }
+
+        files
+    }
+
+    pub fn short_hash(&self) -> &str {
+        short_sha(&self.commit)
+    }
+}
+
+/// Command to find deleted files and optionally bring them back
+pub struct GoneFilesCommand {
+    query: Option<String>,
+    restore: bool,
+    limit: usize,
+}
+
+impl GoneFilesCommand {
+    pub fn new(query: Option<String>, restore: bool, limit: usize) -> Self {
+        Self {
+            query,
+            restore,
+            limit,
+        }
+    }
+
+    /// Deleted files that haven't been re-added, newest deletion first
+    fn deleted_files(root: &str) -> Result<Vec<DeletedFile>> {
+        let output = GitOperations::run(&[
+            "-C",
+            root,
+            "log",
+            "--diff-filter=D",
+            "--name-only",
+            "--date=short",
+            "--format=%x1e%H%x09%an%x09%ad%x09%s",
+        ])?;
+        Ok(DeletedFile::parse_log(&output)
+            .into_iter()
+            .filter(|file| !std::path::Path::new(root).join(&file.path).exists())
+            .collect())
+    }
+
+    /// Files matching the query, best match first
+    fn matching(&self, files: Vec<DeletedFile>) -> Vec<DeletedFile> {
+        let Some(query) = &self.query else {
+            return files;
+        };
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        crate::core::interactive::Interactive::fuzzy_find(&paths, query, None)
+            .into_iter()
+            .map(|(index, _)| files[index].clone())
+            .collect()
+    }
+
+    /// Pick the file to restore: an exact or unique match, or ask
+    fn pick_for_restore<'a>(&self, matches: &'a [DeletedFile]) -> Result<&'a DeletedFile> {
+        use crate::GitXError;
+        use crate::core::interactive::Interactive;
+
+        let query = self.query.as_deref().ok_or_else(|| {
+            GitXError::Other(
+                "Name the file to restore, e.g. 'git x gone-files <path> --restore'".to_string(),
+            )
+        })?;
+
+        if let Some(exact) = matches.iter().find(|file| file.path == query) {
+            return Ok(exact);
+        }
+        if let [only] = matches {
+            return Ok(only);
+        }
+
+        let candidates: Vec<String> = matches
+            .iter()
+            .take(self.limit)
+            .map(|file| file.path.clone())
+            .collect();
+        if Interactive::is_interactive() {
+            let path = Interactive::fuzzy_select(&candidates, "File to restore", Some(0))?;
+            return matches
+                .iter()
+                .find(|file| file.path == path)
+                .ok_or_else(|| GitXError::Other(format!("'{path}' is not a deleted file")));
+        }
+
+        Err(GitXError::Other(format!(
+            "{} deleted files match '{query}', be more specific: {}",
+            matches.len(),
+            candidates.join(", ")
+        )))
+    }
+
+    fn restore_file(root: &str, file: &DeletedFile) -> Result<String> {
+        // The parent of the deleting commit is the last one that had the file
+        GitOperations::run(&[
+            "-C",
+            root,
+            "restore",
+            &format!("--source={}^", file.commit),
+            "--",
+            &file.path,
+        ])?;
+        Ok(format!(
+            "♻️  Restored {} from {}^ (deleted in {} by {} on {})",
+            Format::bold(&file.path),
+            file.short_hash(),
+            file.short_hash(),
+            file.author,
+            file.date
+        ))
+    }
+}
+
+impl Command for GoneFilesCommand {
+    fn execute(&self) -> Result<String> {
+        let root = GitOperations::repo_root()?;
+        let matches = self.matching(Self::deleted_files(&root)?);
+
+        if matches.is_empty() {
+            return Ok(match &self.query {
+                Some(query) => format!("🔍 No deleted files match '{query}'"),
+                None => "✅ No deleted files in history".to_string(),
+            });
+        }
+
+        if self.restore {
+            let file = self.pick_for_restore(&matches)?;
+            return Self::restore_file(&root, file);
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(match &self.query {
+            Some(query) => format!("🗑️  Deleted files matching '{query}' ({}):", matches.len()),
+            None => format!("🗑️  Deleted files ({}):", matches.len()),
+        });
+        for file in matches.iter().take(self.limit) {
+            output.add_line(format!(" • {}", Format::bold(&file.path)));
+            output.add_line(format!(
+                "   deleted in {} by {} on {}: {}",
+                file.short_hash(),
+                file.author,
+                file.date,
+                file.subject
+            ));
+        }
+        if matches.len() > self.limit {
+            output.add_line(format!("   ...and {} more", matches.len() - self.limit));
+        }
+        output.add_line("💡 Bring one back with: git x gone-files <path> --restore".to_string());
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "gone-files"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find when files were deleted and restore them"
+    }
+}
+
+impl GitCommand for GoneFilesCommand {}
+
+/// How a commit reached a branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Landing {
+    /// Brought in by this merge commit
+    Merge(String),
+    /// Committed, fast-forwarded or rebased straight onto the branch
+    Direct,
+}
+
+impl Landing {
+    /// Where `commit` landed, from `git rev-list --first-parent --parents <commit>..<branch>`
+    /// and `git rev-list --ancestry-path <commit>..<branch>`
+    ///
+    /// The oldest first-parent commit that descends from `commit` is where it joined the
+    /// branch: if its first parent is `commit`, it was already on the branch.
+    pub fn find(commit: &str, mainline: &str, ancestry: &str) -> Self {
+        let ancestry: HashSet<&str> = ancestry.lines().map(str::trim).collect();
+        let landing = mainline
+            .lines()
+            .rev()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .find(|hashes| hashes.first().is_some_and(|hash| ancestry.contains(hash)));
+        match landing.as_deref() {
+            Some([merge, first_parent, ..]) if *first_parent != commit => {
+                Landing::Merge(merge.to_string())
+            }
+            _ => Landing::Direct,
+        }
+    }
+}
+
+/// Command to find the merge that brought a commit into a branch
+pub struct FindMergeCommand {
+    commit: String,
+    into: Option<String>,
+    pr: bool,
+}
+
+impl FindMergeCommand {
+    pub fn new(commit: String) -> Self {
+        Self {
+            commit,
+            into: None,
+            pr: false,
+        }
+    }
+
+    /// Branch to look in (default: the default branch)
+    pub fn with_into(mut self, into: Option<String>) -> Self {
+        self.into = into;
+        self
+    }
+
+    /// Also look up the pull request through the GitHub CLI
+    pub fn with_pr(mut self) -> Self {
+        self.pr = true;
+        self
+    }
+
+    /// Hash, subject, author and date lines for a commit
+    fn describe(commit: &str) -> Result<(String, String, String, String)> {
+        let line = GitOperations::run(&[
+            "log",
+            "-1",
+            "--date=format:%Y-%m-%d %H:%M",
+            "--format=%h%x1f%s%x1f%an <%ae>%x1f%ad (%ar)",
+            commit,
+        ])?;
+        let mut fields = line.splitn(4, '\x1f').map(str::to_string);
+        let mut next = || fields.next().unwrap_or_default();
+        Ok((next(), next(), next(), next()))
+    }
+
+    /// The merged pull request containing `commit`, or why it couldn't be looked up
+    fn pull_request(commit: &str) -> std::result::Result<Option<(i64, String, String)>, String> {
+        Network::require("the pull request lookup").map_err(|e| e.to_string())?;
+        let output = std::process::Command::new("gh")
+            .args([
+                "pr",
+                "list",
+                "--state",
+                "merged",
+                "--search",
+                commit,
+                "--limit",
+                "1",
+                "--json",
+                "number,title,url",
+            ])
+            .output()
+            .map_err(|_| "the GitHub CLI (gh) is not installed".to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .next()
+                .unwrap_or("gh pr list failed")
+                .to_string());
+        }
+        let prs = ExportValue::parse_json(&String::from_utf8_lossy(&output.stdout))
+            .map_err(|e| e.to_string())?;
+        Ok(prs.items().first().and_then(|pr| {
+            let ExportValue::Int(number) = pr.get("number")? else {
+                return None;
+            };
+            let title = pr.get("title").and_then(ExportValue::as_str).unwrap_or("");
+            let url = pr.get("url").and_then(ExportValue::as_str).unwrap_or("");
+            Some((*number, title.to_string(), url.to_string()))
+        }))
+    }
+}
+
+impl Command for FindMergeCommand {
+    fn execute(&self) -> Result<String> {
+        let commit = GitOperations::run(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", self.commit),
+        ])
+        .map_err(|_| GitXError::GitCommand(format!("'{}' is not a commit", self.commit)))?;
+        let target = match &self.into {
+            Some(into) => into.clone(),
+            None => GitOperations::default_branch().ok_or_else(|| {
+                GitXError::Other("No default branch found; pass --into <branch>".to_string())
+            })?,
+        };
+        if !GitOperations::commit_exists(&target)? {
+            return Err(GitXError::GitCommand(format!("'{target}' does not exist")));
+        }
+
+        let (short, subject, author, date) = Self::describe(&commit)?;
+        if GitOperations::run(&["merge-base", "--is-ancestor", &commit, &target]).is_err() {
+            return Err(GitXError::Other(format!(
+                "{short} is not on {target}; see where it is with 'git branch -a --contains {short}'"
+            )));
+        }
+
+        let range = format!("{commit}..{target}");
+        let mainline = GitOperations::run(&["rev-list", "--first-parent", "--parents", &range])?;
+        let ancestry = GitOperations::run(&["rev-list", "--ancestry-path", &range])?;
+        let landing = Landing::find(&commit, &mainline, &ancestry);
+
+        let mut output = BufferedOutput::new();
+        let looked_up = match &landing {
+            Landing::Merge(merge) => {
+                let (merge_short, merge_subject, merge_author, merge_date) = Self::describe(merge)?;
+                output.add_line(format!(
+                    "🔀 {} reached {} in merge {}",
+                    Format::bold(&short),
+                    Format::bold(&target),
+                    Format::bold(&merge_short)
+                ));
+                output.add_line(format!("   Commit: {short} {subject}"));
+                output.add_line(format!("   Merge:  {merge_short} {merge_subject}"));
+                output.add_line(format!("   Date:   {merge_date}"));
+                output.add_line(format!("   Author: {merge_author}"));
+                merge.clone()
+            }
+            Landing::Direct => {
+                output.add_line(format!(
+                    "➡️  {} was committed directly on {} (no merge)",
+                    Format::bold(&short),
+                    Format::bold(&target)
+                ));
+                output.add_line(format!("   Commit: {short} {subject}"));
+                output.add_line(format!("   Date:   {date}"));
+                output.add_line(format!("   Author: {author}"));
+                commit.clone()
+            }
+        };
+
+        if self.pr {
+            output.add_line(match Self::pull_request(&looked_up) {
+                Ok(Some((number, title, url))) => format!("   PR:     #{number} {title} {url}"),
+                Ok(None) => "   PR:     none found".to_string(),
+                Err(reason) => format!("   PR:     ⚠️  couldn't look it up: {reason}"),
+            });
+        }
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "find-merge"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find the merge that brought a commit into a branch"
+    }
+}
+
+impl GitCommand for FindMergeCommand {}
+
+/// Repository size at which the file system monitor starts to pay off
+pub const FSMONITOR_MIN_FILES: usize = 10_000;
+
+/// Repository size at which a split index starts to pay off
+pub const SPLIT_INDEX_MIN_FILES: usize = 50_000;
+
+/// Pack count above which repacking is recommended
+pub const MAX_PACKS: usize = 50;
+
+/// Performance-related facts about a repository, gathered by `PerfCommand`
+#[derive(Debug, Clone, Default)]
+pub struct PerfFacts {
+    pub commit_graph: bool,
+    /// Packs were written after the commit-graph, so it misses recent history
+    pub commit_graph_stale: bool,
+    pub write_commit_graph_on_fetch: Option<bool>,
+    pub untracked_cache: Option<bool>,
+    pub fsmonitor: Option<String>,
+    /// Whether git's built-in fsmonitor daemon works on this platform
+    pub fsmonitor_supported: bool,
+    pub split_index: Option<bool>,
+    pub tracked_files: usize,
+    pub packs: usize,
+    pub loose_objects: usize,
+}
+
+/// Something `--apply` can do to act on a recommendation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PerfFix {
+    /// Set a key in the repository's git config
+    Config {
+        key: &'static str,
+        value: &'static str,
+    },
+    /// Run a git command
+    Run(Vec<&'static str>),
+}
+
+impl PerfFix {
+    pub fn describe(&self) -> String {
+        match self {
+            PerfFix::Config { key, value } => format!("git config {key} {value}"),
+            PerfFix::Run(args) => format!("git {}", args.join(" ")),
+        }
+    }
+
+    fn apply(&self) -> Result<()> {
+        match self {
+            PerfFix::Config { key, value } => GitOperations::run(&["config", key, value]),
+            PerfFix::Run(args) => GitOperations::run(args),
+        }
+        .map(|_| ())
+    }
+}
+
+/// Result of one performance check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerfCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+    /// Why the fix helps, shown with the recommendation
+    pub advice: Option<String>,
+    pub fix: Option<PerfFix>,
+}
+
+impl PerfCheck {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            ok: true,
+            detail: detail.into(),
+            advice: None,
+            fix: None,
+        }
+    }
+
+    fn recommend(
+        name: &'static str,
+        detail: impl Into<String>,
+        advice: impl Into<String>,
+        fix: Option<PerfFix>,
+    ) -> Self {
+        Self {
+            name,
+            ok: false,
+            detail: detail.into(),
+            advice: Some(advice.into()),
+            fix,
+        }
+    }
+}
+
+/// Command that inspects git performance features and recommends settings
+pub struct PerfCommand {
+    apply: bool,
+}
+
+impl PerfCommand {
+    pub fn new(apply: bool) -> Self {
+        Self { apply }
+    }
+
+    /// Collect performance facts for the current repository
+    pub fn facts() -> Result<PerfFacts> {
+        let objects = std::path::PathBuf::from(GitOperations::run(&[
+            "rev-parse",
+            "--path-format=absolute",
+            "--git-path",
+            "objects",
+        ])?);
+        let info = objects.join("info");
+        let graph = [
+            info.join("commit-graph"),
+            info.join("commit-graphs/commit-graph-chain"),
+        ]
+        .into_iter()
+        .find(|path| path.exists());
+
+        let pack_times: Vec<std::time::SystemTime> = std::fs::read_dir(objects.join("pack"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "pack"))
+                    .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let graph_time = graph
+            .as_ref()
+            .and_then(|path| std::fs::metadata(path).ok()?.modified().ok());
+        let commit_graph_stale = match graph_time {
+            Some(written) => pack_times.iter().any(|packed| *packed > written),
+            None => false,
+        };
+
+        let counts = GitOperations::run(&["count-objects", "-v"])?;
+        let count = |key: &str| {
+            counts
+                .lines()
+                .find_map(|line| line.strip_prefix(key)?.trim().parse::<usize>().ok())
+                .unwrap_or(0)
+        };
+
+        let config_bool = |key: &str| {
+            GitOperations::run(&["config", "--type=bool", "--get", key])
+                .ok()
+                .map(|value| value.trim() == "true")
+        };
+
+        Ok(PerfFacts {
+            commit_graph: graph.is_some(),
+            commit_graph_stale,
+            write_commit_graph_on_fetch: config_bool("fetch.writeCommitGraph"),
+            untracked_cache: config_bool("core.untrackedCache"),
+            fsmonitor: GitOperations::run(&["config", "--get", "core.fsmonitor"])
+                .ok()
+                .filter(|value| !value.is_empty()),
+            fsmonitor_supported: cfg!(any(target_os = "macos", target_os = "windows")),
+            split_index: config_bool("core.splitIndex"),
+            tracked_files: GitOperations::run(&["ls-files"])?.lines().count(),
+            packs: count("packs:"),
+            loose_objects: count("count:"),
+        })
+    }
+
+    /// Turn facts into checks with recommendations, in display order
+    pub fn evaluate(facts: &PerfFacts) -> Vec<PerfCheck> {
+        let mut checks = Vec::new();
+        let write_graph = PerfFix::Run(vec!["commit-graph", "write", "--reachable"]);
+
+        checks.push(if !facts.commit_graph {
+            PerfCheck::recommend(
+                "Commit-graph",
+                "missing",
+                "speeds up log, merge-base and ahead/behind counts",
+                Some(write_graph),
+            )
+        } else if facts.commit_graph_stale {
+            PerfCheck::recommend(
+                "Commit-graph",
+                "stale (objects packed since it was written)",
+                "recent history isn't covered",
+                Some(write_graph),
+            )
+        } else {
+            PerfCheck::ok("Commit-graph", "present and up to date")
+        });
+
+        checks.push(match facts.write_commit_graph_on_fetch {
+            Some(true) => PerfCheck::ok("Commit-graph on fetch", "enabled"),
+            _ => PerfCheck::recommend(
+                "Commit-graph on fetch",
+                "disabled",
+                "keeps the commit-graph current without manual rewrites",
+                Some(PerfFix::Config {
+                    key: "fetch.writeCommitGraph",
+                    value: "true",
+                }),
+            ),
+        });
+
+        checks.push(match facts.untracked_cache {
+            Some(true) => PerfCheck::ok("Untracked cache", "enabled"),
+            _ => PerfCheck::recommend(
+                "Untracked cache",
+                "disabled",
+                "makes git status skip unchanged directories",
+                Some(PerfFix::Config {
+                    key: "core.untrackedCache",
+                    value: "true",
+                }),
+            ),
+        });
+
+        let large = facts.tracked_files >= FSMONITOR_MIN_FILES;
+        checks.push(match &facts.fsmonitor {
+            Some(value) if value != "false" => {
+                PerfCheck::ok("File system monitor", format!("enabled ({value})"))
+            }
+            _ if !large => PerfCheck::ok(
+                "File system monitor",
+                format!("not needed for {} files", facts.tracked_files),
+            ),
+            _ if !facts.fsmonitor_supported => PerfCheck::recommend(
+                "File system monitor",
+                "disabled",
+                "git's built-in daemon isn't available on this platform; consider a Watchman hook",
+                None,
+            ),
+            _ => PerfCheck::recommend(
+                "File system monitor",
+                "disabled",
+                format!(
+                    "{} tracked files; avoids scanning the work tree on every status",
+                    facts.tracked_files
+                ),
+                Some(PerfFix::Config {
+                    key: "core.fsmonitor",
+                    value: "true",
+                }),
+            ),
+        });
+
+        checks.push(match facts.split_index {
+            Some(true) => PerfCheck::ok("Split index", "enabled"),
+            _ if facts.tracked_files < SPLIT_INDEX_MIN_FILES => {
+                PerfCheck::ok("Split index", "not needed for this index size")
+            }
+            _ => PerfCheck::recommend(
+                "Split index",
+                "disabled",
+                "avoids rewriting the whole index on every change",
+                Some(PerfFix::Config {
+                    key: "core.splitIndex",
+                    value: "true",
+                }),
+            ),
+        });
+
+        checks.push(if facts.packs > MAX_PACKS {
+            PerfCheck::recommend(
+                "Packs",
+                format!("{} packs, {} loose objects", facts.packs, facts.loose_objects),
+                "object lookups slow down as packs pile up; run 'git maintenance run --task=incremental-repack' or 'git gc'",
+                None,
+            )
+        } else {
+            PerfCheck::ok(
+                "Packs",
+                format!("{} packs, {} loose objects", facts.packs, facts.loose_objects),
+            )
+        });
+
+        checks
+    }
+}
+
+impl Command for PerfCommand {
+    fn execute(&self) -> Result<String> {
+        let checks = Self::evaluate(&Self::facts()?);
+
+        let mut output = BufferedOutput::new();
+        output.add_line("⚡ Repository Performance".to_string());
+        output.add_line("=".repeat(30));
+        for check in &checks {
+            let icon = if check.ok { "✅" } else { "⚠️ " };
+            output.add_line(format!("{icon} {}: {}", check.name, check.detail));
+            if let Some(advice) = &check.advice {
+                output.add_line(format!("   💡 {advice}"));
+            }
+        }
+
+        let fixes: Vec<&PerfFix> = checks
+            .iter()
+            .filter_map(|check| check.fix.as_ref())
+            .collect();
+        if fixes.is_empty() {
+            output.add_line(String::new());
+            output.add_line("🎉 Nothing to tune".to_string());
+            return Ok(output.content());
+        }
+
+        output.add_line(String::new());
+        if self.apply {
+            for fix in fixes {
+                match fix.apply() {
+                    Ok(()) => output.add_line(format!("🔧 Applied: {}", fix.describe())),
+                    Err(e) => output.add_line(format!("❌ Failed: {} ({e})", fix.describe())),
+                }
+            }
+        } else {
+            output.add_line("🔧 Recommended changes:".to_string());
+            for fix in fixes {
+                output.add_line(format!("   {}", fix.describe()));
+            }
+            output.add_line("💡 Run 'git x perf --apply' to apply them".to_string());
+        }
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "perf"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check git performance features and recommend settings"
+    }
+}
+
+impl GitCommand for PerfCommand {}
+
+/// Period covered by `heatmap` when no `--since` is given
+pub const DEFAULT_HEATMAP_SINCE: &str = "1 year ago";
+
+/// Cell characters from no activity to the busiest days
+const HEATMAP_LEVELS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+/// Commit counts per day, laid out like a contribution calendar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityHeatmap {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub counts: BTreeMap<NaiveDate, u32>,
+}
+
+impl ActivityHeatmap {
+    /// Count `YYYY-MM-DD` dates between `start` and `end`, one line per commit
+    pub fn from_dates(dates: &str, start: NaiveDate, end: NaiveDate) -> Self {
+        use rayon::prelude::*;
+
+        // Same fold/reduce split as the parallel contributors command
+        let lines: Vec<&str> = dates.lines().collect();
+        let counts = lines
+            .par_iter()
+            .filter_map(|line| NaiveDate::parse_from_str(line.trim(), "%Y-%m-%d").ok())
+            .filter(|date| (start..=end).contains(date))
+            .fold(BTreeMap::new, |mut acc: BTreeMap<NaiveDate, u32>, date| {
+                *acc.entry(date).or_insert(0) += 1;
+                acc
+            })
+            .reduce(BTreeMap::new, |mut acc, map| {
+                for (date, count) in map {
+                    *acc.entry(date).or_insert(0) += count;
+                }
+                acc
+            });
+
+        Self { start, end, counts }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// Busiest day, the earliest one on ties
+    pub fn busiest(&self) -> Option<(NaiveDate, u32)> {
+        self.counts
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(date, count)| (*date, *count))
+    }
+
+    /// Longest run of consecutive days with at least one commit
+    pub fn longest_streak(&self) -> u32 {
+        let mut longest = 0;
+        let mut current = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for date in self.counts.keys() {
+            current = match previous {
+                Some(day) if day.succ_opt() == Some(*date) => current + 1,
+                _ => 1,
+            };
+            longest = longest.max(current);
+            previous = Some(*date);
+        }
+        longest
+    }
+
+    /// Cell for a day, scaled against the busiest day
+    pub fn level(count: u32, max: u32) -> char {
+        if count == 0 || max == 0 {
+            return HEATMAP_LEVELS[0];
+        }
+        let scaled = (count as f64 / max as f64 * 4.0).ceil() as usize;
+        HEATMAP_LEVELS[scaled.clamp(1, 4)]
+    }
+
+    /// Month header, one row per weekday (Monday first) and a column per week
+    pub fn render(&self) -> String {
+        use chrono::{Datelike, Duration};
+
+        let first_monday =
+            self.start - Duration::days(self.start.weekday().num_days_from_monday() as i64);
+        let weeks = ((self.end - first_monday).num_days() / 7 + 1) as usize;
+        let max = self.counts.values().copied().max().unwrap_or(0);
+        let label_width = 4;
+
+        let mut header = vec![' '; label_width + weeks];
+        let mut last_month = None;
+        for week in 0..weeks {
+            let monday = first_monday + Duration::weeks(week as i64);
+            if last_month != Some(monday.month()) {
+                last_month = Some(monday.month());
+                let name = monday.format("%b").to_string();
+                let column = label_width + week;
+                if header.len() < column + name.len() {
+                    header.resize(column + name.len(), ' ');
+                }
+                // Skip labels that would run into the previous one
+                if header[column..column + name.len()]
+                    .iter()
+                    .all(|c| *c == ' ')
+                    && (column == label_width || header[column - 1] == ' ')
+                {
+                    header.splice(column..column + name.len(), name.chars());
+                }
+            }
+        }
+
+        let mut lines = vec![
+            header
+                .into_iter()
+                .collect::<String>()
+                .trim_end()
+                .to_string(),
+        ];
+        for (day, label) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+            let mut row = format!("{label:<width$}", width = label_width);
+            for week in 0..weeks {
+                let date = first_monday + Duration::days((week * 7 + day) as i64);
+                row.push(if date < self.start || date > self.end {
+                    ' '
+                } else {
+                    Self::level(self.counts.get(&date).copied().unwrap_or(0), max)
+                });
+            }
+            lines.push(row.trim_end().to_string());
+        }
+        lines.push(format!(
+            "{}Less {} More",
+            " ".repeat(label_width),
+            HEATMAP_LEVELS
+                .iter()
+                .map(char::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+        lines.join("\n")
+    }
+}
+
+/// Command that renders a calendar heatmap of commit activity
+pub struct HeatmapCommand {
+    since: Option<String>,
+    author: Option<String>,
+    path: Option<String>,
+    branch: Option<String>,
+    all_branches: bool,
+}
+
+impl Default for HeatmapCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeatmapCommand {
+    pub fn new() -> Self {
+        Self {
+            since: None,
+            author: None,
+            path: None,
+            branch: None,
+            all_branches: false,
+        }
+    }
+
+    /// Start of the period, in any format [`TimeSpec`] accepts
+    pub fn with_since(mut self, since: Option<String>) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Only count commits whose author matches
+    pub fn with_author(mut self, author: Option<String>) -> Self {
+        self.author = author;
+        self
+    }
+
+    /// Only count commits touching this path
+    pub fn with_path(mut self, path: Option<String>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Count commits reachable from this branch instead of HEAD
+    pub fn with_branch(mut self, branch: Option<String>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Count commits on every branch
+    pub fn with_all_branches(mut self) -> Self {
+        self.all_branches = true;
+        self
+    }
+}
+
+impl Command for HeatmapCommand {
+    fn execute(&self) -> Result<String> {
+        if let Some(branch) = &self.branch
+            && (branch.starts_with('-')
+                || GitOperations::run(&["rev-parse", "--verify", "--quiet", branch]).is_err())
+        {
+            return Err(GitXError::GitCommand(format!("Unknown branch '{branch}'")));
+        }
+        let since = TimeSpec::parse(self.since.as_deref().unwrap_or(DEFAULT_HEATMAP_SINCE))?;
+        let start = chrono::DateTime::from_timestamp(since.timestamp(), 0)
+            .map(|time| time.with_timezone(&chrono::Local).date_naive())
+            .ok_or_else(|| GitXError::Parse(format!("--since '{since}' is out of range")))?;
+        let end = chrono::Local::now().date_naive();
+
+        let since_arg = format!("--since={}", since.git_arg());
+        let mut args = vec!["log", "--format=%ad", "--date=short", since_arg.as_str()];
+        let author_arg = self
+            .author
+            .as_ref()
+            .map(|author| format!("--author={author}"));
+        if let Some(author) = &author_arg {
+            args.push(author);
+        }
+        if self.all_branches {
+            args.push("--all");
+        } else if let Some(branch) = &self.branch {
+            args.push(branch);
+        }
+        if let Some(path) = &self.path {
+            args.extend(["--", path.as_str()]);
+        }
+
+        let heatmap = ActivityHeatmap::from_dates(&GitOperations::run(&args)?, start, end);
+
+        let mut scope = Vec::new();
+        if let Some(author) = &self.author {
+            scope.push(format!("by {author}"));
+        }
+        if let Some(path) = &self.path {
+            scope.push(format!("in {path}"));
+        }
+        scope.push(if self.all_branches {
+            "on all branches".to_string()
+        } else {
+            format!("on {}", self.branch.as_deref().unwrap_or("HEAD"))
+        });
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🗓️  Commit activity {} since {since}",
+            scope.join(" ")
+        ));
+        output.add_line(String::new());
+        output.add_line(heatmap.render());
+        output.add_line(String::new());
+        match heatmap.busiest() {
+            Some((day, count)) => output.add_line(format!(
+                "📊 {} commit(s) from {start} to {end}, busiest day {day} ({count}), longest streak {} day(s)",
+                heatmap.total(),
+                heatmap.longest_streak()
+            )),
+            None => output.add_line(format!("📊 No commits from {start} to {end}")),
+        }
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "heatmap"
+    }
+
+    fn description(&self) -> &'static str {
+        "Calendar heatmap of commit activity"
+    }
+}
+
+impl GitCommand for HeatmapCommand {}
+
+/// Default `--since` for `audit-merges`
+pub const DEFAULT_AUDIT_SINCE: &str = "90 days ago";
+
+/// How a protected branch lost commits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteKind {
+    /// Force-pushed: seen by a fetch as a forced update, or pushed from here
+    ForcePush,
+    Reset,
+    Rebase,
+    Amend,
+    /// Any other update that wasn't a fast-forward
+    Rewrite,
+}
+
+impl RewriteKind {
+    /// Classify a reflog message such as `fetch: forced-update` or `reset: moving to HEAD~2`
+    pub fn from_reflog(message: &str) -> Self {
+        if message.contains("forced-update") || message.starts_with("update by push") {
+            RewriteKind::ForcePush
+        } else if message.starts_with("reset:") {
+            RewriteKind::Reset
+        } else if message.starts_with("rebase") {
+            RewriteKind::Rebase
+        } else if message.starts_with("commit (amend)") {
+            RewriteKind::Amend
+        } else {
+            RewriteKind::Rewrite
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RewriteKind::ForcePush => "force-push",
+            RewriteKind::Reset => "reset",
+            RewriteKind::Rebase => "rebase",
+            RewriteKind::Amend => "amend",
+            RewriteKind::Rewrite => "rewrite",
+        }
+    }
+}
+
+/// One reflog entry: the ref moved to `new` at `timestamp`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+    pub new: String,
+    pub timestamp: i64,
+    /// Identity of whoever ran the command that moved the ref, `Name <email>`
+    pub identity: String,
+    pub message: String,
+}
+
+impl ReflogEntry {
+    /// `git log -g --date=unix` format parsed by [`ReflogEntry::parse`]
+    pub const FORMAT: &'static str = "--format=%H%x1f%gd%x1f%gn <%ge>%x1f%gs";
+
+    /// Parse entries, newest first; the selector `%gd` carries the time as `ref@{<unix time>}`
+    pub fn parse(output: &str) -> Vec<ReflogEntry> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\x1f');
+                let new = fields.next()?.to_string();
+                let timestamp = fields
+                    .next()?
+                    .rsplit_once("@{")?
+                    .1
+                    .trim_end_matches('}')
+                    .parse()
+                    .ok()?;
+                Some(ReflogEntry {
+                    new,
+                    timestamp,
+                    identity: fields.next()?.to_string(),
+                    message: fields.next().unwrap_or_default().to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A non-fast-forward update of a protected branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefRewrite {
+    /// Short ref name, e.g. `main` or `origin/main`
+    pub reference: String,
+    pub kind: RewriteKind,
+    pub timestamp: i64,
+    pub recorded_by: String,
+    pub old: String,
+    pub new: String,
+    /// Commits reachable from `old` but no longer from `new`
+    pub dropped: usize,
+    /// Committer of the new tip, the closest record of who pushed a remote rewrite
+    pub new_committer: String,
+    pub message: String,
+}
+
+impl RefRewrite {
+    /// When it happened, in UTC
+    pub fn time(&self) -> String {
+        chrono::DateTime::from_timestamp(self.timestamp, 0)
+            .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| self.timestamp.to_string())
+    }
+
+    fn is_remote(&self) -> bool {
+        self.kind == RewriteKind::ForcePush && self.message.contains("forced-update")
+    }
+}
+
+/// Command that audits force-pushes and history rewrites of protected branches
+pub struct AuditMergesCommand {
+    since: Option<String>,
+    branches: Vec<String>,
+    format: Option<ExportFormat>,
+}
+
+impl Default for AuditMergesCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuditMergesCommand {
+    pub fn new() -> Self {
+        Self {
+            since: None,
+            branches: Vec::new(),
+            format: None,
+        }
+    }
+
+    /// Start of the window, in any format [`TimeSpec`] accepts
+    pub fn with_since(mut self, since: Option<String>) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Branch patterns to audit instead of the protected branches
+    pub fn with_branches(mut self, branches: Vec<String>) -> Self {
+        self.branches = branches;
+        self
+    }
+
+    /// Render as CSV/TSV/JSON instead of the human-readable trail
+    pub fn with_format(mut self, format: Option<ExportFormat>) -> Self {
+        self.format = format;
+        self
+    }
+
+    fn since(&self) -> &str {
+        self.since.as_deref().unwrap_or(DEFAULT_AUDIT_SINCE)
+    }
+
+    /// Patterns to audit: `--branch`, else `git-x.protect.branch`, else the default branch
+    fn patterns(&self) -> Vec<String> {
+        if !self.branches.is_empty() {
+            return self.branches.clone();
+        }
+        let protected = crate::commands::branch::ProtectedBranches::load();
+        if !protected.patterns().is_empty() {
+            return protected.patterns().to_vec();
+        }
+        GitOperations::default_branch()
+            .map(|branch| {
+                // `origin/HEAD` resolves to a remote-tracking name
+                let branch = branch.strip_prefix("origin/").unwrap_or(&branch);
+                vec![branch.to_string()]
+            })
+            .unwrap_or_default()
+    }
+
+    /// Local and remote-tracking refs whose branch name matches a pattern
+    fn references(patterns: &[String]) -> Result<Vec<String>> {
+        let refs = GitOperations::run(&[
+            "for-each-ref",
+            "--format=%(refname)",
+            "refs/heads",
+            "refs/remotes",
+        ])?;
+        Ok(refs
+            .lines()
+            .filter(|refname| {
+                let branch = match refname.strip_prefix("refs/heads/") {
+                    Some(branch) => branch,
+                    None => refname
+                        .strip_prefix("refs/remotes/")
+                        .and_then(|rest| rest.split_once('/'))
+                        .map_or("", |(_, branch)| branch),
+                };
+                branch != "HEAD" && patterns.iter().any(|p| glob_match(p, branch))
+            })
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// The non-fast-forward updates of `reference` at or after `cutoff`, newest first
+    fn rewrites(reference: &str, cutoff: i64) -> Result<Vec<RefRewrite>> {
+        let log = GitOperations::run(&[
+            "log",
+            "-g",
+            "--date=unix",
+            ReflogEntry::FORMAT,
+            reference,
+            "--",
+        ])
+        .unwrap_or_default();
+        let entries = ReflogEntry::parse(&log);
+        let short = reference
+            .strip_prefix("refs/heads/")
+            .or_else(|| reference.strip_prefix("refs/remotes/"))
+            .unwrap_or(reference);
+
+        let mut rewrites = Vec::new();
+        // Each entry's old value is the entry before it, which comes next
+        for pair in entries.windows(2) {
+            let (entry, previous) = (&pair[0], &pair[1]);
+            if entry.timestamp < cutoff {
+                break;
+            }
+            if entry.new == previous.new
+                || GitOperations::run(&["merge-base", "--is-ancestor", &previous.new, &entry.new])
+                    .is_ok()
+            {
+                continue;
+            }
+            let dropped = GitOperations::run(&[
+                "rev-list",
+                "--count",
+                &format!("{}..{}", entry.new, previous.new),
+            ])
+            .ok()
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0);
+            let new_committer =
+                GitOperations::run(&["log", "-1", "--format=%cn <%ce>", &entry.new])
+                    .unwrap_or_default();
+            rewrites.push(RefRewrite {
+                reference: short.to_string(),
+                kind: RewriteKind::from_reflog(&entry.message),
+                timestamp: entry.timestamp,
+                recorded_by: entry.identity.clone(),
+                old: previous.new.clone(),
+                new: entry.new.clone(),
+                dropped,
+                new_committer,
+                message: entry.message.clone(),
+            });
+        }
+        Ok(rewrites)
+    }
+
+    fn export(rewrites: &[RefRewrite], format: ExportFormat) -> String {
+        let mut exporter = Exporter::new(&[
+            "time",
+            "branch",
+            "kind",
+            "old",
+            "new",
+            "dropped_commits",
+            "recorded_by",
+            "new_tip_committer",
+            "reflog_message",
+        ]);
+        for rewrite in rewrites {
+            exporter.add_row(vec![
+                chrono::DateTime::from_timestamp(rewrite.timestamp, 0)
+                    .map(|time| time.to_rfc3339())
+                    .unwrap_or_default()
+                    .into(),
+                rewrite.reference.as_str().into(),
+                rewrite.kind.name().into(),
+                rewrite.old.as_str().into(),
+                rewrite.new.as_str().into(),
+                rewrite.dropped.into(),
+                rewrite.recorded_by.as_str().into(),
+                rewrite.new_committer.as_str().into(),
+                rewrite.message.as_str().into(),
+            ]);
+        }
+        exporter.render(format)
+    }
+}
+
+impl Command for AuditMergesCommand {
+    fn execute(&self) -> Result<String> {
+        let patterns = self.patterns();
+        if patterns.is_empty() {
+            return Err(GitXError::Other(
+                "No branches to audit: protect some with 'git x protect <branch>' or pass --branch"
+                    .to_string(),
+            ));
+        }
+
+        let cutoff = TimeSpec::parse(self.since())?.timestamp();
+
+        let mut rewrites = Vec::new();
+        for reference in Self::references(&patterns)? {
+            rewrites.extend(Self::rewrites(&reference, cutoff)?);
+        }
+        rewrites.sort_by(|a, b| {
+            b.timestamp
+                .cmp(&a.timestamp)
+                .then_with(|| a.reference.cmp(&b.reference))
+        });
+
+        if let Some(format) = self.format {
+            return Ok(Self::export(&rewrites, format));
+        }
+
+        let since = self.since();
+        let branches = patterns.join(", ");
+        if rewrites.is_empty() {
+            return Ok(format!(
+                "✅ No force-pushes or history rewrites of {branches} since {since}"
+            ));
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🔍 {} history rewrite(s) of {branches} since {since}:",
+            rewrites.len()
+        ));
+        for rewrite in &rewrites {
+            output.add_line(format!(
+                "⚠️  {} {} {}: {} → {}, {} commit(s) dropped",
+                rewrite.time(),
+                Format::bold(&rewrite.reference),
+                rewrite.kind.name(),
+                short_sha(&rewrite.old),
+                short_sha(&rewrite.new),
+                rewrite.dropped
+            ));
+            if rewrite.is_remote() {
+                output.add_line(format!(
+                    "    new tip committed by {}; fetched by {}",
+                    rewrite.new_committer, rewrite.recorded_by
+                ));
+            } else {
+                output.add_line(format!(
+                    "    by {}: {}",
+                    rewrite.recorded_by, rewrite.message
+                ));
+            }
+        }
+        output.add_line(
+            "💡 Only what this clone's reflogs recorded is shown; export it with --csv or --json"
+                .to_string(),
+        );
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "audit-merges"
+    }
+
+    fn description(&self) -> &'static str {
+        "Audit force-pushes and history rewrites of protected branches"
+    }
+}
+
+impl GitCommand for AuditMergesCommand {}
+
+/// Commits touching more files than this are left out of coupling analysis, unless configured
+pub const DEFAULT_COUPLING_MAX_FILES: usize = 30;
+
+/// Two files that were changed in the same commits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoupledPair {
+    pub first: String,
+    pub second: String,
+    /// Commits that changed both files
+    pub shared: usize,
+    /// Commits that changed `first`
+    pub first_changes: usize,
+    /// Commits that changed `second`
+    pub second_changes: usize,
+}
+
+impl CoupledPair {
+    /// Share of `first`'s commits that also changed `second`, in percent
+    pub fn first_confidence(&self) -> usize {
+        self.shared * 100 / self.first_changes.max(1)
+    }
+
+    /// Share of `second`'s commits that also changed `first`, in percent
+    pub fn second_confidence(&self) -> usize {
+        self.shared * 100 / self.second_changes.max(1)
+    }
+
+    /// The stronger of the two directions
+    pub fn confidence(&self) -> usize {
+        self.first_confidence().max(self.second_confidence())
+    }
+
+    pub fn involves(&self, path: &str) -> bool {
+        self.first == path || self.second == path
+    }
+}
+
+/// Pairs of files changed together at least `min_shared` times, strongest first
+///
+/// Each entry of `commits` is the set of files one commit changed.
+pub fn coupled_pairs(commits: &[Vec<String>], min_shared: usize) -> Vec<CoupledPair> {
+    let mut changes: HashMap<&str, usize> = HashMap::new();
+    let mut shared: HashMap<(&str, &str), usize> = HashMap::new();
+
+    for files in commits {
+        let mut files: Vec<&str> = files.iter().map(String::as_str).collect();
+        files.sort_unstable();
+        files.dedup();
+        for (i, first) in files.iter().enumerate() {
+            *changes.entry(first).or_default() += 1;
+            for second in &files[i + 1..] {
+                *shared.entry((first, second)).or_default() += 1;
+            }
+        }
+    }
+
+    let mut pairs: Vec<CoupledPair> = shared
+        .into_iter()
+        .filter(|(_, count)| *count >= min_shared.max(1))
+        .map(|((first, second), count)| CoupledPair {
+            first: first.to_string(),
+            second: second.to_string(),
+            shared: count,
+            first_changes: changes[first],
+            second_changes: changes[second],
+        })
+        .collect();
+    pairs.sort_by(|a, b| {
+        b.confidence()
+            .cmp(&a.confidence())
+            .then_with(|| b.shared.cmp(&a.shared))
+            .then_with(|| (&a.first, &a.second).cmp(&(&b.first, &b.second)))
+    });
+    pairs
+}
+
+/// Groups of three or more files linked by pairs at or above `min_confidence`
+pub fn coupled_clusters(pairs: &[CoupledPair], min_confidence: usize) -> Vec<Vec<String>> {
+    let mut clusters: Vec<BTreeSet<String>> = Vec::new();
+    for pair in pairs.iter().filter(|p| p.confidence() >= min_confidence) {
+        let linked: Vec<usize> = clusters
+            .iter()
+            .enumerate()
+            .filter(|(_, cluster)| cluster.contains(&pair.first) || cluster.contains(&pair.second))
+            .map(|(i, _)| i)
+            .collect();
+        let mut merged: BTreeSet<String> = [pair.first.clone(), pair.second.clone()].into();
+        for i in linked.into_iter().rev() {
+            merged.extend(clusters.remove(i));
+        }
+        clusters.push(merged);
+    }
+
+    let mut clusters: Vec<Vec<String>> = clusters
+        .into_iter()
+        .filter(|cluster| cluster.len() >= 3)
+        .map(|cluster| cluster.into_iter().collect())
+        .collect();
+    clusters.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    clusters
+}
+
+/// Command that finds files which keep changing in the same commits
+pub struct FilesChangedTogetherCommand {
+    since: Option<String>,
+    path: Option<String>,
+    limit: usize,
+    min_shared: usize,
+    max_files: usize,
+}
+
+impl Default for FilesChangedTogetherCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FilesChangedTogetherCommand {
+    /// Pairs shown unless `--limit` says otherwise
+    pub const DEFAULT_LIMIT: usize = 15;
+    /// Shared commits a pair needs before it is reported
+    pub const DEFAULT_MIN_SHARED: usize = 3;
+    /// Confidence a pair needs to join a cluster
+    pub const CLUSTER_CONFIDENCE: usize = 50;
+
+    pub fn new() -> Self {
+        Self {
+            since: None,
+            path: None,
+            limit: Self::DEFAULT_LIMIT,
+            min_shared: Self::DEFAULT_MIN_SHARED,
+            max_files: DEFAULT_COUPLING_MAX_FILES,
+        }
+    }
+
+    /// Start of the period, in any format [`TimeSpec`] accepts
+    pub fn with_since(mut self, since: Option<String>) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Only report files coupled with this one
+    pub fn with_path(mut self, path: Option<String>) -> Self {
+        self.path = path;
+        self
+    }
+
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        if let Some(limit) = limit {
+            self.limit = limit;
+        }
+        self
+    }
+
+    pub fn with_min_shared(mut self, min_shared: Option<usize>) -> Self {
+        if let Some(min_shared) = min_shared {
+            self.min_shared = min_shared;
+        }
+        self
+    }
+
+    /// Skip commits touching more files than this, such as mass renames or reformatting
+    pub fn with_max_files(mut self, max_files: Option<usize>) -> Self {
+        if let Some(max_files) = max_files {
+            self.max_files = max_files;
+        }
+        self
+    }
+
+    /// Files changed by each commit, leaving out oversized commits and deleted files
+    fn commits(&self) -> Result<(Vec<Vec<String>>, usize)> {
+        let since = self
+            .since
+            .as_deref()
+            .map(|since| TimeSpec::parse(since).map(|since| format!("--since={}", since.git_arg())))
+            .transpose()?;
+        let mut args = vec![
+            "log",
+            "--no-merges",
+            "--no-renames",
+            "--name-only",
+            "--format=%x1e",
+        ];
+        args.extend(since.as_deref());
+        let log = GitOperations::run(&args)?;
+
+        // Relative to the root, like the paths `git log` prints
+        let root = GitOperations::repo_root()?;
+        let tracked = GitOperations::run(&["-C", &root, "ls-files"])?;
+        let tracked: HashSet<&str> = tracked.lines().collect();
+
+        let mut skipped = 0;
+        let commits = log
+            .split('\x1e')
+            .filter_map(|commit| {
+                let files: Vec<String> = commit
+                    .lines()
+                    .map(str::trim)
+                    .filter(|file| tracked.contains(file))
+                    .map(String::from)
+                    .collect();
+                if files.len() > self.max_files {
+                    skipped += 1;
+                    return None;
+                }
+                (files.len() > 1).then_some(files)
+            })
+            .collect();
+        Ok((commits, skipped))
+    }
+
+    fn pair_line(pair: &CoupledPair, rank: usize) -> String {
+        format!(
+            "{rank:>3}. {} ⇄ {}\n     {} shared commits · {}% of {} changes · {}% of {} changes",
+            Format::bold(&pair.first),
+            Format::bold(&pair.second),
+            pair.shared,
+            pair.first_confidence(),
+            pair.first,
+            pair.second_confidence(),
+            pair.second
+        )
+    }
+}
+
+impl Command for FilesChangedTogetherCommand {
+    fn execute(&self) -> Result<String> {
+        let (commits, skipped) = self.commits()?;
+        let mut pairs = coupled_pairs(&commits, self.min_shared);
+        let focus = match &self.path {
+            Some(path) => {
+                let prefix = GitOperations::run(&["rev-parse", "--show-prefix"])?;
+                Some(crate::commands::commit::UndoFileCommand::repo_path(
+                    &prefix, path,
+                ))
+            }
+            None => None,
+        };
+        let period = self
+            .since
+            .as_deref()
+            .map(|since| format!("since {since}"))
+            .unwrap_or_else(|| "all history".to_string());
+
+        let mut output = BufferedOutput::new();
+        match &focus {
+            Some(path) => {
+                output.add_line(format!("🔗 Files that change with {path} ({period})"));
+                pairs.retain(|pair| pair.involves(path));
+            }
+            None => output.add_line(format!("🔗 Files that change together ({period})")),
+        }
+        output.add_line("=".repeat(50));
+
+        if pairs.is_empty() {
+            output.add_line(format!(
+                "✅ No files changed together in {} or more commits",
+                self.min_shared
+            ));
+        }
+
+        match &focus {
+            Some(path) => {
+                for pair in pairs.iter().take(self.limit) {
+                    // Confidence runs from the focused file to the other one
+                    let (other, changes, confidence) = if pair.first == *path {
+                        (&pair.second, pair.first_changes, pair.first_confidence())
+                    } else {
+                        (&pair.first, pair.second_changes, pair.second_confidence())
+                    };
+                    output.add_line(format!(
+                        "   {confidence:>3}%  {other} ({} of {changes} commits)",
+                        pair.shared
+                    ));
+                }
+            }
+            None => {
+                for (i, pair) in pairs.iter().take(self.limit).enumerate() {
+                    output.add_line(Self::pair_line(pair, i + 1));
+                }
+                let clusters = coupled_clusters(&pairs, Self::CLUSTER_CONFIDENCE);
+                if !clusters.is_empty() {
+                    output.add_line(format!(
+                        "\n🧩 Clusters (pairs at {}%+ confidence):",
+                        Self::CLUSTER_CONFIDENCE
+                    ));
+                    for cluster in clusters {
+                        output.add_line(format!("   • {}", cluster.join(", ")));
+                    }
+                }
+            }
+        }
+
+        if pairs.len() > self.limit {
+            output.add_line(format!(
+                "\n… {} more pairs (use --limit to show more)",
+                pairs.len() - self.limit
+            ));
+        }
+        let mut analysed = format!("\n📊 {} multi-file commits analysed", commits.len());
+        if skipped > 0 {
+            analysed.push_str(&format!(
+                "; {skipped} touching more than {} files skipped",
+                self.max_files
+            ));
+        }
+        output.add_line(analysed);
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "files-changed-together"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find files that frequently change in the same commits"
+    }
+}
+
+impl GitCommand for FilesChangedTogetherCommand {}
+
+/// Commits changing more lines than this are flagged as giant, unless configured
+pub const DEFAULT_GIANT_COMMIT_LINES: usize = 1000;
+
+/// Commits changing more files than this are flagged as giant, unless configured
+pub const DEFAULT_GIANT_COMMIT_FILES: usize = 100;
+
+/// Commits up to this many lines count as small; vendored or generated changes below it are routine
+const SMALL_COMMIT_LINES: usize = 200;
+
+/// Percentage of a commit's lines that makes it a vendored or generated drop
+const DROP_SHARE: usize = 80;
+
+/// What commit sizes are measured in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeMetric {
+    Lines,
+    Files,
+}
+
+impl SizeMetric {
+    /// Upper bound of each histogram bucket but the last, which is open-ended
+    pub fn bounds(self) -> &'static [usize] {
+        match self {
+            SizeMetric::Lines => &[10, 50, SMALL_COMMIT_LINES, 500, DEFAULT_GIANT_COMMIT_LINES],
+            SizeMetric::Files => &[1, 3, 10, 30, DEFAULT_GIANT_COMMIT_FILES],
+        }
+    }
+
+    pub fn unit(self) -> &'static str {
+        match self {
+            SizeMetric::Lines => "lines",
+            SizeMetric::Files => "files",
+        }
+    }
+}
+
+impl std::str::FromStr for SizeMetric {
+    type Err = GitXError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "lines" => Ok(SizeMetric::Lines),
+            "files" => Ok(SizeMetric::Files),
+            other => Err(GitXError::Parse(format!(
+                "Unknown size metric '{other}', expected lines or files"
+            ))),
+        }
+    }
+}
+
+/// Why a commit stands out from the rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitAnomaly {
+    VendoredDrop,
+    Generated,
+    Giant,
+}
+
+impl CommitAnomaly {
+    pub fn label(self) -> &'static str {
+        match self {
+            CommitAnomaly::VendoredDrop => "likely vendored code drop",
+            CommitAnomaly::Generated => "mostly generated files",
+            CommitAnomaly::Giant => "giant commit",
+        }
+    }
+}
+
+/// Size of one commit, with the lines spent on vendored and generated files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitSize {
+    pub hash: String,
+    pub subject: String,
+    pub files: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Lines changed in vendored directories
+    pub vendored_lines: usize,
+    /// Lines changed in lockfiles and other generated files
+    pub generated_lines: usize,
+}
+
+impl CommitSize {
+    /// Parse `git log --numstat --format=%x1e%H%x1f%s` output
+    ///
+    /// Paths in `marked_generated` count as generated on top of the built-in
+    /// patterns, e.g. those with the `linguist-generated` attribute.
+    pub fn parse_log(log: &str, marked_generated: &HashSet<String>) -> Vec<CommitSize> {
+        log.split('\x1e')
+            .filter_map(|record| {
+                let mut lines = record.lines();
+                let (hash, subject) = lines.next()?.split_once('\x1f')?;
+                let mut commit = CommitSize {
+                    hash: hash.to_string(),
+                    subject: subject.to_string(),
+                    ..Default::default()
+                };
+                for line in lines {
+                    let mut parts = line.splitn(3, '\t');
+                    let (Some(added), Some(deleted), Some(path)) =
+                        (parts.next(), parts.next(), parts.next())
+                    else {
+                        continue;
+                    };
+                    commit.files += 1;
+                    // Binary files report "-" for both counts
+                    let added = added.parse::<usize>().unwrap_or(0);
+                    let deleted = deleted.parse::<usize>().unwrap_or(0);
+                    commit.insertions += added;
+                    commit.deletions += deleted;
+                    if crate::core::dependencies::is_vendored(path) {
+                        commit.vendored_lines += added + deleted;
+                    } else if is_generated_file(path) || marked_generated.contains(path) {
+                        commit.generated_lines += added + deleted;
+                    }
+                }
+                Some(commit)
+            })
+            .collect()
+    }
+
+    pub fn lines(&self) -> usize {
+        self.insertions + self.deletions
+    }
+
+    pub fn measure(&self, metric: SizeMetric) -> usize {
+        match metric {
+            SizeMetric::Lines => self.lines(),
+            SizeMetric::Files => self.files,
+        }
+    }
+
+    /// The most specific reason this commit stands out, if any
+    pub fn anomaly(&self, giant_lines: usize, giant_files: usize) -> Option<CommitAnomaly> {
+        let lines = self.lines();
+        // Small lockfile bumps and patches to vendored code are routine
+        if lines > SMALL_COMMIT_LINES {
+            if self.vendored_lines * 100 >= lines * DROP_SHARE {
+                return Some(CommitAnomaly::VendoredDrop);
+            }
+            if self.generated_lines * 100 >= lines * DROP_SHARE {
+                return Some(CommitAnomaly::Generated);
+            }
+        }
+        (lines > giant_lines || self.files > giant_files).then_some(CommitAnomaly::Giant)
+    }
+}
+
+/// Bucket labels such as `11-50` and `1001+`, with the commits falling in each
+pub fn size_histogram(commits: &[CommitSize], metric: SizeMetric) -> Vec<(String, usize)> {
+    let bounds = metric.bounds();
+    let mut buckets: Vec<(String, usize)> = bounds
+        .iter()
+        .enumerate()
+        .map(|(i, upper)| {
+            let lower = if i == 0 { 0 } else { bounds[i - 1] + 1 };
+            (format!("{lower}-{upper}"), 0)
+        })
+        .collect();
+    buckets.push((format!("{}+", bounds[bounds.len() - 1] + 1), 0));
+
+    for commit in commits {
+        let size = commit.measure(metric);
+        let bucket = bounds
+            .iter()
+            .position(|upper| size <= *upper)
+            .unwrap_or(bounds.len());
+        buckets[bucket].1 += 1;
+    }
+    buckets
+}
+
+/// Command that buckets commits by size and flags the ones that stand out
+pub struct CommitSizesCommand {
+    range: Option<String>,
+    since: Option<String>,
+    metric: SizeMetric,
+    giant_lines: usize,
+    giant_files: usize,
+    limit: usize,
+}
+
+impl Default for CommitSizesCommand {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl CommitSizesCommand {
+    /// Flagged commits shown unless `--limit` says otherwise
+    pub const DEFAULT_LIMIT: usize = 20;
+    /// Width of the longest histogram bar
+    const BAR_WIDTH: usize = 40;
+
+    /// Measure the commits in `range`, or all of HEAD's history
+    pub fn new(range: Option<String>) -> Self {
+        Self {
+            range,
+            since: None,
+            metric: SizeMetric::Lines,
+            giant_lines: DEFAULT_GIANT_COMMIT_LINES,
+            giant_files: DEFAULT_GIANT_COMMIT_FILES,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+
+    /// Start of the period, in any format [`TimeSpec`] accepts
+    pub fn with_since(mut self, since: Option<String>) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Bucket the histogram by files changed instead of lines
+    pub fn with_metric(mut self, metric: SizeMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    pub fn with_giant_lines(mut self, giant_lines: Option<usize>) -> Self {
+        if let Some(giant_lines) = giant_lines {
+            self.giant_lines = giant_lines;
+        }
+        self
+    }
+
+    pub fn with_giant_files(mut self, giant_files: Option<usize>) -> Self {
+        if let Some(giant_files) = giant_files {
+            self.giant_files = giant_files;
+        }
+        self
+    }
+
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        if let Some(limit) = limit {
+            self.limit = limit;
+        }
+        self
+    }
+
+    fn range(&self) -> &str {
+        self.range.as_deref().unwrap_or("HEAD")
+    }
+
+    /// Sizes of the non-merge commits in range, newest first
+    fn commits(&self) -> Result<Vec<CommitSize>> {
+        let range = self.range();
+        if range.starts_with('-') {
+            return Err(GitXError::GitCommand(format!("Invalid range '{range}'")));
+        }
+        let since = self
+            .since
+            .as_deref()
+            .map(|since| TimeSpec::parse(since).map(|since| format!("--since={}", since.git_arg())))
+            .transpose()?;
+        let mut args = vec![
+            "log",
+            "--no-merges",
+            "--no-renames",
+            "--numstat",
+            "--format=%x1e%H%x1f%s",
+        ];
+        args.extend(since.as_deref());
+        args.push(range);
+        let log = GitOperations::run(&args)?;
+
+        let paths: BTreeSet<&str> = log
+            .lines()
+            .filter_map(|line| line.splitn(3, '\t').nth(2))
+            .collect();
+        Ok(CommitSize::parse_log(
+            &log,
+            &Self::marked_generated(&paths)?,
+        ))
+    }
+
+    /// Paths `.gitattributes` marks as `linguist-generated`
+    fn marked_generated(paths: &BTreeSet<&str>) -> Result<HashSet<String>> {
+        // Log paths are relative to the root
+        let root = GitOperations::repo_root()?;
+        let mut marked = HashSet::new();
+        let paths: Vec<&str> = paths.iter().copied().collect();
+        for chunk in paths.chunks(500) {
+            let mut args = vec![
+                "-C",
+                root.as_str(),
+                "check-attr",
+                "linguist-generated",
+                "--",
+            ];
+            args.extend(chunk);
+            let output = GitOperations::run(&args)?;
+            marked.extend(output.lines().filter_map(|line| {
+                let (path, value) = line.rsplit_once(": linguist-generated: ")?;
+                matches!(value, "set" | "true").then(|| path.to_string())
+            }));
+        }
+        Ok(marked)
+    }
+
+    fn histogram_lines(&self, commits: &[CommitSize]) -> Vec<String> {
+        let histogram = size_histogram(commits, self.metric);
+        let max = histogram.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        let label_width = histogram
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+        histogram
+            .iter()
+            .map(|(label, count)| {
+                // Any commit at all gets at least one block
+                let width = if max == 0 {
+                    0
+                } else {
+                    (count * Self::BAR_WIDTH).div_ceil(max)
+                };
+                format!(
+                    "   {label:>label_width$} {} │{} {count} ({}%)",
+                    self.metric.unit(),
+                    "█".repeat(width),
+                    count * 100 / commits.len()
+                )
+            })
+            .collect()
+    }
+
+    fn percentile(sorted: &[usize], percent: usize) -> usize {
+        let index = (sorted.len() * percent).div_ceil(100).saturating_sub(1);
+        sorted[index.min(sorted.len() - 1)]
+    }
+}
+
+impl Command for CommitSizesCommand {
+    fn execute(&self) -> Result<String> {
+        let commits = self.commits()?;
+        let mut scope = Format::bold(self.range());
+        if let Some(since) = &self.since {
+            scope.push_str(&format!(" since {since}"));
+        }
+        if commits.is_empty() {
+            return Ok(format!("✅ No commits in {scope}"));
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "📏 Commit sizes in {scope} ({} commits, by {} changed)",
+            commits.len(),
+            self.metric.unit()
+        ));
+        output.add_line(String::new());
+        output.add_lines(self.histogram_lines(&commits));
+
+        let mut lines: Vec<usize> = commits.iter().map(CommitSize::lines).collect();
+        lines.sort_unstable();
+        let mut files: Vec<usize> = commits.iter().map(|commit| commit.files).collect();
+        files.sort_unstable();
+        let small = lines.iter().filter(|&&l| l <= SMALL_COMMIT_LINES).count();
+        output.add_line(String::new());
+        output.add_line(format!(
+            "📊 Median {} lines in {} file(s) · 90th percentile {} lines · {}% of commits change {SMALL_COMMIT_LINES} lines or fewer",
+            Self::percentile(&lines, 50),
+            Self::percentile(&files, 50),
+            Self::percentile(&lines, 90),
+            small * 100 / commits.len()
+        ));
+
+        let mut flagged: Vec<(&CommitSize, CommitAnomaly)> = commits
+            .iter()
+            .filter_map(|commit| {
+                commit
+                    .anomaly(self.giant_lines, self.giant_files)
+                    .map(|anomaly| (commit, anomaly))
+            })
+            .collect();
+        if flagged.is_empty() {
+            output.add_line(format!(
+                "✅ No anomalies: every commit is under {} lines and {} files",
+                self.giant_lines, self.giant_files
+            ));
+            return Ok(output.content());
+        }
+
+        flagged.sort_by_key(|(commit, _)| std::cmp::Reverse(commit.lines()));
+        output.add_line(String::new());
+        output.add_line(format!("🚩 {} anomalous commit(s):", flagged.len()));
+        for (commit, anomaly) in flagged.iter().take(self.limit) {
+            output.add_line(format!(
+                "   {} {} (+{} -{}, {} files) {}",
+                short_sha(&commit.hash),
+                Format::bold(anomaly.label()),
+                commit.insertions,
+                commit.deletions,
+                commit.files,
+                commit.subject
+            ));
+        }
+        if flagged.len() > self.limit {
+            output.add_line(format!(
+                "   … {} more (use --limit to show more)",
+                flagged.len() - self.limit
+            ));
+        }
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "commit-sizes"
+    }
+
+    fn description(&self) -> &'static str {
+        "Histogram of commit sizes with giant, vendored and generated commits flagged"
+    }
+}
+
+impl GitCommand for CommitSizesCommand {}
+
+/// Summary line naming the current branch, or the commit when HEAD is detached
+fn current_branch_line(current_branch: &str) -> Result<String> {
+    // `rev-parse --abbrev-ref` reports a detached HEAD as plain "HEAD"
+    let detached = if current_branch == "HEAD" {
+        GitOperations::detached_head()?
+    } else {
+        None
+    };
+    Ok(match detached {
+        Some(head) => Message::new("detached_at")
+            .arg("commit", Format::bold(&head.label()))
+            .to_string(),
+        None => Message::new("current_branch")
+            .arg("branch", Format::bold(current_branch))
+            .to_string(),
+    })
+}
+
+/// `🔗 Upstream: origin/main (2 ahead, 1 behind)`
+fn upstream_line(upstream: &str, ahead: u32, behind: u32) -> String {
+    if ahead > 0 || behind > 0 {
+        Message::new("summary.upstream")
+            .arg("upstream", upstream)
+            .arg("ahead", ahead)
+            .arg("behind", behind)
+            .to_string()
+    } else {
+        Message::new("summary.upstream_current")
+            .arg("upstream", upstream)
+            .to_string()
+    }
+}
+
+/// The five most active authors with medals, nothing when there are none
+fn contributor_lines(since: Option<&str>, authors: &[AuthorStats]) -> Vec<String> {
+    if authors.is_empty() {
+        return Vec::new();
+    }
+    let period = match since {
+        Some(since) => Message::verbatim(since),
+        None => Message::new("summary.all_time"),
+    };
+    let mut lines = vec![
+        Message::new("summary.contributors")
+            .nested("period", period)
+            .to_string(),
+    ];
+    for (i, author) in authors.iter().take(5).enumerate() {
+        let medal = match i {
+            0 => "🥇",
+            1 => "🥈",
+            2 => "🥉",
+            _ => "👤",
+        };
+        lines.push(
+            Message::new("summary.contributor")
+                .arg("medal", medal)
+                .arg("name", &author.name)
+                .arg("count", author.commits)
+                .to_string(),
+        );
+    }
+    lines
+}
+
+/// Directory a file belongs to, truncated to `depth` components; `.` for the root
+fn directory_at_depth(path: &str, depth: usize) -> String {
+    let components: Vec<&str> = path.split('/').collect();
+    if components.len() <= 1 {
+        return ".".to_string();
+    }
+    let dirs = &components[..components.len() - 1];
+    dirs[..dirs.len().min(depth)].join("/")
+}
+
+// Supporting data structures
+#[derive(Debug)]
+struct CommitStats {
+    total_commits: u32,
+    period: String,
+}
+
+#[derive(Debug)]
+struct AuthorStats {
+    name: String,
+    commits: u32,
+}
+
+#[derive(Debug)]
+struct FileStats {
+    total_files: usize,
+    loc: LinesOfCode,
+    /// Generated and vendored files left out of the counts
+    generated_files: usize,
+    /// Set when files outside a sparse checkout couldn't be counted
+    sparse_warning: Option<String>,
+}
+
+impl FileStats {
+    /// Languages named in the summary
+    const TOP_LANGUAGES: usize = 5;
+
+    /// Tracked files of the repository, or of one package
+    fn collect(package: Option<&Package>, include_generated: bool) -> Result<Self> {
+        let root = GitOperations::repo_root()?;
+        let dir = package.map_or(".", |package| package.path.as_str());
+        let files = GitOperations::run(&["-C", &root, "ls-files", "-z", "--", dir])?;
+        let mut paths: Vec<&str> = files.split('\0').filter(|p| !p.is_empty()).collect();
+        let generated = generated_among(include_generated, Path::new(&root), &paths)?;
+        paths.retain(|path| !generated.contains(*path));
+
+        let loc = LinesOfCode::count(Path::new(&root), &paths);
+        let total_files = loc.languages.iter().map(|l| l.files).sum::<usize>() + loc.binary_files;
+        Ok(Self {
+            total_files,
+            loc,
+            generated_files: generated.len(),
+            sparse_warning: SparseCheckout::current_scan_warning(),
+        })
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            Message::new("summary.files")
+                .arg("count", self.total_files)
+                .arg("lines", thousands(self.loc.total_lines()))
+                .arg("code", thousands(self.loc.total_code()))
+                .to_string(),
+        ];
+        if !self.loc.languages.is_empty() {
+            lines.push(
+                Message::new("summary.languages")
+                    .arg("languages", self.loc.describe_top(Self::TOP_LANGUAGES))
+                    .to_string(),
+            );
+        }
+        if self.generated_files > 0 {
+            lines.push(
+                Message::new("summary.generated")
+                    .arg("count", self.generated_files)
+                    .to_string(),
+            );
+        }
+        lines.extend(self.sparse_warning.clone());
+        lines
+    }
+}
+
+#[derive(Debug)]
+struct FileChurn {
+    file: String,
+    changes: u32,
+}
+
+#[derive(Debug)]
+struct LargeFile {
+    path: String,
+    size_mb: f64,
+}
+
+#[derive(Debug, Default)]
+struct DirectoryStat {
+    directory: String,
+    files: usize,
+    insertions: usize,
+    deletions: usize,
+    binary_files: usize,
+}
+
+#[derive(Debug, Clone)]
+struct ContributorStats {
+    name: String,
+    email: String,
+    commit_count: usize,
+    first_commit: String,
+    last_commit: String,
+}
+
+/// Render contributor statistics through the shared export layer
+fn export_contributors(contributors: &[ContributorStats], format: ExportFormat) -> String {
+    let total_commits: usize = contributors.iter().map(|c| c.commit_count).sum();
+    let mut exporter = Exporter::new(&[
+        "name",
+        "email",
+        "commits",
+        "first_commit",
+        "last_commit",
+        "percentage",
+    ]);
+
+    for contributor in contributors {
+        let percentage = if total_commits == 0 {
+            0.0
+        } else {
+            (contributor.commit_count as f64 / total_commits as f64) * 100.0
+        };
+
+        exporter.add_row(vec![
+            contributor.name.as_str().into(),
+            contributor.email.as_str().into(),
+            contributor.commit_count.into(),
+            contributor.first_commit.as_str().into(),
+            contributor.last_commit.as_str().into(),
+            ((percentage * 10.0).round() / 10.0).into(),
+        ]);
+    }
+
+    exporter.render(format)
 }
@@ -1,5 +1,14 @@
+use crate::commands::hooks::SwitchHooks;
+use crate::commands::stash::{StashInfo, utils as stash_utils};
+use crate::core::branch_notes::BranchNote;
+use crate::core::period::{Age, TimeSpec};
+use crate::core::stash_meta::StashMeta;
 use crate::core::traits::*;
-use crate::core::{git::*, interactive::Interactive, safety::Safety, validation::Validate};
+use crate::core::{
+    config::Config, git::*, interactive::Interactive, network::Network, output::*, safety::Safety,
+    secrets::glob_match, validation::Validate,
+};
+use crate::domain::{Branch, BranchGraph, RefName};
 use crate::{GitXError, Result};
 
 /// Branch-related commands grouped together
@@ -9,7 +18,7 @@ impl BranchCommands {
     /// Create a new branch command
     pub fn new_branch(name: &str, from: Option<&str>) -> Result<String> {
         use crate::commands::repository::NewBranchCommand;
-        NewBranchCommand::new(name.to_string(), from.map(|s| s.to_string())).execute()
+        NewBranchCommand::parse(name, from)?.execute()
     }
 
     /// Clean merged branches command
@@ -17,6 +26,13 @@ impl BranchCommands {
         CleanBranchesCommand::new(dry_run).execute()
     }
 
+    /// Clean branches whose upstream is gone
+    pub fn clean_gone_branches(dry_run: bool) -> Result<String> {
+        CleanBranchesCommand::new(dry_run)
+            .with_gone_upstreams()
+            .execute()
+    }
+
     /// Switch to recent branch command
     pub fn switch_recent() -> Result<String> {
         SwitchRecentCommand::new().execute()
@@ -24,7 +40,12 @@ impl BranchCommands {
 
     /// Rename current branch command
     pub fn rename_branch(new_name: &str) -> Result<String> {
-        RenameBranchCommand::new(new_name.to_string()).execute()
+        RenameBranchCommand::new(new_name.parse()?).execute()
+    }
+
+    /// Create a branch at a detached HEAD and switch to it
+    pub fn attach(name: &str) -> Result<String> {
+        AttachCommand::new(name.parse()?).execute()
     }
 
     /// Prune remote branches command
@@ -32,46 +53,105 @@ impl BranchCommands {
         PruneBranchesCommand::new(dry_run).execute()
     }
 
+    /// Archive branches as tags, list archives, or restore one
+    pub fn archive(action: ArchiveAction) -> Result<String> {
+        ArchiveCommand::new(action).execute()
+    }
+
+    /// Show which branches are built on which
+    pub fn branch_deps() -> Result<String> {
+        BranchDepsCommand::new().execute()
+    }
+
+    /// Simulate merging branches onto the default branch in order
+    pub fn merge_queue(branches: Vec<String>, base: Option<String>) -> Result<String> {
+        MergeQueueCommand::new(branches).with_base(base).execute()
+    }
+
     /// Stash current work into a branch
     pub fn stash_branch(branch_name: &str) -> Result<String> {
-        StashBranchCommand::new(branch_name.to_string()).execute()
+        StashBranchCommand::new(branch_name.parse()?).execute()
     }
 }
 
+/// Warnings for branches that other branches are built on; empty if the graph can't be built
+fn orphan_warnings(branches_to_delete: &[String]) -> Vec<String> {
+    BranchGraph::build()
+        .map(|graph| graph.orphan_warnings(branches_to_delete))
+        .unwrap_or_default()
+}
+
 /// Command to clean merged branches
 pub struct CleanBranchesCommand {
     dry_run: bool,
+    gone: bool,
+    yes: bool,
 }
 
 impl CleanBranchesCommand {
     pub fn new(dry_run: bool) -> Self {
-        Self { dry_run }
+        Self {
+            dry_run,
+            gone: false,
+            yes: false,
+        }
+    }
+
+    /// Delete branches whose upstream was removed on the remote instead of merged branches
+    pub fn with_gone_upstreams(mut self) -> Self {
+        self.gone = true;
+        self
+    }
+
+    /// Delete without asking, even when the count would have to be typed to confirm
+    pub fn with_yes(mut self) -> Self {
+        self.yes = true;
+        self
+    }
+
+    fn kind(&self) -> &'static str {
+        if self.gone {
+            "branches with gone upstreams"
+        } else {
+            "merged branches"
+        }
     }
 
     fn get_protected_branches() -> Vec<&'static str> {
         vec!["main", "master", "develop"]
     }
 
-    fn is_protected_branch(branch: &str) -> bool {
+    fn is_protected_branch(branch: &str, baseline: Option<&str>) -> bool {
         Self::get_protected_branches().contains(&branch)
+            || baseline.is_some_and(|baseline| GitOperations::is_default_branch(branch, baseline))
     }
 }
 
 impl Command for CleanBranchesCommand {
     fn execute(&self) -> Result<String> {
-        let merged_branches = GitOperations::merged_branches()?;
+        let (candidates, baseline) = if self.gone {
+            (
+                GitOperations::gone_branches()?,
+                GitOperations::default_branch(),
+            )
+        } else {
+            let baseline = GitOperations::require_default_branch()?;
+            (GitOperations::merged_into(&baseline)?, Some(baseline))
+        };
         let current_branch = GitOperations::current_branch()?;
 
-        let branches_to_delete: Vec<String> = merged_branches
+        let branches_to_delete: Vec<String> = candidates
             .into_iter()
             .filter(|branch| branch != &current_branch)
-            .filter(|branch| !Self::is_protected_branch(branch))
+            .filter(|branch| !Self::is_protected_branch(branch, baseline.as_deref()))
             .collect();
 
         if branches_to_delete.is_empty() {
-            return Ok("No merged branches to delete.".to_string());
+            return Ok(format!("No {} to delete.", self.kind()));
         }
 
+        let warnings = orphan_warnings(&branches_to_delete);
+
         if self.dry_run {
             let mut result = format!(
                 "🧪 (dry run) {} branches would be deleted:\n",
@@ -80,32 +160,52 @@ impl Command for CleanBranchesCommand {
             for branch in &branches_to_delete {
                 result.push_str(&format!("(dry run) Would delete: {branch}\n"));
             }
+            for warning in &warnings {
+                result.push_str(&format!("{warning}\n"));
+            }
             return Ok(result);
         }
 
         // Confirm deletion
-        let details = format!(
-            "This will delete {} merged branches: {}",
+        let mut details = format!(
+            "This will delete {} {}: {}",
             branches_to_delete.len(),
+            self.kind(),
             branches_to_delete.join(", ")
         );
+        for warning in &warnings {
+            details.push_str(&format!("\n{warning}"));
+        }
 
-        if !Safety::confirm_destructive_operation("Clean merged branches", &details)? {
+        if !Safety::confirm_bulk_deletion(
+            &format!("Clean {}", self.kind()),
+            &details,
+            branches_to_delete.len(),
+            "branches",
+            self.yes,
+        )? {
             return Ok("Operation cancelled by user.".to_string());
         }
 
+        // Branches with gone upstreams are often squash-merged, and `-d` only checks merges
+        // into HEAD, not the default branch, so both would be refused
         let mut deleted = Vec::new();
         for branch in branches_to_delete {
-            if BranchOperations::delete(&branch, false).is_ok() {
+            if BranchOperations::delete(&branch, true).is_ok() {
                 deleted.push(branch);
             }
         }
 
-        Ok(format!(
-            "🧹 Deleted {} merged branches:\n{}",
+        let mut result = format!(
+            "🧹 Deleted {} {}:\n{}",
             deleted.len(),
+            self.kind(),
             deleted.join("\n")
-        ))
+        );
+        for warning in &warnings {
+            result.push_str(&format!("\n{warning}"));
+        }
+        Ok(result)
     }
 
     fn name(&self) -> &'static str {
@@ -122,32 +222,71 @@ impl GitCommand for CleanBranchesCommand {}
 /// Async parallel version of CleanBranchesCommand
 pub struct AsyncCleanBranchesCommand {
     dry_run: bool,
+    gone: bool,
+    yes: bool,
 }
 
 impl AsyncCleanBranchesCommand {
     pub fn new(dry_run: bool) -> Self {
-        Self { dry_run }
+        Self {
+            dry_run,
+            gone: false,
+            yes: false,
+        }
+    }
+
+    /// Delete branches whose upstream was removed on the remote instead of merged branches
+    pub fn with_gone_upstreams(mut self) -> Self {
+        self.gone = true;
+        self
+    }
+
+    /// Delete without asking, even when the count would have to be typed to confirm
+    pub fn with_yes(mut self) -> Self {
+        self.yes = true;
+        self
+    }
+
+    fn kind(&self) -> &'static str {
+        if self.gone {
+            "branches with gone upstreams"
+        } else {
+            "merged branches"
+        }
     }
 
     pub async fn execute_parallel(&self) -> Result<String> {
         use crate::core::{git::AsyncGitOperations, safety::Safety};
 
-        // Get merged branches and current branch in parallel
-        let (merged_branches_result, current_branch_result) = tokio::try_join!(
-            AsyncGitOperations::merged_branches(),
+        let baseline = if self.gone {
+            GitOperations::default_branch()
+        } else {
+            Some(GitOperations::require_default_branch()?)
+        };
+
+        // Get candidate branches and current branch in parallel
+        let (candidates_result, current_branch_result) = tokio::try_join!(
+            async {
+                match &baseline {
+                    Some(baseline) if !self.gone => AsyncGitOperations::merged_into(baseline).await,
+                    _ => AsyncGitOperations::gone_branches().await,
+                }
+            },
             AsyncGitOperations::current_branch()
         )?;
 
-        let branches_to_delete: Vec<String> = merged_branches_result
+        let branches_to_delete: Vec<String> = candidates_result
             .into_iter()
             .filter(|branch| branch != &current_branch_result)
-            .filter(|branch| !Self::is_protected_branch(branch))
+            .filter(|branch| !Self::is_protected_branch(branch, baseline.as_deref()))
             .collect();
 
         if branches_to_delete.is_empty() {
-            return Ok("No merged branches to delete.".to_string());
+            return Ok(format!("No {} to delete.", self.kind()));
         }
 
+        let warnings = orphan_warnings(&branches_to_delete);
+
         if self.dry_run {
             let mut result = format!(
                 "🧪 (dry run) {} branches would be deleted:\n",
@@ -156,17 +295,30 @@ impl AsyncCleanBranchesCommand {
             for branch in &branches_to_delete {
                 result.push_str(&format!("(dry run) Would delete: {branch}\n"));
             }
+            for warning in &warnings {
+                result.push_str(&format!("{warning}\n"));
+            }
             return Ok(result);
         }
 
         // Confirm deletion
-        let details = format!(
-            "This will delete {} merged branches: {}",
+        let mut details = format!(
+            "This will delete {} {}: {}",
             branches_to_delete.len(),
+            self.kind(),
             branches_to_delete.join(", ")
         );
+        for warning in &warnings {
+            details.push_str(&format!("\n{warning}"));
+        }
 
-        if !Safety::confirm_destructive_operation("Clean merged branches", &details)? {
+        if !Safety::confirm_bulk_deletion(
+            &format!("Clean {}", self.kind()),
+            &details,
+            branches_to_delete.len(),
+            "branches",
+            self.yes,
+        )? {
             return Ok("Operation cancelled by user.".to_string());
         }
 
@@ -203,13 +355,19 @@ impl AsyncCleanBranchesCommand {
             }
         }
 
+        for warning in &warnings {
+            result.push_str(&format!("{warning}\n"));
+        }
+
         Ok(result)
     }
 
     async fn delete_branch_async(&self, branch: String) -> Result<bool> {
         use crate::core::git::AsyncGitOperations;
 
-        match AsyncGitOperations::run_status(&["branch", "-d", &branch]).await {
+        // Branches with gone upstreams are often squash-merged, and `-d` only checks merges
+        // into HEAD, not the default branch, so both would be refused
+        match AsyncGitOperations::run_status(&["branch", "-D", &branch]).await {
             Ok(_) => Ok(true),
             Err(_) => Ok(false),
         }
@@ -219,13 +377,19 @@ impl AsyncCleanBranchesCommand {
         vec!["main", "master", "develop"]
     }
 
-    fn is_protected_branch(branch: &str) -> bool {
+    fn is_protected_branch(branch: &str, baseline: Option<&str>) -> bool {
         Self::get_protected_branches().contains(&branch)
+            || baseline.is_some_and(|baseline| GitOperations::is_default_branch(branch, baseline))
     }
 }
 impl DryRunnable for CleanBranchesCommand {
     fn execute_dry_run(&self) -> Result<String> {
-        CleanBranchesCommand::new(true).execute()
+        CleanBranchesCommand {
+            dry_run: true,
+            gone: self.gone,
+            yes: self.yes,
+        }
+        .execute()
     }
 
     fn is_dry_run(&self) -> bool {
@@ -240,7 +404,9 @@ impl Destructive for CleanBranchesCommand {
 }
 
 /// Command to switch to a recent branch
-pub struct SwitchRecentCommand;
+pub struct SwitchRecentCommand {
+    hooks: bool,
+}
 
 impl Default for SwitchRecentCommand {
     fn default() -> Self {
@@ -250,7 +416,23 @@ impl Default for SwitchRecentCommand {
 
 impl SwitchRecentCommand {
     pub fn new() -> Self {
-        Self
+        Self { hooks: true }
+    }
+
+    /// Skip the switch hooks from `.git-x.toml`
+    pub fn with_no_hooks(mut self) -> Self {
+        self.hooks = false;
+        self
+    }
+
+    fn switch(&self, branch: &str) -> Result<String> {
+        BranchOperations::switch(branch)?;
+        let mut output = vec![format!("Switched to branch '{branch}'")];
+        output.extend(BranchNoteCommand::reminder(branch));
+        if self.hooks {
+            output.extend(SwitchHooks::after_switch(branch));
+        }
+        Ok(output.join("\n"))
     }
 }
 
@@ -271,8 +453,7 @@ impl Command for SwitchRecentCommand {
             branches[0].clone()
         };
 
-        BranchOperations::switch(&selected_branch)?;
-        Ok(format!("Switched to branch '{selected_branch}'"))
+        self.switch(&selected_branch)
     }
 
     fn name(&self) -> &'static str {
@@ -293,36 +474,417 @@ impl crate::core::traits::Interactive for SwitchRecentCommand {
                 "No recent branches found".to_string(),
             ));
         }
-        BranchOperations::switch(&branches[0])?;
-        Ok(format!("Switched to branch '{}'", branches[0]))
+        self.switch(&branches[0])
+    }
+}
+
+/// Stash operation tag marking the WIP `jump` leaves behind on a branch
+pub const JUMP_STASH_OPERATION: &str = "jump";
+
+/// Command to switch branches, parking uncommitted work on the branch it belongs to
+pub struct JumpCommand {
+    branch: String,
+    include_untracked: bool,
+    restore: bool,
+    hooks: bool,
+}
+
+impl JumpCommand {
+    pub fn new(branch: &str) -> Self {
+        Self {
+            branch: branch.to_string(),
+            include_untracked: false,
+            restore: true,
+            hooks: true,
+        }
+    }
+
+    /// Skip the switch hooks from `.git-x.toml`
+    pub fn with_no_hooks(mut self) -> Self {
+        self.hooks = false;
+        self
+    }
+
+    /// Stash untracked files too instead of carrying them to the other branch
+    pub fn with_untracked(mut self) -> Self {
+        self.include_untracked = true;
+        self
+    }
+
+    /// Leave WIP stashed for the destination branch in the stash
+    pub fn with_no_restore(mut self) -> Self {
+        self.restore = false;
+        self
+    }
+
+    /// Newest WIP `jump` stashed for `branch`
+    pub fn parked_stash(branch: &str) -> Result<Option<StashInfo>> {
+        let list = GitOperations::run(&["stash", "list", "--format=%gd|%s|%ct"])?;
+        Ok(list
+            .lines()
+            .filter_map(stash_utils::parse_stash_line_with_date)
+            .find(|stash| {
+                StashMeta::parse(&stash.message).is_some_and(|meta| {
+                    meta.operation == JUMP_STASH_OPERATION && meta.branch == branch
+                })
+            }))
+    }
+
+    /// The branch to jump to, resolving `-` to the previous one
+    fn destination(&self) -> Result<String> {
+        if self.branch != "-" {
+            return Ok(self.branch.clone());
+        }
+        GitOperations::run(&["rev-parse", "--abbrev-ref", "@{-1}"])
+            .ok()
+            .filter(|branch| !branch.is_empty() && branch != "HEAD")
+            .ok_or_else(|| GitXError::GitCommand("No previous branch to jump back to".to_string()))
+    }
+
+    /// Bring back the WIP parked on `destination`, or say where it is with `--no-restore`
+    fn restore_parked(&self, destination: &str, output: &mut BufferedOutput) -> Result<()> {
+        let Some(stash) = Self::parked_stash(destination)? else {
+            return Ok(());
+        };
+        let age = stash
+            .timestamp
+            .parse::<i64>()
+            .map(|time| stash_utils::relative_age(time, chrono::Utc::now().timestamp()))
+            .unwrap_or_else(|_| "earlier".to_string());
+        if !self.restore {
+            output.add_line(format!(
+                "💡 WIP stashed on '{destination}' {age} is still in {}; 'git stash pop {}' brings it back",
+                stash.name, stash.name
+            ));
+            return Ok(());
+        }
+
+        match GitOperations::run(&["stash", "pop", "--index", &stash.name]) {
+            Ok(_) => output.add_line(format!("📦 Restored WIP stashed on '{destination}' {age}")),
+            Err(e) => {
+                output.add_line(format!(
+                    "⚠️  Couldn't restore WIP stashed on '{destination}' {age}: {e}"
+                ));
+                output.add_line(format!(
+                    "💡 It is still in {}; apply it with 'git stash pop {}'",
+                    stash.name, stash.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Command for JumpCommand {
+    fn execute(&self) -> Result<String> {
+        let destination = self.destination()?;
+        let current = GitOperations::current_branch()?;
+        if current == destination {
+            return Ok(format!("Already on '{destination}'"));
+        }
+
+        let local = GitOperations::run(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{destination}"),
+        ])
+        .is_ok();
+        let remote = GitOperations::run(&[
+            "for-each-ref",
+            "--format=%(refname)",
+            &format!("refs/remotes/*/{destination}"),
+        ])
+        .is_ok_and(|refs| !refs.is_empty());
+        if !local && !remote {
+            return Err(GitXError::GitCommand(format!(
+                "Branch '{destination}' does not exist"
+            )));
+        }
+
+        let mut output = BufferedOutput::new();
+        let untracked = if self.include_untracked {
+            "--untracked-files=all"
+        } else {
+            "--untracked-files=no"
+        };
+        let changes = GitOperations::run(&["status", "--porcelain", untracked])?;
+
+        crate::core::checkpoints::FileCheckpoints::before("jump");
+        crate::core::safety::Transaction::begin("jump")?.run(|| {
+            if !changes.is_empty() {
+                let message = StashMeta::new(JUMP_STASH_OPERATION)
+                    .message(&format!("git-x jump WIP on {current}"));
+                let mut args = vec!["stash", "push", "-m", message.as_str()];
+                if self.include_untracked {
+                    args.push("--include-untracked");
+                }
+                GitOperations::run(&args)?;
+                output.add_line(format!(
+                    "📦 Stashed WIP on '{current}' ({} file(s))",
+                    changes.lines().count()
+                ));
+            }
+            GitOperations::run(&["checkout", "--quiet", &destination])?;
+            output.add_line(format!("🔀 Switched to '{destination}'"));
+            Ok(())
+        })?;
+
+        self.restore_parked(&destination, &mut output)?;
+        output.add_lines(BranchNoteCommand::reminder(&destination));
+        if self.hooks {
+            output.add_lines(SwitchHooks::after_switch(&destination));
+        }
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "jump"
+    }
+
+    fn description(&self) -> &'static str {
+        "Switch branches, stashing WIP and restoring the destination's"
+    }
+}
+
+impl GitCommand for JumpCommand {}
+
+pub enum BranchNoteAction {
+    Set {
+        message: String,
+        branch: Option<String>,
+        expires: Option<String>,
+    },
+    Show {
+        branch: Option<String>,
+    },
+    Clear {
+        branch: Option<String>,
+        expired: bool,
+    },
+    List {
+        all: bool,
+    },
+}
+
+/// Command to attach reminders to branches, shown when git-x switches to them
+pub struct BranchNoteCommand {
+    action: BranchNoteAction,
+}
+
+impl BranchNoteCommand {
+    pub fn new(action: BranchNoteAction) -> Self {
+        Self { action }
+    }
+
+    /// Lines shown after a git-x command switched to `branch`; none without an active note
+    pub fn reminder(branch: &str) -> Vec<String> {
+        let now = chrono::Utc::now().timestamp();
+        BranchNote::get(branch)
+            .filter(|note| !note.is_expired(now))
+            .map(|note| Self::display(&note, now))
+            .unwrap_or_default()
+    }
+
+    fn display(note: &BranchNote, now: i64) -> Vec<String> {
+        let mut lines = vec![format!(
+            "📌 Note on '{}' ({}):",
+            note.branch,
+            Self::age(note, now)
+        )];
+        lines.extend(note.message.lines().map(|line| format!("   {line}")));
+        lines
+    }
+
+    /// When a note was written and when it expires, e.g. `set 3 days ago, expires 2026-10-24`
+    fn age(note: &BranchNote, now: i64) -> String {
+        let set = format!("set {}", stash_utils::relative_age(note.created, now));
+        match note.expires {
+            Some(expires) => {
+                let verb = if note.is_expired(now) {
+                    "expired"
+                } else {
+                    "expires"
+                };
+                let day = chrono::DateTime::from_timestamp(expires, 0)
+                    .map(|time| {
+                        time.with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d")
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                format!("{set}, {verb} {day}")
+            }
+            None => set,
+        }
+    }
+
+    fn branch(branch: &Option<String>) -> Result<String> {
+        match branch {
+            Some(branch) => Ok(branch.clone()),
+            None => GitOperations::require_branch("branch-note"),
+        }
+    }
+
+    fn set(
+        &self,
+        message: &str,
+        branch: &Option<String>,
+        expires: &Option<String>,
+    ) -> Result<String> {
+        let branch = Self::branch(branch)?;
+        if GitOperations::run(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ])
+        .is_err()
+        {
+            return Err(GitXError::GitCommand(format!(
+                "Branch '{branch}' does not exist"
+            )));
+        }
+        if message.trim().is_empty() {
+            return Err(GitXError::Parse("The note is empty".to_string()));
+        }
+        let now = chrono::Utc::now().timestamp();
+        let expires = expires
+            .as_deref()
+            .map(|value| BranchNote::parse_expiry(value, now))
+            .transpose()?;
+        if expires.is_some_and(|expires| expires <= now) {
+            return Err(GitXError::Parse(
+                "The note would already have expired".to_string(),
+            ));
+        }
+
+        let replaced = BranchNote::get(&branch).is_some();
+        let note = BranchNote::new(&branch, message.trim(), expires);
+        note.save()?;
+        let verb = if replaced { "Replaced the" } else { "Added a" };
+        Ok(format!(
+            "📌 {verb} note on '{branch}' ({}); it shows the next time git-x switches to it",
+            Self::age(&note, now)
+        ))
+    }
+
+    fn show(branch: &Option<String>) -> Result<String> {
+        let branch = Self::branch(branch)?;
+        let note = BranchNote::get(&branch)
+            .ok_or_else(|| GitXError::GitCommand(format!("No note on '{branch}'")))?;
+        Ok(Self::display(&note, chrono::Utc::now().timestamp()).join("\n"))
+    }
+
+    fn clear(branch: &Option<String>, expired: bool) -> Result<String> {
+        if expired {
+            let now = chrono::Utc::now().timestamp();
+            let cleared = BranchNote::all()?
+                .into_iter()
+                .filter(|note| note.is_expired(now))
+                .filter(|note| BranchNote::clear(&note.branch))
+                .count();
+            return Ok(format!("🗑️  Cleared {cleared} expired note(s)"));
+        }
+        let branch = Self::branch(branch)?;
+        if !BranchNote::clear(&branch) {
+            return Err(GitXError::GitCommand(format!("No note on '{branch}'")));
+        }
+        Ok(format!("🗑️  Cleared the note on '{branch}'"))
+    }
+
+    fn list(all: bool) -> Result<String> {
+        let now = chrono::Utc::now().timestamp();
+        let (expired, active): (Vec<_>, Vec<_>) = BranchNote::all()?
+            .into_iter()
+            .partition(|note| note.is_expired(now));
+        let shown: Vec<&BranchNote> = if all {
+            active.iter().chain(&expired).collect()
+        } else {
+            active.iter().collect()
+        };
+
+        let mut output = BufferedOutput::new();
+        if shown.is_empty() {
+            output.add_line("No branch notes".to_string());
+        } else {
+            output.add_line("📌 Branch notes:".to_string());
+            let width = shown
+                .iter()
+                .map(|note| note.branch.len())
+                .max()
+                .unwrap_or(0);
+            for note in shown {
+                let mut lines = note.message.lines();
+                output.add_line(format!(
+                    "  {:width$}  {} ({})",
+                    note.branch,
+                    lines.next().unwrap_or_default(),
+                    Self::age(note, now)
+                ));
+                for line in lines {
+                    output.add_line(format!("  {:width$}  {line}", ""));
+                }
+            }
+        }
+        if !all && !expired.is_empty() {
+            output.add_line(format!(
+                "💤 {} expired note(s) hidden; --all shows them, 'git x branch-note clear --expired' removes them",
+                expired.len()
+            ));
+        }
+        Ok(output.content())
+    }
+}
+
+impl Command for BranchNoteCommand {
+    fn execute(&self) -> Result<String> {
+        match &self.action {
+            BranchNoteAction::Set {
+                message,
+                branch,
+                expires,
+            } => self.set(message, branch, expires),
+            BranchNoteAction::Show { branch } => Self::show(branch),
+            BranchNoteAction::Clear { branch, expired } => Self::clear(branch, *expired),
+            BranchNoteAction::List { all } => Self::list(*all),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "branch-note"
+    }
+
+    fn description(&self) -> &'static str {
+        "Attach reminders to branches, shown when git-x switches to them"
     }
 }
 
+impl GitCommand for BranchNoteCommand {}
+
 /// Command to rename current branch
 pub struct RenameBranchCommand {
-    new_name: String,
+    new_name: Branch,
 }
 
 impl RenameBranchCommand {
-    pub fn new(new_name: String) -> Self {
+    pub fn new(new_name: Branch) -> Self {
         Self { new_name }
     }
 }
 
 impl Command for RenameBranchCommand {
     fn execute(&self) -> Result<String> {
-        Validate::branch_name(&self.new_name)?;
+        let current_branch = GitOperations::require_branch("rename-branch")?;
 
-        let current_branch = GitOperations::current_branch()?;
-
-        if BranchOperations::exists(&self.new_name)? {
+        if BranchOperations::exists(self.new_name.as_str())? {
             return Err(GitXError::GitCommand(format!(
                 "Branch '{}' already exists",
                 self.new_name
             )));
         }
 
-        BranchOperations::rename(&self.new_name)?;
+        BranchOperations::rename(self.new_name.as_str())?;
         Ok(format!(
             "✅ Renamed branch '{}' to '{}'",
             current_branch, self.new_name
@@ -340,74 +902,321 @@ impl Command for RenameBranchCommand {
 
 impl GitCommand for RenameBranchCommand {}
 
+/// Command to put a detached HEAD back on a branch
+pub struct AttachCommand {
+    name: Branch,
+}
+
+impl AttachCommand {
+    pub fn new(name: Branch) -> Self {
+        Self { name }
+    }
+}
+
+impl Command for AttachCommand {
+    fn execute(&self) -> Result<String> {
+        let Some(head) = GitOperations::detached_head()? else {
+            let current = GitOperations::current_branch()?;
+            return Err(GitXError::GitCommand(format!(
+                "HEAD is not detached, you are on '{current}'. Use 'git x new {}' to start another branch",
+                self.name
+            )));
+        };
+        if head.rebasing {
+            return Err(GitXError::GitCommand(
+                "A rebase is in progress; finish it with 'git rebase --continue' or 'git rebase --abort' first"
+                    .to_string(),
+            ));
+        }
+        if BranchOperations::exists(self.name.as_str())? {
+            return Err(GitXError::GitCommand(format!(
+                "Branch '{}' already exists",
+                self.name
+            )));
+        }
+
+        BranchOperations::create(self.name.as_str(), None)?;
+
+        let mut output = format!(
+            "✅ Created branch '{}' at {} and switched to it",
+            self.name,
+            head.label()
+        );
+        if head.unreachable > 0 {
+            output.push_str(&format!(
+                "\n🔗 {} commit(s) that were not on any branch are now kept",
+                head.unreachable
+            ));
+        }
+        Ok(output)
+    }
+
+    fn name(&self) -> &'static str {
+        "attach"
+    }
+
+    fn description(&self) -> &'static str {
+        "Create a branch at the detached HEAD and switch to it"
+    }
+}
+
+impl GitCommand for AttachCommand {}
+
 /// Command to prune (delete) merged local branches
 pub struct PruneBranchesCommand {
     dry_run: bool,
+    quiet: bool,
+    yes: bool,
+    older_than: Option<Age>,
+    allow_unmerged: bool,
+    except: Vec<String>,
 }
 
 impl PruneBranchesCommand {
     pub fn new(dry_run: bool) -> Self {
-        Self { dry_run }
+        Self {
+            dry_run,
+            quiet: false,
+            yes: false,
+            older_than: None,
+            allow_unmerged: false,
+            except: Vec::new(),
+        }
+    }
+
+    /// Never prune these branches
+    pub fn with_except(mut self, branches: Vec<String>) -> Self {
+        self.except = branches;
+        self
+    }
+
+    /// Print only the affected branch names, one per line
+    pub fn with_quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    /// Delete without asking, even when the count would have to be typed to confirm
+    pub fn with_yes(mut self) -> Self {
+        self.yes = true;
+        self
+    }
+
+    /// Only prune branches whose last commit is older than `age`
+    pub fn with_older_than(mut self, age: Option<Age>) -> Self {
+        self.older_than = age;
+        self
+    }
+
+    /// Also prune old unmerged branches, archiving each as a tag first
+    pub fn with_allow_unmerged(mut self) -> Self {
+        self.allow_unmerged = true;
+        self
     }
 
     fn get_protected_branches() -> Vec<&'static str> {
         vec!["main", "master", "develop"]
     }
 
-    fn is_protected_branch(branch: &str) -> bool {
+    fn is_protected_branch(branch: &str, baseline: Option<&str>) -> bool {
         Self::get_protected_branches().contains(&branch)
+            || baseline.is_some_and(|baseline| GitOperations::is_default_branch(branch, baseline))
     }
-}
 
-impl Command for PruneBranchesCommand {
-    fn execute(&self) -> Result<String> {
-        let merged_branches = GitOperations::merged_branches()?;
-        let current_branch = GitOperations::current_branch()?;
+    /// Local branches with the unix time of their last commit
+    fn branch_dates() -> Result<Vec<(String, i64)>> {
+        Ok(GitOperations::run(&[
+            "for-each-ref",
+            "--format=%(refname:short) %(committerdate:unix)",
+            "refs/heads",
+        ])?
+        .lines()
+        .filter_map(|line| {
+            let (branch, time) = line.rsplit_once(' ')?;
+            Some((branch.to_string(), time.parse().ok()?))
+        })
+        .collect())
+    }
 
-        let branches_to_delete: Vec<String> = merged_branches
+    /// Branches to delete, and which of them aren't merged
+    fn candidates(&self, baseline: &str) -> Result<(Vec<String>, Vec<String>)> {
+        let merged = GitOperations::merged_into(baseline)?;
+        let current_branch = GitOperations::current_branch()?;
+        let dates = Self::branch_dates()?;
+        let cutoff = self
+            .older_than
+            .as_ref()
+            .map(|age| age.before(chrono::Utc::now().timestamp()));
+
+        let pool = if self.allow_unmerged {
+            dates.iter().map(|(branch, _)| branch.clone()).collect()
+        } else {
+            merged.clone()
+        };
+        let candidates: Vec<String> = pool
             .into_iter()
             .filter(|branch| branch != &current_branch)
-            .filter(|branch| !Self::is_protected_branch(branch))
+            .filter(|branch| !Self::is_protected_branch(branch, Some(baseline)))
+            .filter(|branch| !self.except.contains(branch))
+            .filter(|branch| match cutoff {
+                Some(cutoff) => dates
+                    .iter()
+                    .any(|(name, time)| name == branch && *time < cutoff),
+                None => true,
+            })
             .collect();
+        let unmerged = candidates
+            .iter()
+            .filter(|branch| !merged.contains(branch))
+            .cloned()
+            .collect();
+        Ok((candidates, unmerged))
+    }
+
+    /// "merged branches", "merged branches older than 6 months" and so on
+    fn describe(&self) -> String {
+        let kind = if self.allow_unmerged {
+            "branches"
+        } else {
+            "merged branches"
+        };
+        match &self.older_than {
+            Some(age) => format!("{kind} older than {age}"),
+            None => kind.to_string(),
+        }
+    }
+}
+
+impl Command for PruneBranchesCommand {
+    fn execute(&self) -> Result<String> {
+        let baseline = GitOperations::require_default_branch()?;
+        let (branches_to_delete, unmerged) = self.candidates(&baseline)?;
+        let description = self.describe();
 
         if branches_to_delete.is_empty() {
-            return Ok("✅ No merged branches to prune.".to_string());
+            return Ok(if self.quiet {
+                String::new()
+            } else {
+                format!("✅ No {description} to prune.")
+            });
         }
 
+        if self.dry_run && self.quiet {
+            return Ok(branches_to_delete.join("\n"));
+        }
+
+        let warnings = orphan_warnings(&branches_to_delete);
+        let archives = if unmerged.is_empty() {
+            Vec::new()
+        } else {
+            ArchiveCommand::plan(&unmerged)?
+        };
+
         if self.dry_run {
             let mut result = format!(
                 "🧪 (dry run) {} branches would be deleted:\n",
                 branches_to_delete.len()
             );
             for branch in &branches_to_delete {
-                result.push_str(&format!("(dry run) Would delete: {branch}\n"));
+                match archives.iter().find(|(name, _)| name == branch) {
+                    Some((_, tag)) => result.push_str(&format!(
+                        "(dry run) Would delete: {branch} (unmerged, archived as {tag} first)\n"
+                    )),
+                    None => result.push_str(&format!("(dry run) Would delete: {branch}\n")),
+                }
+            }
+            for warning in &warnings {
+                result.push_str(&format!("{warning}\n"));
             }
             return Ok(result);
         }
 
         // Confirm deletion
-        let details = format!(
-            "This will delete {} merged branches: {}",
+        let mut details = format!(
+            "This will delete {} {description}: {}",
             branches_to_delete.len(),
             branches_to_delete.join(", ")
         );
+        for warning in &warnings {
+            details.push_str(&format!("\n{warning}"));
+        }
 
-        if !Safety::confirm_destructive_operation("Delete merged branches", &details)? {
-            return Ok("Operation cancelled by user.".to_string());
+        let confirmed = if unmerged.is_empty() {
+            Safety::confirm_bulk_deletion(
+                "Delete merged branches",
+                &details,
+                branches_to_delete.len(),
+                "branches",
+                self.yes,
+            )?
+        } else {
+            // Unmerged work is only deleted with someone at the keyboard, whatever --yes says
+            if !Interactive::is_interactive() {
+                return Err(GitXError::Other(format!(
+                    "Refusing to delete {} unmerged branch(es) without interactive confirmation; --yes does not apply to --allow-unmerged",
+                    unmerged.len()
+                )));
+            }
+            details.push_str(&format!(
+                "\n{} of them are not merged and will be kept as archive tags: {}",
+                unmerged.len(),
+                unmerged.join(", ")
+            ));
+            Safety::confirm_bulk_deletion(
+                "Delete old branches",
+                &details,
+                branches_to_delete.len(),
+                "branches",
+                false,
+            )?
+        };
+        if !confirmed {
+            return Ok(if self.quiet {
+                String::new()
+            } else {
+                "Operation cancelled by user.".to_string()
+            });
+        }
+
+        let mut archived = Vec::new();
+        for (branch, tag) in &archives {
+            ArchiveCommand::tag(branch, tag)?;
+            archived.push(format!("📦 Archived {branch} → {tag}"));
         }
 
+        // `-d` only checks merges into HEAD, and these were checked against the default branch
         let mut deleted = Vec::new();
         for branch in branches_to_delete {
-            if BranchOperations::delete(&branch, false).is_ok() {
+            if GitOperations::run_status_with(&["branch", "-D", &branch], self.quiet).is_ok() {
                 deleted.push(branch);
             }
         }
 
-        Ok(format!(
-            "🧹 Deleted {} merged branches:\n{}",
+        if self.quiet {
+            return Ok(deleted.join("\n"));
+        }
+
+        let mut result = archived
+            .iter()
+            .map(|line| format!("{line}\n"))
+            .collect::<String>();
+        result.push_str(&format!(
+            "🧹 Deleted {} {}:\n{}",
             deleted.len(),
+            if self.allow_unmerged {
+                "branches"
+            } else {
+                "merged branches"
+            },
             deleted.join("\n")
-        ))
+        ));
+        for warning in &warnings {
+            result.push_str(&format!("\n{warning}"));
+        }
+        if !archived.is_empty() {
+            result.push_str("\n💡 Restore an archived branch with: git x archive restore <branch>");
+        }
+        Ok(result)
     }
 
     fn name(&self) -> &'static str {
@@ -420,10 +1229,51 @@ impl Command for PruneBranchesCommand {
 }
 
 impl GitCommand for PruneBranchesCommand {}
-impl DryRunnable for PruneBranchesCommand {
-    fn execute_dry_run(&self) -> Result<String> {
-        PruneBranchesCommand::new(true).execute()
-    }
+
+/// Command to show which local branches are built on which
+pub struct BranchDepsCommand;
+
+impl Default for BranchDepsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BranchDepsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Command for BranchDepsCommand {
+    fn execute(&self) -> Result<String> {
+        let graph = BranchGraph::build()?;
+
+        let mut output = BufferedOutput::new();
+        output.add_line("🌳 Branch dependencies".to_string());
+        output.add_line("=".repeat(30));
+        output.add_line(graph.render());
+        if !graph.has_dependencies() {
+            output.add_line("✅ No branch is built on another".to_string());
+        }
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "branch-deps"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show which branches are built on which"
+    }
+}
+
+impl GitCommand for BranchDepsCommand {}
+impl DryRunnable for PruneBranchesCommand {
+    fn execute_dry_run(&self) -> Result<String> {
+        PruneBranchesCommand::new(true).execute()
+    }
 
     fn is_dry_run(&self) -> bool {
         self.dry_run
@@ -438,20 +1288,18 @@ impl Destructive for PruneBranchesCommand {
 
 /// Command to stash work into a new branch
 pub struct StashBranchCommand {
-    branch_name: String,
+    branch_name: Branch,
 }
 
 impl StashBranchCommand {
-    pub fn new(branch_name: String) -> Self {
+    pub fn new(branch_name: Branch) -> Self {
         Self { branch_name }
     }
 }
 
 impl Command for StashBranchCommand {
     fn execute(&self) -> Result<String> {
-        Validate::branch_name(&self.branch_name)?;
-
-        if BranchOperations::exists(&self.branch_name)? {
+        if BranchOperations::exists(self.branch_name.as_str())? {
             return Err(GitXError::GitCommand(format!(
                 "Branch '{}' already exists",
                 self.branch_name
@@ -459,9 +1307,10 @@ impl Command for StashBranchCommand {
         }
 
         // Create branch from current state
-        BranchOperations::create(&self.branch_name, None)?;
+        BranchOperations::create(self.branch_name.as_str(), None)?;
 
         // Reset to clean state
+        crate::core::checkpoints::FileCheckpoints::before("stash-branch");
         GitOperations::run_status(&["reset", "--hard", "HEAD"])?;
 
         Ok(format!(
@@ -485,3 +1334,2019 @@ impl Destructive for StashBranchCommand {
         "This will reset your working directory to a clean state".to_string()
     }
 }
+
+/// Branch archive actions
+#[derive(Debug, Clone)]
+pub enum ArchiveAction {
+    Archive {
+        branches: Vec<String>,
+        remote: bool,
+        dry_run: bool,
+    },
+    List,
+    Restore {
+        name: String,
+        as_branch: Option<String>,
+    },
+}
+
+/// Command to keep stale branches as `archive/<branch>-<date>` tags instead of deleting them
+pub struct ArchiveCommand {
+    action: ArchiveAction,
+}
+
+impl ArchiveCommand {
+    /// Namespace for archive tags
+    pub const PREFIX: &'static str = "archive/";
+
+    pub fn new(action: ArchiveAction) -> Self {
+        Self { action }
+    }
+
+    /// Tag name for a branch archived on `date`, made unique with a numeric suffix
+    pub fn tag_name(branch: &str, date: &str, existing: &[String]) -> String {
+        let base = format!("{}{branch}-{date}", Self::PREFIX);
+        if !existing.contains(&base) {
+            return base;
+        }
+        (2..)
+            .map(|n| format!("{base}-{n}"))
+            .find(|candidate| !existing.contains(candidate))
+            .unwrap_or(base)
+    }
+
+    /// Branch name an archive tag was created from
+    pub fn branch_from_tag(tag: &str) -> Option<String> {
+        // `-YYYY-MM-DD` suffix, as written by `tag_name`
+        fn strip_date(name: &str) -> Option<&str> {
+            let split = name.len().checked_sub(11)?;
+            let (branch, date) = (name.get(..split)?, name.get(split..)?);
+            let is_date = date.char_indices().all(|(i, c)| match i {
+                0 | 5 | 8 => c == '-',
+                _ => c.is_ascii_digit(),
+            });
+            (is_date && !branch.is_empty()).then_some(branch)
+        }
+
+        let name = tag.strip_prefix(Self::PREFIX)?;
+        strip_date(name)
+            .or_else(|| {
+                // Same-day archives get a numeric `-N` suffix
+                let (rest, n) = name.rsplit_once('-')?;
+                n.chars().all(|c| c.is_ascii_digit()).then_some(())?;
+                strip_date(rest)
+            })
+            .map(String::from)
+    }
+
+    /// Archive tags, newest first, as (tag, date, subject)
+    fn archives() -> Result<Vec<(String, String, String)>> {
+        let output = GitOperations::run(&[
+            "for-each-ref",
+            "--sort=-creatordate",
+            "--format=%(refname:short)%09%(creatordate:short)%09%(subject)",
+            &format!("refs/tags/{}", Self::PREFIX.trim_end_matches('/')),
+        ])?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                Some((
+                    parts.next()?.to_string(),
+                    parts.next()?.to_string(),
+                    parts.next().unwrap_or_default().to_string(),
+                ))
+            })
+            .collect())
+    }
+
+    /// Archive tag for each branch, as (branch, tag), dated today
+    pub fn plan(branches: &[String]) -> Result<Vec<(String, String)>> {
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let mut existing: Vec<String> = Self::archives()?.into_iter().map(|a| a.0).collect();
+        let mut plan = Vec::new();
+        for branch in branches {
+            let tag = Self::tag_name(branch, &date, &existing);
+            existing.push(tag.clone());
+            plan.push((branch.clone(), tag));
+        }
+        Ok(plan)
+    }
+
+    /// Keep the tip of `branch` as the annotated archive tag `tag`
+    pub fn tag(branch: &str, tag: &str) -> Result<()> {
+        let sha = GitOperations::run(&["rev-parse", "--short", branch])?;
+        GitOperations::run(&[
+            "tag",
+            "-a",
+            tag,
+            branch,
+            "-m",
+            &format!("Archived branch '{branch}' at {sha}"),
+        ])
+        .map(|_| ())
+    }
+
+    fn archive(&self, branches: &[String], remote: bool, dry_run: bool) -> Result<String> {
+        if branches.is_empty() {
+            return Err(GitXError::Parse(
+                "Specify at least one branch to archive".to_string(),
+            ));
+        }
+
+        let current_branch = GitOperations::current_branch()?;
+        for branch in branches {
+            if branch == &current_branch {
+                return Err(GitXError::GitCommand(format!(
+                    "Cannot archive the current branch '{branch}'; switch to another branch first"
+                )));
+            }
+            if !BranchOperations::exists(branch)? {
+                return Err(GitXError::GitCommand(format!(
+                    "Branch '{branch}' does not exist"
+                )));
+            }
+        }
+
+        let plan = Self::plan(branches)?;
+
+        if dry_run {
+            let mut output = BufferedOutput::new();
+            output.add_line(format!(
+                "🧪 (dry run) {} branch(es) would be archived:",
+                plan.len()
+            ));
+            for (branch, tag) in &plan {
+                output.add_line(format!("(dry run) {branch} → {tag}"));
+            }
+            return Ok(output.content());
+        }
+
+        let details = format!(
+            "This will replace {} branch(es) with archive tags{}: {}",
+            plan.len(),
+            if remote {
+                " and delete them on the remote"
+            } else {
+                ""
+            },
+            branches.join(", ")
+        );
+        if !Safety::confirm_destructive_operation("Archive branches", &details)? {
+            return Ok("Operation cancelled by user.".to_string());
+        }
+
+        let mut output = BufferedOutput::new();
+        for (branch, tag) in &plan {
+            Self::tag(branch, tag)?;
+
+            if remote {
+                let remote_name =
+                    GitOperations::run(&["config", &format!("branch.{branch}.remote")])
+                        .unwrap_or_else(|_| "origin".to_string());
+                // Publish the archive before removing the branch so nothing is lost remotely
+                GitOperations::run(&["push", &remote_name, &format!("refs/tags/{tag}")])?;
+                GitOperations::run(&["push", &remote_name, "--delete", branch])?;
+            }
+
+            GitOperations::run(&["branch", "-D", branch])?;
+            output.add_line(format!("📦 Archived {branch} → {tag}"));
+        }
+        output.add_line("💡 Restore with: git x archive restore <branch>".to_string());
+
+        Ok(output.content())
+    }
+
+    fn list() -> Result<String> {
+        let archives = Self::archives()?;
+        if archives.is_empty() {
+            return Ok("No archived branches.".to_string());
+        }
+
+        let mut table = TableFormatter::new(vec![
+            "Branch".to_string(),
+            "Archived".to_string(),
+            "Tag".to_string(),
+        ]);
+        for (tag, date, _) in &archives {
+            table.add_row(vec![
+                Self::branch_from_tag(tag).unwrap_or_else(|| "?".to_string()),
+                date.clone(),
+                tag.clone(),
+            ]);
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!("📦 {} archived branch(es):", archives.len()));
+        output.add_line(table.render().trim_end().to_string());
+        Ok(output.content())
+    }
+
+    fn restore(name: &str, as_branch: Option<&str>) -> Result<String> {
+        let archives = Self::archives()?;
+        // Accept the full tag, or a branch name meaning its most recent archive
+        let tag = archives
+            .iter()
+            .map(|a| &a.0)
+            .find(|tag| *tag == name || tag.strip_prefix(Self::PREFIX) == Some(name))
+            .or_else(|| {
+                archives
+                    .iter()
+                    .map(|a| &a.0)
+                    .find(|tag| Self::branch_from_tag(tag).as_deref() == Some(name))
+            })
+            .cloned()
+            .ok_or_else(|| GitXError::GitCommand(format!("No archive found for '{name}'")))?;
+
+        let branch = match as_branch {
+            Some(branch) => branch.to_string(),
+            None => Self::branch_from_tag(&tag).ok_or_else(|| {
+                GitXError::Parse(format!(
+                    "Cannot tell which branch '{tag}' came from; pass --as <branch>"
+                ))
+            })?,
+        };
+
+        Validate::branch_name(&branch)?;
+        if BranchOperations::exists(&branch)? {
+            return Err(GitXError::GitCommand(format!(
+                "Branch '{branch}' already exists; restore it under another name with --as"
+            )));
+        }
+
+        GitOperations::run(&["branch", &branch, &format!("{tag}^{{commit}}")])?;
+        GitOperations::run(&["tag", "-d", &tag])?;
+
+        Ok(format!(
+            "♻️  Restored {branch} from {tag}\n💡 If the archive was pushed, remove it with: git push <remote> --delete refs/tags/{tag}"
+        ))
+    }
+}
+
+impl Command for ArchiveCommand {
+    fn execute(&self) -> Result<String> {
+        match &self.action {
+            ArchiveAction::Archive {
+                branches,
+                remote,
+                dry_run,
+            } => self.archive(branches, *remote, *dry_run),
+            ArchiveAction::List => Self::list(),
+            ArchiveAction::Restore { name, as_branch } => Self::restore(name, as_branch.as_deref()),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "archive"
+    }
+
+    fn description(&self) -> &'static str {
+        "Archive branches as tags instead of deleting them"
+    }
+}
+
+impl GitCommand for ArchiveCommand {}
+
+impl Destructive for ArchiveCommand {
+    fn destruction_description(&self) -> String {
+        match &self.action {
+            ArchiveAction::Archive { remote: true, .. } => {
+                "This will replace branches with archive tags and delete them on the remote"
+                    .to_string()
+            }
+            _ => "This will replace branches with archive tags".to_string(),
+        }
+    }
+}
+
+/// What a branch in the merge queue conflicts with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictWith {
+    /// The base branch itself; the branch needs a rebase before anything else
+    Base,
+    /// A branch merged earlier in the queue
+    Branch(String),
+    /// Only the combination of earlier branches, not any single one
+    Queue,
+}
+
+/// How a branch fared when merged on top of everything queued before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueOutcome {
+    Clean,
+    AlreadyMerged,
+    Conflict {
+        with: ConflictWith,
+        files: Vec<String>,
+    },
+    Missing,
+}
+
+/// One branch in the merge queue, in merge order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueEntry {
+    pub branch: String,
+    /// Pull request number and title when the branch came from `gh`
+    pub pull_request: Option<String>,
+    pub outcome: QueueOutcome,
+}
+
+impl QueueEntry {
+    pub fn summary(&self, position: usize) -> String {
+        let name = match &self.pull_request {
+            Some(pr) => format!("{} ({pr})", self.branch),
+            None => self.branch.clone(),
+        };
+        let (icon, state) = match &self.outcome {
+            QueueOutcome::Clean => ("✅", "merges cleanly".to_string()),
+            QueueOutcome::AlreadyMerged => ("➖", "already merged".to_string()),
+            QueueOutcome::Missing => ("❓", "not found".to_string()),
+            QueueOutcome::Conflict { with, files } => {
+                let with = match with {
+                    ConflictWith::Base => "the base branch".to_string(),
+                    ConflictWith::Branch(branch) => branch.clone(),
+                    ConflictWith::Queue => "the branches queued before it".to_string(),
+                };
+                ("❌", format!("conflicts with {with}: {}", files.join(", ")))
+            }
+        };
+        format!("{position:>3}. {icon} {name} — {state}")
+    }
+}
+
+/// Command to simulate merging branches onto the default branch one after another
+///
+/// Merges happen in memory with `git merge-tree`, so neither the working tree
+/// nor any ref is touched. A conflicting branch is left out of the queue and
+/// the remaining branches are merged on top of the ones that went in.
+pub struct MergeQueueCommand {
+    branches: Vec<String>,
+    base: Option<String>,
+    pull_requests: bool,
+}
+
+impl MergeQueueCommand {
+    pub fn new(branches: Vec<String>) -> Self {
+        Self {
+            branches,
+            base: None,
+            pull_requests: false,
+        }
+    }
+
+    /// Merge onto `base` instead of the default branch
+    pub fn with_base(mut self, base: Option<String>) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Also queue the open pull requests against the base, oldest first, via `gh`
+    pub fn with_pull_requests(mut self) -> Self {
+        self.pull_requests = true;
+        self
+    }
+
+    /// Tree id and conflicted paths from `git merge-tree --write-tree --name-only` output
+    pub fn parse_merge_tree(output: &str) -> (String, Vec<String>) {
+        let mut lines = output.lines();
+        let tree = lines.next().unwrap_or_default().trim().to_string();
+        let mut files: Vec<String> = Vec::new();
+        for line in lines.take_while(|line| !line.trim().is_empty()) {
+            // Each conflicted stage is listed, so a path can appear up to three times
+            if !files.iter().any(|file| file == line) {
+                files.push(line.to_string());
+            }
+        }
+        (tree, files)
+    }
+
+    /// Open pull requests as (head branch, "#N title"), oldest first
+    pub fn parse_pull_requests(json: &str) -> Result<Vec<(String, String)>> {
+        let mut prs: Vec<(i64, String, String)> =
+            crate::core::export::ExportValue::parse_json(json)?
+                .items()
+                .iter()
+                .filter_map(|pr| {
+                    let number = match pr.get("number")? {
+                        crate::core::export::ExportValue::Int(number) => *number,
+                        _ => return None,
+                    };
+                    let head = pr.get("headRefName")?.as_str()?.to_string();
+                    let title = pr.get("title").and_then(|t| t.as_str()).unwrap_or_default();
+                    Some((number, head, format!("#{number} {title}")))
+                })
+                .collect();
+        prs.sort_by_key(|(number, _, _)| *number);
+        Ok(prs
+            .into_iter()
+            .map(|(_, head, label)| (head, label))
+            .collect())
+    }
+
+    fn default_base() -> Result<String> {
+        let branch = GitOperations::require_default_branch()?;
+        Ok(branch
+            .strip_prefix("origin/")
+            .map(String::from)
+            .unwrap_or(branch))
+    }
+
+    fn open_pull_requests(base: &str) -> Result<Vec<(String, String)>> {
+        Network::require("gh pr list")?;
+        let args = [
+            "pr",
+            "list",
+            "--state",
+            "open",
+            "--base",
+            base,
+            "--limit",
+            "100",
+            "--json",
+            "number,headRefName,title",
+        ];
+        let output = std::process::Command::new("gh")
+            .args(args)
+            .output()
+            .map_err(|e| GitXError::Other(format!("--prs needs the GitHub CLI (gh): {e}")))?;
+        if !output.status.success() {
+            return Err(GitXError::Other(format!(
+                "gh pr list failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Self::parse_pull_requests(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Commit a branch name points at, falling back to its `origin/` counterpart
+    fn resolve(branch: &str) -> Option<String> {
+        [branch.to_string(), format!("origin/{branch}")]
+            .iter()
+            .find_map(|name| {
+                GitOperations::run(&[
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("{name}^{{commit}}"),
+                ])
+                .ok()
+            })
+    }
+
+    /// Merge `theirs` into `ours` in memory: the resulting tree and any conflicted paths
+    pub fn merge_tree(ours: &str, theirs: &str) -> Result<(String, Vec<String>)> {
+        let args = [
+            "merge-tree",
+            "--write-tree",
+            "--name-only",
+            "--no-messages",
+            ours,
+            theirs,
+        ];
+        // 1 means the merge has conflicts, which is what we're looking for
+        match GitOperations::run_with_exit_codes(&args, &[0, 1]) {
+            Ok(output) => Ok(Self::parse_merge_tree(&output)),
+            Err(GitXError::GitProcess(failure)) if failure.status == Some(129) => {
+                Err(GitXError::Other(
+                    "Merging in memory needs git 2.38 or newer for 'git merge-tree --write-tree'"
+                        .to_string(),
+                ))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Unreferenced merge commit for `tree`, so the next branch can be merged on top
+    fn merge_commit(tree: &str, ours: &str, theirs: &str) -> Result<String> {
+        GitOperations::run(&[
+            "-c",
+            "user.name=git-x",
+            "-c",
+            "user.email=git-x@localhost",
+            "commit-tree",
+            tree,
+            "-p",
+            ours,
+            "-p",
+            theirs,
+            "-m",
+            "git-x merge-queue simulation",
+        ])
+    }
+
+    /// Merge each branch onto the base in order
+    pub fn simulate(&self, base: &str) -> Result<Vec<QueueEntry>> {
+        let mut queue: Vec<(String, Option<String>)> = self
+            .branches
+            .iter()
+            .map(|branch| (branch.clone(), None))
+            .collect();
+        if self.pull_requests {
+            for (head, label) in Self::open_pull_requests(base)? {
+                if !queue.iter().any(|(branch, _)| *branch == head) {
+                    queue.push((head, Some(label)));
+                }
+            }
+        }
+        if queue.is_empty() {
+            return Err(GitXError::Other(
+                "Nothing to queue: name some branches or use --prs".to_string(),
+            ));
+        }
+
+        let base_commit = Self::resolve(base)
+            .ok_or_else(|| GitXError::Other(format!("Base branch '{base}' not found")))?;
+        let mut tip = base_commit.clone();
+        let mut merged: Vec<(String, String)> = Vec::new();
+        let mut entries = Vec::new();
+
+        for (branch, pull_request) in queue {
+            let outcome = match Self::resolve(&branch) {
+                None => QueueOutcome::Missing,
+                Some(commit)
+                    if GitOperations::run(&["merge-base", "--is-ancestor", &commit, &tip])
+                        .is_ok() =>
+                {
+                    QueueOutcome::AlreadyMerged
+                }
+                Some(commit) => {
+                    let (tree, files) = Self::merge_tree(&tip, &commit)?;
+                    if files.is_empty() {
+                        tip = Self::merge_commit(&tree, &tip, &commit)?;
+                        merged.push((branch.clone(), commit));
+                        QueueOutcome::Clean
+                    } else {
+                        let with = Self::conflict_source(&base_commit, &merged, &commit)?;
+                        QueueOutcome::Conflict { with, files }
+                    }
+                }
+            };
+            entries.push(QueueEntry {
+                branch,
+                pull_request,
+                outcome,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// The first thing a conflicting branch can't be merged with on its own
+    fn conflict_source(
+        base: &str,
+        merged: &[(String, String)],
+        commit: &str,
+    ) -> Result<ConflictWith> {
+        if !Self::merge_tree(base, commit)?.1.is_empty() {
+            return Ok(ConflictWith::Base);
+        }
+        for (branch, earlier) in merged {
+            if !Self::merge_tree(earlier, commit)?.1.is_empty() {
+                return Ok(ConflictWith::Branch(branch.clone()));
+            }
+        }
+        Ok(ConflictWith::Queue)
+    }
+}
+
+impl Command for MergeQueueCommand {
+    fn execute(&self) -> Result<String> {
+        let base = match &self.base {
+            Some(base) => base.clone(),
+            None => Self::default_base()?,
+        };
+        let entries = self.simulate(&base)?;
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🚂 Merge queue onto {base} ({} branch(es))",
+            entries.len()
+        ));
+        output.add_line("=".repeat(50));
+        for (index, entry) in entries.iter().enumerate() {
+            output.add_line(entry.summary(index + 1));
+        }
+        output.add_line(String::new());
+
+        let first_conflict = entries.iter().find_map(|entry| match &entry.outcome {
+            QueueOutcome::Conflict { with, files } => Some((entry, with, files)),
+            _ => None,
+        });
+        let clean = entries
+            .iter()
+            .filter(|entry| entry.outcome == QueueOutcome::Clean)
+            .count();
+        match first_conflict {
+            None => output.add_line(format!(
+                "✅ {clean} branch(es) merge cleanly onto {base} in this order"
+            )),
+            Some((entry, with, files)) => {
+                let pair = match with {
+                    ConflictWith::Base => format!("{base} + {}", entry.branch),
+                    ConflictWith::Branch(branch) => format!("{branch} + {}", entry.branch),
+                    ConflictWith::Queue => format!("queue + {}", entry.branch),
+                };
+                output.add_line(format!("❌ First conflict: {pair} ({})", files.join(", ")));
+                output.add_line(format!(
+                    "   {clean} of {} branch(es) merge cleanly; conflicting branches were left out of the queue",
+                    entries.len()
+                ));
+            }
+        }
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "merge-queue"
+    }
+
+    fn description(&self) -> &'static str {
+        "Simulate merging branches onto the default branch in order"
+    }
+}
+
+impl GitCommand for MergeQueueCommand {}
+
+/// A commit as `move` shows it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedCommit {
+    pub sha: String,
+    pub subject: String,
+}
+
+impl PlannedCommit {
+    /// Parse `git log --format=%H%x00%s` output
+    pub fn parse_log(output: &str) -> Vec<Self> {
+        output
+            .lines()
+            .filter_map(|line| line.split_once('\0'))
+            .map(|(sha, subject)| Self {
+                sha: sha.to_string(),
+                subject: subject.to_string(),
+            })
+            .collect()
+    }
+
+    fn lookup(rev: &str) -> Result<Self> {
+        let output = GitOperations::run(&["log", "-1", "--format=%H%x00%s", rev, "--"])?;
+        Self::parse_log(&output)
+            .pop()
+            .ok_or_else(|| GitXError::Parse(format!("Invalid commit reference: '{rev}'")))
+    }
+
+    fn short(&self) -> &str {
+        short_sha(&self.sha)
+    }
+
+    fn label(&self) -> String {
+        format!("{} {}", self.short(), self.subject)
+    }
+}
+
+/// The commits after an old base on a branch, to be replayed onto a new base
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovePlan {
+    pub branch: String,
+    /// Newest first
+    pub commits: Vec<PlannedCommit>,
+    pub old_base: PlannedCommit,
+    /// Branch or ref the old base was given as, if any
+    pub old_base_name: Option<String>,
+    pub onto: String,
+    pub new_base: PlannedCommit,
+}
+
+impl MovePlan {
+    /// History of the branch before and after the move, newest commit first
+    pub fn shape(&self) -> Vec<String> {
+        let mut lines = vec!["Before:".to_string()];
+        lines.extend(self.column("", &self.old_base, self.old_base_name.as_deref()));
+        lines.push(String::new());
+        lines.push("After:".to_string());
+        lines.extend(self.column("'", &self.new_base, Some(&self.onto)));
+        lines
+    }
+
+    fn column(&self, mark: &str, base: &PlannedCommit, base_name: Option<&str>) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .commits
+            .iter()
+            .enumerate()
+            .map(|(index, commit)| {
+                let name = if index == 0 {
+                    format!("  ({})", self.branch)
+                } else {
+                    String::new()
+                };
+                format!("   ● {}{mark} {}{name}", commit.short(), commit.subject)
+            })
+            .collect();
+        let name = base_name
+            .map(|name| format!("  ({name})"))
+            .unwrap_or_default();
+        lines.push(format!("   ○ {}{name}", base.label()));
+        lines
+    }
+}
+
+/// Most commits offered when picking where the moved range starts
+const MOVE_CANDIDATES: usize = 30;
+
+/// Command to move a range of commits onto another base (`git rebase --onto`)
+pub struct MoveCommand {
+    onto: Option<RefName>,
+    branch: Option<Branch>,
+    from: Option<RefName>,
+    dry_run: bool,
+}
+
+impl MoveCommand {
+    pub fn new(onto: Option<RefName>) -> Self {
+        Self {
+            onto,
+            branch: None,
+            from: None,
+            dry_run: false,
+        }
+    }
+
+    /// Move the commits of `branch` instead of the current branch
+    pub fn with_branch(mut self, branch: Option<Branch>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Move the commits after `from` instead of the ones unique to the branch
+    pub fn with_from(mut self, from: Option<RefName>) -> Self {
+        self.from = from;
+        self
+    }
+
+    /// Show the resulting history without moving anything
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    fn pick_branch(&self) -> Result<String> {
+        if let Some(branch) = &self.branch {
+            if !BranchOperations::exists(branch.as_str())? {
+                return Err(GitXError::GitCommand(format!(
+                    "Branch '{branch}' does not exist"
+                )));
+            }
+            return Ok(branch.to_string());
+        }
+        let current = GitOperations::require_branch("move commits")?;
+        if !Interactive::is_interactive() {
+            return Ok(current);
+        }
+        let mut branches = vec![current.clone()];
+        branches.extend(
+            GitOperations::recent_branches(None)?
+                .into_iter()
+                .filter(|branch| *branch != current),
+        );
+        Interactive::branch_picker(&branches, Some("Branch whose commits to move"))
+    }
+
+    fn pick_onto(&self, branch: &str) -> Result<String> {
+        if let Some(onto) = &self.onto {
+            return Ok(onto.to_string());
+        }
+        if !Interactive::is_interactive() {
+            return Err(GitXError::Parse(
+                "No new base given. Pass --onto <base>".to_string(),
+            ));
+        }
+        // Recent branches leave out the current one, which is a fine base for another branch
+        let mut branches = GitOperations::recent_branches(None)?;
+        branches.extend(GitOperations::current_branch().ok());
+        branches.retain(|other| other != branch && other != "HEAD");
+        Interactive::branch_picker(&branches, Some("New base to move them onto"))
+    }
+
+    /// Commits of `branch` on no other local branch, oldest first
+    pub fn unique_commits(branch: &str) -> Result<Vec<String>> {
+        let others: Vec<String> = GitOperations::local_branches()?
+            .into_iter()
+            .filter(|other| other != branch)
+            .collect();
+        let mut args = vec!["rev-list", "--reverse", "--topo-order", branch];
+        if !others.is_empty() {
+            args.push("--not");
+            args.extend(others.iter().map(String::as_str));
+        }
+        Ok(GitOperations::run(&args)?
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    /// Old base and, when known, the branch it is the tip of or lies on
+    fn pick_from(&self, branch: &str, onto: &str) -> Result<(String, Option<String>)> {
+        if let Some(from) = &self.from {
+            return Ok((from.to_string(), Some(from.to_string())));
+        }
+
+        let unique = Self::unique_commits(branch)?;
+        let candidates = PlannedCommit::parse_log(&GitOperations::run(&[
+            "log",
+            "--format=%H%x00%s",
+            &format!("--max-count={MOVE_CANDIDATES}"),
+            &format!("{onto}..{branch}"),
+            "--",
+        ])?);
+
+        let oldest = if Interactive::is_interactive() && !candidates.is_empty() {
+            let labels: Vec<String> = candidates.iter().map(PlannedCommit::label).collect();
+            let default = unique
+                .first()
+                .and_then(|sha| candidates.iter().position(|c| c.sha == *sha))
+                .unwrap_or(candidates.len() - 1);
+            let picked =
+                Interactive::fuzzy_select(&labels, "Oldest commit to move", Some(default))?;
+            let index = labels
+                .iter()
+                .position(|label| *label == picked)
+                .unwrap_or(default);
+            candidates[index].sha.clone()
+        } else {
+            unique.first().cloned().ok_or_else(|| {
+                GitXError::GitCommand(format!(
+                    "Every commit on '{branch}' is also on another branch. Pass --from <old base>"
+                ))
+            })?
+        };
+
+        let base = GitOperations::run(&["rev-parse", "--verify", "--quiet", &format!("{oldest}^")])
+            .map_err(|_| {
+                GitXError::GitCommand(format!(
+                    "The commits to move start at the root commit of '{branch}'. Pass --from <old base>"
+                ))
+            })?;
+        let base_name =
+            GitOperations::run(&["branch", "--format=%(refname:short)", "--points-at", &base])?
+                .lines()
+                .find(|name| *name != branch)
+                .map(String::from);
+        Ok((base, base_name))
+    }
+
+    fn plan(&self) -> Result<MovePlan> {
+        let branch = self.pick_branch()?;
+        let onto = self.pick_onto(&branch)?;
+        let new_base = PlannedCommit::lookup(&onto)
+            .map_err(|_| GitXError::Parse(format!("Invalid commit reference: '{onto}'")))?;
+        let (from, old_base_name) = self.pick_from(&branch, &onto)?;
+        let old_base = PlannedCommit::lookup(&from)?;
+        // A commit hash names nothing more than the commit itself
+        let old_base_name = old_base_name.filter(|name| !old_base.sha.starts_with(name.as_str()));
+
+        if GitOperations::run(&["merge-base", "--is-ancestor", &old_base.sha, &branch]).is_err() {
+            return Err(GitXError::GitCommand(format!(
+                "'{from}' is not in the history of '{branch}'"
+            )));
+        }
+        let range = format!("{}..{branch}", old_base.sha);
+        if !GitOperations::run(&["rev-list", "--merges", &range])?.is_empty() {
+            return Err(GitXError::GitCommand(
+                "Cannot move across merge commits; the rebase would flatten them".to_string(),
+            ));
+        }
+        let commits = PlannedCommit::parse_log(&GitOperations::run(&[
+            "log",
+            "--format=%H%x00%s",
+            &range,
+            "--",
+        ])?);
+        if commits.is_empty() {
+            return Err(GitXError::GitCommand(format!(
+                "Nothing to move: '{branch}' has no commits after '{from}'"
+            )));
+        }
+
+        Ok(MovePlan {
+            branch,
+            commits,
+            old_base,
+            old_base_name,
+            onto,
+            new_base,
+        })
+    }
+
+    /// Number of the moved commits that already exist on a remote
+    fn count_pushed(plan: &MovePlan) -> Result<usize> {
+        let unpushed = GitOperations::run(&[
+            "rev-list",
+            "--count",
+            &format!("{}..{}", plan.old_base.sha, plan.branch),
+            "--not",
+            "--remotes",
+        ])?;
+        let unpushed: usize = unpushed.parse().unwrap_or(plan.commits.len());
+        Ok(plan.commits.len().saturating_sub(unpushed))
+    }
+}
+
+impl Command for MoveCommand {
+    fn execute(&self) -> Result<String> {
+        let plan = self.plan()?;
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🚚 Move {} commit(s) of '{}' onto '{}'",
+            plan.commits.len(),
+            plan.branch,
+            plan.onto
+        ));
+        output.add_line(String::new());
+        output.add_lines(plan.shape());
+        output.add_line(String::new());
+
+        if plan.old_base.sha == plan.new_base.sha {
+            output.add_line(format!(
+                "✅ Nothing to do: '{}' already sits on '{}'",
+                plan.branch, plan.onto
+            ));
+            return Ok(output.content());
+        }
+
+        if self.dry_run {
+            output.add_line(format!(
+                "💡 Dry run: nothing was moved. The same as 'git rebase --onto {} {} {}'",
+                plan.onto,
+                plan.old_base.short(),
+                plan.branch
+            ));
+            return Ok(output.content());
+        }
+
+        if !GitOperations::run(&["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+            return Err(GitXError::GitCommand(
+                "Working directory is not clean. Please commit or stash your changes first."
+                    .to_string(),
+            ));
+        }
+
+        let pushed = Self::count_pushed(&plan)?;
+        if pushed > 0 {
+            output.add_line(format!(
+                "⚠️  {pushed} of {} commits are already pushed; you will need to force-push",
+                plan.commits.len()
+            ));
+            if !self.confirm_destruction()? {
+                return Ok("Operation cancelled by user.".to_string());
+            }
+        } else if Interactive::is_interactive()
+            && !Interactive::confirm(&format!("Move {} commit(s)?", plan.commits.len()), true)?
+        {
+            return Ok("Operation cancelled by user.".to_string());
+        }
+
+        let current = GitOperations::run(&["symbolic-ref", "--quiet", "--short", "HEAD"]).ok();
+        let original_tip = GitOperations::run(&["rev-parse", &plan.branch])?;
+
+        // A conflict aborts the rebase and puts every branch back where it was
+        crate::core::safety::Transaction::begin("move")?.run(|| {
+            GitOperations::run(&[
+                "rebase",
+                "--onto",
+                &plan.new_base.sha,
+                &plan.old_base.sha,
+                &plan.branch,
+            ])
+            .map_err(|e| {
+                let conflicts =
+                    GitOperations::run(&["diff", "--name-only", "--diff-filter=U"])
+                        .unwrap_or_default();
+                if conflicts.is_empty() {
+                    return e;
+                }
+                GitXError::GitCommand(format!(
+                    "Moving '{}' onto '{}' conflicts in {}. To resolve by hand, run: git rebase --onto {} {} {}",
+                    plan.branch,
+                    plan.onto,
+                    conflicts.lines().collect::<Vec<_>>().join(", "),
+                    plan.onto,
+                    plan.old_base.short(),
+                    plan.branch
+                ))
+            })?;
+            // The rebase checks the moved branch out; go back to where the user was
+            match &current {
+                Some(current) if *current != plan.branch => {
+                    GitOperations::run(&["checkout", current]).map(|_| ())
+                }
+                _ => Ok(()),
+            }
+        })?;
+
+        let new_tip = GitOperations::short_hash(&plan.branch)?;
+        let original_short = GitOperations::short_hash(&original_tip)?;
+        output.add_line(format!(
+            "✅ Moved {} commit(s) of '{}' onto '{}' (now at {new_tip})",
+            plan.commits.len(),
+            plan.branch,
+            plan.onto
+        ));
+        output.add_line(if current.as_deref() == Some(plan.branch.as_str()) {
+            format!("💡 To undo, run: git reset --hard {original_short}")
+        } else {
+            format!(
+                "💡 To undo, run: git branch -f {} {original_short}",
+                plan.branch
+            )
+        });
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "move"
+    }
+
+    fn description(&self) -> &'static str {
+        "Move a range of commits onto another base"
+    }
+}
+
+impl GitCommand for MoveCommand {}
+
+impl DryRunnable for MoveCommand {
+    fn execute_dry_run(&self) -> Result<String> {
+        MoveCommand {
+            onto: self.onto.clone(),
+            branch: self.branch.clone(),
+            from: self.from.clone(),
+            dry_run: true,
+        }
+        .execute()
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+impl Destructive for MoveCommand {
+    fn destruction_description(&self) -> String {
+        "This will rewrite the moved commits onto a new base".to_string()
+    }
+}
+
+/// Marker identifying the pre-commit and pre-push guards written by `protect`
+const PROTECT_HOOK_MARKER: &str = "git x protect --check";
+
+/// Branches guarded locally, from `git-x.protect.branch`; `*` matches any run of characters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtectedBranches {
+    patterns: Vec<String>,
+}
+
+impl ProtectedBranches {
+    pub fn new(patterns: Vec<String>) -> Self {
+        Self { patterns }
+    }
+
+    /// The branches this repository protects
+    pub fn load() -> Self {
+        Self::new(Config::get_list("protect.branch"))
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    pub fn matches(&self, branch: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| glob_match(pattern, branch))
+    }
+
+    /// Protected branches a push would update or delete, from the lines git feeds a pre-push hook:
+    /// `<local ref> <local sha> <remote ref> <remote sha>`
+    pub fn blocked_pushes(&self, updates: &str) -> Vec<String> {
+        let mut blocked: Vec<String> = updates
+            .lines()
+            .filter_map(|line| line.split_whitespace().nth(2))
+            .filter_map(|remote_ref| remote_ref.strip_prefix("refs/heads/"))
+            .filter(|branch| self.matches(branch))
+            .map(str::to_string)
+            .collect();
+        blocked.dedup();
+        blocked
+    }
+}
+
+/// What the installed hooks ask `protect --check` to verify
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtectCheck {
+    Commit,
+    Push,
+}
+
+impl std::str::FromStr for ProtectCheck {
+    type Err = GitXError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "commit" => Ok(ProtectCheck::Commit),
+            "push" => Ok(ProtectCheck::Push),
+            other => Err(GitXError::Parse(format!(
+                "Unknown protect check '{other}', expected commit or push"
+            ))),
+        }
+    }
+}
+
+/// Command that registers protected branches and installs local guards against
+/// committing or pushing to them directly
+pub struct ProtectCommand {
+    branches: Vec<String>,
+    remove: bool,
+    check: Option<ProtectCheck>,
+}
+
+impl ProtectCommand {
+    pub fn new(branches: Vec<String>) -> Self {
+        Self {
+            branches,
+            remove: false,
+            check: None,
+        }
+    }
+
+    /// Stop protecting the given branches
+    pub fn with_remove(mut self) -> Self {
+        self.remove = true;
+        self
+    }
+
+    /// Run the guard for a commit or push instead of changing the configuration
+    pub fn with_check(mut self, check: ProtectCheck) -> Self {
+        self.check = Some(check);
+        self
+    }
+
+    /// Rendered output and whether the commit or push should be blocked
+    pub fn run(&self) -> Result<(String, bool)> {
+        match self.check {
+            Some(ProtectCheck::Commit) => Self::check_commit(&ProtectedBranches::load()),
+            Some(ProtectCheck::Push) => {
+                let mut updates = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut updates)?;
+                Ok(Self::check_push(&ProtectedBranches::load(), &updates))
+            }
+            None if self.remove => self.unprotect().map(|output| (output, false)),
+            None if self.branches.is_empty() => Ok((Self::list(), false)),
+            None => self.protect().map(|output| (output, false)),
+        }
+    }
+
+    fn list() -> String {
+        let protected = ProtectedBranches::load();
+        if protected.patterns().is_empty() {
+            return "🛡️  No protected branches. Protect one with 'git x protect <branch>'"
+                .to_string();
+        }
+        let mut output = BufferedOutput::new();
+        output.add_line("🛡️  Protected branches:".to_string());
+        for pattern in protected.patterns() {
+            output.add_line(format!("  {pattern}"));
+        }
+        output.content()
+    }
+
+    fn protect(&self) -> Result<String> {
+        let existing = ProtectedBranches::load();
+        let mut output = BufferedOutput::new();
+
+        for branch in &self.branches {
+            if branch.trim().is_empty() || branch.chars().any(char::is_whitespace) {
+                return Err(GitXError::Parse(format!(
+                    "'{branch}' is not a valid branch name or pattern"
+                )));
+            }
+            if existing.patterns().contains(branch) {
+                output.add_line(format!("✅ '{branch}' is already protected"));
+                continue;
+            }
+            GitOperations::run(&["config", "--add", "git-x.protect.branch", branch])?;
+            output.add_line(format!("🛡️  Protected '{branch}'"));
+        }
+
+        for (hook, check) in [("pre-commit", "commit"), ("pre-push", "push")] {
+            output.add_line(Self::install_hook(hook, check)?);
+        }
+        output.add_line(
+            "💡 Bypass once with 'git commit --no-verify' or 'git push --no-verify'".to_string(),
+        );
+        Ok(output.content())
+    }
+
+    fn unprotect(&self) -> Result<String> {
+        let existing = ProtectedBranches::load();
+        let mut output = BufferedOutput::new();
+
+        for branch in &self.branches {
+            if !existing.patterns().contains(branch) {
+                output.add_line(format!("ℹ️  '{branch}' was not protected"));
+                continue;
+            }
+            GitOperations::run(&[
+                "config",
+                "--fixed-value",
+                "--unset-all",
+                "git-x.protect.branch",
+                branch,
+            ])?;
+            output.add_line(format!("🔓 '{branch}' is no longer protected"));
+        }
+        // The hooks stay installed and do nothing without protected branches
+        Ok(output.content())
+    }
+
+    /// Add the guard to a hook, keeping whatever the hook already runs
+    fn install_hook(hook_name: &str, check: &str) -> Result<String> {
+        let hook = GitOperations::run(&["rev-parse", "--git-path", &format!("hooks/{hook_name}")])?;
+        let path = std::path::Path::new(&hook);
+        let guard = format!("{PROTECT_HOOK_MARKER} {check} || exit 1");
+
+        let content = match std::fs::read_to_string(path) {
+            Ok(existing) if existing.contains(PROTECT_HOOK_MARKER) => {
+                return Ok(format!("✅ {hook_name} guard already installed at {hook}"));
+            }
+            Ok(existing) => {
+                // Only shell hooks can take the guard as their first command
+                let (shebang, rest) = existing.split_once('\n').unwrap_or((&existing, ""));
+                if !(shebang.starts_with("#!") && shebang.ends_with("sh")) {
+                    return Err(GitXError::Other(format!(
+                        "A {hook_name} hook that isn't a shell script exists at {hook}; make it run '{guard}'"
+                    )));
+                }
+                format!(
+                    "{shebang}\n# Added by git-x: block direct {check}es to protected branches\n{guard}\n{rest}"
+                )
+            }
+            Err(_) => format!(
+                "#!/bin/sh\n# Installed by git-x: block direct {check}es to protected branches\n{guard}\n"
+            ),
+        };
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, content)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        Ok(format!("🪝 Installed {hook_name} guard at {hook}"))
+    }
+
+    fn check_commit(protected: &ProtectedBranches) -> Result<(String, bool)> {
+        // Detached HEAD commits reach no branch
+        let Ok(branch) = GitOperations::run(&["symbolic-ref", "--quiet", "--short", "HEAD"]) else {
+            return Ok((String::new(), false));
+        };
+        if !protected.matches(&branch) {
+            return Ok((String::new(), false));
+        }
+
+        if Interactive::is_interactive() {
+            let name = Interactive::text_input(
+                &format!(
+                    "'{branch}' is protected. Commit on a new branch instead (empty to cancel)"
+                ),
+                None,
+                None,
+            )?;
+            let name = name.trim();
+            if !name.is_empty() {
+                let new_branch: Branch = name.parse()?;
+                // The index is untouched, so the commit lands on the new branch
+                GitOperations::run(&["switch", "--quiet", "--create", new_branch.as_str()])?;
+                return Ok((
+                    format!("🌿 Committing on new branch '{new_branch}' instead of '{branch}'"),
+                    false,
+                ));
+            }
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🛡️  '{branch}' is protected: commit on a feature branch instead"
+        ));
+        output.add_line(
+            "💡 git switch -c <feature-branch>, then commit again; your changes come along"
+                .to_string(),
+        );
+        output.add_line("💡 Bypass once with 'git commit --no-verify'".to_string());
+        Ok((output.content(), true))
+    }
+
+    fn check_push(protected: &ProtectedBranches, updates: &str) -> (String, bool) {
+        let blocked = protected.blocked_pushes(updates);
+        if blocked.is_empty() {
+            return (String::new(), false);
+        }
+
+        let mut output = BufferedOutput::new();
+        for branch in &blocked {
+            output.add_line(format!(
+                "🛡️  Pushing to protected branch '{branch}' is blocked"
+            ));
+        }
+        output.add_line(
+            "💡 Push a feature branch and open a pull request: git switch -c <feature-branch> && git push -u origin <feature-branch>"
+                .to_string(),
+        );
+        output.add_line("💡 Bypass once with 'git push --no-verify'".to_string());
+        (output.content(), true)
+    }
+}
+
+impl Command for ProtectCommand {
+    fn execute(&self) -> Result<String> {
+        self.run().map(|(output, _)| output)
+    }
+
+    fn name(&self) -> &'static str {
+        "protect"
+    }
+
+    fn description(&self) -> &'static str {
+        "Guard branches locally against direct commits and pushes"
+    }
+}
+
+impl GitCommand for ProtectCommand {}
+
+/// A pull request as `gh pr view --json` describes it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    /// OPEN, CLOSED or MERGED
+    pub state: String,
+    pub head_branch: String,
+    /// Owner and name of the fork, `None` when the branch lives in the base repository
+    pub fork: Option<(String, String)>,
+    /// Whether people with push rights to the base repository may push to the fork
+    pub maintainer_can_modify: bool,
+}
+
+impl PullRequest {
+    /// Fields requested from `gh pr view --json`
+    pub const GH_FIELDS: &'static str = "number,title,state,headRefName,headRepository,headRepositoryOwner,isCrossRepository,maintainerCanModify";
+
+    pub fn parse(json: &str) -> Result<Self> {
+        use crate::core::export::ExportValue;
+
+        let value = ExportValue::parse_json(json)?;
+        let text = |key: &str| {
+            value
+                .get(key)
+                .and_then(ExportValue::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let flag = |key: &str| matches!(value.get(key), Some(ExportValue::Bool(true)));
+        let number = match value.get("number") {
+            Some(ExportValue::Int(number)) if *number > 0 => *number as u64,
+            _ => {
+                return Err(GitXError::Parse(
+                    "gh returned a pull request without a number".to_string(),
+                ));
+            }
+        };
+        let nested = |key: &str, field: &str| {
+            value
+                .get(key)
+                .and_then(|record| record.get(field))
+                .and_then(ExportValue::as_str)
+                .map(str::to_string)
+        };
+        let fork = if flag("isCrossRepository") {
+            nested("headRepositoryOwner", "login").zip(nested("headRepository", "name"))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            number,
+            title: text("title"),
+            state: text("state"),
+            head_branch: text("headRefName"),
+            fork,
+            maintainer_can_modify: flag("maintainerCanModify"),
+        })
+    }
+
+    /// Local branch every pull request is checked out as
+    pub fn local_branch(number: u64) -> String {
+        format!("pr/{number}")
+    }
+
+    /// URL of `owner/name` on the same host, in the same style, as `base_url`
+    pub fn fork_url(base_url: &str, owner: &str, name: &str) -> Option<String> {
+        let (_, path) = crate::core::ci::Ci::parse_remote(base_url)?;
+        let start = base_url.rfind(&path)?;
+        Some(format!(
+            "{}{owner}/{name}{}",
+            &base_url[..start],
+            &base_url[start + path.len()..]
+        ))
+    }
+}
+
+/// Pull request actions
+#[derive(Debug, Clone)]
+pub enum PrAction {
+    /// Fetch a pull request into `pr/<number>` and switch to it
+    Checkout { number: u64, remote: Option<String> },
+    /// Delete `pr/*` branches whose pull request is closed or merged
+    Clean { all: bool, dry_run: bool },
+}
+
+/// Command that checks out pull requests, forks included, as local branches and cleans them up
+pub struct PrCommand {
+    action: PrAction,
+}
+
+impl PrCommand {
+    pub fn new(action: PrAction) -> Self {
+        Self { action }
+    }
+
+    /// Remote the pull requests are opened against: `upstream` in a fork, otherwise `origin`
+    fn base_remote(remote: Option<&str>) -> Result<String> {
+        let remotes = GitOperations::run(&["remote"])?;
+        let remotes: Vec<&str> = remotes.lines().collect();
+        if let Some(remote) = remote {
+            return remotes
+                .contains(&remote)
+                .then(|| remote.to_string())
+                .ok_or_else(|| GitXError::GitCommand(format!("No remote named '{remote}'")));
+        }
+        ["upstream", "origin"]
+            .into_iter()
+            .find(|name| remotes.contains(name))
+            .map(str::to_string)
+            .ok_or_else(|| {
+                GitXError::GitCommand(
+                    "No 'upstream' or 'origin' remote; pass --remote <name>".to_string(),
+                )
+            })
+    }
+
+    /// The pull request from gh, or why it couldn't be looked up
+    fn view(number: u64, fields: &str) -> std::result::Result<String, String> {
+        Network::require("gh pr view").map_err(|e| e.to_string())?;
+        let number = number.to_string();
+        match std::process::Command::new("gh")
+            .args(["pr", "view", &number, "--json", fields])
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            }
+            Ok(output) => Err(String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .next()
+                .unwrap_or("gh pr view failed")
+                .to_string()),
+            Err(_) => Err("the GitHub CLI (gh) is not installed".to_string()),
+        }
+    }
+
+    fn checkout(number: u64, remote: Option<&str>) -> Result<String> {
+        let base = Self::base_remote(remote)?;
+        let branch = PullRequest::local_branch(number);
+        let mut output = BufferedOutput::new();
+
+        let pull_request = match Self::view(number, PullRequest::GH_FIELDS) {
+            Ok(json) => Some(PullRequest::parse(&json)?),
+            Err(reason) => {
+                output.add_line(format!(
+                    "ℹ️  Couldn't look up #{number} with gh ({reason}), fetching it without push setup"
+                ));
+                None
+            }
+        };
+
+        // GitLab keeps merge requests under their own refs
+        let base_url = GitOperations::run(&["remote", "get-url", &base])?;
+        let pr_ref = if base_url.contains("gitlab") {
+            format!("refs/merge-requests/{number}/head")
+        } else {
+            format!("refs/pull/{number}/head")
+        };
+        GitOperations::run(&["fetch", "--quiet", &base, &pr_ref]).map_err(|e| {
+            if e.to_string().contains("couldn't find remote ref") {
+                GitXError::GitCommand(format!("Pull request #{number} was not found on '{base}'"))
+            } else {
+                e
+            }
+        })?;
+        let fetched = GitOperations::run(&["rev-parse", "FETCH_HEAD"])?;
+
+        match GitOperations::run(&["rev-parse", "--verify", "--quiet", &branch]) {
+            Err(_) => {
+                GitOperations::run(&["branch", &branch, &fetched])?;
+            }
+            Ok(tip) if tip == fetched => {}
+            Ok(tip) => {
+                let fast_forward =
+                    GitOperations::run(&["merge-base", "--is-ancestor", &tip, &fetched]).is_ok();
+                let current = GitOperations::current_branch().ok();
+                if !fast_forward {
+                    output.add_line(format!(
+                        "⚠️  '{branch}' has diverged from #{number} (local commits or a force-push), kept as is"
+                    ));
+                } else if current.as_deref() == Some(branch.as_str()) {
+                    GitOperations::run(&["merge", "--ff-only", "--quiet", &fetched])?;
+                } else {
+                    GitOperations::run(&["branch", "--force", &branch, &fetched])?;
+                }
+            }
+        }
+        GitOperations::run(&[
+            "config",
+            &format!("branch.{branch}.gitxPr"),
+            &number.to_string(),
+        ])?;
+
+        if let Some(pr) = &pull_request {
+            output.add_line(format!(
+                "🔀 Checked out #{number} \"{}\" as '{branch}'",
+                pr.title
+            ));
+            match &pr.fork {
+                None => {
+                    Self::track(&branch, &base, &pr.head_branch)?;
+                    output.add_line(format!(
+                        "🚀 Push with: git push {base} HEAD:{}",
+                        pr.head_branch
+                    ));
+                }
+                Some((owner, name)) if pr.maintainer_can_modify => {
+                    let fork = Self::fork_remote(&base_url, owner, name)?;
+                    // Only needed for tracking; pushing works without it
+                    let _ = GitOperations::run(&["fetch", "--quiet", &fork, &pr.head_branch]);
+                    Self::track(&branch, &fork, &pr.head_branch)?;
+                    output.add_line(format!(
+                        "🚀 Pushes go to {owner}/{name}:{}: git push {fork} HEAD:{}",
+                        pr.head_branch, pr.head_branch
+                    ));
+                }
+                Some((owner, name)) => output.add_line(format!(
+                    "🔒 {owner}/{name} doesn't allow edits from maintainers: review here, but you can't push to #{number}"
+                )),
+            }
+        } else {
+            output.add_line(format!("🔀 Checked out #{number} as '{branch}'"));
+        }
+
+        if GitOperations::current_branch().ok().as_deref() != Some(branch.as_str()) {
+            GitOperations::run(&["switch", "--quiet", &branch])?;
+        }
+        output.add_line("💡 Delete it once the PR is closed with 'git x pr clean'".to_string());
+        Ok(output.content())
+    }
+
+    /// Make `branch` follow `head` on `remote`, so pull and status compare against the PR
+    fn track(branch: &str, remote: &str, head: &str) -> Result<()> {
+        GitOperations::run(&["config", &format!("branch.{branch}.remote"), remote])?;
+        GitOperations::run(&[
+            "config",
+            &format!("branch.{branch}.merge"),
+            &format!("refs/heads/{head}"),
+        ])
+        .map(|_| ())
+    }
+
+    /// Remote for a contributor's fork, added (and marked for `pr clean`) if missing
+    fn fork_remote(base_url: &str, owner: &str, name: &str) -> Result<String> {
+        let url = PullRequest::fork_url(base_url, owner, name).ok_or_else(|| {
+            GitXError::Parse(format!("Could not work out the URL of {owner}/{name}"))
+        })?;
+        let expected = crate::core::ci::Ci::parse_remote(&url);
+
+        for remote in [owner.to_string(), format!("pr-{owner}")] {
+            match GitOperations::run(&["remote", "get-url", &remote]) {
+                Ok(existing) if crate::core::ci::Ci::parse_remote(&existing) == expected => {
+                    return Ok(remote);
+                }
+                // Taken by something else
+                Ok(_) => continue,
+                Err(_) => {
+                    GitOperations::run(&["remote", "add", &remote, &url])?;
+                    GitOperations::run(&["config", &format!("remote.{remote}.gitxPr"), "true"])?;
+                    return Ok(remote);
+                }
+            }
+        }
+        Err(GitXError::GitCommand(format!(
+            "Remotes '{owner}' and 'pr-{owner}' already point elsewhere"
+        )))
+    }
+
+    /// Pull request branches checked out by `pr checkout`, with their numbers
+    fn pr_branches() -> Vec<(String, u64)> {
+        GitOperations::run(&["config", "--get-regexp", r"^branch\..*\.gitxpr$"])
+            .map(|output| {
+                output
+                    .lines()
+                    .filter_map(|line| {
+                        let (key, number) = line.split_once(' ')?;
+                        let branch = key.strip_prefix("branch.")?.strip_suffix(".gitxpr")?;
+                        Some((branch.to_string(), number.parse().ok()?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn clean(all: bool, dry_run: bool) -> Result<String> {
+        let branches = Self::pr_branches();
+        if branches.is_empty() {
+            return Ok("✅ No pull request branches to clean".to_string());
+        }
+
+        let current = GitOperations::current_branch().ok();
+        let base = Self::base_remote(None).ok();
+        let mut output = BufferedOutput::new();
+        let mut stale = Vec::new();
+
+        for (branch, number) in branches {
+            if GitOperations::run(&["rev-parse", "--verify", "--quiet", &branch]).is_err() {
+                // Deleted by hand; forget it
+                let _ =
+                    GitOperations::run(&["config", "--unset", &format!("branch.{branch}.gitxPr")]);
+                continue;
+            }
+            let reason = if all {
+                Some("--all".to_string())
+            } else {
+                Self::closed_reason(&branch, number, base.as_deref())
+            };
+            match reason {
+                Some(_) if current.as_deref() == Some(branch.as_str()) => output.add_line(format!(
+                    "⏭️  {branch} (#{number}) is checked out, switch away to clean it"
+                )),
+                Some(reason) => stale.push((branch, number, reason)),
+                None => output.add_line(format!("📌 {branch} (#{number}) is still open")),
+            }
+        }
+
+        if stale.is_empty() {
+            output.add_line("✅ No pull request branches to clean".to_string());
+            return Ok(output.content());
+        }
+
+        output.add_line(format!(
+            "🧹 {} pull request branch(es) to clean:",
+            stale.len()
+        ));
+        for (branch, number, reason) in &stale {
+            output.add_line(format!("   {branch} (#{number}, {reason})"));
+        }
+        if dry_run {
+            output.add_line("💡 Run without --dry-run to delete them".to_string());
+            return Ok(output.content());
+        }
+        if !Interactive::confirm_or_accept(
+            &format!("Delete {} pull request branch(es)?", stale.len()),
+            true,
+        )? {
+            output.add_line("❌ Cancelled, nothing deleted".to_string());
+            return Ok(output.content());
+        }
+
+        for (branch, _, _) in &stale {
+            // Review branches are rarely merged locally
+            GitOperations::run(&["branch", "--quiet", "-D", branch])?;
+        }
+        output.add_line(format!("✅ Deleted {} branch(es)", stale.len()));
+
+        for remote in Self::unused_fork_remotes() {
+            GitOperations::run(&["remote", "remove", &remote])?;
+            output.add_line(format!("🗑️  Removed remote '{remote}'"));
+        }
+        Ok(output.content())
+    }
+
+    /// Why a pull request branch can go, `None` while its PR is open
+    fn closed_reason(branch: &str, number: u64, base: Option<&str>) -> Option<String> {
+        if let Ok(json) = Self::view(number, "number,state") {
+            return PullRequest::parse(&json)
+                .ok()
+                .filter(|pr| pr.state != "OPEN")
+                .map(|pr| pr.state.to_lowercase());
+        }
+        // Without gh, only a regular merge into the base's default branch is visible
+        let target = format!("{}/HEAD", base?);
+        GitOperations::run(&["merge-base", "--is-ancestor", branch, &target])
+            .ok()
+            .map(|_| format!("merged into {target}"))
+    }
+
+    /// Fork remotes `pr checkout` added that no remaining branch uses
+    fn unused_fork_remotes() -> Vec<String> {
+        let added = GitOperations::run(&["config", "--get-regexp", r"^remote\..*\.gitxpr$"])
+            .unwrap_or_default();
+        let used: Vec<String> =
+            GitOperations::run(&["config", "--get-regexp", r"^branch\..*\.remote$"])
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| line.split_once(' ').map(|(_, remote)| remote.to_string()))
+                .collect();
+        added
+            .lines()
+            .filter_map(|line| {
+                let (key, _) = line.split_once(' ')?;
+                key.strip_prefix("remote.")?
+                    .strip_suffix(".gitxpr")
+                    .map(str::to_string)
+            })
+            .filter(|remote| !used.contains(remote))
+            .collect()
+    }
+}
+
+impl Command for PrCommand {
+    fn execute(&self) -> Result<String> {
+        match &self.action {
+            PrAction::Checkout { number, remote } => Self::checkout(*number, remote.as_deref()),
+            PrAction::Clean { all, dry_run } => Self::clean(*all, *dry_run),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "pr"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check out pull requests as local branches and clean them up"
+    }
+}
+
+impl GitCommand for PrCommand {}
+
+impl Destructive for PrCommand {
+    fn destruction_description(&self) -> String {
+        match &self.action {
+            PrAction::Checkout { .. } => {
+                "This will create or fast-forward a pr/<number> branch".to_string()
+            }
+            PrAction::Clean { .. } => {
+                "This will delete pull request branches and their fork remotes".to_string()
+            }
+        }
+    }
+}
+
+/// What `at` does with the commit it finds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AtAction {
+    /// Check it out on a detached HEAD
+    Detach,
+    /// Create a branch there and switch to it; an empty name picks `at/<ref>-<time>`
+    Create(String),
+    /// Only print the full hash, for scripts
+    Print,
+}
+
+/// Where `at` found the commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtSource {
+    Reflog,
+    CommitDates,
+}
+
+impl AtSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            AtSource::Reflog => "from the reflog",
+            AtSource::CommitDates => "by committer date",
+        }
+    }
+}
+
+/// Weekday and month prefixes that make a time absolute, e.g. `friday 18:00` or `4 jul`
+const CALENDAR_NAMES: [&str; 19] = [
+    "mon", "tue", "wed", "thu", "fri", "sat", "sun", "jan", "feb", "mar", "apr", "may", "jun",
+    "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Command that finds the commit a ref pointed at at some moment
+pub struct AtCommand {
+    when: String,
+    reference: Option<String>,
+    action: AtAction,
+    commit_dates: bool,
+}
+
+impl AtCommand {
+    pub fn new(when: String, reference: Option<String>) -> Self {
+        Self {
+            when,
+            reference,
+            action: AtAction::Detach,
+            commit_dates: false,
+        }
+    }
+
+    pub fn with_action(mut self, action: AtAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Skip the reflog and go by committer dates on the first-parent history
+    pub fn with_commit_dates(mut self) -> Self {
+        self.commit_dates = true;
+        self
+    }
+
+    /// Unix time of `when`: a [`TimeSpec`], or an absolute date git understands, e.g. `yesterday 17:00`
+    pub fn resolve_time(when: &str) -> Result<i64> {
+        let spec_error = match TimeSpec::parse(when) {
+            Ok(spec) => return Ok(spec.timestamp()),
+            Err(error) => error,
+        };
+        // Only absolute dates go to git: a clock time, a day or a weekday or month name
+        let lower = when.to_lowercase();
+        let absolute = lower.chars().any(|c| c.is_ascii_digit())
+            || lower
+                .split_whitespace()
+                .any(|word| CALENDAR_NAMES.iter().any(|name| word.starts_with(name)));
+        if !absolute {
+            return Err(spec_error);
+        }
+
+        let now = chrono::Local::now().timestamp();
+        // `rev-parse --since` prints `--max-age=<unix time>`
+        let time: i64 = GitOperations::run(&["rev-parse", &format!("--since={when}")])?
+            .strip_prefix("--max-age=")
+            .and_then(|secs| secs.parse().ok())
+            .ok_or_else(|| GitXError::Parse(format!("Could not understand time '{when}'")))?;
+        // Approxidate falls back to the current time for words it doesn't know
+        if (time - now).abs() <= 1 {
+            return Err(GitXError::Parse(format!(
+                "Could not understand time '{when}'"
+            )));
+        }
+        Ok(time)
+    }
+
+    /// Newest entry at or before `time` in `git reflog --date=unix --format=%H%x09%gd` output
+    ///
+    /// `None` when the reflog starts after `time`.
+    pub fn reflog_entry(reflog: &str, time: i64) -> Option<String> {
+        reflog.lines().find_map(|line| {
+            let (commit, selector) = line.split_once('\t')?;
+            let (_, stamp) = selector.rsplit_once("@{")?;
+            let stamp: i64 = stamp.strip_suffix('}')?.parse().ok()?;
+            (stamp <= time).then(|| commit.to_string())
+        })
+    }
+
+    /// Branch name for `--create` without a name, e.g. `at/main-2025-07-04-1700`
+    pub fn default_branch_name(reference: &str, time: i64) -> String {
+        format!("at/{reference}-{}", Self::local_time(time, "%Y-%m-%d-%H%M"))
+    }
+
+    fn local_time(time: i64, format: &str) -> String {
+        chrono::DateTime::from_timestamp(time, 0)
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format(format)
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+
+    fn find(&self, reference: &str, time: i64) -> Result<(String, AtSource)> {
+        if !self.commit_dates {
+            let reflog = GitOperations::run(&[
+                "reflog",
+                "show",
+                "--date=unix",
+                "--format=%H%x09%gd",
+                reference,
+            ])
+            .unwrap_or_default();
+            if let Some(commit) = Self::reflog_entry(&reflog, time) {
+                return Ok((commit, AtSource::Reflog));
+            }
+        }
+
+        // Without a reflog reaching back far enough, the newest commit on the
+        // first-parent line committed by then is the closest answer
+        let commit = GitOperations::run(&[
+            "rev-list",
+            "-1",
+            "--first-parent",
+            &format!("--before={time}"),
+            reference,
+        ])?;
+        if commit.is_empty() {
+            return Err(GitXError::Other(format!(
+                "'{reference}' has no commits before {}",
+                Self::local_time(time, "%Y-%m-%d %H:%M")
+            )));
+        }
+        Ok((commit, AtSource::CommitDates))
+    }
+}
+
+impl Command for AtCommand {
+    fn execute(&self) -> Result<String> {
+        let reference = match &self.reference {
+            Some(reference) => reference.clone(),
+            // A detached HEAD has a reflog of its own
+            None => GitOperations::current_branch()?,
+        };
+        if reference.starts_with('-')
+            || GitOperations::run(&[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{reference}^{{commit}}"),
+            ])
+            .is_err()
+        {
+            return Err(GitXError::GitCommand(format!("Unknown ref '{reference}'")));
+        }
+
+        let time = Self::resolve_time(&self.when)?;
+        if time > chrono::Utc::now().timestamp() {
+            return Err(GitXError::Other(format!(
+                "'{}' is in the future",
+                self.when
+            )));
+        }
+        let (commit, source) = self.find(&reference, time)?;
+        if self.action == AtAction::Print {
+            return Ok(commit);
+        }
+
+        let info = GitOperations::run(&["log", "-1", "--format=%h%x09%ct%x09%an%x09%s", &commit])?;
+        let mut fields = info.splitn(4, '\t');
+        let short = fields.next().unwrap_or_default();
+        let committed: i64 = fields
+            .next()
+            .and_then(|time| time.parse().ok())
+            .unwrap_or_default();
+        let author = fields.next().unwrap_or_default();
+        let subject = fields.next().unwrap_or_default();
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🕰️  {} at {} ({}) was {} {subject}",
+            Format::bold(&reference),
+            self.when,
+            Self::local_time(time, "%a %Y-%m-%d %H:%M"),
+            Format::bold(short)
+        ));
+        output.add_line(format!(
+            "   {} · committed {} by {author}",
+            source.label(),
+            Self::local_time(committed, "%Y-%m-%d %H:%M")
+        ));
+
+        match &self.action {
+            AtAction::Detach => {
+                GitOperations::run(&["switch", "--quiet", "--detach", &commit])?;
+                output.add_line(format!("📍 HEAD is now detached at {short}"));
+                output.add_line("💡 Go back with 'git switch -'".to_string());
+            }
+            AtAction::Create(name) => {
+                let name = if name.is_empty() {
+                    Self::default_branch_name(&reference, time)
+                } else {
+                    name.clone()
+                };
+                GitOperations::run(&["switch", "--quiet", "--create", &name, &commit])?;
+                output.add_line(format!("🌿 Created and switched to '{name}'"));
+            }
+            AtAction::Print => {}
+        }
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "at"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check out the commit a branch pointed at at some moment"
+    }
+}
+
+impl GitCommand for AtCommand {}
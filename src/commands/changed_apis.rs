@@ -0,0 +1,569 @@
+use crate::core::dependencies::is_vendored;
+use crate::core::git::GitOperations;
+use crate::core::output::{BufferedOutput, Format};
+use crate::core::traits::*;
+use crate::{GitXError, Result};
+use quote::ToTokens;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+
+/// One public item of a crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiItem {
+    /// Path below the crate, e.g. `core::git::GitOperations::run`
+    pub path: String,
+    /// `fn`, `struct`, `enum`, `union`, `trait`, `type`, `const`, `static`, `use`, `impl` or `macro`
+    pub kind: &'static str,
+    /// The declaration without docs, bodies or private fields
+    pub signature: String,
+}
+
+/// Public items of Rust source, read with `syn`
+pub struct PublicApi;
+
+impl PublicApi {
+    /// Module path of a file below a crate's `src/`, e.g. `core/git.rs` → `core::git`
+    pub fn module_path(relative: &str) -> String {
+        let relative = relative.trim_end_matches(".rs");
+        let mut parts: Vec<&str> = relative.split('/').collect();
+        if matches!(parts.as_slice(), ["lib"]) || parts.last() == Some(&"mod") {
+            parts.pop();
+        }
+        parts.join("::")
+    }
+
+    /// Public items declared in one file, with paths below `module`
+    ///
+    /// Only `pub` items count; whether the enclosing module is itself reachable is
+    /// not checked.
+    pub fn parse(module: &str, source: &str) -> Result<Vec<ApiItem>> {
+        let file = syn::parse_file(source).map_err(|e| GitXError::Parse(e.to_string()))?;
+        let mut items = Vec::new();
+        Self::collect(module, &file.items, &mut items);
+        Ok(items)
+    }
+
+    fn collect(module: &str, items: &[syn::Item], out: &mut Vec<ApiItem>) {
+        let path = |name: &dyn std::fmt::Display| {
+            if module.is_empty() {
+                name.to_string()
+            } else {
+                format!("{module}::{name}")
+            }
+        };
+
+        for item in items {
+            match item {
+                syn::Item::Fn(item) if is_pub(&item.vis) => out.push(api_item(
+                    "fn",
+                    path(&item.sig.ident),
+                    fn_signature(&item.vis, &item.sig),
+                )),
+                syn::Item::Struct(item) if is_pub(&item.vis) => {
+                    let mut item = item.clone();
+                    item.attrs.clear();
+                    let hidden = public_fields(&mut item.fields);
+                    out.push(api_item(
+                        "struct",
+                        path(&item.ident),
+                        with_hidden_fields(tokens(&item), hidden),
+                    ));
+                }
+                syn::Item::Enum(item) if is_pub(&item.vis) => {
+                    let mut item = item.clone();
+                    item.attrs.clear();
+                    for variant in &mut item.variants {
+                        variant.attrs.clear();
+                        variant
+                            .fields
+                            .iter_mut()
+                            .for_each(|field| field.attrs.clear());
+                    }
+                    out.push(api_item("enum", path(&item.ident), tokens(&item)));
+                }
+                syn::Item::Union(item) if is_pub(&item.vis) => {
+                    let mut item = item.clone();
+                    item.attrs.clear();
+                    let mut fields = syn::Fields::Named(item.fields);
+                    let hidden = public_fields(&mut fields);
+                    let syn::Fields::Named(fields) = fields else {
+                        continue;
+                    };
+                    item.fields = fields;
+                    out.push(api_item(
+                        "union",
+                        path(&item.ident),
+                        with_hidden_fields(tokens(&item), hidden),
+                    ));
+                }
+                syn::Item::Trait(item) if is_pub(&item.vis) => {
+                    let name = path(&item.ident);
+                    let mut trait_header = item.clone();
+                    trait_header.attrs.clear();
+                    trait_header.items.clear();
+                    out.push(api_item("trait", name.clone(), header(&trait_header)));
+                    for member in &item.items {
+                        let member = match member {
+                            syn::TraitItem::Fn(member) => api_item(
+                                "fn",
+                                format!("{name}::{}", member.sig.ident),
+                                fn_signature(&syn::Visibility::Inherited, &member.sig),
+                            ),
+                            syn::TraitItem::Type(member) => api_item(
+                                "type",
+                                format!("{name}::{}", member.ident),
+                                format!("type {}{}", member.ident, tokens(&member.generics)),
+                            ),
+                            syn::TraitItem::Const(member) => api_item(
+                                "const",
+                                format!("{name}::{}", member.ident),
+                                format!("const {}: {}", member.ident, tokens(&member.ty)),
+                            ),
+                            _ => continue,
+                        };
+                        out.push(member);
+                    }
+                }
+                syn::Item::Type(item) if is_pub(&item.vis) => {
+                    let mut item = item.clone();
+                    item.attrs.clear();
+                    out.push(api_item("type", path(&item.ident), tokens(&item)));
+                }
+                syn::Item::Const(item) if is_pub(&item.vis) => out.push(api_item(
+                    "const",
+                    path(&item.ident),
+                    format!("pub const {}: {}", item.ident, tokens(&item.ty)),
+                )),
+                syn::Item::Static(item) if is_pub(&item.vis) => out.push(api_item(
+                    "static",
+                    path(&item.ident),
+                    format!("pub static {}: {}", item.ident, tokens(&item.ty)),
+                )),
+                syn::Item::Use(item) if is_pub(&item.vis) => {
+                    let tree = tidy(&tokens(&item.tree));
+                    out.push(api_item("use", path(&tree), format!("pub use {tree}")));
+                }
+                syn::Item::Mod(item) if is_pub(&item.vis) => {
+                    if let Some((_, content)) = &item.content {
+                        Self::collect(&path(&item.ident), content, out);
+                    }
+                }
+                // Trait implementations are part of the API; so are public inherent methods
+                syn::Item::Impl(item) if item.trait_.is_some() => {
+                    let mut impl_header = item.clone();
+                    impl_header.attrs.clear();
+                    impl_header.items.clear();
+                    let signature = tidy(&header(&impl_header));
+                    out.push(api_item("impl", path(&format!("<{signature}>")), signature));
+                }
+                syn::Item::Impl(item) => {
+                    let self_ty = tidy(&tokens(&item.self_ty));
+                    for member in &item.items {
+                        let member = match member {
+                            syn::ImplItem::Fn(member) if is_pub(&member.vis) => api_item(
+                                "fn",
+                                path(&format!("{self_ty}::{}", member.sig.ident)),
+                                fn_signature(&member.vis, &member.sig),
+                            ),
+                            syn::ImplItem::Const(member) if is_pub(&member.vis) => api_item(
+                                "const",
+                                path(&format!("{self_ty}::{}", member.ident)),
+                                format!("pub const {}: {}", member.ident, tokens(&member.ty)),
+                            ),
+                            _ => continue,
+                        };
+                        out.push(member);
+                    }
+                }
+                syn::Item::Macro(item)
+                    if item
+                        .attrs
+                        .iter()
+                        .any(|attr| attr.path().is_ident("macro_export")) =>
+                {
+                    if let Some(ident) = &item.ident {
+                        out.push(api_item(
+                            "macro",
+                            ident.to_string(),
+                            format!("macro_rules! {ident}"),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn api_item(kind: &'static str, path: String, signature: String) -> ApiItem {
+    ApiItem {
+        path,
+        kind,
+        signature: tidy(&signature),
+    }
+}
+
+fn is_pub(vis: &syn::Visibility) -> bool {
+    matches!(vis, syn::Visibility::Public(_))
+}
+
+fn tokens(item: &impl ToTokens) -> String {
+    item.to_token_stream().to_string()
+}
+
+/// `mut` on a parameter only matters inside the function, so it's left out
+fn fn_signature(vis: &syn::Visibility, sig: &syn::Signature) -> String {
+    let mut sig = sig.clone();
+    for input in &mut sig.inputs {
+        match input {
+            syn::FnArg::Receiver(receiver) if receiver.reference.is_none() => {
+                receiver.mutability = None;
+            }
+            syn::FnArg::Typed(typed) => {
+                if let syn::Pat::Ident(ident) = typed.pat.as_mut() {
+                    ident.mutability = None;
+                }
+            }
+            _ => {}
+        }
+    }
+    format!("{} {}", tokens(vis), tokens(&sig))
+        .trim_start()
+        .to_string()
+}
+
+/// An item whose members were removed, without the empty `{ }` left behind
+fn header(item: &impl ToTokens) -> String {
+    tokens(item)
+        .trim_end()
+        .trim_end_matches("{ }")
+        .trim_end()
+        .to_string()
+}
+
+/// Drop private named fields and say whether there were any; private tuple
+/// fields become `_` so positions still count
+fn public_fields(fields: &mut syn::Fields) -> bool {
+    match fields {
+        syn::Fields::Named(named) => {
+            let before = named.named.len();
+            named.named = named
+                .named
+                .iter()
+                .filter(|field| is_pub(&field.vis))
+                .cloned()
+                .map(|mut field| {
+                    field.attrs.clear();
+                    field
+                })
+                .collect();
+            named.named.len() < before
+        }
+        syn::Fields::Unnamed(unnamed) => {
+            for field in &mut unnamed.unnamed {
+                field.attrs.clear();
+                if !is_pub(&field.vis) {
+                    field.ty = syn::Type::Infer(syn::TypeInfer {
+                        underscore_token: Default::default(),
+                    });
+                }
+            }
+            false
+        }
+        syn::Fields::Unit => false,
+    }
+}
+
+/// Mark dropped private fields with `..`, e.g. `pub struct Config { pub name: String, .. }`
+fn with_hidden_fields(signature: String, hidden: bool) -> String {
+    match signature.strip_suffix(" }") {
+        Some(open) if hidden && open.ends_with('{') => format!("{open} .. }}"),
+        Some(fields) if hidden => format!("{fields}, .. }}"),
+        _ => signature,
+    }
+}
+
+/// Undo the spacing `proc_macro2` puts between every token
+fn tidy(tokens: &str) -> String {
+    let mut text = tokens.to_string();
+    for (from, to) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" ::", "::"),
+        ("( ", "("),
+        (" )", ")"),
+        (" ,", ","),
+        (" :", ":"),
+        (" ;", ";"),
+        ("< ", "<"),
+        (" >", ">"),
+        ("& ", "&"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (", }", " }"),
+    ] {
+        text = text.replace(from, to);
+    }
+
+    // `f (` and `Vec <` lose the space, `: (` and `-> (` keep it
+    let mut tidied = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let attached = tidied
+            .chars()
+            .last()
+            .is_some_and(|last| last.is_alphanumeric() || last == '_');
+        if c == ' ' && attached && matches!(chars.peek(), Some('(' | '<')) {
+            continue;
+        }
+        tidied.push(c);
+    }
+    tidied
+}
+
+/// Semantic-versioning bump a set of API changes calls for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemverBump {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl SemverBump {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SemverBump::Major => "major (public items were removed or changed)",
+            SemverBump::Minor => "minor (public items were only added)",
+            SemverBump::Patch => "patch (no public API changes)",
+        }
+    }
+}
+
+/// Public items added, removed and changed between two versions
+#[derive(Debug, Default)]
+pub struct ApiDiff {
+    pub added: Vec<ApiItem>,
+    pub removed: Vec<ApiItem>,
+    /// Old and new declaration
+    pub changed: Vec<(ApiItem, ApiItem)>,
+}
+
+impl ApiDiff {
+    /// Items are matched by path and kind; a different signature is a change
+    pub fn between(old: Vec<ApiItem>, new: Vec<ApiItem>) -> Self {
+        let key = |item: &ApiItem| (item.path.clone(), item.kind);
+        let mut old: BTreeMap<_, ApiItem> =
+            old.into_iter().map(|item| (key(&item), item)).collect();
+        let mut diff = ApiDiff::default();
+
+        let new: BTreeMap<_, ApiItem> = new.into_iter().map(|item| (key(&item), item)).collect();
+        for (key, item) in new {
+            match old.remove(&key) {
+                None => diff.added.push(item),
+                Some(before) if before.signature != item.signature => {
+                    diff.changed.push((before, item))
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = old.into_values().collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn bump(&self) -> SemverBump {
+        if !self.removed.is_empty() || !self.changed.is_empty() {
+            SemverBump::Major
+        } else if !self.added.is_empty() {
+            SemverBump::Minor
+        } else {
+            SemverBump::Patch
+        }
+    }
+}
+
+/// A source file of a library crate at some ref
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateFile {
+    pub path: String,
+    /// Module path, prefixed with the crate's directory name outside the repository root
+    pub module: String,
+}
+
+impl CrateFile {
+    /// Library sources among tracked `files`: everything below a `src/` next to a `src/lib.rs`
+    pub fn find(files: &[&str]) -> Vec<CrateFile> {
+        let roots: Vec<&str> = files
+            .iter()
+            .filter(|file| !is_vendored(file))
+            .filter_map(|file| file.strip_suffix("src/lib.rs"))
+            .filter(|root| root.is_empty() || root.ends_with('/'))
+            .collect();
+
+        files
+            .iter()
+            .filter(|file| file.ends_with(".rs") && !is_vendored(file))
+            .filter_map(|file| {
+                let root = roots
+                    .iter()
+                    .filter(|root| file.starts_with(&format!("{root}src/")))
+                    .max_by_key(|root| root.len())?;
+                let relative = &file[root.len() + "src/".len()..];
+                if relative == "main.rs" || relative.starts_with("bin/") {
+                    return None;
+                }
+                let krate = root.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+                let module = PublicApi::module_path(relative);
+                let module = [krate, module.as_str()]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                Some(CrateFile {
+                    path: file.to_string(),
+                    module,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Command that diffs the public API of the repository's Rust library crates between two refs
+pub struct ChangedApisCommand {
+    from: Option<String>,
+    to: String,
+}
+
+impl ChangedApisCommand {
+    /// Compare `from` (default: the latest tag) with `to` (default: `HEAD`)
+    pub fn new(from: Option<String>, to: Option<String>) -> Self {
+        Self {
+            from,
+            to: to.unwrap_or_else(|| "HEAD".to_string()),
+        }
+    }
+
+    /// Public items at `reference`, and the files `syn` couldn't parse
+    fn api_at(reference: &str) -> Result<(Vec<ApiItem>, Vec<String>)> {
+        let listing = GitOperations::run(&["ls-tree", "-r", "--name-only", reference])?;
+        let files: Vec<&str> = listing.lines().collect();
+        let parsed: Vec<(String, Result<Vec<ApiItem>>)> = CrateFile::find(&files)
+            .par_iter()
+            .map(|file| {
+                let items = GitOperations::run(&["show", &format!("{reference}:{}", file.path)])
+                    .and_then(|source| PublicApi::parse(&file.module, &source));
+                (file.path.clone(), items)
+            })
+            .collect();
+
+        let mut items = Vec::new();
+        let mut skipped = Vec::new();
+        for (path, result) in parsed {
+            match result {
+                Ok(mut found) => items.append(&mut found),
+                Err(_) => skipped.push(path),
+            }
+        }
+        Ok((items, skipped))
+    }
+
+    fn push_items(output: &mut BufferedOutput, heading: String, items: &[ApiItem]) {
+        if items.is_empty() {
+            return;
+        }
+        output.add_line(heading);
+        for item in items {
+            output.add_line(format!("   {} {}", item.kind, Format::bold(&item.path)));
+            output.add_line(format!("      {}", item.signature));
+        }
+    }
+}
+
+impl Command for ChangedApisCommand {
+    fn execute(&self) -> Result<String> {
+        let from = match &self.from {
+            Some(from) => from.clone(),
+            None => GitOperations::run(&["describe", "--tags", "--abbrev=0", &self.to]).map_err(
+                |_| {
+                    GitXError::Other(
+                        "No tag to compare against; pass the ref, e.g. 'git x changed-apis v1.0.0'"
+                            .to_string(),
+                    )
+                },
+            )?,
+        };
+        for reference in [&from, &self.to] {
+            if !GitOperations::commit_exists(reference)? {
+                return Err(GitXError::GitCommand(format!(
+                    "'{reference}' does not exist"
+                )));
+            }
+        }
+
+        let (old, mut skipped) = Self::api_at(&from)?;
+        let (new, skipped_new) = Self::api_at(&self.to)?;
+        skipped.extend(skipped_new);
+        skipped.sort();
+        skipped.dedup();
+        if old.is_empty() && new.is_empty() {
+            return Ok(format!(
+                "ℹ️ No Rust library crates (src/lib.rs) found at {from} or {}",
+                self.to
+            ));
+        }
+        let diff = ApiDiff::between(old, new);
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🦀 Public API changes {}..{}",
+            Format::bold(&from),
+            Format::bold(&self.to)
+        ));
+        if diff.is_empty() {
+            output.add_line("✅ No public API changes".to_string());
+        }
+        Self::push_items(
+            &mut output,
+            format!("➕ Added ({}):", diff.added.len()),
+            &diff.added,
+        );
+        Self::push_items(
+            &mut output,
+            format!("➖ Removed ({}):", diff.removed.len()),
+            &diff.removed,
+        );
+        if !diff.changed.is_empty() {
+            output.add_line(format!("✏️  Changed ({}):", diff.changed.len()));
+            for (before, after) in &diff.changed {
+                output.add_line(format!("   {} {}", after.kind, Format::bold(&after.path)));
+                output.add_line(format!("      - {}", before.signature));
+                output.add_line(format!("      + {}", after.signature));
+            }
+        }
+        if !skipped.is_empty() {
+            output.add_line(format!(
+                "⚠️  Skipped {} file(s) that couldn't be parsed: {}",
+                skipped.len(),
+                skipped.join(", ")
+            ));
+        }
+        output.add_line(format!(
+            "💡 Suggested version bump: {}",
+            diff.bump().label()
+        ));
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "changed-apis"
+    }
+
+    fn description(&self) -> &'static str {
+        "Diff the public API of Rust crates between two refs"
+    }
+}
+
+impl GitCommand for ChangedApisCommand {}
@@ -1,6 +1,21 @@
+use crate::commands::hooks::PROJECT_CONFIG;
+use crate::core::browser::Browser;
+use crate::core::checkpoints::FileCheckpoints;
+use crate::core::config::Config;
+use crate::core::conventional::{
+    CommitLinter, GENERATED_PREFIXES, LintViolation, SKIP_ENV, clean_message,
+};
+use crate::core::export::ExportValue;
 use crate::core::git::*;
+use crate::core::output::BufferedOutput;
+use crate::core::rebase::{RebaseOutcome, RebaseSkipper, SkippedCommit};
+use crate::core::secrets::glob_match;
+use crate::core::tickets::{ID_PLACEHOLDER, TicketPattern, ticket_url};
 use crate::core::traits::*;
+use crate::core::wip::{branch_wip_commits, is_wip, wip_message};
+use crate::domain::{CommitId, RefName};
 use crate::{GitXError, Result};
+use std::path::PathBuf;
 
 /// Commit-related commands grouped together
 pub struct CommitCommands;
@@ -8,7 +23,12 @@ pub struct CommitCommands;
 impl CommitCommands {
     /// Create a fixup commit
     pub fn fixup(commit_hash: &str, auto_rebase: bool) -> Result<String> {
-        FixupCommand::new(commit_hash.to_string(), auto_rebase).execute()
+        FixupCommand::new(commit_hash.parse()?, auto_rebase).execute()
+    }
+
+    /// Create fixup commits for staged hunks, targeting the commits that last touched them
+    pub fn auto_fixup(auto_rebase: bool) -> Result<String> {
+        AutoFixupCommand::new(auto_rebase).execute()
     }
 
     /// Undo the last commit
@@ -16,26 +36,59 @@ impl CommitCommands {
         UndoCommand::new().execute()
     }
 
+    /// List a file's checkpoints, or restore one
+    pub fn undo_file(path: Option<String>, restore: Option<u64>) -> Result<String> {
+        UndoFileCommand::new(path).with_restore(restore).execute()
+    }
+
     /// Bisect workflow
     pub fn bisect(action: BisectAction) -> Result<String> {
         BisectCommand::new(action).execute()
     }
+
+    /// Reword an unpushed commit's message
+    pub fn reword(commit: &str, message: &str) -> Result<String> {
+        RewordCommand::new(commit.parse()?, message.to_string()).execute()
+    }
+
+    /// Install the Conventional Commits commit-msg hook
+    pub fn init_hooks() -> Result<String> {
+        InitHooksCommand::new().execute()
+    }
+
+    /// Squash the last N commits (or a range ending at HEAD) into one
+    pub fn squash(target: &str, message: Option<String>) -> Result<String> {
+        SquashCommand::new(target.to_string(), message).execute()
+    }
+
+    /// Generate or apply an email-formatted patch series
+    pub fn patch(action: PatchAction) -> Result<String> {
+        PatchCommand::new(action).execute()
+    }
 }
 
 /// Command to create fixup commits
 pub struct FixupCommand {
-    commit_hash: String,
+    commit_hash: RefName,
     auto_rebase: bool,
+    quiet: bool,
 }
 
 impl FixupCommand {
-    pub fn new(commit_hash: String, auto_rebase: bool) -> Self {
+    pub fn new(commit_hash: RefName, auto_rebase: bool) -> Self {
         Self {
             commit_hash,
             auto_rebase,
+            quiet: false,
         }
     }
 
+    /// Print only the hash of the fixup commit
+    pub fn with_quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
     fn has_staged_changes() -> Result<bool> {
         let staged = GitOperations::staged_files()?;
         Ok(!staged.is_empty())
@@ -46,7 +99,7 @@ impl Command for FixupCommand {
     fn execute(&self) -> Result<String> {
         // Allow any Git reference (commit hash, branch, tag, etc.)
         // Try to resolve reference to verify it exists
-        if GitOperations::run(&["rev-parse", "--verify", &self.commit_hash]).is_err() {
+        if GitOperations::run(&["rev-parse", "--verify", self.commit_hash.as_str()]).is_err() {
             // Check if we're in a git repo
             if GitOperations::repo_root().is_err() {
                 return Err(GitXError::GitCommand(
@@ -69,7 +122,21 @@ impl Command for FixupCommand {
         }
 
         // Create fixup commit
-        CommitOperations::fixup(&self.commit_hash)?;
+        if self.quiet {
+            GitOperations::run(&["commit", "--fixup", self.commit_hash.as_str()])?;
+            let fixup_hash = GitOperations::run(&["rev-parse", "--short", "HEAD"])?;
+            if self.auto_rebase {
+                // The todo list still needs an editor, so git keeps the terminal
+                GitOperations::run_status(&[
+                    "rebase",
+                    "-i",
+                    "--autosquash",
+                    &format!("{}^", self.commit_hash),
+                ])?;
+            }
+            return Ok(fixup_hash);
+        }
+        CommitOperations::fixup(self.commit_hash.as_str())?;
 
         let mut result = format!("✅ Fixup commit created for {}", self.commit_hash);
 
@@ -113,6 +180,400 @@ impl Command for FixupCommand {
 
 impl GitCommand for FixupCommand {}
 
+/// A staged change from `git diff --cached -U0`, one hunk per region
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedHunk {
+    pub path: String,
+    /// File header (`diff --git`, `index`, `---`, `+++` lines)
+    pub header: Vec<String>,
+    pub old_start: usize,
+    pub old_count: usize,
+    pub new_count: usize,
+    /// `+`, `-` and `\ No newline` lines
+    pub lines: Vec<String>,
+}
+
+impl StagedHunk {
+    /// Split a zero-context diff into hunks
+    ///
+    /// New, deleted, renamed, binary and mode-changed files can't be absorbed
+    /// hunk by hunk; their paths are returned separately.
+    pub fn parse_diff(diff: &str) -> (Vec<StagedHunk>, Vec<String>) {
+        let mut hunks = Vec::new();
+        let mut skipped = Vec::new();
+
+        for file in diff.split("\ndiff --git ").filter(|f| !f.trim().is_empty()) {
+            let file = file.strip_prefix("diff --git ").unwrap_or(file);
+            let mut lines = file.lines();
+            let first = format!("diff --git {}", lines.next().unwrap_or_default());
+            let mut header = vec![first.clone()];
+            let mut path = first
+                .rsplit_once(" b/")
+                .map(|(_, path)| path.to_string())
+                .unwrap_or_default();
+            let mut absorbable = true;
+            let mut current: Option<StagedHunk> = None;
+
+            for line in lines {
+                if let Some(hunk) = current.as_mut() {
+                    if !line.starts_with("@@") {
+                        hunk.lines.push(line.to_string());
+                        continue;
+                    }
+                    hunks.push(current.take().unwrap());
+                }
+
+                if let Some(range) = line.strip_prefix("@@ ") {
+                    let Some((old_start, old_count, new_count)) = Self::parse_range(range) else {
+                        absorbable = false;
+                        break;
+                    };
+                    current = Some(StagedHunk {
+                        path: path.clone(),
+                        header: header.clone(),
+                        old_start,
+                        old_count,
+                        new_count,
+                        lines: Vec::new(),
+                    });
+                } else {
+                    if line.starts_with("new file mode")
+                        || line.starts_with("deleted file mode")
+                        || line.starts_with("old mode")
+                        || line.starts_with("rename from")
+                        || line.starts_with("copy from")
+                        || line.starts_with("Binary files")
+                    {
+                        absorbable = false;
+                    }
+                    if let Some(target) = line.strip_prefix("+++ b/") {
+                        path = target.to_string();
+                    }
+                    header.push(line.to_string());
+                }
+            }
+
+            if absorbable {
+                hunks.extend(current);
+            } else {
+                hunks.retain(|hunk| hunk.path != path);
+                skipped.push(path);
+            }
+        }
+
+        (hunks, skipped)
+    }
+
+    /// `-a,b +c,d @@` into (a, b, d); counts default to 1
+    fn parse_range(range: &str) -> Option<(usize, usize, usize)> {
+        let mut parts = range.split_whitespace();
+        let parse = |part: &str| -> Option<(usize, usize)> {
+            match part.split_once(',') {
+                Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+                None => Some((part.parse().ok()?, 1)),
+            }
+        };
+        let (old_start, old_count) = parse(parts.next()?.strip_prefix('-')?)?;
+        let (_, new_count) = parse(parts.next()?.strip_prefix('+')?)?;
+        Some((old_start, old_count, new_count))
+    }
+
+    /// Lines added minus lines removed
+    pub fn delta(&self) -> isize {
+        self.new_count as isize - self.old_count as isize
+    }
+
+    /// Lines of HEAD whose authorship decides the target commit
+    ///
+    /// Changed lines for modifications; for pure additions, the lines around
+    /// the insertion point.
+    pub fn blame_lines(&self) -> Vec<usize> {
+        if self.old_count > 0 {
+            (self.old_start..self.old_start + self.old_count).collect()
+        } else {
+            [self.old_start, self.old_start + 1]
+                .into_iter()
+                .filter(|line| *line > 0)
+                .collect()
+        }
+    }
+
+    /// Where this hunk starts once `applied` hunks are in the index
+    fn shifted_start(&self, applied: &[&StagedHunk]) -> usize {
+        let shift: isize = applied
+            .iter()
+            .filter(|other| other.path == self.path && other.old_start < self.old_start)
+            .map(|other| other.delta())
+            .sum();
+        (self.old_start as isize + shift).max(0) as usize
+    }
+
+    /// Zero-context patch for `hunks`, positioned for an index that already contains `applied`
+    pub fn build_patch(hunks: &[&StagedHunk], applied: &[&StagedHunk]) -> String {
+        let mut patch = String::new();
+        let mut paths: Vec<&str> = Vec::new();
+        for hunk in hunks {
+            if !paths.contains(&hunk.path.as_str()) {
+                paths.push(&hunk.path);
+            }
+        }
+
+        for path in paths {
+            let mut file_hunks: Vec<&&StagedHunk> =
+                hunks.iter().filter(|hunk| hunk.path == path).collect();
+            file_hunks.sort_by_key(|hunk| hunk.old_start);
+
+            for line in &file_hunks[0].header {
+                patch.push_str(line);
+                patch.push('\n');
+            }
+            let mut offset: isize = 0;
+            for hunk in file_hunks {
+                let old_start = hunk.shifted_start(applied);
+                // Same conventions as diff: additions name the line before, deletions too
+                let new_start = match (hunk.old_count, hunk.new_count) {
+                    (0, _) => old_start as isize + offset + 1,
+                    (_, 0) => old_start as isize + offset - 1,
+                    _ => old_start as isize + offset,
+                };
+                patch.push_str(&format!(
+                    "@@ -{old_start},{} +{},{} @@\n",
+                    hunk.old_count,
+                    new_start.max(0),
+                    hunk.new_count
+                ));
+                for line in &hunk.lines {
+                    patch.push_str(line);
+                    patch.push('\n');
+                }
+                offset += hunk.delta();
+            }
+        }
+        patch
+    }
+
+    /// The single candidate commit behind every blamed line, if there is one
+    pub fn pick_target(blamed: &[String], candidates: &[String]) -> Option<String> {
+        let first = blamed.first()?;
+        (blamed.iter().all(|sha| sha == first) && candidates.contains(first)).then(|| first.clone())
+    }
+}
+
+/// Command that splits staged changes into fixup commits for the commits that last touched them
+pub struct AutoFixupCommand {
+    auto_rebase: bool,
+    quiet: bool,
+}
+
+impl AutoFixupCommand {
+    /// Only commits this recent are considered as fixup targets
+    pub const MAX_CANDIDATES: usize = 100;
+
+    pub fn new(auto_rebase: bool) -> Self {
+        Self {
+            auto_rebase,
+            quiet: false,
+        }
+    }
+
+    /// Print only the hashes of the fixup commits
+    pub fn with_quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    /// Unpushed, non-merge commits, newest first
+    fn candidates() -> Result<Vec<String>> {
+        Ok(GitOperations::run(&[
+            "rev-list",
+            "--no-merges",
+            &format!("--max-count={}", Self::MAX_CANDIDATES),
+            "HEAD",
+            "--not",
+            "--remotes",
+        ])?
+        .lines()
+        .map(String::from)
+        .collect())
+    }
+
+    /// Commits that last touched `lines` of `path` in HEAD; lines past the end are ignored
+    fn blame(path: &str, lines: &[usize]) -> Vec<String> {
+        let mut commits = Vec::new();
+        for line in lines {
+            let range = format!("{line},{line}");
+            if let Ok(output) =
+                GitOperations::run(&["blame", "--porcelain", "-L", &range, "HEAD", "--", path])
+                && let Some(sha) = output.lines().next().and_then(|l| l.split(' ').next())
+            {
+                commits.push(sha.to_string());
+            }
+        }
+        commits
+    }
+
+    fn rebase_base(oldest: &str) -> String {
+        match GitOperations::run(&["rev-parse", "--verify", "--quiet", &format!("{oldest}^")]) {
+            Ok(_) => format!("{oldest}^"),
+            Err(_) => "--root".to_string(),
+        }
+    }
+
+    /// Commit each group as a fixup; on failure put HEAD and the index back
+    fn commit_groups(groups: &[(String, Vec<&StagedHunk>)]) -> Result<Vec<String>> {
+        let original_head = GitOperations::run(&["rev-parse", "HEAD"])?;
+        let original_index = GitOperations::run(&["write-tree"])?;
+        let patch_path = crate::core::validation::Validate::git_dir()?.join("git-x-absorb.patch");
+        let patch_arg = patch_path.display().to_string();
+
+        let result = (|| {
+            GitOperations::run(&["read-tree", "HEAD"])?;
+            let mut applied: Vec<&StagedHunk> = Vec::new();
+            let mut created = Vec::new();
+            for (target, hunks) in groups {
+                std::fs::write(&patch_path, StagedHunk::build_patch(hunks, &applied))?;
+                GitOperations::run(&["apply", "--cached", "--unidiff-zero", &patch_arg])?;
+                GitOperations::run(&["commit", "--quiet", "--no-verify", "--fixup", target])?;
+                created.push(GitOperations::run(&["rev-parse", "--short", "HEAD"])?);
+                applied.extend(hunks.iter().copied());
+            }
+            Ok(created)
+        })();
+        let _ = std::fs::remove_file(&patch_path);
+
+        if result.is_err() {
+            let _ = GitOperations::run(&["reset", "--quiet", "--soft", &original_head]);
+        }
+        // HEAD now holds the absorbed hunks, so this leaves only the rest staged
+        GitOperations::run(&["read-tree", &original_index])?;
+        result
+    }
+}
+
+impl Command for AutoFixupCommand {
+    fn execute(&self) -> Result<String> {
+        if GitOperations::staged_files()?.is_empty() {
+            return Err(GitXError::GitCommand(
+                "No staged changes found. Please stage your changes first with 'git add'"
+                    .to_string(),
+            ));
+        }
+
+        let diff = GitOperations::run(&[
+            "diff",
+            "--cached",
+            "-U0",
+            "--no-color",
+            "--no-ext-diff",
+            "--no-renames",
+        ])?;
+        let (hunks, skipped) = StagedHunk::parse_diff(&diff);
+        let candidates = Self::candidates()?;
+
+        let mut groups: Vec<(String, Vec<&StagedHunk>)> = Vec::new();
+        let mut unmatched: Vec<&StagedHunk> = Vec::new();
+        for hunk in &hunks {
+            let blamed = Self::blame(&hunk.path, &hunk.blame_lines());
+            match StagedHunk::pick_target(&blamed, &candidates) {
+                Some(target) => match groups.iter_mut().find(|(sha, _)| *sha == target) {
+                    Some((_, group)) => group.push(hunk),
+                    None => groups.push((target, vec![hunk])),
+                },
+                None => unmatched.push(hunk),
+            }
+        }
+
+        if groups.is_empty() {
+            return Err(GitXError::Other(
+                "No staged hunk could be matched to a single unpushed commit; use 'git x fixup <commit>' instead"
+                    .to_string(),
+            ));
+        }
+
+        let created = Self::commit_groups(&groups)?;
+        let oldest = groups
+            .iter()
+            .filter_map(|(sha, _)| candidates.iter().position(|c| c == sha))
+            .max()
+            .map(|index| candidates[index].clone())
+            .unwrap_or_else(|| groups[0].0.clone());
+        let base = Self::rebase_base(&oldest);
+
+        if self.quiet {
+            if self.auto_rebase {
+                GitOperations::run(&[
+                    "-c",
+                    "sequence.editor=:",
+                    "rebase",
+                    "-i",
+                    "--autosquash",
+                    "--autostash",
+                    &base,
+                ])?;
+            }
+            return Ok(created.join("\n"));
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🧲 Absorbed staged changes into {} fixup commit(s):",
+            created.len()
+        ));
+        for (hash, (target, hunks)) in created.iter().zip(&groups) {
+            let subject = GitOperations::run(&["log", "-1", "--format=%s", target])?;
+            output.add_line(format!(
+                "   • {hash} fixup! {subject} ({} hunk(s))",
+                hunks.len()
+            ));
+        }
+
+        if !unmatched.is_empty() || !skipped.is_empty() {
+            output.add_line("⚠️  Left staged, no single unpushed commit to target:".to_string());
+            for hunk in &unmatched {
+                output.add_line(format!("   • {}:{}", hunk.path, hunk.old_start.max(1)));
+            }
+            for path in &skipped {
+                output.add_line(format!(
+                    "   • {path} (added, deleted, renamed or binary file)"
+                ));
+            }
+        }
+
+        if self.auto_rebase {
+            match GitOperations::run(&[
+                "-c",
+                "sequence.editor=:",
+                "rebase",
+                "-i",
+                "--autosquash",
+                "--autostash",
+                &base,
+            ]) {
+                Ok(_) => output.add_line("✅ Autosquash rebase completed".to_string()),
+                Err(e) => output.add_line(format!(
+                    "❌ Autosquash rebase stopped: {e}\n💡 Resolve it and run 'git rebase --continue', or 'git rebase --abort'"
+                )),
+            }
+        } else {
+            output.add_line(format!(
+                "💡 To squash them, run: git rebase -i --autosquash {base}"
+            ));
+        }
+
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "fixup"
+    }
+
+    fn description(&self) -> &'static str {
+        "Turn staged hunks into fixup commits for the commits that last touched them"
+    }
+}
+
+impl GitCommand for AutoFixupCommand {}
+
 /// Command to undo the last commit
 pub struct UndoCommand;
 
@@ -150,141 +611,2717 @@ impl Destructive for UndoCommand {
     }
 }
 
-/// Bisect workflow actions
-#[derive(Debug, Clone)]
-pub enum BisectAction {
-    Start { bad: String, good: String },
-    Good,
-    Bad,
-    Skip,
-    Reset,
-    Status,
+/// Command to commit everything as a work-in-progress commit
+#[derive(Default)]
+pub struct WipCommand {
+    message: Option<String>,
 }
 
-/// Command for Git bisect workflow
-pub struct BisectCommand {
-    action: BisectAction,
-}
+impl WipCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-impl BisectCommand {
-    pub fn new(action: BisectAction) -> Self {
-        Self { action }
+    /// Summary after `WIP: `, instead of naming the branch
+    pub fn with_message(mut self, message: Option<String>) -> Self {
+        self.message = message;
+        self
     }
+}
 
-    fn is_bisecting() -> Result<bool> {
-        // Check if .git/BISECT_HEAD exists
-        match GitOperations::repo_root() {
-            Ok(root) => {
-                let bisect_head = std::path::Path::new(&root).join(".git").join("BISECT_HEAD");
-                Ok(bisect_head.exists())
-            }
-            Err(_) => Ok(false),
+impl Command for WipCommand {
+    fn execute(&self) -> Result<String> {
+        let branch = GitOperations::require_branch("commit WIP")?;
+        if GitOperations::is_working_directory_clean()? {
+            return Ok("✅ Nothing to commit".to_string());
         }
-    }
 
-    fn execute_bisect_action(&self) -> Result<String> {
-        match &self.action {
-            BisectAction::Start { bad, good } => {
-                // Allow any Git reference (commit hash, branch, tag, etc.)
-                // Don't validate as strict hex - Git will handle this
+        GitOperations::run_status(&["add", "--all"])?;
+        let files = GitOperations::run(&["diff", "--cached", "--name-only"])?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .count();
+        // --no-verify: pre-commit and commit-msg hooks are for finished work
+        let message = wip_message(self.message.as_deref(), &branch);
+        GitOperations::run_status(&["commit", "--quiet", "--no-verify", "-m", &message])?;
+        let hash = GitOperations::run(&["rev-parse", "--short", "HEAD"])?;
 
-                // Try to resolve references to verify they exist
-                if GitOperations::run(&["rev-parse", "--verify", bad]).is_err() {
-                    return Err(GitXError::GitCommand(format!(
-                        "Reference '{bad}' does not exist"
-                    )));
-                }
-                if GitOperations::run(&["rev-parse", "--verify", good]).is_err() {
-                    return Err(GitXError::GitCommand(format!(
-                        "Reference '{good}' does not exist"
-                    )));
-                }
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🚧 Committed {files} file(s) as WIP {hash} on '{branch}'"
+        ));
+        output.add_line("💡 'git x unwip' brings them back into the working tree".to_string());
+        Ok(output.content())
+    }
 
-                // Start bisect and capture git output for proper feedback
-                let output = GitOperations::run(&["bisect", "start", bad, good])?;
+    fn name(&self) -> &'static str {
+        "wip"
+    }
 
-                let mut result =
-                    format!("🔍 Starting bisect between {bad} (bad) and {good} (good)");
-                if !output.trim().is_empty() {
-                    result = format!("{}\n{}", output.trim(), result);
-                }
-                result.push_str("\n✅ Checked out commit");
+    fn description(&self) -> &'static str {
+        "Commit everything as a WIP commit that lint and hooks skip"
+    }
+}
 
-                Ok(result)
-            }
-            BisectAction::Good => {
-                if !Self::is_bisecting()? {
-                    return Err(GitXError::GitCommand(
-                        "Not currently in bisect mode".to_string(),
-                    ));
-                }
-                GitOperations::run_status(&["bisect", "good"])?;
-                Ok("✅ Marked current commit as good".to_string())
-            }
-            BisectAction::Bad => {
-                if !Self::is_bisecting()? {
-                    return Err(GitXError::GitCommand(
-                        "Not currently in bisect mode".to_string(),
-                    ));
-                }
-                GitOperations::run_status(&["bisect", "bad"])?;
-                Ok("❌ Marked current commit as bad".to_string())
-            }
-            BisectAction::Skip => {
-                if !Self::is_bisecting()? {
-                    return Err(GitXError::GitCommand(
-                        "Not currently in bisect mode".to_string(),
-                    ));
-                }
-                GitOperations::run_status(&["bisect", "skip"])?;
-                Ok("⏭️ Skipped current commit".to_string())
-            }
-            BisectAction::Reset => {
-                if !Self::is_bisecting()? {
-                    return Ok("Not currently in bisect mode".to_string());
-                }
-                GitOperations::run_status(&["bisect", "reset"])?;
-                Ok("🔄 Reset bisect and returned to original branch".to_string())
-            }
-            BisectAction::Status => {
-                if !Self::is_bisecting()? {
-                    return Ok("Not currently in bisect mode".to_string());
-                }
+impl GitCommand for WipCommand {}
 
-                let log = GitOperations::run(&["bisect", "log"])
-                    .unwrap_or_else(|_| "No bisect log available".to_string());
-                Ok(format!("🔍 Bisect status:\n{log}"))
-            }
-        }
+/// Command to uncommit the latest WIP commit back into the working tree
+#[derive(Default)]
+pub struct UnwipCommand;
+
+impl UnwipCommand {
+    pub fn new() -> Self {
+        Self
     }
 }
 
-impl Command for BisectCommand {
+impl Command for UnwipCommand {
     fn execute(&self) -> Result<String> {
-        self.execute_bisect_action()
+        let branch = GitOperations::require_branch("unwip")?;
+        let message = GitOperations::run(&["log", "-1", "--format=%B"])?;
+        let subject = message.lines().next().unwrap_or_default().to_string();
+        if !is_wip(&message) {
+            return Err(GitXError::GitCommand(format!(
+                "HEAD is not a WIP commit: '{subject}'"
+            )));
+        }
+        if GitOperations::run(&["rev-parse", "--verify", "--quiet", "HEAD~1"]).is_err() {
+            return Err(GitXError::GitCommand(
+                "The WIP commit is the first commit; there is nothing to go back to".to_string(),
+            ));
+        }
+
+        let hash = GitOperations::run(&["rev-parse", "--short", "HEAD"])?;
+        let pushed =
+            GitOperations::run_status(&["merge-base", "--is-ancestor", "HEAD", "@{u}"]).is_ok();
+        let files =
+            GitOperations::run(&["diff-tree", "--no-commit-id", "--name-only", "-r", "HEAD"])?
+                .lines()
+                .filter(|line| !line.is_empty())
+                .count();
+        GitOperations::run_status(&["reset", "--quiet", "HEAD~1"])?;
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "↩️  Uncommitted WIP {hash} '{subject}': {files} file(s) back in the working tree"
+        ));
+        if pushed {
+            output.add_line(
+                "⚠️  It was already pushed; the next push needs --force-with-lease".to_string(),
+            );
+        }
+        let remaining = branch_wip_commits(&branch)?;
+        if !remaining.is_empty() {
+            output.add_line(format!(
+                "🚧 {} more WIP commit(s) on '{branch}'",
+                remaining.len()
+            ));
+        }
+        Ok(output.content())
     }
 
     fn name(&self) -> &'static str {
-        "bisect"
+        "unwip"
     }
 
     fn description(&self) -> &'static str {
-        "Simplified Git bisect workflow for finding bugs"
+        "Uncommit the latest WIP commit back into the working tree"
     }
 }
 
-impl GitCommand for BisectCommand {}
+impl GitCommand for UnwipCommand {}
 
-impl Destructive for BisectCommand {
-    fn destruction_description(&self) -> String {
-        match &self.action {
-            BisectAction::Start { .. } => {
-                "This will start a bisect session and change your working directory".to_string()
-            }
-            BisectAction::Reset => {
-                "This will reset the bisect session and return to your original branch".to_string()
+/// Command to list and restore per-file checkpoints of uncommitted changes
+pub struct UndoFileCommand {
+    path: Option<String>,
+    restore: Option<u64>,
+}
+
+impl UndoFileCommand {
+    pub fn new(path: Option<String>) -> Self {
+        Self {
+            path,
+            restore: None,
+        }
+    }
+
+    /// Write this checkpoint back instead of listing
+    pub fn with_restore(mut self, restore: Option<u64>) -> Self {
+        self.restore = restore;
+        self
+    }
+
+    /// `path`, given relative to `prefix` (from `git rev-parse --show-prefix`), relative to the root
+    pub fn repo_path(prefix: &str, path: &str) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        for part in prefix.split('/').chain(path.split('/')) {
+            match part {
+                "" | "." => {}
+                ".." => {
+                    parts.pop();
+                }
+                part => parts.push(part),
             }
-            _ => "This will change your working directory to a different commit".to_string(),
         }
+        parts.join("/")
     }
-}
+
+    fn list_files(checkpoints: &FileCheckpoints) -> Result<String> {
+        let all = checkpoints.list()?;
+        if all.is_empty() {
+            return Ok("📭 No file checkpoints yet; they are taken before git-x operations that could overwrite uncommitted changes".to_string());
+        }
+
+        let mut files: Vec<(String, usize, String)> = Vec::new();
+        for checkpoint in all.iter().rev() {
+            match files
+                .iter_mut()
+                .find(|(path, _, _)| *path == checkpoint.path)
+            {
+                Some((_, count, _)) => *count += 1,
+                None => files.push((checkpoint.path.clone(), 1, checkpoint.when())),
+            }
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!("🕘 Files with checkpoints ({})", files.len()));
+        for (path, count, latest) in files {
+            output.add_line(format!(
+                "   {path} — {count} checkpoint(s), latest {latest}"
+            ));
+        }
+        output.add_line("💡 Show a file's checkpoints with 'git x undo-file <path>'".to_string());
+        Ok(output.content())
+    }
+
+    fn list_history(checkpoints: &FileCheckpoints, root: &str, path: &str) -> Result<String> {
+        let history = checkpoints.history(path)?;
+        if history.is_empty() {
+            return Err(GitXError::Other(format!("No checkpoints for {path}")));
+        }
+        let current = std::fs::read(std::path::Path::new(root).join(path)).ok();
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!("🕘 Checkpoints for {path} (newest first)"));
+        for checkpoint in &history {
+            let content = checkpoints.content(checkpoint).unwrap_or_default();
+            let state = if current.as_ref() == Some(&content) {
+                ", same as working copy"
+            } else {
+                ""
+            };
+            output.add_line(format!(
+                "   #{:<4} {}  {} ({} bytes{state})",
+                checkpoint.id,
+                checkpoint.when(),
+                checkpoint.operation,
+                content.len()
+            ));
+        }
+        output.add_line(format!(
+            "💡 Restore one with 'git x undo-file {path} --restore {}'",
+            history[0].id
+        ));
+        Ok(output.content())
+    }
+}
+
+impl Command for UndoFileCommand {
+    fn execute(&self) -> Result<String> {
+        let checkpoints = FileCheckpoints::open()?;
+        let root = GitOperations::repo_root()?;
+        let path = match &self.path {
+            Some(path) => {
+                let prefix = GitOperations::run(&["rev-parse", "--show-prefix"])?;
+                Some(Self::repo_path(&prefix, path))
+            }
+            None => None,
+        };
+
+        let Some(id) = self.restore else {
+            return match path {
+                Some(path) => Self::list_history(&checkpoints, &root, &path),
+                None => Self::list_files(&checkpoints),
+            };
+        };
+
+        if let Some(path) = &path
+            && checkpoints.get(id)?.path != *path
+        {
+            return Err(GitXError::Other(format!(
+                "Checkpoint #{id} is not a checkpoint of {path}"
+            )));
+        }
+        let (checkpoint, replaced) = checkpoints.restore(std::path::Path::new(&root), id)?;
+        let mut message = format!(
+            "✅ Restored {} from checkpoint #{id} ({}, {})",
+            checkpoint.path,
+            checkpoint.operation,
+            checkpoint.when()
+        );
+        if let Some(replaced) = replaced {
+            message.push_str(&format!(
+                "\n💾 The content it replaced was saved as checkpoint #{}",
+                replaced.id
+            ));
+        }
+        Ok(message)
+    }
+
+    fn name(&self) -> &'static str {
+        "undo-file"
+    }
+
+    fn description(&self) -> &'static str {
+        "List and restore checkpoints of uncommitted file changes"
+    }
+}
+
+impl GitCommand for UndoFileCommand {}
+
+/// Command to squash a range of commits without an interactive rebase
+pub struct SquashCommand {
+    target: String,
+    message: Option<String>,
+}
+
+impl SquashCommand {
+    pub fn new(target: String, message: Option<String>) -> Self {
+        Self { target, message }
+    }
+
+    /// Resolve the target into the base commit the range is squashed onto
+    ///
+    /// Accepts a commit count (`4`), a range ending at HEAD (`abc123..` or
+    /// `abc123..HEAD`), or a bare reference whose descendants are squashed.
+    fn resolve_base(&self) -> Result<String> {
+        let target = self.target.trim();
+
+        if let Ok(count) = target.parse::<u32>() {
+            if count < 2 {
+                return Err(GitXError::Parse(
+                    "Need at least 2 commits to squash".to_string(),
+                ));
+            }
+            return GitOperations::run(&["rev-parse", "--verify", &format!("HEAD~{count}")])
+                .map_err(|_| {
+                    GitXError::GitCommand(format!(
+                        "Cannot squash {count} commits: the range would include the root commit"
+                    ))
+                });
+        }
+
+        let base = match target.split_once("..") {
+            Some((base, end)) => {
+                if !end.is_empty() && end != "HEAD" {
+                    let end_sha = GitOperations::run(&["rev-parse", "--verify", end])?;
+                    let head_sha = GitOperations::run(&["rev-parse", "HEAD"])?;
+                    if end_sha != head_sha {
+                        return Err(GitXError::Parse(format!(
+                            "Range must end at HEAD, got '{end}'"
+                        )));
+                    }
+                }
+                base
+            }
+            None => target,
+        };
+
+        let sha = GitOperations::run(&["rev-parse", "--verify", &format!("{base}^{{commit}}")])
+            .map_err(|_| GitXError::Parse(format!("Invalid commit reference: '{base}'")))?;
+        if GitOperations::run(&["merge-base", "--is-ancestor", &sha, "HEAD"]).is_err() {
+            return Err(GitXError::GitCommand(format!(
+                "Commit {base} is not in the history of the current branch"
+            )));
+        }
+        Ok(sha)
+    }
+
+    fn count_commits(base: &str) -> Result<usize> {
+        let count = GitOperations::run(&["rev-list", "--count", &format!("{base}..HEAD")])?;
+        count
+            .parse()
+            .map_err(|_| GitXError::Parse(format!("Invalid commit count: {count}")))
+    }
+
+    /// Number of commits in the range that already exist on a remote
+    fn count_pushed(base: &str, total: usize) -> Result<usize> {
+        let unpushed = GitOperations::run(&[
+            "rev-list",
+            "--count",
+            &format!("{base}..HEAD"),
+            "--not",
+            "--remotes",
+        ])?;
+        let unpushed: usize = unpushed.parse().unwrap_or(total);
+        Ok(total.saturating_sub(unpushed))
+    }
+
+    /// Combine the messages of all squashed commits, oldest first
+    fn combined_message(base: &str) -> Result<String> {
+        let log = GitOperations::run(&[
+            "log",
+            "--reverse",
+            "--format=%B%x00",
+            &format!("{base}..HEAD"),
+        ])?;
+
+        Ok(log
+            .split('\0')
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+}
+
+impl Command for SquashCommand {
+    fn execute(&self) -> Result<String> {
+        let base = self.resolve_base()?;
+        let count = Self::count_commits(&base)?;
+
+        if count < 2 {
+            return Err(GitXError::GitCommand(format!(
+                "Nothing to squash: only {count} commit(s) after {}",
+                self.target
+            )));
+        }
+
+        if !GitOperations::staged_files()?.is_empty() {
+            return Err(GitXError::GitCommand(
+                "Staged changes would be folded into the squashed commit. Commit or unstage them first"
+                    .to_string(),
+            ));
+        }
+
+        let mut result = String::new();
+
+        let pushed = Self::count_pushed(&base, count)?;
+        if pushed > 0 {
+            result.push_str(&format!(
+                "⚠️  {pushed} of {count} commits are already pushed; you will need to force-push\n"
+            ));
+            if !self.confirm_destruction()? {
+                return Ok("Operation cancelled by user.".to_string());
+            }
+        }
+
+        let message = match &self.message {
+            Some(message) => message.clone(),
+            None => Self::combined_message(&base)?,
+        };
+
+        let backup = self.create_backup()?;
+
+        // A failed commit puts the branch back where it was so nothing is lost
+        crate::core::safety::Transaction::begin("squash")?.run(|| {
+            GitOperations::run(&["reset", "--soft", &base])?;
+            GitOperations::run(&["commit", "-m", &message])
+        })?;
+
+        let new_head = GitOperations::short_hash("HEAD")?;
+        result.push_str(&format!("✅ Squashed {count} commits into {new_head}\n"));
+        if let Some(backup) = backup {
+            result.push_str(&format!("💾 Backup branch: {backup}\n"));
+            result.push_str(&format!("💡 To undo, run: git reset --hard {backup}"));
+        }
+
+        Ok(result.trim_end().to_string())
+    }
+
+    fn name(&self) -> &'static str {
+        "squash"
+    }
+
+    fn description(&self) -> &'static str {
+        "Squash the last N commits (or a range) into one without an interactive rebase"
+    }
+}
+
+impl GitCommand for SquashCommand {}
+
+impl Destructive for SquashCommand {
+    fn destruction_description(&self) -> String {
+        format!(
+            "This will rewrite history by squashing commits from {} into a single commit",
+            self.target
+        )
+    }
+
+    fn create_backup(&self) -> Result<Option<String>> {
+        crate::core::safety::Safety::create_backup_branch(Some("squash")).map(Some)
+    }
+}
+
+/// Command to reword an arbitrary unpushed commit without a manual interactive rebase
+pub struct RewordCommand {
+    commit: RefName,
+    message: String,
+}
+
+impl RewordCommand {
+    pub fn new(commit: RefName, message: String) -> Self {
+        Self { commit, message }
+    }
+
+    /// Tree hashes of every commit in `range`, newest first
+    fn trees(range: &str) -> Result<Vec<String>> {
+        Ok(GitOperations::run(&["log", "--format=%T", range])?
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    /// Run the rebase with a generated todo and a non-interactive message editor
+    fn run_rebase(&self, target: &str, base: Option<&str>, range: &str) -> Result<()> {
+        let git_dir = crate::core::validation::Validate::git_dir()?;
+        let todo_path = git_dir.join("git-x-reword-todo");
+        let message_path = git_dir.join("git-x-reword-message");
+
+        let todo = GitOperations::run(&["rev-list", "--reverse", range])?
+            .lines()
+            .map(|sha| {
+                let action = if sha == target { "reword" } else { "pick" };
+                format!("{action} {sha}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&todo_path, format!("{todo}\n"))?;
+        std::fs::write(&message_path, format!("{}\n", self.message))?;
+
+        let mut args = vec!["rebase", "-i", "--no-autosquash"];
+        args.push(base.unwrap_or("--root"));
+
+        let sequence_editor = format!("cp {}", shell_quote(&todo_path.display().to_string()));
+        let editor = format!("cp {}", shell_quote(&message_path.display().to_string()));
+        let result = GitOperations::run_with_env(
+            &args,
+            &[
+                ("GIT_SEQUENCE_EDITOR", &sequence_editor),
+                ("GIT_EDITOR", &editor),
+            ],
+        );
+
+        let _ = std::fs::remove_file(&todo_path);
+        let _ = std::fs::remove_file(&message_path);
+
+        if let Err(error) = result {
+            let _ = GitOperations::run(&["rebase", "--abort"]);
+            let reason = match error {
+                GitXError::GitProcess(failure) => failure.stderr,
+                other => other.to_string(),
+            };
+            return Err(GitXError::GitCommand(format!(
+                "Rebase failed and was aborted: {reason}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Command for RewordCommand {
+    fn execute(&self) -> Result<String> {
+        if self.message.trim().is_empty() {
+            return Err(GitXError::Parse(
+                "Commit message cannot be empty".to_string(),
+            ));
+        }
+
+        let target = CommitId::resolve(&self.commit).map_err(|_| {
+            GitXError::Parse(format!("Invalid commit reference: '{}'", self.commit))
+        })?;
+
+        if GitOperations::run(&["merge-base", "--is-ancestor", target.as_str(), "HEAD"]).is_err() {
+            return Err(GitXError::GitCommand(format!(
+                "Commit {} is not in the history of the current branch",
+                self.commit
+            )));
+        }
+
+        let pushed_to = GitOperations::run(&["branch", "-r", "--contains", target.as_str()])?;
+        if !pushed_to.trim().is_empty() {
+            return Err(GitXError::GitCommand(format!(
+                "Commit {} has already been pushed ({}). Rewording it would rewrite shared history",
+                self.commit,
+                pushed_to.lines().next().unwrap_or_default().trim()
+            )));
+        }
+
+        if !GitOperations::is_working_directory_clean()? {
+            return Err(GitXError::GitCommand(
+                "Working directory is not clean. Please commit or stash your changes first."
+                    .to_string(),
+            ));
+        }
+
+        let base =
+            GitOperations::run(&["rev-parse", "--verify", "--quiet", &format!("{target}^")]).ok();
+        let range = match &base {
+            Some(base) => format!("{base}..HEAD"),
+            None => "HEAD".to_string(),
+        };
+
+        if !GitOperations::run(&["rev-list", "--merges", &range])?.is_empty() {
+            return Err(GitXError::GitCommand(
+                "Cannot reword across merge commits; the rebase would flatten them".to_string(),
+            ));
+        }
+
+        let original_head = GitOperations::run(&["rev-parse", "HEAD"])?;
+        let trees_before = Self::trees(&range)?;
+
+        self.run_rebase(target.as_str(), base.as_deref(), &range)?;
+
+        // Verify only messages changed: every rewritten commit keeps its tree
+        if Self::trees(&range)? != trees_before {
+            GitOperations::run(&["reset", "--hard", &original_head])?;
+            return Err(GitXError::GitCommand(
+                "Reword changed file contents, so history was restored to its original state"
+                    .to_string(),
+            ));
+        }
+
+        // The reworded commit sits as many commits below HEAD as it had descendants
+        let descendants =
+            GitOperations::run(&["rev-list", "--count", &format!("{target}..{original_head}")])?;
+        let old_short = GitOperations::short_hash(target.as_str())?;
+        let new_sha = GitOperations::run(&["rev-parse", &format!("HEAD~{descendants}")])?;
+        let new_short = GitOperations::short_hash(&new_sha)?;
+        let original_short = GitOperations::short_hash(&original_head)?;
+
+        Ok(format!(
+            "✅ Reworded {old_short} → {new_short}\n🌳 Verified {} commit(s): only messages changed\n💡 To undo, run: git reset --hard {original_short}",
+            trees_before.len()
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "reword"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reword an unpushed commit's message without a manual interactive rebase"
+    }
+}
+
+impl GitCommand for RewordCommand {}
+
+impl Destructive for RewordCommand {
+    fn destruction_description(&self) -> String {
+        format!(
+            "This will rewrite the history after {} to change its message",
+            self.commit
+        )
+    }
+}
+
+/// Marker identifying the commit-msg hook written by `init-hooks`
+const COMMIT_MSG_HOOK_MARKER: &str = "git x init-hooks --check";
+
+/// Command that installs a commit-msg hook enforcing Conventional Commits,
+/// and performs the check when the hook runs
+pub struct InitHooksCommand {
+    force: bool,
+    check: Option<PathBuf>,
+}
+
+impl Default for InitHooksCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InitHooksCommand {
+    pub fn new() -> Self {
+        Self {
+            force: false,
+            check: None,
+        }
+    }
+
+    /// Replace a commit-msg hook git-x didn't write, keeping a backup
+    pub fn with_force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    /// Lint the message in `path` instead of installing the hook
+    pub fn with_check(mut self, path: PathBuf) -> Self {
+        self.check = Some(path);
+        self
+    }
+
+    /// Rendered output and whether the commit should be rejected
+    pub fn run(&self) -> Result<(String, bool)> {
+        let Some(path) = &self.check else {
+            return self.install().map(|output| (output, false));
+        };
+
+        if std::env::var(SKIP_ENV).is_ok_and(|value| !value.is_empty() && value != "0") {
+            return Ok((
+                format!("⏭️  Commit message check skipped ({SKIP_ENV} is set)"),
+                false,
+            ));
+        }
+
+        let message = std::fs::read_to_string(path)?;
+        let linter = CommitLinter::new(Config::get_list("lint.types"));
+        let violations = linter.lint(&message);
+        Ok((
+            Self::render(&violations, linter.types()),
+            !violations.is_empty(),
+        ))
+    }
+
+    /// Explain each violation, quoting the offending line
+    pub fn render(violations: &[LintViolation], types: &[String]) -> String {
+        if violations.is_empty() {
+            return "✅ Commit message follows Conventional Commits".to_string();
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line("❌ Commit message does not follow Conventional Commits:".to_string());
+        let mut last_line = None;
+        for violation in violations {
+            if last_line != Some(violation.line) {
+                output.add_line(format!("  line {}: \"{}\"", violation.line, violation.text));
+                last_line = Some(violation.line);
+            }
+            output.add_line(format!("    → {}", violation.message));
+        }
+        output.add_line(format!("💡 Allowed types: {}", types.join(", ")));
+        output.add_line("💡 Example: feat(parser): support nested arrays".to_string());
+        output.add_line(format!(
+            "💡 Skip the check once with '{SKIP_ENV}=1 git commit ...'"
+        ));
+        output.content()
+    }
+
+    fn install(&self) -> Result<String> {
+        let hook = GitOperations::run(&["rev-parse", "--git-path", "hooks/commit-msg"])?;
+        let path = std::path::Path::new(&hook);
+        let mut output = BufferedOutput::new();
+
+        if let Ok(existing) = std::fs::read_to_string(path) {
+            if existing.contains(COMMIT_MSG_HOOK_MARKER) {
+                return Ok(format!(
+                    "✅ Conventional commit hook already installed at {hook}"
+                ));
+            }
+            // A hook holding only the ticket check gets the lint appended
+            if existing.contains(TICKETS_HOOK_MARKER) {
+                let combined = existing.replacen(
+                    &format!("exec {TICKETS_HOOK_MARKER} \"$1\""),
+                    &format!("{TICKETS_HOOK_MARKER} \"$1\"\nexec {COMMIT_MSG_HOOK_MARKER} \"$1\""),
+                    1,
+                );
+                write_hook(path, &combined)?;
+                return Ok(format!(
+                    "🪝 Added the Conventional Commits check to the commit-msg hook at {hook}"
+                ));
+            }
+            if !self.force {
+                return Err(GitXError::Other(format!(
+                    "A commit-msg hook already exists at {hook}; rerun with --force to replace it (a backup is kept)"
+                )));
+            }
+            let backup = format!("{hook}.backup");
+            std::fs::rename(path, &backup)?;
+            output.add_line(format!("📦 Moved the existing hook to {backup}"));
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(
+            path,
+            format!(
+                "#!/bin/sh\n# Installed by git-x: enforce Conventional Commits messages\n# Skip once with {SKIP_ENV}=1\nexec {COMMIT_MSG_HOOK_MARKER} \"$1\"\n"
+            ),
+        )?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        output.add_line(format!("🪝 Installed commit-msg hook at {hook}"));
+        Ok(output.content())
+    }
+}
+
+impl Command for InitHooksCommand {
+    fn execute(&self) -> Result<String> {
+        self.run().map(|(output, _)| output)
+    }
+
+    fn name(&self) -> &'static str {
+        "init-hooks"
+    }
+
+    fn description(&self) -> &'static str {
+        "Install a commit-msg hook that enforces Conventional Commits"
+    }
+}
+
+impl GitCommand for InitHooksCommand {}
+
+/// Marker identifying the prepare-commit-msg hook written by `commit-template --install`
+const TEMPLATE_HOOK_MARKER: &str = "git x commit-template --fill";
+
+/// A message template used when the staged paths fall under `pattern`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitTemplate {
+    /// Directory prefix such as `docs/`, or a path where `*` matches any run of characters
+    pub pattern: String,
+    /// Template file, relative to the repository root
+    pub file: String,
+}
+
+impl CommitTemplate {
+    /// Whether `path` falls under the pattern; a pattern without `*` also covers everything
+    /// below it, so `docs` and `docs/` match `docs/guide.md`
+    pub fn matches(&self, path: &str) -> bool {
+        let pattern = self.pattern.trim_end_matches('/');
+        glob_match(pattern, path) || path.starts_with(&format!("{pattern}/"))
+    }
+}
+
+/// The `[commit.templates]` table of `.git-x.toml`, in file order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitTemplates {
+    pub templates: Vec<CommitTemplate>,
+}
+
+impl CommitTemplates {
+    /// Read the templates from `.git-x.toml` at the repository root; none when the file is missing
+    pub fn load() -> Result<Self> {
+        let path = PathBuf::from(GitOperations::repo_root()?).join(PROJECT_CONFIG);
+        match std::fs::read_to_string(&path) {
+            Ok(toml) => Self::parse(&toml),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Parse templates such as:
+    ///
+    /// ```toml
+    /// [commit.templates]
+    /// "docs/" = ".github/commit-templates/docs.txt"
+    /// "services/payments/" = ".github/commit-templates/payments.txt"
+    /// "*" = ".github/commit-templates/default.txt"
+    /// ```
+    pub fn parse(toml: &str) -> Result<Self> {
+        let document = crate::core::toml::parse(toml)?;
+        let Some(table) = document
+            .get("commit")
+            .and_then(|commit| commit.get("templates"))
+        else {
+            return Ok(Self::default());
+        };
+        let ExportValue::Record(entries) = table else {
+            return Err(GitXError::Parse(format!(
+                "[commit.templates] in {PROJECT_CONFIG} must be a table of path patterns"
+            )));
+        };
+
+        let templates = entries
+            .iter()
+            .map(|(pattern, value)| match value.as_str() {
+                Some(file) if !file.trim().is_empty() => Ok(CommitTemplate {
+                    pattern: pattern.clone(),
+                    file: file.to_string(),
+                }),
+                _ => Err(GitXError::Parse(format!(
+                    "Commit template '{pattern}' in {PROJECT_CONFIG} needs a template file"
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { templates })
+    }
+
+    /// The template for a commit of `paths`, in order of precedence:
+    ///
+    /// 1. a pattern covering every staged path beats one covering only some of them
+    /// 2. then the longest, most specific pattern
+    /// 3. then the one listed first
+    pub fn select(&self, paths: &[String]) -> Option<&CommitTemplate> {
+        let mut best: Option<(&CommitTemplate, bool)> = None;
+        for template in &self.templates {
+            let matched = paths.iter().filter(|path| template.matches(path)).count();
+            if matched == 0 {
+                continue;
+            }
+            let covers_all = matched == paths.len();
+            let better = match best {
+                None => true,
+                Some((current, current_covers_all)) => {
+                    (covers_all, template.pattern.len())
+                        > (current_covers_all, current.pattern.len())
+                }
+            };
+            if better {
+                best = Some((template, covers_all));
+            }
+        }
+        best.map(|(template, _)| template)
+    }
+
+    /// Put `template` in place of the message git prepared, keeping its comment lines
+    /// (and the `--verbose` diff below them)
+    pub fn fill(message: &str, template: &str) -> String {
+        let comments = message
+            .find("\n#")
+            .map(|start| &message[start + 1..])
+            .or_else(|| message.starts_with('#').then_some(message))
+            .unwrap_or("");
+        format!("{}\n\n{comments}", template.trim_end())
+    }
+}
+
+/// Command that shows which commit template applies to the staged paths, installs the
+/// prepare-commit-msg hook applying it, and fills in the message when the hook runs
+pub struct CommitTemplateCommand {
+    install: bool,
+    force: bool,
+    fill: Option<(PathBuf, String)>,
+}
+
+impl Default for CommitTemplateCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommitTemplateCommand {
+    pub fn new() -> Self {
+        Self {
+            install: false,
+            force: false,
+            fill: None,
+        }
+    }
+
+    /// Install the prepare-commit-msg hook instead of previewing
+    pub fn with_install(mut self) -> Self {
+        self.install = true;
+        self
+    }
+
+    /// Replace a prepare-commit-msg hook git-x didn't write, keeping a backup
+    pub fn with_force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    /// Fill the message file git passes the hook; `source` is the hook's second argument
+    pub fn with_fill(mut self, path: PathBuf, source: String) -> Self {
+        self.fill = Some((path, source));
+        self
+    }
+
+    fn staged_paths() -> Result<Vec<String>> {
+        Ok(GitOperations::run(&["diff", "--cached", "--name-only"])?
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    fn read_template(template: &CommitTemplate) -> Result<String> {
+        let path = PathBuf::from(GitOperations::repo_root()?).join(&template.file);
+        std::fs::read_to_string(&path).map_err(|_| {
+            GitXError::Other(format!(
+                "Commit template '{}' for '{}' in {PROJECT_CONFIG} cannot be read",
+                template.file, template.pattern
+            ))
+        })
+    }
+
+    fn preview(&self) -> Result<String> {
+        let templates = CommitTemplates::load()?;
+        if templates.templates.is_empty() {
+            return Ok(format!(
+                "No commit templates configured; add a [commit.templates] table to {PROJECT_CONFIG}"
+            ));
+        }
+        let paths = Self::staged_paths()?;
+        if paths.is_empty() {
+            return Ok("Nothing staged; stage changes to see which template applies".to_string());
+        }
+
+        let Some(template) = templates.select(&paths) else {
+            return Ok(format!(
+                "No commit template matches the {} staged path(s); git's commit.template applies",
+                paths.len()
+            ));
+        };
+        let content = Self::read_template(template)?;
+        let matched = paths.iter().filter(|path| template.matches(path)).count();
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "📝 {} (pattern '{}', {matched} of {} staged path(s))",
+            template.file,
+            template.pattern,
+            paths.len()
+        ));
+        output.add_line(String::new());
+        for line in content.trim_end().lines() {
+            output.add_line(if line.is_empty() {
+                String::new()
+            } else {
+                format!("  {line}")
+            });
+        }
+        if !Self::hook_installed()? {
+            output.add_line(String::new());
+            output.add_line(
+                "💡 Run 'git x commit-template --install' to apply it on git commit".to_string(),
+            );
+        }
+        Ok(output.content())
+    }
+
+    /// Fill the message for a plain `git commit`, or one that started from git's own
+    /// commit.template; messages given with -m, merges, squashes and amends are left alone
+    fn fill(path: &std::path::Path, source: &str) -> Result<String> {
+        if !source.is_empty() && source != "template" {
+            return Ok(String::new());
+        }
+        let templates = CommitTemplates::load()?;
+        let Some(template) = templates.select(&Self::staged_paths()?) else {
+            return Ok(String::new());
+        };
+        let content = Self::read_template(template)?;
+        let message = std::fs::read_to_string(path)?;
+        std::fs::write(path, CommitTemplates::fill(&message, &content))?;
+        Ok(String::new())
+    }
+
+    fn hook_path() -> Result<String> {
+        GitOperations::run(&["rev-parse", "--git-path", "hooks/prepare-commit-msg"])
+    }
+
+    fn hook_installed() -> Result<bool> {
+        Ok(std::fs::read_to_string(Self::hook_path()?)
+            .is_ok_and(|hook| hook.contains(TEMPLATE_HOOK_MARKER)))
+    }
+
+    fn install(&self) -> Result<String> {
+        let hook = Self::hook_path()?;
+        let path = std::path::Path::new(&hook);
+        let mut output = BufferedOutput::new();
+
+        if let Ok(existing) = std::fs::read_to_string(path) {
+            if existing.contains(TEMPLATE_HOOK_MARKER) {
+                return Ok(format!(
+                    "✅ Commit template hook already installed at {hook}"
+                ));
+            }
+            if !self.force {
+                return Err(GitXError::Other(format!(
+                    "A prepare-commit-msg hook already exists at {hook}; rerun with --force to replace it (a backup is kept)"
+                )));
+            }
+            let backup = format!("{hook}.backup");
+            std::fs::rename(path, &backup)?;
+            output.add_line(format!("📦 Moved the existing hook to {backup}"));
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(
+            path,
+            format!(
+                "#!/bin/sh\n# Installed by git-x: start commit messages from the template in {PROJECT_CONFIG}\nexec {TEMPLATE_HOOK_MARKER} \"$1\" --source \"$2\"\n"
+            ),
+        )?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        output.add_line(format!("🪝 Installed prepare-commit-msg hook at {hook}"));
+        Ok(output.content())
+    }
+}
+
+impl Command for CommitTemplateCommand {
+    fn execute(&self) -> Result<String> {
+        if let Some((path, source)) = &self.fill {
+            return Self::fill(path, source);
+        }
+        if self.install {
+            return self.install();
+        }
+        self.preview()
+    }
+
+    fn name(&self) -> &'static str {
+        "commit-template"
+    }
+
+    fn description(&self) -> &'static str {
+        "Start commit messages from a template chosen by the staged paths"
+    }
+}
+
+impl GitCommand for CommitTemplateCommand {}
+
+/// Marker identifying the ticket check `tickets --install-hook` adds to the commit-msg hook
+const TICKETS_HOOK_MARKER: &str = "git x tickets --check";
+
+/// Command that lists commits grouped by the tickets their messages reference,
+/// opens a ticket in the browser, or warns from the commit-msg hook when a
+/// message references none
+pub struct TicketsCommand {
+    range: Option<String>,
+    open: Option<String>,
+    install: bool,
+    force: bool,
+    check: Option<PathBuf>,
+}
+
+impl Default for TicketsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TicketsCommand {
+    pub fn new() -> Self {
+        Self {
+            range: None,
+            open: None,
+            install: false,
+            force: false,
+            check: None,
+        }
+    }
+
+    /// Commits to scan instead of the current branch's own
+    pub fn with_range(mut self, range: String) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Open this ticket's URL instead of listing
+    pub fn with_open(mut self, id: String) -> Self {
+        self.open = Some(id);
+        self
+    }
+
+    /// Add the ticket check to the commit-msg hook instead of listing
+    pub fn with_install(mut self) -> Self {
+        self.install = true;
+        self
+    }
+
+    /// Replace a commit-msg hook git-x didn't write, keeping a backup
+    pub fn with_force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    /// Check the message in `path`, as the hook does
+    pub fn with_check(mut self, path: PathBuf) -> Self {
+        self.check = Some(path);
+        self
+    }
+
+    /// The current branch's commits not on the default branch; on the default
+    /// branch itself, the commits since the last tag
+    fn default_range() -> String {
+        let current = GitOperations::current_branch().ok();
+        if let Some(base) = GitOperations::default_branch()
+            && current.as_deref() != Some(base.as_str())
+        {
+            return format!("{base}..HEAD");
+        }
+        match GitOperations::run(&["describe", "--tags", "--abbrev=0"]) {
+            Ok(tag) => format!("{tag}..HEAD"),
+            Err(_) => "HEAD".to_string(),
+        }
+    }
+
+    /// Commits in `range`, newest first, as (short hash, subject, full message)
+    fn commits(range: &str) -> Result<Vec<(String, String, String)>> {
+        let log = GitOperations::run(&["log", "--format=%h%x1f%s%x1f%B%x1e", range, "--"])
+            .map_err(|_| GitXError::Parse(format!("Invalid range '{range}'")))?;
+        Ok(log
+            .split('\x1e')
+            .filter_map(|record| {
+                let mut fields = record.trim_start_matches('\n').splitn(3, '\x1f');
+                let hash = fields.next()?.to_string();
+                let subject = fields.next()?.to_string();
+                let message = fields.next().unwrap_or_default().to_string();
+                (!hash.is_empty()).then_some((hash, subject, message))
+            })
+            .collect())
+    }
+
+    /// Group commits by ticket, in the order tickets first appear; commits
+    /// referencing several tickets are listed under each, the rest come last
+    pub fn group(
+        commits: &[(String, String, String)],
+        pattern: &TicketPattern,
+    ) -> (Vec<(String, Vec<String>)>, Vec<String>) {
+        let mut tickets: Vec<(String, Vec<String>)> = Vec::new();
+        let mut untracked = Vec::new();
+        for (hash, subject, message) in commits {
+            let line = format!("{hash} {subject}");
+            let ids = pattern.find(message);
+            if ids.is_empty() {
+                untracked.push(line);
+                continue;
+            }
+            for id in ids {
+                match tickets.iter_mut().find(|(known, _)| *known == id) {
+                    Some((_, lines)) => lines.push(line.clone()),
+                    None => tickets.push((id, vec![line.clone()])),
+                }
+            }
+        }
+        (tickets, untracked)
+    }
+
+    fn list(&self) -> Result<String> {
+        let range = self.range.clone().unwrap_or_else(Self::default_range);
+        let pattern = TicketPattern::from_config()?;
+        let commits = Self::commits(&range)?;
+        if commits.is_empty() {
+            return Ok(format!(
+                "✅ No commits in {range}; pass a range such as 'main..HEAD'"
+            ));
+        }
+
+        let template = Config::get("tickets.url");
+        let (tickets, untracked) = Self::group(&commits, &pattern);
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🎫 {} ticket(s) across {} commit(s) in {range}",
+            tickets.len(),
+            commits.len()
+        ));
+        for (id, lines) in &tickets {
+            let url = template
+                .as_deref()
+                .and_then(|template| ticket_url(template, id).ok())
+                .map(|url| format!(" → {url}"))
+                .unwrap_or_default();
+            output.add_line(format!("{id} ({} commit(s)){url}", lines.len()));
+            for line in lines {
+                output.add_line(format!("  {line}"));
+            }
+        }
+        if !untracked.is_empty() {
+            output.add_line(format!("❓ No ticket ({} commit(s)):", untracked.len()));
+            for line in &untracked {
+                output.add_line(format!("  {line}"));
+            }
+        }
+        if tickets.is_empty() {
+            output.add_line(format!(
+                "💡 No message matched {}; set git-x.tickets.pattern to your tracker's format",
+                pattern.as_str()
+            ));
+        } else if template.is_none() {
+            output.add_line(format!(
+                "💡 Link tickets with 'git config git-x.tickets.url https://jira.example.com/browse/{ID_PLACEHOLDER}'"
+            ));
+        }
+        Ok(output.content())
+    }
+
+    fn open(id: &str) -> Result<String> {
+        let template = Config::get("tickets.url").ok_or_else(|| {
+            GitXError::Other(format!(
+                "No ticket URL configured; set one with 'git config git-x.tickets.url https://jira.example.com/browse/{ID_PLACEHOLDER}'"
+            ))
+        })?;
+        let url = ticket_url(&template, id)?;
+        Browser::open(&url)
+            .map_err(|e| GitXError::Other(format!("{e}\n💡 The ticket is at {url}")))?;
+        Ok(format!("🌐 Opened {url}"))
+    }
+
+    /// A warning when the message references no ticket, empty otherwise
+    pub fn check(path: &std::path::Path) -> Result<String> {
+        let message = std::fs::read_to_string(path)?;
+        let lines = clean_message(&message);
+        let Some(header) = lines.first() else {
+            return Ok(String::new());
+        };
+        if GENERATED_PREFIXES.iter().any(|p| header.starts_with(p)) || is_wip(&message) {
+            return Ok(String::new());
+        }
+        let pattern = TicketPattern::from_config()?;
+        if !pattern.find(&lines.join("\n")).is_empty() {
+            return Ok(String::new());
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "⚠️  Commit message references no ticket (pattern: {})",
+            pattern.as_str()
+        ));
+        let branch = GitOperations::current_branch().unwrap_or_default();
+        if let Some(id) = pattern.find(&branch).first() {
+            output.add_line(format!(
+                "💡 The branch name mentions {id}; add it with 'git commit --amend'"
+            ));
+        }
+        Ok(output.content())
+    }
+
+    fn install(&self) -> Result<String> {
+        let hook = GitOperations::run(&["rev-parse", "--git-path", "hooks/commit-msg"])?;
+        let path = std::path::Path::new(&hook);
+        let mut output = BufferedOutput::new();
+
+        if let Ok(existing) = std::fs::read_to_string(path) {
+            if existing.contains(TICKETS_HOOK_MARKER) {
+                return Ok(format!("✅ Ticket check already installed at {hook}"));
+            }
+            // Run ahead of the Conventional Commits check, which may reject the commit
+            if existing.contains(COMMIT_MSG_HOOK_MARKER) {
+                let combined = existing.replacen(
+                    &format!("exec {COMMIT_MSG_HOOK_MARKER}"),
+                    &format!("{TICKETS_HOOK_MARKER} \"$1\"\nexec {COMMIT_MSG_HOOK_MARKER}"),
+                    1,
+                );
+                write_hook(path, &combined)?;
+                return Ok(format!(
+                    "🪝 Added the ticket check to the commit-msg hook at {hook}"
+                ));
+            }
+            if !self.force {
+                return Err(GitXError::Other(format!(
+                    "A commit-msg hook already exists at {hook}; rerun with --force to replace it (a backup is kept)"
+                )));
+            }
+            let backup = format!("{hook}.backup");
+            std::fs::rename(path, &backup)?;
+            output.add_line(format!("📦 Moved the existing hook to {backup}"));
+        }
+
+        write_hook(
+            path,
+            &format!(
+                "#!/bin/sh\n# Installed by git-x: warn when a commit message references no ticket\nexec {TICKETS_HOOK_MARKER} \"$1\"\n"
+            ),
+        )?;
+        output.add_line(format!("🪝 Installed commit-msg hook at {hook}"));
+        Ok(output.content())
+    }
+}
+
+impl Command for TicketsCommand {
+    fn execute(&self) -> Result<String> {
+        if let Some(path) = &self.check {
+            return Self::check(path);
+        }
+        if self.install {
+            return self.install();
+        }
+        if let Some(id) = &self.open {
+            return Self::open(id);
+        }
+        self.list()
+    }
+
+    fn name(&self) -> &'static str {
+        "tickets"
+    }
+
+    fn description(&self) -> &'static str {
+        "List commits by the tickets their messages reference"
+    }
+}
+
+impl GitCommand for TicketsCommand {}
+
+/// Write an executable hook script, creating the hooks directory if needed
+fn write_hook(path: &std::path::Path, script: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, script)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+/// Email patch workflow actions
+#[derive(Debug, Clone)]
+pub enum PatchAction {
+    Send {
+        range: String,
+        output_dir: String,
+        onto: Option<String>,
+    },
+    Apply {
+        source: String,
+        three_way: bool,
+    },
+}
+
+/// Command wrapping `git format-patch` and `git am` for mailing-list workflows
+pub struct PatchCommand {
+    action: PatchAction,
+}
+
+impl PatchCommand {
+    pub fn new(action: PatchAction) -> Self {
+        Self { action }
+    }
+
+    /// Normalize N, `<ref>` or `<a>..<b>` into an explicit range
+    pub fn resolve_range(range: &str) -> String {
+        if !range.is_empty() && range.chars().all(|c| c.is_ascii_digit()) {
+            format!("HEAD~{range}..HEAD")
+        } else if range.contains("..") {
+            range.to_string()
+        } else {
+            format!("{range}..HEAD")
+        }
+    }
+
+    /// Patch files to apply from a directory (cover letters excluded) or a single mbox
+    pub fn collect_patches(source: &str) -> Result<Vec<String>> {
+        let path = std::path::Path::new(source);
+        if path.is_file() {
+            return Ok(vec![source.to_string()]);
+        }
+        if !path.is_dir() {
+            return Err(GitXError::GitCommand(format!(
+                "Patch source '{source}' does not exist"
+            )));
+        }
+
+        let mut patches: Vec<String> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|p| {
+                p.is_file()
+                    && p.extension()
+                        .is_some_and(|ext| ext == "patch" || ext == "eml" || ext == "mbox")
+                    && !p
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().contains("cover-letter"))
+            })
+            .map(|p| p.display().to_string())
+            .collect();
+        patches.sort();
+
+        Ok(patches)
+    }
+
+    /// Apply the series onto `onto` in a throwaway index; returns the first failing patch
+    fn check_applies(onto: &str, patches: &[String]) -> Result<Option<(String, String)>> {
+        let index = crate::core::validation::Validate::git_dir()?.join("git-x-patch-check-index");
+        let index_env = index.display().to_string();
+        let git = |args: &[&str]| {
+            GitOperations::run_with_env(args, &[("GIT_INDEX_FILE", index_env.as_str())])
+        };
+
+        let result = (|| -> Result<Option<(String, String)>> {
+            match git(&["read-tree", onto]) {
+                Err(GitXError::GitProcess(failure)) => {
+                    return Err(GitXError::GitCommand(format!(
+                        "Cannot check series against '{onto}': {}",
+                        failure.stderr
+                    )));
+                }
+                result => result?,
+            };
+
+            for patch in patches {
+                match git(&["apply", "--cached", patch]) {
+                    Err(GitXError::GitProcess(failure)) => {
+                        return Ok(Some((patch.clone(), failure.stderr)));
+                    }
+                    result => result?,
+                };
+            }
+            Ok(None)
+        })();
+
+        let _ = std::fs::remove_file(&index);
+        result
+    }
+
+    fn send(&self, range: &str, output_dir: &str, onto: Option<&str>) -> Result<String> {
+        let range = Self::resolve_range(range);
+        let count: usize = GitOperations::run(&["rev-list", "--count", "--no-merges", &range])
+            .map_err(|_| GitXError::Parse(format!("Invalid range: '{range}'")))?
+            .parse()
+            .unwrap_or(0);
+        if count == 0 {
+            return Err(GitXError::GitCommand(format!("No commits in {range}")));
+        }
+
+        let mut args = vec!["format-patch", "--no-merges", "-o", output_dir];
+        if count > 1 {
+            args.push("--cover-letter");
+        }
+        args.push(&range);
+        let files: Vec<String> = GitOperations::run(&args)?
+            .lines()
+            .map(String::from)
+            .collect();
+
+        let mut output = vec![format!(
+            "📧 Wrote {count} patch(es) to {output_dir}/{}",
+            if count > 1 {
+                " with a cover letter"
+            } else {
+                ""
+            }
+        )];
+        output.extend(files.iter().map(|file| format!("  {file}")));
+
+        // Default to checking against the range's base
+        let onto = onto
+            .map(String::from)
+            .or_else(|| range.split("..").next().map(String::from))
+            .filter(|base| !base.is_empty() && !base.starts_with("HEAD"));
+        if let Some(onto) = onto {
+            let series: Vec<String> = files
+                .iter()
+                .filter(|file| !file.contains("cover-letter"))
+                .cloned()
+                .collect();
+            match Self::check_applies(&onto, &series)? {
+                None => output.push(format!("✅ Series applies cleanly onto {onto}")),
+                Some((patch, reason)) => output.push(format!(
+                    "⚠️  Series does not apply cleanly onto {onto}: {patch} failed\n   {reason}"
+                )),
+            }
+        }
+
+        if count > 1 {
+            output.push(
+                "💡 Fill in the cover letter, then send with: git send-email <dir>/*.patch"
+                    .to_string(),
+            );
+        } else {
+            output.push("💡 Send with: git send-email <patch>".to_string());
+        }
+
+        Ok(output.join("\n"))
+    }
+
+    fn apply(&self, source: &str, three_way: bool) -> Result<String> {
+        let patches = Self::collect_patches(source)?;
+        if patches.is_empty() {
+            return Err(GitXError::GitCommand(format!(
+                "No patches found in '{source}'"
+            )));
+        }
+
+        if !GitOperations::run(&["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+            return Err(GitXError::GitCommand(
+                "Working directory has uncommitted changes. Please commit or stash them first."
+                    .to_string(),
+            ));
+        }
+
+        let original_head = GitOperations::run(&["rev-parse", "HEAD"])?;
+        let mut args = vec!["am"];
+        if three_way {
+            args.push("--3way");
+        }
+        args.extend(patches.iter().map(String::as_str));
+
+        if let Err(error) = GitOperations::run(&args) {
+            let GitXError::GitProcess(failure) = error else {
+                return Err(error);
+            };
+            // `git am` keeps the message of the patch it stopped at
+            let stopped_at =
+                GitOperations::run(&["rev-parse", "--git-path", "rebase-apply/final-commit"])
+                    .ok()
+                    .and_then(|path| std::fs::read_to_string(path).ok())
+                    .and_then(|message| message.lines().next().map(|line| line.trim().to_string()))
+                    .unwrap_or_default();
+            return Err(GitXError::GitCommand(format!(
+                "Patch '{stopped_at}' did not apply: {}. Resolve the conflicts and run 'git am --continue', or 'git am --abort' to give up",
+                failure.stderr.lines().next().unwrap_or_default().trim()
+            )));
+        }
+
+        let applied =
+            GitOperations::run(&["rev-list", "--count", &format!("{original_head}..HEAD")])?;
+        let branch = GitOperations::current_branch()?;
+        let original_short = GitOperations::short_hash(&original_head)?;
+
+        Ok(format!(
+            "✅ Applied {applied} patch(es) onto {branch}\n💡 To undo, run: git reset --hard {original_short}"
+        ))
+    }
+}
+
+impl Command for PatchCommand {
+    fn execute(&self) -> Result<String> {
+        match &self.action {
+            PatchAction::Send {
+                range,
+                output_dir,
+                onto,
+            } => self.send(range, output_dir, onto.as_deref()),
+            PatchAction::Apply { source, three_way } => self.apply(source, *three_way),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "patch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Generate and apply email-formatted patch series"
+    }
+}
+
+impl GitCommand for PatchCommand {}
+
+/// Rebase helper actions
+#[derive(Debug, Clone)]
+pub enum RebaseAction {
+    /// Skip stopped commits already upstream and continue once conflicts are resolved
+    ContinueSmart,
+}
+
+/// Command that moves a stopped rebase along, skipping commits already upstream
+pub struct RebaseCommand {
+    action: RebaseAction,
+}
+
+impl RebaseCommand {
+    pub fn new(action: RebaseAction) -> Self {
+        Self { action }
+    }
+
+    fn continue_smart(&self) -> Result<String> {
+        if !RebaseSkipper::in_progress() {
+            return Err(GitXError::GitCommand(
+                "No rebase in progress; start one with 'git rebase <upstream>' or 'git x sync'"
+                    .to_string(),
+            ));
+        }
+
+        let mut output = BufferedOutput::new();
+        match RebaseSkipper::load()?.resolve()? {
+            RebaseOutcome::Finished { skipped } => {
+                output.add_lines(skipped.iter().map(SkippedCommit::describe).collect());
+                output.add_line(if skipped.is_empty() {
+                    "✅ Rebase finished".to_string()
+                } else {
+                    format!(
+                        "✅ Rebase finished, skipped {} already-applied commit(s)",
+                        skipped.len()
+                    )
+                });
+            }
+            RebaseOutcome::Stopped {
+                skipped,
+                commit,
+                subject,
+                conflicts,
+            } => {
+                output.add_lines(skipped.iter().map(SkippedCommit::describe).collect());
+                output.add_line(format!(
+                    "⚠️  Stopped on {} {subject}: it isn't upstream yet and conflicts in:",
+                    GitOperations::short_hash(&commit)?
+                ));
+                output.add_lines(conflicts.iter().map(|file| format!("   {file}")).collect());
+                output.add_line(
+                    "💡 Resolve and 'git add' them, then run 'git x rebase continue-smart' again"
+                        .to_string(),
+                );
+            }
+        }
+        Ok(output.content())
+    }
+}
+
+impl Command for RebaseCommand {
+    fn execute(&self) -> Result<String> {
+        match self.action {
+            RebaseAction::ContinueSmart => self.continue_smart(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "rebase"
+    }
+
+    fn description(&self) -> &'static str {
+        "Continue a rebase, skipping commits already upstream"
+    }
+}
+
+impl GitCommand for RebaseCommand {}
+
+impl Destructive for PatchCommand {
+    fn destruction_description(&self) -> String {
+        match &self.action {
+            PatchAction::Send { .. } => "This will write patch files to disk".to_string(),
+            PatchAction::Apply { source, .. } => {
+                format!("This will create commits on the current branch from {source}")
+            }
+        }
+    }
+}
+
+/// Bisect workflow actions
+#[derive(Debug, Clone)]
+pub enum BisectAction {
+    Start {
+        bad: String,
+        good: String,
+    },
+    /// Start from `bad`, finding the good commit with [`BisectCommand::find_good`]
+    AutoStart {
+        bad: String,
+        test: Option<String>,
+        max_tests: usize,
+    },
+    Good,
+    Bad,
+    Skip,
+    Reset,
+    Status,
+}
+
+/// Test runs `bisect start --auto-good --test` allows unless told otherwise
+pub const DEFAULT_BISECT_MAX_TESTS: usize = 8;
+
+/// Where `bisect start --auto-good` found its good commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoodCommit {
+    /// The test passed `distance` first-parent commits before bad
+    TestPassed { commit: String, distance: usize },
+    /// Where the branch with the bad commit forked from the default branch
+    MergeBase { commit: String, branch: String },
+    /// Latest tag before the bad commit
+    Tag(String),
+}
+
+impl GoodCommit {
+    /// Revision to hand to `git bisect start`
+    pub fn rev(&self) -> &str {
+        match self {
+            GoodCommit::TestPassed { commit, .. } | GoodCommit::MergeBase { commit, .. } => commit,
+            GoodCommit::Tag(tag) => tag,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            GoodCommit::TestPassed { commit, distance } => {
+                format!(
+                    "{} (test passes, {distance} commit(s) before bad)",
+                    short_sha(commit)
+                )
+            }
+            GoodCommit::MergeBase { commit, branch } => {
+                format!(
+                    "{} (where the branch forked from {branch})",
+                    short_sha(commit)
+                )
+            }
+            GoodCommit::Tag(tag) => format!("{tag} (latest tag before bad)"),
+        }
+    }
+}
+
+/// Detached worktree the bisect test runs in, removed when dropped
+struct TestWorktree {
+    path: PathBuf,
+}
+
+impl TestWorktree {
+    fn add(commit: &str) -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("git-x-bisect-{}", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+        GitOperations::run(&["worktree", "add", "--detach", "--quiet", &path_str, commit])?;
+        Ok(Self { path })
+    }
+
+    /// Check out `commit` and report whether `test` exits successfully there
+    fn passes(&self, commit: &str, test: &str) -> Result<bool> {
+        let path = self.path.to_string_lossy();
+        GitOperations::run(&["-C", &path, "checkout", "--quiet", "--detach", commit])?;
+        let status = std::process::Command::new("sh")
+            .args(["-c", test])
+            .current_dir(&self.path)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()?;
+        Ok(status.success())
+    }
+}
+
+impl Drop for TestWorktree {
+    fn drop(&mut self) {
+        let _ = GitOperations::run(&[
+            "worktree",
+            "remove",
+            "--force",
+            &self.path.to_string_lossy(),
+        ]);
+    }
+}
+
+/// Command for Git bisect workflow
+pub struct BisectCommand {
+    action: BisectAction,
+}
+
+impl BisectCommand {
+    pub fn new(action: BisectAction) -> Self {
+        Self { action }
+    }
+
+    /// First-parent distances tried when searching for a passing commit: 1, 2, 4, ...
+    ///
+    /// Capped at the root commit, so short histories end early.
+    pub fn probe_distances(history_len: usize, max_tests: usize) -> Vec<usize> {
+        let mut distances = Vec::new();
+        let mut distance = 1;
+        while distances.len() < max_tests && history_len > 1 {
+            let capped = distance.min(history_len - 1);
+            if distances.last() == Some(&capped) {
+                break;
+            }
+            distances.push(capped);
+            distance *= 2;
+        }
+        distances
+    }
+
+    /// Pick a good commit for `bad` without being told one
+    ///
+    /// With a test command, walks back along the first parents of `bad` in growing
+    /// steps until the test passes, running it at most `max_tests` times (the first
+    /// run checks that it fails on `bad`). Without one, uses the merge-base with the
+    /// default branch when `bad` is on another branch, else the latest tag.
+    pub fn find_good(
+        bad: &str,
+        test: Option<&str>,
+        max_tests: usize,
+        output: &mut BufferedOutput,
+    ) -> Result<GoodCommit> {
+        let bad_commit =
+            GitOperations::run(&["rev-parse", "--verify", &format!("{bad}^{{commit}}")])
+                .map_err(|_| GitXError::GitCommand(format!("Reference '{bad}' does not exist")))?;
+        let bad_commit = bad_commit.trim();
+
+        if let Some(test) = test {
+            return Self::find_passing(bad, bad_commit, test, max_tests, output);
+        }
+
+        if let Some(branch) = GitOperations::default_branch()
+            && let Ok(base) = GitOperations::run(&["merge-base", bad_commit, &branch])
+            && base.trim() != bad_commit
+        {
+            return Ok(GoodCommit::MergeBase {
+                commit: base.trim().to_string(),
+                branch,
+            });
+        }
+
+        GitOperations::run(&["describe", "--tags", "--abbrev=0", &format!("{bad_commit}^")])
+            .map(|tag| GoodCommit::Tag(tag.trim().to_string()))
+            .map_err(|_| {
+                GitXError::GitCommand(
+                    "Could not find a good commit: no merge-base with the default branch and no earlier tag. Pass one explicitly or use --test".to_string(),
+                )
+            })
+    }
+
+    fn find_passing(
+        bad: &str,
+        bad_commit: &str,
+        test: &str,
+        max_tests: usize,
+        output: &mut BufferedOutput,
+    ) -> Result<GoodCommit> {
+        let limit = 1usize << max_tests.min(20);
+        let history = GitOperations::run(&[
+            "rev-list",
+            "--first-parent",
+            &format!("--max-count={}", limit + 1),
+            bad_commit,
+        ])?;
+        let history: Vec<&str> = history.lines().collect();
+
+        output.add_line(format!(
+            "🧪 Looking for the last commit where '{test}' passes"
+        ));
+        let worktree = TestWorktree::add(bad_commit)?;
+        if worktree.passes(bad_commit, test)? {
+            return Err(GitXError::GitCommand(format!(
+                "'{test}' passes on {bad}, so there is nothing to bisect"
+            )));
+        }
+        output.add_line(format!("   ❌ {} ({bad}) fails", short_sha(bad_commit)));
+
+        for distance in Self::probe_distances(history.len(), max_tests.saturating_sub(1)) {
+            let commit = history[distance];
+            if worktree.passes(commit, test)? {
+                output.add_line(format!(
+                    "   ✅ {} ({bad}~{distance}) passes",
+                    short_sha(commit)
+                ));
+                return Ok(GoodCommit::TestPassed {
+                    commit: commit.to_string(),
+                    distance,
+                });
+            }
+            output.add_line(format!(
+                "   ❌ {} ({bad}~{distance}) fails",
+                short_sha(commit)
+            ));
+        }
+
+        Err(GitXError::GitCommand(format!(
+            "'{test}' failed on every commit tried; pass a good commit explicitly or raise --max-tests"
+        )))
+    }
+
+    fn start(bad: &str, good: &str) -> Result<String> {
+        // Allow any Git reference (commit hash, branch, tag, etc.)
+        // Don't validate as strict hex - Git will handle this
+
+        // Try to resolve references to verify they exist
+        if GitOperations::run(&["rev-parse", "--verify", bad]).is_err() {
+            return Err(GitXError::GitCommand(format!(
+                "Reference '{bad}' does not exist"
+            )));
+        }
+        if GitOperations::run(&["rev-parse", "--verify", good]).is_err() {
+            return Err(GitXError::GitCommand(format!(
+                "Reference '{good}' does not exist"
+            )));
+        }
+
+        // Start bisect and capture git output for proper feedback
+        let output = GitOperations::run(&["bisect", "start", bad, good])?;
+
+        let mut result = format!("🔍 Starting bisect between {bad} (bad) and {good} (good)");
+        if !output.trim().is_empty() {
+            result = format!("{}\n{}", output.trim(), result);
+        }
+        result.push_str("\n✅ Checked out commit");
+
+        Ok(result)
+    }
+
+    fn is_bisecting() -> Result<bool> {
+        // Check if .git/BISECT_HEAD exists
+        match GitOperations::repo_root() {
+            Ok(root) => {
+                let bisect_head = std::path::Path::new(&root).join(".git").join("BISECT_HEAD");
+                Ok(bisect_head.exists())
+            }
+            Err(_) => Ok(false),
+        }
+    }
+
+    fn execute_bisect_action(&self) -> Result<String> {
+        match &self.action {
+            BisectAction::Start { bad, good } => Self::start(bad, good),
+            BisectAction::AutoStart {
+                bad,
+                test,
+                max_tests,
+            } => {
+                if Self::is_bisecting()? {
+                    return Err(GitXError::GitCommand(
+                        "Already bisecting; run 'git x bisect reset' first".to_string(),
+                    ));
+                }
+                let mut output = BufferedOutput::new();
+                let good = Self::find_good(bad, test.as_deref(), *max_tests, &mut output)?;
+                output.add_line(format!("🟢 Good commit: {}", good.describe()));
+                output.add_line(Self::start(bad, good.rev())?);
+                if let Some(test) = test {
+                    output.add_line(format!(
+                        "💡 Let git finish it: git bisect run sh -c '{test}'"
+                    ));
+                }
+                Ok(output.content())
+            }
+            BisectAction::Good => {
+                if !Self::is_bisecting()? {
+                    return Err(GitXError::GitCommand(
+                        "Not currently in bisect mode".to_string(),
+                    ));
+                }
+                GitOperations::run_status(&["bisect", "good"])?;
+                Ok("✅ Marked current commit as good".to_string())
+            }
+            BisectAction::Bad => {
+                if !Self::is_bisecting()? {
+                    return Err(GitXError::GitCommand(
+                        "Not currently in bisect mode".to_string(),
+                    ));
+                }
+                GitOperations::run_status(&["bisect", "bad"])?;
+                Ok("❌ Marked current commit as bad".to_string())
+            }
+            BisectAction::Skip => {
+                if !Self::is_bisecting()? {
+                    return Err(GitXError::GitCommand(
+                        "Not currently in bisect mode".to_string(),
+                    ));
+                }
+                GitOperations::run_status(&["bisect", "skip"])?;
+                Ok("⏭️ Skipped current commit".to_string())
+            }
+            BisectAction::Reset => {
+                if !Self::is_bisecting()? {
+                    return Ok("Not currently in bisect mode".to_string());
+                }
+                GitOperations::run_status(&["bisect", "reset"])?;
+                Ok("🔄 Reset bisect and returned to original branch".to_string())
+            }
+            BisectAction::Status => {
+                if !Self::is_bisecting()? {
+                    return Ok("Not currently in bisect mode".to_string());
+                }
+
+                let log = GitOperations::run(&["bisect", "log"])
+                    .unwrap_or_else(|_| "No bisect log available".to_string());
+                Ok(format!("🔍 Bisect status:\n{log}"))
+            }
+        }
+    }
+}
+
+impl Command for BisectCommand {
+    fn execute(&self) -> Result<String> {
+        self.execute_bisect_action()
+    }
+
+    fn name(&self) -> &'static str {
+        "bisect"
+    }
+
+    fn description(&self) -> &'static str {
+        "Simplified Git bisect workflow for finding bugs"
+    }
+}
+
+impl GitCommand for BisectCommand {}
+
+impl Destructive for BisectCommand {
+    fn destruction_description(&self) -> String {
+        match &self.action {
+            BisectAction::Start { .. } | BisectAction::AutoStart { .. } => {
+                "This will start a bisect session and change your working directory".to_string()
+            }
+            BisectAction::Reset => {
+                "This will reset the bisect session and return to your original branch".to_string()
+            }
+            _ => "This will change your working directory to a different commit".to_string(),
+        }
+    }
+}
+
+/// Temporary ref the source history is fetched into
+const IMPORT_REF: &str = "refs/git-x/import";
+
+/// A commit of the imported history, with its parents as rewritten by path limiting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceCommit {
+    pub sha: String,
+    pub parents: Vec<String>,
+    pub author_name: String,
+    pub author_email: String,
+    /// Author date in git's raw `<unix> <offset>` form
+    pub author_date: String,
+    pub message: String,
+}
+
+impl SourceCommit {
+    /// Fields of `git log` output that [`SourceCommit::parse_log`] reads
+    pub const FORMAT: &'static str = "--format=%H%x00%P%x00%an%x00%ae%x00%ad%x00%B%x1e";
+
+    /// Parse `git log --date=raw` output in [`SourceCommit::FORMAT`]
+    pub fn parse_log(output: &str) -> Vec<Self> {
+        output
+            .split('\x1e')
+            .filter_map(|record| {
+                let mut fields = record.trim_start_matches('\n').splitn(6, '\0');
+                let sha = fields.next()?.to_string();
+                if sha.is_empty() {
+                    return None;
+                }
+                Some(Self {
+                    sha,
+                    parents: fields
+                        .next()?
+                        .split_whitespace()
+                        .map(String::from)
+                        .collect(),
+                    author_name: fields.next()?.to_string(),
+                    author_email: fields.next()?.to_string(),
+                    author_date: fields.next()?.to_string(),
+                    message: fields.next()?.trim_end().to_string(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn subject(&self) -> &str {
+        self.message.lines().next().unwrap_or("")
+    }
+}
+
+/// Command that transplants the history of a directory in another local repository
+/// into a directory of this one
+pub struct ImportCommitsCommand {
+    source: PathBuf,
+    path: Option<String>,
+    into: Option<String>,
+    rev: String,
+    dry_run: bool,
+}
+
+impl ImportCommitsCommand {
+    pub fn new(source: PathBuf) -> Self {
+        Self {
+            source,
+            path: None,
+            into: None,
+            rev: "HEAD".to_string(),
+            dry_run: false,
+        }
+    }
+
+    /// Import only this directory of the source repository
+    pub fn with_path(mut self, path: Option<String>) -> Self {
+        self.path = path
+            .map(|path| Self::clean_path(&path))
+            .filter(|p| !p.is_empty());
+        self
+    }
+
+    /// Directory of this repository the history lands in
+    pub fn with_into(mut self, into: Option<String>) -> Self {
+        self.into = into.map(|into| Self::clean_path(&into));
+        self
+    }
+
+    /// Branch or tag of the source repository to import
+    pub fn with_rev(mut self, rev: String) -> Self {
+        self.rev = rev;
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    fn clean_path(path: &str) -> String {
+        path.trim()
+            .trim_start_matches("./")
+            .trim_matches('/')
+            .to_string()
+    }
+
+    /// Where the history lands: `--into`, else the imported directory, else the
+    /// source repository's name
+    pub fn destination(&self) -> Result<String> {
+        let into = match (&self.into, &self.path) {
+            (Some(into), _) => into.clone(),
+            (None, Some(path)) => path.clone(),
+            (None, None) => std::fs::canonicalize(&self.source)?
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+        if into.is_empty() || into.split('/').any(|part| part.is_empty() || part == "..") {
+            return Err(GitXError::Parse(format!(
+                "Invalid destination '{into}': give a directory inside the repository with --into"
+            )));
+        }
+        Ok(into)
+    }
+
+    /// Fetch the source history into [`IMPORT_REF`], returning the commit it points at
+    fn fetch(&self) -> Result<String> {
+        let source = self.source.to_string_lossy();
+        GitOperations::run(&["-C", source.as_ref(), "rev-parse", "--git-dir"])
+            .map_err(|_| GitXError::GitCommand(format!("{source} is not a git repository")))?;
+        GitOperations::run(&[
+            "fetch",
+            "--quiet",
+            "--no-tags",
+            source.as_ref(),
+            &format!("+{}:{IMPORT_REF}", self.rev),
+        ])
+        .map_err(|_| {
+            GitXError::GitCommand(format!(
+                "Cannot fetch '{}' from {source}: give a branch or tag with --from",
+                self.rev
+            ))
+        })?;
+        GitOperations::run(&["rev-parse", IMPORT_REF])
+    }
+
+    /// Commits that touch the imported directory, parents first
+    fn commits(&self) -> Result<Vec<SourceCommit>> {
+        let mut args = vec![
+            "log",
+            "--topo-order",
+            "--reverse",
+            "--parents",
+            "--date=raw",
+            SourceCommit::FORMAT,
+            IMPORT_REF,
+        ];
+        if let Some(path) = &self.path {
+            args.extend(["--", path.as_str()]);
+        }
+        Ok(SourceCommit::parse_log(&GitOperations::run(&args)?))
+    }
+
+    /// The imported directory's tree in `commit`, if it has one
+    fn subtree(&self, commit: &str) -> Option<String> {
+        let spec = match &self.path {
+            Some(path) => format!("{commit}:{path}"),
+            None => format!("{commit}^{{tree}}"),
+        };
+        GitOperations::run(&["rev-parse", "--verify", "--quiet", &spec]).ok()
+    }
+
+    fn preview(&self, commits: &[SourceCommit], into: &str) -> String {
+        let mut output = BufferedOutput::new();
+        let merges = commits.iter().filter(|c| c.parents.len() > 1).count();
+        output.add_line(format!(
+            "🔍 Would create {} commit(s){} in {into}/ from {}{}:",
+            commits.len(),
+            if merges > 0 {
+                format!(" ({merges} merge(s))")
+            } else {
+                String::new()
+            },
+            self.source.display(),
+            self.path
+                .as_ref()
+                .map(|path| format!(" ({path}/)"))
+                .unwrap_or_default()
+        ));
+        for commit in commits {
+            let date = commit
+                .author_date
+                .split_whitespace()
+                .next()
+                .and_then(|secs| secs.parse().ok())
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            output.add_line(format!(
+                "   {} {date} {} — {}",
+                short_sha(&commit.sha),
+                commit.author_name,
+                commit.subject()
+            ));
+        }
+        output.add_line("💡 Run without --dry-run to import".to_string());
+        output.content()
+    }
+
+    /// Recreate `commits` on top of HEAD with each imported tree under `into`
+    fn rewrite(&self, commits: &[SourceCommit], into: &str, head: &str) -> Result<String> {
+        let graft = TreeGraft::new(head, into)?;
+        let name = std::fs::canonicalize(&self.source)
+            .ok()
+            .and_then(|path| path.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| self.source.display().to_string());
+
+        let mut rewritten: std::collections::HashMap<&str, String> =
+            std::collections::HashMap::new();
+        let mut tip = head.to_string();
+        for commit in commits {
+            let tree = graft.tree(self.subtree(&commit.sha).as_deref())?;
+            let mut parents: Vec<&str> = commit
+                .parents
+                .iter()
+                .filter_map(|parent| rewritten.get(parent.as_str()).map(String::as_str))
+                .collect();
+            if parents.is_empty() {
+                parents.push(head);
+            }
+            let message = format!(
+                "{}\n\nImported-from: {name} {}",
+                commit.message,
+                short_sha(&commit.sha)
+            );
+
+            let mut args = vec!["commit-tree", tree.as_str()];
+            for parent in &parents {
+                args.extend(["-p", parent]);
+            }
+            args.extend(["-m", message.as_str()]);
+            tip = GitOperations::run_with_env(
+                &args,
+                &[
+                    ("GIT_AUTHOR_NAME", &commit.author_name),
+                    ("GIT_AUTHOR_EMAIL", &commit.author_email),
+                    ("GIT_AUTHOR_DATE", &commit.author_date),
+                ],
+            )?;
+            rewritten.insert(&commit.sha, tip.clone());
+        }
+        Ok(tip)
+    }
+
+    fn import(&self, into: &str) -> Result<String> {
+        let head =
+            GitOperations::run(&["rev-parse", "--verify", "--quiet", "HEAD"]).map_err(|_| {
+                GitXError::GitCommand(
+                    "The current branch has no commits yet; commit something first".to_string(),
+                )
+            })?;
+        if GitOperations::run(&["cat-file", "-e", &format!("HEAD:{into}")]).is_ok() {
+            return Err(GitXError::GitCommand(format!(
+                "'{into}' already exists; pick another directory with --into"
+            )));
+        }
+
+        self.fetch()?;
+        if let Some(path) = &self.path {
+            let kind = GitOperations::run(&["cat-file", "-t", &format!("{IMPORT_REF}:{path}")]);
+            if kind.ok().as_deref() != Some("tree") {
+                return Err(GitXError::GitCommand(format!(
+                    "'{path}' is not a directory in {} at {}",
+                    self.source.display(),
+                    self.rev
+                )));
+            }
+        }
+        let commits = self.commits()?;
+        if commits.is_empty() {
+            return Err(GitXError::GitCommand("No commits to import".to_string()));
+        }
+        if self.dry_run {
+            return Ok(self.preview(&commits, into));
+        }
+
+        let tip = self.rewrite(&commits, into, &head)?;
+        crate::core::safety::Transaction::begin("import-commits")?
+            .run(|| GitOperations::run(&["merge", "--ff-only", "--quiet", &tip]))?;
+
+        Ok(format!(
+            "✅ Imported {} commit(s) into {into}/, keeping their authors and dates\n💡 To undo, run: git reset --keep {}",
+            commits.len(),
+            short_sha(&head)
+        ))
+    }
+}
+
+impl Command for ImportCommitsCommand {
+    fn execute(&self) -> Result<String> {
+        let into = self.destination()?;
+        let result = self.import(&into);
+        let _ = GitOperations::run(&["update-ref", "-d", IMPORT_REF]);
+        result
+    }
+
+    fn name(&self) -> &'static str {
+        "import-commits"
+    }
+
+    fn description(&self) -> &'static str {
+        "Import the history of a directory of another local repository"
+    }
+}
+
+impl GitCommand for ImportCommitsCommand {}
+
+/// Builds trees equal to a base tree with one directory swapped in
+///
+/// The listings along the path are read once, so each imported commit costs one
+/// `git mktree` per path component.
+struct TreeGraft {
+    base: String,
+    /// Entries of each directory along the path, minus the next component, and its name
+    levels: Vec<(Vec<String>, String)>,
+}
+
+impl TreeGraft {
+    fn new(base: &str, path: &str) -> Result<Self> {
+        let mut levels = Vec::new();
+        let mut dir = String::new();
+        for component in path.split('/') {
+            let tree = if dir.is_empty() {
+                format!("{base}^{{tree}}")
+            } else {
+                format!("{base}:{dir}")
+            };
+            let entries = GitOperations::run(&["ls-tree", "-z", &tree])
+                .map(|listing| {
+                    listing
+                        .split('\0')
+                        .filter(|entry| {
+                            !entry.is_empty()
+                                && entry.split_once('\t').map(|(_, name)| name) != Some(component)
+                        })
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default();
+            levels.push((entries, component.to_string()));
+            dir = if dir.is_empty() {
+                component.to_string()
+            } else {
+                format!("{dir}/{component}")
+            };
+        }
+        Ok(Self {
+            base: base.to_string(),
+            levels,
+        })
+    }
+
+    /// The base tree with `subtree` at the path, or the base tree itself when `None`
+    fn tree(&self, subtree: Option<&str>) -> Result<String> {
+        let Some(subtree) = subtree else {
+            return GitOperations::run(&["rev-parse", &format!("{}^{{tree}}", self.base)]);
+        };
+        let mut tree = subtree.to_string();
+        for (entries, name) in self.levels.iter().rev() {
+            let mut entries = entries.clone();
+            entries.push(format!("040000 tree {tree}\t{name}"));
+            tree = Self::mktree(&entries)?;
+        }
+        Ok(tree)
+    }
+
+    fn mktree(entries: &[String]) -> Result<String> {
+        let input = entries
+            .iter()
+            .map(|entry| format!("{entry}\0"))
+            .collect::<String>();
+        GitOperations::run_with_input(&["mktree", "-z"], &input)
+    }
+}
+
+/// Namespace recording reverted merges: `<REVERTED_PREFIX><branch>` points at the revert
+const REVERTED_PREFIX: &str = "refs/git-x/reverted/";
+
+/// Command that reverts a merge against the right parent and records it for `re-merge`
+pub struct RevertMergeCommand {
+    commit: String,
+    mainline: Option<usize>,
+}
+
+impl RevertMergeCommand {
+    pub fn new(commit: String) -> Self {
+        Self {
+            commit,
+            mainline: None,
+        }
+    }
+
+    /// Parent to keep, counting from 1, instead of the one on the first-parent history
+    pub fn with_mainline(mut self, mainline: Option<usize>) -> Self {
+        self.mainline = mainline;
+        self
+    }
+
+    /// Branch named in a merge subject git or a forge wrote, such as
+    /// `Merge branch 'feature' into main` or `Merge pull request #12 from owner/feature`
+    pub fn merged_branch(subject: &str) -> Option<String> {
+        let quoted = |rest: &str| rest.split('\'').nth(1).map(String::from);
+        if let Some(rest) = subject.strip_prefix("Merge branch ") {
+            return quoted(rest);
+        }
+        if let Some(rest) = subject.strip_prefix("Merge remote-tracking branch ") {
+            return quoted(rest);
+        }
+        let rest = subject.strip_prefix("Merge pull request #")?;
+        let (_, source) = rest.split_once(" from ")?;
+        let (_, branch) = source.split_whitespace().next()?.split_once('/')?;
+        Some(branch.to_string())
+    }
+
+    /// The parent to keep: the one on the current branch's first-parent history
+    fn mainline(merge: &str, parents: &[String]) -> Result<usize> {
+        let first_parents = GitOperations::run(&["rev-list", "--first-parent", "HEAD"])?;
+        if first_parents.lines().any(|sha| sha == merge) {
+            return Ok(1);
+        }
+        Err(GitXError::GitCommand(format!(
+            "{} was merged into another branch before reaching this one; pick the parent to keep with --mainline (1-{})",
+            short_sha(merge),
+            parents.len()
+        )))
+    }
+
+    /// Name the revert is recorded under: the merged branch, else the merged commit
+    fn branch(merge: &str, merged: &str) -> Result<String> {
+        let subject = GitOperations::run(&["log", "-1", "--format=%s", merge])?;
+        if let Some(branch) = Self::merged_branch(&subject)
+            && GitOperations::run(&["check-ref-format", &format!("{REVERTED_PREFIX}{branch}")])
+                .is_ok()
+        {
+            return Ok(branch);
+        }
+        Ok(GitOperations::run(&[
+            "for-each-ref",
+            "--format=%(refname:short)",
+            "--points-at",
+            merged,
+            "refs/heads",
+        ])?
+        .lines()
+        .next()
+        .map(String::from)
+        .unwrap_or_else(|| short_sha(merged).to_string()))
+    }
+}
+
+impl Command for RevertMergeCommand {
+    fn execute(&self) -> Result<String> {
+        let merge = GitOperations::run(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", self.commit),
+        ])
+        .map_err(|_| GitXError::GitCommand(format!("'{}' is not a commit", self.commit)))?;
+        let parents: Vec<String> =
+            GitOperations::run(&["rev-list", "--parents", "-n", "1", &merge])?
+                .split_whitespace()
+                .skip(1)
+                .map(String::from)
+                .collect();
+        if parents.len() < 2 {
+            return Err(GitXError::GitCommand(format!(
+                "{} is not a merge commit; use 'git revert {}'",
+                short_sha(&merge),
+                short_sha(&merge)
+            )));
+        }
+        if GitOperations::run(&["merge-base", "--is-ancestor", &merge, "HEAD"]).is_err() {
+            return Err(GitXError::GitCommand(format!(
+                "{} is not on the current branch",
+                short_sha(&merge)
+            )));
+        }
+        let mainline = match self.mainline {
+            Some(n) if (1..=parents.len()).contains(&n) => n,
+            Some(n) => {
+                return Err(GitXError::GitCommand(format!(
+                    "{} has {} parents; --mainline {n} is out of range",
+                    short_sha(&merge),
+                    parents.len()
+                )));
+            }
+            None => Self::mainline(&merge, &parents)?,
+        };
+        let kept = &parents[mainline - 1];
+        let merged = parents
+            .iter()
+            .enumerate()
+            .find(|(i, _)| *i != mainline - 1)
+            .map(|(_, parent)| parent.as_str())
+            .unwrap_or_default();
+
+        let branch = Self::branch(&merge, merged)?;
+        let record = format!("{REVERTED_PREFIX}{branch}");
+        if let Ok(revert) = GitOperations::run(&["rev-parse", "--verify", "--quiet", &record]) {
+            return Err(GitXError::GitCommand(format!(
+                "The merge of '{branch}' was already reverted by {}; run 'git x re-merge {branch}' first",
+                short_sha(&revert)
+            )));
+        }
+        if !GitOperations::run(&["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+            return Err(GitXError::GitCommand(
+                "Working directory has uncommitted changes. Please commit or stash them first."
+                    .to_string(),
+            ));
+        }
+
+        let head = GitOperations::run(&["rev-parse", "HEAD"])?;
+        let mainline_arg = mainline.to_string();
+        let revert = crate::core::safety::Transaction::begin("revert-merge")?.run(|| {
+            GitOperations::run(&["revert", "--no-edit", "-m", &mainline_arg, &merge])?;
+            let revert = GitOperations::run(&["rev-parse", "HEAD"])?;
+            GitOperations::run(&[
+                "update-ref",
+                "-m",
+                &format!("revert-merge {merge}"),
+                &record,
+                &revert,
+            ])?;
+            Ok(revert)
+        })?;
+
+        let count = GitOperations::run(&["rev-list", "--count", &format!("{kept}..{merged}")])?;
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "✅ Reverted merge {} of '{branch}' as {}, keeping parent {mainline} ({})",
+            short_sha(&merge),
+            short_sha(&revert),
+            short_sha(kept)
+        ));
+        output.add_line(format!(
+            "⚠️  Git still counts the {count} commit(s) from '{branch}' as merged: merging it again only brings commits made after {}",
+            short_sha(merged)
+        ));
+        output.add_line(format!(
+            "💡 When it's ready to come back, run: git x re-merge {branch}"
+        ));
+        output.add_line(format!(
+            "💡 To undo before pushing, run: git reset --keep {}",
+            short_sha(&head)
+        ));
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "revert-merge"
+    }
+
+    fn description(&self) -> &'static str {
+        "Revert a merge commit, recording it so the branch can be merged again"
+    }
+}
+
+impl GitCommand for RevertMergeCommand {}
+
+/// Command that brings back a branch whose merge `revert-merge` reverted
+pub struct ReMergeCommand {
+    branch: Option<String>,
+}
+
+impl ReMergeCommand {
+    /// Without a branch, lists the recorded reverts
+    pub fn new(branch: Option<String>) -> Self {
+        Self { branch }
+    }
+
+    /// Recorded reverts as (branch, revert commit)
+    pub fn recorded() -> Result<Vec<(String, String)>> {
+        Ok(GitOperations::run(&[
+            "for-each-ref",
+            "--format=%(refname) %(objectname)",
+            REVERTED_PREFIX,
+        ])?
+        .lines()
+        .filter_map(|line| {
+            let (name, sha) = line.split_once(' ')?;
+            Some((
+                name.strip_prefix(REVERTED_PREFIX)?.to_string(),
+                sha.to_string(),
+            ))
+        })
+        .collect())
+    }
+
+    fn list() -> Result<String> {
+        let recorded = Self::recorded()?;
+        if recorded.is_empty() {
+            return Ok("No reverted merges recorded".to_string());
+        }
+        let mut output = BufferedOutput::new();
+        output.add_line("↩️  Reverted merges:".to_string());
+        for (branch, revert) in recorded {
+            let subject = GitOperations::run(&["log", "-1", "--format=%s", &revert])?;
+            output.add_line(format!("  {branch:<24} {} {subject}", short_sha(&revert)));
+        }
+        output.add_line("💡 Bring one back with: git x re-merge <branch>".to_string());
+        Ok(output.content())
+    }
+
+    fn re_merge(branch: &str) -> Result<String> {
+        let record = format!("{REVERTED_PREFIX}{branch}");
+        let Ok(revert) = GitOperations::run(&["rev-parse", "--verify", "--quiet", &record]) else {
+            let known: Vec<String> = Self::recorded()?.into_iter().map(|(b, _)| b).collect();
+            let hint = if known.is_empty() {
+                String::new()
+            } else {
+                format!(" (recorded: {})", known.join(", "))
+            };
+            return Err(GitXError::GitCommand(format!(
+                "No reverted merge recorded for '{branch}'{hint}"
+            )));
+        };
+        if GitOperations::run(&["merge-base", "--is-ancestor", &revert, "HEAD"]).is_err() {
+            return Err(GitXError::GitCommand(format!(
+                "The revert {} is not on the current branch; switch to the branch it was made on",
+                short_sha(&revert)
+            )));
+        }
+        if !GitOperations::run(&["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+            return Err(GitXError::GitCommand(
+                "Working directory has uncommitted changes. Please commit or stash them first."
+                    .to_string(),
+            ));
+        }
+
+        let head = GitOperations::run(&["rev-parse", "HEAD"])?;
+        let tip = GitOperations::run(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{branch}^{{commit}}"),
+        ])
+        .ok();
+        let mut output = BufferedOutput::new();
+        crate::core::safety::Transaction::begin("re-merge")?.run(|| {
+            GitOperations::run(&["revert", "--no-edit", &revert])?;
+            output.add_line(format!(
+                "✅ Reverted the revert {}: the changes from '{branch}' are back",
+                short_sha(&revert)
+            ));
+
+            // Commits made on the branch since the first merge come in the usual way
+            if let Some(tip) = &tip {
+                let newer = GitOperations::run(&["rev-list", "--count", &format!("HEAD..{tip}")])?;
+                if newer != "0" {
+                    GitOperations::run(&["merge", "--no-edit", "--quiet", branch])?;
+                    output.add_line(format!("🔀 Merged {newer} newer commit(s) from '{branch}'"));
+                }
+            }
+            GitOperations::run(&["update-ref", "-d", &record])
+        })?;
+        output.add_line(format!(
+            "💡 To undo before pushing, run: git reset --keep {}",
+            short_sha(&head)
+        ));
+        Ok(output.content())
+    }
+}
+
+impl Command for ReMergeCommand {
+    fn execute(&self) -> Result<String> {
+        match &self.branch {
+            Some(branch) => Self::re_merge(branch),
+            None => Self::list(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "re-merge"
+    }
+
+    fn description(&self) -> &'static str {
+        "Bring back a branch whose merge was reverted with revert-merge"
+    }
+}
+
+impl GitCommand for ReMergeCommand {}
@@ -1,9 +1,32 @@
+use crate::core::git::GitOperations;
 use crate::core::traits::Command;
 use crate::{GitXError, Result};
 use clap_complete::Shell;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Bash helper offering `git-x __complete` values for the word being completed
+const BASH_DYNAMIC: &str = r#"_git_x_dynamic() {
+    local IFS=$'\n'
+    local values=($(git-x __complete -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null))
+    [[ ${#values[@]} -gt 0 ]] || return 1
+    COMPREPLY=("${values[@]}")
+}
+"#;
+
+/// Zsh helper offering `git-x __complete` values for the word being completed
+const ZSH_DYNAMIC: &str = r#"_git_x_dynamic() {
+    local -a values
+    values=("${(@f)$(git-x __complete -- "${(@)words[2,CURRENT]}" 2>/dev/null)}")
+    [[ -n ${values[1]} ]] || return 1
+    compadd -a values
+}
+"#;
+
+/// Fish rule adding `git-x __complete` values to the static completions
+const FISH_DYNAMIC: &str =
+    "complete -c git-x -a '(git-x __complete -- (commandline -opc)[2..-1] (commandline -ct))'\n";
+
 pub struct CompletionInstallCommand {
     shell: Shell,
 }
@@ -119,7 +142,9 @@ impl CompletionInstallCommand {
         use clap_complete::generate;
         use std::io::Cursor;
 
-        let mut cmd = Cli::command();
+        // The bash generator joins subcommand paths with `__`, which `__complete` breaks;
+        // the command is hidden, so its name never shows up in the script
+        let mut cmd = Cli::command().mut_subcommand("__complete", |c| c.name("complete"));
         let mut buf = Cursor::new(Vec::new());
 
         match self.shell {
@@ -140,8 +165,24 @@ impl CompletionInstallCommand {
             }
         }
 
-        String::from_utf8(buf.into_inner())
-            .map_err(|e| GitXError::Other(format!("Failed to generate completion script: {e}")))
+        let script = String::from_utf8(buf.into_inner())
+            .map_err(|e| GitXError::Other(format!("Failed to generate completion script: {e}")))?;
+        Ok(Self::with_dynamic_values(self.shell, &script))
+    }
+
+    /// Make the generated script ask `git-x __complete` for branches, remotes and stashes
+    /// first, falling back to the static completions when it has nothing to offer
+    pub fn with_dynamic_values(shell: Shell, script: &str) -> String {
+        let (helper, hook) = match shell {
+            Shell::Bash => (BASH_DYNAMIC, "    _git_x_dynamic && return 0\n"),
+            Shell::Zsh => (ZSH_DYNAMIC, "    _git_x_dynamic && return 0\n"),
+            Shell::Fish => return format!("{script}\n{FISH_DYNAMIC}"),
+            _ => return script.to_string(),
+        };
+        match script.split_once("_git-x() {\n") {
+            Some((before, after)) => format!("{before}{helper}\n_git-x() {{\n{hook}{after}"),
+            None => script.to_string(),
+        }
     }
 
     fn get_shell_setup_instructions(&self, completion_path: &Path) -> String {
@@ -216,3 +257,235 @@ impl Command for CompletionInstallCommand {
         "Install shell completion to standard location"
     }
 }
+
+/// Values that change with the repository, so shells have to ask for them at completion time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicValues {
+    /// Local and remote-tracking branches
+    Branches,
+    LocalBranches,
+    RemoteBranches,
+    Remotes,
+    /// Branches that have stashes
+    StashBranches,
+    Stashes,
+}
+
+/// Arguments completed with [`DynamicValues`], by subcommand path and argument id
+const DYNAMIC_ARGS: &[(&str, &str, DynamicValues)] = &[
+    ("archive", "branches", DynamicValues::LocalBranches),
+    ("branch-diff", "target", DynamicValues::Branches),
+    ("diff-stat", "from", DynamicValues::Branches),
+    ("diff-stat", "to", DynamicValues::Branches),
+    ("doctor connection", "remote", DynamicValues::Remotes),
+    ("find-merge", "into", DynamicValues::Branches),
+    ("heatmap", "branch", DynamicValues::Branches),
+    ("hooks switch", "branch", DynamicValues::LocalBranches),
+    ("hotfix", "from", DynamicValues::Branches),
+    ("jump", "branch", DynamicValues::Branches),
+    ("merge-queue", "base", DynamicValues::Branches),
+    ("merge-queue", "branches", DynamicValues::LocalBranches),
+    ("mirror push", "remote", DynamicValues::Remotes),
+    ("mirror schedule", "remote", DynamicValues::Remotes),
+    ("mirror verify", "remote", DynamicValues::Remotes),
+    ("move", "branch", DynamicValues::LocalBranches),
+    ("move", "from", DynamicValues::Branches),
+    ("move", "onto", DynamicValues::Branches),
+    ("new", "from", DynamicValues::Branches),
+    ("pr checkout", "remote", DynamicValues::Remotes),
+    ("protect", "branches", DynamicValues::LocalBranches),
+    ("review", "base", DynamicValues::Branches),
+    ("review", "target", DynamicValues::Branches),
+    (
+        "stash-branch apply-by-branch",
+        "branch_name",
+        DynamicValues::StashBranches,
+    ),
+    ("stash-branch create", "stash_ref", DynamicValues::Stashes),
+    ("stash-branch export", "stash_ref", DynamicValues::Stashes),
+    ("upstream set", "upstream", DynamicValues::RemoteBranches),
+    ("what", "target", DynamicValues::Branches),
+];
+
+impl DynamicValues {
+    /// Values for `arg` of the subcommand at `path`, e.g. `("stash-branch apply-by-branch", "branch_name")`
+    pub fn for_arg(path: &str, arg: &str) -> Option<Self> {
+        DYNAMIC_ARGS
+            .iter()
+            .find(|(command, id, _)| *command == path && *id == arg)
+            .map(|(_, _, values)| *values)
+    }
+
+    /// Ask git for the current values
+    pub fn list(self) -> Result<Vec<String>> {
+        let mut values = match self {
+            DynamicValues::Branches => {
+                let mut branches = Self::refs("refs/heads")?;
+                branches.extend(Self::remote_branches()?);
+                branches
+            }
+            DynamicValues::LocalBranches => Self::refs("refs/heads")?,
+            DynamicValues::RemoteBranches => Self::remote_branches()?,
+            DynamicValues::Remotes => GitOperations::run(&["remote"])?
+                .lines()
+                .map(str::to_string)
+                .collect(),
+            DynamicValues::StashBranches => {
+                let stashes = GitOperations::run(&["stash", "list", "--pretty=format:%gd|%s"])?;
+                stashes
+                    .lines()
+                    .filter_map(crate::commands::stash::utils::parse_stash_line_with_branch)
+                    .map(|stash| stash.branch)
+                    .filter(|branch| branch != "unknown")
+                    .collect()
+            }
+            DynamicValues::Stashes => GitOperations::run(&["stash", "list", "--format=%gd"])?
+                .lines()
+                .map(str::to_string)
+                .collect(),
+        };
+        if self != DynamicValues::Stashes {
+            values.sort();
+        }
+        values.dedup();
+        Ok(values)
+    }
+
+    fn refs(namespace: &str) -> Result<Vec<String>> {
+        let output = GitOperations::run(&["for-each-ref", "--format=%(refname:short)", namespace])?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    fn remote_branches() -> Result<Vec<String>> {
+        // `refs/remotes/origin/HEAD` shortens to the bare remote name
+        Ok(Self::refs("refs/remotes")?
+            .into_iter()
+            .filter(|name| name.contains('/') && !name.ends_with("/HEAD"))
+            .collect())
+    }
+}
+
+/// Hidden command shells call at completion time: `git-x __complete -- <words>`
+///
+/// The words are the command line after `git-x`, ending with the word being completed.
+pub struct CompleteCommand {
+    words: Vec<String>,
+}
+
+impl CompleteCommand {
+    pub fn new(words: Vec<String>) -> Self {
+        Self { words }
+    }
+
+    /// The subcommand path and argument id the last word is a value for, and the part of
+    /// the word to complete (after `--option=`, if any)
+    pub fn position(words: &[String]) -> Option<(String, String, String)> {
+        use clap::CommandFactory;
+
+        let (current, before) = words.split_last()?;
+        let mut command = crate::cli::Cli::command();
+        command.build();
+        let mut path: Vec<String> = Vec::new();
+        let mut positional = 0;
+        let mut expecting: Option<String> = None;
+
+        for word in before {
+            if let Some(arg) = expecting.take() {
+                // Bash splits `--option=value` into three words
+                if word == "=" {
+                    expecting = Some(arg);
+                }
+                continue;
+            }
+            if word == "--" {
+                continue;
+            }
+            if let Some(flag) = word.strip_prefix("--") {
+                let (name, inline) = match flag.split_once('=') {
+                    Some((name, _)) => (name, true),
+                    None => (flag, false),
+                };
+                if let Some(arg) = command.get_arguments().find(|a| a.get_long() == Some(name))
+                    && arg.get_action().takes_values()
+                    && !inline
+                {
+                    expecting = Some(arg.get_id().to_string());
+                }
+                continue;
+            }
+            if let Some(short) = word.strip_prefix('-').filter(|s| s.len() == 1) {
+                let short = short.chars().next()?;
+                if let Some(arg) = command
+                    .get_arguments()
+                    .find(|a| a.get_short() == Some(short))
+                    && arg.get_action().takes_values()
+                {
+                    expecting = Some(arg.get_id().to_string());
+                }
+                continue;
+            }
+            if let Some(sub) = command.find_subcommand(word).cloned() {
+                path.push(sub.get_name().to_string());
+                command = sub;
+                positional = 0;
+                continue;
+            }
+            positional += 1;
+        }
+
+        let path = path.join(" ");
+        if let Some(arg) = expecting {
+            return Some((path, arg, current.clone()));
+        }
+        if let Some((name, partial)) = current.strip_prefix("--").and_then(|f| f.split_once('=')) {
+            let arg = command
+                .get_arguments()
+                .find(|a| a.get_long() == Some(name))?;
+            return Some((path, arg.get_id().to_string(), partial.to_string()));
+        }
+        if current.starts_with('-') {
+            return None;
+        }
+
+        // A list positional takes every remaining word
+        let positionals: Vec<&clap::Arg> = command.get_positionals().collect();
+        let arg = positionals.get(positional).or_else(|| {
+            positionals
+                .last()
+                .filter(|arg| arg.get_num_args().is_some_and(|n| n.max_values() > 1))
+        })?;
+        Some((path, arg.get_id().to_string(), current.clone()))
+    }
+}
+
+impl Command for CompleteCommand {
+    fn execute(&self) -> Result<String> {
+        let Some((path, arg, partial)) = Self::position(&self.words) else {
+            return Ok(String::new());
+        };
+        let Some(values) = DynamicValues::for_arg(&path, &arg) else {
+            return Ok(String::new());
+        };
+        // Outside a repository there is simply nothing to offer
+        let values = values.list().unwrap_or_default();
+        let prefix = self
+            .words
+            .last()
+            .map(|word| &word[..word.len() - partial.len()])
+            .unwrap_or_default();
+        Ok(values
+            .iter()
+            .filter(|value| value.starts_with(&partial))
+            .map(|value| format!("{prefix}{value}"))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn name(&self) -> &'static str {
+        "__complete"
+    }
+
+    fn description(&self) -> &'static str {
+        "Print completions for branches, remotes and stashes"
+    }
+}
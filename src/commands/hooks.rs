@@ -0,0 +1,582 @@
+use crate::core::config::Config;
+use crate::core::export::ExportValue;
+use crate::core::git::GitOperations;
+use crate::core::output::BufferedOutput;
+use crate::core::secrets::glob_match;
+use crate::core::traits::*;
+use crate::{GitXError, Result};
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+/// Manifest `hooks run-ci` reads, relative to the repository root, unless configured
+pub const DEFAULT_CI_MANIFEST: &str = ".git-x/ci.toml";
+
+/// Marker identifying the pre-push hook line written by `hooks run-ci --install-hook`
+const RUN_CI_HOOK_MARKER: &str = "git x hooks run-ci";
+
+/// Output lines shown for each failing check
+const FAILURE_TAIL_LINES: usize = 20;
+
+/// Project file, at the repository root, holding the hooks shared through the repository
+pub const PROJECT_CONFIG: &str = ".git-x.toml";
+
+/// Run `command` through the platform shell in `dir`, with stdin closed
+///
+/// A pre-push hook's stdin carries the refs being pushed, which commands must not eat.
+fn run_shell(
+    dir: &Path,
+    command: &str,
+    env: &[(&str, &str)],
+) -> std::io::Result<std::process::Output> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    std::process::Command::new(shell)
+        .args([flag, command])
+        .envs(env.iter().copied())
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()
+}
+
+/// The last [`FAILURE_TAIL_LINES`] lines of `output`, indented, after a count of what was cut
+fn tail_lines(output: &str) -> Vec<String> {
+    let lines: Vec<&str> = output.lines().collect();
+    let skipped = lines.len().saturating_sub(FAILURE_TAIL_LINES);
+    let mut tail = Vec::new();
+    if skipped > 0 {
+        tail.push(format!("   … {skipped} earlier line(s)"));
+    }
+    tail.extend(lines[skipped..].iter().map(|line| format!("   {line}")));
+    tail
+}
+
+/// A named shell command CI runs, e.g. `clippy = "cargo clippy -- -D warnings"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiCheck {
+    pub name: String,
+    pub run: String,
+    /// Directory to run in, relative to the repository root
+    pub dir: Option<String>,
+}
+
+/// The checks listed in a CI manifest, in file order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CiManifest {
+    pub checks: Vec<CiCheck>,
+}
+
+impl CiManifest {
+    /// Read a manifest such as:
+    ///
+    /// ```toml
+    /// [checks]
+    /// fmt = "cargo fmt --all -- --check"
+    /// clippy = "cargo clippy --all-targets -- -D warnings"
+    /// test = { run = "cargo test", dir = "crates/core" }
+    /// ```
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.is_file() {
+            return Err(GitXError::Other(format!(
+                "No CI manifest at {}: list the checks under [checks], e.g. 'fmt = \"cargo fmt --check\"', or pass --manifest",
+                path.display()
+            )));
+        }
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse(toml: &str) -> Result<Self> {
+        let document = crate::core::toml::parse(toml)?;
+        let Some(ExportValue::Record(entries)) = document.get("checks") else {
+            return Err(GitXError::Parse(
+                "The CI manifest has no [checks] table".to_string(),
+            ));
+        };
+
+        let checks = entries
+            .iter()
+            .map(|(name, value)| {
+                let (run, dir) = match value {
+                    ExportValue::Text(run) => (Some(run.as_str()), None),
+                    ExportValue::Record(_) => (
+                        value.get("run").and_then(ExportValue::as_str),
+                        value.get("dir").and_then(ExportValue::as_str),
+                    ),
+                    _ => (None, None),
+                };
+                match run {
+                    Some(run) if !run.trim().is_empty() => Ok(CiCheck {
+                        name: name.clone(),
+                        run: run.to_string(),
+                        dir: dir.map(String::from),
+                    }),
+                    _ => Err(GitXError::Parse(format!(
+                        "Check '{name}' needs a command: '{name} = \"<command>\"' or '{name} = {{ run = \"<command>\" }}'"
+                    ))),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if checks.is_empty() {
+            return Err(GitXError::Parse(
+                "The CI manifest's [checks] table is empty".to_string(),
+            ));
+        }
+        Ok(Self { checks })
+    }
+
+    /// Only the named checks, in manifest order; all of them when `names` is empty
+    pub fn select(self, names: &[String]) -> Result<Self> {
+        if let Some(unknown) = names
+            .iter()
+            .find(|name| !self.checks.iter().any(|check| &check.name == *name))
+        {
+            let known: Vec<&str> = self.checks.iter().map(|c| c.name.as_str()).collect();
+            return Err(GitXError::Other(format!(
+                "No check named '{unknown}' in the CI manifest; it defines {}",
+                known.join(", ")
+            )));
+        }
+        let checks = self
+            .checks
+            .into_iter()
+            .filter(|check| names.is_empty() || names.contains(&check.name))
+            .collect();
+        Ok(Self { checks })
+    }
+}
+
+/// How one check went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckOutcome {
+    pub name: String,
+    /// Exit code, `None` when the command couldn't start or was killed by a signal
+    pub code: Option<i32>,
+    pub duration: Duration,
+    /// Stdout followed by stderr
+    pub output: String,
+}
+
+impl CheckOutcome {
+    pub fn passed(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+/// Command that runs the checks CI runs, locally and in parallel
+pub struct RunCiCommand {
+    checks: Vec<String>,
+    manifest: Option<PathBuf>,
+    install_hook: bool,
+}
+
+impl RunCiCommand {
+    /// Run only the named checks; every check when empty
+    pub fn new(checks: Vec<String>) -> Self {
+        Self {
+            checks,
+            manifest: None,
+            install_hook: false,
+        }
+    }
+
+    /// Read this manifest instead of `git-x.hooks.ciManifest`
+    pub fn with_manifest(mut self, manifest: PathBuf) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// Install a pre-push hook that runs the checks instead of running them
+    pub fn with_install_hook(mut self) -> Self {
+        self.install_hook = true;
+        self
+    }
+
+    /// Rendered output and whether any check failed
+    pub fn run(&self) -> Result<(String, bool)> {
+        if self.install_hook {
+            return Self::install_pre_push_hook().map(|output| (output, false));
+        }
+
+        let root = PathBuf::from(GitOperations::repo_root()?);
+        let (manifest_path, shown) = match &self.manifest {
+            Some(path) => (path.clone(), path.display().to_string()),
+            None => {
+                let relative = Config::get("hooks.ciManifest")
+                    .unwrap_or_else(|| DEFAULT_CI_MANIFEST.to_string());
+                (root.join(&relative), relative)
+            }
+        };
+        let manifest = CiManifest::load(&manifest_path)?.select(&self.checks)?;
+
+        let started = Instant::now();
+        let outcomes = Self::run_checks(&root, &manifest.checks);
+        let output = Self::render(&shown, &outcomes, started.elapsed());
+        Ok((output, outcomes.iter().any(|outcome| !outcome.passed())))
+    }
+
+    /// Run every check at once, up to `--jobs` at a time, keeping manifest order
+    fn run_checks(root: &Path, checks: &[CiCheck]) -> Vec<CheckOutcome> {
+        use indicatif::{ProgressBar, ProgressStyle};
+
+        let pb = if atty::is(atty::Stream::Stderr)
+            && std::env::var("GIT_X_NON_INTERACTIVE").is_err()
+        {
+            let pb = ProgressBar::new(checks.len() as u64);
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template("{spinner:.green} [{elapsed_precise}] {pos}/{len} checks done {msg}")
+                    .expect("Failed to set progress style"),
+            );
+            pb.enable_steady_tick(Duration::from_millis(120));
+            pb
+        } else {
+            ProgressBar::hidden()
+        };
+
+        let outcomes = checks
+            .par_iter()
+            .map(|check| {
+                let outcome = Self::run_check(root, check);
+                pb.inc(1);
+                outcome
+            })
+            .collect();
+        pb.finish_and_clear();
+        outcomes
+    }
+
+    fn run_check(root: &Path, check: &CiCheck) -> CheckOutcome {
+        let dir = match &check.dir {
+            Some(dir) => root.join(dir),
+            None => root.to_path_buf(),
+        };
+
+        let started = Instant::now();
+        let result = run_shell(&dir, &check.run, &[]);
+        let duration = started.elapsed();
+
+        match result {
+            Ok(output) => CheckOutcome {
+                name: check.name.clone(),
+                code: output.status.code(),
+                duration,
+                output: format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                ),
+            },
+            Err(e) => CheckOutcome {
+                name: check.name.clone(),
+                code: None,
+                duration,
+                output: format!("Could not run '{}' in {}: {e}", check.run, dir.display()),
+            },
+        }
+    }
+
+    /// One line per check, the tail of each failure, and the totals
+    pub fn render(manifest: &str, outcomes: &[CheckOutcome], total: Duration) -> String {
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🧪 Ran {} check(s) from {manifest}",
+            outcomes.len()
+        ));
+
+        let width = outcomes.iter().map(|o| o.name.len()).max().unwrap_or(0);
+        for outcome in outcomes {
+            let status = match outcome.code {
+                Some(0) => String::new(),
+                Some(code) => format!("  (exit status {code})"),
+                None => "  (did not finish)".to_string(),
+            };
+            output.add_line(format!(
+                "{} {:<width$}  {:>6.1}s{status}",
+                if outcome.passed() { "✅" } else { "❌" },
+                outcome.name,
+                outcome.duration.as_secs_f64(),
+            ));
+        }
+
+        let failed: Vec<&CheckOutcome> = outcomes.iter().filter(|o| !o.passed()).collect();
+        for outcome in &failed {
+            output.add_line(format!("\n── {} ──", outcome.name));
+            output.add_lines(tail_lines(&outcome.output));
+        }
+
+        let work: Duration = outcomes.iter().map(|o| o.duration).sum();
+        let timing = format!(
+            "{:.1}s ({:.1}s of checks run in parallel)",
+            total.as_secs_f64(),
+            work.as_secs_f64()
+        );
+        output.add_line(String::new());
+        if failed.is_empty() {
+            output.add_line(format!(
+                "✅ All {} check(s) passed in {timing}",
+                outcomes.len()
+            ));
+        } else {
+            let names: Vec<&str> = failed.iter().map(|o| o.name.as_str()).collect();
+            output.add_line(format!(
+                "❌ {} of {} check(s) failed in {timing}: {}",
+                failed.len(),
+                outcomes.len(),
+                names.join(", ")
+            ));
+        }
+        output.content()
+    }
+
+    /// Add the checks to the pre-push hook, keeping whatever the hook already runs
+    fn install_pre_push_hook() -> Result<String> {
+        let hook = GitOperations::run(&["rev-parse", "--git-path", "hooks/pre-push"])?;
+        let path = Path::new(&hook);
+        let guard = format!("{RUN_CI_HOOK_MARKER} || exit 1");
+
+        let content = match std::fs::read_to_string(path) {
+            Ok(existing) if existing.contains(RUN_CI_HOOK_MARKER) => {
+                return Ok(format!(
+                    "✅ CI checks already run by the pre-push hook at {hook}"
+                ));
+            }
+            Ok(existing) => {
+                // Only shell hooks can take the checks as their first command
+                let (shebang, rest) = existing.split_once('\n').unwrap_or((&existing, ""));
+                if !(shebang.starts_with("#!") && shebang.ends_with("sh")) {
+                    return Err(GitXError::Other(format!(
+                        "A pre-push hook that isn't a shell script exists at {hook}; make it run '{guard}'"
+                    )));
+                }
+                format!(
+                    "{shebang}\n# Added by git-x: run the CI checks before pushing\n{guard}\n{rest}"
+                )
+            }
+            Err(_) => format!(
+                "#!/bin/sh\n# Installed by git-x: run the CI checks before pushing\n{guard}\n"
+            ),
+        };
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, content)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        Ok(format!(
+            "🪝 Installed pre-push hook at {hook}\n💡 Bypass once with 'git push --no-verify'"
+        ))
+    }
+}
+
+impl Command for RunCiCommand {
+    fn execute(&self) -> Result<String> {
+        self.run().map(|(output, _)| output)
+    }
+
+    fn name(&self) -> &'static str {
+        "hooks run-ci"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run the checks CI runs, locally and in parallel"
+    }
+}
+
+impl GitCommand for RunCiCommand {}
+
+/// Commands to run after switching to a branch matching `pattern`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwitchHook {
+    /// Branch name where `*` matches any run of characters
+    pub pattern: String,
+    pub commands: Vec<String>,
+}
+
+/// The `[hooks.switch]` table of `.git-x.toml`, in file order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwitchHooks {
+    pub hooks: Vec<SwitchHook>,
+}
+
+impl SwitchHooks {
+    /// Read the hooks from `.git-x.toml` at the repository root; none when the file is missing
+    pub fn load() -> Result<Self> {
+        let path = PathBuf::from(GitOperations::repo_root()?).join(PROJECT_CONFIG);
+        match std::fs::read_to_string(&path) {
+            Ok(toml) => Self::parse(&toml),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Parse hooks such as:
+    ///
+    /// ```toml
+    /// [hooks.switch]
+    /// "*" = "direnv allow"
+    /// "release/*" = ["nvm use 18", "echo 'Schema changed: run make migrate'"]
+    /// ```
+    pub fn parse(toml: &str) -> Result<Self> {
+        let document = crate::core::toml::parse(toml)?;
+        let Some(table) = document.get("hooks").and_then(|hooks| hooks.get("switch")) else {
+            return Ok(Self::default());
+        };
+        let ExportValue::Record(entries) = table else {
+            return Err(GitXError::Parse(format!(
+                "[hooks.switch] in {PROJECT_CONFIG} must be a table of branch patterns"
+            )));
+        };
+
+        let hooks = entries
+            .iter()
+            .map(|(pattern, value)| {
+                let commands = match value {
+                    ExportValue::Text(command) => Some(vec![command.clone()]),
+                    ExportValue::List(items) => items
+                        .iter()
+                        .map(|item| item.as_str().map(String::from))
+                        .collect(),
+                    _ => None,
+                };
+                match commands {
+                    Some(commands) if commands.iter().all(|c| !c.trim().is_empty()) => {
+                        Ok(SwitchHook {
+                            pattern: pattern.clone(),
+                            commands,
+                        })
+                    }
+                    _ => Err(GitXError::Parse(format!(
+                        "Switch hook '{pattern}' in {PROJECT_CONFIG} needs a command or a list of commands"
+                    ))),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { hooks })
+    }
+
+    /// Commands for `branch` as (pattern, command), from every matching pattern in file order
+    pub fn commands_for(&self, branch: &str) -> Vec<(&str, &str)> {
+        self.hooks
+            .iter()
+            .filter(|hook| glob_match(&hook.pattern, branch))
+            .flat_map(|hook| {
+                hook.commands
+                    .iter()
+                    .map(|command| (hook.pattern.as_str(), command.as_str()))
+            })
+            .collect()
+    }
+
+    /// Run the commands for `branch` from the repository root, reporting each one
+    ///
+    /// A failing command is reported but doesn't stop the rest: the switch already happened.
+    pub fn run(&self, branch: &str) -> Result<Vec<String>> {
+        let commands = self.commands_for(branch);
+        if commands.is_empty() {
+            return Ok(Vec::new());
+        }
+        let root = PathBuf::from(GitOperations::repo_root()?);
+
+        let mut lines = Vec::new();
+        for (_, command) in commands {
+            match run_shell(&root, command, &[("GIT_X_BRANCH", branch)]) {
+                Ok(output) => {
+                    lines.push(match output.status.code() {
+                        Some(0) => format!("🪝 {command}"),
+                        Some(code) => {
+                            format!("⚠️  Switch hook '{command}' failed (exit status {code})")
+                        }
+                        None => format!("⚠️  Switch hook '{command}' did not finish"),
+                    });
+                    lines.extend(tail_lines(&format!(
+                        "{}{}",
+                        String::from_utf8_lossy(&output.stdout),
+                        String::from_utf8_lossy(&output.stderr)
+                    )));
+                }
+                Err(e) => lines.push(format!("⚠️  Could not run switch hook '{command}': {e}")),
+            }
+        }
+        Ok(lines)
+    }
+
+    /// Lines reporting the hooks run after a git-x command switched to `branch`
+    ///
+    /// Switching already succeeded, so a broken `.git-x.toml` only produces a warning.
+    pub fn after_switch(branch: &str) -> Vec<String> {
+        match Self::load().and_then(|hooks| hooks.run(branch)) {
+            Ok(lines) => lines,
+            Err(e) => vec![format!("⚠️  Switch hooks not run: {e}")],
+        }
+    }
+}
+
+/// Command that runs, or lists, the switch hooks for a branch
+pub struct SwitchHooksCommand {
+    branch: Option<String>,
+    dry_run: bool,
+}
+
+impl SwitchHooksCommand {
+    /// Hooks for `branch`; the current branch when `None`
+    pub fn new(branch: Option<String>) -> Self {
+        Self {
+            branch,
+            dry_run: false,
+        }
+    }
+
+    /// List the commands that would run instead of running them
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+}
+
+impl Command for SwitchHooksCommand {
+    fn execute(&self) -> Result<String> {
+        let branch = match &self.branch {
+            Some(branch) => branch.clone(),
+            None => GitOperations::require_branch("hooks switch")?,
+        };
+        let hooks = SwitchHooks::load()?;
+        let commands = hooks.commands_for(&branch);
+        if commands.is_empty() {
+            return Ok(format!(
+                "No switch hooks in {PROJECT_CONFIG} match '{branch}'"
+            ));
+        }
+
+        if self.dry_run {
+            let mut output = BufferedOutput::new();
+            output.add_line(format!(
+                "🪝 Switching to '{branch}' would run {} command(s):",
+                commands.len()
+            ));
+            for (pattern, command) in commands {
+                output.add_line(format!("   {command}  ({pattern})"));
+            }
+            return Ok(output.content());
+        }
+        Ok(hooks.run(&branch)?.join("\n"))
+    }
+
+    fn name(&self) -> &'static str {
+        "hooks switch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run the switch hooks from .git-x.toml for a branch"
+    }
+}
+
+impl GitCommand for SwitchHooksCommand {}
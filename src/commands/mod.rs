@@ -1,14 +1,26 @@
 pub mod analysis;
 pub mod branch;
+#[cfg(feature = "changed-apis")]
+pub mod changed_apis;
 pub mod commit;
 pub mod completion;
+pub mod hooks;
 pub mod repository;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod setup;
 pub mod stash;
 
 // Re-export commonly used types
 pub use analysis::*;
 pub use branch::*;
+#[cfg(feature = "changed-apis")]
+pub use changed_apis::*;
 pub use commit::*;
 pub use completion::*;
+pub use hooks::*;
 pub use repository::*;
+#[cfg(feature = "serve")]
+pub use serve::*;
+pub use setup::*;
 pub use stash::*;
@@ -0,0 +1,283 @@
+use crate::commands::analysis::{AsyncSummaryCommand, ParallelContributorsCommand};
+use crate::commands::repository::{AsyncHealthCommand, AsyncInfoCommand};
+use crate::core::export::{ExportFormat, ExportValue};
+use crate::core::output::JsonSink;
+use crate::{GitXError, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request head read before giving up on a client
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Contributors listed on the dashboard
+const CONTRIBUTORS_SHOWN: usize = 20;
+
+const DASHBOARD_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>git-x dashboard</title>
+<style>
+body{font-family:sans-serif;max-width:72rem;margin:2rem auto;padding:0 1rem;color:#24292f}
+main{display:grid;grid-template-columns:repeat(auto-fit,minmax(32rem,1fr));gap:1rem}
+section{border:1px solid #d0d7de;border-radius:6px;padding:0 1rem 1rem;overflow-x:auto}
+table{border-collapse:collapse;width:100%;font-size:.9rem}
+th,td{text-align:left;padding:.2rem .5rem;border-bottom:1px solid #eaeef2;vertical-align:top}
+.error{color:#cf222e}
+#updated{color:#57606a;font-size:.85rem}
+</style>
+</head>
+<body>
+<h1>git-x dashboard</h1>
+<p id="updated">Loading...</p>
+<main>
+<section><h2>Info</h2><div id="info"></div></section>
+<section><h2>Summary</h2><div id="summary"></div></section>
+<section><h2>Health</h2><div id="health"></div></section>
+<section><h2>Contributors</h2><div id="contributors"></div></section>
+</main>
+<script>
+const REFRESH_SECONDS = {{refresh}};
+
+function el(tag, text) {
+  const node = document.createElement(tag);
+  if (text !== undefined) node.textContent = text;
+  return node;
+}
+
+function isRecord(value) {
+  return value !== null && typeof value === "object" && !Array.isArray(value);
+}
+
+function render(value) {
+  if (Array.isArray(value) && value.length > 0 && value.every(isRecord)) {
+    const table = el("table");
+    const keys = Object.keys(value[0]);
+    const head = table.insertRow();
+    keys.forEach(key => head.appendChild(el("th", key)));
+    value.forEach(record => {
+      const row = table.insertRow();
+      keys.forEach(key => row.insertCell().appendChild(render(record[key])));
+    });
+    return table;
+  }
+  if (isRecord(value)) {
+    const table = el("table");
+    Object.entries(value).forEach(([key, field]) => {
+      const row = table.insertRow();
+      row.appendChild(el("th", key));
+      row.insertCell().appendChild(render(field));
+    });
+    return table;
+  }
+  if (Array.isArray(value)) return el("span", value.join(", "));
+  return el("span", value === null ? "-" : String(value));
+}
+
+async function load(panel) {
+  const target = document.getElementById(panel);
+  try {
+    const response = await fetch("/api/" + panel);
+    const body = await response.json();
+    target.replaceChildren(response.ok ? render(body) : el("p", body.error));
+    if (!response.ok) target.firstChild.className = "error";
+  } catch (e) {
+    target.replaceChildren(el("p", String(e)));
+    target.firstChild.className = "error";
+  }
+}
+
+async function refresh() {
+  await Promise.all(["info", "summary", "health", "contributors"].map(load));
+  document.getElementById("updated").textContent =
+    "Updated " + new Date().toLocaleTimeString() + ", refreshing every " + REFRESH_SECONDS + "s";
+}
+
+refresh();
+setInterval(refresh, REFRESH_SECONDS * 1000);
+</script>
+</body>
+</html>
+"#;
+
+/// What a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Dashboard,
+    Info,
+    Summary,
+    Health,
+    Contributors,
+    NotFound,
+    MethodNotAllowed,
+    /// The `Host` header isn't this server, as with a DNS-rebinding page
+    Forbidden,
+}
+
+impl Route {
+    /// Route from an HTTP request line such as `GET /api/info HTTP/1.1`
+    pub fn resolve(request_line: &str) -> Self {
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Route::NotFound;
+        };
+        if method != "GET" {
+            return Route::MethodNotAllowed;
+        }
+        let path = target.split(['?', '#']).next().unwrap_or(target);
+        match path.trim_end_matches('/') {
+            "" | "/index.html" => Route::Dashboard,
+            "/api/info" => Route::Info,
+            "/api/summary" => Route::Summary,
+            "/api/health" => Route::Health,
+            "/api/contributors" => Route::Contributors,
+            _ => Route::NotFound,
+        }
+    }
+
+    /// Route a whole request head, refusing any `Host` but `127.0.0.1:<port>` or `localhost:<port>`
+    pub fn from_request(request: &str, port: u16) -> Self {
+        let mut lines = request.lines();
+        let request_line = lines.next().unwrap_or_default();
+        let host = lines
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("host"))
+            .map(|(_, value)| value.trim().to_ascii_lowercase());
+        let allowed = [format!("127.0.0.1:{port}"), format!("localhost:{port}")];
+        match host {
+            Some(host) if allowed.contains(&host) => Self::resolve(request_line),
+            _ => Route::Forbidden,
+        }
+    }
+}
+
+/// A complete HTTP/1.1 response; every connection is closed after one
+pub fn http_response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// The dashboard page, reloading its panels every `refresh` seconds
+pub fn dashboard_html(refresh: u64) -> String {
+    DASHBOARD_HTML.replace("{{refresh}}", &refresh.to_string())
+}
+
+/// Local, read-only web dashboard over the JSON output of info, summary, health and contributors
+pub struct ServeCommand {
+    port: u16,
+    refresh: u64,
+}
+
+impl ServeCommand {
+    pub fn new(port: u16) -> Self {
+        Self { port, refresh: 30 }
+    }
+
+    /// Seconds between dashboard refreshes
+    pub fn with_refresh(mut self, refresh: u64) -> Self {
+        self.refresh = refresh.max(1);
+        self
+    }
+
+    /// Serve on localhost until interrupted
+    pub async fn run(&self) -> Result<()> {
+        // Only ever bound to loopback: the dashboard shows repository details
+        let listener = TcpListener::bind(("127.0.0.1", self.port)).await?;
+        let address = listener.local_addr()?;
+        let port = address.port();
+        println!("🌐 Serving dashboard on http://{address} (Ctrl+C to stop)");
+
+        // Commands stream into non-Send sinks, so connections share this thread
+        let local = tokio::task::LocalSet::new();
+        let refresh = self.refresh;
+        local
+            .run_until(async move {
+                loop {
+                    let (stream, _) = listener.accept().await?;
+                    tokio::task::spawn_local(async move {
+                        if let Err(e) = Self::handle(stream, port, refresh).await {
+                            eprintln!("⚠️  Request failed: {e}");
+                        }
+                    });
+                }
+            })
+            .await
+    }
+
+    async fn handle(mut stream: TcpStream, port: u16, refresh: u64) -> Result<()> {
+        let request = Self::read_request_head(&mut stream).await?;
+
+        let response = match Route::from_request(&request, port) {
+            Route::Dashboard => http_response(
+                "200 OK",
+                "text/html; charset=utf-8",
+                &dashboard_html(refresh),
+            ),
+            Route::NotFound => http_response("404 Not Found", "text/plain", "Not found"),
+            Route::Forbidden => http_response("403 Forbidden", "text/plain", "Unknown host"),
+            Route::MethodNotAllowed => http_response(
+                "405 Method Not Allowed",
+                "text/plain",
+                "The dashboard is read-only",
+            ),
+            route => match Self::panel_json(route).await {
+                Ok(json) => http_response("200 OK", "application/json", &json),
+                Err(e) => http_response(
+                    "500 Internal Server Error",
+                    "application/json",
+                    &ExportValue::record(vec![("error", ExportValue::from(e.to_string()))])
+                        .to_json(),
+                ),
+            },
+        };
+
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await?;
+        Ok(())
+    }
+
+    async fn read_request_head(stream: &mut TcpStream) -> Result<String> {
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+            if request.len() > MAX_REQUEST_BYTES {
+                return Err(GitXError::Parse("Request head too large".to_string()));
+            }
+        }
+        Ok(String::from_utf8_lossy(&request).to_string())
+    }
+
+    async fn panel_json(route: Route) -> Result<String> {
+        match route {
+            Route::Info => AsyncInfoCommand::new().execute_json().await,
+            Route::Summary => {
+                AsyncSummaryCommand::new(None)
+                    .with_json()
+                    .execute_parallel()
+                    .await
+            }
+            Route::Health => {
+                let mut sink = JsonSink::new();
+                AsyncHealthCommand::new().stream_parallel(&mut sink).await?;
+                Ok(sink.to_json())
+            }
+            Route::Contributors => {
+                tokio::task::spawn_blocking(|| {
+                    ParallelContributorsCommand::new(None)
+                        .with_limit(Some(CONTRIBUTORS_SHOWN))
+                        .with_format(Some(ExportFormat::Json))
+                        .execute_parallel()
+                })
+                .await?
+            }
+            _ => Err(GitXError::Other(format!("No JSON panel for {route:?}"))),
+        }
+    }
+}
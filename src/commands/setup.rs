@@ -0,0 +1,656 @@
+use crate::commands::completion::CompletionInstallCommand;
+use crate::core::config::Config;
+use crate::core::git::GitOperations;
+use crate::core::interactive::Interactive;
+use crate::core::output::BufferedOutput;
+use crate::core::traits::Command;
+use crate::core::validation::Validate;
+use crate::{GitXError, Result};
+use clap_complete::Shell;
+
+/// Oldest git release that supports everything the wizard configures (`init.defaultBranch`)
+pub const MIN_GIT_VERSION: (u32, u32) = (2, 28);
+
+/// Aliases offered by the wizard, as `git <alias>` => `git <command>`
+pub const SUGGESTED_ALIASES: &[(&str, &str)] = &[
+    ("xi", "x info"),
+    ("xs", "x sync"),
+    ("xn", "x new"),
+    ("xr", "x switch-recent"),
+    ("xc", "x clean-branches"),
+    ("xu", "x undo"),
+];
+
+/// How `git pull` integrates upstream changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullStrategy {
+    Rebase,
+    Merge,
+    FastForwardOnly,
+}
+
+impl PullStrategy {
+    pub const ALL: [PullStrategy; 3] = [
+        PullStrategy::Rebase,
+        PullStrategy::Merge,
+        PullStrategy::FastForwardOnly,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PullStrategy::Rebase => "rebase",
+            PullStrategy::Merge => "merge",
+            PullStrategy::FastForwardOnly => "fast-forward only",
+        }
+    }
+
+    /// Config values selecting this strategy; `None` means the key is unset
+    pub fn settings(&self) -> [(&'static str, Option<&'static str>); 2] {
+        match self {
+            PullStrategy::Rebase => [("pull.rebase", Some("true")), ("pull.ff", None)],
+            PullStrategy::Merge => [("pull.rebase", Some("false")), ("pull.ff", None)],
+            PullStrategy::FastForwardOnly => {
+                [("pull.rebase", Some("false")), ("pull.ff", Some("only"))]
+            }
+        }
+    }
+
+    /// Strategy described by existing `pull.rebase` and `pull.ff` values
+    pub fn from_config(rebase: Option<&str>, ff: Option<&str>) -> Option<Self> {
+        if ff == Some("only") {
+            return Some(PullStrategy::FastForwardOnly);
+        }
+        match rebase? {
+            "false" => Some(PullStrategy::Merge),
+            _ => Some(PullStrategy::Rebase),
+        }
+    }
+}
+
+/// First-run wizard that configures git and git-x for a new machine
+///
+/// Everything is written to the global git config. Without a terminal (or with
+/// `--yes`) suggested values are applied only where nothing is configured yet.
+pub struct SetupCommand {
+    assume_yes: bool,
+}
+
+impl Default for SetupCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetupCommand {
+    pub fn new() -> Self {
+        Self { assume_yes: false }
+    }
+
+    /// Accept suggested values without prompting
+    pub fn with_defaults(mut self) -> Self {
+        self.assume_yes = true;
+        self
+    }
+
+    fn prompting(&self) -> bool {
+        !self.assume_yes && Interactive::is_interactive()
+    }
+
+    /// Parse `git --version` output such as `git version 2.39.3 (Apple Git-146)`
+    pub fn parse_git_version(output: &str) -> Option<(u32, u32, u32)> {
+        let version = output.split_whitespace().nth(2)?;
+        let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let patch = parts.next().flatten().unwrap_or(0);
+        Some((major, minor, patch))
+    }
+
+    /// Shell named by a `$SHELL` path, if completion can be installed for it
+    pub fn detect_shell(shell_path: &str) -> Option<Shell> {
+        match shell_path.rsplit('/').next()? {
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "fish" => Some(Shell::Fish),
+            _ => None,
+        }
+    }
+
+    fn global_get(key: &str) -> Option<String> {
+        GitOperations::run(&["config", "--global", "--get", key])
+            .ok()
+            .filter(|value| !value.is_empty())
+    }
+
+    fn global_set(key: &str, value: &str) -> Result<()> {
+        GitOperations::run(&["config", "--global", key, value]).map(|_| ())
+    }
+
+    fn check_git_version(output: &mut BufferedOutput) -> Result<()> {
+        let raw = GitOperations::run(&["--version"])?;
+        let (min_major, min_minor) = MIN_GIT_VERSION;
+        output.add_line(match Self::parse_git_version(&raw) {
+            Some((major, minor, patch)) if (major, minor) >= MIN_GIT_VERSION => {
+                format!("✅ git {major}.{minor}.{patch}")
+            }
+            Some((major, minor, patch)) => format!(
+                "⚠️  git {major}.{minor}.{patch} is older than {min_major}.{min_minor}; please upgrade"
+            ),
+            None => format!("⚠️  Could not determine git version from '{raw}'"),
+        });
+        Ok(())
+    }
+
+    fn configure_identity(&self, output: &mut BufferedOutput) -> Result<()> {
+        let mut identity = Vec::new();
+        for (key, prompt, example) in [
+            ("user.name", "Your name", "Your Name"),
+            ("user.email", "Your email", "you@example.com"),
+        ] {
+            let current = Self::global_get(key);
+            let value = if self.prompting() {
+                let input = Interactive::text_input(prompt, current.as_deref(), None)?;
+                let input = input.trim().to_string();
+                if input.is_empty() {
+                    current
+                } else {
+                    if current.as_deref() != Some(input.as_str()) {
+                        Self::global_set(key, &input)?;
+                    }
+                    Some(input)
+                }
+            } else {
+                current
+            };
+
+            match value {
+                Some(value) => identity.push(value),
+                None => output.add_line(format!(
+                    "⚠️  {key} not set, run: git config --global {key} \"{example}\""
+                )),
+            }
+        }
+
+        if let [name, email] = identity.as_slice() {
+            output.add_line(format!("👤 Identity: {name} <{email}>"));
+        }
+        Ok(())
+    }
+
+    fn configure_default_branch(&self, output: &mut BufferedOutput) -> Result<()> {
+        let current = Self::global_get("init.defaultBranch");
+        let suggested = current.clone().unwrap_or_else(|| "main".to_string());
+        let branch = if self.prompting() {
+            Interactive::text_input(
+                "Default branch for new repositories",
+                Some(&suggested),
+                Some(Validate::branch_name),
+            )?
+        } else {
+            suggested
+        };
+
+        if current.as_deref() == Some(branch.as_str()) {
+            output.add_line(format!("🌿 Default branch: {branch} (unchanged)"));
+        } else {
+            Self::global_set("init.defaultBranch", &branch)?;
+            output.add_line(format!("🌿 Default branch: {branch}"));
+        }
+        Ok(())
+    }
+
+    fn configure_pull_strategy(&self, output: &mut BufferedOutput) -> Result<()> {
+        let current = PullStrategy::from_config(
+            Self::global_get("pull.rebase").as_deref(),
+            Self::global_get("pull.ff").as_deref(),
+        );
+        let suggested = current.unwrap_or(PullStrategy::Rebase);
+        let strategy = if self.prompting() {
+            let labels: Vec<&str> = PullStrategy::ALL.iter().map(|s| s.label()).collect();
+            let default = PullStrategy::ALL.iter().position(|s| *s == suggested);
+            let label = Interactive::fuzzy_select(&labels, "How should 'git pull' work?", default)?;
+            PullStrategy::ALL
+                .into_iter()
+                .find(|s| s.label() == label)
+                .unwrap_or(suggested)
+        } else {
+            suggested
+        };
+
+        if current == Some(strategy) {
+            output.add_line(format!(
+                "🔀 Pull strategy: {} (unchanged)",
+                strategy.label()
+            ));
+            return Ok(());
+        }
+
+        for (key, value) in strategy.settings() {
+            match value {
+                Some(value) => Self::global_set(key, value)?,
+                // Unsetting a key that isn't set fails, which is fine here
+                None => {
+                    let _ = GitOperations::run(&["config", "--global", "--unset", key]);
+                }
+            }
+        }
+        output.add_line(format!("🔀 Pull strategy: {}", strategy.label()));
+        Ok(())
+    }
+
+    fn configure_aliases(&self, output: &mut BufferedOutput) -> Result<()> {
+        let mut available = Vec::new();
+        for (alias, command) in SUGGESTED_ALIASES {
+            match Self::global_get(&format!("alias.{alias}")) {
+                None => available.push((*alias, *command)),
+                Some(existing) if existing != *command => output.add_line(format!(
+                    "⚠️  Skipping alias '{alias}': already set to '{existing}'"
+                )),
+                Some(_) => {}
+            }
+        }
+
+        if available.is_empty() {
+            output.add_line("🔗 Aliases: already configured".to_string());
+            return Ok(());
+        }
+
+        let chosen: Vec<(&str, &str)> = if self.prompting() {
+            let labels: Vec<String> = available
+                .iter()
+                .map(|(alias, command)| format!("git {alias} → git {command}"))
+                .collect();
+            Interactive::multi_select(&labels, "Aliases to add (space to toggle)")?
+                .into_iter()
+                .map(|index| available[index])
+                .collect()
+        } else {
+            available
+        };
+
+        if chosen.is_empty() {
+            output.add_line("🔗 Aliases: none added".to_string());
+            return Ok(());
+        }
+
+        for (alias, command) in &chosen {
+            Self::global_set(&format!("alias.{alias}"), command)?;
+        }
+        output.add_line("🔗 Aliases added:".to_string());
+        for (alias, command) in &chosen {
+            output.add_line(format!("   • git {alias} → git {command}"));
+        }
+        Ok(())
+    }
+
+    fn install_completion(&self, output: &mut BufferedOutput) -> Result<()> {
+        let shell_path = std::env::var("SHELL").unwrap_or_default();
+        let Some(shell) = Self::detect_shell(&shell_path) else {
+            output.add_line(
+                "⌨️  Completion: shell not detected, run 'git x completion-install <shell>'"
+                    .to_string(),
+            );
+            return Ok(());
+        };
+
+        if self.prompting() && !Interactive::confirm(&format!("Install {shell} completion?"), true)?
+        {
+            output.add_line("⌨️  Completion: skipped".to_string());
+            return Ok(());
+        }
+
+        // A failed install shouldn't undo the rest of the setup
+        match CompletionInstallCommand::new(shell).execute() {
+            Ok(_) => output.add_line(format!("⌨️  Completion installed for {shell}")),
+            Err(e) => output.add_line(format!("⚠️  Completion not installed: {e}")),
+        }
+        Ok(())
+    }
+
+    fn save_settings(output: &mut BufferedOutput) -> Result<()> {
+        Config::set_global("setup.version", env!("CARGO_PKG_VERSION"))?;
+
+        // `--show-origin` prefixes each entry with `file:<path>`
+        let location = GitOperations::run(&["config", "--global", "--list", "--show-origin"])
+            .ok()
+            .and_then(|list| {
+                let origin = list.lines().next()?.split('\t').next()?.to_string();
+                origin.strip_prefix("file:").map(String::from)
+            })
+            .unwrap_or_else(|| "global git config".to_string());
+        output.add_line(format!("📝 Settings saved to {location}"));
+        Ok(())
+    }
+}
+
+impl Command for SetupCommand {
+    fn execute(&self) -> Result<String> {
+        let mut output = BufferedOutput::new();
+        output.add_line("🔧 git-x setup".to_string());
+        output.add_line("=".repeat(30));
+
+        Self::check_git_version(&mut output)?;
+        self.configure_identity(&mut output)?;
+        self.configure_default_branch(&mut output)?;
+        self.configure_pull_strategy(&mut output)?;
+        self.configure_aliases(&mut output)?;
+        self.install_completion(&mut output)?;
+        Self::save_settings(&mut output)?;
+
+        output.add_line("🎉 All set! Run 'git x --help' to see what's available.".to_string());
+        Ok(output.content())
+    }
+
+    fn name(&self) -> &'static str {
+        "setup"
+    }
+
+    fn description(&self) -> &'static str {
+        "Configure git and git-x for a new machine"
+    }
+}
+
+/// Git config file aliases are written to and removed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AliasScope {
+    /// The current repository's `.git/config`
+    Local,
+    /// The user's `~/.gitconfig`
+    Global,
+    /// The machine-wide config, usually needing root
+    System,
+}
+
+impl AliasScope {
+    pub fn flag(self) -> &'static str {
+        match self {
+            AliasScope::Local => "--local",
+            AliasScope::Global => "--global",
+            AliasScope::System => "--system",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AliasScope::Local => "local",
+            AliasScope::Global => "global",
+            AliasScope::System => "system",
+        }
+    }
+}
+
+/// An alias defined in git config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitAlias {
+    pub name: String,
+    pub command: String,
+    /// Scope reported by `git config --show-scope`, e.g. `global`
+    pub scope: String,
+}
+
+impl GitAlias {
+    /// Parse `git config --show-scope --get-regexp ^alias\.` output
+    pub fn parse_list(output: &str) -> Vec<GitAlias> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let (scope, entry) = line.split_once('\t')?;
+                let (key, command) = entry.split_once(' ').unwrap_or((entry, ""));
+                Some(GitAlias {
+                    name: key.strip_prefix("alias.")?.to_string(),
+                    command: command.to_string(),
+                    scope: scope.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Whether the alias runs a git-x command
+    pub fn runs_git_x(&self) -> bool {
+        let command = self.command.trim();
+        command == "x"
+            || command.starts_with("x ")
+            || command.starts_with("!git-x")
+            || command.starts_with("!git x ")
+    }
+}
+
+/// Alias actions
+#[derive(Debug, Clone)]
+pub enum AliasAction {
+    /// Add aliases: recommended names, or `name=command` for a git-x command;
+    /// every recommended alias when `aliases` is empty
+    Install {
+        aliases: Vec<String>,
+        scope: AliasScope,
+        force: bool,
+    },
+    /// Show aliases that run git-x, or every alias with `all`
+    List { all: bool },
+    /// Remove the named aliases, or every git-x alias in the scope
+    Remove {
+        names: Vec<String>,
+        scope: AliasScope,
+    },
+}
+
+/// Command that installs, lists and removes git aliases for git-x commands
+pub struct AliasCommand {
+    action: AliasAction,
+}
+
+impl AliasCommand {
+    pub fn new(action: AliasAction) -> Self {
+        Self { action }
+    }
+
+    /// Aliases across every scope, in the order git reads them
+    fn configured() -> Vec<GitAlias> {
+        // `--get-regexp` exits 1 when nothing matches
+        GitOperations::run(&["config", "--show-scope", "--get-regexp", "^alias\\."])
+            .map(|output| GitAlias::parse_list(&output))
+            .unwrap_or_default()
+    }
+
+    /// Turn `name` or `name=command` into the alias and the value to store
+    pub fn resolve(spec: &str) -> Result<(String, String)> {
+        if let Some((name, command)) = spec.split_once('=') {
+            let (name, command) = (name.trim(), command.trim());
+            if name.is_empty() || command.is_empty() {
+                return Err(GitXError::Parse(format!(
+                    "Expected 'name=command', got '{spec}'"
+                )));
+            }
+            let command = command
+                .strip_prefix("git-x ")
+                .or_else(|| command.strip_prefix("git x "))
+                .unwrap_or(command);
+            return Ok((name.to_string(), format!("x {command}")));
+        }
+
+        SUGGESTED_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == spec)
+            .map(|(alias, command)| (alias.to_string(), command.to_string()))
+            .ok_or_else(|| {
+                let known: Vec<&str> = SUGGESTED_ALIASES.iter().map(|(alias, _)| *alias).collect();
+                GitXError::Parse(format!(
+                    "'{spec}' is not a recommended alias (available: {}); use '{spec}=<command>' for your own",
+                    known.join(", ")
+                ))
+            })
+    }
+
+    fn install(aliases: &[String], scope: AliasScope, force: bool) -> Result<String> {
+        let wanted: Vec<(String, String)> = if aliases.is_empty() {
+            SUGGESTED_ALIASES
+                .iter()
+                .map(|(alias, command)| (alias.to_string(), command.to_string()))
+                .collect()
+        } else {
+            aliases
+                .iter()
+                .map(|spec| Self::resolve(spec))
+                .collect::<Result<_>>()?
+        };
+
+        let configured = Self::configured();
+        let mut output = BufferedOutput::new();
+        let mut installed = Vec::new();
+        for (name, command) in wanted {
+            // The last definition wins, as it does for git
+            let existing = configured.iter().rev().find(|alias| alias.name == name);
+            match existing {
+                Some(alias) if alias.command == command => output.add_line(format!(
+                    "✅ git {name} → git {command} (already in {} config)",
+                    alias.scope
+                )),
+                Some(alias) if !force => output.add_line(format!(
+                    "⚠️  Skipping '{name}': already set to '{}' in {} config (use --force to replace)",
+                    alias.command, alias.scope
+                )),
+                _ => {
+                    GitOperations::run(&[
+                        "config",
+                        scope.flag(),
+                        &format!("alias.{name}"),
+                        &command,
+                    ])?;
+                    installed.push(format!("   • git {name} → git {command}"));
+                }
+            }
+        }
+
+        if !installed.is_empty() {
+            output.add_line(format!(
+                "🔗 Installed {} alias(es) in {} config:",
+                installed.len(),
+                scope.label()
+            ));
+            for line in installed {
+                output.add_line(line);
+            }
+        }
+        Ok(output.content())
+    }
+
+    fn list(all: bool) -> String {
+        let configured = Self::configured();
+        let shown: Vec<&GitAlias> = configured
+            .iter()
+            .filter(|alias| all || alias.runs_git_x())
+            .collect();
+
+        let mut output = BufferedOutput::new();
+        if shown.is_empty() {
+            output.add_line(if all {
+                "🔗 No git aliases configured".to_string()
+            } else {
+                "🔗 No git-x aliases configured".to_string()
+            });
+        } else {
+            output.add_line(if all {
+                "🔗 Git aliases:".to_string()
+            } else {
+                "🔗 git-x aliases:".to_string()
+            });
+            let width = shown
+                .iter()
+                .map(|a| a.name.chars().count())
+                .max()
+                .unwrap_or(0);
+            for alias in shown {
+                output.add_line(format!(
+                    "   git {:<width$} → git {} ({})",
+                    alias.name, alias.command, alias.scope
+                ));
+            }
+        }
+
+        let missing: Vec<&str> = SUGGESTED_ALIASES
+            .iter()
+            .filter(|(name, _)| !configured.iter().any(|alias| alias.name == *name))
+            .map(|(name, _)| *name)
+            .collect();
+        if !missing.is_empty() {
+            output.add_line(format!(
+                "💡 Recommended but not installed: {} (run 'git x alias install')",
+                missing.join(", ")
+            ));
+        }
+        output.content()
+    }
+
+    fn remove(names: &[String], scope: AliasScope) -> Result<String> {
+        let in_scope: Vec<GitAlias> = Self::configured()
+            .into_iter()
+            .filter(|alias| alias.scope == scope.label())
+            .collect();
+
+        let mut targets: Vec<&GitAlias> = if names.is_empty() {
+            in_scope.iter().filter(|alias| alias.runs_git_x()).collect()
+        } else {
+            let missing: Vec<&str> = names
+                .iter()
+                .filter(|name| !in_scope.iter().any(|alias| &alias.name == *name))
+                .map(String::as_str)
+                .collect();
+            if !missing.is_empty() {
+                return Err(GitXError::GitCommand(format!(
+                    "No alias named {} in {} config",
+                    missing.join(", "),
+                    scope.label()
+                )));
+            }
+            in_scope
+                .iter()
+                .filter(|alias| names.contains(&alias.name))
+                .collect()
+        };
+        // Multi-valued aliases are listed once per value
+        targets.dedup_by(|a, b| a.name == b.name);
+
+        if targets.is_empty() {
+            return Ok(format!("✅ No git-x aliases in {} config", scope.label()));
+        }
+
+        let mut output = BufferedOutput::new();
+        output.add_line(format!(
+            "🗑️  Removed {} alias(es) from {} config:",
+            targets.len(),
+            scope.label()
+        ));
+        for alias in targets {
+            GitOperations::run(&[
+                "config",
+                scope.flag(),
+                "--unset-all",
+                &format!("alias.{}", alias.name),
+            ])?;
+            output.add_line(format!("   • git {} → git {}", alias.name, alias.command));
+        }
+        Ok(output.content())
+    }
+}
+
+impl Command for AliasCommand {
+    fn execute(&self) -> Result<String> {
+        match &self.action {
+            AliasAction::Install {
+                aliases,
+                scope,
+                force,
+            } => Self::install(aliases, *scope, *force),
+            AliasAction::List { all } => Ok(Self::list(*all)),
+            AliasAction::Remove { names, scope } => Self::remove(names, *scope),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "alias"
+    }
+
+    fn description(&self) -> &'static str {
+        "Install, list and remove git aliases for git-x commands"
+    }
+}
@@ -1,5 +1,7 @@
+use crate::core::diff_renderer::DiffRenderer;
 use crate::core::git::*;
 use crate::core::safety::Safety;
+use crate::core::stash_meta::StashMeta;
 use crate::core::traits::*;
 use crate::{GitXError, Result};
 
@@ -21,22 +23,34 @@ impl StashCommands {
         StashCommand::new(StashBranchAction::Clean {
             older_than,
             dry_run,
+            mine: false,
+            yes: false,
         })
         .execute()
     }
 
+    /// List stashes, or only the ones git-x created
+    pub fn list(mine: bool) -> Result<String> {
+        StashCommand::new(StashBranchAction::List { mine }).execute()
+    }
+
     /// Apply stashes by branch
     pub fn apply_by_branch(branch_name: String, list_only: bool) -> Result<String> {
         StashCommand::new(StashBranchAction::ApplyByBranch {
             branch_name,
             list_only,
+            pop: false,
+            newest_only: false,
         })
         .execute()
     }
 
     /// Interactive stash management
     pub fn interactive() -> Result<String> {
-        StashCommand::new(StashBranchAction::Interactive).execute()
+        StashCommand::new(StashBranchAction::Interactive {
+            sort: StashSort::Age,
+        })
+        .execute()
     }
 
     /// Export stashes to patch files
@@ -59,18 +73,59 @@ pub enum StashBranchAction {
     Clean {
         older_than: Option<String>,
         dry_run: bool,
+        /// Only stashes git-x created
+        mine: bool,
+        /// Skip the confirmation, including the typed one for many stashes
+        yes: bool,
+    },
+    List {
+        /// Only stashes git-x created
+        mine: bool,
     },
     ApplyByBranch {
         branch_name: String,
         list_only: bool,
+        /// Drop each stash once it applied cleanly
+        pop: bool,
+        /// Only the branch's most recent stash
+        newest_only: bool,
+    },
+    Interactive {
+        sort: StashSort,
     },
-    Interactive,
     Export {
         output_dir: String,
         stash_ref: Option<String>,
     },
 }
 
+/// Order of stashes in the interactive manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StashSort {
+    /// Newest first, as `git stash list` shows them
+    #[default]
+    Age,
+    /// Grouped by originating branch, newest first within a branch
+    Branch,
+}
+
+impl std::str::FromStr for StashSort {
+    type Err = GitXError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "age" => Ok(StashSort::Age),
+            "branch" => Ok(StashSort::Branch),
+            _ => Err(GitXError::Parse(format!(
+                "Unknown stash sort '{s}' (use age or branch)"
+            ))),
+        }
+    }
+}
+
+/// Stash list size above which the interactive manager asks for a filter first
+pub const DEFAULT_STASH_FILTER_THRESHOLD: usize = 15;
+
 /// Stash information structure
 #[derive(Debug, Clone)]
 pub struct StashInfo {
@@ -99,12 +154,17 @@ impl StashCommand {
             StashBranchAction::Clean {
                 older_than,
                 dry_run,
-            } => self.clean_old_stashes(older_than, *dry_run),
+                mine,
+                yes,
+            } => self.clean_old_stashes(older_than, *dry_run, *mine, *yes),
+            StashBranchAction::List { mine } => self.list_stashes(*mine),
             StashBranchAction::ApplyByBranch {
                 branch_name,
                 list_only,
-            } => self.apply_stashes_by_branch(branch_name, *list_only),
-            StashBranchAction::Interactive => self.interactive_stash_management(),
+                pop,
+                newest_only,
+            } => self.apply_stashes_by_branch(branch_name, *list_only, *pop, *newest_only),
+            StashBranchAction::Interactive { sort } => self.interactive_stash_management(*sort),
             StashBranchAction::Export {
                 output_dir,
                 stash_ref,
@@ -141,9 +201,18 @@ impl StashCommand {
         ))
     }
 
-    fn clean_old_stashes(&self, older_than: &Option<String>, dry_run: bool) -> Result<String> {
+    fn clean_old_stashes(
+        &self,
+        older_than: &Option<String>,
+        dry_run: bool,
+        mine: bool,
+        yes: bool,
+    ) -> Result<String> {
         // Get all stashes with timestamps
-        let stashes = self.get_stash_list_with_dates()?;
+        let mut stashes = self.get_stash_list_with_dates()?;
+        if mine {
+            stashes.retain(|stash| StashMeta::parse(&stash.message).is_some());
+        }
 
         if stashes.is_empty() {
             return Ok("ℹ️ No stashes found".to_string());
@@ -180,11 +249,22 @@ impl StashCommand {
                 stash_names.join(", ")
             );
 
-            let confirmed = Safety::confirm_destructive_operation("Clean old stashes", &details)?;
+            let confirmed = Safety::confirm_bulk_deletion(
+                "Clean old stashes",
+                &details,
+                count,
+                "stashes",
+                yes,
+            )?;
             if !confirmed {
                 return Ok("Operation cancelled by user.".to_string());
             }
 
+            // Drop the highest stash@{n} first so the remaining references stay valid
+            let mut stashes_to_clean = stashes_to_clean;
+            stashes_to_clean
+                .sort_by_key(|stash| std::cmp::Reverse(utils::stash_index(&stash.name)));
+
             let mut deleted_count = 0;
             for stash in &stashes_to_clean {
                 match self.delete_stash(&stash.name) {
@@ -200,59 +280,147 @@ impl StashCommand {
         Ok(result)
     }
 
-    fn apply_stashes_by_branch(&self, branch_name: &str, list_only: bool) -> Result<String> {
+    fn list_stashes(&self, mine: bool) -> Result<String> {
+        let stashes: Vec<StashInfo> =
+            GitOperations::run(&["stash", "list", "--pretty=format:%gd|%s|%ct"])?
+                .lines()
+                .filter_map(|line| self.parse_stash_line_with_date(line))
+                .filter(|stash| !mine || StashMeta::parse(&stash.message).is_some())
+                .collect();
+
+        if stashes.is_empty() {
+            return Ok(if mine {
+                "📝 No stashes created by git-x".to_string()
+            } else {
+                "📝 No stashes found".to_string()
+            });
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut result = if mine {
+            format!("📦 {} stash(es) created by git-x:\n", stashes.len())
+        } else {
+            format!("📦 {} stash(es):\n", stashes.len())
+        };
+        for stash in &stashes {
+            result.push_str(&format!("  {}\n", utils::format_stash_listing(stash, now)));
+        }
+        Ok(result.trim_end().to_string())
+    }
+
+    fn apply_stashes_by_branch(
+        &self,
+        branch_name: &str,
+        list_only: bool,
+        pop: bool,
+        newest_only: bool,
+    ) -> Result<String> {
         // Get all stashes with their branch information
         let stashes = self.get_stash_list_with_branches()?;
 
-        // Filter stashes by branch
-        let branch_stashes: Vec<_> = stashes
+        // Filter stashes by branch; `git stash list` puts the newest first
+        let mut branch_stashes: Vec<_> = stashes
             .into_iter()
             .filter(|s| s.branch == branch_name)
             .collect();
+        if newest_only {
+            branch_stashes.truncate(1);
+        }
 
         if branch_stashes.is_empty() {
             return Ok(format!("No stashes found for branch '{branch_name}'"));
         }
 
         let count = branch_stashes.len();
-        let mut result = if list_only {
-            format!("📋 Found {count} stash(es) for branch '{branch_name}':\n")
-        } else {
-            format!("🔄 Applying {count} stash(es) from branch '{branch_name}':\n")
-        };
-
-        for stash in &branch_stashes {
-            if list_only {
+        if list_only {
+            let mut result = format!("📋 Found {count} stash(es) for branch '{branch_name}':\n");
+            for stash in &branch_stashes {
                 result.push_str(&format!("  {}: {}\n", stash.name, stash.message));
-            } else {
-                match self.apply_stash(&stash.name) {
-                    Ok(()) => result.push_str(&format!("  ✅ Applied {}\n", stash.name)),
-                    Err(e) => {
-                        result.push_str(&format!("  ❌ Failed to apply {}: {}\n", stash.name, e))
-                    }
-                }
             }
+            return Ok(result);
+        }
+
+        // Oldest first: that's the order the work was done in, and popping a stash
+        // only renumbers the older ones, which are already applied
+        branch_stashes.reverse();
+        let verb = if pop { "Popping" } else { "Applying" };
+        let mut result = format!("🔄 {verb} {count} stash(es) from branch '{branch_name}':\n");
+        for (index, stash) in branch_stashes.iter().enumerate() {
+            if let Err(e) = self.apply_stash(&stash.name, pop) {
+                let applied: Vec<&str> = branch_stashes[..index]
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect();
+                let pending: Vec<&str> = branch_stashes[index + 1..]
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect();
+                let conflicts = GitOperations::run(&["diff", "--name-only", "--diff-filter=U"])
+                    .unwrap_or_default();
+                let conflicts: Vec<&str> = conflicts.lines().collect();
+                return Err(utils::apply_failure(
+                    stash, pop, &e, &conflicts, &applied, &pending,
+                ));
+            }
+            let done = if pop { "Popped" } else { "Applied" };
+            result.push_str(&format!("  ✅ {done} {}: {}\n", stash.name, stash.message));
         }
 
         Ok(result)
     }
 
-    fn interactive_stash_management(&self) -> Result<String> {
+    fn interactive_stash_management(&self, sort: StashSort) -> Result<String> {
+        use crate::core::config::Config;
+        use crate::core::interactive::Interactive;
         use dialoguer::{MultiSelect, Select, theme::ColorfulTheme};
 
-        // Get all stashes
-        let stashes = self.get_stash_list_with_branches()?;
+        // Get all stashes, with their creation time as a unix timestamp
+        let mut stashes: Vec<StashInfo> =
+            GitOperations::run(&["stash", "list", "--pretty=format:%gd|%s|%ct"])?
+                .lines()
+                .filter_map(|line| self.parse_stash_line_with_date(line))
+                .collect();
 
         if stashes.is_empty() {
             return Ok("📝 No stashes found".to_string());
         }
 
-        // Create display items for selection
-        let stash_display: Vec<String> = stashes
+        let now = chrono::Utc::now().timestamp();
+        utils::sort_stashes(&mut stashes, sort);
+        let mut stash_display: Vec<String> = stashes
             .iter()
-            .map(|s| format!("{}: {} (from {})", s.name, s.message, s.branch))
+            .map(|s| utils::format_stash_entry(s, now))
             .collect();
 
+        // Long lists get a fuzzy filter before the menus
+        let threshold = Config::get("stash.filterThreshold")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_STASH_FILTER_THRESHOLD);
+        if stashes.len() > threshold && Interactive::is_interactive() {
+            let query = Interactive::text_input(
+                &format!(
+                    "🔎 {} stashes, filter by message, branch or age (empty for all)",
+                    stashes.len()
+                ),
+                Some(""),
+                None,
+            )?;
+            if !query.trim().is_empty() {
+                let mut matched: Vec<usize> =
+                    Interactive::fuzzy_find(&stash_display, query.trim(), None)
+                        .into_iter()
+                        .map(|(index, _)| index)
+                        .collect();
+                if matched.is_empty() {
+                    return Ok(format!("🔍 No stashes match '{}'", query.trim()));
+                }
+                // Keep the chosen sort order rather than match score
+                matched.sort_unstable();
+                stashes = matched.iter().map(|&i| stashes[i].clone()).collect();
+                stash_display = matched.iter().map(|&i| stash_display[i].clone()).collect();
+            }
+        }
+
         // Action selection menu
         let actions = vec![
             "Apply selected stash",
@@ -278,7 +446,7 @@ impl StashCommand {
                     .items(&stash_display)
                     .interact()?;
 
-                self.apply_stash(&stashes[selection].name)?;
+                self.apply_stash(&stashes[selection].name, false)?;
                 Ok(format!("✅ Applied stash: {}", stashes[selection].name))
             }
             Ok(1) => {
@@ -294,10 +462,14 @@ impl StashCommand {
                     return Ok("No stashes selected for deletion".to_string());
                 }
 
+                // Drop the highest stash@{n} first so the remaining references stay valid
+                let mut selected: Vec<&StashInfo> =
+                    selections.iter().map(|&idx| &stashes[idx]).collect();
+                selected.sort_by_key(|stash| std::cmp::Reverse(utils::stash_index(&stash.name)));
+
                 let mut deleted_count = 0;
-                for &idx in selections.iter().rev() {
-                    // Delete in reverse order to maintain indices
-                    if self.delete_stash(&stashes[idx].name).is_ok() {
+                for stash in selected {
+                    if self.delete_stash(&stash.name).is_ok() {
                         deleted_count += 1;
                     }
                 }
@@ -336,7 +508,12 @@ impl StashCommand {
                     .items(&stash_display)
                     .interact()?;
 
-                let diff = GitOperations::run(&["stash", "show", "-p", &stashes[selection].name])?;
+                let diff = DiffRenderer::configured().render(&[
+                    "stash",
+                    "show",
+                    "-p",
+                    &stashes[selection].name,
+                ])?;
                 Ok(format!(
                     "📊 Diff for {}:\n{}",
                     stashes[selection].name, diff
@@ -345,11 +522,8 @@ impl StashCommand {
             Ok(4) => {
                 // List all stashes
                 let mut result = "📝 All stashes:\n".to_string();
-                for stash in &stashes {
-                    result.push_str(&format!(
-                        "  {}: {} (from {})\n",
-                        stash.name, stash.message, stash.branch
-                    ));
+                for entry in &stash_display {
+                    result.push_str(&format!("  {entry}\n"));
                 }
                 Ok(result)
             }
@@ -549,8 +723,11 @@ impl StashCommand {
         GitOperations::run_status(&["stash", "drop", stash_name])
     }
 
-    fn apply_stash(&self, stash_name: &str) -> Result<()> {
-        GitOperations::run_status(&["stash", "apply", stash_name])
+    /// `git stash pop` keeps the stash when it conflicts, like `apply` does
+    fn apply_stash(&self, stash_name: &str, pop: bool) -> Result<()> {
+        let action = if pop { "pop" } else { "apply" };
+        crate::core::checkpoints::FileCheckpoints::before(&format!("stash {action}"));
+        GitOperations::run_status(&["stash", action, stash_name])
     }
 }
 
@@ -582,13 +759,19 @@ impl Destructive for StashCommand {
             StashBranchAction::Clean { dry_run: false, .. } => {
                 "This will permanently delete the selected stashes".to_string()
             }
+            StashBranchAction::List { .. } => "This will only list stashes".to_string(),
             StashBranchAction::ApplyByBranch {
                 list_only: true, ..
             } => "This will only list stashes without applying them".to_string(),
+            StashBranchAction::ApplyByBranch {
+                list_only: false,
+                pop: true,
+                ..
+            } => "This will apply stashes to your working directory and drop them".to_string(),
             StashBranchAction::ApplyByBranch {
                 list_only: false, ..
             } => "This will apply stashes to your working directory".to_string(),
-            StashBranchAction::Interactive => {
+            StashBranchAction::Interactive { .. } => {
                 "Interactive stash management - actions will be confirmed individually".to_string()
             }
             StashBranchAction::Export { .. } => {
@@ -600,10 +783,49 @@ impl Destructive for StashCommand {
 
 // Public utility functions for testing and external use
 pub mod utils {
-    use super::StashInfo;
+    use super::{StashInfo, StashMeta, StashSort};
     use crate::core::git::GitOperations;
     use crate::{GitXError, Result};
 
+    /// Why `apply-by-branch` stopped at `stash`, how to resolve it and which stashes
+    /// were handled before it or are still waiting
+    pub fn apply_failure(
+        stash: &StashInfo,
+        popped: bool,
+        error: &GitXError,
+        conflicts: &[&str],
+        applied: &[&str],
+        pending: &[&str],
+    ) -> GitXError {
+        let mut message = if conflicts.is_empty() {
+            format!(
+                "Failed to apply {} ({}): {error}",
+                stash.name, stash.message
+            )
+        } else {
+            format!(
+                "{} ({}) conflicts in {}. Resolve the conflicts and 'git add' the files",
+                stash.name,
+                stash.message,
+                conflicts.join(", ")
+            )
+        };
+        if popped && !conflicts.is_empty() {
+            message.push_str(&format!(
+                "; the stash was kept, drop it with 'git stash drop {}' once you're done",
+                stash.name
+            ));
+        }
+        if !applied.is_empty() {
+            let done = if popped { "Popped" } else { "Applied" };
+            message.push_str(&format!("\n   {done} before it: {}", applied.join(", ")));
+        }
+        if !pending.is_empty() {
+            message.push_str(&format!("\n   Not applied yet: {}", pending.join(", ")));
+        }
+        GitXError::GitCommand(message)
+    }
+
     pub fn validate_branch_name(name: &str) -> Result<()> {
         if name.is_empty() {
             return Err(GitXError::GitCommand(
@@ -701,6 +923,75 @@ pub mod utils {
         }
     }
 
+    /// Numeric index of a `stash@{n}` reference
+    pub fn stash_index(name: &str) -> Option<usize> {
+        name.strip_prefix("stash@{")?
+            .strip_suffix('}')?
+            .parse()
+            .ok()
+    }
+
+    /// Human-readable age of a unix timestamp relative to `now`
+    pub fn relative_age(timestamp: i64, now: i64) -> String {
+        let seconds = (now - timestamp).max(0);
+        let (value, unit) = match seconds {
+            s if s < 60 => return "just now".to_string(),
+            s if s < 3_600 => (s / 60, "minute"),
+            s if s < 86_400 => (s / 3_600, "hour"),
+            s if s < 86_400 * 7 => (s / 86_400, "day"),
+            s if s < 86_400 * 30 => (s / (86_400 * 7), "week"),
+            s if s < 86_400 * 365 => (s / (86_400 * 30), "month"),
+            s => (s / (86_400 * 365), "year"),
+        };
+        let plural = if value == 1 { "" } else { "s" };
+        format!("{value} {unit}{plural} ago")
+    }
+
+    /// Sort stashes whose `timestamp` holds a unix time
+    pub fn sort_stashes(stashes: &mut [StashInfo], sort: StashSort) {
+        let time = |stash: &StashInfo| stash.timestamp.parse::<i64>().unwrap_or(0);
+        match sort {
+            StashSort::Age => stashes.sort_by_key(|s| std::cmp::Reverse(time(s))),
+            StashSort::Branch => {
+                stashes.sort_by(|a, b| a.branch.cmp(&b.branch).then_with(|| time(b).cmp(&time(a))))
+            }
+        }
+    }
+
+    /// One line for the interactive list: reference, age, branch and message
+    pub fn format_stash_entry(stash: &StashInfo, now: i64) -> String {
+        let age = stash
+            .timestamp
+            .parse::<i64>()
+            .map(|time| relative_age(time, now))
+            .unwrap_or_else(|_| "unknown age".to_string());
+        format!(
+            "{}  {age}  [{}]  {}",
+            stash.name, stash.branch, stash.message
+        )
+    }
+
+    /// One line for `list`, naming the operation and version behind stashes git-x created
+    pub fn format_stash_listing(stash: &StashInfo, now: i64) -> String {
+        let Some(meta) = StashMeta::parse(&stash.message) else {
+            return format_stash_entry(stash, now);
+        };
+        let age = stash
+            .timestamp
+            .parse::<i64>()
+            .map(|time| relative_age(time, now))
+            .unwrap_or_else(|_| "unknown age".to_string());
+        let message = StashMeta::strip(&stash.message);
+        // git prefixes `-m` messages with the branch, which the metadata already names
+        let message = message
+            .split_once(": ")
+            .map_or(message, |(_, description)| description);
+        format!(
+            "{}  {age}  [{}]  🤖 {} (git-x {})  {message}",
+            stash.name, meta.branch, meta.operation, meta.version
+        )
+    }
+
     pub fn format_applying_stashes_message(branch_name: &str, count: usize) -> String {
         format!("🔄 Applying {count} stash(es) from branch '{branch_name}':")
     }
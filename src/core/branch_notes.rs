@@ -0,0 +1,135 @@
+use crate::core::git::GitOperations;
+use crate::{GitXError, Result};
+use chrono::{Local, NaiveDate, TimeZone};
+
+/// Config variables a note is kept in, next to git's own `branch.<name>.description`
+const MESSAGE: &str = "gitxnote";
+const CREATED: &str = "gitxnotecreated";
+const EXPIRES: &str = "gitxnoteexpires";
+
+/// A reminder attached to a branch, shown whenever git-x switches to it
+///
+/// Kept in the branch's config section, so `git branch -m` carries it along and
+/// `git branch -d` removes it with the branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchNote {
+    pub branch: String,
+    pub message: String,
+    /// Unix time the note was written
+    pub created: i64,
+    /// Unix time after which the note is no longer shown
+    pub expires: Option<i64>,
+}
+
+impl BranchNote {
+    pub fn new(branch: &str, message: &str, expires: Option<i64>) -> Self {
+        Self {
+            branch: branch.to_string(),
+            message: message.to_string(),
+            created: chrono::Utc::now().timestamp(),
+            expires,
+        }
+    }
+
+    /// The note on `branch`, expired or not
+    pub fn get(branch: &str) -> Option<Self> {
+        let read =
+            |variable: &str| GitOperations::run(&["config", "--get", &key(branch, variable)]).ok();
+        let message = read(MESSAGE)?;
+        Some(Self {
+            branch: branch.to_string(),
+            message,
+            created: read(CREATED).and_then(|v| v.parse().ok()).unwrap_or(0),
+            expires: read(EXPIRES).and_then(|v| v.parse().ok()),
+        })
+    }
+
+    /// Every branch note, oldest first
+    pub fn all() -> Result<Vec<Self>> {
+        // -z keeps multi-line messages apart: each entry is `key\nvalue\0`
+        let output = GitOperations::run(&[
+            "config",
+            "-z",
+            "--get-regexp",
+            &format!(r"^branch\..*\.{MESSAGE}$"),
+        ])
+        .unwrap_or_default();
+        let mut notes: Vec<Self> = output
+            .split('\0')
+            .filter_map(|entry| entry.split_once('\n'))
+            .filter_map(|(key, _)| {
+                let branch = key
+                    .strip_prefix("branch.")?
+                    .strip_suffix(&format!(".{MESSAGE}"))?;
+                Self::get(branch)
+            })
+            .collect();
+        notes.sort_by_key(|note| note.created);
+        Ok(notes)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        // Fails when there was no expiry to replace, which is fine
+        let _ = GitOperations::run(&["config", "--unset", &key(&self.branch, EXPIRES)]);
+        GitOperations::run(&["config", &key(&self.branch, MESSAGE), &self.message])?;
+        GitOperations::run(&[
+            "config",
+            &key(&self.branch, CREATED),
+            &self.created.to_string(),
+        ])?;
+        if let Some(expires) = self.expires {
+            GitOperations::run(&["config", &key(&self.branch, EXPIRES), &expires.to_string()])?;
+        }
+        Ok(())
+    }
+
+    /// Remove the note on `branch`; false when it had none
+    pub fn clear(branch: &str) -> bool {
+        let removed = GitOperations::run(&["config", "--unset", &key(branch, MESSAGE)]).is_ok();
+        for variable in [CREATED, EXPIRES] {
+            let _ = GitOperations::run(&["config", "--unset", &key(branch, variable)]);
+        }
+        removed
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Unix time a note expires at: `<n>h`, `<n>d` or `<n>w` from `now`, or the end of a
+    /// `YYYY-MM-DD` day in local time
+    pub fn parse_expiry(value: &str, now: i64) -> Result<i64> {
+        let value = value.trim();
+        if let Ok(day) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            let end = day.and_hms_opt(23, 59, 59).expect("valid time of day");
+            return Local
+                .from_local_datetime(&end)
+                .earliest()
+                .map(|time| time.timestamp())
+                .ok_or_else(|| GitXError::Parse(format!("'{value}' isn't a local date")));
+        }
+
+        let invalid = || {
+            GitXError::Parse(format!(
+                "Invalid expiry '{value}': use a duration like 12h, 3d or 2w, or a date like 2026-11-01"
+            ))
+        };
+        let (split, _) = value.char_indices().last().ok_or_else(invalid)?;
+        let (count, unit) = value.split_at(split);
+        let count: i64 = count.parse().map_err(|_| invalid())?;
+        let seconds = match unit {
+            "h" => 3_600,
+            "d" => 86_400,
+            "w" => 86_400 * 7,
+            _ => return Err(invalid()),
+        };
+        if count <= 0 {
+            return Err(invalid());
+        }
+        Ok(now + count * seconds)
+    }
+}
+
+fn key(branch: &str, variable: &str) -> String {
+    format!("branch.{branch}.{variable}")
+}
@@ -0,0 +1,78 @@
+use crate::core::config::Config;
+use crate::{GitXError, Result};
+use std::process::{Command, Stdio};
+
+/// The system web browser, reached through the platform's URL opener
+///
+/// `git-x.browser` names a command to use instead, given the URL as its argument;
+/// `$BROWSER` is used when the setting is unset.
+pub struct Browser;
+
+impl Browser {
+    pub fn open(url: &str) -> Result<()> {
+        let configured = Config::get("browser")
+            .or_else(|| std::env::var("BROWSER").ok())
+            .filter(|command| !command.trim().is_empty());
+        if let Some(command) = configured {
+            return Self::run(&shell(&command, url))
+                .map_err(|e| GitXError::Other(format!("Browser command '{command}' failed: {e}")));
+        }
+
+        let opener = Self::opener(url);
+        Self::run(&opener).map_err(|e| {
+            GitXError::Other(format!(
+                "Couldn't start {} ({e}); set git-x.browser to a command that opens URLs",
+                opener[0]
+            ))
+        })
+    }
+
+    /// The platform's opener for `url`
+    fn opener(url: &str) -> Vec<String> {
+        let tool = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+        if cfg!(target_os = "macos") {
+            return tool(&["open", url]);
+        }
+        // `cmd /C start` would split the URL on `&`
+        if cfg!(windows) {
+            return tool(&["rundll32", "url.dll,FileProtocolHandler", url]);
+        }
+        // WSL reaches the Windows browser
+        if std::env::var_os("WSL_DISTRO_NAME").is_some() {
+            return tool(&["wslview", url]);
+        }
+        tool(&["xdg-open", url])
+    }
+
+    fn run(tool: &[String]) -> std::io::Result<()> {
+        let status = Command::new(&tool[0])
+            .args(&tool[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(std::io::Error::other(status.to_string()))
+        }
+    }
+}
+
+/// Run a configured command through the platform shell, passing the URL as its last argument
+fn shell(command: &str, url: &str) -> Vec<String> {
+    if cfg!(windows) {
+        return vec![
+            "cmd".to_string(),
+            "/C".to_string(),
+            format!("{command} \"{url}\""),
+        ];
+    }
+    vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        format!("{command} \"$1\""),
+        "git-x".to_string(),
+        url.to_string(),
+    ]
+}
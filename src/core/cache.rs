@@ -0,0 +1,46 @@
+use crate::Result;
+use crate::core::export::ExportValue;
+use std::path::PathBuf;
+
+/// Results of expensive scans, each kept for the commit it was computed at
+///
+/// Stored as JSON under `.git/git-x/cache`, one file per scan. The key is usually the
+/// commit, plus whatever else the scan reads. Storing a result under a new key replaces
+/// the old one, so the cache holds at most one entry per scan.
+pub struct CommitCache {
+    dir: PathBuf,
+}
+
+impl CommitCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Cache of the current repository
+    pub fn open() -> Result<Self> {
+        let git_dir = crate::core::validation::Validate::git_dir()?;
+        Ok(Self::new(git_dir.join("git-x").join("cache")))
+    }
+
+    fn path(&self, scan: &str) -> PathBuf {
+        self.dir.join(format!("{scan}.json"))
+    }
+
+    /// The stored result of `scan`, if it was stored under `key`
+    pub fn get(&self, scan: &str, key: &str) -> Option<ExportValue> {
+        let stored = std::fs::read_to_string(self.path(scan)).ok()?;
+        let stored = ExportValue::parse_json(&stored).ok()?;
+        if stored.get("key")?.as_str()? != key {
+            return None;
+        }
+        stored.get("result").cloned()
+    }
+
+    /// Store the result of `scan` under `key`
+    pub fn put(&self, scan: &str, key: &str, result: ExportValue) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let stored = ExportValue::record(vec![("key", ExportValue::from(key)), ("result", result)]);
+        std::fs::write(self.path(scan), stored.to_json())?;
+        Ok(())
+    }
+}
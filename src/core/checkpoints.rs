@@ -0,0 +1,227 @@
+use crate::core::git::GitOperations;
+use crate::{GitXError, Result};
+use std::path::{Path, PathBuf};
+
+/// Checkpoints kept per file; older ones are dropped as new ones are recorded
+pub const MAX_CHECKPOINTS_PER_FILE: usize = 20;
+
+/// A saved copy of one file, taken before git-x changed the working tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCheckpoint {
+    pub id: u64,
+    /// Unix time the copy was taken
+    pub time: i64,
+    /// The git-x operation that was about to run, e.g. `sync`
+    pub operation: String,
+    /// Path relative to the repository root
+    pub path: String,
+}
+
+impl FileCheckpoint {
+    /// Parse an index line: `id<TAB>time<TAB>operation<TAB>path`
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        Some(Self {
+            id: fields.next()?.parse().ok()?,
+            time: fields.next()?.parse().ok()?,
+            operation: fields.next()?.to_string(),
+            path: fields.next()?.to_string(),
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.id, self.time, self.operation, self.path
+        )
+    }
+
+    /// Local time the copy was taken, e.g. `2025-07-01 14:03`
+    pub fn when(&self) -> String {
+        chrono::DateTime::from_timestamp(self.time, 0)
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Per-file copies of uncommitted changes, stored under `.git/git-x/checkpoints`
+///
+/// Committed content is already in history; these copies cover the changes
+/// that never were, so they can be brought back after an operation clobbers them.
+pub struct FileCheckpoints {
+    dir: PathBuf,
+}
+
+impl FileCheckpoints {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Checkpoint store of the current repository
+    pub fn open() -> Result<Self> {
+        let git_dir = crate::core::validation::Validate::git_dir()?;
+        Ok(Self::new(git_dir.join("git-x").join("checkpoints")))
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.dir.join("index")
+    }
+
+    fn content_path(&self, id: u64) -> PathBuf {
+        self.dir.join(id.to_string())
+    }
+
+    /// Every checkpoint, oldest first
+    pub fn list(&self) -> Result<Vec<FileCheckpoint>> {
+        match std::fs::read_to_string(self.index_path()) {
+            Ok(index) => Ok(index.lines().filter_map(FileCheckpoint::parse).collect()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Checkpoints of one file, newest first
+    pub fn history(&self, path: &str) -> Result<Vec<FileCheckpoint>> {
+        let mut history: Vec<FileCheckpoint> = self
+            .list()?
+            .into_iter()
+            .filter(|checkpoint| checkpoint.path == path)
+            .collect();
+        history.reverse();
+        Ok(history)
+    }
+
+    pub fn get(&self, id: u64) -> Result<FileCheckpoint> {
+        self.list()?
+            .into_iter()
+            .find(|checkpoint| checkpoint.id == id)
+            .ok_or_else(|| GitXError::Other(format!("No checkpoint #{id}")))
+    }
+
+    pub fn content(&self, checkpoint: &FileCheckpoint) -> Result<Vec<u8>> {
+        Ok(std::fs::read(self.content_path(checkpoint.id))?)
+    }
+
+    /// Copy each file under `root` into a new checkpoint
+    ///
+    /// Missing files and files identical to their latest checkpoint are
+    /// skipped. Returns the checkpoints that were written.
+    pub fn record(
+        &self,
+        root: &Path,
+        paths: &[String],
+        operation: &str,
+    ) -> Result<Vec<FileCheckpoint>> {
+        let mut checkpoints = self.list()?;
+        let mut next_id = checkpoints.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+        let time = chrono::Utc::now().timestamp();
+        let operation = operation.replace(['\t', '\n'], " ");
+        let mut recorded = Vec::new();
+
+        for path in paths {
+            let Ok(content) = std::fs::read(root.join(path)) else {
+                continue;
+            };
+            let latest = checkpoints.iter().rev().find(|c| &c.path == path);
+            if latest.is_some_and(|latest| self.content(latest).is_ok_and(|saved| saved == content))
+            {
+                continue;
+            }
+
+            std::fs::create_dir_all(&self.dir)?;
+            std::fs::write(self.content_path(next_id), &content)?;
+            let checkpoint = FileCheckpoint {
+                id: next_id,
+                time,
+                operation: operation.clone(),
+                path: path.clone(),
+            };
+            next_id += 1;
+            checkpoints.push(checkpoint.clone());
+            recorded.push(checkpoint);
+        }
+
+        if !recorded.is_empty() {
+            self.save(self.prune(checkpoints))?;
+        }
+        Ok(recorded)
+    }
+
+    /// Drop all but the newest [`MAX_CHECKPOINTS_PER_FILE`] checkpoints of each file
+    fn prune(&self, checkpoints: Vec<FileCheckpoint>) -> Vec<FileCheckpoint> {
+        let mut kept = Vec::with_capacity(checkpoints.len());
+        for (index, checkpoint) in checkpoints.iter().enumerate() {
+            let newer = checkpoints[index + 1..]
+                .iter()
+                .filter(|c| c.path == checkpoint.path)
+                .count();
+            if newer < MAX_CHECKPOINTS_PER_FILE {
+                kept.push(checkpoint.clone());
+            } else {
+                let _ = std::fs::remove_file(self.content_path(checkpoint.id));
+            }
+        }
+        kept
+    }
+
+    fn save(&self, checkpoints: Vec<FileCheckpoint>) -> Result<()> {
+        let index: String = checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.to_line() + "\n")
+            .collect();
+        std::fs::write(self.index_path(), index)?;
+        Ok(())
+    }
+
+    /// Write a checkpoint back to its file under `root`, checkpointing what it replaces
+    ///
+    /// Returns the checkpoint of the replaced content, if it differed.
+    pub fn restore(
+        &self,
+        root: &Path,
+        id: u64,
+    ) -> Result<(FileCheckpoint, Option<FileCheckpoint>)> {
+        let checkpoint = self.get(id)?;
+        let content = self.content(&checkpoint)?;
+        let replaced = self
+            .record(
+                root,
+                std::slice::from_ref(&checkpoint.path),
+                &format!("before undo-file --restore {id}"),
+            )?
+            .pop();
+
+        let target = root.join(&checkpoint.path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(target, content)?;
+        Ok((checkpoint, replaced))
+    }
+
+    /// Checkpoint every tracked file with uncommitted changes before `operation`
+    ///
+    /// Best effort: a failure here never stops the operation itself.
+    pub fn before(operation: &str) {
+        let record = || -> Result<()> {
+            // Staged and unstaged changes alike, relative to the repository root
+            let diff = GitOperations::run(&["diff", "--name-only", "-z", "HEAD"])?;
+            let changed: Vec<String> = diff
+                .split('\0')
+                .filter(|path| !path.is_empty())
+                .map(String::from)
+                .collect();
+            if changed.is_empty() {
+                return Ok(());
+            }
+            let root = PathBuf::from(GitOperations::repo_root()?);
+            Self::open()?.record(&root, &changed, &format!("before {operation}"))?;
+            Ok(())
+        };
+        let _ = record();
+    }
+}
@@ -0,0 +1,377 @@
+use crate::core::config::Config;
+use crate::core::export::ExportValue;
+use crate::core::git::GitOperations;
+use crate::core::network::Network;
+use crate::{GitXError, Result};
+
+/// Where the repository's CI runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CiProvider {
+    /// GitHub Actions, queried through the GitHub CLI (`gh`)
+    GitHub,
+    /// GitLab CI, queried through the REST API with a personal access token
+    GitLab(GitLabProject),
+}
+
+impl CiProvider {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CiProvider::GitHub => "GitHub Actions",
+            CiProvider::GitLab(_) => "GitLab CI",
+        }
+    }
+}
+
+/// GitLab project coordinates for API requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitLabProject {
+    /// API root, e.g. `https://gitlab.com/api/v4`
+    pub api_url: String,
+    /// Project path, e.g. `group/subgroup/project`
+    pub path: String,
+    pub token: String,
+}
+
+impl GitLabProject {
+    /// URL for a project endpoint such as `pipelines?ref=main`
+    pub fn endpoint(&self, path: &str) -> String {
+        format!("{}/projects/{}/{path}", self.api_url, encode(&self.path))
+    }
+}
+
+/// Outcome of a CI run or job, normalised across providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiState {
+    Queued,
+    Running,
+    Success,
+    Failed,
+    Cancelled,
+    Skipped,
+}
+
+impl CiState {
+    /// State from a GitHub Actions `status` and `conclusion` pair
+    pub fn from_github(status: &str, conclusion: &str) -> Self {
+        match status {
+            "completed" => match conclusion {
+                "success" | "neutral" => CiState::Success,
+                "cancelled" => CiState::Cancelled,
+                "skipped" => CiState::Skipped,
+                _ => CiState::Failed,
+            },
+            "in_progress" => CiState::Running,
+            _ => CiState::Queued,
+        }
+    }
+
+    /// State from a GitLab pipeline or job `status`
+    pub fn from_gitlab(status: &str) -> Self {
+        match status {
+            "success" => CiState::Success,
+            "failed" => CiState::Failed,
+            "canceled" => CiState::Cancelled,
+            "skipped" | "manual" => CiState::Skipped,
+            "running" => CiState::Running,
+            _ => CiState::Queued,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, CiState::Queued | CiState::Running)
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            CiState::Queued => "⏸️ ",
+            CiState::Running => "⏳",
+            CiState::Success => "✅",
+            CiState::Failed => "❌",
+            CiState::Cancelled => "🚫",
+            CiState::Skipped => "⏭️ ",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CiState::Queued => "queued",
+            CiState::Running => "running",
+            CiState::Success => "passed",
+            CiState::Failed => "failed",
+            CiState::Cancelled => "cancelled",
+            CiState::Skipped => "skipped",
+        }
+    }
+}
+
+/// A single job within a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiJob {
+    pub name: String,
+    pub state: CiState,
+}
+
+/// A workflow run (GitHub) or pipeline (GitLab)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiRun {
+    pub id: String,
+    pub name: String,
+    pub sha: String,
+    pub state: CiState,
+    pub url: String,
+    pub jobs: Vec<CiJob>,
+}
+
+/// Percent-encode a value for use in a URL path or query
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+fn text(value: &ExportValue, key: &str) -> String {
+    match value.get(key) {
+        Some(ExportValue::Text(s)) => s.clone(),
+        Some(ExportValue::Int(i)) => i.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// CI status lookups for GitHub Actions and GitLab CI
+pub struct Ci;
+
+impl Ci {
+    /// Environment variable checked when `git-x.ci.gitlabToken` is unset
+    pub const GITLAB_TOKEN_ENV: &'static str = "GITLAB_TOKEN";
+
+    /// Pick a provider from `git-x.ci.provider`, the `origin` remote and the tools available
+    pub fn detect() -> Result<CiProvider> {
+        let remote = GitOperations::run(&["remote", "get-url", "origin"]).ok();
+        let host_path = remote.as_deref().and_then(Self::parse_remote);
+        let forced = Config::get("ci.provider").map(|p| p.to_lowercase());
+        let on_gitlab = match forced.as_deref() {
+            Some("gitlab") => true,
+            Some("github") => false,
+            Some(other) => {
+                return Err(GitXError::Other(format!(
+                    "Unknown CI provider '{other}' in git-x.ci.provider (use github or gitlab)"
+                )));
+            }
+            None => host_path
+                .as_ref()
+                .is_some_and(|(host, _)| host.contains("gitlab")),
+        };
+
+        if on_gitlab {
+            let token = Config::get("ci.gitlabToken")
+                .or_else(|| std::env::var(Self::GITLAB_TOKEN_ENV).ok())
+                .filter(|token| !token.is_empty())
+                .ok_or_else(|| {
+                    GitXError::Other(
+                        "GitLab CI needs an API token: git config git-x.ci.gitlabToken <token> (or set GITLAB_TOKEN)"
+                            .to_string(),
+                    )
+                })?;
+            let (host, path) = host_path.ok_or_else(|| {
+                GitXError::Other(
+                    "Could not read the GitLab project from remote 'origin'".to_string(),
+                )
+            })?;
+            let api_url = Config::get("ci.gitlabUrl")
+                .unwrap_or_else(|| format!("https://{host}"))
+                .trim_end_matches('/')
+                .to_string();
+            return Ok(CiProvider::GitLab(GitLabProject {
+                api_url: format!("{api_url}/api/v4"),
+                path,
+                token,
+            }));
+        }
+
+        // Same detection as the PR status in `info`: the CLI is there if it runs
+        match std::process::Command::new("gh").arg("--version").output() {
+            Ok(output) if output.status.success() => Ok(CiProvider::GitHub),
+            _ => Err(GitXError::Other(
+                "No CI provider available: install the GitHub CLI (gh) or configure git-x.ci.gitlabToken"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Host and project path from a remote URL (`git@host:group/repo.git`, `https://host/group/repo`)
+    pub fn parse_remote(url: &str) -> Option<(String, String)> {
+        let url = url.trim();
+        let rest = if let Some((_, rest)) = url.split_once("://") {
+            rest.split_once('@').map_or(rest, |(_, host)| host)
+        } else {
+            // scp-like syntax: user@host:path
+            let (_, rest) = url.split_once('@')?;
+            return rest.split_once(':').and_then(|(host, path)| {
+                Self::clean_path(path).map(|path| (host.to_string(), path))
+            });
+        };
+        let (host, path) = rest.split_once('/')?;
+        let host = host.split(':').next()?.to_string();
+        Self::clean_path(path).map(|path| (host, path))
+    }
+
+    fn clean_path(path: &str) -> Option<String> {
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        (!path.is_empty()).then(|| path.to_string())
+    }
+
+    /// Runs for `sha` on `branch`, falling back to the latest commit CI has seen
+    pub fn runs(provider: &CiProvider, branch: &str, sha: &str) -> Result<Vec<CiRun>> {
+        let runs = match provider {
+            CiProvider::GitHub => {
+                let json = Self::gh(&[
+                    "run",
+                    "list",
+                    "--branch",
+                    branch,
+                    "--limit",
+                    "20",
+                    "--json",
+                    "databaseId,workflowName,headSha,status,conclusion,url",
+                ])?;
+                Self::parse_github_runs(&json)?
+            }
+            CiProvider::GitLab(project) => {
+                let json = Self::gitlab(
+                    project,
+                    &format!("pipelines?ref={}&per_page=20", encode(branch)),
+                )?;
+                Self::parse_gitlab_pipelines(&json)?
+            }
+        };
+
+        let mut runs = Self::latest_for(runs, sha);
+        for run in &mut runs {
+            run.jobs = match provider {
+                CiProvider::GitHub => Self::parse_github_jobs(&Self::gh(&[
+                    "run", "view", &run.id, "--json", "jobs",
+                ])?)?,
+                CiProvider::GitLab(project) => Self::parse_gitlab_jobs(&Self::gitlab(
+                    project,
+                    &format!("pipelines/{}/jobs?per_page=100", run.id),
+                )?)?,
+            };
+        }
+        Ok(runs)
+    }
+
+    /// Keep the runs for `sha`, or for the newest commit with runs when `sha` has none
+    ///
+    /// Runs are expected newest first, as both providers return them.
+    pub fn latest_for(runs: Vec<CiRun>, sha: &str) -> Vec<CiRun> {
+        let target = if runs.iter().any(|run| run.sha == sha) {
+            sha.to_string()
+        } else {
+            match runs.first() {
+                Some(run) => run.sha.clone(),
+                None => return runs,
+            }
+        };
+        runs.into_iter().filter(|run| run.sha == target).collect()
+    }
+
+    /// Parse `gh run list --json databaseId,workflowName,headSha,status,conclusion,url`
+    pub fn parse_github_runs(json: &str) -> Result<Vec<CiRun>> {
+        Ok(ExportValue::parse_json(json)?
+            .items()
+            .iter()
+            .map(|run| CiRun {
+                id: text(run, "databaseId"),
+                name: text(run, "workflowName"),
+                sha: text(run, "headSha"),
+                state: CiState::from_github(&text(run, "status"), &text(run, "conclusion")),
+                url: text(run, "url"),
+                jobs: Vec::new(),
+            })
+            .collect())
+    }
+
+    /// Parse `gh run view <id> --json jobs`
+    pub fn parse_github_jobs(json: &str) -> Result<Vec<CiJob>> {
+        let value = ExportValue::parse_json(json)?;
+        Ok(value
+            .get("jobs")
+            .map(|jobs| jobs.items())
+            .unwrap_or_default()
+            .iter()
+            .map(|job| CiJob {
+                name: text(job, "name"),
+                state: CiState::from_github(&text(job, "status"), &text(job, "conclusion")),
+            })
+            .collect())
+    }
+
+    /// Parse the GitLab `GET /projects/:id/pipelines` response
+    pub fn parse_gitlab_pipelines(json: &str) -> Result<Vec<CiRun>> {
+        Ok(ExportValue::parse_json(json)?
+            .items()
+            .iter()
+            .map(|pipeline| {
+                let id = text(pipeline, "id");
+                CiRun {
+                    name: format!("Pipeline #{id}"),
+                    id,
+                    sha: text(pipeline, "sha"),
+                    state: CiState::from_gitlab(&text(pipeline, "status")),
+                    url: text(pipeline, "web_url"),
+                    jobs: Vec::new(),
+                }
+            })
+            .collect())
+    }
+
+    /// Parse the GitLab `GET /projects/:id/pipelines/:id/jobs` response
+    pub fn parse_gitlab_jobs(json: &str) -> Result<Vec<CiJob>> {
+        Ok(ExportValue::parse_json(json)?
+            .items()
+            .iter()
+            .map(|job| CiJob {
+                name: text(job, "name"),
+                state: CiState::from_gitlab(&text(job, "status")),
+            })
+            .collect())
+    }
+
+    fn gh(args: &[&str]) -> Result<String> {
+        Network::require(&format!("gh {}", args[..2].join(" ")))?;
+        let output = std::process::Command::new("gh").args(args).output()?;
+        if !output.status.success() {
+            return Err(GitXError::Other(format!(
+                "gh {} failed: {}",
+                args[..2].join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn gitlab(project: &GitLabProject, path: &str) -> Result<String> {
+        Network::require("the GitLab API request")?;
+        let url = project.endpoint(path);
+        let output = std::process::Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--header"])
+            .arg(format!("PRIVATE-TOKEN: {}", project.token))
+            .arg(&url)
+            .output()
+            .map_err(|e| GitXError::Other(format!("curl is needed for GitLab CI: {e}")))?;
+        if !output.status.success() {
+            return Err(GitXError::Other(format!(
+                "GitLab API request failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
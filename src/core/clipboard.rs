@@ -0,0 +1,105 @@
+use crate::core::config::Config;
+use crate::{GitXError, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// The system clipboard, reached through the platform's copy tool
+///
+/// `git-x.clipboard` names a shell command to use instead, e.g. an OSC 52 helper
+/// for SSH sessions or `tmux load-buffer -`.
+pub struct Clipboard;
+
+impl Clipboard {
+    /// Copy `text` without the terminal styling it was printed with
+    pub fn copy(text: &str) -> Result<()> {
+        let text = console::strip_ansi_codes(text);
+        if let Some(command) = Config::get("clipboard") {
+            return Self::pipe(&shell(&command), text.as_ref()).map_err(|e| {
+                GitXError::Other(format!("git-x.clipboard command '{command}' failed: {e}"))
+            });
+        }
+
+        let tools = Self::tools();
+        for tool in &tools {
+            if Self::pipe(tool, text.as_ref()).is_ok() {
+                return Ok(());
+            }
+        }
+        Err(GitXError::Other(Self::unavailable(&tools)))
+    }
+
+    /// Copy tools to try for this platform and session, most specific first
+    fn tools() -> Vec<Vec<String>> {
+        let tool = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+        if cfg!(target_os = "macos") {
+            return vec![tool(&["pbcopy"])];
+        }
+        if cfg!(windows) {
+            return vec![tool(&[
+                "powershell",
+                "-NoProfile",
+                "-Command",
+                "[Console]::InputEncoding = [Text.Encoding]::UTF8; Set-Clipboard -Value ([Console]::In.ReadToEnd())",
+            ])];
+        }
+        let mut tools = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            tools.push(tool(&["wl-copy"]));
+        }
+        if std::env::var_os("DISPLAY").is_some() {
+            tools.push(tool(&["xclip", "-selection", "clipboard"]));
+            tools.push(tool(&["xsel", "--clipboard", "--input"]));
+        }
+        // WSL reaches the Windows clipboard
+        if std::env::var_os("WSL_DISTRO_NAME").is_some() {
+            tools.push(tool(&["clip.exe"]));
+        }
+        tools
+    }
+
+    fn unavailable(tools: &[Vec<String>]) -> String {
+        if tools.is_empty() {
+            let session = if std::env::var_os("SSH_CONNECTION").is_some() {
+                "this SSH session has no display"
+            } else {
+                "no display to copy to"
+            };
+            return format!(
+                "No clipboard available ({session}); set git-x.clipboard to a command that reads stdin"
+            );
+        }
+        let names: Vec<&str> = tools.iter().map(|tool| tool[0].as_str()).collect();
+        format!(
+            "No clipboard tool worked (tried {}); install one or set git-x.clipboard",
+            names.join(", ")
+        )
+    }
+
+    fn pipe(tool: &[String], text: &str) -> std::io::Result<()> {
+        let mut child = Command::new(&tool[0])
+            .args(&tool[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(std::io::Error::other(status.to_string()))
+        }
+    }
+}
+
+/// Run a configured command through the platform shell
+fn shell(command: &str) -> Vec<String> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    vec![shell.to_string(), flag.to_string(), command.to_string()]
+}
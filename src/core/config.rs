@@ -0,0 +1,358 @@
+use crate::core::git::GitOperations;
+use crate::core::tickets;
+
+/// A setting git-x reads, as listed by `show-config`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setting {
+    /// Key below the `git-x.` namespace; a trailing `.` matches any name, e.g. `team.`
+    pub key: &'static str,
+    /// Value used when unset, if there is a fixed one
+    pub default: Option<&'static str>,
+    /// Every value counts instead of the last one winning
+    pub list: bool,
+    pub description: &'static str,
+}
+
+impl Setting {
+    const fn single(
+        key: &'static str,
+        default: Option<&'static str>,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            key,
+            default,
+            list: false,
+            description,
+        }
+    }
+
+    const fn list(
+        key: &'static str,
+        default: Option<&'static str>,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            key,
+            default,
+            list: true,
+            description,
+        }
+    }
+
+    /// Whether a full `git-x.` key belongs to this setting; git lowercases variable names
+    pub fn matches(&self, key: &str) -> bool {
+        let Some(key) = key.strip_prefix("git-x.") else {
+            return false;
+        };
+        if self.key.ends_with('.') {
+            key.len() > self.key.len() && key[..self.key.len()].eq_ignore_ascii_case(self.key)
+        } else {
+            key.eq_ignore_ascii_case(self.key)
+        }
+    }
+}
+
+/// Repository settings read from `git config` under the `git-x.` namespace
+///
+/// Settings can live in any git config scope, e.g.:
+/// `git config git-x.hotfix.versionFile VERSION`
+pub struct Config;
+
+impl Config {
+    /// Prefix applied to every key
+    pub const NAMESPACE: &'static str = "git-x";
+
+    /// Every setting git-x reads, in the order `show-config` lists them
+    pub const SETTINGS: &'static [Setting] = &[
+        Setting::list(
+            "analysis.generated",
+            None,
+            "Paths large-files, technical-debt and summary treat as generated, e.g. *.pb.go",
+        ),
+        Setting::single(
+            "browser",
+            None,
+            "Command that opens ticket URLs, given the URL (default: $BROWSER, then the platform opener)",
+        ),
+        Setting::single(
+            "ci.provider",
+            None,
+            "CI provider, github or gitlab (detected from origin)",
+        ),
+        Setting::single("ci.gitlabToken", None, "GitLab API token (or GITLAB_TOKEN)"),
+        Setting::single(
+            "ci.gitlabUrl",
+            None,
+            "GitLab base URL (default: the origin host)",
+        ),
+        Setting::single(
+            "clipboard",
+            None,
+            "Command --copy pipes output to (default: pbcopy, PowerShell, wl-copy, xclip or xsel)",
+        ),
+        Setting::single(
+            "configCheck.policy",
+            Some(".git-x-policy"),
+            "Policy file config-check reads, relative to the repository root",
+        ),
+        Setting::single(
+            "debt.lockfileMonths",
+            Some("6"),
+            "Lockfile age technical-debt flags",
+        ),
+        Setting::single(
+            "diff.renderer",
+            Some("auto"),
+            "Diff renderer for stash and review diffs: auto, delta, difftastic, diff-so-fancy, plain or a command",
+        ),
+        Setting::single(
+            "drift.maxCommits",
+            Some("50"),
+            "Commits behind the default branch before info and health flag a branch",
+        ),
+        Setting::single(
+            "drift.maxDays",
+            Some("14"),
+            "Days behind the default branch before info and health flag a branch",
+        ),
+        Setting::single(
+            "hotfix.base",
+            None,
+            "Branch hotfixes start from when there is no release tag",
+        ),
+        Setting::single(
+            "hotfix.releaseBranch",
+            None,
+            "Branch hotfixes are also merged into",
+        ),
+        Setting::single(
+            "hotfix.versionFile",
+            None,
+            "File whose version hotfix bumps",
+        ),
+        Setting::single(
+            "hooks.ciManifest",
+            Some(".git-x/ci.toml"),
+            "Manifest hooks run-ci reads, relative to the repository root",
+        ),
+        Setting::list(
+            "info.baselines",
+            Some("main,master,develop"),
+            "Branches info compares against",
+        ),
+        Setting::single(
+            "jobs",
+            None,
+            "Git processes and worker threads run at once (default: one per CPU)",
+        ),
+        Setting::single(
+            "language",
+            None,
+            "Language of info, health and summary output, en or de (default: from LANG)",
+        ),
+        Setting::list(
+            "lint.types",
+            None,
+            "Commit types accepted on top of the Conventional Commits ones",
+        ),
+        Setting::single(
+            "lock.backend",
+            Some("auto"),
+            "Where lock keeps locks: ref, lfs, or auto (LFS when the repository uses it)",
+        ),
+        Setting::single(
+            "lock.remote",
+            Some("origin"),
+            "Remote lock shares its refs/git-x/locks ref through",
+        ),
+        Setting::single("mirror.remote", Some("backup"), "Remote mirror pushes to"),
+        Setting::single(
+            "new.checkFreshness",
+            Some("false"),
+            "Always check the base is up to date in new",
+        ),
+        Setting::single(
+            "new.maxBehind",
+            Some("0"),
+            "Commits the base may lag its upstream before new warns",
+        ),
+        Setting::single(
+            "offline",
+            Some("false"),
+            "Skip every network operation, as --offline does",
+        ),
+        Setting::list(
+            "protect.branch",
+            None,
+            "Branches protect guards against direct commits and pushes",
+        ),
+        Setting::list(
+            "ready.skip",
+            None,
+            "Checks ready leaves out: rebased, conflicts, lint, ci, todos or secrets",
+        ),
+        Setting::single(
+            "repos.depth",
+            Some("3"),
+            "Directory levels below each root uncommitted and repos scan search for repositories",
+        ),
+        Setting::list(
+            "repos.known",
+            None,
+            "Repositories repos list and goto know about, kept in global config",
+        ),
+        Setting::list(
+            "repos.root",
+            None,
+            "Directories uncommitted and repos scan search for repositories (default: the current one)",
+        ),
+        Setting::single(
+            "reviewSize.maxFiles",
+            Some("20"),
+            "Changed files before review-size calls a branch too large",
+        ),
+        Setting::single(
+            "reviewSize.maxLines",
+            Some("400"),
+            "Changed lines before review-size calls a branch too large",
+        ),
+        Setting::single(
+            "reviewSize.maxPackages",
+            Some("3"),
+            "Packages (or top-level directories) touched before review-size calls a branch too large",
+        ),
+        Setting::single(
+            "reviewSize.minTestRatio",
+            Some("0.3"),
+            "Changed test lines per changed code line review-size expects",
+        ),
+        Setting::single(
+            "safety.typeToConfirm",
+            Some("10"),
+            "Branches or stashes a deletion may remove before the count must be typed to confirm (0: never)",
+        ),
+        Setting::list("scan.allow", None, "Patterns scan-staged never reports"),
+        Setting::single(
+            "scan.failOn",
+            Some("medium"),
+            "Lowest severity that blocks a commit",
+        ),
+        Setting::single(
+            "stash.filterThreshold",
+            Some("15"),
+            "Stash count above which stash-branch offers a filter",
+        ),
+        Setting::list(
+            "team.",
+            None,
+            "Team members used by summary --team and standup --team",
+        ),
+        Setting::single(
+            "tickets.pattern",
+            Some(tickets::DEFAULT_PATTERN),
+            "Regex matching ticket IDs in commit messages; a capture group narrows the ID",
+        ),
+        Setting::single(
+            "tickets.url",
+            None,
+            "Ticket URL with an {id} placeholder, e.g. https://jira.example.com/browse/{id}",
+        ),
+    ];
+
+    /// Environment variables git-x reads, with what they do
+    pub const ENV_VARS: &'static [(&'static str, &'static str)] = &[
+        (
+            "BROWSER",
+            "Command that opens ticket URLs when git-x.browser is unset",
+        ),
+        (
+            "GIT_X_NON_INTERACTIVE",
+            "Never prompt, take the default answer",
+        ),
+        (
+            "GIT_X_OFFLINE",
+            "Skip every network operation, as --offline does",
+        ),
+        (
+            "GIT_X_SKIP_COMMIT_LINT",
+            "Skip the commit-msg check for one commit",
+        ),
+        (
+            "GITLAB_TOKEN",
+            "GitLab API token when git-x.ci.gitlabToken is unset",
+        ),
+        (
+            "LANG",
+            "Output language when git-x.language is unset (LC_ALL and LC_MESSAGES take precedence)",
+        ),
+    ];
+
+    /// Get a single value, or `None` when unset or outside a repository
+    pub fn get(key: &str) -> Option<String> {
+        GitOperations::run(&["config", "--get", &Self::full_key(key)])
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Get a list value
+    ///
+    /// Supports both repeated keys (`git config --add`) and comma-separated values.
+    pub fn get_list(key: &str) -> Vec<String> {
+        GitOperations::run(&["config", "--get-all", &Self::full_key(key)])
+            .map(|output| {
+                output
+                    .lines()
+                    .flat_map(|line| line.split(','))
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Names set below a prefix, e.g. every team under `team.`
+    ///
+    /// Git lowercases the last part of a key, so names come back lowercased.
+    pub fn names(prefix: &str) -> Vec<String> {
+        let full_prefix = Self::full_key(prefix);
+        let pattern = format!("^{}", full_prefix.replace('.', "\\."));
+        let Ok(output) = GitOperations::run(&["config", "--name-only", "--get-regexp", &pattern])
+        else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = output
+            .lines()
+            .filter(|key| {
+                key.get(..full_prefix.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(&full_prefix))
+            })
+            .map(|key| key[full_prefix.len()..].to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Get a boolean value using git's own boolean parsing
+    pub fn get_bool(key: &str) -> Option<bool> {
+        GitOperations::run(&["config", "--type=bool", "--get", &Self::full_key(key)])
+            .ok()
+            .and_then(|value| match value.trim() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            })
+    }
+
+    /// Set a value in the user's global git config
+    pub fn set_global(key: &str, value: &str) -> crate::Result<()> {
+        GitOperations::run(&["config", "--global", &Self::full_key(key), value]).map(|_| ())
+    }
+
+    fn full_key(key: &str) -> String {
+        format!("{}.{key}", Self::NAMESPACE)
+    }
+}
@@ -0,0 +1,178 @@
+use crate::core::wip::is_wip;
+
+/// Commit types accepted unless `git-x.lint.types` adds more
+pub const DEFAULT_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+/// Longest header accepted, so subjects stay readable in `git log --oneline`
+pub const MAX_HEADER_LENGTH: usize = 72;
+
+/// Environment variable that skips the check for one commit
+pub const SKIP_ENV: &str = "GIT_X_SKIP_COMMIT_LINT";
+
+/// Messages written by git itself, which are never linted
+pub const GENERATED_PREFIXES: &[&str] = &["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+/// Line git adds above the diff in `commit --verbose`; everything after it is dropped
+const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+
+/// The `type(scope)!: description` first line of a conventional commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalHeader {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl ConventionalHeader {
+    /// Split a header into its parts, `None` if it doesn't have the expected shape
+    pub fn parse(line: &str) -> Option<Self> {
+        let (prefix, description) = line.split_once(": ")?;
+        let (prefix, breaking) = match prefix.strip_suffix('!') {
+            Some(rest) => (rest, true),
+            None => (prefix, false),
+        };
+        let (kind, scope) = match prefix.split_once('(') {
+            Some((kind, rest)) => {
+                let scope = rest.strip_suffix(')')?;
+                if scope.is_empty() || scope.contains(['(', ')']) {
+                    return None;
+                }
+                (kind, Some(scope.to_string()))
+            }
+            None => (prefix, None),
+        };
+
+        if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_lowercase()) {
+            return None;
+        }
+
+        Some(Self {
+            kind: kind.to_string(),
+            scope,
+            breaking,
+            description: description.trim().to_string(),
+        })
+    }
+}
+
+/// A rule a commit message breaks, with the line it was found on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintViolation {
+    /// 1-based line in the cleaned-up message
+    pub line: usize,
+    pub text: String,
+    pub message: String,
+}
+
+/// Checks commit messages against the Conventional Commits rules
+pub struct CommitLinter {
+    types: Vec<String>,
+}
+
+impl Default for CommitLinter {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl CommitLinter {
+    /// `extra_types` are accepted in addition to [`DEFAULT_TYPES`]
+    pub fn new(extra_types: Vec<String>) -> Self {
+        let mut types: Vec<String> = DEFAULT_TYPES.iter().map(|t| t.to_string()).collect();
+        for extra in extra_types {
+            if !types.contains(&extra) {
+                types.push(extra);
+            }
+        }
+        Self { types }
+    }
+
+    pub fn types(&self) -> &[String] {
+        &self.types
+    }
+
+    /// Every rule the message breaks; empty when it is fine
+    pub fn lint(&self, message: &str) -> Vec<LintViolation> {
+        let lines = clean_message(message);
+        let Some(header) = lines.first() else {
+            // Git aborts empty commits on its own
+            return Vec::new();
+        };
+        if GENERATED_PREFIXES.iter().any(|p| header.starts_with(p)) || is_wip(message) {
+            return Vec::new();
+        }
+
+        let mut violations = Vec::new();
+        let violation = |line: usize, text: &str, message: String| LintViolation {
+            line,
+            text: text.to_string(),
+            message,
+        };
+
+        match ConventionalHeader::parse(header) {
+            None => violations.push(violation(
+                1,
+                header,
+                "header must look like 'type(scope): description'".to_string(),
+            )),
+            Some(parsed) => {
+                if !self.types.contains(&parsed.kind) {
+                    violations.push(violation(
+                        1,
+                        header,
+                        format!("unknown type '{}'", parsed.kind),
+                    ));
+                }
+                if parsed.description.is_empty() {
+                    violations.push(violation(1, header, "description is empty".to_string()));
+                } else if parsed.description.ends_with('.') {
+                    violations.push(violation(
+                        1,
+                        header,
+                        "description should not end with a period".to_string(),
+                    ));
+                }
+            }
+        }
+
+        let length = header.chars().count();
+        if length > MAX_HEADER_LENGTH {
+            violations.push(violation(
+                1,
+                header,
+                format!("header is {length} characters, the limit is {MAX_HEADER_LENGTH}"),
+            ));
+        }
+
+        if let Some(second) = lines.get(1).filter(|line| !line.trim().is_empty()) {
+            violations.push(violation(
+                2,
+                second,
+                "leave a blank line between the header and the body".to_string(),
+            ));
+        }
+
+        violations
+    }
+}
+
+/// Message lines as git will store them: comments and the verbose diff removed,
+/// surrounding blank lines trimmed
+pub fn clean_message(message: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = message
+        .lines()
+        .take_while(|line| *line != SCISSORS)
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.trim_end())
+        .collect();
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let leading = lines.iter().take_while(|line| line.is_empty()).count();
+    lines.drain(..leading);
+    lines
+}
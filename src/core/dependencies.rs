@@ -0,0 +1,286 @@
+use crate::Result;
+use crate::core::git::GitOperations;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+/// Lockfiles untouched for longer than this are reported, unless configured otherwise
+pub const DEFAULT_LOCKFILE_MONTHS: u32 = 6;
+
+/// Directories holding other people's manifests, which are never reported
+const VENDORED_DIRS: &[&str] = &["node_modules/", "vendor/", "third_party/"];
+
+/// Whether a path lies in a directory of vendored third-party code
+pub fn is_vendored(path: &str) -> bool {
+    VENDORED_DIRS.iter().any(|dir| path.contains(dir))
+}
+
+/// Whether a path is a lockfile written by a package manager rather than by hand
+pub fn is_lockfile(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    Ecosystem::ALL.into_iter().any(|ecosystem| {
+        ecosystem.manifest() != file_name && ecosystem.lockfiles().contains(&file_name)
+    })
+}
+
+/// Package ecosystems whose manifests are recognised
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Go,
+    Python,
+}
+
+impl Ecosystem {
+    pub const ALL: [Ecosystem; 4] = [
+        Ecosystem::Cargo,
+        Ecosystem::Npm,
+        Ecosystem::Go,
+        Ecosystem::Python,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "Cargo",
+            Ecosystem::Npm => "npm",
+            Ecosystem::Go => "Go",
+            Ecosystem::Python => "Python",
+        }
+    }
+
+    pub fn manifest(self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "Cargo.toml",
+            Ecosystem::Npm => "package.json",
+            Ecosystem::Go => "go.mod",
+            Ecosystem::Python => "requirements.txt",
+        }
+    }
+
+    /// Files that pin resolved versions next to the manifest, in order of preference
+    ///
+    /// `requirements.txt` pins versions itself, so it is its own lockfile.
+    pub fn lockfiles(self) -> &'static [&'static str] {
+        match self {
+            Ecosystem::Cargo => &["Cargo.lock"],
+            Ecosystem::Npm => &[
+                "package-lock.json",
+                "yarn.lock",
+                "pnpm-lock.yaml",
+                "npm-shrinkwrap.json",
+            ],
+            Ecosystem::Go => &["go.sum"],
+            Ecosystem::Python => &["requirements.txt"],
+        }
+    }
+
+    /// Ecosystem a tracked file is the manifest of
+    pub fn from_path(path: &str) -> Option<Self> {
+        let file_name = path.rsplit('/').next()?;
+        Self::ALL
+            .into_iter()
+            .find(|ecosystem| ecosystem.manifest() == file_name)
+    }
+
+    /// Program and arguments that list outdated dependencies
+    fn outdated_command(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Ecosystem::Cargo => ("cargo", &["outdated", "--root-deps-only"]),
+            Ecosystem::Npm => ("npm", &["outdated", "--parseable"]),
+            Ecosystem::Go => ("go", &["list", "-u", "-m", "all"]),
+            Ecosystem::Python => ("pip", &["list", "--outdated", "--format=freeze"]),
+        }
+    }
+
+    /// Count outdated dependencies in the output of [`Ecosystem::outdated_command`]
+    ///
+    /// `pip` reports the whole environment, so only packages named in the
+    /// manifest are counted.
+    pub fn count_outdated(self, output: &str, manifest: &str) -> usize {
+        match self {
+            // Rows follow the `----` rule under the table header
+            Ecosystem::Cargo => output
+                .lines()
+                .skip_while(|line| !line.starts_with("----"))
+                .skip(1)
+                .filter(|line| !line.trim().is_empty())
+                .count(),
+            Ecosystem::Npm => output
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count(),
+            // Modules with a newer version end in `[v1.2.3]`
+            Ecosystem::Go => output.lines().filter(|line| line.ends_with(']')).count(),
+            Ecosystem::Python => {
+                let required: HashSet<String> =
+                    manifest.lines().filter_map(requirement_name).collect();
+                output
+                    .lines()
+                    .filter_map(requirement_name)
+                    .filter(|name| required.contains(name))
+                    .count()
+            }
+        }
+    }
+}
+
+/// Normalised package name of a `requirements.txt` or `pip freeze` line
+fn requirement_name(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with(['#', '-']) {
+        return None;
+    }
+    let end = line
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(line.len());
+    let name = line[..end].to_ascii_lowercase().replace('_', "-");
+    (!name.is_empty()).then_some(name)
+}
+
+/// A dependency manifest found in the repository and the state of its lockfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyManifest {
+    pub ecosystem: Ecosystem,
+    /// Path relative to the repository root
+    pub manifest: String,
+    /// Committed lockfile next to the manifest, if any
+    pub lockfile: Option<String>,
+    /// Days since the lockfile was last committed
+    pub lock_age_days: Option<i64>,
+    /// Outdated dependency count; `None` when not checked or the tool is missing
+    pub outdated: Option<usize>,
+}
+
+impl DependencyManifest {
+    /// Whether the lockfile is missing or older than `max_age_days`, or anything is outdated
+    pub fn needs_attention(&self, max_age_days: i64) -> bool {
+        self.lockfile.is_none()
+            || self.lock_age_days.is_some_and(|age| age > max_age_days)
+            || self.outdated.is_some_and(|count| count > 0)
+    }
+
+    /// One report line, flagged when [`DependencyManifest::needs_attention`]
+    pub fn summary(&self, max_age_days: i64) -> String {
+        let icon = if self.needs_attention(max_age_days) {
+            "⚠️ "
+        } else {
+            "✅"
+        };
+        let state = match (&self.lockfile, self.lock_age_days) {
+            (None, _) => "no lockfile committed".to_string(),
+            (Some(lockfile), Some(age)) if age > max_age_days => {
+                format!("{} last updated {}", file_name(lockfile), describe_age(age))
+            }
+            (Some(lockfile), Some(age)) => {
+                format!("{} updated {}", file_name(lockfile), describe_age(age))
+            }
+            (Some(lockfile), None) => format!("{} not committed yet", file_name(lockfile)),
+        };
+        let outdated = match self.outdated {
+            Some(count) => format!(", {count} outdated"),
+            None => String::new(),
+        };
+        format!(
+            "   {icon} {} ({}): {state}{outdated}",
+            self.manifest,
+            self.ecosystem.label()
+        )
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Days as a rough human-readable age
+pub fn describe_age(days: i64) -> String {
+    match days {
+        0 => "today".to_string(),
+        1 => "1 day ago".to_string(),
+        2..60 => format!("{days} days ago"),
+        _ => format!("{} months ago", days / 30),
+    }
+}
+
+/// Dependency manifest detection for the technical-debt report
+pub struct Dependencies;
+
+impl Dependencies {
+    /// Every tracked manifest outside vendored directories, with its lockfile age
+    ///
+    /// With `check_outdated`, each ecosystem's own tool is asked for outdated
+    /// dependencies when it is installed; this can hit the network.
+    pub fn find(check_outdated: bool) -> Result<Vec<DependencyManifest>> {
+        let root = GitOperations::repo_root()?;
+        let tracked = GitOperations::run(&["-C", &root, "ls-files"])?;
+        let tracked: HashSet<&str> = tracked.lines().collect();
+
+        let mut manifests: Vec<&str> = tracked
+            .iter()
+            .copied()
+            .filter(|path| !is_vendored(path))
+            .filter(|path| Ecosystem::from_path(path).is_some())
+            .collect();
+        manifests.sort_unstable();
+
+        let now = Utc::now().timestamp();
+        let mut found = Vec::new();
+        for manifest in manifests {
+            let Some(ecosystem) = Ecosystem::from_path(manifest) else {
+                continue;
+            };
+            let dir = &manifest[..manifest.len() - ecosystem.manifest().len()];
+            let lockfile = ecosystem
+                .lockfiles()
+                .iter()
+                .map(|lock| format!("{dir}{lock}"))
+                .find(|lock| tracked.contains(lock.as_str()));
+            let lock_age_days = lockfile
+                .as_deref()
+                .and_then(|lock| Self::last_commit_time(&root, lock))
+                .map(|time| (now - time).max(0) / 86_400);
+            let outdated = if check_outdated {
+                Self::outdated(ecosystem, &Path::new(&root).join(dir), manifest, &root)
+            } else {
+                None
+            };
+
+            found.push(DependencyManifest {
+                ecosystem,
+                manifest: manifest.to_string(),
+                lockfile,
+                lock_age_days,
+                outdated,
+            });
+        }
+
+        Ok(found)
+    }
+
+    /// Unix time of the last commit touching `path`
+    fn last_commit_time(root: &str, path: &str) -> Option<i64> {
+        GitOperations::run(&["-C", root, "log", "-1", "--format=%ct", "--", path])
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Outdated count from the ecosystem's tool, `None` when it isn't installed or fails
+    fn outdated(ecosystem: Ecosystem, dir: &Path, manifest: &str, root: &str) -> Option<usize> {
+        let (program, args) = ecosystem.outdated_command();
+        let output = Command::new(program)
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .ok()?;
+        // `npm outdated` exits 1 when it finds something; the others fail for real
+        if !output.status.success() && ecosystem != Ecosystem::Npm {
+            return None;
+        }
+        let manifest_content = std::fs::read_to_string(Path::new(root).join(manifest)).ok()?;
+        Some(ecosystem.count_outdated(&String::from_utf8_lossy(&output.stdout), &manifest_content))
+    }
+}
@@ -0,0 +1,174 @@
+use crate::Result;
+use crate::core::config::Config;
+use crate::core::git::GitOperations;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// How diffs git-x shows in the terminal are rendered
+///
+/// `git-x.diff.renderer` picks one: `auto` (the default) uses the first of delta,
+/// difftastic and diff-so-fancy that is installed, `plain` keeps git's own output, and
+/// anything else is a shell command reading a unified diff on stdin. Whatever is picked,
+/// output that isn't going to a terminal stays plain, and a renderer that fails falls
+/// back to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffRenderer {
+    /// Git's own output
+    Plain,
+    /// A command reading a unified diff on stdin, such as delta or diff-so-fancy
+    Filter(Vec<String>),
+    /// A command git runs for each file as its external diff, such as difftastic
+    External(String),
+}
+
+impl DiffRenderer {
+    /// The renderer for a `git-x.diff.renderer` value; `None` for `auto`
+    pub fn parse(setting: &str) -> Option<Self> {
+        let setting = setting.trim();
+        let program = setting.split_whitespace().next().unwrap_or_default();
+        match program {
+            "" | "auto" => None,
+            "plain" | "none" => Some(Self::Plain),
+            "difftastic" if program == setting => Some(Self::External("difft".to_string())),
+            "difft" => Some(Self::External(setting.to_string())),
+            "delta" | "diff-so-fancy" if program == setting => {
+                Some(Self::Filter(vec![setting.to_string()]))
+            }
+            _ => Some(Self::Filter(shell(setting))),
+        }
+    }
+
+    /// The configured renderer, or the detected one; plain when stdout isn't a terminal
+    pub fn configured() -> Self {
+        if !atty::is(atty::Stream::Stdout) {
+            return Self::Plain;
+        }
+        Config::get("diff.renderer")
+            .and_then(|setting| Self::parse(&setting))
+            .unwrap_or_else(Self::detect)
+    }
+
+    /// The first of delta, difftastic and diff-so-fancy that is installed
+    pub fn detect() -> Self {
+        let installed = |program: &str| {
+            Command::new(program)
+                .arg("--version")
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok()
+        };
+        if installed("delta") {
+            Self::Filter(vec!["delta".to_string()])
+        } else if installed("difft") {
+            Self::External("difft".to_string())
+        } else if installed("diff-so-fancy") {
+            Self::Filter(vec!["diff-so-fancy".to_string()])
+        } else {
+            Self::Plain
+        }
+    }
+
+    /// Arguments to run git with so its output suits the renderer
+    ///
+    /// Options go before the first option in `args`, which keeps them after
+    /// subcommands such as `stash show`.
+    pub fn git_args(&self, args: &[&str]) -> Vec<String> {
+        let (config, options): (Vec<String>, &[&str]) = match self {
+            Self::Plain => (Vec::new(), &[]),
+            Self::Filter(_) => (Vec::new(), &["--color=always", "--no-ext-diff"]),
+            Self::External(command) => (
+                vec!["-c".to_string(), format!("diff.external={command}")],
+                &["--ext-diff"],
+            ),
+        };
+        let at = args
+            .iter()
+            .position(|arg| arg.starts_with('-'))
+            .unwrap_or(args.len());
+        let mut git_args = config;
+        git_args.extend(args[..at].iter().map(|arg| arg.to_string()));
+        git_args.extend(options.iter().map(|option| option.to_string()));
+        git_args.extend(args[at..].iter().map(|arg| arg.to_string()));
+        git_args
+    }
+
+    /// Run the diff-producing git command `args` and render its output
+    pub fn render(&self, args: &[&str]) -> Result<String> {
+        let rendered = match self {
+            Self::Plain => None,
+            Self::Filter(filter) => self
+                .git_output(args)
+                .and_then(|diff| Self::filter(filter, &diff)),
+            Self::External(_) => self.git_output(args),
+        };
+        match rendered {
+            Some(rendered) => Ok(rendered),
+            None => GitOperations::run(args),
+        }
+    }
+
+    /// Show the diff from `args` on the terminal, without a pager
+    pub fn show(&self, args: &[&str]) -> Result<()> {
+        if *self == Self::Plain {
+            let mut git_args = vec!["--no-pager"];
+            git_args.extend(args);
+            return GitOperations::run_status(&git_args);
+        }
+        println!("{}", self.render(args)?);
+        Ok(())
+    }
+
+    fn git_output(&self, args: &[&str]) -> Option<String> {
+        let output = Command::new("git")
+            .args(self.git_args(args))
+            .env("DFT_COLOR", "always")
+            .output()
+            .ok()?;
+        output.status.success().then(|| {
+            String::from_utf8_lossy(&output.stdout)
+                .trim_end()
+                .to_string()
+        })
+    }
+
+    /// Pipe `diff` through `filter`; `None` when it can't run or fails
+    fn filter(filter: &[String], diff: &str) -> Option<String> {
+        let mut command = Command::new(&filter[0]);
+        command
+            .args(&filter[1..])
+            // The output is printed below the prompt, not paged
+            .env("DELTA_PAGER", "cat")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        // Captured output has no terminal to measure, so pass its width on
+        if let Some((_, columns)) = console::Term::stdout().size_checked() {
+            command.env("COLUMNS", columns.to_string());
+        }
+        let mut child = command.spawn().ok()?;
+        let stdin = child.stdin.take();
+        // Written from another thread so a long diff can't fill the pipe and stall both sides
+        let output = std::thread::scope(|scope| {
+            scope.spawn(move || stdin.map(|mut stdin| stdin.write_all(diff.as_bytes())));
+            child.wait_with_output()
+        })
+        .ok()?;
+        output.status.success().then(|| {
+            String::from_utf8_lossy(&output.stdout)
+                .trim_end()
+                .to_string()
+        })
+    }
+}
+
+/// Run a configured command through the platform shell
+fn shell(command: &str) -> Vec<String> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    vec![shell.to_string(), flag.to_string(), command.to_string()]
+}
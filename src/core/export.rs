@@ -0,0 +1,478 @@
+use crate::{GitXError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Machine-readable output formats shared by all exporting commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Tsv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Names accepted by `FromStr`, in display order
+    pub fn names() -> Vec<&'static str> {
+        vec!["csv", "tsv", "json"]
+    }
+
+    /// Canonical lowercase name of the format
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Tsv => "tsv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = GitXError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "tsv" => Ok(ExportFormat::Tsv),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(GitXError::Parse(format!("Unsupported format: {s}"))),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A single exported value, rendered as plain text for CSV/TSV or typed for JSON
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    List(Vec<ExportValue>),
+    Record(Vec<(String, ExportValue)>),
+}
+
+impl ExportValue {
+    /// Build a record from key/value pairs, keeping insertion order
+    pub fn record<K: Into<String>>(fields: Vec<(K, ExportValue)>) -> Self {
+        ExportValue::Record(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Render the value as JSON
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    /// Render the value as plain text (used for delimited formats)
+    pub fn to_plain(&self) -> String {
+        match self {
+            ExportValue::Null => String::new(),
+            ExportValue::Bool(b) => b.to_string(),
+            ExportValue::Int(i) => i.to_string(),
+            ExportValue::Float(f) => f.to_string(),
+            ExportValue::Text(s) => s.clone(),
+            ExportValue::List(items) => items
+                .iter()
+                .map(|item| item.to_plain())
+                .collect::<Vec<_>>()
+                .join(";"),
+            ExportValue::Record(_) => self.to_json(),
+        }
+    }
+
+    /// Parse a JSON document, such as the output of `gh --json` or a REST API
+    ///
+    /// Objects become records (keeping key order) and whole numbers become `Int`.
+    pub fn parse_json(input: &str) -> Result<Self> {
+        let mut parser = JsonParser {
+            chars: input.chars().peekable(),
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(value),
+            Some(c) => Err(GitXError::Parse(format!(
+                "Unexpected '{c}' after JSON value"
+            ))),
+        }
+    }
+
+    /// Field of a record, `None` for missing keys and non-records
+    pub fn get(&self, key: &str) -> Option<&ExportValue> {
+        match self {
+            ExportValue::Record(fields) => fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Text content, `None` for other kinds of value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ExportValue::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Items of a list, empty for other kinds of value
+    pub fn items(&self) -> &[ExportValue] {
+        match self {
+            ExportValue::List(items) => items,
+            _ => &[],
+        }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        match self {
+            ExportValue::Null => out.push_str("null"),
+            ExportValue::Bool(b) => out.push_str(&b.to_string()),
+            ExportValue::Int(i) => out.push_str(&i.to_string()),
+            ExportValue::Float(f) if f.is_finite() => out.push_str(&f.to_string()),
+            ExportValue::Float(_) => out.push_str("null"),
+            ExportValue::Text(s) => Self::write_json_string(s, out),
+            ExportValue::List(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.write_json(out);
+                }
+                out.push(']');
+            }
+            ExportValue::Record(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    Self::write_json_string(key, out);
+                    out.push(':');
+                    value.write_json(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    fn write_json_string(s: &str, out: &mut String) {
+        out.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+}
+
+/// Minimal recursive-descent JSON reader backing `ExportValue::parse_json`
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl JsonParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(GitXError::Parse(format!(
+                "Expected '{expected}' in JSON, found '{c}'"
+            ))),
+            None => Err(GitXError::Parse(format!(
+                "Expected '{expected}' in JSON, found end of input"
+            ))),
+        }
+    }
+
+    fn value(&mut self) -> Result<ExportValue> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(ExportValue::Text),
+            Some('t') => self.literal("true", ExportValue::Bool(true)),
+            Some('f') => self.literal("false", ExportValue::Bool(false)),
+            Some('n') => self.literal("null", ExportValue::Null),
+            Some(c) if *c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => Err(GitXError::Parse(format!("Unexpected '{c}' in JSON"))),
+            None => Err(GitXError::Parse("Unexpected end of JSON".to_string())),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: ExportValue) -> Result<ExportValue> {
+        for expected in word.chars() {
+            if self.chars.next() != Some(expected) {
+                return Err(GitXError::Parse(format!(
+                    "Invalid JSON literal, expected {word}"
+                )));
+            }
+        }
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<ExportValue> {
+        let mut text = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            text.push(c);
+        }
+        if let Ok(int) = text.parse::<i64>() {
+            return Ok(ExportValue::Int(int));
+        }
+        text.parse::<f64>()
+            .map(ExportValue::Float)
+            .map_err(|_| GitXError::Parse(format!("Invalid JSON number: {text}")))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(out),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('b') => out.push('\u{8}'),
+                    Some('f') => out.push('\u{c}'),
+                    Some('u') => {
+                        let code = self.hex4()?;
+                        // Surrogate pairs encode characters outside the basic plane
+                        let c = if (0xD800..0xDC00).contains(&code) {
+                            self.literal("\\u", ExportValue::Null)?;
+                            let low = self.hex4()?;
+                            if !(0xDC00..0xE000).contains(&low) {
+                                return Err(GitXError::Parse(format!(
+                                    "Invalid JSON surrogate pair: \\u{code:04x}\\u{low:04x}"
+                                )));
+                            }
+                            char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00))
+                        } else {
+                            char::from_u32(code)
+                        };
+                        out.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    Some(c) => out.push(c),
+                    None => break,
+                },
+                Some(c) => out.push(c),
+                None => break,
+            }
+        }
+        Err(GitXError::Parse("Unterminated JSON string".to_string()))
+    }
+
+    fn hex4(&mut self) -> Result<u32> {
+        let digits: String = (0..4).filter_map(|_| self.chars.next()).collect();
+        u32::from_str_radix(&digits, 16)
+            .map_err(|_| GitXError::Parse(format!("Invalid JSON escape: \\u{digits}")))
+    }
+
+    fn array(&mut self) -> Result<ExportValue> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&']').is_some() {
+            return Ok(ExportValue::List(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(ExportValue::List(items)),
+                _ => return Err(GitXError::Parse("Expected ',' or ']' in JSON".to_string())),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<ExportValue> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(ExportValue::Record(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(ExportValue::Record(fields)),
+                _ => return Err(GitXError::Parse("Expected ',' or '}' in JSON".to_string())),
+            }
+        }
+    }
+}
+
+impl From<&str> for ExportValue {
+    fn from(value: &str) -> Self {
+        ExportValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for ExportValue {
+    fn from(value: String) -> Self {
+        ExportValue::Text(value)
+    }
+}
+
+impl From<bool> for ExportValue {
+    fn from(value: bool) -> Self {
+        ExportValue::Bool(value)
+    }
+}
+
+impl From<i64> for ExportValue {
+    fn from(value: i64) -> Self {
+        ExportValue::Int(value)
+    }
+}
+
+impl From<u32> for ExportValue {
+    fn from(value: u32) -> Self {
+        ExportValue::Int(value as i64)
+    }
+}
+
+impl From<usize> for ExportValue {
+    fn from(value: usize) -> Self {
+        ExportValue::Int(value as i64)
+    }
+}
+
+impl From<f64> for ExportValue {
+    fn from(value: f64) -> Self {
+        ExportValue::Float(value)
+    }
+}
+
+impl<T: Into<ExportValue>> From<Option<T>> for ExportValue {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(ExportValue::Null)
+    }
+}
+
+impl<T: Into<ExportValue>> From<Vec<T>> for ExportValue {
+    fn from(value: Vec<T>) -> Self {
+        ExportValue::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Tabular exporter that renders the same rows as CSV, TSV or JSON
+pub struct Exporter {
+    headers: Vec<String>,
+    rows: Vec<Vec<ExportValue>>,
+}
+
+impl Exporter {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<ExportValue>) {
+        self.rows.push(row);
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Render all rows in the requested format
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => self.render_delimited(','),
+            ExportFormat::Tsv => self.render_delimited('\t'),
+            ExportFormat::Json => self.to_value().to_json(),
+        }
+    }
+
+    /// Convert rows into a list of records keyed by header
+    pub fn to_value(&self) -> ExportValue {
+        ExportValue::List(
+            self.rows
+                .iter()
+                .map(|row| {
+                    ExportValue::Record(
+                        self.headers
+                            .iter()
+                            .cloned()
+                            .zip(row.iter().cloned())
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn render_delimited(&self, delimiter: char) -> String {
+        let mut lines = Vec::with_capacity(self.rows.len() + 1);
+        lines.push(
+            self.headers
+                .iter()
+                .map(|h| Self::escape_field(h, delimiter))
+                .collect::<Vec<_>>()
+                .join(&delimiter.to_string()),
+        );
+
+        for row in &self.rows {
+            lines.push(
+                row.iter()
+                    .map(|value| Self::escape_field(&value.to_plain(), delimiter))
+                    .collect::<Vec<_>>()
+                    .join(&delimiter.to_string()),
+            );
+        }
+
+        lines.join("\n")
+    }
+
+    /// Escape a field for delimited output
+    ///
+    /// CSV follows RFC 4180 quoting; TSV has no quoting convention, so tabs and
+    /// newlines inside fields are replaced with spaces instead.
+    pub fn escape_field(field: &str, delimiter: char) -> String {
+        if delimiter == '\t' {
+            return field.replace(['\t', '\n', '\r'], " ");
+        }
+
+        if field.contains(delimiter) || field.contains(['"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+}
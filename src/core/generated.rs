@@ -0,0 +1,118 @@
+use crate::Result;
+use crate::core::config::Config;
+use crate::core::dependencies::{is_lockfile, is_vendored};
+use crate::core::git::GitOperations;
+use crate::core::secrets::glob_match;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// File name endings of minified bundles, source maps and generated code
+const GENERATED_SUFFIXES: &[&str] = &[
+    ".min.js",
+    ".min.css",
+    ".js.map",
+    ".css.map",
+    ".pb.go",
+    "_pb2.py",
+    ".g.dart",
+    ".designer.cs",
+    ".snap",
+];
+
+/// Directories holding build output or generated code
+const GENERATED_DIRS: &[&str] = &["dist/", "generated/", "__generated__/"];
+
+/// `.gitattributes` attributes GitHub's linguist uses to mark files
+const ATTRIBUTES: [&str; 2] = ["linguist-generated", "linguist-vendored"];
+
+/// Whether a path looks like tool output: lockfiles, minified bundles, generated code
+pub fn is_generated_file(path: &str) -> bool {
+    is_lockfile(path)
+        || GENERATED_SUFFIXES
+            .iter()
+            .any(|suffix| path.ends_with(suffix))
+        || GENERATED_DIRS
+            .iter()
+            .any(|dir| path.starts_with(dir) || path.contains(&format!("/{dir}")))
+}
+
+/// Generated and vendored files, which analysis commands leave out unless asked
+///
+/// A file counts when `.gitattributes` sets `linguist-generated` or `linguist-vendored`
+/// on it, when it matches a `git-x.analysis.generated` pattern, or when it looks like a
+/// lockfile, bundle or vendored dependency. Setting either attribute to false keeps a
+/// file the built-in rules would leave out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeneratedFiles {
+    patterns: Vec<String>,
+}
+
+impl GeneratedFiles {
+    /// Patterns from `git-x.analysis.generated`
+    pub fn load() -> Self {
+        Self::with_patterns(Config::get_list("analysis.generated"))
+    }
+
+    /// Patterns use `*` for any characters; one without `/` matches the file name
+    pub fn with_patterns(patterns: Vec<String>) -> Self {
+        Self { patterns }
+    }
+
+    /// Whether a path relative to the repository root matches a configured pattern
+    pub fn matches_pattern(&self, path: &str) -> bool {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        self.patterns.iter().any(|pattern| {
+            if pattern.contains('/') {
+                glob_match(pattern.trim_start_matches('/'), path)
+            } else {
+                glob_match(pattern, file_name)
+            }
+        })
+    }
+
+    /// Whether a path is left out, given its linguist attribute values from `check-attr`
+    pub fn is_excluded(&self, path: &str, generated: &str, vendored: &str) -> bool {
+        let explicit = |value: &str| match value {
+            "set" | "true" => Some(true),
+            "unset" | "false" => Some(false),
+            _ => None,
+        };
+        explicit(generated).unwrap_or_else(|| is_generated_file(path) || self.matches_pattern(path))
+            || explicit(vendored).unwrap_or_else(|| is_vendored(path))
+    }
+
+    /// Which of `paths`, relative to `dir`, are generated or vendored
+    pub fn detect(&self, dir: &Path, paths: &[&str]) -> Result<HashSet<String>> {
+        if paths.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let dir = dir.to_string_lossy();
+        // Built-in rules and patterns look at the path from the repository root
+        let prefix = GitOperations::run(&["-C", dir.as_ref(), "rev-parse", "--show-prefix"])?;
+
+        let mut args = vec!["-C", dir.as_ref(), "check-attr", "-z", "--stdin"];
+        args.extend(ATTRIBUTES);
+        let input: String = paths.iter().map(|path| format!("{path}\0")).collect();
+        let stdout = GitOperations::run_with_input(&args, &input)?;
+
+        // -z output is a flat list of path, attribute, value triples
+        let fields: Vec<&str> = stdout.split('\0').collect();
+        let mut values: HashMap<&str, [&str; 2]> = HashMap::new();
+        for triple in fields.chunks(3).filter(|triple| triple.len() == 3) {
+            let entry = values.entry(triple[0]).or_insert(["unspecified"; 2]);
+            if let Some(index) = ATTRIBUTES.iter().position(|attr| *attr == triple[1]) {
+                entry[index] = triple[2];
+            }
+        }
+
+        Ok(paths
+            .iter()
+            .filter(|path| {
+                let [generated, vendored] =
+                    values.get(**path).copied().unwrap_or(["unspecified"; 2]);
+                self.is_excluded(&format!("{prefix}{path}"), generated, vendored)
+            })
+            .map(|path| path.to_string())
+            .collect())
+    }
+}
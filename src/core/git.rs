@@ -1,34 +1,356 @@
+use crate::core::i18n::Message;
+use crate::core::jobs::Jobs;
+use crate::core::network::Network;
 use crate::{GitXError, Result};
 use std::process::Command;
 
 /// Core git operations abstraction
 pub struct GitOperations;
 
-impl GitOperations {
-    /// Execute a git command and return stdout as String
-    pub fn run(args: &[&str]) -> Result<String> {
+/// Executes git commands for [`GitOperations`] and [`AsyncGitOperations`]
+///
+/// The system implementation spawns `git`; with the `testing` feature a
+/// replaying backend can be installed instead (see `core::testing`).
+pub trait GitBackend: Send + Sync {
+    /// Run `git <args>` and return trimmed stdout, or stderr as the error
+    fn run(&self, args: &[&str]) -> Result<String>;
+}
+
+/// Backend that runs the real `git` binary
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemGitBackend;
+
+impl GitBackend for SystemGitBackend {
+    fn run(&self, args: &[&str]) -> Result<String> {
         let output = Command::new("git").args(args).output()?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
         } else {
-            let stderr_output = String::from_utf8_lossy(&output.stderr);
-            let stderr = stderr_output.trim();
-            Err(GitXError::GitCommand(stderr.to_string()))
+            Err(GitXError::from_output(args, &output))
+        }
+    }
+}
+
+/// First seven characters of a commit hash, as `git log --oneline` shows them
+pub fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Quote `text` as a single word for the shell git runs editor and filter commands in
+pub fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// Upstream tracking state of a local branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchUpstream {
+    pub branch: String,
+    /// Configured upstream (e.g. `origin/feature`), even if it no longer exists
+    pub upstream: Option<String>,
+    /// The upstream is configured but its remote-tracking ref was deleted
+    pub gone: bool,
+}
+
+impl BranchUpstream {
+    /// Format string for `git for-each-ref` understood by [`BranchUpstream::parse`]
+    pub const FORMAT: &'static str =
+        "--format=%(refname:short)%09%(upstream:short)%09%(upstream:track)";
+
+    /// Parse `git for-each-ref` output produced with [`BranchUpstream::FORMAT`]
+    pub fn parse(output: &str) -> Vec<Self> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split('\t');
+                let branch = parts.next()?.trim();
+                if branch.is_empty() {
+                    return None;
+                }
+                let upstream = parts.next().map(str::trim).filter(|u| !u.is_empty());
+                let track = parts.next().unwrap_or_default().trim();
+                Some(Self {
+                    branch: branch.to_string(),
+                    upstream: upstream.map(String::from),
+                    gone: upstream.is_some() && track == "[gone]",
+                })
+            })
+            .collect()
+    }
+}
+
+/// The commit checked out when HEAD isn't on a branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedHead {
+    /// Abbreviated commit hash
+    pub commit: String,
+    /// `git describe --tags --always`, e.g. `v1.2.0-3-gabc1234`
+    pub describe: String,
+    pub subject: String,
+    /// Commits reachable from HEAD but from no local branch
+    pub unreachable: u32,
+    /// HEAD is detached because a rebase is in progress
+    pub rebasing: bool,
+}
+
+impl DetachedHead {
+    /// Commit and describe output, without repeating the hash
+    pub fn label(&self) -> String {
+        if self.describe == self.commit {
+            self.commit.clone()
+        } else {
+            format!("{} ({})", self.commit, self.describe)
+        }
+    }
+
+    /// Why `action` can't run here and how to get back onto a branch
+    pub fn error(&self, action: &str) -> GitXError {
+        let hint = if self.rebasing {
+            "Finish the rebase with 'git rebase --continue' or 'git rebase --abort' first"
+                .to_string()
+        } else {
+            "Run 'git x attach <name>' to create a branch here, or 'git switch <branch>' to go back to one"
+                .to_string()
+        };
+        GitXError::Other(format!(
+            "HEAD is detached at {}; {action} needs a branch. {hint}",
+            self.label()
+        ))
+    }
+}
+
+/// Ahead/behind counts of a branch relative to a baseline ref
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaselineComparison {
+    pub baseline: String,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+impl BaselineComparison {
+    /// Parse `git rev-list --left-right --count <baseline>...<branch>` output
+    pub fn parse(baseline: &str, output: &str) -> Option<Self> {
+        let mut parts = output.split_whitespace();
+        let behind = parts.next()?.parse().ok()?;
+        let ahead = parts.next()?.parse().ok()?;
+        Some(Self {
+            baseline: baseline.to_string(),
+            ahead,
+            behind,
+        })
+    }
+
+    /// Short status label
+    pub fn status(&self) -> &'static str {
+        match (self.ahead, self.behind) {
+            (0, 0) => "up to date",
+            (_, 0) => "ahead",
+            (0, _) => "behind",
+            _ => "diverged",
+        }
+    }
+}
+
+/// How far a branch has fallen behind the default branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchDrift {
+    pub baseline: String,
+    /// Baseline commits missing from the branch
+    pub behind: u32,
+    /// Days since the baseline first moved past the branch
+    pub days: i64,
+}
+
+impl BranchDrift {
+    /// Commits behind before a branch counts as drifting, unless configured
+    pub const DEFAULT_MAX_COMMITS: u32 = 50;
+    /// Days behind before a branch counts as drifting, unless configured
+    pub const DEFAULT_MAX_DAYS: i64 = 14;
+
+    /// Limits from `git-x.drift.maxCommits` and `git-x.drift.maxDays`
+    pub fn limits() -> (u32, i64) {
+        use crate::core::config::Config;
+        (
+            Config::get("drift.maxCommits")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(Self::DEFAULT_MAX_COMMITS),
+            Config::get("drift.maxDays")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(Self::DEFAULT_MAX_DAYS),
+        )
+    }
+
+    /// Whether the branch is further behind than either limit allows
+    pub fn exceeds(&self, (max_commits, max_days): (u32, i64)) -> bool {
+        self.behind > max_commits || self.days > max_days
+    }
+
+    /// E.g. `142 commit(s) and 23 day(s) behind origin/main`
+    pub fn describe(&self) -> String {
+        self.message().english()
+    }
+
+    /// [`describe`](Self::describe) for output that is shown in the user's language
+    pub fn message(&self) -> Message {
+        Message::new("drift")
+            .arg("behind", self.behind)
+            .arg("days", self.days)
+            .arg("baseline", &self.baseline)
+    }
+}
+
+impl GitOperations {
+    /// Execute a git command and return stdout as String
+    pub fn run(args: &[&str]) -> Result<String> {
+        Network::check_git(args)?;
+        #[cfg(feature = "testing")]
+        if let Some(backend) = crate::core::testing::installed_backend() {
+            return backend.run(args);
         }
+
+        SystemGitBackend.run(args)
     }
 
     /// Execute a git command and return success status
     pub fn run_status(args: &[&str]) -> Result<()> {
+        Network::check_git(args)?;
+        #[cfg(feature = "testing")]
+        if let Some(backend) = crate::core::testing::installed_backend() {
+            return backend.run(args).map(|_| ());
+        }
+
         let status = Command::new("git").args(args).status()?;
 
         if status.success() {
             Ok(())
         } else {
-            Err(GitXError::GitCommand(format!(
-                "Git command failed: git {}",
-                args.join(" ")
-            )))
+            Err(GitXError::git_failure(args, status.code(), ""))
+        }
+    }
+
+    /// Execute a git command, showing git's own output unless `quiet`
+    pub fn run_status_with(args: &[&str], quiet: bool) -> Result<()> {
+        if quiet {
+            Self::run(args).map(|_| ())
+        } else {
+            Self::run_status(args)
+        }
+    }
+
+    /// [`run`](Self::run) with extra environment variables, e.g. `GIT_INDEX_FILE`
+    pub fn run_with_env(args: &[&str], env: &[(&str, &str)]) -> Result<String> {
+        Self::run_with(args, env, None, &[0])
+    }
+
+    /// [`run`](Self::run) with `input` written to git's stdin
+    pub fn run_with_input(args: &[&str], input: &str) -> Result<String> {
+        Self::run_with(args, &[], Some(input), &[0])
+    }
+
+    /// [`run`](Self::run) that also accepts the exit codes in `success`, e.g. 1 from
+    /// `git merge-tree` when the merge has conflicts
+    pub fn run_with_exit_codes(args: &[&str], success: &[i32]) -> Result<String> {
+        Self::run_with(args, &[], None, success)
+    }
+
+    fn run_with(
+        args: &[&str],
+        env: &[(&str, &str)],
+        input: Option<&str>,
+        success: &[i32],
+    ) -> Result<String> {
+        use std::process::Stdio;
+
+        Network::check_git(args)?;
+        #[cfg(feature = "testing")]
+        if let Some(backend) = crate::core::testing::installed_backend() {
+            return backend.run(args);
+        }
+
+        let mut child = Command::new("git")
+            .args(args)
+            .envs(env.iter().copied())
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        // Written from another thread so a large input can't fill the pipe and stall both sides
+        let output = std::thread::scope(|scope| {
+            scope.spawn(move || {
+                use std::io::Write;
+                stdin
+                    .zip(input)
+                    .map(|(mut stdin, input)| stdin.write_all(input.as_bytes()))
+            });
+            child.wait_with_output()
+        })?;
+
+        if output
+            .status
+            .code()
+            .is_some_and(|code| success.contains(&code))
+        {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(GitXError::from_output(args, &output))
+        }
+    }
+
+    /// Run git and hand its stdout to `record` one `separator`-terminated record at a
+    /// time, as git writes them; `record` returns `false` to stop git early
+    pub fn run_streaming(
+        args: &[&str],
+        separator: u8,
+        mut record: impl FnMut(&[u8]) -> Result<bool>,
+    ) -> Result<()> {
+        use std::io::BufRead;
+        use std::process::Stdio;
+
+        Network::check_git(args)?;
+        #[cfg(feature = "testing")]
+        if let Some(backend) = crate::core::testing::installed_backend() {
+            for chunk in backend
+                .run(args)?
+                .as_bytes()
+                .split(|byte| *byte == separator)
+            {
+                if !record(chunk)? {
+                    break;
+                }
+            }
+            return Ok(());
+        }
+
+        let mut child = Command::new("git")
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| GitXError::Other("Failed to read git output".to_string()))?;
+        for chunk in std::io::BufReader::new(stdout).split(separator) {
+            let keep_going = chunk
+                .map_err(GitXError::from)
+                .and_then(|chunk| record(&chunk));
+            if !matches!(keep_going, Ok(true)) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return keep_going.map(|_| ());
+            }
+        }
+
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(GitXError::from_output(args, &output))
         }
     }
 
@@ -37,6 +359,42 @@ impl GitOperations {
         Self::run(&["rev-parse", "--abbrev-ref", "HEAD"])
     }
 
+    /// The checked-out commit when HEAD is detached, `None` when on a branch
+    pub fn detached_head() -> Result<Option<DetachedHead>> {
+        // Fails only when HEAD isn't a symbolic ref; an unborn branch still is one
+        if Self::run(&["symbolic-ref", "-q", "HEAD"]).is_ok() {
+            return Ok(None);
+        }
+
+        let commit = Self::run(&["rev-parse", "--short", "HEAD"])?;
+        let describe = Self::run(&["describe", "--tags", "--always"]).unwrap_or(commit.clone());
+        let subject = Self::run(&["log", "-1", "--format=%s"]).unwrap_or_default();
+        let unreachable = Self::run(&["rev-list", "--count", "HEAD", "--not", "--branches"])
+            .ok()
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0);
+        let rebasing = ["rebase-merge", "rebase-apply"].iter().any(|dir| {
+            Self::run(&["rev-parse", "--git-path", dir])
+                .is_ok_and(|path| std::path::Path::new(&path).exists())
+        });
+
+        Ok(Some(DetachedHead {
+            commit,
+            describe,
+            subject,
+            unreachable,
+            rebasing,
+        }))
+    }
+
+    /// Current branch, or an error explaining how to get onto one when HEAD is detached
+    pub fn require_branch(action: &str) -> Result<String> {
+        if let Some(head) = Self::detached_head()? {
+            return Err(head.error(action));
+        }
+        Self::current_branch()
+    }
+
     /// Get repository root path
     pub fn repo_root() -> Result<String> {
         Self::run(&["rev-parse", "--show-toplevel"])
@@ -46,7 +404,7 @@ impl GitOperations {
     pub fn commit_exists(commit: &str) -> Result<bool> {
         match Self::run(&["rev-parse", "--verify", &format!("{commit}^{{commit}}")]) {
             Ok(_) => Ok(true),
-            Err(GitXError::GitCommand(_)) => Ok(false),
+            Err(GitXError::GitCommand(_) | GitXError::GitProcess(_)) => Ok(false),
             Err(e) => Err(e),
         }
     }
@@ -89,6 +447,22 @@ impl GitOperations {
         }
     }
 
+    /// Upstream tracking state of local branches (all branches, or just `branch`)
+    pub fn branch_upstreams(branch: Option<&str>) -> Result<Vec<BranchUpstream>> {
+        let refs = branch.map_or_else(|| "refs/heads".to_string(), |b| format!("refs/heads/{b}"));
+        let output = Self::run(&["for-each-ref", BranchUpstream::FORMAT, &refs])?;
+        Ok(BranchUpstream::parse(&output))
+    }
+
+    /// Local branches whose upstream was deleted on the remote
+    pub fn gone_branches() -> Result<Vec<String>> {
+        Ok(Self::branch_upstreams(None)?
+            .into_iter()
+            .filter(|b| b.gone)
+            .map(|b| b.branch)
+            .collect())
+    }
+
     /// Get all local branches
     pub fn local_branches() -> Result<Vec<String>> {
         let output = Self::run(&["branch", "--format=%(refname:short)"])?;
@@ -123,9 +497,14 @@ impl GitOperations {
         Ok(branches)
     }
 
-    /// Get merged branches
+    /// Local branches merged into the default branch
     pub fn merged_branches() -> Result<Vec<String>> {
-        let output = Self::run(&["branch", "--merged"])?;
+        Self::merged_into(&Self::require_default_branch()?)
+    }
+
+    /// Local branches merged into `baseline`
+    pub fn merged_into(baseline: &str) -> Result<Vec<String>> {
+        let output = Self::run(&["branch", "--merged", baseline])?;
         let branches: Vec<String> = output
             .lines()
             .map(|line| line.trim().trim_start_matches("* ").to_string())
@@ -140,6 +519,98 @@ impl GitOperations {
         Ok(output.trim().is_empty())
     }
 
+    /// Default branch: `origin/HEAD`, else the local branch `init.defaultBranch` names,
+    /// `main` or `master`, else the only local branch
+    pub fn default_branch() -> Option<String> {
+        if let Ok(branch) = Self::run(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+            return Some(branch);
+        }
+
+        let configured = Self::run(&["config", "--get", "init.defaultBranch"]).ok();
+        let local = Self::run(&["for-each-ref", "--format=%(refname:short)", "refs/heads"])
+            .unwrap_or_default();
+        let local: Vec<&str> = local.lines().collect();
+        configured
+            .iter()
+            .map(String::as_str)
+            .chain(["main", "master"])
+            .find(|branch| local.contains(branch))
+            .or(match local.as_slice() {
+                [only] => Some(*only),
+                _ => None,
+            })
+            .map(String::from)
+    }
+
+    /// Default branch, or an error saying how to set one
+    pub fn require_default_branch() -> Result<String> {
+        Self::default_branch().ok_or_else(|| {
+            GitXError::Other(
+                "No default branch found: origin/HEAD isn't set and there's no local main or master. \
+                 Run 'git remote set-head origin --auto' or 'git config init.defaultBranch <branch>'"
+                    .to_string(),
+            )
+        })
+    }
+
+    /// How far `branch` is behind `baseline`; `None` when it has every baseline commit
+    pub fn drift(branch: &str, baseline: &str) -> Result<Option<BranchDrift>> {
+        let range = format!("{branch}..{baseline}");
+        let behind: u32 = Self::run(&["rev-list", "--count", &range])?
+            .parse()
+            .unwrap_or(0);
+        if behind == 0 {
+            return Ok(None);
+        }
+        // The oldest first-parent commit the branch lacks is when the baseline moved
+        // past it; commits merged in from elsewhere keep their older dates
+        let oldest: i64 = Self::run(&["log", "--first-parent", "--format=%ct", &range])?
+            .lines()
+            .last()
+            .and_then(|time| time.trim().parse().ok())
+            .unwrap_or(0);
+        let days = (chrono::Utc::now().timestamp() - oldest).max(0) / 86_400;
+        Ok(Some(BranchDrift {
+            baseline: baseline.to_string(),
+            behind,
+            days,
+        }))
+    }
+
+    /// Whether `branch` is the default branch itself or its local copy
+    pub fn is_default_branch(branch: &str, default: &str) -> bool {
+        branch == default
+            || default
+                .split_once('/')
+                .is_some_and(|(remote, name)| name == branch && !remote.is_empty())
+    }
+
+    /// Compare the current branch against a baseline ref; `None` if the baseline doesn't exist
+    pub fn compare_to_baseline(
+        current_branch: &str,
+        baseline: &str,
+    ) -> Result<Option<BaselineComparison>> {
+        if Self::run(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{baseline}^{{commit}}"),
+        ])
+        .is_err()
+        {
+            return Ok(None);
+        }
+
+        let output = Self::run(&[
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("{baseline}...{current_branch}"),
+        ])?;
+
+        Ok(BaselineComparison::parse(baseline, &output))
+    }
+
     /// Get staged files
     pub fn staged_files() -> Result<Vec<String>> {
         let output = Self::run(&["diff", "--cached", "--name-only"])?;
@@ -158,22 +629,59 @@ pub struct AsyncGitOperations;
 impl AsyncGitOperations {
     /// Execute a git command asynchronously and return stdout as String
     pub async fn run(args: &[&str]) -> Result<String> {
+        Network::check_git(args)?;
+        #[cfg(feature = "testing")]
+        if let Some(backend) = crate::core::testing::installed_backend() {
+            return backend.run(args);
+        }
+
+        let _slot = Jobs::git_process().await;
+        let output = tokio::process::Command::new("git")
+            .args(args)
+            .output()
+            .await?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(GitXError::from_output(args, &output))
+        }
+    }
+
+    /// [`run`](Self::run) with extra environment variables; git is killed if the
+    /// future is dropped, so callers can put a timeout on it
+    pub async fn run_with_env(args: &[&str], env: &[(&str, &str)]) -> Result<String> {
+        Network::check_git(args)?;
+        #[cfg(feature = "testing")]
+        if let Some(backend) = crate::core::testing::installed_backend() {
+            return backend.run(args);
+        }
+
+        let _slot = Jobs::git_process().await;
         let output = tokio::process::Command::new("git")
             .args(args)
+            .envs(env.iter().copied())
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
             .output()
             .await?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
         } else {
-            let stderr_output = String::from_utf8_lossy(&output.stderr);
-            let stderr = stderr_output.trim();
-            Err(GitXError::GitCommand(stderr.to_string()))
+            Err(GitXError::from_output(args, &output))
         }
     }
 
     /// Execute a git command asynchronously and return success status
     pub async fn run_status(args: &[&str]) -> Result<()> {
+        Network::check_git(args)?;
+        #[cfg(feature = "testing")]
+        if let Some(backend) = crate::core::testing::installed_backend() {
+            return backend.run(args).map(|_| ());
+        }
+
+        let _slot = Jobs::git_process().await;
         let status = tokio::process::Command::new("git")
             .args(args)
             .status()
@@ -182,10 +690,7 @@ impl AsyncGitOperations {
         if status.success() {
             Ok(())
         } else {
-            Err(GitXError::GitCommand(format!(
-                "Git command failed: git {}",
-                args.join(" ")
-            )))
+            Err(GitXError::git_failure(args, status.code(), ""))
         }
     }
 
@@ -203,7 +708,7 @@ impl AsyncGitOperations {
     pub async fn commit_exists(commit: &str) -> Result<bool> {
         match Self::run(&["rev-parse", "--verify", &format!("{commit}^{{commit}}")]).await {
             Ok(_) => Ok(true),
-            Err(GitXError::GitCommand(_)) => Ok(false),
+            Err(GitXError::GitCommand(_) | GitXError::GitProcess(_)) => Ok(false),
             Err(e) => Err(e),
         }
     }
@@ -284,9 +789,30 @@ impl AsyncGitOperations {
         Ok(branches)
     }
 
-    /// Get merged branches
+    /// Upstream tracking state of all local branches
+    pub async fn branch_upstreams() -> Result<Vec<BranchUpstream>> {
+        let output = Self::run(&["for-each-ref", BranchUpstream::FORMAT, "refs/heads"]).await?;
+        Ok(BranchUpstream::parse(&output))
+    }
+
+    /// Local branches whose upstream was deleted on the remote
+    pub async fn gone_branches() -> Result<Vec<String>> {
+        Ok(Self::branch_upstreams()
+            .await?
+            .into_iter()
+            .filter(|b| b.gone)
+            .map(|b| b.branch)
+            .collect())
+    }
+
+    /// Local branches merged into the default branch
     pub async fn merged_branches() -> Result<Vec<String>> {
-        let output = Self::run(&["branch", "--merged"]).await?;
+        Self::merged_into(&GitOperations::require_default_branch()?).await
+    }
+
+    /// Local branches merged into `baseline`
+    pub async fn merged_into(baseline: &str) -> Result<Vec<String>> {
+        let output = Self::run(&["branch", "--merged", baseline]).await?;
         let branches: Vec<String> = output
             .lines()
             .map(|line| line.trim().trim_start_matches("* ").to_string())
@@ -331,6 +857,9 @@ impl AsyncGitOperations {
 
     /// Check GitHub PR status using external gh command
     pub async fn check_github_pr_status() -> Result<Option<String>> {
+        if Network::is_offline() {
+            return Ok(Some(crate::core::i18n::t("info.pr_offline")));
+        }
         match tokio::process::Command::new("gh")
             .args(["pr", "status", "--json", "currentBranch"])
             .output()
@@ -339,76 +868,51 @@ impl AsyncGitOperations {
             Ok(output) if output.status.success() => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 if stdout.trim().is_empty() || stdout.contains("null") {
-                    Ok(Some("❌ No open PR for current branch".to_string()))
+                    Ok(Some(crate::core::i18n::t("info.pr_none")))
                 } else {
-                    Ok(Some("✅ Open PR found for current branch".to_string()))
+                    Ok(Some(crate::core::i18n::t("info.pr_open")))
                 }
             }
             _ => Ok(None), // GitHub CLI not available or error
         }
     }
 
-    /// Get branch differences against main branches
-    pub async fn get_branch_differences(current_branch: &str) -> Result<Vec<String>> {
-        let mut differences = Vec::new();
-
-        // Check against main/master/develop in parallel
-        let checks = ["main", "master", "develop"]
+    /// Compare the current branch against each baseline ref in parallel
+    pub async fn compare_to_baselines(
+        current_branch: &str,
+        baselines: &[String],
+    ) -> Result<Vec<BaselineComparison>> {
+        // Check every baseline in parallel, keeping the configured order
+        let checks = baselines
             .iter()
-            .filter(|&&branch| branch != current_branch)
-            .map(|&main_branch| async move {
-                // Check if this main branch exists
-                if Self::run(&[
+            .filter(|baseline| baseline.as_str() != current_branch)
+            .map(|baseline| async move {
+                Self::run(&[
                     "rev-parse",
                     "--verify",
-                    &format!("refs/heads/{main_branch}"),
+                    "--quiet",
+                    &format!("{baseline}^{{commit}}"),
                 ])
                 .await
-                .is_ok()
-                {
-                    // Get ahead/behind count
-                    if let Ok(output) = Self::run(&[
-                        "rev-list",
-                        "--left-right",
-                        "--count",
-                        &format!("{main_branch}...{current_branch}"),
-                    ])
-                    .await
-                    {
-                        let parts: Vec<&str> = output.split_whitespace().collect();
-                        if parts.len() == 2 {
-                            let behind: u32 = parts[0].parse().unwrap_or(0);
-                            let ahead: u32 = parts[1].parse().unwrap_or(0);
-
-                            if ahead > 0 || behind > 0 {
-                                let mut status_parts = Vec::new();
-                                if ahead > 0 {
-                                    status_parts.push(format!("{ahead} ahead"));
-                                }
-                                if behind > 0 {
-                                    status_parts.push(format!("{behind} behind"));
-                                }
-                                return Some(format!(
-                                    "📊 vs {}: {}",
-                                    main_branch,
-                                    status_parts.join(", ")
-                                ));
-                            } else {
-                                return Some(format!("✅ vs {main_branch}: Up to date"));
-                            }
-                        }
-                    }
-                }
-                None
-            });
+                .ok()?;
 
-        // Execute all branch checks in parallel
-        let results = futures::future::join_all(checks).await;
-        if let Some(diff) = results.into_iter().flatten().next() {
-            differences.push(diff);
-        }
+                let output = Self::run(&[
+                    "rev-list",
+                    "--left-right",
+                    "--count",
+                    &format!("{baseline}...{current_branch}"),
+                ])
+                .await
+                .ok()?;
+
+                BaselineComparison::parse(baseline, &output)
+            });
 
-        Ok(differences)
+        Ok(futures::future::join_all(checks)
+            .await
+            .into_iter()
+            .flatten()
+            .collect())
     }
 }
 
@@ -445,7 +949,7 @@ impl BranchOperations {
     pub fn exists(name: &str) -> Result<bool> {
         match GitOperations::run(&["rev-parse", "--verify", &format!("refs/heads/{name}")]) {
             Ok(_) => Ok(true),
-            Err(GitXError::GitCommand(_)) => Ok(false),
+            Err(GitXError::GitCommand(_) | GitXError::GitProcess(_)) => Ok(false),
             Err(e) => Err(e),
         }
     }
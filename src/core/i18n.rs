@@ -0,0 +1,810 @@
+use crate::core::config::Config;
+use crate::core::export::ExportValue;
+use std::fmt;
+use std::sync::OnceLock;
+
+static CURRENT: OnceLock<Language> = OnceLock::new();
+
+/// Languages human-readable output can be written in
+///
+/// Only text meant to be read is translated; JSON records, porcelain output and
+/// values that come from git (branch names, dates such as `1 month ago`) are the
+/// same in every language.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    /// Every supported language, English first
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// ISO 639-1 code, as accepted by `git-x.language`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    /// Language named by a code or POSIX locale such as `de`, `de_AT.UTF-8` or `en-US`
+    ///
+    /// `C` and `POSIX` mean English; unsupported languages give `None`.
+    pub fn from_locale(locale: &str) -> Option<Language> {
+        let language = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Language::English),
+            "de" => Some(Language::German),
+            _ => None,
+        }
+    }
+
+    /// `git-x.language`, else the first of `LC_ALL`, `LC_MESSAGES` and `LANG` that is
+    /// set; English when that names an unsupported language
+    pub fn resolve() -> Language {
+        if let Some(language) = Config::get("language").and_then(|value| Self::from_locale(&value))
+        {
+            return language;
+        }
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// The output language, resolved once per run
+    pub fn current() -> Language {
+        *CURRENT.get_or_init(Self::resolve)
+    }
+
+    /// Template for `key`, falling back to English for keys this language lacks
+    pub fn template(&self, key: &str) -> Option<&'static str> {
+        let find = |catalog: &[(&str, &'static str)]| {
+            catalog
+                .iter()
+                .find(|(candidate, _)| *candidate == key)
+                .map(|(_, template)| *template)
+        };
+        find(self.catalog()).or_else(|| find(EN))
+    }
+
+    /// Every key and template this language translates
+    pub fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => EN,
+            Language::German => DE,
+        }
+    }
+}
+
+/// A value filled into a message placeholder
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Arg {
+    Text(String),
+    Message(Message),
+}
+
+/// Text looked up in the catalog by key, rendered only once the language is known
+///
+/// Placeholders in templates are written `{name}` and filled with [`Message::arg`].
+/// Commands render it with `to_string()` for people and with [`Message::english`] for
+/// JSON records, so machine-readable output never depends on the locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    key: &'static str,
+    args: Vec<(&'static str, Arg)>,
+}
+
+impl Message {
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            args: Vec::new(),
+        }
+    }
+
+    /// Text that reads the same in every language, such as a file path
+    pub fn verbatim(text: impl Into<String>) -> Self {
+        Self::new("verbatim").arg("text", text.into())
+    }
+
+    /// Fill the `{name}` placeholder with a value shown as is
+    pub fn arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.push((name, Arg::Text(value.to_string())));
+        self
+    }
+
+    /// Fill the `{name}` placeholder with another message, in the same language
+    pub fn nested(mut self, name: &'static str, message: Message) -> Self {
+        self.args.push((name, Arg::Message(message)));
+        self
+    }
+
+    /// The text in `language`; an unknown key renders as itself
+    ///
+    /// Placeholders without an argument are kept, and values are never searched for
+    /// placeholders themselves.
+    pub fn render(&self, language: Language) -> String {
+        let mut rest = language.template(self.key).unwrap_or(self.key);
+        let mut text = String::with_capacity(rest.len());
+        while let Some(open) = rest.find('{') {
+            text.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            let value = after.find('}').and_then(|close| {
+                let (_, value) = self
+                    .args
+                    .iter()
+                    .find(|(name, _)| *name == &after[..close])?;
+                Some((close, value))
+            });
+            match value {
+                Some((close, Arg::Text(value))) => {
+                    text.push_str(value);
+                    rest = &after[close + 1..];
+                }
+                Some((close, Arg::Message(message))) => {
+                    text.push_str(&message.render(language));
+                    rest = &after[close + 1..];
+                }
+                None => {
+                    text.push('{');
+                    rest = after;
+                }
+            }
+        }
+        text.push_str(rest);
+        text
+    }
+
+    /// The text for machine-readable output
+    pub fn english(&self) -> String {
+        self.render(Language::English)
+    }
+
+    /// The key and arguments as a value, so the message can be stored and rendered later
+    pub fn to_value(&self) -> ExportValue {
+        let args = self
+            .args
+            .iter()
+            .map(|(name, arg)| {
+                let value = match arg {
+                    Arg::Text(text) => ("text", ExportValue::from(text.as_str())),
+                    Arg::Message(message) => ("message", message.to_value()),
+                };
+                ExportValue::record(vec![("name", ExportValue::from(*name)), value])
+            })
+            .collect();
+        ExportValue::record(vec![
+            ("key", ExportValue::from(self.key)),
+            ("args", ExportValue::List(args)),
+        ])
+    }
+
+    /// A message stored with [`Message::to_value`]
+    ///
+    /// `None` when the key or a placeholder is no longer in the English catalog, such as
+    /// a value stored by another version of git-x.
+    pub fn from_value(value: &ExportValue) -> Option<Self> {
+        let key = value.get("key")?.as_str()?;
+        let (key, template) = EN.iter().find(|(known, _)| *known == key)?;
+        let mut message = Self::new(key);
+        for arg in value.get("args")?.items() {
+            let name = arg.get("name")?.as_str()?;
+            // Placeholder names are borrowed from the template, which lives forever
+            let start = template.find(&format!("{{{name}}}"))? + 1;
+            let name = &template[start..start + name.len()];
+            let arg = match (arg.get("text"), arg.get("message")) {
+                (Some(text), _) => Arg::Text(text.as_str()?.to_string()),
+                (None, Some(nested)) => Arg::Message(Self::from_value(nested)?),
+                (None, None) => return None,
+            };
+            message.args.push((name, arg));
+        }
+        Some(message)
+    }
+}
+
+impl fmt::Display for Message {
+    /// Renders in [`Language::current`]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Language::current()))
+    }
+}
+
+/// Shorthand for a message without placeholders, in the current language
+pub fn t(key: &'static str) -> String {
+    Message::new(key).to_string()
+}
+
+/// English templates; every other catalog translates a subset of these keys
+const EN: &[(&str, &str)] = &[
+    ("verbatim", "{text}"),
+    ("repository", "🗂️  Repository: {name}"),
+    ("current_branch", "📍 Current branch: {branch}"),
+    ("detached_at", "📍 Detached HEAD at {commit}"),
+    ("upstream", "🔗 Upstream: {upstream}"),
+    (
+        "drift",
+        "{behind} commit(s) and {days} day(s) behind {baseline}",
+    ),
+    // info
+    ("info.commit", "📝 Commit: {subject}"),
+    ("info.rebasing", "🔄 Rebase in progress"),
+    (
+        "info.unreachable",
+        "⚠️  {count} commit(s) here are not on any branch; keep them with 'git x attach <name>'",
+    ),
+    (
+        "info.attach_hint",
+        "💡 Run 'git x attach <name>' to start a branch here",
+    ),
+    ("info.ahead", "{count} ahead"),
+    ("info.behind", "{count} behind"),
+    ("info.status", "📊 Status: {status}"),
+    ("info.up_to_date", "✅ Status: Up to date"),
+    ("info.no_upstream", "❌ No upstream configured"),
+    ("info.clean", "✅ Working directory: Clean"),
+    ("info.dirty", "⚠️  Working directory: Has changes"),
+    ("info.staged_none", "📋 Staged files: None"),
+    ("info.staged", "📋 Staged files: {count} file(s)"),
+    ("info.recent_activity", "📋 Recent activity:"),
+    ("info.recent_branches", "🕒 Recent branches:"),
+    ("info.pr_open", "✅ Open PR found for current branch"),
+    ("info.pr_none", "❌ No open PR for current branch"),
+    (
+        "info.pr_offline",
+        "📴 Pull request status: unavailable offline",
+    ),
+    ("info.baselines", "📊 Baselines:"),
+    ("info.column.baseline", "Baseline"),
+    ("info.column.ahead", "Ahead"),
+    ("info.column.behind", "Behind"),
+    ("info.column.status", "Status"),
+    ("info.baseline.up_to_date", "up to date"),
+    ("info.baseline.ahead", "ahead"),
+    ("info.baseline.behind", "behind"),
+    ("info.baseline.diverged", "diverged"),
+    (
+        "info.drifting",
+        "⏳ Drifting: {drift} (limit: {commits} commits or {days} days)",
+    ),
+    (
+        "info.catch_up",
+        "💡 Catch up with 'git rebase {baseline}' or 'git merge {baseline}'",
+    ),
+    (
+        "info.wip",
+        "🚧 {count} WIP commit(s) on this branch, latest {commit}; 'git x unwip' brings it back into the working tree",
+    ),
+    // health
+    ("health.title", "🏥 Repository Health Check"),
+    (
+        "health.title_parallel",
+        "🏥 Repository Health Check (Parallel)",
+    ),
+    ("health.starting", "Starting health check..."),
+    ("health.complete", "Health check complete!"),
+    ("health.healthy", "🎉 Repository is healthy!"),
+    ("health.found", "🔧 Found {count} issue(s):"),
+    ("health.config.progress", "Checking Git configuration..."),
+    ("health.config.ok", "✅ Git configuration: OK"),
+    ("health.config.issues", "❌ Git configuration: Issues found"),
+    ("health.remotes.progress", "Checking remotes..."),
+    ("health.remotes.ok", "✅ Remotes: OK"),
+    ("health.remotes.issues", "⚠️  Remotes: Issues found"),
+    ("health.branches.progress", "Analyzing branches..."),
+    ("health.branches.ok", "✅ Branches: OK"),
+    ("health.branches.issues", "⚠️  Branches: Issues found"),
+    ("health.workdir.progress", "Checking working directory..."),
+    ("health.workdir.ok", "✅ Working directory: Clean"),
+    ("health.workdir.issues", "ℹ️  Working directory: Has notes"),
+    ("health.size.progress", "Analyzing repository size..."),
+    ("health.size.ok", "✅ Repository size: OK"),
+    ("health.size.issues", "⚠️  Repository size: Large"),
+    (
+        "health.security.progress",
+        "Scanning for security issues...",
+    ),
+    ("health.security.ok", "✅ Security: No obvious issues found"),
+    (
+        "health.security.issues",
+        "⚠️  Security: Potential issues found",
+    ),
+    ("health.gitignore.progress", "Validating .gitignore..."),
+    ("health.gitignore.ok", "✅ .gitignore: Looks good"),
+    (
+        "health.gitignore.issues",
+        "⚠️  .gitignore: Suggestions available",
+    ),
+    ("health.binary.progress", "Analyzing binary files..."),
+    ("health.binary.ok", "✅ Binary files: OK"),
+    (
+        "health.binary.issues",
+        "⚠️  Binary files: Review recommended",
+    ),
+    ("health.lfs.progress", "Checking Git LFS..."),
+    ("health.lfs.ok", "✅ Git LFS: OK"),
+    ("health.lfs.issues", "⚠️  Git LFS: Issues found"),
+    ("health.line_endings.progress", "Checking line endings..."),
+    ("health.line_endings.ok", "✅ Line endings: Consistent"),
+    (
+        "health.line_endings.issues",
+        "⚠️  Line endings: CRLF committed",
+    ),
+    ("health.no_user_name", "❌ Git user.name not configured"),
+    ("health.no_user_email", "❌ Git user.email not configured"),
+    ("health.no_remotes", "⚠️  No remotes configured"),
+    ("health.remotes_failed", "❌ Could not check remotes"),
+    (
+        "health.many_branches",
+        "⚠️  Many local branches ({count}) - consider cleaning up",
+    ),
+    ("health.branches_failed", "❌ Could not check branches"),
+    (
+        "health.stale",
+        "⚠️  {count} potentially stale branches found",
+    ),
+    ("health.drifted", "⚠️  {branch} has drifted: {drift}"),
+    (
+        "health.more_drifting",
+        "⚠️  … and {count} more branch(es) drifting from {baseline}",
+    ),
+    ("health.untracked", "⚠️  {count} untracked files found"),
+    ("health.staged", "ℹ️  {count} files staged for commit"),
+    (
+        "health.large_repository",
+        "⚠️  Repository size: {size} (consider cleanup)",
+    ),
+    (
+        "health.sensitive_commits",
+        "🔒 {count} potentially sensitive commit message(s) found:",
+    ),
+    (
+        "health.sensitive_files",
+        "🔐 {count} potentially sensitive file(s) in repository:",
+    ),
+    (
+        "health.rm_secrets_hint",
+        "     💡 Purge them from history with 'git x rm-secrets <path>'",
+    ),
+    (
+        "health.env_files",
+        "⚠️  {count} environment file(s) found - ensure no secrets are committed:",
+    ),
+    ("health.item", "     • {item}"),
+    ("health.and_more", "     • ...and {count} more"),
+    ("health.no_gitignore", "📝 No .gitignore file found"),
+    (
+        "health.should_ignore",
+        "🗂️  {count} {kind} tracked (consider adding to .gitignore):",
+    ),
+    ("health.ignore.logs", "log files"),
+    ("health.ignore.temporary", "temporary files"),
+    ("health.ignore.swap", "swap files"),
+    ("health.ignore.backup", "backup files"),
+    ("health.ignore.macos", "macOS system files"),
+    ("health.ignore.windows", "Windows system files"),
+    ("health.ignore.node", "Node.js dependencies"),
+    ("health.ignore.rust", "Rust build artifacts"),
+    ("health.ignore.vscode", "VS Code settings"),
+    ("health.ignore.idea", "IntelliJ settings"),
+    (
+        "health.binary_files",
+        "📦 {count} likely binary files tracked (consider Git LFS for large files)",
+    ),
+    (
+        "health.large_files",
+        "📏 {count} large file(s) > 1MB found:",
+    ),
+    (
+        "health.limited",
+        "     • Analysis limited to first {count} files for performance (use --deep to check all)",
+    ),
+    ("health.lfs_failed", "❌ Could not check Git LFS"),
+    (
+        "health.line_endings_failed",
+        "❌ Could not check line endings",
+    ),
+    ("health.skipped", "⏭️  {check}: skipped with --fast"),
+    (
+        "health.cached",
+        "💾 Results for {commit} from the cache (run with --no-cache to check again)",
+    ),
+    (
+        "health.offline",
+        "📴 Offline: remote branches are as last fetched, LFS push status unavailable",
+    ),
+    (
+        "health.history_secrets",
+        "🔑 {count} possible secret(s) added somewhere in history:",
+    ),
+    (
+        "lfs.not_installed",
+        "🗄️  .gitattributes uses LFS but git-lfs is not installed",
+    ),
+    (
+        "lfs.uncovered",
+        "📦 {count} large binary file(s) not covered by LFS:",
+    ),
+    (
+        "lfs.not_downloaded",
+        "⬇️  {count} LFS file(s) not downloaded (run 'git lfs pull')",
+    ),
+    (
+        "lfs.pending",
+        "⬆️  {count} LFS object(s) waiting to be pushed",
+    ),
+    (
+        "line_endings.mixed",
+        "🔀 {count} file(s) mix LF and CRLF line endings:",
+    ),
+    (
+        "line_endings.crlf",
+        "↩️  {count} file(s) committed with CRLF would be renormalized:",
+    ),
+    (
+        "line_endings.no_attributes",
+        "📝 {count} text file(s) without a text or eol setting in .gitattributes follow each machine's core.autocrlf",
+    ),
+    (
+        "line_endings.fix_hint",
+        "     💡 Run 'git x line-endings --fix' to add recommended attributes and renormalize",
+    ),
+    // summary
+    ("summary.title", "📊 Repository Summary"),
+    (
+        "summary.upstream",
+        "🔗 Upstream: {upstream} ({ahead} ahead, {behind} behind)",
+    ),
+    (
+        "summary.upstream_current",
+        "🔗 Upstream: {upstream} (up to date)",
+    ),
+    ("summary.commits", "📈 Commits ({period}): {count}"),
+    ("summary.commits_failed", "📈 Commits: Unable to retrieve"),
+    ("summary.contributors", "👥 Top contributors ({period}): "),
+    ("summary.all_time", "all time"),
+    ("summary.contributor", "   {medal} {name} ({count} commits)"),
+    (
+        "summary.contributors_failed",
+        "👥 Contributors: Unable to retrieve",
+    ),
+    (
+        "summary.files",
+        "📁 Files: {count} total, {lines} lines ({code} non-blank)",
+    ),
+    ("summary.files_failed", "📁 Files: Unable to retrieve"),
+    ("summary.languages", "🗣️  Languages: {languages}"),
+    (
+        "summary.generated",
+        "🙈 {count} generated or vendored file(s) not counted (--include-generated to count them)",
+    ),
+    ("summary.filters", "🔎 Filters: {filters}"),
+    ("summary.no_commits", "📅 No commits found since {since}"),
+    ("summary.since", "📅 Commit Summary since {since}:"),
+    (
+        "summary.since_by_author",
+        "📅 Commit Summary since {since} by author:",
+    ),
+    ("summary.author", " 👤 {author} ({count} commits)"),
+    ("summary.by", "(by {author}, {time})"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("repository", "🗂️  Repository: {name}"),
+    ("current_branch", "📍 Aktueller Branch: {branch}"),
+    ("detached_at", "📍 Losgelöster HEAD bei {commit}"),
+    ("upstream", "🔗 Upstream: {upstream}"),
+    (
+        "drift",
+        "{behind} Commit(s) und {days} Tag(e) hinter {baseline}",
+    ),
+    // info
+    ("info.commit", "📝 Commit: {subject}"),
+    ("info.rebasing", "🔄 Rebase läuft"),
+    (
+        "info.unreachable",
+        "⚠️  {count} Commit(s) hier liegen auf keinem Branch; behalte sie mit 'git x attach <name>'",
+    ),
+    (
+        "info.attach_hint",
+        "💡 Starte hier einen Branch mit 'git x attach <name>'",
+    ),
+    ("info.ahead", "{count} voraus"),
+    ("info.behind", "{count} zurück"),
+    ("info.status", "📊 Status: {status}"),
+    ("info.up_to_date", "✅ Status: Aktuell"),
+    ("info.no_upstream", "❌ Kein Upstream konfiguriert"),
+    ("info.clean", "✅ Arbeitsverzeichnis: Sauber"),
+    ("info.dirty", "⚠️  Arbeitsverzeichnis: Hat Änderungen"),
+    ("info.staged_none", "📋 Vorgemerkte Dateien: Keine"),
+    ("info.staged", "📋 Vorgemerkte Dateien: {count} Datei(en)"),
+    ("info.recent_activity", "📋 Letzte Aktivität:"),
+    ("info.recent_branches", "🕒 Zuletzt genutzte Branches:"),
+    (
+        "info.pr_open",
+        "✅ Offener PR für den aktuellen Branch gefunden",
+    ),
+    (
+        "info.pr_none",
+        "❌ Kein offener PR für den aktuellen Branch",
+    ),
+    (
+        "info.pr_offline",
+        "📴 Pull-Request-Status: offline nicht verfügbar",
+    ),
+    ("info.baselines", "📊 Vergleichsbranches:"),
+    ("info.column.baseline", "Vergleich"),
+    ("info.column.ahead", "Voraus"),
+    ("info.column.behind", "Zurück"),
+    ("info.column.status", "Status"),
+    ("info.baseline.up_to_date", "aktuell"),
+    ("info.baseline.ahead", "voraus"),
+    ("info.baseline.behind", "zurück"),
+    ("info.baseline.diverged", "auseinandergelaufen"),
+    (
+        "info.drifting",
+        "⏳ Veraltet: {drift} (Grenze: {commits} Commits oder {days} Tage)",
+    ),
+    (
+        "info.catch_up",
+        "💡 Hole auf mit 'git rebase {baseline}' oder 'git merge {baseline}'",
+    ),
+    (
+        "info.wip",
+        "🚧 {count} WIP-Commit(s) auf diesem Branch, zuletzt {commit}; 'git x unwip' holt ihn ins Arbeitsverzeichnis zurück",
+    ),
+    // health
+    ("health.title", "🏥 Zustand des Repositorys"),
+    (
+        "health.title_parallel",
+        "🏥 Zustand des Repositorys (parallel)",
+    ),
+    ("health.starting", "Starte Prüfung..."),
+    ("health.complete", "Prüfung abgeschlossen!"),
+    ("health.healthy", "🎉 Das Repository ist in gutem Zustand!"),
+    ("health.found", "🔧 {count} Problem(e) gefunden:"),
+    ("health.config.progress", "Prüfe Git-Konfiguration..."),
+    ("health.config.ok", "✅ Git-Konfiguration: OK"),
+    (
+        "health.config.issues",
+        "❌ Git-Konfiguration: Probleme gefunden",
+    ),
+    ("health.remotes.progress", "Prüfe Remotes..."),
+    ("health.remotes.ok", "✅ Remotes: OK"),
+    ("health.remotes.issues", "⚠️  Remotes: Probleme gefunden"),
+    ("health.branches.progress", "Analysiere Branches..."),
+    ("health.branches.ok", "✅ Branches: OK"),
+    ("health.branches.issues", "⚠️  Branches: Probleme gefunden"),
+    ("health.workdir.progress", "Prüfe Arbeitsverzeichnis..."),
+    ("health.workdir.ok", "✅ Arbeitsverzeichnis: Sauber"),
+    (
+        "health.workdir.issues",
+        "ℹ️  Arbeitsverzeichnis: Hinweise vorhanden",
+    ),
+    (
+        "health.size.progress",
+        "Analysiere Größe des Repositorys...",
+    ),
+    ("health.size.ok", "✅ Größe des Repositorys: OK"),
+    ("health.size.issues", "⚠️  Größe des Repositorys: Groß"),
+    (
+        "health.security.progress",
+        "Suche nach Sicherheitsproblemen...",
+    ),
+    (
+        "health.security.ok",
+        "✅ Sicherheit: Keine offensichtlichen Probleme",
+    ),
+    (
+        "health.security.issues",
+        "⚠️  Sicherheit: Mögliche Probleme gefunden",
+    ),
+    ("health.gitignore.progress", "Prüfe .gitignore..."),
+    ("health.gitignore.ok", "✅ .gitignore: Sieht gut aus"),
+    (
+        "health.gitignore.issues",
+        "⚠️  .gitignore: Vorschläge vorhanden",
+    ),
+    ("health.binary.progress", "Analysiere Binärdateien..."),
+    ("health.binary.ok", "✅ Binärdateien: OK"),
+    (
+        "health.binary.issues",
+        "⚠️  Binärdateien: Überprüfung empfohlen",
+    ),
+    ("health.lfs.progress", "Prüfe Git LFS..."),
+    ("health.lfs.ok", "✅ Git LFS: OK"),
+    ("health.lfs.issues", "⚠️  Git LFS: Probleme gefunden"),
+    ("health.line_endings.progress", "Prüfe Zeilenenden..."),
+    ("health.line_endings.ok", "✅ Zeilenenden: Einheitlich"),
+    (
+        "health.line_endings.issues",
+        "⚠️  Zeilenenden: CRLF committet",
+    ),
+    (
+        "health.no_user_name",
+        "❌ Git user.name ist nicht konfiguriert",
+    ),
+    (
+        "health.no_user_email",
+        "❌ Git user.email ist nicht konfiguriert",
+    ),
+    ("health.no_remotes", "⚠️  Keine Remotes konfiguriert"),
+    (
+        "health.remotes_failed",
+        "❌ Remotes konnten nicht geprüft werden",
+    ),
+    (
+        "health.many_branches",
+        "⚠️  Viele lokale Branches ({count}) - Aufräumen empfohlen",
+    ),
+    (
+        "health.branches_failed",
+        "❌ Branches konnten nicht geprüft werden",
+    ),
+    (
+        "health.stale",
+        "⚠️  {count} möglicherweise veraltete Branches gefunden",
+    ),
+    ("health.drifted", "⚠️  {branch} ist veraltet: {drift}"),
+    (
+        "health.more_drifting",
+        "⚠️  … und {count} weitere(r) Branch(es) hinter {baseline}",
+    ),
+    (
+        "health.untracked",
+        "⚠️  {count} nicht versionierte Dateien gefunden",
+    ),
+    (
+        "health.staged",
+        "ℹ️  {count} Dateien für den Commit vorgemerkt",
+    ),
+    (
+        "health.large_repository",
+        "⚠️  Größe des Repositorys: {size} (Aufräumen empfohlen)",
+    ),
+    (
+        "health.sensitive_commits",
+        "🔒 {count} möglicherweise sensible Commit-Nachricht(en) gefunden:",
+    ),
+    (
+        "health.sensitive_files",
+        "🔐 {count} möglicherweise sensible Datei(en) im Repository:",
+    ),
+    (
+        "health.rm_secrets_hint",
+        "     💡 Entferne sie mit 'git x rm-secrets <path>' aus der Historie",
+    ),
+    (
+        "health.env_files",
+        "⚠️  {count} Umgebungsdatei(en) gefunden - stelle sicher, dass keine Geheimnisse committet sind:",
+    ),
+    ("health.item", "     • {item}"),
+    ("health.and_more", "     • ...und {count} weitere"),
+    ("health.no_gitignore", "📝 Keine .gitignore-Datei gefunden"),
+    (
+        "health.should_ignore",
+        "🗂️  {count} {kind} versioniert (in .gitignore aufnehmen?):",
+    ),
+    ("health.ignore.logs", "Logdateien"),
+    ("health.ignore.temporary", "temporäre Dateien"),
+    ("health.ignore.swap", "Swap-Dateien"),
+    ("health.ignore.backup", "Sicherungsdateien"),
+    ("health.ignore.macos", "macOS-Systemdateien"),
+    ("health.ignore.windows", "Windows-Systemdateien"),
+    ("health.ignore.node", "Node.js-Abhängigkeiten"),
+    ("health.ignore.rust", "Rust-Build-Artefakte"),
+    ("health.ignore.vscode", "VS-Code-Einstellungen"),
+    ("health.ignore.idea", "IntelliJ-Einstellungen"),
+    (
+        "health.binary_files",
+        "📦 {count} vermutliche Binärdateien versioniert (Git LFS für große Dateien erwägen)",
+    ),
+    (
+        "health.large_files",
+        "📏 {count} große Datei(en) > 1MB gefunden:",
+    ),
+    (
+        "health.limited",
+        "     • Analyse aus Leistungsgründen auf die ersten {count} Dateien begrenzt (--deep prüft alle)",
+    ),
+    (
+        "health.lfs_failed",
+        "❌ Git LFS konnte nicht geprüft werden",
+    ),
+    (
+        "health.line_endings_failed",
+        "❌ Zeilenenden konnten nicht geprüft werden",
+    ),
+    ("health.skipped", "⏭️  {check}: mit --fast übersprungen"),
+    (
+        "health.cached",
+        "💾 Ergebnisse für {commit} aus dem Cache (mit --no-cache erneut prüfen)",
+    ),
+    (
+        "health.offline",
+        "📴 Offline: Remote-Branches wie zuletzt abgerufen, LFS-Push-Status nicht verfügbar",
+    ),
+    (
+        "health.history_secrets",
+        "🔑 {count} mögliche(s) Geheimnis(se) irgendwo in der Historie hinzugefügt:",
+    ),
+    (
+        "lfs.not_installed",
+        "🗄️  .gitattributes nutzt LFS, aber git-lfs ist nicht installiert",
+    ),
+    (
+        "lfs.uncovered",
+        "📦 {count} große Binärdatei(en) nicht von LFS erfasst:",
+    ),
+    (
+        "lfs.not_downloaded",
+        "⬇️  {count} LFS-Datei(en) nicht heruntergeladen ('git lfs pull' ausführen)",
+    ),
+    (
+        "lfs.pending",
+        "⬆️  {count} LFS-Objekt(e) warten auf den Push",
+    ),
+    (
+        "line_endings.mixed",
+        "🔀 {count} Datei(en) mischen LF- und CRLF-Zeilenenden:",
+    ),
+    (
+        "line_endings.crlf",
+        "↩️  {count} mit CRLF committete Datei(en) würden normalisiert:",
+    ),
+    (
+        "line_endings.no_attributes",
+        "📝 {count} Textdatei(en) ohne text- oder eol-Einstellung in .gitattributes folgen dem core.autocrlf jedes Rechners",
+    ),
+    (
+        "line_endings.fix_hint",
+        "     💡 'git x line-endings --fix' ergänzt empfohlene Attribute und normalisiert",
+    ),
+    // summary
+    ("summary.title", "📊 Zusammenfassung des Repositorys"),
+    (
+        "summary.upstream",
+        "🔗 Upstream: {upstream} ({ahead} voraus, {behind} zurück)",
+    ),
+    (
+        "summary.upstream_current",
+        "🔗 Upstream: {upstream} (aktuell)",
+    ),
+    ("summary.commits", "📈 Commits ({period}): {count}"),
+    ("summary.commits_failed", "📈 Commits: Nicht ermittelbar"),
+    ("summary.contributors", "👥 Top-Mitwirkende ({period}): "),
+    ("summary.all_time", "gesamt"),
+    ("summary.contributor", "   {medal} {name} ({count} Commits)"),
+    (
+        "summary.contributors_failed",
+        "👥 Mitwirkende: Nicht ermittelbar",
+    ),
+    (
+        "summary.files",
+        "📁 Dateien: {count} insgesamt, {lines} Zeilen ({code} nicht leer)",
+    ),
+    ("summary.files_failed", "📁 Dateien: Nicht ermittelbar"),
+    ("summary.languages", "🗣️  Sprachen: {languages}"),
+    (
+        "summary.generated",
+        "🙈 {count} generierte oder mitgelieferte Datei(en) nicht gezählt (--include-generated zählt sie mit)",
+    ),
+    ("summary.filters", "🔎 Filter: {filters}"),
+    ("summary.no_commits", "📅 Keine Commits seit {since}"),
+    ("summary.since", "📅 Commits seit {since}:"),
+    (
+        "summary.since_by_author",
+        "📅 Commits seit {since} nach Autor:",
+    ),
+    ("summary.author", " 👤 {author} ({count} Commits)"),
+    ("summary.by", "(von {author}, {time})"),
+];
@@ -1,5 +1,5 @@
 use crate::{GitXError, Result};
-use dialoguer::{Confirm, FuzzySelect, Input};
+use dialoguer::{Confirm, FuzzySelect, Input, MultiSelect};
 use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 
 /// Interactive utilities with fuzzy search capabilities
@@ -63,6 +63,25 @@ impl Interactive {
         Ok(branches[selection].clone())
     }
 
+    /// Show a multi-selection menu, returning the indices of the chosen items
+    pub fn multi_select<T: ToString>(items: &[T], prompt: &str) -> Result<Vec<usize>> {
+        MultiSelect::new()
+            .with_prompt(prompt)
+            .items(items)
+            .interact()
+            .map_err(|e| GitXError::GitCommand(format!("Selection cancelled: {e}")))
+    }
+
+    /// Like [`Self::multi_select`], with every item ticked to begin with
+    pub fn multi_select_all<T: ToString>(items: &[T], prompt: &str) -> Result<Vec<usize>> {
+        MultiSelect::new()
+            .with_prompt(prompt)
+            .items(items)
+            .defaults(&vec![true; items.len()])
+            .interact()
+            .map_err(|e| GitXError::GitCommand(format!("Selection cancelled: {e}")))
+    }
+
     /// Get text input with validation
     pub fn text_input(
         prompt: &str,
@@ -114,7 +133,7 @@ impl Interactive {
             .collect();
 
         // Sort by score (highest first)
-        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results.sort_by_key(|r| std::cmp::Reverse(r.1));
 
         if let Some(limit) = limit {
             results.truncate(limit);
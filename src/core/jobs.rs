@@ -0,0 +1,38 @@
+use crate::core::config::Config;
+use std::sync::OnceLock;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+static GIT_PROCESSES: OnceLock<Semaphore> = OnceLock::new();
+
+/// How much git-x runs at once: rayon's thread pool, the async runtime's
+/// workers and the git processes spawned concurrently
+pub struct Jobs;
+
+impl Jobs {
+    /// `--jobs`, else `git-x.jobs`, else one per CPU; 0 also means one per CPU
+    pub fn resolve(flag: Option<usize>) -> usize {
+        flag.or_else(|| Config::get("jobs").and_then(|jobs| jobs.parse().ok()))
+            .filter(|jobs| *jobs > 0)
+            .unwrap_or_else(Self::available)
+    }
+
+    /// Number of CPUs, or 1 when it can't be determined
+    pub fn available() -> usize {
+        std::thread::available_parallelism().map_or(1, |cpus| cpus.get())
+    }
+
+    /// Size rayon's global pool and cap concurrent async git processes
+    ///
+    /// Only the first call has any effect, so it must run before any parallel work.
+    pub fn configure(jobs: usize) {
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global();
+        let _ = GIT_PROCESSES.set(Semaphore::new(jobs));
+    }
+
+    /// A slot to run one git process, held until it exits; `None` when unlimited
+    pub async fn git_process() -> Option<SemaphorePermit<'static>> {
+        GIT_PROCESSES.get()?.acquire().await.ok()
+    }
+}
@@ -0,0 +1,220 @@
+use crate::Result;
+use crate::core::git::GitOperations;
+use crate::core::i18n::Message;
+use std::io::Read;
+use std::path::Path;
+
+/// First line of every Git LFS pointer file
+pub const POINTER_HEADER: &str = "version https://git-lfs.github.com/spec/v1";
+
+/// Files at or above this size are considered large
+pub const LARGE_FILE_BYTES: u64 = 1_000_000;
+
+/// Snapshot of how the repository uses Git LFS
+#[derive(Debug, Clone, Default)]
+pub struct LfsStatus {
+    /// Whether the `git lfs` extension is available
+    pub installed: bool,
+    /// `.gitattributes` patterns routed through the lfs filter
+    pub patterns: Vec<String>,
+    /// Large binary files that are not covered by an LFS pattern
+    pub uncovered: Vec<(String, u64)>,
+    /// LFS files whose working copy is still a pointer
+    pub not_downloaded: Vec<String>,
+    /// Objects waiting to be pushed; `None` when it couldn't be determined
+    pub pending_pushes: Option<Vec<String>>,
+}
+
+impl LfsStatus {
+    /// Problems worth reporting, formatted like the other health issues
+    pub fn issues(&self) -> Vec<String> {
+        self.messages().iter().map(Message::english).collect()
+    }
+
+    /// [`issues`](Self::issues), to be rendered in the user's language
+    pub fn messages(&self) -> Vec<Message> {
+        let mut issues = Vec::new();
+
+        if !self.installed && !self.patterns.is_empty() {
+            issues.push(Message::new("lfs.not_installed"));
+        }
+
+        if !self.uncovered.is_empty() {
+            issues.push(Message::new("lfs.uncovered").arg("count", self.uncovered.len()));
+            for (file, size) in self.uncovered.iter().take(10) {
+                let size_mb = *size as f64 / 1_000_000.0;
+                issues.push(
+                    Message::new("health.item").arg("item", format!("{file} ({size_mb:.1} MB)")),
+                );
+            }
+            if self.uncovered.len() > 10 {
+                issues
+                    .push(Message::new("health.and_more").arg("count", self.uncovered.len() - 10));
+            }
+        }
+
+        if !self.not_downloaded.is_empty() {
+            issues.push(Message::new("lfs.not_downloaded").arg("count", self.not_downloaded.len()));
+        }
+
+        if let Some(pushes) = &self.pending_pushes
+            && !pushes.is_empty()
+        {
+            issues.push(Message::new("lfs.pending").arg("count", pushes.len()));
+        }
+
+        issues
+    }
+}
+
+/// Git LFS awareness that works with or without the git-lfs extension installed
+pub struct Lfs;
+
+impl Lfs {
+    /// Patterns using the lfs filter in `.gitattributes` content
+    pub fn patterns_from(attributes: &str) -> Vec<String> {
+        attributes
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?;
+                parts
+                    .any(|attr| attr == "filter=lfs")
+                    .then(|| pattern.to_string())
+            })
+            .collect()
+    }
+
+    /// Patterns from the root `.gitattributes` and any tracked nested ones
+    pub fn tracked_patterns() -> Result<Vec<String>> {
+        let root = GitOperations::repo_root()?;
+        let mut files = vec![".gitattributes".to_string()];
+        files.extend(
+            GitOperations::run(&["-C", &root, "ls-files", "*.gitattributes"])?
+                .lines()
+                .filter(|file| *file != ".gitattributes")
+                .map(String::from),
+        );
+
+        let mut patterns = Vec::new();
+        for file in files {
+            let Ok(content) = std::fs::read_to_string(Path::new(&root).join(&file)) else {
+                continue;
+            };
+            let prefix = file.trim_end_matches(".gitattributes");
+            patterns.extend(
+                Self::patterns_from(&content)
+                    .into_iter()
+                    .map(|pattern| format!("{prefix}{pattern}")),
+            );
+        }
+
+        Ok(patterns)
+    }
+
+    /// Whether the repository routes anything through LFS
+    pub fn is_used() -> bool {
+        Self::tracked_patterns().is_ok_and(|patterns| !patterns.is_empty())
+    }
+
+    /// Whether the `git lfs` extension is available
+    pub fn is_installed() -> bool {
+        GitOperations::run(&["lfs", "version"]).is_ok()
+    }
+
+    /// Whether content is an LFS pointer rather than the real file
+    pub fn is_pointer(content: &[u8]) -> bool {
+        content.starts_with(POINTER_HEADER.as_bytes())
+    }
+
+    /// Heuristic binary check: a NUL byte in the first 8000 bytes, as git does
+    pub fn is_binary(content: &[u8]) -> bool {
+        content.iter().take(8000).any(|b| *b == 0)
+    }
+
+    /// Tracked files with their lfs filter status, relative to the current directory
+    fn files_with_filter() -> Result<Vec<(String, bool)>> {
+        let files = GitOperations::run(&["ls-files", "-z"])?;
+        if files.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let output =
+            GitOperations::run_with_input(&["check-attr", "-z", "--stdin", "filter"], &files)?;
+
+        // -z output is a flat list of path, attribute, value triples
+        let fields: Vec<String> = output.split('\0').map(String::from).collect();
+        Ok(fields
+            .chunks(3)
+            .filter(|triple| triple.len() == 3)
+            .map(|triple| (triple[0].clone(), triple[2] == "lfs"))
+            .collect())
+    }
+
+    /// Read the start of a file for pointer/binary sniffing
+    fn read_head(path: &str) -> Option<Vec<u8>> {
+        let mut buffer = vec![0; 8000];
+        let mut file = std::fs::File::open(path).ok()?;
+        let read = file.read(&mut buffer).ok()?;
+        buffer.truncate(read);
+        Some(buffer)
+    }
+
+    /// LFS objects the next push would upload, if git-lfs can tell us
+    fn pending_pushes() -> Option<Vec<String>> {
+        let upstream = GitOperations::upstream_branch().ok()?;
+        let (remote, branch) = upstream.split_once('/')?;
+        let output = GitOperations::run(&["lfs", "push", "--dry-run", remote, branch]).ok()?;
+        Some(
+            output
+                .lines()
+                .filter_map(|line| line.strip_prefix("push "))
+                .map(String::from)
+                .collect(),
+        )
+    }
+
+    /// Collect the full LFS status for the current repository
+    pub fn status() -> Result<LfsStatus> {
+        let installed = Self::is_installed();
+        let patterns = Self::tracked_patterns()?;
+
+        let mut uncovered = Vec::new();
+        let mut not_downloaded = Vec::new();
+        for (file, is_lfs) in Self::files_with_filter()? {
+            let Ok(metadata) = std::fs::metadata(&file) else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+
+            if is_lfs {
+                if Self::read_head(&file).is_some_and(|head| Self::is_pointer(&head)) {
+                    not_downloaded.push(file);
+                }
+            } else if metadata.len() >= LARGE_FILE_BYTES
+                && Self::read_head(&file).is_some_and(|head| Self::is_binary(&head))
+            {
+                uncovered.push((file, metadata.len()));
+            }
+        }
+        uncovered.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+        let pending_pushes = if installed && !patterns.is_empty() {
+            Self::pending_pushes()
+        } else {
+            None
+        };
+
+        Ok(LfsStatus {
+            installed,
+            patterns,
+            uncovered,
+            not_downloaded,
+            pending_pushes,
+        })
+    }
+}
@@ -0,0 +1,193 @@
+use crate::Result;
+use crate::core::git::GitOperations;
+use crate::core::i18n::Message;
+use std::path::Path;
+
+/// Attributes `line-endings --fix` adds: LF everywhere except Windows scripts
+pub const RECOMMENDED_ATTRIBUTES: &str =
+    "* text=auto eol=lf\n*.bat text eol=crlf\n*.cmd text eol=crlf\n";
+
+/// Line endings of one tracked file, as reported by `git ls-files --eol`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEol {
+    pub path: String,
+    /// Line endings in the index: `lf`, `crlf`, `mixed`, `none`, or `-text` for binary
+    pub index: String,
+    /// Line endings in the working tree, in the same notation
+    pub worktree: String,
+    /// The `text`/`eol` attributes in effect, e.g. `text=auto eol=lf`; empty when none
+    pub attributes: String,
+}
+
+impl FileEol {
+    /// Parse `git ls-files --eol -z` output
+    pub fn parse(output: &str) -> Vec<Self> {
+        output
+            .split('\0')
+            .filter_map(|entry| {
+                let (info, path) = entry.split_once('\t')?;
+                let mut fields = info.split_whitespace();
+                let index = fields.next()?.strip_prefix("i/")?.to_string();
+                let worktree = fields.next()?.strip_prefix("w/")?.to_string();
+                let attributes = fields
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .strip_prefix("attr/")?
+                    .to_string();
+                Some(Self {
+                    path: path.to_string(),
+                    index,
+                    worktree,
+                    attributes,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether the attributes mark the file as binary (`-text`)
+    pub fn is_binary(&self) -> bool {
+        self.index == "-text" || self.attributes.split_whitespace().any(|a| a == "-text")
+    }
+
+    /// Whether the attributes set `text` or `eol` for this file
+    pub fn has_eol_attributes(&self) -> bool {
+        self.attributes
+            .split_whitespace()
+            .any(|a| a == "text" || a.starts_with("text=") || a.starts_with("eol="))
+    }
+}
+
+/// Line-ending problems in the tracked files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineEndingStatus {
+    /// Number of tracked text files
+    pub text_files: usize,
+    /// Files mixing LF and CRLF, in the index or the working tree
+    pub mixed: Vec<String>,
+    /// Text files committed with CRLF that `git add --renormalize` would convert
+    pub renormalize: Vec<String>,
+    /// Text files `.gitattributes` sets neither `text` nor `eol` for
+    pub unattributed: usize,
+    /// The local `core.autocrlf` setting, if any
+    pub autocrlf: Option<String>,
+}
+
+impl LineEndingStatus {
+    /// Summarize parsed `git ls-files --eol` entries
+    pub fn from_files(files: &[FileEol]) -> Self {
+        let text: Vec<&FileEol> = files.iter().filter(|file| !file.is_binary()).collect();
+        Self {
+            text_files: text.len(),
+            mixed: text
+                .iter()
+                .filter(|file| file.index == "mixed" || file.worktree == "mixed")
+                .map(|file| file.path.clone())
+                .collect(),
+            renormalize: text
+                .iter()
+                .filter(|file| file.index == "crlf" || file.index == "mixed")
+                .map(|file| file.path.clone())
+                .collect(),
+            unattributed: text
+                .iter()
+                .filter(|file| !file.has_eol_attributes())
+                .count(),
+            autocrlf: None,
+        }
+    }
+
+    /// Whether CRLF has made it into the repository
+    pub fn has_crlf(&self) -> bool {
+        !self.mixed.is_empty() || !self.renormalize.is_empty()
+    }
+
+    /// Whether there is nothing for `--fix` to do
+    pub fn is_clean(&self) -> bool {
+        self.unattributed == 0 && !self.has_crlf()
+    }
+
+    /// Problems worth reporting, formatted like the other health issues
+    ///
+    /// Missing attributes only count once CRLF has actually been committed,
+    /// so LF-only repositories without a `.gitattributes` stay healthy.
+    pub fn messages(&self) -> Vec<Message> {
+        let mut issues = Vec::new();
+        if !self.has_crlf() {
+            return issues;
+        }
+
+        if !self.mixed.is_empty() {
+            issues.push(Message::new("line_endings.mixed").arg("count", self.mixed.len()));
+            push_items(&mut issues, &self.mixed);
+        }
+
+        if !self.renormalize.is_empty() {
+            issues.push(Message::new("line_endings.crlf").arg("count", self.renormalize.len()));
+            push_items(&mut issues, &self.renormalize);
+        }
+
+        if self.unattributed > 0 {
+            issues.push(Message::new("line_endings.no_attributes").arg("count", self.unattributed));
+        }
+
+        issues.push(Message::new("line_endings.fix_hint"));
+        issues
+    }
+}
+
+fn push_items(issues: &mut Vec<Message>, items: &[String]) {
+    for item in items.iter().take(5) {
+        issues.push(Message::new("health.item").arg("item", item));
+    }
+    if items.len() > 5 {
+        issues.push(Message::new("health.and_more").arg("count", items.len() - 5));
+    }
+}
+
+/// Line-ending detection and normalization
+pub struct LineEndings;
+
+impl LineEndings {
+    /// Collect the line-ending status of the current repository
+    pub fn status() -> Result<LineEndingStatus> {
+        let output = GitOperations::run(&["ls-files", "--eol", "-z"])?;
+        let mut status = LineEndingStatus::from_files(&FileEol::parse(&output));
+        status.autocrlf = GitOperations::run(&["config", "core.autocrlf"])
+            .ok()
+            .filter(|value| !value.is_empty());
+        Ok(status)
+    }
+
+    /// Append [`RECOMMENDED_ATTRIBUTES`] to the root `.gitattributes`
+    pub fn write_attributes(root: &str) -> Result<()> {
+        let path = Path::new(root).join(".gitattributes");
+        let mut content = std::fs::read_to_string(&path).unwrap_or_default();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        content.push_str("# Line endings (added by git x line-endings --fix)\n");
+        content.push_str(RECOMMENDED_ATTRIBUTES);
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Stage `.gitattributes` and re-add every tracked file under the new attributes
+    ///
+    /// Returns the files whose staged content changed.
+    pub fn renormalize(root: &str) -> Result<Vec<String>> {
+        if Path::new(root).join(".gitattributes").exists() {
+            GitOperations::run_status(&["-C", root, "add", ".gitattributes"])?;
+        }
+        GitOperations::run_status(&["-C", root, "add", "--renormalize", "."])?;
+        Ok(
+            GitOperations::run(&["-C", root, "diff", "--cached", "--name-only"])?
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect(),
+        )
+    }
+}
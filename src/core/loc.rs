@@ -0,0 +1,250 @@
+use crate::Result;
+use crate::core::export::ExportValue;
+use crate::core::git::GitOperations;
+use crate::core::lfs::Lfs;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Languages recognised by file extension, then by well-known file names
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("py", "Python"),
+    ("go", "Go"),
+    ("js", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("scala", "Scala"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("hpp", "C++"),
+    ("cs", "C#"),
+    ("swift", "Swift"),
+    ("rb", "Ruby"),
+    ("php", "PHP"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("hs", "Haskell"),
+    ("lua", "Lua"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("fish", "Shell"),
+    ("ps1", "PowerShell"),
+    ("sql", "SQL"),
+    ("html", "HTML"),
+    ("htm", "HTML"),
+    ("css", "CSS"),
+    ("scss", "CSS"),
+    ("sass", "CSS"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("md", "Markdown"),
+    ("markdown", "Markdown"),
+    ("rst", "reStructuredText"),
+    ("txt", "Text"),
+    ("toml", "TOML"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("json", "JSON"),
+    ("xml", "XML"),
+    ("proto", "Protocol Buffers"),
+    ("tf", "Terraform"),
+    ("nix", "Nix"),
+];
+
+const FILE_NAMES: &[(&str, &str)] = &[
+    ("Makefile", "Makefile"),
+    ("Dockerfile", "Dockerfile"),
+    ("Jenkinsfile", "Groovy"),
+    ("Rakefile", "Ruby"),
+    ("Gemfile", "Ruby"),
+];
+
+/// Language of a path, from its extension or file name
+///
+/// Unknown extensions are reported as themselves (`.xyz`), files without one as `Other`.
+pub fn language(path: &str) -> String {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    if let Some((_, language)) = FILE_NAMES.iter().find(|(name, _)| *name == file_name) {
+        return language.to_string();
+    }
+    match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && !extension.is_empty() => {
+            let extension = extension.to_ascii_lowercase();
+            EXTENSIONS
+                .iter()
+                .find(|(known, _)| *known == extension)
+                .map(|(_, language)| language.to_string())
+                .unwrap_or_else(|| format!(".{extension}"))
+        }
+        _ => "Other".to_string(),
+    }
+}
+
+/// Lines in text content, counting a last line without a newline
+pub fn count_lines(content: &[u8]) -> (usize, usize) {
+    let mut lines = 0;
+    let mut blank = 0;
+    for line in content.split(|&byte| byte == b'\n') {
+        lines += 1;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            blank += 1;
+        }
+    }
+    // The empty piece after a trailing newline isn't a line
+    if content.is_empty() || content.ends_with(b"\n") {
+        lines -= 1;
+        blank -= 1;
+    }
+    (lines, blank)
+}
+
+/// Line counts of one language
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageStats {
+    pub language: String,
+    pub files: usize,
+    pub lines: usize,
+    /// Lines with nothing but whitespace
+    pub blank: usize,
+}
+
+impl LanguageStats {
+    /// Lines with something on them
+    pub fn code(&self) -> usize {
+        self.lines - self.blank
+    }
+}
+
+/// Lines of code across the tracked files, per language
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinesOfCode {
+    /// Largest line count first
+    pub languages: Vec<LanguageStats>,
+    pub binary_files: usize,
+}
+
+impl LinesOfCode {
+    /// Count every tracked file of the current repository
+    pub fn tracked() -> Result<Self> {
+        Self::tracked_in(".")
+    }
+
+    /// Count the tracked files below `dir`, relative to the repository root
+    pub fn tracked_in(dir: &str) -> Result<Self> {
+        let root = GitOperations::repo_root()?;
+        let files = GitOperations::run(&["-C", &root, "ls-files", "-z", "--", dir])?;
+        let paths: Vec<&str> = files.split('\0').filter(|p| !p.is_empty()).collect();
+        Ok(Self::count(Path::new(&root), &paths))
+    }
+
+    /// Count `paths` under `root` in parallel; unreadable files (e.g. deleted) are skipped
+    pub fn count(root: &Path, paths: &[&str]) -> Self {
+        let counted: Vec<Option<(String, usize, usize)>> = paths
+            .par_iter()
+            .filter_map(|path| {
+                let content = std::fs::read(root.join(path)).ok()?;
+                if Lfs::is_binary(&content) {
+                    return Some(None);
+                }
+                let (lines, blank) = count_lines(&content);
+                Some(Some((language(path), lines, blank)))
+            })
+            .collect();
+
+        let mut by_language: HashMap<String, LanguageStats> = HashMap::new();
+        let mut binary_files = 0;
+        for entry in counted {
+            let Some((language, lines, blank)) = entry else {
+                binary_files += 1;
+                continue;
+            };
+            let stats = by_language
+                .entry(language.clone())
+                .or_insert_with(|| LanguageStats {
+                    language,
+                    ..LanguageStats::default()
+                });
+            stats.files += 1;
+            stats.lines += lines;
+            stats.blank += blank;
+        }
+
+        let mut languages: Vec<LanguageStats> = by_language.into_values().collect();
+        languages.sort_by(|a, b| {
+            b.lines
+                .cmp(&a.lines)
+                .then_with(|| a.language.cmp(&b.language))
+        });
+        Self {
+            languages,
+            binary_files,
+        }
+    }
+
+    pub fn total_lines(&self) -> usize {
+        self.languages.iter().map(|l| l.lines).sum()
+    }
+
+    pub fn total_code(&self) -> usize {
+        self.languages.iter().map(LanguageStats::code).sum()
+    }
+
+    /// `Rust 12,340 (71%), Markdown 2,100 (12%)` for the `top` largest languages
+    pub fn describe_top(&self, top: usize) -> String {
+        let total = self.total_lines().max(1);
+        self.languages
+            .iter()
+            .take(top)
+            .map(|stats| {
+                format!(
+                    "{} {} ({}%)",
+                    stats.language,
+                    thousands(stats.lines),
+                    stats.lines * 100 / total
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn to_value(&self) -> ExportValue {
+        ExportValue::List(
+            self.languages
+                .iter()
+                .map(|stats| {
+                    ExportValue::record(vec![
+                        ("language", ExportValue::from(stats.language.as_str())),
+                        ("files", stats.files.into()),
+                        ("lines", stats.lines.into()),
+                        ("code", stats.code().into()),
+                        ("blank", stats.blank.into()),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// `12345` as `12,345`
+pub fn thousands(value: usize) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    out
+}
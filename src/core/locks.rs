@@ -0,0 +1,328 @@
+use crate::core::config::Config;
+use crate::core::export::ExportValue;
+use crate::core::git::GitOperations;
+use crate::core::lfs::Lfs;
+use crate::{GitXError, Result};
+
+/// An advisory lock on one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileLock {
+    /// Path from the repository root
+    pub path: String,
+    pub owner: String,
+    /// Owner's email; empty for LFS locks, which only carry a name
+    pub email: String,
+    /// Unix time the lock was taken
+    pub locked_at: i64,
+    /// Held by the current user
+    pub ours: bool,
+}
+
+impl FileLock {
+    /// Parse the lock file kept on [`LockStore::REF`]: `path\temail\towner\tunix time` per line
+    pub fn parse_list(content: &str, email: &str) -> Vec<Self> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let path = fields.next()?.to_string();
+                let lock_email = fields.next()?.to_string();
+                Some(Self {
+                    ours: lock_email == email,
+                    owner: fields.next()?.to_string(),
+                    locked_at: fields.next()?.parse().ok()?,
+                    email: lock_email,
+                    path,
+                })
+            })
+            .collect()
+    }
+
+    /// Render locks in the format [`FileLock::parse_list`] reads, sorted by path
+    pub fn render_list(locks: &[Self]) -> String {
+        let mut lines: Vec<String> = locks
+            .iter()
+            .map(|lock| {
+                format!(
+                    "{}\t{}\t{}\t{}\n",
+                    lock.path, lock.email, lock.owner, lock.locked_at
+                )
+            })
+            .collect();
+        lines.sort();
+        lines.concat()
+    }
+
+    /// Parse `git lfs locks --json`, or the `{"ours": [...], "theirs": [...]}` that
+    /// `--verify` prints
+    pub fn parse_lfs(json: &str, name: &str) -> Result<Vec<Self>> {
+        let value = ExportValue::parse_json(json)?;
+        let lock = |item: &ExportValue, ours: Option<bool>| -> Option<Self> {
+            let owner = item
+                .get("owner")
+                .and_then(|owner| owner.get("name"))
+                .and_then(ExportValue::as_str)
+                .unwrap_or("")
+                .to_string();
+            Some(Self {
+                path: item.get("path")?.as_str()?.to_string(),
+                ours: ours.unwrap_or(owner == name),
+                owner,
+                email: String::new(),
+                locked_at: item
+                    .get("locked_at")
+                    .and_then(ExportValue::as_str)
+                    .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+                    .map(|time| time.timestamp())
+                    .unwrap_or(0),
+            })
+        };
+
+        if value.get("ours").is_some() || value.get("theirs").is_some() {
+            let side = |key: &str, ours: bool| {
+                value
+                    .get(key)
+                    .map(|list| list.items().to_vec())
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|item| lock(item, Some(ours)))
+                    .collect::<Vec<_>>()
+            };
+            let mut locks = side("ours", true);
+            locks.extend(side("theirs", false));
+            return Ok(locks);
+        }
+        Ok(value
+            .items()
+            .iter()
+            .filter_map(|item| lock(item, None))
+            .collect())
+    }
+
+    /// Locks held by someone else on any of `paths`
+    pub fn conflicts<'a>(locks: &'a [Self], paths: &[String]) -> Vec<&'a Self> {
+        locks
+            .iter()
+            .filter(|lock| !lock.ours && paths.contains(&lock.path))
+            .collect()
+    }
+}
+
+/// Where locks are kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockStore {
+    /// A lock file on a ref shared through a remote
+    Ref { remote: String },
+    /// The Git LFS locking API of the LFS server
+    Lfs,
+}
+
+impl LockStore {
+    /// Ref holding the lock file, on the remote and as a local cache
+    pub const REF: &'static str = "refs/git-x/locks";
+    const FILE: &'static str = "locks";
+    /// Attempts at updating the shared ref when others update it at the same time
+    const ATTEMPTS: usize = 3;
+
+    /// The configured store: `git-x.lock.backend` is `ref`, `lfs` or `auto` (LFS when the
+    /// repository uses it and git-lfs is installed)
+    pub fn configured() -> Result<Self> {
+        let remote = Config::get("lock.remote").unwrap_or_else(|| "origin".to_string());
+        let backend = Config::get("lock.backend").unwrap_or_else(|| "auto".to_string());
+        match backend.as_str() {
+            "lfs" => Ok(Self::Lfs),
+            "ref" => Ok(Self::Ref { remote }),
+            "auto" if Lfs::is_used() && Lfs::is_installed() => Ok(Self::Lfs),
+            "auto" => Ok(Self::Ref { remote }),
+            other => Err(GitXError::Parse(format!(
+                "Unknown git-x.lock.backend '{other}', expected auto, ref or lfs"
+            ))),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Ref { remote } => format!("{} on {remote}", Self::REF),
+            Self::Lfs => "Git LFS locks".to_string(),
+        }
+    }
+
+    /// Current locks; `offline` reads the local copy without asking the remote
+    pub fn list(&self, offline: bool) -> Result<Vec<FileLock>> {
+        match self {
+            Self::Ref { remote } => {
+                let tip = if offline {
+                    GitOperations::run(&["rev-parse", "--verify", "--quiet", Self::REF]).ok()
+                } else {
+                    Self::fetch(remote)?
+                };
+                Ok(Self::read(tip.as_deref(), &identity()?.1))
+            }
+            Self::Lfs => {
+                let name = GitOperations::run(&["config", "user.name"]).unwrap_or_default();
+                let json = GitOperations::run(&["lfs", "locks", "--verify", "--json"])
+                    .or_else(|_| GitOperations::run(&["lfs", "locks", "--json"]))?;
+                FileLock::parse_lfs(&json, &name)
+            }
+        }
+    }
+
+    /// Lock every path, or none of them when someone else holds one
+    pub fn take(&self, paths: &[String]) -> Result<Vec<FileLock>> {
+        match self {
+            Self::Ref { remote } => {
+                let (owner, email) = identity()?;
+                self.update(remote, &format!("Lock {}", paths.join(", ")), |locks| {
+                    let taken = FileLock::conflicts(locks, paths);
+                    if let Some(lock) = taken.first() {
+                        return Err(GitXError::GitCommand(format!(
+                            "{} is locked by {}",
+                            lock.path, lock.owner
+                        )));
+                    }
+                    let now = chrono::Utc::now().timestamp();
+                    for path in paths {
+                        if !locks.iter().any(|lock| &lock.path == path) {
+                            locks.push(FileLock {
+                                path: path.clone(),
+                                owner: owner.clone(),
+                                email: email.clone(),
+                                locked_at: now,
+                                ours: true,
+                            });
+                        }
+                    }
+                    Ok(())
+                })?;
+                Ok(self
+                    .list(true)?
+                    .into_iter()
+                    .filter(|lock| paths.contains(&lock.path))
+                    .collect())
+            }
+            Self::Lfs => {
+                let name = GitOperations::run(&["config", "user.name"]).unwrap_or_default();
+                let mut taken = Vec::new();
+                for path in paths {
+                    let json = GitOperations::run(&["lfs", "lock", "--json", path])?;
+                    let item = format!("[{json}]");
+                    taken.extend(
+                        FileLock::parse_lfs(&item, &name)?
+                            .into_iter()
+                            .map(|lock| FileLock { ours: true, ..lock }),
+                    );
+                }
+                Ok(taken)
+            }
+        }
+    }
+
+    /// Unlock `paths`; `force` breaks locks held by someone else
+    pub fn release(&self, paths: &[String], force: bool) -> Result<()> {
+        match self {
+            Self::Ref { remote } => {
+                self.update(remote, &format!("Unlock {}", paths.join(", ")), |locks| {
+                    if !force && let Some(lock) = FileLock::conflicts(locks, paths).first() {
+                        return Err(GitXError::GitCommand(format!(
+                            "{} is locked by {}; use --force to break the lock",
+                            lock.path, lock.owner
+                        )));
+                    }
+                    locks.retain(|lock| !paths.contains(&lock.path));
+                    Ok(())
+                })
+            }
+            Self::Lfs => {
+                for path in paths {
+                    let mut args = vec!["lfs", "unlock", path.as_str()];
+                    if force {
+                        args.push("--force");
+                    }
+                    GitOperations::run(&args)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Fetch the shared ref into the local copy; `None` when nobody has locked anything yet
+    fn fetch(remote: &str) -> Result<Option<String>> {
+        let advertised = GitOperations::run(&["ls-remote", remote, Self::REF]).map_err(|_| {
+            GitXError::GitCommand(format!(
+                "Cannot reach '{remote}' to read locks; set git-x.lock.remote to the shared remote"
+            ))
+        })?;
+        if advertised.is_empty() {
+            let _ = GitOperations::run(&["update-ref", "-d", Self::REF]);
+            return Ok(None);
+        }
+        GitOperations::run(&[
+            "fetch",
+            "--quiet",
+            "--no-tags",
+            remote,
+            &format!("+{}:{}", Self::REF, Self::REF),
+        ])?;
+        GitOperations::run(&["rev-parse", Self::REF]).map(Some)
+    }
+
+    fn read(tip: Option<&str>, email: &str) -> Vec<FileLock> {
+        tip.and_then(|tip| {
+            GitOperations::run(&["cat-file", "blob", &format!("{tip}:{}", Self::FILE)]).ok()
+        })
+        .map(|content| FileLock::parse_list(&content, email))
+        .unwrap_or_default()
+    }
+
+    /// Change the lock file and push it, starting over when someone else pushed first
+    fn update(
+        &self,
+        remote: &str,
+        message: &str,
+        change: impl Fn(&mut Vec<FileLock>) -> Result<()>,
+    ) -> Result<()> {
+        let (_, email) = identity()?;
+        for _ in 0..Self::ATTEMPTS {
+            let tip = Self::fetch(remote)?;
+            let mut locks = Self::read(tip.as_deref(), &email);
+            change(&mut locks)?;
+
+            let blob = GitOperations::run_with_input(
+                &["hash-object", "-w", "--stdin"],
+                &FileLock::render_list(&locks),
+            )?;
+            let tree = GitOperations::run_with_input(
+                &["mktree"],
+                &format!("100644 blob {blob}\t{}\n", Self::FILE),
+            )?;
+            let mut args = vec!["commit-tree", tree.as_str(), "-m", message];
+            if let Some(tip) = &tip {
+                args.extend(["-p", tip.as_str()]);
+            }
+            let commit = GitOperations::run(&args)?;
+
+            // Not forced, so the push fails if the ref moved since the fetch
+            let refspec = format!("{commit}:{}", Self::REF);
+            if GitOperations::run(&["push", "--quiet", remote, &refspec]).is_ok() {
+                GitOperations::run(&["update-ref", Self::REF, &commit])?;
+                return Ok(());
+            }
+        }
+        Err(GitXError::GitCommand(format!(
+            "Could not update {} on {remote}; check you can push there and try again",
+            Self::REF
+        )))
+    }
+}
+
+/// The current user as (name, email), which locks on the shared ref are recorded under
+fn identity() -> Result<(String, String)> {
+    let email = GitOperations::run(&["config", "user.email"])
+        .ok()
+        .filter(|email| !email.is_empty())
+        .ok_or_else(|| {
+            GitXError::Other("Set user.email so locks can say who holds them".to_string())
+        })?;
+    let name = GitOperations::run(&["config", "user.name"]).unwrap_or_else(|_| email.clone());
+    Ok((name, email))
+}
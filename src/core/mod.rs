@@ -1,8 +1,41 @@
+pub mod branch_notes;
+pub mod browser;
+pub mod cache;
+pub mod checkpoints;
+pub mod ci;
+pub mod clipboard;
+pub mod config;
+pub mod connection;
+pub mod conventional;
+pub mod dependencies;
+pub mod diff_renderer;
+pub mod export;
+pub mod generated;
 pub mod git;
+pub mod i18n;
 pub mod interactive;
+pub mod jobs;
+pub mod lfs;
+pub mod line_endings;
+pub mod loc;
+pub mod locks;
+pub mod network;
 pub mod output;
+pub mod packages;
+pub mod period;
+pub mod rebase;
+pub mod report;
+pub mod repos;
 pub mod safety;
+pub mod secrets;
+pub mod sparse;
+pub mod stash_meta;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod tickets;
+pub mod toml;
 pub mod traits;
 pub mod validation;
+pub mod wip;
 
 pub use traits::*;
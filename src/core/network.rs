@@ -0,0 +1,97 @@
+use crate::core::config::Config;
+use crate::{GitXError, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Environment variable that turns on offline mode, e.g. for a whole CI job
+pub const OFFLINE_ENV: &str = "GIT_X_OFFLINE";
+
+/// Git subcommands that talk to a remote
+const GIT_NETWORK_COMMANDS: &[&str] = &["fetch", "pull", "push", "ls-remote", "clone"];
+
+/// `git lfs` subcommands that talk to the LFS server
+const LFS_NETWORK_COMMANDS: &[&str] = &["fetch", "pull", "push", "lock", "locks", "unlock"];
+
+/// Offline mode, where every network operation is skipped instead of left to time out
+///
+/// Fetches, pushes and `ls-remote` probes are refused by [`GitOperations`](crate::core::git::GitOperations)
+/// itself; `gh` and HTTP calls check [`Network::require`] first. Paths to local
+/// repositories still work.
+pub struct Network;
+
+impl Network {
+    /// `--offline`, else a non-empty [`OFFLINE_ENV`] other than `0`, else `git-x.offline`
+    pub fn resolve(flag: bool) -> bool {
+        flag || std::env::var(OFFLINE_ENV).is_ok_and(|value| !value.is_empty() && value != "0")
+            || Config::get_bool("offline").unwrap_or(false)
+    }
+
+    /// Turn offline mode on or off for the rest of the process
+    pub fn configure(offline: bool) {
+        OFFLINE.store(offline, Ordering::Relaxed);
+    }
+
+    pub fn is_offline() -> bool {
+        OFFLINE.load(Ordering::Relaxed)
+    }
+
+    /// Err when offline, naming the operation that needed the network
+    pub fn require(operation: &str) -> Result<()> {
+        if Self::is_offline() {
+            Err(GitXError::Offline(operation.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Err when offline and `git <args>` would reach a remote
+    pub fn check_git(args: &[&str]) -> Result<()> {
+        if Self::is_offline() && Self::is_network_command(args) {
+            Err(GitXError::Offline(format!("git {}", args.join(" "))))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether `git <args>` talks to a remote; fetching from or pushing to a
+    /// local path doesn't count
+    pub fn is_network_command(args: &[&str]) -> bool {
+        let mut rest = args.iter().copied();
+        // Global options such as `-C <dir>` come before the subcommand
+        let Some(command) = (loop {
+            match rest.next() {
+                Some("-C" | "-c" | "--git-dir" | "--work-tree") => {
+                    rest.next();
+                }
+                Some(option) if option.starts_with('-') => {}
+                other => break other,
+            }
+        }) else {
+            return false;
+        };
+
+        let operands: Vec<&str> = rest.filter(|arg| !arg.starts_with('-')).collect();
+        match command {
+            "lfs" => operands
+                .first()
+                .is_some_and(|sub| LFS_NETWORK_COMMANDS.contains(sub)),
+            "remote" => operands.first() == Some(&"update"),
+            "submodule" => operands.first() == Some(&"update"),
+            command if GIT_NETWORK_COMMANDS.contains(&command) => {
+                !operands.first().is_some_and(|remote| is_local(remote))
+            }
+            _ => false,
+        }
+    }
+
+    /// Output line marking a section left out while offline
+    pub fn unavailable(section: &str) -> String {
+        format!("📴 {section}: unavailable offline")
+    }
+}
+
+/// A `file://` URL or a path to a repository on this machine
+fn is_local(remote: &str) -> bool {
+    remote.starts_with("file://") || std::path::Path::new(remote).exists()
+}
@@ -1,6 +1,54 @@
+use crate::core::export::ExportValue;
 use console::style;
 use std::fmt;
 
+/// Destination for command output, so slow commands can show results as they go
+pub trait OutputSink {
+    /// Write one line of human-readable output
+    fn line(&mut self, line: String);
+
+    /// Write one structured result; text sinks ignore these
+    fn record(&mut self, _record: ExportValue) {}
+}
+
+/// Prints every line as soon as it is written
+pub struct StdoutSink;
+
+impl OutputSink for StdoutSink {
+    fn line(&mut self, line: String) {
+        println!("{line}");
+    }
+}
+
+/// Collects structured records and ignores text, for `--json` output
+#[derive(Debug, Default)]
+pub struct JsonSink {
+    records: Vec<ExportValue>,
+}
+
+impl JsonSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn records(&self) -> &[ExportValue] {
+        &self.records
+    }
+
+    /// All records as a JSON array
+    pub fn to_json(&self) -> String {
+        ExportValue::List(self.records.clone()).to_json()
+    }
+}
+
+impl OutputSink for JsonSink {
+    fn line(&mut self, _line: String) {}
+
+    fn record(&mut self, record: ExportValue) {
+        self.records.push(record);
+    }
+}
+
 /// Buffered output utility for better performance
 pub struct BufferedOutput {
     lines: Vec<String>,
@@ -57,6 +105,13 @@ impl BufferedOutput {
     }
 }
 
+/// The capture-to-string sink: what `Command::execute` returns
+impl OutputSink for BufferedOutput {
+    fn line(&mut self, line: String) {
+        self.add_line(line);
+    }
+}
+
 impl Default for BufferedOutput {
     fn default() -> Self {
         Self::new()
@@ -104,82 +159,212 @@ impl Format {
     }
 }
 
+/// How a table is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableLayout {
+    /// Aligned columns, with the widest text columns truncated to fit `width` if set
+    Aligned { width: Option<usize> },
+    /// One tab-separated line per row without styling, for pipes and scripts
+    Tabs,
+}
+
+impl TableLayout {
+    /// Aligned to the terminal's width on a terminal, tab-separated when piped
+    pub fn detect() -> Self {
+        if !atty::is(atty::Stream::Stdout) {
+            return Self::Tabs;
+        }
+        let width = std::env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse().ok())
+            .or_else(|| {
+                console::Term::stdout()
+                    .size_checked()
+                    .map(|(_, columns)| columns as usize)
+            });
+        Self::Aligned { width }
+    }
+}
+
 /// Output formatters for different data types
 pub struct TableFormatter {
     headers: Vec<String>,
     rows: Vec<Vec<String>>,
+    layout: Option<TableLayout>,
 }
 
 impl TableFormatter {
+    /// Columns are never truncated below this many characters
+    const MIN_WIDTH: usize = 8;
+    const GAP: &'static str = "  ";
+
     pub fn new(headers: Vec<String>) -> Self {
         Self {
             headers,
             rows: Vec::new(),
+            layout: None,
         }
     }
 
+    /// Lay the table out this way in [`TableFormatter::render`] instead of detecting it
+    pub fn with_layout(mut self, layout: TableLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
     pub fn add_row(&mut self, row: Vec<String>) {
         self.rows.push(row);
     }
 
+    /// Aligned columns at their full width
     pub fn format(&self) -> String {
+        self.layout_as(TableLayout::Aligned { width: None })
+    }
+
+    /// Fitted to the terminal, or tab-separated when stdout isn't one
+    pub fn render(&self) -> String {
+        self.layout_as(self.layout.unwrap_or_else(TableLayout::detect))
+    }
+
+    fn layout_as(&self, layout: TableLayout) -> String {
         if self.rows.is_empty() {
             return "No data to display".to_string();
         }
+        match layout {
+            TableLayout::Tabs => self.tabs(),
+            TableLayout::Aligned { width } => self.aligned(width),
+        }
+    }
 
-        let mut output = String::new();
+    fn tabs(&self) -> String {
+        std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| console::strip_ansi_codes(cell).replace(['\t', '\n'], " "))
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .map(|line| format!("{line}\n"))
+            .collect()
+    }
+
+    /// Numeric columns (every cell a number, such as `42`, `-3`, `+10` or `12.5%`) are
+    /// right-aligned and never truncated
+    fn numeric_columns(&self) -> Vec<bool> {
+        (0..self.headers.len())
+            .map(|column| {
+                let mut cells = self
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| console::strip_ansi_codes(cell).trim().to_string())
+                    .filter(|cell| !cell.is_empty())
+                    .peekable();
+                cells.peek().is_some()
+                    && cells.all(|cell| {
+                        let number = cell.trim_start_matches(['+', '-']).trim_end_matches('%');
+                        !number.is_empty() && number.parse::<f64>().is_ok()
+                    })
+            })
+            .collect()
+    }
 
-        // Calculate column widths
-        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.len()).collect();
+    /// Natural width of each column, narrowed widest-first until the table fits `limit`
+    fn widths(&self, numeric: &[bool], limit: Option<usize>) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .map(|h| console::measure_text_width(h))
+            .collect();
         for row in &self.rows {
             for (i, cell) in row.iter().enumerate() {
                 if i < widths.len() {
-                    widths[i] = widths[i].max(cell.len());
+                    widths[i] = widths[i].max(console::measure_text_width(cell));
                 }
             }
         }
 
-        // Format headers
-        for (i, header) in self.headers.iter().enumerate() {
-            if i > 0 {
-                output.push_str("  ");
+        let Some(limit) = limit else {
+            return widths;
+        };
+        let gaps = Self::GAP.len() * widths.len().saturating_sub(1);
+        while widths.iter().sum::<usize>() + gaps > limit {
+            let widest = (0..widths.len())
+                .filter(|&i| !numeric[i] && widths[i] > Self::MIN_WIDTH)
+                .max_by_key(|&i| widths[i]);
+            match widest {
+                Some(i) => widths[i] -= 1,
+                // Nothing left to narrow; let the terminal wrap
+                None => break,
             }
-            output.push_str(&format!(
-                "{:<width$}",
-                header,
-                width = widths.get(i).unwrap_or(&0)
-            ));
         }
-        output.push('\n');
+        widths
+    }
 
-        // Add separator
-        for (i, width) in widths.iter().enumerate() {
-            if i > 0 {
-                output.push_str("  ");
-            }
-            output.push_str(&"-".repeat(*width));
-        }
-        output.push('\n');
+    fn aligned(&self, limit: Option<usize>) -> String {
+        let numeric = self.numeric_columns();
+        let widths = self.widths(&numeric, limit);
+        let line = |cells: &[String]| {
+            widths
+                .iter()
+                .enumerate()
+                .map(|(i, &width)| {
+                    let cell = cells.get(i).map(String::as_str).unwrap_or("");
+                    pad(&truncate_middle(cell, width), width, numeric[i])
+                })
+                .collect::<Vec<_>>()
+                .join(Self::GAP)
+        };
 
-        // Format rows
+        let mut output = String::new();
+        output.push_str(&line(&self.headers));
+        output.push('\n');
+        let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        output.push_str(&separator.join(Self::GAP));
+        output.push('\n');
         for row in &self.rows {
-            for (i, cell) in row.iter().enumerate() {
-                if i > 0 {
-                    output.push_str("  ");
-                }
-                output.push_str(&format!(
-                    "{:<width$}",
-                    cell,
-                    width = widths.get(i).unwrap_or(&0)
-                ));
-            }
+            output.push_str(&line(row));
             output.push('\n');
         }
-
         output
     }
 }
 
+/// Shorten `text` to `width` characters by replacing its middle with `…`, which
+/// keeps both the prefix and the distinguishing end of names like
+/// `feature/JIRA-1234-retry-payments`
+pub fn truncate_middle(text: &str, width: usize) -> String {
+    if console::measure_text_width(text) <= width {
+        return text.to_string();
+    }
+    let chars: Vec<char> = console::strip_ansi_codes(text).chars().collect();
+    if chars.len() <= width {
+        return chars.into_iter().collect();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let keep = width - 1;
+    let head = keep.div_ceil(2);
+    let tail = keep / 2;
+    format!(
+        "{}…{}",
+        chars[..head].iter().collect::<String>(),
+        chars[chars.len() - tail..].iter().collect::<String>()
+    )
+}
+
+/// Pad `text` to `width` terminal columns, ignoring color codes
+fn pad(text: &str, width: usize, right: bool) -> String {
+    let fill = " ".repeat(width.saturating_sub(console::measure_text_width(text)));
+    if right {
+        format!("{fill}{text}")
+    } else {
+        format!("{text}{fill}")
+    }
+}
+
 /// Progress indicator for long-running operations
 pub struct ProgressIndicator {
     message: String,
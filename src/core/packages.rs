@@ -0,0 +1,254 @@
+use crate::core::dependencies::{Ecosystem, is_vendored};
+use crate::core::export::ExportValue;
+use crate::core::git::GitOperations;
+use crate::core::secrets::glob_match;
+use crate::{GitXError, Result};
+use std::path::Path;
+
+/// A package of a monorepo, declared by a workspace or its own `go.mod`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    pub name: String,
+    /// Directory relative to the repository root, without a trailing `/`
+    pub path: String,
+    pub ecosystem: Ecosystem,
+}
+
+impl Package {
+    /// Whether `query` is this package's name, its directory, or the last part of either
+    ///
+    /// Lets `api` pick `@acme/api`, `github.com/acme/shop/api` or `services/api`.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim_end_matches('/');
+        let last = |text: &str| text.rsplit('/').next().unwrap_or(text).to_string();
+        self.name == query
+            || self.path == query
+            || last(&self.name) == query
+            || last(&self.path) == query
+    }
+
+    /// `name (path)`, or just the path when they are the same
+    pub fn label(&self) -> String {
+        if self.name == self.path {
+            self.path.clone()
+        } else {
+            format!("{} ({})", self.name, self.path)
+        }
+    }
+}
+
+/// Package boundaries of a monorepo
+///
+/// Recognises Cargo workspace members, npm/yarn `workspaces` and pnpm
+/// `pnpm-workspace.yaml` globs, and every Go module below the root. A package
+/// at the repository root would cover everything, so it is never listed.
+pub struct Packages;
+
+impl Packages {
+    /// Packages of the current repository, sorted by path
+    pub fn detect() -> Result<Vec<Package>> {
+        let root = GitOperations::repo_root()?;
+        let tracked = GitOperations::run(&["-C", &root, "ls-files"])?;
+        let tracked: Vec<&str> = tracked.lines().collect();
+        Ok(Self::from_files(&tracked, |path| {
+            std::fs::read_to_string(Path::new(&root).join(path)).ok()
+        }))
+    }
+
+    /// Packages declared among the `tracked` paths, reading file contents through `read`
+    pub fn from_files(tracked: &[&str], read: impl Fn(&str) -> Option<String>) -> Vec<Package> {
+        let cargo_workspace = read("Cargo.toml")
+            .and_then(|manifest| crate::core::toml::parse(&manifest).ok())
+            .and_then(|manifest| {
+                let workspace = manifest.get("workspace")?;
+                Some((
+                    toml_strings(workspace, "members"),
+                    toml_strings(workspace, "exclude"),
+                ))
+            });
+
+        let mut npm_members = Vec::new();
+        if let Some(manifest) =
+            read("package.json").and_then(|manifest| ExportValue::parse_json(&manifest).ok())
+        {
+            let workspaces = manifest.get("workspaces");
+            let globs = workspaces
+                .and_then(|value| value.get("packages"))
+                .or(workspaces);
+            npm_members.extend(
+                globs
+                    .map(ExportValue::items)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(ExportValue::as_str)
+                    .map(str::to_string),
+            );
+        }
+        if let Some(workspace) = read("pnpm-workspace.yaml") {
+            npm_members.extend(pnpm_packages(&workspace));
+        }
+        let (npm_excludes, npm_members): (Vec<String>, Vec<String>) = npm_members
+            .into_iter()
+            .partition(|glob| glob.starts_with('!'));
+        let npm_excludes: Vec<String> = npm_excludes
+            .iter()
+            .map(|glob| glob[1..].to_string())
+            .collect();
+
+        let mut packages = Vec::new();
+        for manifest in tracked.iter().filter(|path| !is_vendored(path)) {
+            let Some(ecosystem) = Ecosystem::from_path(manifest) else {
+                continue;
+            };
+            let Some(dir) = manifest
+                .strip_suffix(ecosystem.manifest())
+                .and_then(|dir| dir.strip_suffix('/'))
+            else {
+                // At the root
+                continue;
+            };
+            let in_workspace = |members: &[String], excludes: &[String]| {
+                members.iter().any(|glob| dir_matches(glob, dir))
+                    && !excludes.iter().any(|glob| dir_matches(glob, dir))
+            };
+
+            let name = match ecosystem {
+                Ecosystem::Cargo => {
+                    let Some((members, excludes)) = &cargo_workspace else {
+                        continue;
+                    };
+                    if !in_workspace(members, excludes) {
+                        continue;
+                    }
+                    read(manifest)
+                        .and_then(|content| crate::core::toml::parse(&content).ok())
+                        .and_then(|value| {
+                            value
+                                .get("package")
+                                .and_then(|package| package.get("name"))
+                                .and_then(|n| n.as_str())
+                                .map(str::to_string)
+                        })
+                }
+                Ecosystem::Npm => {
+                    if !in_workspace(&npm_members, &npm_excludes) {
+                        continue;
+                    }
+                    read(manifest)
+                        .and_then(|content| ExportValue::parse_json(&content).ok())
+                        .and_then(|value| {
+                            value
+                                .get("name")
+                                .and_then(|n| n.as_str())
+                                .map(str::to_string)
+                        })
+                }
+                Ecosystem::Go => read(manifest).and_then(|content| go_module(&content)),
+                Ecosystem::Python => continue,
+            };
+
+            packages.push(Package {
+                name: name.unwrap_or_else(|| dir.rsplit('/').next().unwrap_or(dir).to_string()),
+                path: dir.to_string(),
+                ecosystem,
+            });
+        }
+
+        packages.sort_by(|a, b| a.path.cmp(&b.path));
+        packages
+    }
+
+    /// The package `query` names, see [`Package::matches`]
+    pub fn find(query: &str) -> Result<Package> {
+        Self::select(Self::detect()?, query)
+    }
+
+    /// Pick the package `query` names: an exact name or path wins over a partial match
+    pub fn select(packages: Vec<Package>, query: &str) -> Result<Package> {
+        if packages.is_empty() {
+            return Err(GitXError::Other(
+                "No workspace packages found (Cargo and npm/pnpm/yarn workspaces and Go modules are recognised)"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(exact) = packages
+            .iter()
+            .find(|package| package.name == query || package.path == query.trim_end_matches('/'))
+        {
+            return Ok(exact.clone());
+        }
+
+        let mut matching: Vec<Package> = packages
+            .into_iter()
+            .filter(|package| package.matches(query))
+            .collect();
+        match matching.len() {
+            0 => Err(GitXError::Other(format!(
+                "No package named '{query}'; run 'git x packages' to list them"
+            ))),
+            1 => Ok(matching.remove(0)),
+            _ => Err(GitXError::Other(format!(
+                "'{query}' matches several packages: {}",
+                matching
+                    .iter()
+                    .map(Package::label)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+}
+
+/// Whether a workspace glob such as `crates/*` or `apps/**` covers `dir`
+///
+/// Unlike [`glob_match`], `*` stays within one path segment and `**` spans any number.
+pub fn dir_matches(glob: &str, dir: &str) -> bool {
+    let glob = glob.trim_start_matches("./").trim_end_matches('/');
+    let pattern: Vec<&str> = glob.split('/').collect();
+    let path: Vec<&str> = dir.split('/').collect();
+    segments_match(&pattern, &path)
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(first, remaining)| {
+            glob_match(segment, first) && segments_match(rest, remaining)
+        }),
+    }
+}
+
+/// Strings of an array value in a parsed TOML table
+fn toml_strings(table: &ExportValue, key: &str) -> Vec<String> {
+    table
+        .get(key)
+        .map(ExportValue::items)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(ExportValue::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Globs listed under `packages:` in `pnpm-workspace.yaml`
+fn pnpm_packages(yaml: &str) -> Vec<String> {
+    yaml.lines()
+        .skip_while(|line| line.trim_end() != "packages:")
+        .skip(1)
+        .take_while(|line| line.starts_with(' ') || line.starts_with('-') || line.trim().is_empty())
+        .filter_map(|line| line.trim().strip_prefix('-'))
+        .map(|glob| glob.trim().trim_matches(['"', '\'']).to_string())
+        .filter(|glob| !glob.is_empty())
+        .collect()
+}
+
+/// Module path declared by a `go.mod`
+fn go_module(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let module = line.trim().strip_prefix("module ")?;
+        let module = module.split("//").next().unwrap_or(module);
+        Some(module.trim().trim_matches('"').to_string())
+    })
+}
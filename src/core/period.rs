@@ -0,0 +1,204 @@
+use crate::core::git::GitOperations;
+use crate::{GitXError, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+
+/// A length of time given on the command line, such as `6m`, `2w` or `3 months`
+///
+/// Units are hours, days, weeks, months (30 days) and years (365 days); `m` is months,
+/// as ages are about stale work rather than minutes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Age {
+    seconds: i64,
+    label: String,
+}
+
+impl Age {
+    const UNITS: &'static [(&'static [&'static str], i64, &'static str)] = &[
+        (&["h", "hour", "hours"], 3_600, "hour"),
+        (&["d", "day", "days"], 86_400, "day"),
+        (&["w", "week", "weeks"], 86_400 * 7, "week"),
+        (&["m", "mo", "month", "months"], 86_400 * 30, "month"),
+        (&["y", "year", "years"], 86_400 * 365, "year"),
+    ];
+
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// Unix time this long before `now`
+    pub fn before(&self, now: i64) -> i64 {
+        now - self.seconds
+    }
+}
+
+impl std::str::FromStr for Age {
+    type Err = GitXError;
+
+    fn from_str(text: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            GitXError::Parse(format!(
+                "Invalid age '{text}'; use a number and a unit, e.g. 12h, 30d, 2w, 6m or 1y"
+            ))
+        };
+        let text = text.trim();
+        let split = text
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let amount: i64 = text[..split].parse().map_err(|_| invalid())?;
+        let unit = text[split..].trim().to_lowercase();
+        let (_, seconds, name) = Self::UNITS
+            .iter()
+            .find(|(names, _, _)| names.contains(&unit.as_str()))
+            .ok_or_else(invalid)?;
+
+        let plural = if amount == 1 { "" } else { "s" };
+        Ok(Self {
+            seconds: amount.checked_mul(*seconds).ok_or_else(invalid)?,
+            label: format!("{amount} {name}{plural}"),
+        })
+    }
+}
+
+impl std::fmt::Display for Age {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+/// A point in time given to `--since` or `--until`
+///
+/// Accepts an age (`2w`, `3 months`, `3 months ago`), `now`, `today` or `yesterday`,
+/// an ISO date or date-time (`2025-07-01`, `2025-07-01T09:30`, RFC 3339), and
+/// `last-tag` or `last-release` for the date of the latest tag reachable from HEAD.
+/// Dates without a time start at local midnight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeSpec {
+    timestamp: i64,
+    label: String,
+    /// The tag behind `last-tag` and `last-release`
+    reference: Option<String>,
+}
+
+impl TimeSpec {
+    /// Resolve `text` now, asking git for the tag behind `last-tag` and `last-release`
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let tag = match text.to_lowercase().as_str() {
+            "last-tag" => Some(Self::latest_tag(&[], "tag")?),
+            "last-release" => Some(Self::latest_tag(&["v[0-9]*", "[0-9]*"], "release tag")?),
+            _ => None,
+        };
+        match tag {
+            Some(tag) => {
+                let timestamp = GitOperations::run(&[
+                    "log",
+                    "-1",
+                    "--format=%ct",
+                    &format!("{tag}^{{commit}}"),
+                ])?
+                .parse()
+                .map_err(|_| GitXError::Parse(format!("Could not read the date of tag '{tag}'")))?;
+                Ok(Self {
+                    timestamp,
+                    label: tag.clone(),
+                    reference: Some(tag),
+                })
+            }
+            None => Self::parse_at(text, chrono::Local::now()),
+        }
+    }
+
+    /// Resolve `text` relative to `now`; `last-tag` and `last-release` need [`Self::parse`]
+    pub fn parse_at(text: &str, now: DateTime<Local>) -> Result<Self> {
+        let text = text.trim();
+        let invalid = || {
+            GitXError::Parse(format!(
+                "Invalid time '{text}'; use an age (2w, 3 months), a date (2025-07-01), \
+                 'yesterday', 'last-tag' or 'last-release'"
+            ))
+        };
+        let spec = |timestamp: i64, label: String| Self {
+            timestamp,
+            label,
+            reference: None,
+        };
+        let midnight = |date: NaiveDate| {
+            date.and_hms_opt(0, 0, 0)
+                .and_then(|time| Local.from_local_datetime(&time).earliest())
+                .map(|time| time.timestamp())
+        };
+
+        let lower = text.to_lowercase();
+        match lower.as_str() {
+            "now" => return Ok(spec(now.timestamp(), "now".to_string())),
+            "today" => {
+                let timestamp = midnight(now.date_naive()).ok_or_else(invalid)?;
+                return Ok(spec(timestamp, "today".to_string()));
+            }
+            "yesterday" => {
+                let date = now.date_naive().pred_opt().ok_or_else(invalid)?;
+                let timestamp = midnight(date).ok_or_else(invalid)?;
+                return Ok(spec(timestamp, "yesterday".to_string()));
+            }
+            _ => {}
+        }
+
+        let age_text = lower.strip_suffix("ago").unwrap_or(&lower).trim_end();
+        if let Ok(age) = age_text.parse::<Age>() {
+            return Ok(spec(age.before(now.timestamp()), format!("{age} ago")));
+        }
+
+        if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+            return Ok(spec(time.timestamp(), text.to_string()));
+        }
+        for format in [
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d %H:%M",
+        ] {
+            if let Ok(time) = NaiveDateTime::parse_from_str(text, format) {
+                let time = Local
+                    .from_local_datetime(&time)
+                    .earliest()
+                    .ok_or_else(invalid)?;
+                return Ok(spec(time.timestamp(), text.to_string()));
+            }
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+            return Ok(spec(midnight(date).ok_or_else(invalid)?, text.to_string()));
+        }
+        Err(invalid())
+    }
+
+    /// Unix time of this point
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// The tag this point came from, for `last-tag` and `last-release`
+    pub fn reference(&self) -> Option<&str> {
+        self.reference.as_deref()
+    }
+
+    /// Value for git's `--since`/`--until`, which take `@<unix time>` as is
+    pub fn git_arg(&self) -> String {
+        format!("@{}", self.timestamp)
+    }
+
+    /// Latest tag reachable from HEAD matching any of `patterns` (all tags when empty)
+    fn latest_tag(patterns: &[&str], kind: &str) -> Result<String> {
+        let mut args = vec!["describe", "--tags", "--abbrev=0"];
+        for pattern in patterns {
+            args.extend(["--match", pattern]);
+        }
+        GitOperations::run(&args)
+            .map_err(|_| GitXError::GitCommand(format!("No {kind} reachable from HEAD")))
+    }
+}
+
+impl std::fmt::Display for TimeSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
@@ -0,0 +1,262 @@
+use crate::core::git::{GitOperations, short_sha};
+use crate::{GitXError, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Why a commit the rebase stopped on is already upstream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppliedUpstream {
+    /// An upstream commit has the same patch, e.g. a cherry-pick or a rebased copy
+    Patch { upstream: String },
+    /// An upstream commit has the combined patch of this and neighbouring branch
+    /// commits, as a squash merge does; `commits` counts them all
+    Squashed { upstream: String, commits: usize },
+}
+
+impl AppliedUpstream {
+    pub fn upstream(&self) -> &str {
+        match self {
+            Self::Patch { upstream } | Self::Squashed { upstream, .. } => upstream,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let upstream = short_sha(self.upstream());
+        let subject = GitOperations::run(&["log", "-1", "--format=%s", self.upstream()])
+            .map(|subject| format!(" '{subject}'"))
+            .unwrap_or_default();
+        match self {
+            Self::Patch { .. } => format!("already upstream as {upstream}{subject}"),
+            Self::Squashed { commits, .. } => {
+                format!("one of {commits} commits squashed upstream into {upstream}{subject}")
+            }
+        }
+    }
+}
+
+/// A commit left out of the rebase because it was already applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedCommit {
+    pub commit: String,
+    pub subject: String,
+    pub reason: AppliedUpstream,
+}
+
+impl SkippedCommit {
+    pub fn describe(&self) -> String {
+        format!(
+            "⏭️  Skipped {} {} ({})",
+            short_sha(&self.commit),
+            self.subject,
+            self.reason.describe()
+        )
+    }
+}
+
+/// How a rebase ended up after skipping the commits that were already applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebaseOutcome {
+    Finished {
+        skipped: Vec<SkippedCommit>,
+    },
+    /// Stopped on a conflict that needs resolving by hand
+    Stopped {
+        skipped: Vec<SkippedCommit>,
+        commit: String,
+        subject: String,
+        conflicts: Vec<String>,
+    },
+}
+
+/// Skips the commits a rebase stops on when their changes are already upstream
+///
+/// Rebasing onto a branch that squash-merged your work, or picked some of it,
+/// conflicts on every such commit. A stopped commit is skipped when its patch id
+/// (`git patch-id --stable`) matches a commit on the new base, or when the combined
+/// patch of the branch commits around it does, as after a squash merge.
+#[derive(Debug, Clone)]
+pub struct RebaseSkipper {
+    /// Branch commits being rebased, oldest first
+    commits: Vec<String>,
+    /// Patch id of each commit on the new base since the branch forked
+    upstream: HashMap<String, String>,
+    quiet: bool,
+}
+
+impl RebaseSkipper {
+    /// Whether a rebase is stopped in this repository
+    pub fn in_progress() -> bool {
+        Self::state_dir().is_some()
+    }
+
+    fn state_dir() -> Option<PathBuf> {
+        ["rebase-merge", "rebase-apply"].iter().find_map(|dir| {
+            let path = PathBuf::from(GitOperations::run(&["rev-parse", "--git-path", dir]).ok()?);
+            path.is_dir().then_some(path)
+        })
+    }
+
+    /// Read what the rebase in progress is replaying and onto what
+    pub fn load() -> Result<Self> {
+        let dir = Self::state_dir()
+            .ok_or_else(|| GitXError::GitCommand("No rebase in progress".to_string()))?;
+        let read = |name: &str| -> Result<String> {
+            Ok(std::fs::read_to_string(dir.join(name))?.trim().to_string())
+        };
+        let onto = read("onto")?;
+        let orig_head = read("orig-head")?;
+        let base = GitOperations::run(&["merge-base", &onto, &orig_head])?;
+
+        let commits = GitOperations::run(&[
+            "rev-list",
+            "--reverse",
+            "--no-merges",
+            &format!("{base}..{orig_head}"),
+        ])?
+        .lines()
+        .map(String::from)
+        .collect();
+        let log = GitOperations::run(&[
+            "log",
+            "-p",
+            "--no-merges",
+            "--no-color",
+            "--no-ext-diff",
+            &format!("{base}..{onto}"),
+        ])?;
+        let upstream = patch_ids(&log)?.into_iter().collect();
+
+        Ok(Self {
+            commits,
+            upstream,
+            quiet: false,
+        })
+    }
+
+    /// Keep git's own output off the terminal
+    pub fn with_quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    /// Which upstream commit already holds the changes of `commit`, if any
+    pub fn applied_upstream(&self, commit: &str) -> Result<Option<AppliedUpstream>> {
+        if self.upstream.is_empty() {
+            return Ok(None);
+        }
+        let Some(index) = self.commits.iter().position(|c| c == commit) else {
+            // Not one of the branch's own commits: compare its patch alone
+            let patch = GitOperations::run(&["show", "--no-color", "--no-ext-diff", commit])?;
+            return Ok(self
+                .find(&patch)?
+                .map(|upstream| AppliedUpstream::Patch { upstream }));
+        };
+
+        // The commit alone, then runs of commits from the start of the branch or from
+        // this commit, which is how squash merges of a branch or of a stacked part look
+        let mut ranges = vec![(index, index)];
+        for end in index..self.commits.len() {
+            if end > 0 {
+                ranges.push((0, end));
+            }
+            if index > 0 && end > index {
+                ranges.push((index, end));
+            }
+        }
+        for (start, end) in ranges {
+            let patch = GitOperations::run(&[
+                "diff",
+                "--no-color",
+                "--no-ext-diff",
+                &format!("{}^", self.commits[start]),
+                &self.commits[end],
+            ])?;
+            if let Some(upstream) = self.find(&patch)? {
+                return Ok(Some(if start == end {
+                    AppliedUpstream::Patch { upstream }
+                } else {
+                    AppliedUpstream::Squashed {
+                        upstream,
+                        commits: end - start + 1,
+                    }
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn find(&self, patch: &str) -> Result<Option<String>> {
+        Ok(patch_ids(patch)?
+            .first()
+            .and_then(|(patch_id, _)| self.upstream.get(patch_id))
+            .cloned())
+    }
+
+    /// Skip stopped commits that are already upstream until the rebase finishes or
+    /// stops on a conflict that isn't; a stop with every conflict resolved continues
+    pub fn resolve(&self) -> Result<RebaseOutcome> {
+        let mut skipped = Vec::new();
+        while Self::in_progress() {
+            let commit = GitOperations::run(&["rev-parse", "--verify", "--quiet", "REBASE_HEAD"])
+                .map_err(|_| {
+                    GitXError::GitCommand(
+                        "The rebase stopped without a commit to apply (an 'edit' or 'break'); finish it with 'git rebase --continue'"
+                            .to_string(),
+                    )
+                })?;
+            let subject = GitOperations::run(&["log", "-1", "--format=%s", &commit])?;
+            let conflicts: Vec<String> =
+                GitOperations::run(&["diff", "--name-only", "--diff-filter=U"])?
+                    .lines()
+                    .map(String::from)
+                    .collect();
+
+            let step = if conflicts.is_empty() {
+                ["-c", "core.editor=true", "rebase", "--continue"]
+            } else {
+                match self.applied_upstream(&commit)? {
+                    Some(reason) => {
+                        skipped.push(SkippedCommit {
+                            commit: commit.clone(),
+                            subject,
+                            reason,
+                        });
+                        ["-c", "core.editor=true", "rebase", "--skip"]
+                    }
+                    None => {
+                        return Ok(RebaseOutcome::Stopped {
+                            skipped,
+                            commit,
+                            subject,
+                            conflicts,
+                        });
+                    }
+                }
+            };
+
+            // A failing step is the next commit conflicting, unless the rebase is stuck
+            if let Err(e) = GitOperations::run_status_with(&step, self.quiet) {
+                let moved =
+                    GitOperations::run(&["rev-parse", "--verify", "--quiet", "REBASE_HEAD"])
+                        .map_or(true, |next| next != commit);
+                if !Self::in_progress() || !moved {
+                    return Err(e);
+                }
+            }
+        }
+        Ok(RebaseOutcome::Finished { skipped })
+    }
+}
+
+/// `git patch-id --stable` of each commit in a patch series, as (patch id, commit)
+fn patch_ids(patch: &str) -> Result<Vec<(String, String)>> {
+    if patch.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let output = GitOperations::run_with_input(&["patch-id", "--stable"], patch)?;
+    Ok(output
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(patch_id, commit)| (patch_id.to_string(), commit.to_string()))
+        .collect())
+}
@@ -0,0 +1,200 @@
+use crate::{GitXError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Document formats a consolidated report can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// Names accepted by `FromStr`, in display order
+    pub fn names() -> Vec<&'static str> {
+        vec!["markdown", "html"]
+    }
+
+    /// Canonical lowercase name of the format
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "markdown",
+            ReportFormat::Html => "html",
+        }
+    }
+
+    /// Format implied by a file extension, `None` for anything unrecognised
+    pub fn from_path(path: &str) -> Option<Self> {
+        let (_, extension) = path.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            "html" | "htm" => Some(ReportFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = GitXError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(GitXError::Parse(format!(
+                "Unsupported report format: {s} (expected markdown or html)"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// One titled block of command output in a report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportSection {
+    pub title: String,
+    /// Plain text, shown preformatted
+    pub body: String,
+}
+
+impl ReportSection {
+    /// Link target for the table of contents, as GitHub derives it from a heading
+    pub fn anchor(&self) -> String {
+        self.title
+            .to_lowercase()
+            .chars()
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A titled document made of sections, rendered with a table of contents
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub title: String,
+    /// Lines shown under the title, e.g. when and where the report was generated
+    pub intro: Vec<String>,
+    pub sections: Vec<ReportSection>,
+}
+
+impl Report {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn add_intro(&mut self, line: impl Into<String>) -> &mut Self {
+        self.intro.push(line.into());
+        self
+    }
+
+    /// Add a section; terminal colours in `body` are removed
+    pub fn add_section(&mut self, title: impl Into<String>, body: &str) -> &mut Self {
+        self.sections.push(ReportSection {
+            title: title.into(),
+            body: console::strip_ansi_codes(body).trim_end().to_string(),
+        });
+        self
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.render_markdown(),
+            ReportFormat::Html => self.render_html(),
+        }
+    }
+
+    fn render_markdown(&self) -> String {
+        let mut out = vec![format!("# {}", self.title), String::new()];
+        if !self.intro.is_empty() {
+            out.extend(self.intro.iter().cloned());
+            out.push(String::new());
+        }
+
+        out.push("## Contents".to_string());
+        out.push(String::new());
+        for section in &self.sections {
+            out.push(format!("- [{}](#{})", section.title, section.anchor()));
+        }
+
+        for section in &self.sections {
+            out.push(String::new());
+            out.push(format!("## {}", section.title));
+            out.push(String::new());
+            out.push("```text".to_string());
+            out.push(section.body.clone());
+            out.push("```".to_string());
+        }
+
+        out.join("\n") + "\n"
+    }
+
+    fn render_html(&self) -> String {
+        let title = escape_html(&self.title);
+        let mut out = vec![
+            "<!DOCTYPE html>".to_string(),
+            "<html lang=\"en\">".to_string(),
+            "<head>".to_string(),
+            "<meta charset=\"utf-8\">".to_string(),
+            format!("<title>{title}</title>"),
+            "<style>body{font-family:sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem}pre{background:#f6f8fa;padding:1rem;overflow-x:auto}</style>".to_string(),
+            "</head>".to_string(),
+            "<body>".to_string(),
+            format!("<h1>{title}</h1>"),
+        ];
+        for line in &self.intro {
+            out.push(format!("<p>{}</p>", escape_html(line)));
+        }
+
+        out.push("<nav>".to_string());
+        out.push("<h2>Contents</h2>".to_string());
+        out.push("<ul>".to_string());
+        for section in &self.sections {
+            out.push(format!(
+                "<li><a href=\"#{}\">{}</a></li>",
+                section.anchor(),
+                escape_html(&section.title)
+            ));
+        }
+        out.push("</ul>".to_string());
+        out.push("</nav>".to_string());
+
+        for section in &self.sections {
+            out.push(format!(
+                "<h2 id=\"{}\">{}</h2>",
+                section.anchor(),
+                escape_html(&section.title)
+            ));
+            out.push(format!("<pre>{}</pre>", escape_html(&section.body)));
+        }
+
+        out.push("</body>".to_string());
+        out.push("</html>".to_string());
+        out.join("\n") + "\n"
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
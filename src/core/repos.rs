@@ -0,0 +1,226 @@
+use crate::core::config::Config;
+use crate::core::git::GitOperations;
+use crate::core::interactive::Interactive;
+use crate::{GitXError, Result};
+use std::path::{Path, PathBuf};
+
+/// Directory levels below each root searched for repositories, unless configured
+pub const DEFAULT_DISCOVERY_DEPTH: usize = 3;
+
+/// Directories never searched: dependencies and build output hold no repositories of yours
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "build", "dist"];
+
+/// Finds the git repositories under a set of directories, for commands that work
+/// across all of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoDiscovery {
+    roots: Vec<PathBuf>,
+    depth: usize,
+}
+
+impl RepoDiscovery {
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        Self {
+            roots,
+            depth: DEFAULT_DISCOVERY_DEPTH,
+        }
+    }
+
+    /// Search the given roots, else `git-x.repos.root`, else the current directory,
+    /// `git-x.repos.depth` levels deep
+    pub fn configured(roots: Vec<PathBuf>) -> Self {
+        let roots = if roots.is_empty() {
+            Config::get_list("repos.root")
+                .iter()
+                .map(|root| expand_home(root))
+                .collect()
+        } else {
+            roots
+        };
+        let roots = if roots.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            roots
+        };
+        let depth = Config::get("repos.depth")
+            .and_then(|depth| depth.parse().ok())
+            .unwrap_or(DEFAULT_DISCOVERY_DEPTH);
+        Self::new(roots).with_depth(depth)
+    }
+
+    /// Search this many directory levels below each root
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Every repository found, sorted by path
+    ///
+    /// A repository's own subdirectories aren't searched, so submodules and vendored
+    /// checkouts stay part of the repository that holds them.
+    pub fn discover(&self) -> Vec<PathBuf> {
+        let mut repos = Vec::new();
+        for root in &self.roots {
+            Self::visit(root, self.depth, &mut repos);
+        }
+        repos.sort();
+        repos.dedup();
+        repos
+    }
+
+    fn visit(dir: &Path, depth: usize, repos: &mut Vec<PathBuf>) {
+        // `.git` is a file in worktrees and submodules
+        if dir.join(".git").exists() {
+            repos.push(dir.to_path_buf());
+            return;
+        }
+        if depth == 0 {
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref())
+            })
+            .map(|entry| entry.path())
+            .collect();
+        children.sort();
+        for child in children {
+            Self::visit(&child, depth - 1, repos);
+        }
+    }
+}
+
+/// Expand a leading `~/` to the home directory, as git does for path settings
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Repositories git-x knows about on this machine, for `goto` and `repos list`
+///
+/// Kept as absolute paths in the multi-valued global `git-x.repos.known`, so
+/// `git for-each-repo --config=git-x.repos.known` can run a command in each.
+pub struct RepoRegistry;
+
+impl RepoRegistry {
+    /// Global config key holding the registered paths
+    pub const KEY: &'static str = "git-x.repos.known";
+
+    /// Registered repositories, in the order they were added
+    pub fn list() -> Vec<PathBuf> {
+        GitOperations::run(&["config", "--global", "--get-all", Self::KEY])
+            .map(|list| list.lines().map(PathBuf::from).collect())
+            .unwrap_or_default()
+    }
+
+    /// Register the repository at or above `path`, returning its root and whether it
+    /// was new
+    pub fn add(path: &Path) -> Result<(PathBuf, bool)> {
+        let dir = path.to_string_lossy();
+        let root = GitOperations::run(&["-C", dir.as_ref(), "rev-parse", "--show-toplevel"])
+            .map_err(|_| GitXError::GitCommand(format!("{dir} is not in a git repository")))?;
+        let root = PathBuf::from(root);
+        if Self::list().contains(&root) {
+            return Ok((root, false));
+        }
+        GitOperations::run(&[
+            "config",
+            "--global",
+            "--add",
+            Self::KEY,
+            &root.to_string_lossy(),
+        ])?;
+        Ok((root, true))
+    }
+
+    /// Forget a registered repository
+    pub fn remove(path: &Path) -> Result<()> {
+        GitOperations::run(&[
+            "config",
+            "--global",
+            "--fixed-value",
+            "--unset",
+            Self::KEY,
+            &path.to_string_lossy(),
+        ])
+        .map(|_| ())
+    }
+
+    /// Short name of a repository: its directory name
+    pub fn name(path: &Path) -> String {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string())
+    }
+
+    /// The repository `query` names: an exact or leading match of its name, else the best
+    /// fuzzy match of its name or path; a query with `/` matches the end of the path
+    pub fn find(query: &str, repos: &[PathBuf]) -> Result<PathBuf> {
+        let query = query.trim_end_matches('/');
+        let lower = query.to_lowercase();
+        let names: Vec<String> = repos.iter().map(|repo| Self::name(repo)).collect();
+        let paths: Vec<String> = repos
+            .iter()
+            .map(|repo| repo.display().to_string())
+            .collect();
+
+        let pick = |matches: Vec<usize>| -> Option<Result<PathBuf>> {
+            match matches.as_slice() {
+                [] => None,
+                [only] => Some(Ok(repos[*only].clone())),
+                many => Some(Err(GitXError::Other(format!(
+                    "'{query}' matches {} repositories: {}; add part of the path, e.g. {}",
+                    many.len(),
+                    many.iter()
+                        .map(|&i| paths[i].as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    repos[many[0]]
+                        .parent()
+                        .map(|parent| format!("{}/{}", Self::name(parent), names[many[0]]))
+                        .unwrap_or_else(|| paths[many[0]].clone())
+                )))),
+            }
+        };
+        let matching =
+            |test: &dyn Fn(usize) -> bool| (0..repos.len()).filter(|&i| test(i)).collect();
+
+        let exact = if query.contains('/') {
+            matching(&|i| paths[i].to_lowercase().ends_with(&format!("/{lower}")))
+        } else {
+            matching(&|i| names[i].to_lowercase() == lower)
+        };
+        if let Some(found) = pick(exact) {
+            return found;
+        }
+        if let Some(found) = pick(matching(&|i| names[i].to_lowercase().starts_with(&lower))) {
+            return found;
+        }
+
+        let candidates = if query.contains('/') { &paths } else { &names };
+        let ranked = Interactive::fuzzy_find(candidates, &lower, None);
+        let best: Vec<usize> = ranked
+            .iter()
+            .take_while(|(_, score)| Some(score) == ranked.first().map(|(_, best)| best))
+            .map(|(index, _)| *index)
+            .collect();
+        pick(best).unwrap_or_else(|| {
+            Err(GitXError::Other(format!(
+                "No known repository matches '{query}'; register one with 'git x repos add <path>' or 'git x repos scan <dir>'"
+            )))
+        })
+    }
+}
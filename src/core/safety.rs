@@ -1,6 +1,15 @@
-use crate::core::{git::GitOperations, interactive::Interactive};
+use crate::core::{
+    checkpoints::FileCheckpoints,
+    config::Config,
+    git::{GitOperations, short_sha},
+    interactive::Interactive,
+    stash_meta::StashMeta,
+};
 use crate::{GitXError, Result};
 
+/// Branches or stashes a deletion may remove before the user has to type a phrase to confirm
+pub const DEFAULT_TYPED_CONFIRMATION_THRESHOLD: usize = 10;
+
 /// Safety and backup utilities for destructive operations
 pub struct Safety;
 
@@ -62,6 +71,51 @@ impl Safety {
         Interactive::confirm(&prompt, false)
     }
 
+    /// Confirm deleting `count` branches or stashes
+    ///
+    /// Above `git-x.safety.typeToConfirm` items a yes/no prompt is too easy to accept
+    /// by reflex, so the user has to type a phrase such as `delete 12 branches`.
+    /// Non-interactive runs refuse such deletions unless `assume_yes` is set, which
+    /// also skips the prompt for smaller ones.
+    pub fn confirm_bulk_deletion(
+        operation: &str,
+        details: &str,
+        count: usize,
+        noun: &str,
+        assume_yes: bool,
+    ) -> Result<bool> {
+        if assume_yes {
+            return Ok(true);
+        }
+        if !Self::requires_typed_confirmation(count) {
+            return Self::confirm_destructive_operation(operation, details);
+        }
+
+        let phrase = Self::typed_confirmation_phrase(count, noun);
+        if !Interactive::is_interactive() {
+            return Err(GitXError::Other(format!(
+                "Refusing to {phrase} without typed confirmation; pass --yes to allow it in non-interactive runs"
+            )));
+        }
+
+        eprintln!("⚠️  {operation} This is a destructive operation.\n{details}");
+        let typed = Interactive::text_input(&format!("Type '{phrase}' to confirm"), None, None)?;
+        Ok(typed.trim() == phrase)
+    }
+
+    /// Whether deleting `count` items needs a typed phrase rather than yes/no; 0 turns it off
+    pub fn requires_typed_confirmation(count: usize) -> bool {
+        let threshold = Config::get("safety.typeToConfirm")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_TYPED_CONFIRMATION_THRESHOLD);
+        threshold > 0 && count > threshold
+    }
+
+    /// The phrase to type, e.g. `delete 12 branches`
+    pub fn typed_confirmation_phrase(count: usize, noun: &str) -> String {
+        format!("delete {count} {noun}")
+    }
+
     /// Check if we're in a test environment
     fn is_test_environment() -> bool {
         std::env::var("CARGO_TARGET_TMPDIR").is_ok() || std::env::var("CI").is_ok() || cfg!(test)
@@ -70,9 +124,10 @@ impl Safety {
     /// Create a safety checkpoint (stash) before operation
     pub fn create_checkpoint(message: Option<&str>) -> Result<String> {
         let checkpoint_msg = message.unwrap_or("git-x safety checkpoint");
+        let stash_msg = StashMeta::new("checkpoint").message(checkpoint_msg);
 
         let output = std::process::Command::new("git")
-            .args(["stash", "push", "-m", checkpoint_msg])
+            .args(["stash", "push", "-m", &stash_msg])
             .output()?;
 
         if !output.status.success() {
@@ -87,6 +142,7 @@ impl Safety {
 
     /// Restore from safety checkpoint if operation fails
     pub fn restore_checkpoint() -> Result<()> {
+        FileCheckpoints::before("restoring a safety checkpoint");
         let status = std::process::Command::new("git")
             .args(["stash", "pop"])
             .status()?;
@@ -230,6 +286,7 @@ impl SafetyBuilder {
         };
 
         if self.checkpoint_needed {
+            FileCheckpoints::before(&self.operation_name);
             Safety::create_checkpoint(Some(&format!("Before {}", self.operation_name)))?;
         }
 
@@ -260,3 +317,189 @@ impl SafetyBuilder {
         }
     }
 }
+
+/// Repository state captured before a multi-step command, so it can be put back
+///
+/// Commands that chain several git steps (a fetch and a rebase, a checkout and
+/// cherry-picks, a reset and a commit) run them through [`Transaction::run`]:
+/// when any step fails, HEAD, the local branches and the stash go back to how
+/// they were and what was restored is printed before the error.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    operation: String,
+    head: String,
+    /// Checked-out branch, `None` when HEAD was detached
+    branch: Option<String>,
+    /// Every local branch and the commit it pointed at
+    refs: Vec<(String, String)>,
+    /// Stash entries, newest first
+    stash: Vec<String>,
+    /// No tracked changes, so the working tree can be reset without losing anything
+    clean: bool,
+}
+
+impl Transaction {
+    /// Capture the current state before `operation` changes anything
+    pub fn begin(operation: &str) -> Result<Self> {
+        let head = GitOperations::run(&["rev-parse", "HEAD"])?;
+        let branch = GitOperations::run(&["symbolic-ref", "--quiet", "--short", "HEAD"]).ok();
+        let clean =
+            GitOperations::run(&["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+
+        Ok(Self {
+            operation: operation.to_string(),
+            head,
+            branch,
+            refs: Self::branch_refs()?,
+            stash: Self::stash_entries(),
+            clean,
+        })
+    }
+
+    fn branch_refs() -> Result<Vec<(String, String)>> {
+        Ok(GitOperations::run(&[
+            "for-each-ref",
+            "--format=%(refname:short) %(objectname)",
+            "refs/heads",
+        ])?
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(name, sha)| (name.to_string(), sha.to_string()))
+        .collect())
+    }
+
+    fn stash_entries() -> Vec<String> {
+        GitOperations::run(&["stash", "list", "--format=%H"])
+            .map(|list| list.lines().map(String::from).collect())
+            .unwrap_or_default()
+    }
+
+    /// Run `steps`, rolling back to the captured state if they fail
+    pub fn run<T>(self, steps: impl FnOnce() -> Result<T>) -> Result<T> {
+        let error = match steps() {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+
+        match self.rollback() {
+            Ok(restored) if restored.is_empty() => {}
+            Ok(restored) => {
+                eprintln!("↩️  {} failed, rolled back:", self.operation);
+                for line in restored {
+                    eprintln!("   • {line}");
+                }
+            }
+            Err(rollback_error) => {
+                eprintln!(
+                    "Warning: Failed to roll back {}: {rollback_error}",
+                    self.operation
+                );
+                eprintln!(
+                    "💡 The branch was at {} before; 'git x undo-file' has copies of changed files",
+                    short_sha(&self.head)
+                );
+            }
+        }
+        Err(error)
+    }
+
+    /// Put HEAD, the local branches and the stash back, describing each change
+    pub fn rollback(&self) -> Result<Vec<String>> {
+        let mut restored = Vec::new();
+
+        for state in crate::core::validation::Validate::detect_stale_state()? {
+            let Some(command) = state.abort_command() else {
+                continue;
+            };
+            let args: Vec<&str> = command.split_whitespace().skip(1).collect();
+            GitOperations::run(&args)?;
+            restored.push(format!("Ran '{command}'"));
+        }
+
+        let stash = Self::stash_entries();
+        // Entries pushed on top of the captured ones hold changes taken out of the tree
+        let pushed = match self.stash.first() {
+            Some(top) => stash.iter().position(|entry| entry == top).unwrap_or(0),
+            None => stash.len(),
+        };
+        // Nothing uncommitted can be lost: it was clean, or the changes are in the stash
+        let discard = self.clean || pushed > 0;
+
+        let current_branch =
+            GitOperations::run(&["symbolic-ref", "--quiet", "--short", "HEAD"]).ok();
+        if current_branch != self.branch {
+            let mut args = vec!["checkout"];
+            if discard {
+                args.push("--force");
+            }
+            match &self.branch {
+                Some(branch) => args.push(branch),
+                None => args.extend(["--detach", self.head.as_str()]),
+            }
+            GitOperations::run(&args)?;
+            restored.push(match &self.branch {
+                Some(branch) => format!("Switched back to '{branch}'"),
+                None => format!("Detached HEAD back at {}", short_sha(&self.head)),
+            });
+        }
+
+        let current_head = GitOperations::run(&["rev-parse", "HEAD"])?;
+        if current_head != self.head {
+            let mode = if discard { "--hard" } else { "--soft" };
+            GitOperations::run(&["reset", mode, &self.head])?;
+            restored.push(format!(
+                "Reset {} from {} to {}",
+                self.branch.as_deref().unwrap_or("HEAD"),
+                short_sha(&current_head),
+                short_sha(&self.head)
+            ));
+        } else if discard && pushed > 0 {
+            // Clear whatever the failed step left in the tree before unstashing
+            GitOperations::run(&["reset", "--hard", &self.head])?;
+        }
+
+        for (name, sha) in Self::branch_refs()? {
+            if Some(&name) == self.branch.as_ref() {
+                continue;
+            }
+            match self.refs.iter().find(|(captured, _)| *captured == name) {
+                None => {
+                    GitOperations::run(&["branch", "-D", &name])?;
+                    restored.push(format!("Deleted branch '{name}' (was {})", short_sha(&sha)));
+                }
+                Some((_, original)) if *original != sha => {
+                    GitOperations::run(&["update-ref", &format!("refs/heads/{name}"), original])?;
+                    restored.push(format!(
+                        "Reset '{name}' from {} to {}",
+                        short_sha(&sha),
+                        short_sha(original)
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        let remaining = Self::branch_refs()?;
+        for (name, original) in &self.refs {
+            if !remaining.iter().any(|(existing, _)| existing == name) {
+                GitOperations::run(&["branch", name, original])?;
+                restored.push(format!(
+                    "Recreated branch '{name}' at {}",
+                    short_sha(original)
+                ));
+            }
+        }
+
+        for _ in 0..pushed {
+            GitOperations::run(&["stash", "pop", "--index"])?;
+            restored.push("Put stashed changes back in the working tree".to_string());
+        }
+        if let Some(missing) = self.stash.iter().find(|entry| !stash.contains(entry)) {
+            restored.push(format!(
+                "Stash entry {} was dropped; recover it with 'git stash store {missing}'",
+                short_sha(missing)
+            ));
+        }
+
+        Ok(restored)
+    }
+}
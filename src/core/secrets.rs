@@ -0,0 +1,341 @@
+use crate::{GitXError, Result};
+
+/// Key file extensions that shouldn't be committed at all
+pub const SENSITIVE_EXTENSIONS: &[&str] = &["pem", "key", "p12", "pfx", "jks"];
+
+/// Files skipped unless configured otherwise: lockfiles are full of checksums
+pub const DEFAULT_ALLOWLIST: &[&str] = &["*.lock", "package-lock.json", "pnpm-lock.yaml", "go.sum"];
+
+/// Comment marker that silences findings on its line
+pub const INLINE_ALLOW: &str = "git-x: allow-secret";
+
+/// Shortest token considered for the entropy check
+pub const MIN_ENTROPY_TOKEN: usize = 20;
+
+/// Shannon entropy (bits per character) above which a token looks random
+pub const ENTROPY_THRESHOLD: f64 = 4.5;
+
+/// Words that make a high-entropy value on the same line more suspicious
+const SECRET_HINTS: &[&str] = &[
+    "key",
+    "secret",
+    "token",
+    "password",
+    "passwd",
+    "credential",
+    "auth",
+];
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Low => "🔹",
+            Severity::Medium => "⚠️ ",
+            Severity::High => "🚨",
+        }
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = GitXError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            _ => Err(GitXError::Parse(format!(
+                "Unknown severity '{s}' (use low, medium or high)"
+            ))),
+        }
+    }
+}
+
+/// Something in the staged changes that looks like a secret
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretFinding {
+    pub path: String,
+    /// Line in the staged file, `None` for whole-file findings
+    pub line: Option<usize>,
+    pub rule: &'static str,
+    pub severity: Severity,
+    /// The matched text, partially masked
+    pub excerpt: String,
+}
+
+impl SecretFinding {
+    pub fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{line}", self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// A line added by the staged diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddedLine {
+    pub path: String,
+    pub line: usize,
+    pub content: String,
+}
+
+/// Heuristic secret detection over staged changes
+pub struct SecretScanner {
+    allowlist: Vec<String>,
+}
+
+impl SecretScanner {
+    /// `allowlist` entries are path globs (`*` wildcards) or literal values to ignore
+    pub fn new(allowlist: Vec<String>) -> Self {
+        Self { allowlist }
+    }
+
+    /// Parse `git diff --cached -U0` into added lines and the paths of new files
+    pub fn parse_diff(diff: &str) -> (Vec<AddedLine>, Vec<String>) {
+        let mut added = Vec::new();
+        let mut new_files = Vec::new();
+        let mut path = String::new();
+        let mut is_new = false;
+        let mut next_line = 0;
+
+        for line in diff.lines() {
+            if line.starts_with("diff --git ") {
+                path.clear();
+                is_new = false;
+            } else if line.starts_with("new file mode") {
+                is_new = true;
+            } else if let Some(target) = line.strip_prefix("+++ ") {
+                path = target.strip_prefix("b/").unwrap_or(target).to_string();
+                if is_new {
+                    new_files.push(path.clone());
+                }
+            } else if let Some(range) = line.strip_prefix("@@ ") {
+                // @@ -a,b +c,d @@
+                next_line = range
+                    .split_whitespace()
+                    .find_map(|part| part.strip_prefix('+'))
+                    .and_then(|new| new.split(',').next()?.parse().ok())
+                    .unwrap_or(0);
+            } else if let Some(content) = line.strip_prefix('+') {
+                added.push(AddedLine {
+                    path: path.clone(),
+                    line: next_line,
+                    content: content.to_string(),
+                });
+                next_line += 1;
+            }
+        }
+
+        (added, new_files)
+    }
+
+    /// Findings for the given added lines and newly added files, minus allowlisted ones
+    pub fn scan(&self, added: &[AddedLine], new_files: &[String]) -> Vec<SecretFinding> {
+        let mut findings: Vec<SecretFinding> = new_files
+            .iter()
+            .filter_map(|path| Self::check_file(path))
+            .collect();
+
+        for line in added {
+            if line.content.contains(INLINE_ALLOW) {
+                continue;
+            }
+            findings.extend(Self::check_line(&line.content).into_iter().map(
+                |(rule, severity, matched)| SecretFinding {
+                    path: line.path.clone(),
+                    line: Some(line.line),
+                    rule,
+                    severity,
+                    excerpt: mask(&matched),
+                },
+            ));
+        }
+
+        findings.retain(|finding| !self.is_allowed(finding, added));
+        findings
+    }
+
+    fn is_allowed(&self, finding: &SecretFinding, added: &[AddedLine]) -> bool {
+        let content = finding.line.and_then(|number| {
+            added
+                .iter()
+                .find(|line| line.path == finding.path && line.line == number)
+                .map(|line| line.content.as_str())
+        });
+        DEFAULT_ALLOWLIST
+            .iter()
+            .map(|entry| entry.to_string())
+            .chain(self.allowlist.iter().cloned())
+            .any(|entry| {
+                glob_match(&entry, &finding.path)
+                    || glob_match(&entry, file_name(&finding.path))
+                    || (!entry.contains('*') && content.is_some_and(|c| c.contains(&entry)))
+            })
+    }
+
+    /// Whole-file findings for a newly added path
+    pub fn check_file(path: &str) -> Option<SecretFinding> {
+        let name = file_name(path);
+        let finding = |rule, severity| SecretFinding {
+            path: path.to_string(),
+            line: None,
+            rule,
+            severity,
+            excerpt: String::new(),
+        };
+
+        if is_env_file(name) {
+            return Some(finding("env-file", Severity::Medium));
+        }
+        let extension = name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+        if extension.is_some_and(|ext| SENSITIVE_EXTENSIONS.contains(&ext.as_str())) {
+            return Some(finding("key-file", Severity::High));
+        }
+        None
+    }
+
+    /// Rules matching one line, as (rule, severity, matched text)
+    pub fn check_line(line: &str) -> Vec<(&'static str, Severity, String)> {
+        let mut hits = Vec::new();
+        let lower = line.to_ascii_lowercase();
+
+        if line.contains("-----BEGIN") && line.contains("PRIVATE KEY-----") {
+            hits.push(("private-key", Severity::High, line.trim().to_string()));
+        }
+        if lower
+            .replace(' ', "")
+            .contains("\"type\":\"service_account\"")
+        {
+            hits.push((
+                "gcp-service-account",
+                Severity::High,
+                line.trim().to_string(),
+            ));
+        }
+
+        for token in tokens(line) {
+            if is_aws_access_key(token) {
+                hits.push(("aws-access-key", Severity::High, token.to_string()));
+            } else if is_gcp_api_key(token) {
+                hits.push(("gcp-api-key", Severity::High, token.to_string()));
+            } else if lower.contains("aws_secret") && token.len() == 40 {
+                hits.push(("aws-secret-key", Severity::High, token.to_string()));
+            }
+        }
+
+        // Entropy is a fallback; a line already flagged doesn't need it
+        if hits.is_empty()
+            && let Some(token) = tokens(line)
+                .filter(|token| looks_random(token))
+                .max_by_key(|token| token.len())
+        {
+            let hinted = SECRET_HINTS.iter().any(|hint| lower.contains(hint));
+            let severity = if hinted {
+                Severity::Medium
+            } else {
+                Severity::Low
+            };
+            hits.push(("high-entropy", severity, token.to_string()));
+        }
+
+        hits
+    }
+}
+
+/// `.env` and `.env.local`-style files, but not committed templates
+pub fn is_env_file(name: &str) -> bool {
+    let templates = [".example", ".sample", ".template", ".dist"];
+    (name == ".env" || name.starts_with(".env."))
+        && !templates.iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// Shannon entropy of a string in bits per character
+pub fn shannon_entropy(value: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for c in value.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    let len = value.chars().count() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn looks_random(token: &str) -> bool {
+    token.len() >= MIN_ENTROPY_TOKEN
+        && token.chars().any(|c| c.is_ascii_digit())
+        && token.chars().any(|c| c.is_ascii_uppercase())
+        && token.chars().any(|c| c.is_ascii_lowercase())
+        && shannon_entropy(token) >= ENTROPY_THRESHOLD
+}
+
+fn is_aws_access_key(token: &str) -> bool {
+    token.len() == 20
+        && (token.starts_with("AKIA") || token.starts_with("ASIA"))
+        && token
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+fn is_gcp_api_key(token: &str) -> bool {
+    token.len() == 39 && token.starts_with("AIza")
+}
+
+/// Runs of base64/url-safe characters, the alphabet most keys are written in
+fn tokens(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !(c.is_ascii_alphanumeric() || "+/=_-".contains(c)))
+        .map(|token| token.trim_end_matches('='))
+        .filter(|token| !token.is_empty())
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Show only the start of a match so the report doesn't leak the secret itself
+fn mask(value: &str) -> String {
+    let visible: String = value.chars().take(6).collect();
+    if value.chars().count() <= 6 {
+        visible
+    } else {
+        format!("{visible}…")
+    }
+}
+
+/// Match a path against a pattern where `*` matches any run of characters
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(remaining) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=remaining.len())
+                .filter(|&i| remaining.is_char_boundary(i))
+                .any(|i| glob_match(rest, &remaining[i..]))
+        }
+    }
+}
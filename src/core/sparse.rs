@@ -0,0 +1,99 @@
+use crate::core::git::GitOperations;
+
+/// How much of the tracked tree is checked out
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseCheckout {
+    pub enabled: bool,
+    /// Patterns are directories rather than gitignore-style patterns
+    pub cone: bool,
+    /// Directories in cone mode, patterns otherwise
+    pub patterns: Vec<String>,
+    pub tracked_files: usize,
+    /// Tracked files git leaves out of the working tree
+    pub skipped_files: usize,
+}
+
+impl SparseCheckout {
+    /// Read the sparse-checkout state of the current worktree
+    pub fn detect() -> Self {
+        let enabled = Self::config_bool("core.sparseCheckout");
+        if !enabled {
+            return Self {
+                tracked_files: GitOperations::run(&["ls-files"])
+                    .map(|files| files.lines().count())
+                    .unwrap_or_default(),
+                ..Self::default()
+            };
+        }
+
+        // `ls-files -t` tags files outside the sparse checkout with `S`
+        let (tracked_files, skipped_files) = GitOperations::run(&["ls-files", "-t"])
+            .map(|files| {
+                files.lines().fold((0, 0), |(tracked, skipped), line| {
+                    (tracked + 1, skipped + usize::from(line.starts_with("S ")))
+                })
+            })
+            .unwrap_or_default();
+
+        Self {
+            enabled,
+            cone: Self::config_bool("core.sparseCheckoutCone"),
+            patterns: GitOperations::run(&["sparse-checkout", "list"])
+                .map(|patterns| patterns.lines().map(str::to_string).collect())
+                .unwrap_or_default(),
+            tracked_files,
+            skipped_files,
+        }
+    }
+
+    pub fn checked_out_files(&self) -> usize {
+        self.tracked_files - self.skipped_files
+    }
+
+    /// Warning for commands that scan the working tree, when part of it is missing
+    pub fn scan_warning(&self) -> Option<String> {
+        (self.enabled && self.skipped_files > 0).then(|| {
+            format!(
+                "⚠️  Sparse checkout: only {} of {} tracked files are on disk, results cover those only ('git x sparse status')",
+                self.checked_out_files(),
+                self.tracked_files
+            )
+        })
+    }
+
+    /// `scan_warning` for the current worktree
+    pub fn current_scan_warning() -> Option<String> {
+        // Cheap check first: most repositories aren't sparse
+        if !Self::config_bool("core.sparseCheckout") {
+            return None;
+        }
+        Self::detect().scan_warning()
+    }
+
+    fn config_bool(key: &str) -> bool {
+        GitOperations::run(&["config", "--type=bool", "--get", key])
+            .is_ok_and(|value| value == "true")
+    }
+}
+
+/// Remotes a partial clone fetches missing objects from, with their filter
+pub fn partial_clone_remotes() -> Vec<(String, String)> {
+    GitOperations::run(&[
+        "config",
+        "--get-regexp",
+        r"^remote\..*\.partialclonefilter$",
+    ])
+    .map(|output| {
+        output
+            .lines()
+            .filter_map(|line| {
+                let (key, filter) = line.split_once(' ')?;
+                let remote = key
+                    .strip_prefix("remote.")?
+                    .strip_suffix(".partialclonefilter")?;
+                Some((remote.to_string(), filter.to_string()))
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
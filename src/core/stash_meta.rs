@@ -0,0 +1,76 @@
+use crate::Result;
+use crate::core::git::GitOperations;
+
+/// Opens the metadata git-x appends to the message of every stash it creates
+const MARKER: &str = "[git-x ";
+
+/// Which operation made a stash, on which branch, when and with which git-x
+///
+/// Kept at the end of the stash message, e.g.
+/// `git-x sync autostash [git-x op=sync branch=main at=2026-10-17T09:30:00Z v=1.1.0]`,
+/// so stashes git-x created can be told apart from the user's own.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StashMeta {
+    pub operation: String,
+    pub branch: String,
+    /// UTC, RFC 3339
+    pub created: String,
+    pub version: String,
+}
+
+impl StashMeta {
+    /// Metadata for a stash `operation` is about to create on the current branch
+    pub fn new(operation: &str) -> Self {
+        Self {
+            operation: operation.to_string(),
+            branch: GitOperations::current_branch().unwrap_or_else(|_| "HEAD".to_string()),
+            created: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// `description` followed by the metadata, for `git stash push -m`
+    pub fn message(&self, description: &str) -> String {
+        format!(
+            "{description} {MARKER}op={} branch={} at={} v={}]",
+            self.operation, self.branch, self.created, self.version
+        )
+    }
+
+    /// Metadata at the end of a stash message; `None` for stashes git-x didn't create
+    pub fn parse(message: &str) -> Option<Self> {
+        let start = message.rfind(MARKER)?;
+        let fields = message[start + MARKER.len()..]
+            .trim_end()
+            .strip_suffix(']')?;
+
+        let mut meta = Self::default();
+        for field in fields.split_whitespace() {
+            let (key, value) = field.split_once('=')?;
+            let value = value.to_string();
+            match key {
+                "op" => meta.operation = value,
+                "branch" => meta.branch = value,
+                "at" => meta.created = value,
+                "v" => meta.version = value,
+                // Written by a newer git-x
+                _ => {}
+            }
+        }
+        (!meta.operation.is_empty()).then_some(meta)
+    }
+
+    /// The message without its metadata
+    pub fn strip(message: &str) -> &str {
+        match (message.rfind(MARKER), Self::parse(message)) {
+            (Some(start), Some(_)) => message[..start].trim_end(),
+            _ => message,
+        }
+    }
+
+    /// Stash the tracked changes, tagged as made by `operation`
+    pub fn push(operation: &str, description: &str) -> Result<()> {
+        let message = Self::new(operation).message(description);
+        GitOperations::run(&["stash", "push", "-m", &message]).map(|_| ())
+    }
+}
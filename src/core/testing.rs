@@ -0,0 +1,356 @@
+//! Record and replay git output so command logic can be tested without real repositories
+//!
+//! Available with the `testing` feature. Install a backend with [`install`]; while the
+//! returned guard is alive every call through `GitOperations` and `AsyncGitOperations`
+//! goes to it instead of the `git` binary.
+
+use crate::core::git::{GitBackend, SystemGitBackend};
+use crate::{GitXError, Result};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Recorded outcome of a git call: trimmed stdout, or stderr on failure
+pub type RecordedResult = std::result::Result<String, String>;
+
+static BACKEND: RwLock<Option<Arc<dyn GitBackend>>> = RwLock::new(None);
+
+/// The backend installed with [`install`], if any
+pub fn installed_backend() -> Option<Arc<dyn GitBackend>> {
+    BACKEND.read().ok().and_then(|backend| backend.clone())
+}
+
+/// Route all git calls to `backend` until the guard is dropped
+pub fn install(backend: Arc<dyn GitBackend>) -> BackendGuard {
+    let previous = BACKEND
+        .write()
+        .map(|mut slot| slot.replace(backend))
+        .unwrap_or_default();
+    BackendGuard { previous }
+}
+
+/// Restores the previously installed backend when dropped
+pub struct BackendGuard {
+    previous: Option<Arc<dyn GitBackend>>,
+}
+
+impl Drop for BackendGuard {
+    fn drop(&mut self) {
+        if let Ok(mut slot) = BACKEND.write() {
+            *slot = self.previous.take();
+        }
+    }
+}
+
+/// One recorded git invocation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureEntry {
+    pub args: Vec<String>,
+    pub result: RecordedResult,
+}
+
+/// A sequence of recorded git invocations
+///
+/// Fixture files are plain text so they review well in diffs:
+///
+/// ```text
+/// # comments are allowed before the first entry
+/// $ git rev-parse --abbrev-ref HEAD
+/// main
+/// $ git log '--since=3 days ago' --oneline
+/// a1b2c3d Fix login
+/// $ git config user.name
+/// ! error: key does not contain a section
+/// ```
+///
+/// Arguments containing whitespace or quotes are single-quoted (`''` escapes a quote).
+/// Output lines that would look like markup are prefixed with `\`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fixture {
+    pub entries: Vec<FixtureEntry>,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, args: &[&str], result: RecordedResult) {
+        self.entries.push(FixtureEntry {
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            result,
+        });
+    }
+
+    /// Parse fixture file content
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut fixture = Self::new();
+        let mut current: Option<(Vec<String>, Vec<String>)> = None;
+
+        let finish = |fixture: &mut Fixture, entry: Option<(Vec<String>, Vec<String>)>| {
+            if let Some((args, body)) = entry {
+                // Markup is detected on raw lines, before `\` escapes are removed
+                let unescape = |line: &String| line.strip_prefix('\\').unwrap_or(line).to_string();
+                let result = match body.first().and_then(|first| first.strip_prefix('!')) {
+                    Some(message) => {
+                        let mut error =
+                            vec![message.strip_prefix(' ').unwrap_or(message).to_string()];
+                        error.extend(body[1..].iter().map(unescape));
+                        Err(error.join("\n"))
+                    }
+                    None => Ok(body.iter().map(unescape).collect::<Vec<_>>().join("\n")),
+                };
+                fixture.entries.push(FixtureEntry { args, result });
+            }
+        };
+
+        for (number, line) in content.lines().enumerate() {
+            if let Some(command) = line.strip_prefix("$ git") {
+                finish(&mut fixture, current.take());
+                let args = Self::split_args(command).ok_or_else(|| {
+                    GitXError::Parse(format!("Unbalanced quote on fixture line {}", number + 1))
+                })?;
+                current = Some((args, Vec::new()));
+            } else if let Some((_, body)) = current.as_mut() {
+                body.push(line.to_string());
+            } else if !line.trim().is_empty() && !line.starts_with('#') {
+                return Err(GitXError::Parse(format!(
+                    "Expected '$ git ...' on fixture line {}",
+                    number + 1
+                )));
+            }
+        }
+        finish(&mut fixture, current.take());
+
+        Ok(fixture)
+    }
+
+    /// Render as fixture file content
+    pub fn render(&self) -> String {
+        let mut out = Vec::new();
+        for entry in &self.entries {
+            let args: Vec<String> = entry.args.iter().map(|arg| Self::quote(arg)).collect();
+            out.push(format!("$ git {}", args.join(" ")).trim_end().to_string());
+
+            let (prefix, text) = match &entry.result {
+                Ok(stdout) => ("", stdout),
+                Err(stderr) => ("! ", stderr),
+            };
+            if text.is_empty() {
+                if !prefix.is_empty() {
+                    out.push("!".to_string());
+                }
+                continue;
+            }
+            for (i, line) in text.lines().enumerate() {
+                if i == 0 && !prefix.is_empty() {
+                    out.push(format!("{prefix}{line}"));
+                } else if line.starts_with(['$', '!', '\\']) {
+                    out.push(format!("\\{line}"));
+                } else {
+                    out.push(line.to_string());
+                }
+            }
+        }
+        out.join("\n") + "\n"
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(std::fs::write(path, self.render())?)
+    }
+
+    fn quote(arg: &str) -> String {
+        if !arg.is_empty() && !arg.contains(|c: char| c.is_whitespace() || c == '\'') {
+            arg.to_string()
+        } else {
+            format!("'{}'", arg.replace('\'', "''"))
+        }
+    }
+
+    fn split_args(line: &str) -> Option<Vec<String>> {
+        let mut args = Vec::new();
+        let mut chars = line.trim().chars().peekable();
+
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+                continue;
+            }
+
+            let mut arg = String::new();
+            if c == '\'' {
+                chars.next();
+                loop {
+                    match chars.next()? {
+                        '\'' if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            arg.push('\'');
+                        }
+                        '\'' => break,
+                        other => arg.push(other),
+                    }
+                }
+            } else {
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    arg.push(c);
+                    chars.next();
+                }
+            }
+            args.push(arg);
+        }
+
+        Some(args)
+    }
+}
+
+/// Backend that answers from recorded output
+///
+/// Responses for the same arguments are returned in order; the last one repeats
+/// once the others are used up. Unknown commands fail with a descriptive error.
+#[derive(Default)]
+pub struct ReplayGitBackend {
+    responses: Mutex<HashMap<Vec<String>, VecDeque<RecordedResult>>>,
+    calls: Mutex<Vec<Vec<String>>>,
+}
+
+impl ReplayGitBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_fixture(fixture: Fixture) -> Self {
+        let backend = Self::new();
+        for entry in fixture.entries {
+            backend.push(entry.args, entry.result);
+        }
+        backend
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::from_fixture(Fixture::load(path)?))
+    }
+
+    /// Answer `git <args>` with `stdout`
+    pub fn with_output(self, args: &[&str], stdout: &str) -> Self {
+        self.push(
+            args.iter().map(|arg| arg.to_string()).collect(),
+            Ok(stdout.to_string()),
+        );
+        self
+    }
+
+    /// Fail `git <args>` with `stderr`
+    pub fn with_error(self, args: &[&str], stderr: &str) -> Self {
+        self.push(
+            args.iter().map(|arg| arg.to_string()).collect(),
+            Err(stderr.to_string()),
+        );
+        self
+    }
+
+    /// Every command run so far, as `git ...` strings
+    pub fn calls(&self) -> Vec<String> {
+        self.calls
+            .lock()
+            .map(|calls| {
+                calls
+                    .iter()
+                    .map(|args| format!("git {}", args.join(" ")))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn push(&self, args: Vec<String>, result: RecordedResult) {
+        if let Ok(mut responses) = self.responses.lock() {
+            responses.entry(args).or_default().push_back(result);
+        }
+    }
+}
+
+impl GitBackend for ReplayGitBackend {
+    fn run(&self, args: &[&str]) -> Result<String> {
+        let key: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.push(key.clone());
+        }
+
+        let response = self.responses.lock().ok().and_then(|mut responses| {
+            let queue = responses.get_mut(&key)?;
+            if queue.len() > 1 {
+                queue.pop_front()
+            } else {
+                queue.front().cloned()
+            }
+        });
+
+        match response {
+            Some(Ok(stdout)) => Ok(stdout),
+            Some(Err(stderr)) => Err(GitXError::git_failure(args, Some(1), &stderr)),
+            None => Err(GitXError::GitCommand(format!(
+                "No recorded output for: git {}",
+                args.join(" ")
+            ))),
+        }
+    }
+}
+
+/// Backend that runs real git and records every call into a [`Fixture`]
+pub struct RecordingGitBackend {
+    inner: Box<dyn GitBackend>,
+    fixture: Mutex<Fixture>,
+}
+
+impl Default for RecordingGitBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecordingGitBackend {
+    pub fn new() -> Self {
+        Self::wrapping(Box::new(SystemGitBackend))
+    }
+
+    pub fn wrapping(inner: Box<dyn GitBackend>) -> Self {
+        Self {
+            inner,
+            fixture: Mutex::new(Fixture::new()),
+        }
+    }
+
+    /// Everything recorded so far
+    pub fn fixture(&self) -> Fixture {
+        self.fixture
+            .lock()
+            .map(|fixture| fixture.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.fixture().save(path)
+    }
+}
+
+impl GitBackend for RecordingGitBackend {
+    fn run(&self, args: &[&str]) -> Result<String> {
+        let result = self.inner.run(args);
+        let recorded = match &result {
+            Ok(stdout) => Ok(stdout.clone()),
+            Err(GitXError::GitCommand(stderr)) => Err(stderr.clone()),
+            Err(GitXError::GitProcess(failure)) => Err(failure.stderr.clone()),
+            Err(other) => Err(other.to_string()),
+        };
+        if let Ok(mut fixture) = self.fixture.lock() {
+            fixture.push(args, recorded);
+        }
+        result
+    }
+}
@@ -0,0 +1,58 @@
+use crate::core::config::Config;
+use crate::{GitXError, Result};
+use regex::Regex;
+
+/// Ticket references matched unless `git-x.tickets.pattern` sets another: JIRA-style keys
+pub const DEFAULT_PATTERN: &str = r"\b[A-Z][A-Z0-9]+-[0-9]+\b";
+
+/// Placeholder in `git-x.tickets.url` replaced by the ticket ID
+pub const ID_PLACEHOLDER: &str = "{id}";
+
+/// Finds issue tracker references in commit messages
+///
+/// When the pattern has a capture group, the group is the ID, so `#([0-9]+)`
+/// yields `42` for `#42`.
+pub struct TicketPattern {
+    regex: Regex,
+}
+
+impl TicketPattern {
+    pub fn new(pattern: &str) -> Result<Self> {
+        Regex::new(pattern)
+            .map(|regex| Self { regex })
+            .map_err(|e| GitXError::Parse(format!("Invalid ticket pattern '{pattern}': {e}")))
+    }
+
+    /// `git-x.tickets.pattern`, else [`DEFAULT_PATTERN`]
+    pub fn from_config() -> Result<Self> {
+        Self::new(&Config::get("tickets.pattern").unwrap_or_else(|| DEFAULT_PATTERN.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// Ticket IDs in `text`, each once, in the order they first appear
+    pub fn find(&self, text: &str) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for captures in self.regex.captures_iter(text) {
+            let Some(id) = captures.get(1).or_else(|| captures.get(0)) else {
+                continue;
+            };
+            if !id.as_str().is_empty() && !ids.iter().any(|known| known == id.as_str()) {
+                ids.push(id.as_str().to_string());
+            }
+        }
+        ids
+    }
+}
+
+/// The tracker URL for `id`, from a template such as `https://jira.example.com/browse/{id}`
+pub fn ticket_url(template: &str, id: &str) -> Result<String> {
+    if !template.contains(ID_PLACEHOLDER) {
+        return Err(GitXError::Parse(format!(
+            "Ticket URL template '{template}' has no {ID_PLACEHOLDER} placeholder"
+        )));
+    }
+    Ok(template.replace(ID_PLACEHOLDER, id))
+}
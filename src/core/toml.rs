@@ -0,0 +1,398 @@
+use crate::core::export::ExportValue;
+use crate::{GitXError, Result};
+use std::fmt;
+
+/// Parse a TOML document, such as a `.git-x/ci.toml` manifest or a `Cargo.toml`
+///
+/// Tables become records in file order. Covers what configuration files use:
+/// tables and arrays of tables, dotted and quoted keys, single- and multi-line
+/// strings, numbers, booleans, arrays and inline tables. Dates are rejected.
+pub fn parse(input: &str) -> Result<ExportValue> {
+    TomlParser {
+        chars: input.chars().peekable(),
+        line: 1,
+    }
+    .document()
+}
+
+/// Fields of a TOML table
+type TomlTable = Vec<(String, ExportValue)>;
+
+/// What the quotes opening a string turned out to be
+enum Opening {
+    /// `""` or `''`
+    Empty,
+    SingleLine,
+    /// `"""` or `'''`
+    MultiLine,
+}
+
+/// Minimal TOML reader backing [`parse`]
+struct TomlParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl TomlParser<'_> {
+    fn error(&self, message: impl fmt::Display) -> GitXError {
+        GitXError::Parse(format!("TOML line {}: {message}", self.line))
+    }
+
+    /// Next character, counting lines as strings span them
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    /// Skip spaces, tabs and a comment running to the end of the line
+    fn skip_inline(&mut self) {
+        while self.chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+        if self.chars.peek() == Some(&'#') {
+            while self.chars.next_if(|c| *c != '\n').is_some() {}
+        }
+    }
+
+    /// Skip whitespace, comments and line breaks
+    fn skip_blank(&mut self) {
+        loop {
+            self.skip_inline();
+            match self.chars.peek() {
+                Some('\n') | Some('\r') => {
+                    self.next();
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Nothing but a comment may follow on the line
+    fn end_of_line(&mut self) -> Result<()> {
+        self.skip_inline();
+        match self.chars.peek().copied() {
+            None | Some('\n') | Some('\r') => Ok(()),
+            Some(c) => Err(self.error(format!("unexpected '{c}'"))),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip_inline();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(self.error(format!("expected '{expected}', found '{c}'"))),
+            None => Err(self.error(format!("expected '{expected}', found end of file"))),
+        }
+    }
+
+    fn document(&mut self) -> Result<ExportValue> {
+        let mut root = TomlTable::new();
+        let mut current: Vec<String> = Vec::new();
+        loop {
+            self.skip_blank();
+            match self.chars.peek() {
+                None => return Ok(ExportValue::Record(root)),
+                Some('[') => {
+                    self.chars.next();
+                    let array = self.chars.next_if_eq(&'[').is_some();
+                    let path = self.key()?;
+                    self.expect(']')?;
+                    if array {
+                        self.expect(']')?;
+                    }
+                    self.end_of_line()?;
+
+                    let (name, parents) = path.split_last().expect("keys have a segment");
+                    let parent = Self::table(&mut root, parents).map_err(|e| self.error(e))?;
+                    match parent.iter_mut().find(|(key, _)| key == name) {
+                        Some((_, ExportValue::List(items))) if array => {
+                            items.push(ExportValue::Record(Vec::new()))
+                        }
+                        Some((_, ExportValue::Record(_))) if !array => {}
+                        Some(_) => return Err(self.error(format!("'{name}' is already defined"))),
+                        None => {
+                            let table = ExportValue::Record(Vec::new());
+                            let value = if array {
+                                ExportValue::List(vec![table])
+                            } else {
+                                table
+                            };
+                            parent.push((name.clone(), value));
+                        }
+                    }
+                    current = path;
+                }
+                Some(_) => {
+                    let path = self.key()?;
+                    self.expect('=')?;
+                    let value = self.value()?;
+                    self.end_of_line()?;
+                    let table = Self::table(&mut root, &current).map_err(|e| self.error(e))?;
+                    Self::insert(table, &path, value).map_err(|e| self.error(e))?;
+                }
+            }
+        }
+    }
+
+    /// The table at `path`, creating missing ones; the last entry of an array of tables
+    fn table<'t>(
+        mut fields: &'t mut TomlTable,
+        path: &[String],
+    ) -> std::result::Result<&'t mut TomlTable, String> {
+        for segment in path {
+            let index = match fields.iter().position(|(key, _)| key == segment) {
+                Some(index) => index,
+                None => {
+                    fields.push((segment.clone(), ExportValue::Record(Vec::new())));
+                    fields.len() - 1
+                }
+            };
+            fields = match &mut fields[index].1 {
+                ExportValue::Record(inner) => inner,
+                ExportValue::List(items) => match items.last_mut() {
+                    Some(ExportValue::Record(inner)) => inner,
+                    _ => return Err(format!("'{segment}' is not a table")),
+                },
+                _ => return Err(format!("'{segment}' is not a table")),
+            };
+        }
+        Ok(fields)
+    }
+
+    /// Set a dotted key relative to `table`; keys can't be defined twice
+    fn insert(
+        table: &mut TomlTable,
+        path: &[String],
+        value: ExportValue,
+    ) -> std::result::Result<(), String> {
+        let (name, parents) = path.split_last().expect("keys have a segment");
+        let table = Self::table(table, parents)?;
+        if table.iter().any(|(key, _)| key == name) {
+            return Err(format!("'{name}' is already defined"));
+        }
+        table.push((name.clone(), value));
+        Ok(())
+    }
+
+    /// A bare, quoted or dotted key
+    fn key(&mut self) -> Result<Vec<String>> {
+        let mut path = Vec::new();
+        loop {
+            self.skip_inline();
+            let segment = match self.chars.peek() {
+                Some('"') => self.basic_string()?,
+                Some('\'') => self.literal_string()?,
+                _ => {
+                    let mut bare = String::new();
+                    while let Some(c) = self
+                        .chars
+                        .next_if(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+                    {
+                        bare.push(c);
+                    }
+                    if bare.is_empty() {
+                        return Err(self.error("expected a key"));
+                    }
+                    bare
+                }
+            };
+            path.push(segment);
+            self.skip_inline();
+            if self.chars.next_if_eq(&'.').is_none() {
+                return Ok(path);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<ExportValue> {
+        self.skip_inline();
+        match self.chars.peek() {
+            Some('"') => self.basic_string().map(ExportValue::Text),
+            Some('\'') => self.literal_string().map(ExportValue::Text),
+            Some('[') => self.array(),
+            Some('{') => self.inline_table(),
+            Some('\n') | Some('\r') | None => Err(self.error("expected a value")),
+            Some(_) => self.scalar(),
+        }
+    }
+
+    /// Booleans and numbers; dates are not supported
+    fn scalar(&mut self) -> Result<ExportValue> {
+        let mut text = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| !c.is_whitespace() && !matches!(c, ',' | ']' | '}' | '#'))
+        {
+            text.push(c);
+        }
+        let number = text.replace('_', "");
+        match text.as_str() {
+            "true" => Ok(ExportValue::Bool(true)),
+            "false" => Ok(ExportValue::Bool(false)),
+            _ => number
+                .parse::<i64>()
+                .map(ExportValue::Int)
+                .or_else(|_| number.parse::<f64>().map(ExportValue::Float))
+                .map_err(|_| self.error(format!("unsupported value '{text}'"))),
+        }
+    }
+
+    /// Consume the opening quotes of a string
+    fn opening(&mut self, quote: char) -> Opening {
+        self.chars.next();
+        if self.chars.next_if_eq(&quote).is_none() {
+            return Opening::SingleLine;
+        }
+        if self.chars.next_if_eq(&quote).is_none() {
+            return Opening::Empty;
+        }
+        // A line break right after the opening quotes isn't part of the string
+        self.chars.next_if_eq(&'\r');
+        if self.chars.peek() == Some(&'\n') {
+            self.next();
+        }
+        Opening::MultiLine
+    }
+
+    /// After a quote inside a multi-line string: closed once three in a row are read,
+    /// keeping up to two more as content (`""""` ends with a quote)
+    fn closes_multi_line(&mut self, quote: char, out: &mut String) -> bool {
+        let mut count = 1;
+        while count < 5 && self.chars.next_if_eq(&quote).is_some() {
+            count += 1;
+        }
+        if count >= 3 {
+            out.extend(std::iter::repeat_n(quote, count - 3));
+            return true;
+        }
+        out.extend(std::iter::repeat_n(quote, count));
+        false
+    }
+
+    fn basic_string(&mut self) -> Result<String> {
+        let multi_line = match self.opening('"') {
+            Opening::Empty => return Ok(String::new()),
+            Opening::SingleLine => false,
+            Opening::MultiLine => true,
+        };
+        let mut out = String::new();
+        loop {
+            match self.next() {
+                Some('"') if !multi_line => return Ok(out),
+                Some('"') => {
+                    if self.closes_multi_line('"', &mut out) {
+                        return Ok(out);
+                    }
+                }
+                Some('\\') => {
+                    let c = self.chars.peek().copied();
+                    if multi_line && matches!(c, Some(' ' | '\t' | '\r' | '\n')) {
+                        // Line-ending backslash: drop the break and leading whitespace
+                        while self
+                            .chars
+                            .peek()
+                            .is_some_and(|c| matches!(c, ' ' | '\t' | '\r' | '\n'))
+                        {
+                            self.next();
+                        }
+                    } else {
+                        self.escape(&mut out)?;
+                    }
+                }
+                Some('\n') if !multi_line => break,
+                None => break,
+                Some(c) => out.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    /// The character after a backslash in a basic string
+    fn escape(&mut self, out: &mut String) -> Result<()> {
+        match self.chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(kind @ ('u' | 'U')) => {
+                let width = if kind == 'u' { 4 } else { 8 };
+                let digits: String = (0..width).filter_map(|_| self.chars.next()).collect();
+                let c = u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error(format!("invalid escape \\{kind}{digits}")))?;
+                out.push(c);
+            }
+            Some(c) => return Err(self.error(format!("invalid escape \\{c}"))),
+            None => return Err(self.error("unterminated string")),
+        }
+        Ok(())
+    }
+
+    fn literal_string(&mut self) -> Result<String> {
+        let multi_line = match self.opening('\'') {
+            Opening::Empty => return Ok(String::new()),
+            Opening::SingleLine => false,
+            Opening::MultiLine => true,
+        };
+        let mut out = String::new();
+        loop {
+            match self.next() {
+                Some('\'') if !multi_line => return Ok(out),
+                Some('\'') => {
+                    if self.closes_multi_line('\'', &mut out) {
+                        return Ok(out);
+                    }
+                }
+                Some('\n') if !multi_line => break,
+                None => break,
+                Some(c) => out.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    /// Arrays may span lines and end with a trailing comma
+    fn array(&mut self) -> Result<ExportValue> {
+        self.chars.next();
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.chars.next_if_eq(&']').is_some() {
+                return Ok(ExportValue::List(items));
+            }
+            items.push(self.value()?);
+            self.skip_blank();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(ExportValue::List(items)),
+                _ => return Err(self.error("expected ',' or ']' in array")),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<ExportValue> {
+        self.chars.next();
+        let mut fields = TomlTable::new();
+        self.skip_inline();
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(ExportValue::Record(fields));
+        }
+        loop {
+            let path = self.key()?;
+            self.expect('=')?;
+            let value = self.value()?;
+            Self::insert(&mut fields, &path, value).map_err(|e| self.error(e))?;
+            self.skip_inline();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(ExportValue::Record(fields)),
+                _ => return Err(self.error("expected ',' or '}' in inline table")),
+            }
+        }
+    }
+}
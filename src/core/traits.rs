@@ -12,6 +12,19 @@ pub trait Command {
     fn description(&self) -> &'static str;
 }
 
+/// Trait for commands that can write output incrementally instead of all at the end
+pub trait StreamingCommand: Command {
+    /// Run the command, writing each result to `sink` as soon as it is known
+    fn stream(&self, sink: &mut dyn crate::core::output::OutputSink) -> Result<()>;
+
+    /// Run the command and collect its text output, which is what `execute` returns
+    fn capture(&self) -> Result<String> {
+        let mut output = crate::core::output::BufferedOutput::new();
+        self.stream(&mut output)?;
+        Ok(output.content())
+    }
+}
+
 /// Trait for commands that support dry-run mode
 pub trait DryRunnable: Command {
     /// Execute the command in dry-run mode
@@ -1,5 +1,6 @@
 use crate::core::git::GitOperations;
 use crate::{GitXError, Result};
+use std::path::{Path, PathBuf};
 
 /// Common validation utilities
 pub struct Validate;
@@ -71,6 +72,28 @@ impl Validate {
         Ok(())
     }
 
+    /// Validate a revision such as a branch, tag, `HEAD~2` or commit hash
+    pub fn ref_name(name: &str) -> Result<()> {
+        if name.is_empty() {
+            return Err(GitXError::Parse("Reference cannot be empty".to_string()));
+        }
+
+        // A leading dash would be read as an option by git
+        if name.starts_with('-') {
+            return Err(GitXError::Parse(format!(
+                "Reference '{name}' cannot start with '-'"
+            )));
+        }
+
+        if name.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(GitXError::Parse(format!(
+                "Reference '{name}' contains invalid characters"
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Validate remote name format
     pub fn remote_name(name: &str) -> Result<()> {
         if name.is_empty() {
@@ -194,6 +217,180 @@ impl Validate {
     }
 }
 
+/// Leftover repository state from an interrupted or crashed git process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaleState {
+    /// A `*.lock` file (index.lock, HEAD.lock, refs/**.lock, ...)
+    LockFile(PathBuf),
+    MergeInProgress,
+    RebaseInProgress,
+    CherryPickInProgress,
+    RevertInProgress,
+}
+
+impl StaleState {
+    /// Human-readable explanation of the state
+    pub fn description(&self) -> String {
+        match self {
+            StaleState::LockFile(path) => {
+                format!("Lock file left behind: {}", path.display())
+            }
+            StaleState::MergeInProgress => "A merge is in progress (MERGE_HEAD exists)".to_string(),
+            StaleState::RebaseInProgress => {
+                "A rebase is in progress (rebase state directory exists)".to_string()
+            }
+            StaleState::CherryPickInProgress => {
+                "A cherry-pick is in progress (CHERRY_PICK_HEAD exists)".to_string()
+            }
+            StaleState::RevertInProgress => {
+                "A revert is in progress (REVERT_HEAD exists)".to_string()
+            }
+        }
+    }
+
+    /// Git command that safely aborts an in-progress operation
+    pub fn abort_command(&self) -> Option<&'static str> {
+        match self {
+            StaleState::LockFile(_) => None,
+            StaleState::MergeInProgress => Some("git merge --abort"),
+            StaleState::RebaseInProgress => Some("git rebase --abort"),
+            StaleState::CherryPickInProgress => Some("git cherry-pick --abort"),
+            StaleState::RevertInProgress => Some("git revert --abort"),
+        }
+    }
+}
+
+impl Validate {
+    /// Absolute path of the repository's git directory
+    pub fn git_dir() -> Result<PathBuf> {
+        GitOperations::run(&["rev-parse", "--absolute-git-dir"]).map(PathBuf::from)
+    }
+
+    /// Detect lock files and interrupted operations left in the git directory
+    ///
+    /// Returns an empty list outside of a git repository so callers keep
+    /// their usual "not a git repository" error.
+    pub fn detect_stale_state() -> Result<Vec<StaleState>> {
+        let git_dir = match Self::git_dir() {
+            Ok(dir) => dir,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut issues = Vec::new();
+
+        for name in [
+            "index.lock",
+            "HEAD.lock",
+            "ORIG_HEAD.lock",
+            "config.lock",
+            "packed-refs.lock",
+            "shallow.lock",
+        ] {
+            let path = git_dir.join(name);
+            if path.is_file() {
+                issues.push(StaleState::LockFile(path));
+            }
+        }
+
+        let mut ref_locks = Vec::new();
+        Self::collect_lock_files(&git_dir.join("refs"), &mut ref_locks);
+        ref_locks.sort();
+        issues.extend(ref_locks.into_iter().map(StaleState::LockFile));
+
+        if git_dir.join("MERGE_HEAD").exists() {
+            issues.push(StaleState::MergeInProgress);
+        }
+        if git_dir.join("rebase-merge").is_dir() || git_dir.join("rebase-apply").is_dir() {
+            issues.push(StaleState::RebaseInProgress);
+        }
+        if git_dir.join("CHERRY_PICK_HEAD").exists() {
+            issues.push(StaleState::CherryPickInProgress);
+        }
+        if git_dir.join("REVERT_HEAD").exists() {
+            issues.push(StaleState::RevertInProgress);
+        }
+
+        Ok(issues)
+    }
+
+    fn collect_lock_files(dir: &Path, found: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                Self::collect_lock_files(&path, found);
+            } else if path.extension().is_some_and(|ext| ext == "lock") {
+                found.push(path);
+            }
+        }
+    }
+
+    /// Shared precondition: fail with an explanation if the repository has stale locks
+    /// or an interrupted operation
+    pub fn no_stale_locks() -> Result<()> {
+        let issues = Self::detect_stale_state()?;
+        if issues.is_empty() {
+            return Ok(());
+        }
+
+        let details = issues
+            .iter()
+            .map(|issue| format!("  • {}", issue.description()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Err(GitXError::GitCommand(format!(
+            "Repository is in an interrupted state (a previous git command may have crashed):\n{details}\n💡 Run 'git x fix-locks' to inspect and clean up"
+        )))
+    }
+
+    /// Check whether another git process is running inside the given repository
+    ///
+    /// On Linux this inspects `/proc` and only counts git processes whose working
+    /// directory is inside the repository. Elsewhere any running git process counts.
+    pub fn git_process_running(repo_dir: &Path) -> bool {
+        let proc_dir = Path::new("/proc");
+        if proc_dir.is_dir() {
+            let Ok(entries) = std::fs::read_dir(proc_dir) else {
+                return false;
+            };
+            let own_pid = std::process::id().to_string();
+
+            return entries.flatten().any(|entry| {
+                let pid = entry.file_name();
+                let pid = pid.to_string_lossy();
+                if !pid.chars().all(|c| c.is_ascii_digit()) || pid == own_pid {
+                    return false;
+                }
+
+                let is_git = std::fs::read_to_string(entry.path().join("comm"))
+                    .map(|comm| comm.trim() == "git")
+                    .unwrap_or(false);
+
+                is_git
+                    && std::fs::read_link(entry.path().join("cwd"))
+                        .map(|cwd| cwd.starts_with(repo_dir))
+                        .unwrap_or(false)
+            });
+        }
+
+        std::process::Command::new("ps")
+            .args(["-A", "-o", "comm="])
+            .output()
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout).lines().any(|line| {
+                    Path::new(line.trim())
+                        .file_name()
+                        .is_some_and(|name| name == "git" || name == "git.exe")
+                })
+            })
+            .unwrap_or(false)
+    }
+}
+
 /// Specific validators for different types of input
 pub struct BranchNameValidator;
 
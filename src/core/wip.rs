@@ -0,0 +1,62 @@
+use crate::Result;
+use crate::core::git::GitOperations;
+
+/// Trailer `git x wip` adds so lint, hooks and `ready` recognize the commit
+pub const WIP_TRAILER: &str = "Git-X-WIP: true";
+
+/// Whether a commit message marks work in progress
+///
+/// Either the trailer `git x wip` writes, or a subject starting with a
+/// standalone "WIP" (`WIP`, `WIP: ...`, `wip! ...`, but not `Wipe ...`).
+pub fn is_wip(message: &str) -> bool {
+    if message.lines().any(|line| line.trim() == WIP_TRAILER) {
+        return true;
+    }
+    let subject = message.lines().next().unwrap_or_default().trim_start();
+    subject
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("wip"))
+        && !subject[3..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric())
+}
+
+/// The message `git x wip` commits with
+pub fn wip_message(summary: Option<&str>, branch: &str) -> String {
+    let summary = summary
+        .map(str::trim)
+        .filter(|summary| !summary.is_empty())
+        .map_or_else(|| format!("work in progress on {branch}"), String::from);
+    format!("WIP: {summary}\n\n{WIP_TRAILER}\n")
+}
+
+/// WIP commits on the current branch, newest first, as (short hash, subject)
+///
+/// On the default branch that's the commits not pushed upstream; elsewhere
+/// the commits not on the default branch. Empty on a detached HEAD.
+pub fn branch_wip_commits(branch: &str) -> Result<Vec<(String, String)>> {
+    if branch == "HEAD" {
+        return Ok(Vec::new());
+    }
+    let range = match GitOperations::default_branch() {
+        Some(default) if !GitOperations::is_default_branch(branch, &default) => {
+            format!("{default}..HEAD")
+        }
+        _ => match GitOperations::upstream_branch() {
+            Ok(upstream) => format!("{upstream}..HEAD"),
+            Err(_) => return Ok(Vec::new()),
+        },
+    };
+
+    let log = GitOperations::run(&["log", "--no-merges", "--format=%h%x1f%B%x1e", &range])?;
+    Ok(log
+        .split('\u{1e}')
+        .filter_map(|record| record.trim_start().split_once('\u{1f}'))
+        .filter(|(_, message)| is_wip(message))
+        .map(|(hash, message)| {
+            let subject = message.lines().next().unwrap_or_default();
+            (hash.to_string(), subject.to_string())
+        })
+        .collect())
+}
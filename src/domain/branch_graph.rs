@@ -0,0 +1,189 @@
+use crate::Result;
+use crate::core::git::GitOperations;
+use std::collections::BTreeMap;
+
+/// Long-lived branches that are always roots, even after merging others in
+pub const TRUNK_BRANCHES: &[&str] = &["main", "master", "develop"];
+
+/// Which local branches are built on top of which
+///
+/// A branch depends on another when the other's tip is in its history. Only the
+/// nearest such branches are kept, so `main -> feature -> feature-part2` doesn't
+/// also record `feature-part2` as depending on `main`.
+#[derive(Debug, Clone, Default)]
+pub struct BranchGraph {
+    parents: BTreeMap<String, Vec<String>>,
+}
+
+impl BranchGraph {
+    /// Build the graph from branch tips and, for each branch, the branches its
+    /// history contains (itself included, as `git branch --merged` reports)
+    ///
+    /// Trunk branches never get parents: once a feature is merged into `main`,
+    /// `main` contains it but isn't built on it.
+    pub fn from_containment(
+        tips: &BTreeMap<String, String>,
+        contained: &BTreeMap<String, Vec<String>>,
+    ) -> Self {
+        let no_branches = Vec::new();
+        let contains = |outer: &str, inner: &str| {
+            contained
+                .get(outer)
+                .unwrap_or(&no_branches)
+                .iter()
+                .any(|branch| branch == inner)
+        };
+        // Branches pointing at the same commit don't depend on each other
+        let same_tip = |a: &str, b: &str| tips.get(a) == tips.get(b);
+
+        let mut parents = BTreeMap::new();
+        for branch in tips.keys() {
+            if TRUNK_BRANCHES.contains(&branch.as_str()) {
+                parents.insert(branch.clone(), Vec::new());
+                continue;
+            }
+            let candidates: Vec<&String> = contained
+                .get(branch)
+                .unwrap_or(&no_branches)
+                .iter()
+                .filter(|other| *other != branch && !same_tip(other, branch))
+                .collect();
+
+            let mut nearest: Vec<String> = candidates
+                .iter()
+                .filter(|candidate| {
+                    !candidates.iter().any(|other| {
+                        other != *candidate
+                            && !same_tip(other, candidate)
+                            && contains(other, candidate)
+                    })
+                })
+                .map(|candidate| candidate.to_string())
+                .collect();
+            nearest.sort();
+            parents.insert(branch.clone(), nearest);
+        }
+
+        Self { parents }
+    }
+
+    /// Build the graph for the local branches of the current repository
+    pub fn build() -> Result<Self> {
+        let refs = GitOperations::run(&[
+            "for-each-ref",
+            "--format=%(refname:short) %(objectname)",
+            "refs/heads",
+        ])?;
+        let tips: BTreeMap<String, String> = refs
+            .lines()
+            .filter_map(|line| line.rsplit_once(' '))
+            .map(|(branch, tip)| (branch.to_string(), tip.to_string()))
+            .collect();
+
+        let mut contained = BTreeMap::new();
+        for branch in tips.keys() {
+            let merged = GitOperations::run(&[
+                "for-each-ref",
+                "--format=%(refname:short)",
+                "--merged",
+                branch,
+                "refs/heads",
+            ])?;
+            contained.insert(branch.clone(), merged.lines().map(String::from).collect());
+        }
+
+        Ok(Self::from_containment(&tips, &contained))
+    }
+
+    /// Branches a branch is directly based on
+    pub fn parents(&self, branch: &str) -> &[String] {
+        self.parents.get(branch).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Branches directly based on `branch`
+    pub fn dependents(&self, branch: &str) -> Vec<String> {
+        self.parents
+            .iter()
+            .filter(|(_, parents)| parents.iter().any(|parent| parent == branch))
+            .map(|(child, _)| child.clone())
+            .collect()
+    }
+
+    /// Whether any branch is based on another
+    pub fn has_dependencies(&self) -> bool {
+        self.parents.values().any(|parents| !parents.is_empty())
+    }
+
+    /// Branches that would lose their base if `deleting` were removed, keyed by
+    /// the deleted branch
+    ///
+    /// Dependents that are deleted too, or that keep another base (such as a
+    /// branch at the same commit), don't count.
+    pub fn orphaned_by(&self, deleting: &[String]) -> Vec<(String, Vec<String>)> {
+        deleting
+            .iter()
+            .filter_map(|branch| {
+                let orphans: Vec<String> = self
+                    .dependents(branch)
+                    .into_iter()
+                    .filter(|child| !deleting.contains(child))
+                    .filter(|child| {
+                        self.parents(child)
+                            .iter()
+                            .all(|parent| deleting.contains(parent))
+                    })
+                    .collect();
+                (!orphans.is_empty()).then(|| (branch.clone(), orphans))
+            })
+            .collect()
+    }
+
+    /// Warning lines for `orphaned_by`, ready to show before deleting
+    pub fn orphan_warnings(&self, deleting: &[String]) -> Vec<String> {
+        self.orphaned_by(deleting)
+            .into_iter()
+            .map(|(branch, orphans)| {
+                format!(
+                    "⚠️  '{branch}' has branches built on it: {} (rebase them first with 'git rebase --onto')",
+                    orphans.join(", ")
+                )
+            })
+            .collect()
+    }
+
+    /// Render as a tree, one root per branch that isn't based on another
+    pub fn render(&self) -> String {
+        let mut lines = Vec::new();
+        for (branch, parents) in &self.parents {
+            if parents.is_empty() {
+                lines.push(branch.clone());
+                self.render_children(branch, "", &mut lines);
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn render_children(&self, branch: &str, prefix: &str, lines: &mut Vec<String>) {
+        let children = self.dependents(branch);
+        for (i, child) in children.iter().enumerate() {
+            let last = i + 1 == children.len();
+            let others: Vec<&str> = self
+                .parents(child)
+                .iter()
+                .filter(|parent| *parent != branch)
+                .map(String::as_str)
+                .collect();
+            let note = if others.is_empty() {
+                String::new()
+            } else {
+                format!(" (also on {})", others.join(", "))
+            };
+            lines.push(format!(
+                "{prefix}{}{child}{note}",
+                if last { "└── " } else { "├── " }
+            ));
+            let child_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            self.render_children(child, &child_prefix, lines);
+        }
+    }
+}
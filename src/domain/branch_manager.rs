@@ -191,7 +191,8 @@ impl BranchManager {
         &self,
         request: CleanBranchesRequest,
     ) -> Result<CleanBranchesResult> {
-        let merged_branches = GitOperations::merged_branches()?;
+        let baseline = GitOperations::require_default_branch()?;
+        let merged_branches = GitOperations::merged_into(&baseline)?;
         let current_branch = GitOperations::current_branch()?;
         let protected_branches = ["main", "master", "develop"];
 
@@ -199,6 +200,7 @@ impl BranchManager {
             .into_iter()
             .filter(|branch| branch != &current_branch)
             .filter(|branch| !protected_branches.contains(&branch.as_str()))
+            .filter(|branch| !GitOperations::is_default_branch(branch, &baseline))
             .collect();
 
         if candidates.is_empty() {
@@ -225,7 +227,13 @@ impl BranchManager {
                     candidates.join(", ")
                 );
 
-                if !Safety::confirm_destructive_operation("Clean merged branches", &details)? {
+                if !Safety::confirm_bulk_deletion(
+                    "Clean merged branches",
+                    &details,
+                    candidates.len(),
+                    "branches",
+                    false,
+                )? {
                     return Ok(CleanBranchesResult {
                         candidates: candidates.clone(),
                         deleted: vec![],
@@ -235,9 +243,9 @@ impl BranchManager {
                 }
             }
 
-            // Perform deletions
+            // `-d` only checks merges into HEAD, and these were checked against the default branch
             for branch in &candidates {
-                match BranchOperations::delete(branch, false) {
+                match BranchOperations::delete(branch, true) {
                     Ok(_) => deleted.push(branch.clone()),
                     Err(_) => failed.push(branch.clone()),
                 }
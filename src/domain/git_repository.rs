@@ -96,12 +96,10 @@ impl GitRepository {
         Validate::in_git_repo()?;
 
         match operation {
-            "destructive" => {
-                if !GitOperations::is_working_directory_clean()? {
-                    return Err(GitXError::GitCommand(
-                        "Working directory must be clean for destructive operations".to_string(),
-                    ));
-                }
+            "destructive" if !GitOperations::is_working_directory_clean()? => {
+                return Err(GitXError::GitCommand(
+                    "Working directory must be clean for destructive operations".to_string(),
+                ));
             }
             "commit" => {
                 let staged = GitOperations::staged_files()?;
@@ -1,5 +1,9 @@
+pub mod branch_graph;
 pub mod branch_manager;
 pub mod git_repository;
+pub mod refs;
 
+pub use branch_graph::*;
 pub use branch_manager::*;
 pub use git_repository::*;
+pub use refs::*;
@@ -0,0 +1,165 @@
+use crate::core::{
+    git::{GitOperations, short_sha},
+    validation::Validate,
+};
+use crate::{GitXError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Implements the string conversions shared by every name type
+macro_rules! string_newtype {
+    ($name:ident) => {
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = GitXError;
+
+            fn from_str(s: &str) -> Result<Self> {
+                Self::new(s)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> String {
+                value.0
+            }
+        }
+    };
+}
+
+/// A local branch name, checked with [`Validate::branch_name`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Branch(String);
+
+impl Branch {
+    pub fn new(name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        Validate::branch_name(&name)?;
+        Ok(Self(name))
+    }
+}
+
+string_newtype!(Branch);
+
+/// Any revision git can resolve: a branch, tag, `HEAD~2`, or commit hash
+///
+/// Only the syntax is checked here; whether it exists is up to the command.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RefName(String);
+
+impl RefName {
+    pub fn new(name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        Validate::ref_name(&name)?;
+        Ok(Self(name))
+    }
+}
+
+string_newtype!(RefName);
+
+impl From<Branch> for RefName {
+    fn from(branch: Branch) -> Self {
+        Self(branch.0)
+    }
+}
+
+/// A full or abbreviated commit hash, checked with [`Validate::commit_hash`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CommitId(String);
+
+impl CommitId {
+    pub fn new(hash: impl Into<String>) -> Result<Self> {
+        let hash = hash.into();
+        Validate::commit_hash(&hash)?;
+        Ok(Self(hash))
+    }
+
+    /// Full hash of the commit a revision points at
+    pub fn resolve(rev: &RefName) -> Result<Self> {
+        let hash = GitOperations::run(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{rev}^{{commit}}"),
+        ])?;
+        Self::new(hash.trim())
+    }
+
+    /// First seven characters, as `git log --oneline` shows them
+    pub fn short(&self) -> &str {
+        short_sha(&self.0)
+    }
+}
+
+string_newtype!(CommitId);
+
+impl From<CommitId> for RefName {
+    fn from(commit: CommitId) -> Self {
+        Self(commit.0)
+    }
+}
+
+/// A branch on a remote, written `remote/branch` (e.g. `origin/feature/login`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RemoteBranch {
+    remote: String,
+    branch: Branch,
+}
+
+impl RemoteBranch {
+    pub fn new(remote: impl Into<String>, branch: Branch) -> Result<Self> {
+        let remote = remote.into();
+        Validate::remote_name(&remote)?;
+        Ok(Self { remote, branch })
+    }
+
+    pub fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    pub fn branch(&self) -> &Branch {
+        &self.branch
+    }
+}
+
+impl fmt::Display for RemoteBranch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.remote, self.branch)
+    }
+}
+
+impl FromStr for RemoteBranch {
+    type Err = GitXError;
+
+    /// Split at the first `/`: remote names can't contain one, branch names can
+    fn from_str(s: &str) -> Result<Self> {
+        let (remote, branch) = s.split_once('/').ok_or_else(|| {
+            GitXError::Parse(format!(
+                "Remote branch '{s}' must be written as <remote>/<branch>"
+            ))
+        })?;
+        Self::new(remote, Branch::new(branch)?)
+    }
+}
+
+impl From<RemoteBranch> for RefName {
+    fn from(remote_branch: RemoteBranch) -> Self {
+        Self(remote_branch.to_string())
+    }
+}
@@ -17,14 +17,79 @@ pub mod cli;
 #[cfg(test)]
 pub mod examples;
 
+/// A git process that exited unsuccessfully
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitFailure {
+    /// Arguments passed to `git`
+    pub args: Vec<String>,
+    /// Exit code, `None` when git was killed by a signal
+    pub status: Option<i32>,
+    /// Captured stderr, trimmed; empty when git wrote straight to the terminal
+    pub stderr: String,
+}
+
+impl GitFailure {
+    pub fn new(args: &[&str], status: Option<i32>, stderr: &str) -> Self {
+        Self {
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            status,
+            stderr: stderr.trim().to_string(),
+        }
+    }
+
+    /// The command line as it would be typed, e.g. `git push origin main`
+    pub fn command(&self) -> String {
+        std::iter::once("git")
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn status_label(&self) -> String {
+        match self.status {
+            Some(code) => format!("exit status {code}"),
+            None => "terminated by signal".to_string(),
+        }
+    }
+
+    /// First line of git's message, or the command and status when it printed nothing
+    pub fn summary(&self) -> String {
+        match self.stderr.lines().find(|line| !line.trim().is_empty()) {
+            Some(line) => line.trim().to_string(),
+            None => format!("{} ({})", self.command(), self.status_label()),
+        }
+    }
+}
+
+impl std::fmt::Display for GitFailure {
+    /// `{}` is the [`GitFailure::summary`]; `{:#}` adds the command, status and full stderr
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !f.alternate() {
+            return write!(f, "{}", self.summary());
+        }
+        write!(f, "{}\n  {}", self.command(), self.status_label())?;
+        if !self.stderr.is_empty() {
+            write!(f, "\n  stderr:")?;
+            for line in self.stderr.lines() {
+                write!(f, "\n    {line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Common error type for git-x operations
 #[derive(Debug)]
 pub enum GitXError {
     GitCommand(String),
+    /// A git invocation failed; see [`GitXError::git_failure`]
+    GitProcess(GitFailure),
     Io(std::io::Error),
     Parse(String),
     Dialog(String),
     Join(String),
+    /// A network operation skipped because offline mode is on
+    Offline(String),
     Other(String),
 }
 
@@ -32,10 +97,17 @@ impl std::fmt::Display for GitXError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             GitXError::GitCommand(cmd) => write!(f, "Git command failed: {cmd}"),
+            GitXError::GitProcess(failure) if f.alternate() => {
+                write!(f, "Git command failed: {failure:#}")
+            }
+            GitXError::GitProcess(failure) => write!(f, "Git command failed: {failure}"),
             GitXError::Io(err) => write!(f, "IO error: {err}"),
             GitXError::Parse(msg) => write!(f, "Parse error: {msg}"),
             GitXError::Dialog(msg) => write!(f, "Dialog error: {msg}"),
             GitXError::Join(msg) => write!(f, "Join error: {msg}"),
+            GitXError::Offline(operation) => {
+                write!(f, "Skipped {operation}: offline mode is on")
+            }
             GitXError::Other(msg) => write!(f, "{msg}"),
         }
     }
@@ -46,14 +118,32 @@ impl std::error::Error for GitXError {
         match self {
             GitXError::Io(err) => Some(err),
             GitXError::GitCommand(_)
+            | GitXError::GitProcess(_)
             | GitXError::Parse(_)
             | GitXError::Dialog(_)
             | GitXError::Join(_)
+            | GitXError::Offline(_)
             | GitXError::Other(_) => None,
         }
     }
 }
 
+impl GitXError {
+    /// Error for `git <args>` exiting with `status` after writing `stderr`
+    pub fn git_failure(args: &[&str], status: Option<i32>, stderr: &str) -> Self {
+        GitXError::GitProcess(GitFailure::new(args, status, stderr))
+    }
+
+    /// Error for a finished git process, reading status and stderr from its output
+    pub fn from_output(args: &[&str], output: &std::process::Output) -> Self {
+        Self::git_failure(
+            args,
+            output.status.code(),
+            &String::from_utf8_lossy(&output.stderr),
+        )
+    }
+}
+
 impl From<std::io::Error> for GitXError {
     fn from(err: std::io::Error) -> Self {
         GitXError::Io(err)
@@ -4,151 +4,827 @@ use clap::Parser;
 use git_x::cli::{Cli, Commands};
 
 use git_x::commands::analysis::{
-    AsyncSummaryCommand, ColorGraphCommand, GraphCommand, ParallelContributorsCommand,
-    ParallelLargeFilesCommand, ParallelTechnicalDebtCommand, SinceCommand as NewSinceCommand,
+    AsyncSummaryCommand, AuditMergesCommand, BranchDiffCommand, ColorGraphCommand, DependencyCheck,
+    DiffStatCommand, ExportReportCommand, FindMergeCommand, GoneFilesCommand, GraphCommand,
+    GraphOptions, HeatmapCommand, LogCursor, LogJsonCommand, OwnershipCommand, PackagesCommand,
+    ParallelContributorsCommand, ParallelLargeFilesCommand, ParallelTechnicalDebtCommand,
+    PerfCommand, ReviewCommand, ReviewSizeCommand, SinceCommand as NewSinceCommand, SummaryFilter,
     WhatCommand,
 };
-use git_x::commands::branch::AsyncCleanBranchesCommand;
-use git_x::commands::commit::{BisectCommand, FixupCommand, UndoCommand as NewUndoCommand};
+use git_x::commands::branch::{ArchiveCommand, AsyncCleanBranchesCommand, BranchDepsCommand};
+use git_x::commands::commit::{
+    AutoFixupCommand, BisectCommand, FixupCommand, ImportCommitsCommand, PatchCommand,
+    RewordCommand, SquashCommand, UndoCommand as NewUndoCommand,
+};
 use git_x::commands::repository::{
-    AsyncHealthCommand, AsyncInfoCommand, AsyncUpstreamCommand, NewBranchCommand,
+    AsyncHealthCommand, AsyncInfoCommand, AsyncUpstreamCommand, CiCommand, FixLocksCommand,
+    ForkSetupCommand, GotoCommand, HealthDepth, HotfixCommand, LfsCommand, LineEndingsCommand,
+    MirrorCommand, NewBranchCommand, ReposAction, ReposCommand, RmSecretsCommand,
+    ScanStagedCommand, ShowConfigCommand, UncommittedCommand, WhoamiCommand,
 };
-use git_x::core::traits::Command as NewCommand;
+use git_x::core::clipboard::Clipboard;
+use git_x::core::export::ExportFormat;
+use git_x::core::jobs::Jobs;
+use git_x::core::network::Network;
+use git_x::core::output::{JsonSink, StdoutSink};
+use git_x::core::packages::Packages;
+use git_x::core::traits::{Command as NewCommand, StreamingCommand};
+use git_x::core::validation::Validate;
 
-#[tokio::main]
-async fn main() {
+fn main() {
     let cli = Cli::parse();
 
+    // Sized before anything runs in parallel, since pools can't be resized later
+    let jobs = Jobs::resolve(cli.jobs);
+    Jobs::configure(jobs);
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(jobs)
+        .enable_all()
+        .build()
+        .expect("Failed to start the async runtime");
+    runtime.block_on(run(cli));
+}
+
+async fn run(cli: Cli) {
+    let quiet = cli.quiet;
+    let verbose = cli.verbose;
+    Network::configure(Network::resolve(cli.offline));
+
+    if let Err(e) = check_preconditions(&cli.command) {
+        report(Err(e), quiet, verbose);
+        return;
+    }
+
     match cli.command {
         Commands::RenameBranch { new_name } => {
             use git_x::commands::branch::RenameBranchCommand;
-            let cmd = RenameBranchCommand::new(new_name);
-            match NewCommand::execute(&cmd) {
+            let result = new_name
+                .parse()
+                .and_then(|name| NewCommand::execute(&RenameBranchCommand::new(name)));
+            match result {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Attach { name } => {
+            use git_x::commands::branch::AttachCommand;
+            let result = name
+                .parse()
+                .and_then(|name| NewCommand::execute(&AttachCommand::new(name)));
+            match result {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::PruneBranches { except: _, dry_run } => {
+        Commands::PruneBranches {
+            except,
+            dry_run,
+            yes,
+            older_than,
+            allow_unmerged,
+        } => {
             use git_x::commands::branch::PruneBranchesCommand;
-            let cmd = PruneBranchesCommand::new(dry_run);
+            let result = older_than
+                .map(|age| age.parse())
+                .transpose()
+                .and_then(|older_than| {
+                    let except = except
+                        .iter()
+                        .flat_map(|list| list.split(','))
+                        .map(str::trim)
+                        .filter(|branch| !branch.is_empty())
+                        .map(String::from)
+                        .collect();
+                    let mut cmd = PruneBranchesCommand::new(dry_run)
+                        .with_older_than(older_than)
+                        .with_except(except);
+                    if allow_unmerged {
+                        cmd = cmd.with_allow_unmerged();
+                    }
+                    if quiet {
+                        cmd = cmd.with_quiet();
+                    }
+                    if yes {
+                        cmd = cmd.with_yes();
+                    }
+                    NewCommand::execute(&cmd)
+                });
+            report(result, quiet, verbose);
+        }
+
+        Commands::BranchDeps => {
+            let cmd = BranchDepsCommand::new();
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::MergeQueue {
+            branches,
+            base,
+            prs,
+        } => {
+            use git_x::commands::branch::MergeQueueCommand;
+            let mut cmd = MergeQueueCommand::new(branches).with_base(base);
+            if prs {
+                cmd = cmd.with_pull_requests();
+            }
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Move {
+            branch,
+            onto,
+            from,
+            dry_run,
+        } => {
+            use git_x::commands::branch::MoveCommand;
+            let result = (|| {
+                let mut cmd = MoveCommand::new(onto.map(|onto| onto.parse()).transpose()?)
+                    .with_branch(branch.map(|branch| branch.parse()).transpose()?)
+                    .with_from(from.map(|from| from.parse()).transpose()?);
+                if dry_run {
+                    cmd = cmd.with_dry_run();
+                }
+                NewCommand::execute(&cmd)
+            })();
+            match result {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::At {
+            when,
+            reference,
+            create,
+            print,
+            commit_dates,
+        } => {
+            use git_x::commands::branch::{AtAction, AtCommand};
+            let action = match (create, print) {
+                (_, true) => AtAction::Print,
+                (Some(name), false) => AtAction::Create(name),
+                (None, false) => AtAction::Detach,
+            };
+            let mut cmd = AtCommand::new(when, reference).with_action(action);
+            if commit_dates {
+                cmd = cmd.with_commit_dates();
+            }
+            report(NewCommand::execute(&cmd), quiet, verbose);
+        }
+
+        Commands::Pr { action } => {
+            use git_x::commands::branch::{PrAction, PrCommand};
+
+            let pr_action = match action {
+                git_x::cli::PrAction::Checkout { number, remote } => {
+                    PrAction::Checkout { number, remote }
+                }
+                git_x::cli::PrAction::Clean { all, dry_run } => PrAction::Clean { all, dry_run },
+            };
+            match NewCommand::execute(&PrCommand::new(pr_action)) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::Info => {
+        Commands::Info { json } => {
             let cmd = AsyncInfoCommand::new();
-            match cmd.execute_parallel().await {
+            let result = if json {
+                cmd.execute_json().await
+            } else {
+                cmd.execute_parallel().await
+            };
+            match result {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::Graph => {
-            let cmd = GraphCommand::new();
+        Commands::Graph {
+            first_parent,
+            merges_only,
+            branches,
+        } => {
+            let result = graph_options(first_parent, merges_only, branches).and_then(|options| {
+                NewCommand::execute(&GraphCommand::new().with_options(options))
+            });
+            match result {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::ColorGraph {
+            first_parent,
+            merges_only,
+            branches,
+        } => {
+            let result = graph_options(first_parent, merges_only, branches).and_then(|options| {
+                NewCommand::execute(&ColorGraphCommand::new().with_options(options))
+            });
+            match result {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Health {
+            json,
+            fast,
+            deep,
+            no_cache,
+        } => {
+            let depth = if fast {
+                HealthDepth::Fast
+            } else if deep {
+                HealthDepth::Deep
+            } else {
+                HealthDepth::Standard
+            };
+            let mut cmd = AsyncHealthCommand::new().with_depth(depth);
+            if no_cache {
+                cmd = cmd.with_no_cache();
+            }
+            if json {
+                let mut sink = JsonSink::new();
+                match cmd.stream_parallel(&mut sink).await {
+                    Ok(()) => println!("{}", sink.to_json()),
+                    Err(e) => print_error(&e, verbose),
+                }
+            } else if let Err(e) = cmd.stream_parallel(&mut StdoutSink).await {
+                print_error(&e, verbose);
+            }
+        }
+
+        Commands::Since { reference, copy } => {
+            let cmd = match reference {
+                Some(reference) => NewSinceCommand::new(reference),
+                None => NewSinceCommand::with_default_reference(),
+            };
+            match NewCommand::execute(&cmd) {
+                Ok(output) => print_and_copy(&output, copy),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Undo => {
+            let cmd = NewUndoCommand::new();
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
-        Commands::ColorGraph => {
-            let cmd = ColorGraphCommand::new();
+
+        Commands::Wip { message } => {
+            use git_x::commands::commit::WipCommand;
+            let cmd = WipCommand::new().with_message(message);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::Health => {
-            let cmd = AsyncHealthCommand::new();
-            match cmd.execute_parallel().await {
+        Commands::Unwip => {
+            use git_x::commands::commit::UnwipCommand;
+            match NewCommand::execute(&UnwipCommand::new()) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::Since { reference } => {
-            let cmd = NewSinceCommand::new(reference);
+        Commands::UndoFile { path, restore } => {
+            use git_x::commands::commit::UndoFileCommand;
+            let cmd = UndoFileCommand::new(path).with_restore(restore);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::Undo => {
-            let cmd = NewUndoCommand::new();
+        Commands::Archive {
+            action,
+            branches,
+            remote,
+            dry_run,
+        } => {
+            use git_x::commands::branch::ArchiveAction;
+
+            let archive_action = match action {
+                None => ArchiveAction::Archive {
+                    branches,
+                    remote,
+                    dry_run,
+                },
+                Some(git_x::cli::ArchiveAction::List) => ArchiveAction::List,
+                Some(git_x::cli::ArchiveAction::Restore { name, as_branch }) => {
+                    ArchiveAction::Restore { name, as_branch }
+                }
+            };
+
+            let cmd = ArchiveCommand::new(archive_action);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::CleanBranches { dry_run } => {
-            let cmd = AsyncCleanBranchesCommand::new(dry_run);
+        Commands::CleanBranches { dry_run, gone, yes } => {
+            let mut cmd = AsyncCleanBranchesCommand::new(dry_run);
+            if gone {
+                cmd = cmd.with_gone_upstreams();
+            }
+            if yes {
+                cmd = cmd.with_yes();
+            }
             match cmd.execute_parallel().await {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::What {
+            target,
+            package,
+            copy,
+        } => {
+            let result = (|| {
+                let mut cmd = WhatCommand::new(target.map(|t| t.parse()).transpose()?);
+                if let Some(package) = package {
+                    cmd = cmd.with_package(Packages::find(&package)?);
+                }
+                NewCommand::execute(&cmd)
+            })();
+            match result {
+                Ok(output) => print_and_copy(&output, copy),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::What { target } => {
-            let cmd = WhatCommand::new(target);
+        Commands::Packages => {
+            report(NewCommand::execute(&PackagesCommand::new()), quiet, verbose);
+        }
+
+        Commands::BranchDiff { target } => {
+            let result = target
+                .parse()
+                .and_then(|target| NewCommand::execute(&BranchDiffCommand::new(target)));
+            match result {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::GoneFiles {
+            query,
+            restore,
+            limit,
+        } => {
+            let cmd = GoneFilesCommand::new(query, restore, limit);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::Summary { since } => {
-            let cmd = AsyncSummaryCommand::new(since);
-            match cmd.execute_parallel().await {
+        Commands::FindMerge {
+            commit,
+            into,
+            pr,
+            copy,
+        } => {
+            let mut cmd = FindMergeCommand::new(commit).with_into(into);
+            if pr {
+                cmd = cmd.with_pr();
+            }
+            match NewCommand::execute(&cmd) {
+                Ok(output) => print_and_copy(&output, copy),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Perf { apply } => {
+            let cmd = PerfCommand::new(apply);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Heatmap {
+            since,
+            author,
+            branch,
+            all,
+            path,
+        } => {
+            let mut cmd = HeatmapCommand::new()
+                .with_since(since)
+                .with_author(author)
+                .with_path(path)
+                .with_branch(branch);
+            if all {
+                cmd = cmd.with_all_branches();
+            }
+            match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::Sync { merge } => {
+        Commands::FilesChangedTogether {
+            since,
+            limit,
+            min_shared,
+            max_files,
+            path,
+        } => {
+            use git_x::commands::analysis::FilesChangedTogetherCommand;
+            let cmd = FilesChangedTogetherCommand::new()
+                .with_since(since)
+                .with_path(path)
+                .with_limit(limit)
+                .with_min_shared(min_shared)
+                .with_max_files(max_files);
+            report(NewCommand::execute(&cmd), quiet, verbose);
+        }
+
+        Commands::CommitSizes {
+            range,
+            since,
+            by,
+            giant_lines,
+            giant_files,
+            limit,
+        } => {
+            use git_x::commands::analysis::CommitSizesCommand;
+            let result = by.parse().and_then(|metric| {
+                CommitSizesCommand::new(range)
+                    .with_since(since)
+                    .with_metric(metric)
+                    .with_giant_lines(giant_lines)
+                    .with_giant_files(giant_files)
+                    .with_limit(limit)
+                    .execute()
+            });
+            report(result, quiet, verbose);
+        }
+
+        Commands::Summary {
+            since,
+            exclude_merges,
+            authors,
+            teams,
+            by_author,
+            package,
+            include_generated,
+            json,
+            copy,
+        } => {
+            let package = match package.as_deref().map(Packages::find).transpose() {
+                Ok(package) => package,
+                Err(e) => {
+                    print_error(&e, verbose);
+                    return;
+                }
+            };
+            let mut cmd = AsyncSummaryCommand::new(since).with_filter(SummaryFilter {
+                exclude_merges,
+                authors,
+                teams,
+                by_author,
+                package,
+                include_generated,
+            });
+            if json {
+                cmd = cmd.with_json();
+            }
+            match cmd.execute_parallel().await {
+                Ok(output) => print_and_copy(&output, copy),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Standup {
+            since,
+            teams,
+            exclude_merges,
+            authors,
+        } => {
+            use git_x::commands::analysis::StandupCommand;
+            let cmd = StandupCommand::new(since)
+                .with_filter(SummaryFilter {
+                    exclude_merges,
+                    authors,
+                    ..SummaryFilter::default()
+                })
+                .with_teams(teams);
+            report(NewCommand::execute(&cmd), quiet, verbose);
+        }
+
+        Commands::Sync { merge, autostash } => {
             use git_x::commands::repository::{SyncCommand, SyncStrategy};
             let strategy = if merge {
                 SyncStrategy::Merge
             } else {
                 SyncStrategy::Rebase
             };
-            let cmd = SyncCommand::new(strategy);
+            let mut cmd = SyncCommand::new(strategy);
+            if autostash {
+                cmd = cmd.with_autostash();
+            }
+            if quiet {
+                cmd = cmd.with_quiet();
+            }
+            report(NewCommand::execute(&cmd), quiet, verbose);
+        }
+
+        Commands::New {
+            branch_name,
+            from,
+            fresh,
+            max_behind,
+            update,
+            no_hooks,
+        } => {
+            use git_x::commands::repository::FreshnessCheck;
+            let result =
+                NewBranchCommand::parse(&branch_name, from.as_deref()).and_then(|mut cmd| {
+                    cmd = cmd.with_freshness(FreshnessCheck::resolve(fresh, max_behind, update));
+                    if quiet {
+                        cmd = cmd.with_quiet();
+                    }
+                    if no_hooks {
+                        cmd = cmd.with_no_hooks();
+                    }
+                    NewCommand::execute(&cmd)
+                });
+            report(result, quiet, verbose);
+        }
+
+        Commands::LargeFiles {
+            limit,
+            threshold,
+            include_generated,
+        } => {
+            let mut cmd = ParallelLargeFilesCommand::new(threshold, Some(limit));
+            if include_generated {
+                cmd = cmd.with_include_generated();
+            }
+            match cmd.execute_parallel() {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Fixup {
+            commit_hash,
+            rebase,
+            auto,
+        } => {
+            if auto {
+                let mut cmd = AutoFixupCommand::new(rebase);
+                if quiet {
+                    cmd = cmd.with_quiet();
+                }
+                report(NewCommand::execute(&cmd), quiet, verbose);
+            } else {
+                let result = commit_hash.unwrap_or_default().parse().and_then(|commit| {
+                    let mut cmd = FixupCommand::new(commit, rebase);
+                    if quiet {
+                        cmd = cmd.with_quiet();
+                    }
+                    NewCommand::execute(&cmd)
+                });
+                report(result, quiet, verbose);
+            }
+        }
+        Commands::Reword { commit, message } => {
+            let result = commit
+                .parse()
+                .and_then(|commit| NewCommand::execute(&RewordCommand::new(commit, message)));
+            match result {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::Squash { target, message } => {
+            let cmd = SquashCommand::new(target, message);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::DiffStat {
+            from,
+            to,
+            depth,
+            json,
+        } => {
+            let cmd = DiffStatCommand::new(from, to, depth)
+                .with_format(json.then_some(ExportFormat::Json));
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::AuditMerges {
+            since,
+            branches,
+            json,
+            csv,
+        } => {
+            let format = if json {
+                Some(ExportFormat::Json)
+            } else if csv {
+                Some(ExportFormat::Csv)
+            } else {
+                None
+            };
+            let cmd = AuditMergesCommand::new()
+                .with_since(since)
+                .with_branches(branches)
+                .with_format(format);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
+        Commands::Ownership {
+            since,
+            depth,
+            top,
+            json,
+            csv,
+        } => {
+            let format = if json {
+                Some(ExportFormat::Json)
+            } else if csv {
+                Some(ExportFormat::Csv)
+            } else {
+                None
+            };
+            let cmd = OwnershipCommand::new()
+                .with_since(since)
+                .with_depth(depth)
+                .with_top(top)
+                .with_format(format);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::ExportReport { format, output } => {
+            let result = format
+                .map(|format| format.parse())
+                .transpose()
+                .and_then(|format| {
+                    NewCommand::execute(
+                        &ExportReportCommand::new()
+                            .with_format(format)
+                            .with_output(output),
+                    )
+                });
+            match result {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::LogJson {
+            range,
+            since,
+            until,
+            limit,
+            cursor,
+            no_files,
+        } => {
+            let result = cursor
+                .map(|cursor| cursor.parse::<LogCursor>())
+                .transpose()
+                .and_then(|cursor| {
+                    let mut cmd = LogJsonCommand::new(range)
+                        .with_since(since)
+                        .with_until(until)
+                        .with_limit(limit);
+                    if let Some(cursor) = cursor {
+                        cmd = cmd.with_cursor(cursor);
+                    }
+                    if no_files {
+                        cmd = cmd.without_files();
+                    }
+                    // Streamed, so huge histories are written as they are read
+                    cmd.stream(&mut StdoutSink)
+                });
+            if let Err(e) = result {
+                print_error(&e, verbose);
+            }
+        }
+        Commands::ReviewSize { target, json } => {
+            let mut cmd = ReviewSizeCommand::new(target);
+            if json {
+                cmd = cmd.with_json();
+            }
+            report(NewCommand::execute(&cmd), quiet, verbose);
+        }
 
-        Commands::New { branch_name, from } => {
-            let cmd = NewBranchCommand::new(branch_name, from);
+        Commands::Review {
+            target,
+            base,
+            output,
+        } => {
+            let cmd = ReviewCommand::new(target, base, output);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
+        Commands::Hotfix {
+            name,
+            commits,
+            from,
+        } => {
+            let result = (|| {
+                let commits = commits
+                    .iter()
+                    .map(|commit| commit.parse())
+                    .collect::<git_x::Result<Vec<_>>>()?;
+                let from = from.map(|f| f.parse()).transpose()?;
+                NewCommand::execute(&HotfixCommand::new(name.parse()?, commits, from))
+            })();
+            match result {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::FixLocks { dry_run, abort } => {
+            let cmd = FixLocksCommand::new(dry_run, abort);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+        Commands::Rebase { action } => {
+            use git_x::commands::commit::{RebaseAction, RebaseCommand};
 
-        Commands::LargeFiles { limit, threshold } => {
-            let cmd = ParallelLargeFilesCommand::new(threshold, Some(limit));
-            match cmd.execute_parallel() {
+            let rebase_action = match action {
+                git_x::cli::RebaseAction::ContinueSmart => RebaseAction::ContinueSmart,
+            };
+            report(
+                NewCommand::execute(&RebaseCommand::new(rebase_action)),
+                quiet,
+                verbose,
+            );
+        }
+
+        Commands::Patch { action } => {
+            use git_x::commands::commit::PatchAction;
+
+            let patch_action = match action {
+                git_x::cli::PatchAction::Send {
+                    range,
+                    output_dir,
+                    onto,
+                } => PatchAction::Send {
+                    range,
+                    output_dir,
+                    onto,
+                },
+                git_x::cli::PatchAction::Apply { source, no_3way } => PatchAction::Apply {
+                    source,
+                    three_way: !no_3way,
+                },
+            };
+
+            let cmd = PatchCommand::new(patch_action);
+            match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::Fixup {
-            commit_hash,
-            rebase,
+        Commands::ImportCommits {
+            source,
+            path,
+            into,
+            from,
+            dry_run,
         } => {
-            let cmd = FixupCommand::new(commit_hash, rebase);
+            let cmd = ImportCommitsCommand::new(source)
+                .with_path(path)
+                .with_into(into)
+                .with_rev(from)
+                .with_dry_run(dry_run);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
+
         Commands::StashBranch { action } => {
             use git_x::commands::stash::{StashBranchAction as StashAction, StashCommand};
 
@@ -164,18 +840,29 @@ async fn main() {
                 git_x::cli::StashBranchAction::Clean {
                     older_than,
                     dry_run,
+                    mine,
+                    yes,
                 } => StashAction::Clean {
                     older_than,
                     dry_run,
+                    mine,
+                    yes,
                 },
+                git_x::cli::StashBranchAction::List { mine } => StashAction::List { mine },
                 git_x::cli::StashBranchAction::ApplyByBranch {
                     branch_name,
                     list_only,
+                    pop,
+                    newest_only,
                 } => StashAction::ApplyByBranch {
                     branch_name,
                     list_only,
+                    pop,
+                    newest_only,
+                },
+                git_x::cli::StashBranchAction::Interactive { sort } => StashAction::Interactive {
+                    sort: sort.parse().unwrap_or_default(),
                 },
-                git_x::cli::StashBranchAction::Interactive => StashAction::Interactive,
                 git_x::cli::StashBranchAction::Export {
                     output_dir,
                     stash_ref,
@@ -188,66 +875,710 @@ async fn main() {
             let cmd = StashCommand::new(stash_action);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Lock { action } => {
+            use git_x::commands::repository::{LockAction, LockCommand};
+
+            let (action, check) = match action {
+                git_x::cli::LockAction::Take { paths } => (LockAction::Take { paths }, false),
+                git_x::cli::LockAction::Release { paths, force } => {
+                    (LockAction::Release { paths, force }, false)
+                }
+                git_x::cli::LockAction::Status => (LockAction::Status, false),
+                git_x::cli::LockAction::Check { check } => match check.parse() {
+                    Ok(check) => (LockAction::Check(check), true),
+                    Err(e) => {
+                        print_error(&e, verbose);
+                        std::process::exit(1);
+                    }
+                },
+            };
+            let cmd = LockCommand::new(action);
+            match NewCommand::execute(&cmd) {
+                // Runs in commit and push hooks, where output belongs on stderr
+                Ok(output) if check => {
+                    if !output.is_empty() {
+                        eprintln!("{output}");
+                    }
+                }
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Lfs { action } => {
+            use git_x::commands::repository::LfsAction;
+
+            let lfs_action = match action {
+                git_x::cli::LfsAction::Status => LfsAction::Status,
+            };
+
+            let cmd = LfsCommand::new(lfs_action);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::LineEndings { fix } => {
+            let cmd = LineEndingsCommand::new().with_fix(fix);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Whoami => {
+            let cmd = WhoamiCommand::new();
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::ShowConfig { json } => {
+            let cmd = ShowConfigCommand::new().with_json(json).with_quiet(quiet);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::ConfigCheck {
+            policy,
+            apply,
+            local,
+        } => {
+            use git_x::commands::repository::ConfigCheckCommand;
+            let mut cmd = ConfigCheckCommand::new().with_policy(policy);
+            if apply {
+                cmd = cmd.with_apply();
+            }
+            if local {
+                cmd = cmd.with_local();
+            }
+            // Exits non-zero on drift so CI and onboarding scripts can gate on it
+            match cmd.run() {
+                Ok((output, drift)) => {
+                    println!("{output}");
+                    if drift {
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    print_error(&e, verbose);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::InitHooks { force, check } => {
+            use git_x::commands::commit::InitHooksCommand;
+            let mut cmd = InitHooksCommand::new();
+            if force {
+                cmd = cmd.with_force();
+            }
+            if let Some(path) = check {
+                cmd = cmd.with_check(path);
+            }
+            // The hook's exit status is what rejects the commit
+            match cmd.run() {
+                Ok((output, rejected)) => {
+                    if rejected {
+                        eprintln!("{output}");
+                        std::process::exit(1);
+                    }
+                    println!("{output}");
+                }
+                Err(e) => {
+                    print_error(&e, verbose);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Tickets {
+            range,
+            open,
+            install_hook,
+            force,
+            check,
+        } => {
+            use git_x::commands::commit::TicketsCommand;
+            let mut cmd = TicketsCommand::new();
+            if let Some(range) = range {
+                cmd = cmd.with_range(range);
+            }
+            if let Some(id) = open {
+                cmd = cmd.with_open(id);
+            }
+            if install_hook {
+                cmd = cmd.with_install();
+            }
+            if force {
+                cmd = cmd.with_force();
+            }
+            let hook = check.is_some();
+            if let Some(path) = check {
+                cmd = cmd.with_check(path);
+            }
+            match cmd.execute() {
+                // The hook only warns, so the commit always goes ahead
+                Ok(output) if hook => {
+                    if !output.is_empty() {
+                        eprintln!("{output}");
+                    }
+                }
+                Ok(output) => println!("{output}"),
+                Err(e) if hook => eprintln!("⚠️  Ticket check skipped: {e}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::RevertMerge { commit, mainline } => {
+            use git_x::commands::commit::RevertMergeCommand;
+            match RevertMergeCommand::new(commit)
+                .with_mainline(mainline)
+                .execute()
+            {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::ReMerge { branch } => {
+            use git_x::commands::commit::ReMergeCommand;
+            match ReMergeCommand::new(branch).execute() {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::CommitTemplate {
+            install,
+            force,
+            fill,
+            source,
+        } => {
+            use git_x::commands::commit::CommitTemplateCommand;
+            let mut cmd = CommitTemplateCommand::new();
+            if install {
+                cmd = cmd.with_install();
+            }
+            if force {
+                cmd = cmd.with_force();
+            }
+            let hook = fill.is_some();
+            if let Some(path) = fill {
+                cmd = cmd.with_fill(path, source);
+            }
+            match cmd.execute() {
+                Ok(output) => {
+                    if !output.is_empty() {
+                        println!("{output}");
+                    }
+                }
+                // Never block the commit over a template: git still opens the editor
+                Err(e) if hook => eprintln!("⚠️  Commit template not applied: {e}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Hooks { action } => match action {
+            git_x::cli::HooksAction::RunCi {
+                checks,
+                manifest,
+                install_hook,
+            } => {
+                use git_x::commands::hooks::RunCiCommand;
+                let mut cmd = RunCiCommand::new(checks);
+                if let Some(manifest) = manifest {
+                    cmd = cmd.with_manifest(manifest);
+                }
+                if install_hook {
+                    cmd = cmd.with_install_hook();
+                }
+                // Runs as a pre-push hook, so the exit status is what blocks the push
+                match cmd.run() {
+                    Ok((output, failed)) => {
+                        println!("{output}");
+                        if failed {
+                            std::process::exit(1);
+                        }
+                    }
+                    Err(e) => {
+                        print_error(&e, verbose);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            git_x::cli::HooksAction::Switch { branch, dry_run } => {
+                use git_x::commands::hooks::SwitchHooksCommand;
+                let mut cmd = SwitchHooksCommand::new(branch);
+                if dry_run {
+                    cmd = cmd.with_dry_run();
+                }
+                match NewCommand::execute(&cmd) {
+                    Ok(output) => println!("{output}"),
+                    Err(e) => print_error(&e, verbose),
+                }
+            }
+        },
+
+        Commands::Protect {
+            branches,
+            remove,
+            check,
+        } => {
+            use git_x::commands::branch::ProtectCommand;
+            let result = (|| {
+                let mut cmd = ProtectCommand::new(branches);
+                if remove {
+                    cmd = cmd.with_remove();
+                }
+                if let Some(check) = check {
+                    cmd = cmd.with_check(check.parse()?);
+                }
+                cmd.run()
+            })();
+            // Runs as a pre-commit and pre-push hook, so the exit status is what blocks
+            match result {
+                Ok((output, blocked)) => {
+                    if blocked {
+                        eprintln!("{output}");
+                        std::process::exit(1);
+                    }
+                    if !output.is_empty() {
+                        println!("{output}");
+                    }
+                }
+                Err(e) => {
+                    print_error(&e, verbose);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Ready {
+            target,
+            skip,
+            no_fetch,
+        } => {
+            use git_x::commands::repository::ReadyCommand;
+            let mut cmd = ReadyCommand::new().with_skip(skip);
+            if let Some(target) = target {
+                cmd = cmd.with_target(target);
+            }
+            if no_fetch {
+                cmd = cmd.with_no_fetch();
+            }
+            // Exits non-zero when a check fails so scripts and CI can gate on it
+            match cmd.run() {
+                Ok((output, failed)) => {
+                    println!("{output}");
+                    if failed {
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    print_error(&e, verbose);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::ScanStaged {
+            fail_on,
+            install_hook,
+        } => {
+            let mut cmd = ScanStagedCommand::new();
+            if let Some(severity) = fail_on.and_then(|level| level.parse().ok()) {
+                cmd = cmd.with_fail_on(severity);
+            }
+            if install_hook {
+                cmd = cmd.with_install_hook();
+            }
+            // Runs as a pre-commit hook, so the exit status is what blocks the commit
+            match cmd.run() {
+                Ok((output, blocked)) => {
+                    println!("{output}");
+                    if blocked {
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    print_error(&e, verbose);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::RmSecrets {
+            patterns,
+            dry_run,
+            internal,
+        } => {
+            let mut cmd = RmSecretsCommand::new(patterns);
+            if dry_run {
+                cmd = cmd.with_dry_run();
+            }
+            if internal {
+                cmd = cmd.with_internal();
+            }
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Uncommitted {
+            roots,
+            depth,
+            all,
+            json,
+            csv,
+        } => {
+            let format = if json {
+                Some(ExportFormat::Json)
+            } else if csv {
+                Some(ExportFormat::Csv)
+            } else {
+                None
+            };
+            let mut cmd = UncommittedCommand::new(roots)
+                .with_depth(depth)
+                .with_format(format);
+            if all {
+                cmd = cmd.with_all();
+            }
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Repos { action } => {
+            let action = match action {
+                git_x::cli::ReposAction::Add { paths } => ReposAction::Add { paths },
+                git_x::cli::ReposAction::Scan { roots, depth } => {
+                    ReposAction::Scan { roots, depth }
+                }
+                git_x::cli::ReposAction::Remove { name } => ReposAction::Remove { name },
+                git_x::cli::ReposAction::List { json } => ReposAction::List { json },
+            };
+            let cmd = ReposCommand::new(action);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Goto { name } => {
+            let cmd = GotoCommand::new(name);
+            match NewCommand::execute(&cmd) {
+                Ok(path) => println!("{path}"),
+                Err(e) => {
+                    // Print nothing on stdout, so `cd "$(git x goto name)"` stays put
+                    print_error(&e, verbose);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Remotes { action } => {
+            use git_x::commands::repository::{RemotesAction, RemotesCommand};
+
+            let remotes_action = match action {
+                git_x::cli::RemotesAction::Migrate {
+                    from,
+                    to,
+                    dry_run,
+                    no_verify,
+                    repos,
+                } => RemotesAction::Migrate {
+                    from,
+                    to,
+                    repos,
+                    dry_run,
+                    verify: !no_verify,
+                },
+            };
+            // Streamed, so the planned changes are on screen before the prompt
+            if let Err(e) = RemotesCommand::new(remotes_action).stream(&mut StdoutSink) {
+                print_error(&e, verbose);
+            }
+        }
+
+        Commands::Sparse { action } => {
+            use git_x::commands::repository::{SparseAction, SparseCommand};
+
+            let sparse_action = match action {
+                git_x::cli::SparseAction::Status => SparseAction::Status,
+                git_x::cli::SparseAction::Add { paths, no_cone } => {
+                    SparseAction::Add { paths, no_cone }
+                }
+                git_x::cli::SparseAction::Disable => SparseAction::Disable,
+            };
+            match SparseCommand::new(sparse_action).execute() {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Doctor { action } => {
+            use git_x::commands::repository::{DoctorAction, DoctorCommand};
+
+            let (doctor_action, timeout) = match action {
+                git_x::cli::DoctorAction::Connection { remote, timeout } => {
+                    (DoctorAction::Connection { remote }, timeout)
+                }
+            };
+            // Streamed so the environment is on screen while remotes are checked
+            let cmd = DoctorCommand::new(doctor_action).with_timeout(timeout);
+            if let Err(e) = cmd.stream(&mut StdoutSink).await {
+                print_error(&e, verbose);
+            }
+        }
+
+        Commands::Mirror { action } => {
+            use git_x::commands::repository::MirrorAction;
+
+            let mirror_action = match action {
+                git_x::cli::MirrorAction::Push { remote } => MirrorAction::Push { remote },
+                git_x::cli::MirrorAction::Verify { remote } => MirrorAction::Verify { remote },
+                git_x::cli::MirrorAction::Schedule { remote, unregister } => {
+                    MirrorAction::Schedule { remote, unregister }
+                }
+            };
+
+            let cmd = MirrorCommand::new(mirror_action);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::ForkSetup {
+            upstream,
+            branch,
+            dry_run,
+        } => {
+            let mut cmd = ForkSetupCommand::new(upstream);
+            if let Some(branch) = branch {
+                cmd = cmd.with_branch(branch);
+            }
+            if dry_run {
+                cmd = cmd.with_dry_run();
+            }
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Ci { action } => {
+            use git_x::commands::repository::CiAction;
+
+            let ci_action = match action {
+                git_x::cli::CiAction::Status { watch, interval } => {
+                    CiAction::Status { watch, interval }
+                }
+            };
+
+            let cmd = CiCommand::new(ci_action);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
         Commands::Upstream { action } => {
             // Convert CLI action to repository action
+            let mut sync_dry_run = false;
             let repo_action = match action {
                 git_x::cli::UpstreamAction::Set { upstream } => {
-                    // Parse "remote/branch" format
-                    let parts: Vec<&str> = upstream.split('/').collect();
-                    if parts.len() >= 2 {
-                        git_x::commands::repository::UpstreamAction::Set {
-                            remote: parts[0].to_string(),
-                            branch: parts[1..].join("/"),
-                        }
+                    // A bare branch name is taken to live on origin
+                    let upstream = if upstream.contains('/') {
+                        upstream
                     } else {
-                        git_x::commands::repository::UpstreamAction::Set {
-                            remote: "origin".to_string(),
-                            branch: upstream,
+                        format!("origin/{upstream}")
+                    };
+                    match upstream.parse() {
+                        Ok(upstream) => {
+                            git_x::commands::repository::UpstreamAction::Set { upstream }
+                        }
+                        Err(e) => {
+                            print_error(&e, verbose);
+                            return;
                         }
                     }
                 }
                 git_x::cli::UpstreamAction::Status => {
                     git_x::commands::repository::UpstreamAction::Status
                 }
-                git_x::cli::UpstreamAction::SyncAll {
-                    dry_run: _,
-                    merge: _,
-                } => git_x::commands::repository::UpstreamAction::SyncAll,
+                git_x::cli::UpstreamAction::SyncAll { dry_run, .. } => {
+                    sync_dry_run = dry_run;
+                    git_x::commands::repository::UpstreamAction::SyncAll
+                }
+                git_x::cli::UpstreamAction::Adopt {
+                    remote,
+                    exclude,
+                    dry_run,
+                } => {
+                    sync_dry_run = dry_run;
+                    git_x::commands::repository::UpstreamAction::Adopt { remote, exclude }
+                }
             };
 
-            let cmd = AsyncUpstreamCommand::new(repo_action);
+            let mut cmd = AsyncUpstreamCommand::new(repo_action);
+            if sync_dry_run {
+                cmd = cmd.with_dry_run();
+            }
             match cmd.execute_parallel().await {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::SwitchRecent => {
+        Commands::SwitchRecent { no_hooks } => {
             use git_x::commands::branch::SwitchRecentCommand;
-            let cmd = SwitchRecentCommand::new();
+            let mut cmd = SwitchRecentCommand::new();
+            if no_hooks {
+                cmd = cmd.with_no_hooks();
+            }
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Jump {
+            branch,
+            include_untracked,
+            no_restore,
+            no_hooks,
+        } => {
+            use git_x::commands::branch::JumpCommand;
+            let mut cmd = JumpCommand::new(&branch);
+            if no_hooks {
+                cmd = cmd.with_no_hooks();
+            }
+            if include_untracked {
+                cmd = cmd.with_untracked();
+            }
+            if no_restore {
+                cmd = cmd.with_no_restore();
+            }
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::BranchNote { action } => {
+            use git_x::commands::branch::{BranchNoteAction, BranchNoteCommand};
+
+            let action = match action {
+                git_x::cli::BranchNoteAction::Set {
+                    message,
+                    branch,
+                    expires,
+                } => BranchNoteAction::Set {
+                    message,
+                    branch,
+                    expires,
+                },
+                git_x::cli::BranchNoteAction::Show { branch } => BranchNoteAction::Show { branch },
+                git_x::cli::BranchNoteAction::Clear { branch, expired } => {
+                    BranchNoteAction::Clear { branch, expired }
+                }
+                git_x::cli::BranchNoteAction::List { all } => BranchNoteAction::List { all },
+            };
+            report(
+                NewCommand::execute(&BranchNoteCommand::new(action)),
+                quiet,
+                verbose,
+            );
+        }
+
+        #[cfg(feature = "changed-apis")]
+        Commands::ChangedApis { from, to } => {
+            let cmd = git_x::commands::changed_apis::ChangedApisCommand::new(from, to);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        #[cfg(feature = "serve")]
+        Commands::Serve { port, refresh } => {
+            let cmd = git_x::commands::serve::ServeCommand::new(port).with_refresh(refresh);
+            if let Err(e) = cmd.run().await {
+                print_error(&e, verbose);
             }
         }
 
-        Commands::Contributors => {
-            let cmd = ParallelContributorsCommand::new(None);
+        Commands::Contributors {
+            since,
+            until,
+            limit,
+            offset,
+            fast,
+            mut paths,
+            package,
+            csv,
+            tsv,
+        } => {
+            if let Some(package) = package {
+                match Packages::find(&package) {
+                    Ok(package) => paths.push(package.path),
+                    Err(e) => {
+                        print_error(&e, verbose);
+                        return;
+                    }
+                }
+            }
+            let format = if csv {
+                Some(ExportFormat::Csv)
+            } else if tsv {
+                Some(ExportFormat::Tsv)
+            } else {
+                None
+            };
+            let mut cmd = ParallelContributorsCommand::new(since)
+                .with_until(until)
+                .with_paths(paths)
+                .with_limit(limit)
+                .with_offset(offset)
+                .with_format(format);
+            if fast {
+                cmd = cmd.with_fast();
+            }
             match cmd.execute_parallel() {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
-        Commands::TechnicalDebt => {
-            let cmd = ParallelTechnicalDebtCommand::new();
+        Commands::TechnicalDebt {
+            lockfile_months,
+            outdated,
+            include_generated,
+        } => {
+            let mut cmd =
+                ParallelTechnicalDebtCommand::new().with_dependency_check(DependencyCheck {
+                    lockfile_months,
+                    outdated,
+                });
+            if include_generated {
+                cmd = cmd.with_include_generated();
+            }
             match cmd.execute_parallel() {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
@@ -256,8 +1587,26 @@ async fn main() {
 
             // Convert CLI action to commit action (parameter order difference)
             let commit_action = match action {
-                git_x::cli::BisectAction::Start { good, bad } => {
-                    CommitBisectAction::Start { bad, good } // Note: swapped order
+                git_x::cli::BisectAction::Start {
+                    good,
+                    bad,
+                    auto_good: true,
+                    test,
+                    max_tests,
+                } => CommitBisectAction::AutoStart {
+                    // The only positional is the bad commit
+                    bad: good.or(bad).unwrap_or_else(|| "HEAD".to_string()),
+                    test,
+                    max_tests,
+                },
+                git_x::cli::BisectAction::Start { good, bad, .. } => {
+                    match (good, bad) {
+                        (Some(good), Some(bad)) => CommitBisectAction::Start { bad, good }, // Note: swapped order
+                        _ => {
+                            eprintln!("❌ Give both a good and a bad commit, or use --auto-good");
+                            return;
+                        }
+                    }
                 }
                 git_x::cli::BisectAction::Good => CommitBisectAction::Good,
                 git_x::cli::BisectAction::Bad => CommitBisectAction::Bad,
@@ -269,7 +1618,7 @@ async fn main() {
             let cmd = BisectCommand::new(commit_action);
             match NewCommand::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
 
@@ -278,8 +1627,187 @@ async fn main() {
             let cmd = CompletionInstallCommand::new(shell);
             match git_x::core::traits::Command::execute(&cmd) {
                 Ok(output) => println!("{output}"),
-                Err(e) => eprintln!("❌ {e}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+
+        Commands::Complete { words } => {
+            use git_x::commands::completion::CompleteCommand;
+            // Shells read candidates from stdout; errors must not end up there
+            if let Ok(output) = git_x::core::traits::Command::execute(&CompleteCommand::new(words))
+                && !output.is_empty()
+            {
+                println!("{output}");
+            }
+        }
+
+        Commands::Alias { action } => {
+            use git_x::commands::setup::{AliasAction, AliasCommand, AliasScope};
+
+            let scope = |local: bool, system: bool| {
+                if local {
+                    AliasScope::Local
+                } else if system {
+                    AliasScope::System
+                } else {
+                    AliasScope::Global
+                }
+            };
+            let alias_action = match action {
+                git_x::cli::AliasAction::Install {
+                    aliases,
+                    local,
+                    system,
+                    force,
+                } => AliasAction::Install {
+                    aliases,
+                    scope: scope(local, system),
+                    force,
+                },
+                git_x::cli::AliasAction::List { all } => AliasAction::List { all },
+                git_x::cli::AliasAction::Remove {
+                    names,
+                    local,
+                    system,
+                } => AliasAction::Remove {
+                    names,
+                    scope: scope(local, system),
+                },
+            };
+
+            let cmd = AliasCommand::new(alias_action);
+            match NewCommand::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
             }
         }
+
+        Commands::Setup { yes } => {
+            use git_x::commands::setup::SetupCommand;
+            let mut cmd = SetupCommand::new();
+            if yes {
+                cmd = cmd.with_defaults();
+            }
+            match git_x::core::traits::Command::execute(&cmd) {
+                Ok(output) => println!("{output}"),
+                Err(e) => print_error(&e, verbose),
+            }
+        }
+    }
+}
+
+/// Print a command's result
+///
+/// In quiet mode empty output prints nothing and errors exit non-zero, so
+/// scripts can rely on stdout and the exit status alone.
+fn report(result: git_x::Result<String>, quiet: bool, verbose: bool) {
+    match result {
+        Ok(output) if quiet && output.is_empty() => {}
+        Ok(output) => println!("{output}"),
+        Err(e) if quiet && verbose => {
+            eprintln!("error: {e:#}");
+            std::process::exit(1);
+        }
+        Err(e) if quiet => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+        Err(e) => print_error(&e, verbose),
+    }
+}
+
+/// Print an error, with the failing git command, exit status and full stderr when `verbose`
+/// Print a command's output, then put it on the clipboard when `--copy` asks for it
+fn print_and_copy(output: &str, copy: bool) {
+    println!("{output}");
+    if copy {
+        match Clipboard::copy(output) {
+            Ok(()) => eprintln!("📋 Copied to the clipboard"),
+            Err(e) => eprintln!("⚠️  Not copied: {e}"),
+        }
+    }
+}
+
+/// Graph options from the flags `graph` and `color-graph` share
+fn graph_options(
+    first_parent: bool,
+    merges_only: bool,
+    branches: Vec<String>,
+) -> git_x::Result<GraphOptions> {
+    Ok(GraphOptions {
+        first_parent,
+        merges_only,
+        branches: branches
+            .iter()
+            .map(|branch| branch.parse())
+            .collect::<git_x::Result<_>>()?,
+    })
+}
+
+fn print_error(e: &git_x::GitXError, verbose: bool) {
+    if verbose {
+        eprintln!("❌ {e:#}");
+    } else {
+        eprintln!("❌ {e}");
+    }
+}
+
+/// Commands that write to the repository refuse to run on top of stale locks
+/// or an interrupted merge/rebase, which would otherwise fail with cryptic errors
+fn check_preconditions(command: &Commands) -> git_x::Result<()> {
+    match command {
+        Commands::RenameBranch { .. }
+        | Commands::Attach { .. }
+        | Commands::PruneBranches { .. }
+        | Commands::CleanBranches { .. }
+        | Commands::Archive {
+            action: None | Some(git_x::cli::ArchiveAction::Restore { .. }),
+            ..
+        }
+        | Commands::Undo
+        | Commands::Wip { .. }
+        | Commands::Unwip
+        | Commands::GoneFiles { restore: true, .. }
+        | Commands::Perf { apply: true }
+        | Commands::Sync { .. }
+        | Commands::New { .. }
+        | Commands::Fixup { .. }
+        | Commands::Squash { .. }
+        | Commands::Move { dry_run: false, .. }
+        | Commands::Pr { .. }
+        | Commands::At { print: false, .. }
+        | Commands::Reword { .. }
+        | Commands::RmSecrets { dry_run: false, .. }
+        | Commands::Hotfix { .. }
+        | Commands::Patch {
+            action: git_x::cli::PatchAction::Apply { .. },
+        }
+        | Commands::ImportCommits { dry_run: false, .. }
+        | Commands::RevertMerge { .. }
+        | Commands::ReMerge {
+            branch: Some(..), ..
+        }
+        | Commands::StashBranch {
+            action:
+                git_x::cli::StashBranchAction::Create { .. }
+                | git_x::cli::StashBranchAction::Clean { dry_run: false, .. }
+                | git_x::cli::StashBranchAction::ApplyByBranch {
+                    list_only: false, ..
+                }
+                | git_x::cli::StashBranchAction::Interactive { .. },
+        }
+        | Commands::Sparse {
+            action: git_x::cli::SparseAction::Add { .. } | git_x::cli::SparseAction::Disable,
+        }
+        | Commands::Upstream {
+            action:
+                git_x::cli::UpstreamAction::Set { .. }
+                | git_x::cli::UpstreamAction::SyncAll { dry_run: false, .. }
+                | git_x::cli::UpstreamAction::Adopt { dry_run: false, .. },
+        }
+        | Commands::LineEndings { fix: true }
+        | Commands::Jump { .. }
+        | Commands::SwitchRecent { .. } => Validate::no_stale_locks(),
+        _ => Ok(()),
     }
 }
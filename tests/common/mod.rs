@@ -19,7 +19,14 @@ impl TestRepo {
     }
 
     /// Configure Git user identity for this repository
+    ///
+    /// Also pins English output, so assertions hold under any locale.
     fn configure_git_identity(&self) {
+        StdCommand::new("git")
+            .args(["config", "git-x.language", "en"])
+            .current_dir(&self.path)
+            .assert()
+            .success();
         StdCommand::new("git")
             .args(["config", "user.name", "Test User"])
             .current_dir(&self.path)
@@ -32,6 +39,11 @@ impl TestRepo {
             .success();
     }
 
+    /// Run git in this repo, failing the test if it fails, and return its trimmed stdout
+    pub fn git(&self, args: &[&str]) -> String {
+        git(&self.path, args)
+    }
+
     /// Run a git-x command in this repo and return the Command for assertions
     pub fn run_git_x(&self, args: &[&str]) -> assert_cmd::assert::Assert {
         Command::cargo_bin("git-x")
@@ -39,6 +51,7 @@ impl TestRepo {
             .args(args)
             .current_dir(self.path())
             .env("GIT_X_NON_INTERACTIVE", "1")
+            .env("LC_ALL", "C")
             .assert()
     }
 
@@ -71,9 +84,11 @@ impl TestRepo {
                 let threshold_val = threshold.parse::<f64>().unwrap_or(1.0);
                 AnalysisCommands::large_files(Some(threshold_val), Some(10))
             }
+            ["diff-stat", from] => AnalysisCommands::diff_stat(from.to_string(), None, 1),
             ["what"] => AnalysisCommands::what(None),
             ["what", "--target", target] => AnalysisCommands::what(Some(target.to_string())),
             ["clean-branches", "--dry-run"] => BranchCommands::clean_branches(true),
+            ["clean-branches", "--gone", "--dry-run"] => BranchCommands::clean_gone_branches(true),
             ["prune-branches", "--dry-run"] => BranchCommands::prune_branches(true),
             ["prune-branches", "--except", _except, "--dry-run"] => {
                 BranchCommands::prune_branches(true)
@@ -82,10 +97,19 @@ impl TestRepo {
             ["new", branch_name, "--from", from] => {
                 BranchCommands::new_branch(branch_name, Some(from))
             }
+            ["hotfix", name, "--from", from] => {
+                RepositoryCommands::hotfix(name.to_string(), vec![], Some(from.to_string()))
+            }
             ["rename-branch", new_name] => BranchCommands::rename_branch(new_name),
             ["undo"] => CommitCommands::undo(),
             ["fixup", commit_hash] => CommitCommands::fixup(commit_hash, false),
             ["fixup", commit_hash, "--rebase"] => CommitCommands::fixup(commit_hash, true),
+            ["reword", commit, "-m", message] => CommitCommands::reword(commit, message),
+            ["squash", target] => CommitCommands::squash(target, None),
+            ["squash", target, "-m", message] => {
+                CommitCommands::squash(target, Some(message.to_string()))
+            }
+            ["fix-locks", "--dry-run"] => RepositoryCommands::fix_locks(true, false),
             ["sync"] => RepositoryCommands::sync(SyncStrategy::Auto),
             ["sync", "--merge"] => RepositoryCommands::sync(SyncStrategy::Merge),
             ["bisect", "start", good, bad] => CommitCommands::bisect(BisectAction::Start {
@@ -97,16 +121,13 @@ impl TestRepo {
             ["bisect", "skip"] => CommitCommands::bisect(BisectAction::Skip),
             ["bisect", "reset"] => CommitCommands::bisect(BisectAction::Reset),
             ["bisect", "status"] => CommitCommands::bisect(BisectAction::Status),
-            ["upstream", "set", upstream] => RepositoryCommands::upstream(UpstreamAction::Set {
-                remote: upstream.split('/').next().unwrap_or("origin").to_string(),
-                branch: upstream.split('/').skip(1).collect::<Vec<_>>().join("/"),
+            ["upstream", "set", upstream] => upstream.parse().and_then(|upstream| {
+                RepositoryCommands::upstream(UpstreamAction::Set { upstream })
             }),
             ["upstream", "status"] => RepositoryCommands::upstream(UpstreamAction::Status),
-            ["upstream", "sync-all", "--dry-run"] => {
-                RepositoryCommands::upstream(UpstreamAction::SyncAll)
-            }
+            ["upstream", "sync-all", "--dry-run"] => RepositoryCommands::sync_all_upstreams(true),
             ["upstream", "sync-all", "--merge", "--dry-run"] => {
-                RepositoryCommands::upstream(UpstreamAction::SyncAll)
+                RepositoryCommands::sync_all_upstreams(true)
             }
             ["stash-branch", "create", branch_name] => {
                 StashCommands::create_branch(branch_name.to_string(), None)
@@ -215,6 +236,21 @@ impl TestRepo {
     }
 }
 
+/// Run git in `dir`, failing the test if it fails, and return its trimmed stdout
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    let output = StdCommand::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
 /// Create a basic Git repository with a single commit
 pub fn basic_repo() -> TestRepo {
     let temp = tempdir().unwrap();
@@ -510,6 +546,26 @@ pub fn repo_with_conventional_commits() -> TestRepo {
     repo
 }
 
+/// Create repo on `main` plus a checked-out `branch_name` whose upstream was deleted
+/// on the remote
+///
+/// Returns the remote too, since dropping it removes the bare repository.
+pub fn repo_with_gone_upstream(branch_name: &str) -> (TestRepo, TestRepo) {
+    let repo = repo_with_branch("main");
+    repo.create_branch(branch_name);
+    repo.add_commit("gone.txt", "unmerged work", "work on gone branch");
+    let remote = repo.setup_remote(branch_name);
+
+    // Deleting through the local repo also prunes the remote-tracking ref
+    StdCommand::new("git")
+        .args(["push", "origin", "--delete", branch_name])
+        .current_dir(&repo.path)
+        .assert()
+        .success();
+
+    (repo, remote)
+}
+
 /// Create repo with remote that has commits ahead (for testing behind status)
 pub fn repo_with_remote_ahead(branch_name: &str) -> (TestRepo, TestRepo) {
     let repo = repo_with_branch(branch_name);
@@ -0,0 +1,148 @@
+use assert_cmd::Command;
+use git_x::commands::setup::{AliasCommand, GitAlias};
+use predicates::prelude::*;
+use predicates::str::contains;
+use serial_test::serial;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A repository inside an isolated home directory, so global config is throwaway
+fn isolated_repo() -> TempDir {
+    let home = TempDir::new().unwrap();
+    git(home.path(), &["init", "-q", "repo"]);
+    home
+}
+
+fn git(home: &Path, args: &[&str]) -> String {
+    let dir = if home.join("repo").exists() {
+        home.join("repo")
+    } else {
+        home.to_path_buf()
+    };
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn run_alias(home: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("git-x")
+        .unwrap()
+        .arg("alias")
+        .args(args)
+        .current_dir(home.join("repo"))
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .assert()
+}
+
+#[test]
+fn test_git_alias_parse_list() {
+    let aliases = GitAlias::parse_list(
+        "global\talias.xi x info\nlocal\talias.co checkout\nglobal\talias.st !git-x status -s\n",
+    );
+    assert_eq!(aliases.len(), 3);
+    assert_eq!(aliases[0].name, "xi");
+    assert_eq!(aliases[0].command, "x info");
+    assert_eq!(aliases[1].scope, "local");
+    assert!(aliases[0].runs_git_x());
+    assert!(!aliases[1].runs_git_x());
+    assert!(aliases[2].runs_git_x());
+}
+
+#[test]
+fn test_alias_resolve() {
+    assert_eq!(
+        AliasCommand::resolve("xr").unwrap(),
+        ("xr".to_string(), "x switch-recent".to_string())
+    );
+    assert_eq!(
+        AliasCommand::resolve("sw=switch-recent").unwrap(),
+        ("sw".to_string(), "x switch-recent".to_string())
+    );
+    assert_eq!(
+        AliasCommand::resolve("st=git-x info").unwrap(),
+        ("st".to_string(), "x info".to_string())
+    );
+    assert!(
+        AliasCommand::resolve("nope")
+            .unwrap_err()
+            .to_string()
+            .contains("not a recommended alias")
+    );
+    assert!(AliasCommand::resolve("=info").is_err());
+}
+
+#[test]
+#[serial]
+fn test_alias_install_list_remove() {
+    let home = isolated_repo();
+    git(home.path(), &["config", "--global", "alias.xu", "reset"]);
+
+    run_alias(home.path(), &["install"])
+        .success()
+        .stdout(contains("Installed 5 alias(es) in global config"))
+        .stdout(contains("git xr → git x switch-recent"))
+        .stdout(contains("Skipping 'xu': already set to 'reset'"));
+    assert_eq!(
+        git(home.path(), &["config", "--global", "alias.xi"]),
+        "x info"
+    );
+    assert_eq!(
+        git(home.path(), &["config", "--global", "alias.xu"]),
+        "reset"
+    );
+
+    run_alias(home.path(), &["install", "xi"])
+        .success()
+        .stdout(contains("already in global config"));
+
+    run_alias(home.path(), &["install", "--local", "sw=switch-recent"])
+        .success()
+        .stdout(contains("Installed 1 alias(es) in local config"));
+    assert_eq!(
+        git(home.path(), &["config", "--local", "alias.sw"]),
+        "x switch-recent"
+    );
+
+    run_alias(home.path(), &["list"])
+        .success()
+        .stdout(contains("git sw → git x switch-recent (local)"))
+        .stdout(contains("git xu").not());
+    run_alias(home.path(), &["list", "--all"])
+        .success()
+        .stdout(contains("git xu → git reset (global)"));
+
+    run_alias(home.path(), &["remove", "ghost"])
+        .success()
+        .stderr(contains("No alias named ghost in global config"));
+
+    run_alias(home.path(), &["remove"])
+        .success()
+        .stdout(contains("Removed 5 alias(es) from global config"));
+    // Other aliases and other scopes are left alone
+    assert_eq!(
+        git(home.path(), &["config", "--global", "alias.xu"]),
+        "reset"
+    );
+    assert_eq!(
+        git(home.path(), &["config", "--local", "alias.sw"]),
+        "x switch-recent"
+    );
+
+    run_alias(home.path(), &["remove", "--local", "sw"])
+        .success()
+        .stdout(contains("git sw → git x switch-recent"));
+    run_alias(home.path(), &["list"])
+        .success()
+        .stdout(contains("No git-x aliases configured"))
+        .stdout(contains("Recommended but not installed: xi"));
+}
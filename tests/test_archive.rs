@@ -0,0 +1,160 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::branch::{ArchiveAction, ArchiveCommand};
+use git_x::core::traits::Command;
+use predicates::str::contains;
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// `main` plus a `feature/old` branch with its own commit
+fn repo_with_stale_branch() -> common::TestRepo {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature/old");
+    repo.add_commit("old.txt", "old", "Old work");
+    repo.checkout_branch("main");
+    repo
+}
+
+#[test]
+#[serial]
+fn test_archive_tag_name_is_unique() {
+    let existing = vec![
+        "archive/feature-2025-01-02".to_string(),
+        "archive/feature-2025-01-02-2".to_string(),
+    ];
+
+    assert_eq!(
+        ArchiveCommand::tag_name("other", "2025-01-02", &existing),
+        "archive/other-2025-01-02"
+    );
+    assert_eq!(
+        ArchiveCommand::tag_name("feature", "2025-01-02", &existing),
+        "archive/feature-2025-01-02-3"
+    );
+}
+
+#[test]
+#[serial]
+fn test_archive_branch_from_tag() {
+    assert_eq!(
+        ArchiveCommand::branch_from_tag("archive/feature/old-2025-01-02"),
+        Some("feature/old".to_string())
+    );
+    assert_eq!(
+        ArchiveCommand::branch_from_tag("archive/fix-10-2025-01-02-12"),
+        Some("fix-10".to_string())
+    );
+    assert_eq!(ArchiveCommand::branch_from_tag("archive/no-date"), None);
+    assert_eq!(ArchiveCommand::branch_from_tag("v1.0.0"), None);
+}
+
+#[test]
+#[serial]
+fn test_archive_replaces_branch_with_tag() {
+    let repo = repo_with_stale_branch();
+    let tip = repo.git(&["rev-parse", "feature/old"]);
+    let tag = format!("archive/feature/old-{}", today());
+
+    repo.run_git_x(&["archive", "feature/old"])
+        .success()
+        .stdout(contains(format!("📦 Archived feature/old → {tag}")));
+
+    assert!(repo.git(&["branch", "--list", "feature/old"]).is_empty());
+    assert_eq!(repo.git(&["rev-parse", &format!("{tag}^{{commit}}")]), tip);
+}
+
+#[test]
+#[serial]
+fn test_archive_dry_run_changes_nothing() {
+    let repo = repo_with_stale_branch();
+
+    repo.run_git_x(&["archive", "feature/old", "--dry-run"])
+        .success()
+        .stdout(contains("(dry run) feature/old → archive/feature/old-"));
+
+    assert!(!repo.git(&["branch", "--list", "feature/old"]).is_empty());
+    assert!(repo.git(&["tag", "--list", "archive/*"]).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_archive_refuses_current_branch() {
+    let repo = repo_with_stale_branch();
+
+    repo.run_git_x(&["archive", "main"])
+        .success()
+        .stderr(contains("Cannot archive the current branch 'main'"));
+}
+
+#[test]
+#[serial]
+fn test_archive_list_and_restore() {
+    let repo = repo_with_stale_branch();
+    let tip = repo.git(&["rev-parse", "feature/old"]);
+    repo.run_git_x(&["archive", "feature/old"]).success();
+
+    repo.run_git_x(&["archive", "list"])
+        .success()
+        .stdout(contains("📦 1 archived branch(es):"))
+        .stdout(contains("feature/old"))
+        .stdout(contains(today()));
+
+    repo.run_git_x(&["archive", "restore", "feature/old"])
+        .success()
+        .stdout(contains("♻️  Restored feature/old"));
+
+    assert_eq!(repo.git(&["rev-parse", "feature/old"]), tip);
+    assert!(repo.git(&["tag", "--list", "archive/*"]).is_empty());
+
+    repo.run_git_x(&["archive", "list"])
+        .success()
+        .stdout(contains("No archived branches."));
+}
+
+#[test]
+#[serial]
+fn test_archive_remote_deletes_remote_branch() {
+    let repo = repo_with_stale_branch();
+    repo.checkout_branch("feature/old");
+    let remote = repo.setup_remote("feature/old");
+    repo.checkout_branch("main");
+
+    repo.run_git_x(&["archive", "feature/old", "--remote"])
+        .success()
+        .stdout(contains("📦 Archived feature/old"));
+
+    let remote_refs = std::process::Command::new("git")
+        .args(["for-each-ref", "--format=%(refname)"])
+        .current_dir(remote.path())
+        .output()
+        .unwrap();
+    let remote_refs = String::from_utf8_lossy(&remote_refs.stdout);
+    assert!(!remote_refs.contains("refs/heads/feature/old"));
+    assert!(remote_refs.contains("refs/tags/archive/feature/old-"));
+}
+
+#[test]
+#[serial]
+fn test_archive_restore_unknown_direct() {
+    let repo = repo_with_branch("main");
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+    let result = ArchiveCommand::new(ArchiveAction::Restore {
+        name: "missing".to_string(),
+        as_branch: None,
+    })
+    .execute();
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("No archive found for 'missing'")
+    );
+}
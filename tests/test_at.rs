@@ -0,0 +1,114 @@
+use serial_test::serial;
+mod common;
+
+use common::{TestRepo, basic_repo};
+use git_x::commands::branch::AtCommand;
+use predicates::str::contains;
+use std::process::Command;
+
+/// Commit on a given day; the reflog records the same time
+fn commit_on(repo: &TestRepo, date: &str, message: &str) -> String {
+    std::fs::write(repo.path().join("day.txt"), date).unwrap();
+    repo.git(&["add", "day.txt"]);
+    Command::new("git")
+        .args(["commit", "--quiet", "-m", message])
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .current_dir(repo.path())
+        .status()
+        .unwrap();
+    repo.git(&["rev-parse", "HEAD"])
+}
+
+#[test]
+fn test_reflog_entry() {
+    let reflog = "ccc\tmain@{300}\nbbb\tmain@{200}\naaa\tmain@{100}\n";
+    assert_eq!(AtCommand::reflog_entry(reflog, 250).as_deref(), Some("bbb"));
+    assert_eq!(AtCommand::reflog_entry(reflog, 300).as_deref(), Some("ccc"));
+    assert_eq!(AtCommand::reflog_entry(reflog, 99), None);
+    assert_eq!(AtCommand::reflog_entry("", 500), None);
+    assert!(AtCommand::default_branch_name("main", 0).starts_with("at/main-19"));
+}
+
+#[test]
+#[serial]
+fn test_at_finds_commit_by_reflog_and_dates() {
+    let repo = basic_repo();
+    let branch = repo.git(&["rev-parse", "--abbrev-ref", "HEAD"]);
+    let first = commit_on(&repo, "2024-01-01 10:00", "Monday");
+    let second = commit_on(&repo, "2024-01-02 10:00", "Tuesday");
+    commit_on(&repo, "2024-01-03 10:00", "Wednesday");
+
+    repo.run_git_x(&["at", "2024-01-02 12:00", "--print"])
+        .success()
+        .stdout(format!("{second}\n"));
+    repo.run_git_x(&[
+        "at",
+        "2024-01-01 23:00",
+        &branch,
+        "--print",
+        "--commit-dates",
+    ])
+    .success()
+    .stdout(format!("{first}\n"));
+
+    repo.run_git_x(&["at", "2024-01-01 12:00"])
+        .success()
+        .stdout(contains("at 2024-01-01 12:00"))
+        .stdout(contains("Monday"))
+        .stdout(contains("from the reflog"))
+        .stdout(contains("HEAD is now detached"));
+    assert_eq!(repo.git(&["rev-parse", "HEAD"]), first);
+    repo.git(&["switch", "--quiet", &branch]);
+
+    repo.run_git_x(&["at", "2024-01-02 12:00", "--create", "friday-look"])
+        .success()
+        .stdout(contains("Created and switched to 'friday-look'"));
+    assert_eq!(repo.git(&["rev-parse", "friday-look"]), second);
+    assert_eq!(
+        repo.git(&["rev-parse", "--abbrev-ref", "HEAD"]),
+        "friday-look"
+    );
+
+    repo.run_git_x(&["at", "2024-01-02 12:00", &branch, "--create"])
+        .success()
+        .stdout(contains(format!(
+            "Created and switched to 'at/{branch}-2024-01-02-1200'"
+        )));
+}
+
+#[test]
+#[serial]
+fn test_at_errors() {
+    let repo = basic_repo();
+    commit_on(&repo, "2024-01-01 10:00", "Monday");
+
+    repo.run_git_x(&["at", "2023-06-01", "--print"])
+        .success()
+        .stderr(contains("has no commits before 2023-06-01"));
+    repo.run_git_x(&["at", "yesterday", "no-such-branch"])
+        .success()
+        .stderr(contains("Unknown ref 'no-such-branch'"));
+    repo.run_git_x(&["at", "2030-01-01"])
+        .success()
+        .stderr(contains("is in the future"));
+    repo.run_git_x(&["at", "yesterdya", "--print"])
+        .success()
+        .stderr(contains("Invalid time 'yesterdya'"));
+    repo.run_git_x(&["at", "decade", "--print"])
+        .success()
+        .stderr(contains("Could not understand time 'decade'"));
+}
+
+#[test]
+#[serial]
+fn test_resolve_time() {
+    let _repo = basic_repo();
+    let now = chrono::Local::now().timestamp();
+    let day_ago = AtCommand::resolve_time("1 day ago").unwrap();
+    assert!((now - 86_400 - day_ago).abs() <= 2);
+    assert!(AtCommand::resolve_time("last monday").unwrap() < now);
+    assert!(AtCommand::resolve_time("yesterday 17:00").unwrap() < now);
+    assert!(AtCommand::resolve_time("yesterdya").is_err());
+    assert!(AtCommand::resolve_time("monkey").is_err());
+}
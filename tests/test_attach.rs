@@ -0,0 +1,105 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_commits;
+use git_x::core::git::DetachedHead;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// Repository checked out at a tagged commit with one commit on top that no branch has
+fn detached_repo() -> common::TestRepo {
+    let repo = repo_with_commits(3);
+    repo.git(&["tag", "v1.0", "HEAD~1"]);
+    repo.git(&["checkout", "-q", "--detach", "v1.0"]);
+    repo.add_commit("detached.txt", "work", "Detached work");
+    repo
+}
+
+fn head(describe: &str) -> DetachedHead {
+    DetachedHead {
+        commit: "abc1234".to_string(),
+        describe: describe.to_string(),
+        subject: "Fix".to_string(),
+        unreachable: 0,
+        rebasing: false,
+    }
+}
+
+#[test]
+fn test_detached_head_label_and_error() {
+    assert_eq!(head("abc1234").label(), "abc1234");
+    assert_eq!(head("v1.0-1-gabc1234").label(), "abc1234 (v1.0-1-gabc1234)");
+
+    let error = head("abc1234").error("sync").to_string();
+    assert!(error.contains("HEAD is detached at abc1234; sync needs a branch"));
+    assert!(error.contains("git x attach <name>"));
+
+    let rebasing = DetachedHead {
+        rebasing: true,
+        ..head("abc1234")
+    };
+    assert!(
+        rebasing
+            .error("sync")
+            .to_string()
+            .contains("git rebase --continue")
+    );
+}
+
+#[test]
+#[serial]
+fn test_info_shows_detached_head() {
+    let repo = detached_repo();
+
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("Detached HEAD at"))
+        .stdout(contains("(v1.0-1-g"))
+        .stdout(contains("Commit: Detached work"))
+        .stdout(contains("1 commit(s) here are not on any branch"))
+        .stdout(contains("Current branch: HEAD").not());
+}
+
+#[test]
+#[serial]
+fn test_branch_commands_explain_detached_head() {
+    let repo = detached_repo();
+
+    for args in [
+        &["sync"][..],
+        &["what"],
+        &["branch-diff", "v1.0"],
+        &["rename-branch", "renamed"],
+        &["upstream", "set", "origin/main"],
+    ] {
+        repo.run_git_x(args)
+            .success()
+            .stderr(contains("HEAD is detached at"))
+            .stderr(contains("git x attach <name>"));
+    }
+}
+
+#[test]
+#[serial]
+fn test_attach_creates_branch_at_head() {
+    let repo = detached_repo();
+    let commit = repo.git(&["rev-parse", "HEAD"]);
+
+    repo.git(&["branch", "taken", "v1.0"]);
+    repo.run_git_x(&["attach", "taken"])
+        .success()
+        .stderr(contains("Branch 'taken' already exists"));
+
+    repo.run_git_x(&["attach", "rescued"])
+        .success()
+        .stdout(contains("Created branch 'rescued' at"))
+        .stdout(contains(
+            "1 commit(s) that were not on any branch are now kept",
+        ));
+    assert_eq!(repo.git(&["symbolic-ref", "--short", "HEAD"]), "rescued");
+    assert_eq!(repo.git(&["rev-parse", "rescued"]), commit);
+
+    repo.run_git_x(&["attach", "another"])
+        .success()
+        .stderr(contains("HEAD is not detached, you are on 'rescued'"));
+}
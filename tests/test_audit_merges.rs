@@ -0,0 +1,108 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::analysis::{ReflogEntry, RewriteKind};
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::process::Command;
+
+#[test]
+fn test_rewrite_kind_from_reflog() {
+    assert_eq!(
+        RewriteKind::from_reflog("fetch: forced-update"),
+        RewriteKind::ForcePush
+    );
+    assert_eq!(
+        RewriteKind::from_reflog("update by push"),
+        RewriteKind::ForcePush
+    );
+    assert_eq!(
+        RewriteKind::from_reflog("reset: moving to HEAD~2"),
+        RewriteKind::Reset
+    );
+    assert_eq!(
+        RewriteKind::from_reflog("rebase (finish): refs/heads/main onto 1a2b3c4"),
+        RewriteKind::Rebase
+    );
+    assert_eq!(
+        RewriteKind::from_reflog("commit (amend): Fix typo"),
+        RewriteKind::Amend
+    );
+    assert_eq!(
+        RewriteKind::from_reflog("branch: Created from HEAD"),
+        RewriteKind::Rewrite
+    );
+}
+
+#[test]
+fn test_reflog_entry_parse() {
+    let entries = ReflogEntry::parse(
+        "bbb\x1fmain@{1700000100}\x1fAda <ada@example.com>\x1freset: moving to HEAD~1\n\
+         aaa\x1fmain@{1700000000}\x1fAda <ada@example.com>\x1fcommit: Add a\n\
+         broken line\n",
+    );
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].new, "bbb");
+    assert_eq!(entries[0].timestamp, 1_700_000_100);
+    assert_eq!(entries[0].identity, "Ada <ada@example.com>");
+    assert_eq!(entries[1].message, "commit: Add a");
+}
+
+#[test]
+#[serial]
+fn test_audit_merges_command() {
+    let repo = repo_with_branch("main");
+    let _remote = repo.setup_remote("main");
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(repo.path())
+            .output()
+            .unwrap()
+    };
+
+    repo.run_git_x(&["audit-merges", "--branch", "main"])
+        .success()
+        .stdout(contains(
+            "✅ No force-pushes or history rewrites of main since 90 days ago",
+        ));
+
+    repo.add_commit("bad.txt", "oops", "Commit that gets dropped");
+    git(&["push", "-q", "origin", "main"]);
+    git(&["reset", "-q", "--hard", "HEAD~1"]);
+    git(&["push", "-q", "--force", "origin", "main"]);
+
+    repo.run_git_x(&["audit-merges", "--branch", "main"])
+        .success()
+        .stdout(contains("2 history rewrite(s) of main since 90 days ago"))
+        .stdout(contains("origin/main force-push"))
+        .stdout(contains("main reset"))
+        .stdout(contains("1 commit(s) dropped"))
+        .stdout(contains("reset: moving to HEAD~1"));
+
+    repo.run_git_x(&["audit-merges", "--branch", "main", "--json"])
+        .success()
+        .stdout(contains(r#""kind":"force-push""#))
+        .stdout(contains(r#""dropped_commits":1"#));
+
+    repo.run_git_x(&["audit-merges", "--branch", "release/*"])
+        .success()
+        .stdout(contains("No force-pushes or history rewrites of release/*"))
+        .stdout(contains("⚠️").not());
+}
+
+#[test]
+#[serial]
+fn test_audit_merges_uses_protected_branches() {
+    let repo = repo_with_branch("main");
+    Command::new("git")
+        .args(["config", "git-x.protect.branch", "release/*"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["audit-merges", "--since", "1 week ago"])
+        .success()
+        .stdout(contains("of release/* since 1 week ago"));
+}
@@ -94,15 +94,21 @@ fn test_bisect_run_start_function() {
 
     // Test start action through run function
     let action = BisectAction::Start {
-        good: commits[0].clone(),
-        bad: commits[4].clone(),
+        good: Some(commits[0].clone()),
+        bad: Some(commits[4].clone()),
+        auto_good: false,
+        test: None,
+        max_tests: 8,
     };
 
     use git_x::commands::commit::{BisectAction as CommitBisectAction, BisectCommand};
     use git_x::core::traits::Command;
 
     let commit_action = match action {
-        BisectAction::Start { good, bad } => CommitBisectAction::Start { bad, good },
+        BisectAction::Start { good, bad, .. } => CommitBisectAction::Start {
+            bad: bad.unwrap(),
+            good: good.unwrap(),
+        },
         BisectAction::Good => CommitBisectAction::Good,
         BisectAction::Bad => CommitBisectAction::Bad,
         BisectAction::Skip => CommitBisectAction::Skip,
@@ -128,15 +134,21 @@ fn test_bisect_run_start_function_invalid_commits() {
 
     // Test start action with invalid commit references
     let action = BisectAction::Start {
-        good: "invalid_commit".to_string(),
-        bad: "another_invalid".to_string(),
+        good: Some("invalid_commit".to_string()),
+        bad: Some("another_invalid".to_string()),
+        auto_good: false,
+        test: None,
+        max_tests: 8,
     };
 
     use git_x::commands::commit::{BisectAction as CommitBisectAction, BisectCommand};
     use git_x::core::traits::Command;
 
     let commit_action = match action {
-        BisectAction::Start { good, bad } => CommitBisectAction::Start { bad, good },
+        BisectAction::Start { good, bad, .. } => CommitBisectAction::Start {
+            bad: bad.unwrap(),
+            good: good.unwrap(),
+        },
         BisectAction::Good => CommitBisectAction::Good,
         BisectAction::Bad => CommitBisectAction::Bad,
         BisectAction::Skip => CommitBisectAction::Skip,
@@ -164,7 +176,10 @@ fn test_bisect_run_good_function() {
     use git_x::core::traits::Command;
 
     let commit_action = match action {
-        BisectAction::Start { good, bad } => CommitBisectAction::Start { bad, good },
+        BisectAction::Start { good, bad, .. } => CommitBisectAction::Start {
+            bad: bad.unwrap(),
+            good: good.unwrap(),
+        },
         BisectAction::Good => CommitBisectAction::Good,
         BisectAction::Bad => CommitBisectAction::Bad,
         BisectAction::Skip => CommitBisectAction::Skip,
@@ -192,7 +207,10 @@ fn test_bisect_run_bad_function() {
     use git_x::core::traits::Command;
 
     let commit_action = match action {
-        BisectAction::Start { good, bad } => CommitBisectAction::Start { bad, good },
+        BisectAction::Start { good, bad, .. } => CommitBisectAction::Start {
+            bad: bad.unwrap(),
+            good: good.unwrap(),
+        },
         BisectAction::Good => CommitBisectAction::Good,
         BisectAction::Bad => CommitBisectAction::Bad,
         BisectAction::Skip => CommitBisectAction::Skip,
@@ -220,7 +238,10 @@ fn test_bisect_run_skip_function() {
     use git_x::core::traits::Command;
 
     let commit_action = match action {
-        BisectAction::Start { good, bad } => CommitBisectAction::Start { bad, good },
+        BisectAction::Start { good, bad, .. } => CommitBisectAction::Start {
+            bad: bad.unwrap(),
+            good: good.unwrap(),
+        },
         BisectAction::Good => CommitBisectAction::Good,
         BisectAction::Bad => CommitBisectAction::Bad,
         BisectAction::Skip => CommitBisectAction::Skip,
@@ -248,7 +269,10 @@ fn test_bisect_run_reset_function() {
     use git_x::core::traits::Command;
 
     let commit_action = match action {
-        BisectAction::Start { good, bad } => CommitBisectAction::Start { bad, good },
+        BisectAction::Start { good, bad, .. } => CommitBisectAction::Start {
+            bad: bad.unwrap(),
+            good: good.unwrap(),
+        },
         BisectAction::Good => CommitBisectAction::Good,
         BisectAction::Bad => CommitBisectAction::Bad,
         BisectAction::Skip => CommitBisectAction::Skip,
@@ -278,7 +302,10 @@ fn test_bisect_run_status_function() {
     use git_x::core::traits::Command;
 
     let commit_action = match action {
-        BisectAction::Start { good, bad } => CommitBisectAction::Start { bad, good },
+        BisectAction::Start { good, bad, .. } => CommitBisectAction::Start {
+            bad: bad.unwrap(),
+            good: good.unwrap(),
+        },
         BisectAction::Good => CommitBisectAction::Good,
         BisectAction::Bad => CommitBisectAction::Bad,
         BisectAction::Skip => CommitBisectAction::Skip,
@@ -657,3 +684,172 @@ fn test_bisect_command_direct() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+// --auto-good
+
+fn git_x_bisect(repo_path: &PathBuf, args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("git-x")
+        .expect("Failed to find binary")
+        .arg("bisect")
+        .args(args)
+        .current_dir(repo_path)
+        .assert()
+}
+
+#[test]
+fn test_bisect_probe_distances() {
+    assert_eq!(
+        git_x::commands::commit::BisectCommand::probe_distances(100, 5),
+        [1, 2, 4, 8, 16]
+    );
+    // Stops at the root commit
+    assert_eq!(
+        git_x::commands::commit::BisectCommand::probe_distances(6, 8),
+        [1, 2, 4, 5]
+    );
+    assert!(git_x::commands::commit::BisectCommand::probe_distances(1, 8).is_empty());
+    assert!(git_x::commands::commit::BisectCommand::probe_distances(100, 0).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_bisect_auto_good_with_test_command() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let commits = create_commit_history(&repo_path);
+
+    // Commit 4 added file4.txt, which "breaks" the test
+    git_x_bisect(
+        &repo_path,
+        &["start", "--auto-good", "--test", "test ! -f file4.txt"],
+    )
+    .success()
+    .stdout(predicate::str::contains("(HEAD) fails"))
+    .stdout(predicate::str::contains("(HEAD~1) fails"))
+    .stdout(predicate::str::contains("(HEAD~2) passes"))
+    .stdout(predicate::str::contains(format!(
+        "Good commit: {}",
+        &commits[2][..7]
+    )))
+    .stdout(predicate::str::contains("Starting bisect"))
+    .stdout(predicate::str::contains("git bisect run sh -c"));
+
+    // The search ran in a separate worktree, which is gone again
+    let worktrees = Command::new("git")
+        .args(["worktree", "list"])
+        .current_dir(&repo_path)
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&worktrees.stdout).lines().count(),
+        1
+    );
+
+    git_x_bisect(&repo_path, &["reset"]).success();
+}
+
+#[test]
+#[serial]
+fn test_bisect_auto_good_test_passing_on_bad() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    create_commit_history(&repo_path);
+
+    git_x_bisect(&repo_path, &["start", "--auto-good", "--test", "true"])
+        .success()
+        .stderr(predicate::str::contains("nothing to bisect"));
+
+    git_x_bisect(
+        &repo_path,
+        &[
+            "start",
+            "--auto-good",
+            "--test",
+            "false",
+            "--max-tests",
+            "3",
+        ],
+    )
+    .success()
+    .stderr(predicate::str::contains("failed on every commit tried"));
+}
+
+#[test]
+#[serial]
+fn test_bisect_auto_good_uses_latest_tag() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let commits = create_commit_history(&repo_path);
+    Command::new("git")
+        .args(["tag", "v1.0.0", &commits[1]])
+        .current_dir(&repo_path)
+        .assert()
+        .success();
+
+    git_x_bisect(&repo_path, &["start", "--auto-good"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Good commit: v1.0.0 (latest tag before bad)",
+        ))
+        .stdout(predicate::str::contains(
+            "Starting bisect between HEAD (bad) and v1.0.0",
+        ));
+
+    git_x_bisect(&repo_path, &["reset"]).success();
+}
+
+#[test]
+#[serial]
+fn test_bisect_auto_good_uses_merge_base_on_branch() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let commits = create_commit_history(&repo_path);
+    for args in [
+        vec!["branch", "-M", "main"],
+        vec!["checkout", "-q", "-b", "feature"],
+        vec!["commit", "-q", "--allow-empty", "-m", "Feature work"],
+    ] {
+        Command::new("git")
+            .args(&args)
+            .current_dir(&repo_path)
+            .assert()
+            .success();
+    }
+
+    git_x_bisect(&repo_path, &["start", "--auto-good", "feature"])
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Good commit: {} (where the branch forked from main)",
+            &commits[4][..7]
+        )))
+        .stdout(predicate::str::contains(
+            "Starting bisect between feature (bad)",
+        ));
+
+    git_x_bisect(&repo_path, &["reset"]).success();
+}
+
+#[test]
+#[serial]
+fn test_bisect_auto_good_without_candidates() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    create_commit_history(&repo_path);
+    Command::new("git")
+        .args(["branch", "-M", "trunk"])
+        .current_dir(&repo_path)
+        .assert()
+        .success();
+
+    git_x_bisect(&repo_path, &["start", "--auto-good"])
+        .success()
+        .stderr(predicate::str::contains("Could not find a good commit"));
+}
+
+#[test]
+#[serial]
+fn test_bisect_start_argument_rules() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    // Both commits are needed without --auto-good
+    git_x_bisect(&repo_path, &["start", "HEAD"]).failure();
+    // --auto-good takes at most the bad commit
+    git_x_bisect(&repo_path, &["start", "--auto-good", "HEAD", "HEAD~1"]).failure();
+    // --test only makes sense with --auto-good
+    git_x_bisect(&repo_path, &["start", "HEAD~1", "HEAD", "--test", "true"]).failure();
+}
@@ -0,0 +1,75 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_feature_ahead;
+use git_x::commands::analysis::SideCommit;
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::process::Command;
+
+#[test]
+fn test_side_commit_parse() {
+    let outgoing = SideCommit::parse(">\x1fabc1234\x1fAdd login\x1fAlice\x1f2 days ago").unwrap();
+    assert!(outgoing.outgoing);
+    assert_eq!(outgoing.hash, "abc1234");
+    assert_eq!(outgoing.subject, "Add login");
+    assert_eq!(outgoing.author, "Alice");
+    assert_eq!(outgoing.age, "2 days ago");
+
+    let incoming = SideCommit::parse("<\x1fdef5678\x1fFix CI\x1fBob\x1f3 hours ago").unwrap();
+    assert!(!incoming.outgoing);
+
+    assert!(SideCommit::parse("").is_none());
+    assert!(SideCommit::parse("=\x1fabc\x1fs\x1fa\x1fnow").is_none());
+    assert!(SideCommit::parse(">\x1fabc1234\x1fmissing fields").is_none());
+}
+
+#[test]
+#[serial]
+fn test_branch_diff_lists_both_sides() {
+    let repo = repo_with_feature_ahead("feature/login", "main");
+
+    repo.run_git_x(&["branch-diff", "main"])
+        .success()
+        .stdout(contains("feature/login vs main"))
+        .stdout(contains("Outgoing: 1 commit(s) only on feature/login"))
+        .stdout(contains("modified file (Test User"))
+        .stdout(contains("Incoming: 0 commit(s) only on main"))
+        .stdout(contains("(none)"));
+
+    Command::new("git")
+        .args(["checkout", "-q", "main"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    repo.add_commit("other.txt", "x", "main only");
+    Command::new("git")
+        .args(["checkout", "-q", "feature/login"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["branch-diff", "main"])
+        .success()
+        .stdout(contains("Incoming: 1 commit(s) only on main"))
+        .stdout(contains("main only"))
+        .stdout(contains("(none)").not());
+}
+
+#[test]
+#[serial]
+fn test_branch_diff_same_history() {
+    let repo = repo_with_feature_ahead("feature/login", "main");
+    repo.run_git_x(&["branch-diff", "feature/login"])
+        .success()
+        .stdout(contains("No differences"));
+}
+
+#[test]
+#[serial]
+fn test_branch_diff_unknown_target() {
+    let repo = repo_with_feature_ahead("feature/login", "main");
+    repo.run_git_x(&["branch-diff", "does-not-exist"])
+        .success()
+        .stderr(contains("'does-not-exist' does not exist"));
+}
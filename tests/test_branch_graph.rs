@@ -0,0 +1,136 @@
+use serial_test::serial;
+mod common;
+
+use git_x::domain::BranchGraph;
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::collections::BTreeMap;
+use std::process::Command;
+
+fn map(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+    entries
+        .iter()
+        .map(|(key, values)| {
+            (
+                key.to_string(),
+                values.iter().map(|value| value.to_string()).collect(),
+            )
+        })
+        .collect()
+}
+
+fn tips(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries
+        .iter()
+        .map(|(branch, tip)| (branch.to_string(), tip.to_string()))
+        .collect()
+}
+
+/// main <- feature <- feature-2, plus hotfix at the same commit as main
+fn stacked_graph() -> BranchGraph {
+    BranchGraph::from_containment(
+        &tips(&[
+            ("main", "a"),
+            ("hotfix", "a"),
+            ("feature", "b"),
+            ("feature-2", "c"),
+        ]),
+        &map(&[
+            ("main", &["main", "hotfix"]),
+            ("hotfix", &["main", "hotfix"]),
+            ("feature", &["main", "hotfix", "feature"]),
+            ("feature-2", &["main", "hotfix", "feature", "feature-2"]),
+        ]),
+    )
+}
+
+#[test]
+#[serial]
+fn test_branch_graph_keeps_nearest_parents() {
+    let graph = stacked_graph();
+
+    assert!(graph.parents("main").is_empty());
+    assert!(graph.parents("hotfix").is_empty());
+    assert_eq!(graph.parents("feature"), ["hotfix", "main"]);
+    assert_eq!(graph.parents("feature-2"), ["feature"]);
+    assert_eq!(graph.dependents("feature"), vec!["feature-2"]);
+    assert!(graph.has_dependencies());
+}
+
+#[test]
+#[serial]
+fn test_branch_graph_orphans() {
+    let graph = stacked_graph();
+
+    assert_eq!(
+        graph.orphaned_by(&["feature".to_string()]),
+        vec![("feature".to_string(), vec!["feature-2".to_string()])]
+    );
+    // Deleting the dependent too leaves nothing orphaned
+    assert!(
+        graph
+            .orphaned_by(&["feature".to_string(), "feature-2".to_string()])
+            .is_empty()
+    );
+    // `feature` keeps `main` as a base when `hotfix` goes
+    assert!(graph.orphaned_by(&["hotfix".to_string()]).is_empty());
+    assert!(graph.orphan_warnings(&["feature".to_string()])[0].contains("feature-2"));
+}
+
+#[test]
+#[serial]
+fn test_branch_graph_render() {
+    let rendered = stacked_graph().render();
+
+    assert_eq!(
+        rendered,
+        "hotfix\n└── feature (also on main)\n    └── feature-2\nmain\n└── feature (also on hotfix)\n    └── feature-2"
+    );
+}
+
+/// `feature/a` merged into main with a merge commit, `feature/a-2` built on it but unmerged
+fn repo_with_stacked_branches() -> common::TestRepo {
+    let repo = common::repo_with_branch("main");
+    repo.create_branch("feature/a");
+    repo.add_commit("a.txt", "a", "Work on a");
+    repo.create_branch("feature/a-2");
+    repo.add_commit("a2.txt", "a2", "Build on a");
+    repo.checkout_branch("main");
+    Command::new("git")
+        .args(["merge", "--no-ff", "feature/a", "-m", "Merge feature/a"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    repo
+}
+
+#[test]
+#[serial]
+fn test_branch_deps_command() {
+    let repo = repo_with_stacked_branches();
+
+    repo.run_git_x(&["branch-deps"])
+        .success()
+        .stdout(contains("🌳 Branch dependencies"))
+        .stdout(contains("feature/a\n└── feature/a-2"));
+}
+
+#[test]
+#[serial]
+fn test_clean_branches_warns_about_orphans() {
+    let repo = repo_with_stacked_branches();
+
+    repo.run_git_x(&["clean-branches", "--dry-run"])
+        .success()
+        .stdout(contains("Would delete: feature/a"))
+        .stdout(contains("Would delete: feature/a-2").not())
+        .stdout(contains(
+            "'feature/a' has branches built on it: feature/a-2 (rebase",
+        ));
+
+    repo.run_git_x(&["prune-branches", "--dry-run"])
+        .success()
+        .stdout(contains(
+            "'feature/a' has branches built on it: feature/a-2",
+        ));
+}
@@ -0,0 +1,135 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::core::branch_notes::BranchNote;
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::process::Command;
+
+#[test]
+fn test_parse_expiry() {
+    let now = 1_700_000_000;
+    assert_eq!(
+        BranchNote::parse_expiry("12h", now).unwrap(),
+        now + 12 * 3_600
+    );
+    assert_eq!(
+        BranchNote::parse_expiry("3d", now).unwrap(),
+        now + 3 * 86_400
+    );
+    assert_eq!(
+        BranchNote::parse_expiry("2w", now).unwrap(),
+        now + 14 * 86_400
+    );
+    assert!(BranchNote::parse_expiry("2030-01-01", now).unwrap() > now);
+    for invalid in ["", "d", "0d", "-1d", "3x", "3 days", "3é"] {
+        assert!(BranchNote::parse_expiry(invalid, now).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+#[serial]
+fn test_branch_note_shows_on_switch() {
+    let repo = repo_with_branch("main");
+    repo.add_commit("a.txt", "a\n", "Add a");
+    repo.create_branch("feature");
+    repo.checkout_branch("main");
+
+    repo.run_git_x(&[
+        "branch-note",
+        "set",
+        "waiting on review feedback\nfor the API change",
+        "--branch",
+        "feature",
+        "--expires",
+        "3d",
+    ])
+    .success()
+    .stdout(contains("📌 Added a note on 'feature'"));
+
+    repo.run_git_x(&["jump", "feature"])
+        .success()
+        .stdout(contains("📌 Note on 'feature' (set just now, expires"))
+        .stdout(contains(
+            "   waiting on review feedback\n   for the API change",
+        ));
+    repo.run_git_x(&["jump", "main"])
+        .success()
+        .stdout(contains("📌").not());
+
+    // The note follows the branch when it's renamed
+    Command::new("git")
+        .args(["branch", "-m", "feature", "api-change"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    repo.run_git_x(&["notes", "show", "api-change"])
+        .success()
+        .stdout(contains("waiting on review feedback"));
+
+    repo.run_git_x(&["notes", "clear", "api-change"])
+        .success()
+        .stdout(contains("🗑️  Cleared the note on 'api-change'"));
+    repo.run_git_x(&["jump", "api-change"])
+        .success()
+        .stdout(contains("📌").not());
+}
+
+#[test]
+#[serial]
+fn test_branch_notes_list_and_expiry() {
+    let repo = repo_with_branch("main");
+    repo.add_commit("a.txt", "a\n", "Add a");
+    repo.create_branch("feature");
+
+    repo.run_git_x(&["notes", "list"])
+        .success()
+        .stdout(contains("No branch notes"));
+    repo.run_git_x(&["notes", "set", "rebase after release"])
+        .success();
+    repo.run_git_x(&[
+        "notes",
+        "set",
+        "stale",
+        "--branch",
+        "main",
+        "--expires",
+        "2020-01-01",
+    ])
+    .success()
+    .stderr(contains("would already have expired"));
+
+    // Expire a note by hand, as if it was written long ago
+    Command::new("git")
+        .args(["config", "branch.main.gitxnote", "ship it"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    Command::new("git")
+        .args(["config", "branch.main.gitxnoteexpires", "1577880000"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["notes", "list"])
+        .success()
+        .stdout(contains("feature  rebase after release (set just now)"))
+        .stdout(contains("ship it").not())
+        .stdout(contains("💤 1 expired note(s) hidden"));
+    repo.run_git_x(&["notes", "list", "--all"])
+        .success()
+        .stdout(contains("main     ship it"))
+        .stdout(contains("expired 2020-01-01"));
+    repo.run_git_x(&["jump", "main"])
+        .success()
+        .stdout(contains("📌").not());
+
+    repo.run_git_x(&["notes", "clear", "--expired"])
+        .success()
+        .stdout(contains("Cleared 1 expired note(s)"));
+    repo.run_git_x(&["notes", "list", "--all"])
+        .success()
+        .stdout(contains("ship it").not())
+        .stdout(contains("rebase after release"));
+}
@@ -0,0 +1,177 @@
+#![cfg(feature = "changed-apis")]
+
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::changed_apis::{ApiDiff, ApiItem, CrateFile, PublicApi, SemverBump};
+use predicates::str::contains;
+use std::process::Command;
+
+fn item<'a>(items: &'a [ApiItem], path: &str) -> &'a ApiItem {
+    items
+        .iter()
+        .find(|item| item.path == path)
+        .unwrap_or_else(|| panic!("no public item {path}"))
+}
+
+#[test]
+fn test_module_path() {
+    assert_eq!(PublicApi::module_path("lib.rs"), "");
+    assert_eq!(PublicApi::module_path("core/git.rs"), "core::git");
+    assert_eq!(PublicApi::module_path("core/mod.rs"), "core");
+}
+
+#[test]
+fn test_public_api_parse() {
+    let source = r#"
+        /// Documented
+        pub fn parse(mut input: &str, limit: Option<usize>) -> Vec<String> { vec![] }
+        fn private() {}
+        pub struct Config { pub name: String, secret: u8 }
+        pub struct Handle(pub u32, u8);
+        pub enum Mode { Fast, Slow { level: u8 } }
+        pub trait Render { fn render(&self) -> String; }
+        impl Config {
+            pub fn new() -> Self { todo!() }
+            fn hidden(&self) {}
+        }
+        impl std::fmt::Display for Config {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }
+        }
+        pub mod inner { pub const LIMIT: usize = 3; mod private { pub fn unreachable() {} } }
+        pub use inner::LIMIT;
+    "#;
+    let items = PublicApi::parse("text", source).unwrap();
+
+    assert_eq!(
+        item(&items, "text::parse").signature,
+        "pub fn parse(input: &str, limit: Option<usize>) -> Vec<String>"
+    );
+    assert!(items.iter().all(|item| item.path != "text::private"));
+    assert_eq!(
+        item(&items, "text::Config").signature,
+        "pub struct Config { pub name: String, .. }"
+    );
+    assert_eq!(
+        item(&items, "text::Handle").signature,
+        "pub struct Handle(pub u32, _);"
+    );
+    assert_eq!(item(&items, "text::Mode").kind, "enum");
+    assert_eq!(
+        item(&items, "text::Render::render").signature,
+        "fn render(&self) -> String"
+    );
+    assert_eq!(
+        item(&items, "text::Config::new").signature,
+        "pub fn new() -> Self"
+    );
+    assert!(items.iter().all(|item| item.path != "text::Config::hidden"));
+    assert_eq!(
+        item(&items, "text::<impl std::fmt::Display for Config>").kind,
+        "impl"
+    );
+    assert_eq!(item(&items, "text::inner::LIMIT").kind, "const");
+
+    assert!(PublicApi::parse("text", "pub fn broken(").is_err());
+}
+
+#[test]
+fn test_api_diff() {
+    let api = |source: &str| PublicApi::parse("", source).unwrap();
+    let old = api("pub fn keep() {} pub fn gone() {} pub fn change(a: u8) {}");
+
+    let diff = ApiDiff::between(old.clone(), old.clone());
+    assert!(diff.is_empty());
+    assert_eq!(diff.bump(), SemverBump::Patch);
+
+    let diff = ApiDiff::between(
+        old.clone(),
+        api("pub fn keep() {} pub fn gone() {} pub fn change(a: u8) {} pub fn extra() {}"),
+    );
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.bump(), SemverBump::Minor);
+
+    let diff = ApiDiff::between(old, api("pub fn keep() {} pub fn change(a: u16) {}"));
+    assert_eq!(diff.removed[0].path, "gone");
+    assert_eq!(diff.changed[0].1.signature, "pub fn change(a: u16)");
+    assert_eq!(diff.bump(), SemverBump::Major);
+}
+
+#[test]
+fn test_crate_file_find() {
+    let files = [
+        "Cargo.toml",
+        "src/lib.rs",
+        "src/main.rs",
+        "src/core/mod.rs",
+        "src/bin/tool.rs",
+        "crates/parser/src/lib.rs",
+        "crates/parser/src/token.rs",
+        "examples/demo.rs",
+        "vendor/dep/src/lib.rs",
+    ];
+    let found = CrateFile::find(&files);
+    let modules: Vec<(&str, &str)> = found
+        .iter()
+        .map(|file| (file.path.as_str(), file.module.as_str()))
+        .collect();
+
+    assert_eq!(
+        modules,
+        vec![
+            ("src/lib.rs", ""),
+            ("src/core/mod.rs", "core"),
+            ("crates/parser/src/lib.rs", "parser"),
+            ("crates/parser/src/token.rs", "parser::token"),
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn test_changed_apis_command() {
+    let repo = repo_with_branch("main");
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(repo.path())
+            .output()
+            .unwrap();
+    };
+
+    repo.run_git_x(&["changed-apis"])
+        .success()
+        .stderr(contains("No tag to compare against"));
+
+    std::fs::create_dir(repo.path().join("src")).unwrap();
+    repo.add_commit(
+        "src/lib.rs",
+        "pub fn parse(input: &str) -> u8 { 0 }\npub fn old() {}\n",
+        "Add library",
+    );
+    git(&["tag", "v1.0.0"]);
+    repo.add_commit(
+        "src/lib.rs",
+        "pub fn parse(input: &str, strict: bool) -> u8 { 0 }\npub fn render() {}\n",
+        "Rework library",
+    );
+
+    repo.run_git_x(&["changed-apis"])
+        .success()
+        .stdout(contains("v1.0.0"))
+        .stdout(contains("➕ Added (1)"))
+        .stdout(contains("pub fn render()"))
+        .stdout(contains("➖ Removed (1)"))
+        .stdout(contains("+ pub fn parse(input: &str, strict: bool) -> u8"))
+        .stdout(contains("Suggested version bump: major"));
+
+    repo.run_git_x(&["changed-apis", "HEAD", "HEAD"])
+        .success()
+        .stdout(contains("No public API changes"))
+        .stdout(contains("patch"));
+
+    repo.run_git_x(&["changed-apis", "no-such-tag"])
+        .success()
+        .stderr(contains("does not exist"));
+}
@@ -4,6 +4,7 @@ mod common;
 use common::repo_with_merged_branch;
 use git_x::commands::branch::CleanBranchesCommand;
 use git_x::core::traits::Command;
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 use std::process::Command as StdCommand;
 
@@ -123,3 +124,57 @@ fn test_clean_branches_actually_deletes_branch() {
     let stdout_after = String::from_utf8_lossy(&output_after.stdout);
     assert!(!stdout_after.contains("feature/cleanup"));
 }
+
+#[test]
+#[serial]
+fn test_clean_branches_gone_deletes_unmerged_gone_branch() {
+    let (repo, _remote) = common::repo_with_gone_upstream("feature/shipped");
+    repo.checkout_branch("main");
+    repo.create_branch("local-only");
+    repo.checkout_branch("main");
+
+    repo.run_git_x(&["clean-branches", "--gone", "--dry-run"])
+        .success()
+        .stdout(contains("(dry run) Would delete: feature/shipped"))
+        .stdout(contains("local-only").not());
+
+    repo.run_git_x(&["clean-branches", "--gone"])
+        .success()
+        .stdout(contains("✅ Deleted 1 branches"))
+        .stdout(contains("feature/shipped"));
+
+    let branches = StdCommand::new("git")
+        .args(["branch", "--format=%(refname:short)"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    let branches = String::from_utf8_lossy(&branches.stdout);
+    assert!(!branches.contains("feature/shipped"));
+    assert!(branches.contains("local-only"));
+}
+
+#[test]
+#[serial]
+fn test_clean_branches_gone_none_found() {
+    let repo = common::repo_with_branch("main");
+
+    repo.run_git_x(&["clean-branches", "--gone"])
+        .success()
+        .stdout(contains("No branches with gone upstreams to delete."));
+}
+
+#[test]
+#[serial]
+fn test_clean_gone_branches_command_direct() {
+    let (repo, _remote) = common::repo_with_gone_upstream("feature/shipped");
+    repo.checkout_branch("main");
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+    let result = CleanBranchesCommand::new(true)
+        .with_gone_upstreams()
+        .execute();
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert!(result.unwrap().contains("Would delete: feature/shipped"));
+}
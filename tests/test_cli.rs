@@ -1,5 +1,5 @@
 use clap::Parser;
-use git_x::cli::{Cli, Commands};
+use git_x::cli::{CiAction, Cli, Commands, MirrorAction, RemotesAction};
 use serial_test::serial;
 
 #[test]
@@ -14,12 +14,24 @@ fn test_cli_parse_rename_branch() {
     }
 }
 
+#[test]
+fn test_cli_parse_attach() {
+    let cli = Cli::try_parse_from(["git-x", "attach", "rescued"]).unwrap();
+    match cli.command {
+        Commands::Attach { name } => assert_eq!(name, "rescued"),
+        _ => panic!("Expected Attach command"),
+    }
+    assert!(Cli::try_parse_from(["git-x", "attach"]).is_err());
+}
+
 #[test]
 #[serial]
 fn test_cli_parse_prune_branches() {
     let cli = Cli::try_parse_from(["git-x", "prune-branches"]).unwrap();
     match cli.command {
-        Commands::PruneBranches { except, dry_run } => {
+        Commands::PruneBranches {
+            except, dry_run, ..
+        } => {
             assert!(except.is_none());
             assert!(!dry_run);
         }
@@ -32,7 +44,9 @@ fn test_cli_parse_prune_branches() {
 fn test_cli_parse_prune_branches_with_except() {
     let cli = Cli::try_parse_from(["git-x", "prune-branches", "--except", "main,develop"]).unwrap();
     match cli.command {
-        Commands::PruneBranches { except, dry_run } => {
+        Commands::PruneBranches {
+            except, dry_run, ..
+        } => {
             assert_eq!(except, Some("main,develop".to_string()));
             assert!(!dry_run);
         }
@@ -45,7 +59,9 @@ fn test_cli_parse_prune_branches_with_except() {
 fn test_cli_parse_prune_branches_with_dry_run() {
     let cli = Cli::try_parse_from(["git-x", "prune-branches", "--dry-run"]).unwrap();
     match cli.command {
-        Commands::PruneBranches { except, dry_run } => {
+        Commands::PruneBranches {
+            except, dry_run, ..
+        } => {
             assert!(except.is_none());
             assert!(dry_run);
         }
@@ -59,7 +75,9 @@ fn test_cli_parse_prune_branches_with_except_and_dry_run() {
     let cli =
         Cli::try_parse_from(["git-x", "prune-branches", "--except", "main", "--dry-run"]).unwrap();
     match cli.command {
-        Commands::PruneBranches { except, dry_run } => {
+        Commands::PruneBranches {
+            except, dry_run, ..
+        } => {
             assert_eq!(except, Some("main".to_string()));
             assert!(dry_run);
         }
@@ -72,7 +90,7 @@ fn test_cli_parse_prune_branches_with_except_and_dry_run() {
 fn test_cli_parse_info() {
     let cli = Cli::try_parse_from(["git-x", "info"]).unwrap();
     match cli.command {
-        Commands::Info => {}
+        Commands::Info { json } => assert!(!json),
         _ => panic!("Expected Info command"),
     }
 }
@@ -82,7 +100,7 @@ fn test_cli_parse_info() {
 fn test_cli_parse_graph() {
     let cli = Cli::try_parse_from(["git-x", "graph"]).unwrap();
     match cli.command {
-        Commands::Graph => {}
+        Commands::Graph { .. } => {}
         _ => panic!("Expected Graph command"),
     }
 }
@@ -92,7 +110,7 @@ fn test_cli_parse_graph() {
 fn test_cli_parse_color_graph() {
     let cli = Cli::try_parse_from(["git-x", "color-graph"]).unwrap();
     match cli.command {
-        Commands::ColorGraph => {}
+        Commands::ColorGraph { .. } => {}
         _ => panic!("Expected ColorGraph command"),
     }
 }
@@ -102,9 +120,27 @@ fn test_cli_parse_color_graph() {
 fn test_cli_parse_health() {
     let cli = Cli::try_parse_from(["git-x", "health"]).unwrap();
     match cli.command {
-        Commands::Health => {}
+        Commands::Health { json, fast, .. } => assert!(!json && !fast),
         _ => panic!("Expected Health command"),
     }
+
+    let cli = Cli::try_parse_from(["git-x", "health", "--json"]).unwrap();
+    match cli.command {
+        Commands::Health { json, .. } => assert!(json),
+        _ => panic!("Expected Health command"),
+    }
+
+    let cli = Cli::try_parse_from(["git-x", "health", "--fast", "--no-cache"]).unwrap();
+    match cli.command {
+        Commands::Health {
+            fast,
+            deep,
+            no_cache,
+            ..
+        } => assert!(fast && !deep && no_cache),
+        _ => panic!("Expected Health command"),
+    }
+    assert!(Cli::try_parse_from(["git-x", "health", "--fast", "--deep"]).is_err());
 }
 
 #[test]
@@ -112,11 +148,17 @@ fn test_cli_parse_health() {
 fn test_cli_parse_since() {
     let cli = Cli::try_parse_from(["git-x", "since", "main"]).unwrap();
     match cli.command {
-        Commands::Since { reference } => {
-            assert_eq!(reference, "main");
+        Commands::Since { reference, .. } => {
+            assert_eq!(reference.as_deref(), Some("main"));
         }
         _ => panic!("Expected Since command"),
     }
+
+    let cli = Cli::try_parse_from(["git-x", "since"]).unwrap();
+    match cli.command {
+        Commands::Since { reference, .. } => assert_eq!(reference, None),
+        _ => panic!("Expected Since command"),
+    }
 }
 
 #[test]
@@ -134,8 +176,9 @@ fn test_cli_parse_undo() {
 fn test_cli_parse_clean_branches() {
     let cli = Cli::try_parse_from(["git-x", "clean-branches"]).unwrap();
     match cli.command {
-        Commands::CleanBranches { dry_run } => {
+        Commands::CleanBranches { dry_run, gone, .. } => {
             assert!(!dry_run);
+            assert!(!gone);
         }
         _ => panic!("Expected CleanBranches command"),
     }
@@ -146,31 +189,137 @@ fn test_cli_parse_clean_branches() {
 fn test_cli_parse_clean_branches_dry_run() {
     let cli = Cli::try_parse_from(["git-x", "clean-branches", "--dry-run"]).unwrap();
     match cli.command {
-        Commands::CleanBranches { dry_run } => {
+        Commands::CleanBranches { dry_run, gone, .. } => {
             assert!(dry_run);
+            assert!(!gone);
         }
         _ => panic!("Expected CleanBranches command"),
     }
 }
 
+#[test]
+#[serial]
+fn test_cli_parse_technical_debt() {
+    let cli = Cli::try_parse_from(["git-x", "technical-debt"]).unwrap();
+    match cli.command {
+        Commands::TechnicalDebt {
+            lockfile_months,
+            outdated,
+            include_generated,
+        } => {
+            assert_eq!(lockfile_months, None);
+            assert!(!outdated);
+            assert!(!include_generated);
+        }
+        _ => panic!("Expected TechnicalDebt command"),
+    }
+
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "technical-debt",
+        "--lockfile-months",
+        "3",
+        "--outdated",
+        "--include-generated",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::TechnicalDebt {
+            lockfile_months,
+            outdated,
+            include_generated,
+        } => {
+            assert_eq!(lockfile_months, Some(3));
+            assert!(outdated);
+            assert!(include_generated);
+        }
+        _ => panic!("Expected TechnicalDebt command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_show_config() {
+    let cli = Cli::try_parse_from(["git-x", "show-config", "--json"]).unwrap();
+    match cli.command {
+        Commands::ShowConfig { json } => assert!(json),
+        _ => panic!("Expected ShowConfig command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_bisect_start_auto_good() {
+    use git_x::cli::BisectAction;
+
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "bisect",
+        "start",
+        "--auto-good",
+        "--test",
+        "cargo test",
+        "v2.0",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Bisect {
+            action:
+                BisectAction::Start {
+                    good,
+                    bad,
+                    auto_good,
+                    test,
+                    max_tests,
+                },
+        } => {
+            assert_eq!(good.as_deref(), Some("v2.0"));
+            assert_eq!(bad, None);
+            assert!(auto_good);
+            assert_eq!(test.as_deref(), Some("cargo test"));
+            assert_eq!(max_tests, 8);
+        }
+        _ => panic!("Expected Bisect start command"),
+    }
+
+    assert!(Cli::try_parse_from(["git-x", "bisect", "start", "abc123"]).is_err());
+}
+
 #[test]
 #[serial]
 fn test_cli_parse_what() {
     let cli = Cli::try_parse_from(["git-x", "what"]).unwrap();
     match cli.command {
-        Commands::What { target } => {
+        Commands::What {
+            target,
+            package,
+            copy,
+        } => {
             assert!(target.is_none());
+            assert!(package.is_none());
+            assert!(!copy);
         }
         _ => panic!("Expected What command"),
     }
 }
 
+#[test]
+#[serial]
+fn test_cli_parse_branch_diff() {
+    let cli = Cli::try_parse_from(["git-x", "branch-diff", "main"]).unwrap();
+    match cli.command {
+        Commands::BranchDiff { target } => assert_eq!(target, "main"),
+        _ => panic!("Expected BranchDiff command"),
+    }
+    assert!(Cli::try_parse_from(["git-x", "branch-diff"]).is_err());
+}
+
 #[test]
 #[serial]
 fn test_cli_parse_what_with_target() {
     let cli = Cli::try_parse_from(["git-x", "what", "--target", "develop"]).unwrap();
     match cli.command {
-        Commands::What { target } => {
+        Commands::What { target, .. } => {
             assert_eq!(target, Some("develop".to_string()));
         }
         _ => panic!("Expected What command"),
@@ -182,9 +331,748 @@ fn test_cli_parse_what_with_target() {
 fn test_cli_parse_summary() {
     let cli = Cli::try_parse_from(["git-x", "summary", "--since", "3 days ago"]).unwrap();
     match cli.command {
-        Commands::Summary { since } => {
+        Commands::Summary { since, .. } => {
             assert_eq!(since, Some("3 days ago".to_string()));
         }
         _ => panic!("Expected Summary command"),
     }
 }
+
+#[test]
+#[serial]
+fn test_cli_parse_contributors_export() {
+    let cli = Cli::try_parse_from(["git-x", "contributors", "--csv"]).unwrap();
+    match cli.command {
+        Commands::Contributors { csv, tsv, .. } => {
+            assert!(csv);
+            assert!(!tsv);
+        }
+        _ => panic!("Expected Contributors command"),
+    }
+
+    assert!(Cli::try_parse_from(["git-x", "contributors", "--csv", "--tsv"]).is_err());
+}
+
+#[test]
+fn test_cli_parse_contributors_scope() {
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "contributors",
+        "--since",
+        "1 year ago",
+        "--until",
+        "2025-01-01",
+        "--limit",
+        "50",
+        "--offset",
+        "50",
+        "--fast",
+        "src",
+        "docs",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Contributors {
+            since,
+            until,
+            limit,
+            offset,
+            fast,
+            paths,
+            ..
+        } => {
+            assert_eq!(since.as_deref(), Some("1 year ago"));
+            assert_eq!(until.as_deref(), Some("2025-01-01"));
+            assert_eq!(limit, Some(50));
+            assert_eq!(offset, 50);
+            assert!(fast);
+            assert_eq!(paths, ["src", "docs"]);
+        }
+        _ => panic!("Expected Contributors command"),
+    }
+
+    let cli = Cli::try_parse_from(["git-x", "contributors"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Contributors {
+            limit: None,
+            offset: 0,
+            fast: false,
+            ..
+        }
+    ));
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_squash() {
+    let cli = Cli::try_parse_from(["git-x", "squash", "4", "-m", "implement login"]).unwrap();
+    match cli.command {
+        Commands::Squash { target, message } => {
+            assert_eq!(target, "4");
+            assert_eq!(message, Some("implement login".to_string()));
+        }
+        _ => panic!("Expected Squash command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_fix_locks() {
+    let cli = Cli::try_parse_from(["git-x", "fix-locks", "--dry-run"]).unwrap();
+    match cli.command {
+        Commands::FixLocks { dry_run, abort } => {
+            assert!(dry_run);
+            assert!(!abort);
+        }
+        _ => panic!("Expected FixLocks command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_hotfix() {
+    let cli = Cli::try_parse_from(["git-x", "hotfix", "crash", "abc123", "def456"]).unwrap();
+    match cli.command {
+        Commands::Hotfix {
+            name,
+            commits,
+            from,
+        } => {
+            assert_eq!(name, "crash");
+            assert_eq!(commits, vec!["abc123", "def456"]);
+            assert!(from.is_none());
+        }
+        _ => panic!("Expected Hotfix command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_diff_stat() {
+    let cli =
+        Cli::try_parse_from(["git-x", "diff-stat", "main", "feature", "--depth", "2"]).unwrap();
+    match cli.command {
+        Commands::DiffStat {
+            from,
+            to,
+            depth,
+            json,
+        } => {
+            assert_eq!(from, "main");
+            assert_eq!(to, Some("feature".to_string()));
+            assert_eq!(depth, 2);
+            assert!(!json);
+        }
+        _ => panic!("Expected DiffStat command"),
+    }
+}
+
+#[test]
+fn test_cli_parse_export_report() {
+    let cli = Cli::try_parse_from(["git-x", "export-report", "-o", "report.html"]).unwrap();
+    match cli.command {
+        Commands::ExportReport { format, output } => {
+            assert_eq!(format, None);
+            assert_eq!(output.as_deref(), Some("report.html"));
+        }
+        _ => panic!("Expected ExportReport command"),
+    }
+    assert!(Cli::try_parse_from(["git-x", "export-report", "--format", "pdf"]).is_err());
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_ownership() {
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "ownership",
+        "--since",
+        "3 months ago",
+        "--top",
+        "2",
+        "--csv",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Ownership {
+            since,
+            depth,
+            top,
+            json,
+            csv,
+        } => {
+            assert_eq!(since, Some("3 months ago".to_string()));
+            assert_eq!(depth, 1);
+            assert_eq!(top, 2);
+            assert!(!json);
+            assert!(csv);
+        }
+        _ => panic!("Expected Ownership command"),
+    }
+
+    assert!(Cli::try_parse_from(["git-x", "ownership", "--json", "--csv"]).is_err());
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_reword() {
+    let cli = Cli::try_parse_from(["git-x", "reword", "abc123", "-m", "new message"]).unwrap();
+    match cli.command {
+        Commands::Reword { commit, message } => {
+            assert_eq!(commit, "abc123");
+            assert_eq!(message, "new message");
+        }
+        _ => panic!("Expected Reword command"),
+    }
+
+    assert!(Cli::try_parse_from(["git-x", "reword", "abc123"]).is_err());
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_clean_branches_gone() {
+    let cli = Cli::try_parse_from(["git-x", "clean-branches", "--gone", "--dry-run"]).unwrap();
+    match cli.command {
+        Commands::CleanBranches { dry_run, gone, .. } => {
+            assert!(dry_run);
+            assert!(gone);
+        }
+        _ => panic!("Expected CleanBranches command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_review() {
+    let cli = Cli::try_parse_from([
+        "git-x", "review", "feature", "--base", "develop", "-o", "out.md",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Review {
+            target,
+            base,
+            output,
+        } => {
+            assert_eq!(target, "feature");
+            assert_eq!(base, Some("develop".to_string()));
+            assert_eq!(output, Some("out.md".to_string()));
+        }
+        _ => panic!("Expected Review command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_patch() {
+    let cli = Cli::try_parse_from(["git-x", "patch", "send", "main..feature"]).unwrap();
+    match cli.command {
+        Commands::Patch {
+            action:
+                git_x::cli::PatchAction::Send {
+                    range,
+                    output_dir,
+                    onto,
+                },
+        } => {
+            assert_eq!(range, "main..feature");
+            assert_eq!(output_dir, "patches");
+            assert!(onto.is_none());
+        }
+        _ => panic!("Expected Patch send command"),
+    }
+
+    let cli = Cli::try_parse_from(["git-x", "patch", "apply", "series.mbox", "--no-3way"]).unwrap();
+    match cli.command {
+        Commands::Patch {
+            action: git_x::cli::PatchAction::Apply { source, no_3way },
+        } => {
+            assert_eq!(source, "series.mbox");
+            assert!(no_3way);
+        }
+        _ => panic!("Expected Patch apply command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_lfs_status() {
+    let cli = Cli::try_parse_from(["git-x", "lfs", "status"]).unwrap();
+    match cli.command {
+        Commands::Lfs {
+            action: git_x::cli::LfsAction::Status,
+        } => {}
+        _ => panic!("Expected Lfs status command"),
+    }
+}
+
+#[test]
+#[serial]
+fn test_cli_parse_archive() {
+    let cli = Cli::try_parse_from(["git-x", "archive", "old", "older", "--remote"]).unwrap();
+    match cli.command {
+        Commands::Archive {
+            action,
+            branches,
+            remote,
+            dry_run,
+        } => {
+            assert!(action.is_none());
+            assert_eq!(branches, vec!["old", "older"]);
+            assert!(remote);
+            assert!(!dry_run);
+        }
+        _ => panic!("Expected Archive command"),
+    }
+
+    let cli =
+        Cli::try_parse_from(["git-x", "archive", "restore", "old", "--as", "revived"]).unwrap();
+    match cli.command {
+        Commands::Archive {
+            action: Some(git_x::cli::ArchiveAction::Restore { name, as_branch }),
+            ..
+        } => {
+            assert_eq!(name, "old");
+            assert_eq!(as_branch, Some("revived".to_string()));
+        }
+        _ => panic!("Expected Archive restore command"),
+    }
+}
+
+#[test]
+fn test_cli_parse_setup() {
+    let cli = Cli::try_parse_from(["git-x", "setup"]).unwrap();
+    assert!(matches!(cli.command, Commands::Setup { yes: false }));
+
+    let cli = Cli::try_parse_from(["git-x", "setup", "-y"]).unwrap();
+    assert!(matches!(cli.command, Commands::Setup { yes: true }));
+}
+
+#[test]
+fn test_cli_parse_alias() {
+    use git_x::cli::AliasAction;
+
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "alias",
+        "install",
+        "xr",
+        "sw=switch-recent",
+        "--local",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Alias {
+            action:
+                AliasAction::Install {
+                    aliases,
+                    local,
+                    system,
+                    force,
+                },
+        } => {
+            assert_eq!(aliases, ["xr", "sw=switch-recent"]);
+            assert!(local);
+            assert!(!system);
+            assert!(!force);
+        }
+        _ => panic!("Expected Alias install command"),
+    }
+
+    let cli = Cli::try_parse_from(["git-x", "alias", "list", "--all"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Alias {
+            action: AliasAction::List { all: true }
+        }
+    ));
+
+    assert!(Cli::try_parse_from(["git-x", "alias", "remove", "--local", "--system"]).is_err());
+}
+
+#[test]
+fn test_cli_parse_summary_filters() {
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "summary",
+        "--exclude-merges",
+        "--author",
+        "alice",
+        "--author",
+        "bob",
+        "--team",
+        "backend",
+        "--by-author",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Summary {
+            since,
+            exclude_merges,
+            authors,
+            teams,
+            by_author,
+            package,
+            include_generated,
+            json,
+            ..
+        } => {
+            assert_eq!(since, None);
+            assert!(exclude_merges);
+            assert_eq!(authors, vec!["alice", "bob"]);
+            assert_eq!(teams, vec!["backend"]);
+            assert!(by_author);
+            assert!(package.is_none());
+            assert!(!include_generated);
+            assert!(!json);
+        }
+        _ => panic!("Expected Summary command"),
+    }
+}
+
+#[test]
+fn test_cli_parse_global_quiet() {
+    let cli = Cli::try_parse_from(["git-x", "sync"]).unwrap();
+    assert!(!cli.quiet);
+
+    let cli = Cli::try_parse_from(["git-x", "-q", "sync"]).unwrap();
+    assert!(cli.quiet);
+
+    let cli = Cli::try_parse_from(["git-x", "new", "feature", "--quiet"]).unwrap();
+    assert!(cli.quiet);
+    assert!(matches!(cli.command, Commands::New { .. }));
+}
+
+#[test]
+fn test_cli_parse_new_freshness() {
+    let cli =
+        Cli::try_parse_from(["git-x", "new", "feature", "--max-behind", "5", "--update"]).unwrap();
+    match cli.command {
+        Commands::New {
+            fresh,
+            max_behind,
+            update,
+            ..
+        } => {
+            assert!(!fresh);
+            assert_eq!(max_behind, Some(5));
+            assert!(update);
+        }
+        _ => panic!("Expected New command"),
+    }
+}
+
+#[test]
+fn test_cli_parse_undo_file() {
+    let cli = Cli::try_parse_from(["git-x", "undo-file", "src/lib.rs", "--restore", "3"]).unwrap();
+    match cli.command {
+        Commands::UndoFile { path, restore } => {
+            assert_eq!(path.as_deref(), Some("src/lib.rs"));
+            assert_eq!(restore, Some(3));
+        }
+        _ => panic!("Expected UndoFile command"),
+    }
+
+    let cli = Cli::try_parse_from(["git-x", "undo-file"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::UndoFile {
+            path: None,
+            restore: None
+        }
+    ));
+}
+
+#[test]
+fn test_cli_parse_merge_queue() {
+    let cli = Cli::try_parse_from(["git-x", "merge-queue", "a", "b", "--base", "develop"]).unwrap();
+    match cli.command {
+        Commands::MergeQueue {
+            branches,
+            base,
+            prs,
+        } => {
+            assert_eq!(branches, ["a", "b"]);
+            assert_eq!(base.as_deref(), Some("develop"));
+            assert!(!prs);
+        }
+        _ => panic!("Expected MergeQueue command"),
+    }
+
+    let cli = Cli::try_parse_from(["git-x", "merge-queue", "--prs"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::MergeQueue { prs: true, .. }
+    ));
+}
+
+#[test]
+fn test_cli_parse_global_verbose() {
+    let cli = Cli::try_parse_from(["git-x", "sync"]).unwrap();
+    assert!(!cli.verbose);
+
+    let cli = Cli::try_parse_from(["git-x", "--verbose", "sync"]).unwrap();
+    assert!(cli.verbose);
+
+    let cli = Cli::try_parse_from(["git-x", "what", "--verbose"]).unwrap();
+    assert!(cli.verbose);
+}
+
+#[test]
+fn test_cli_parse_gone_files() {
+    let cli = Cli::try_parse_from(["git-x", "gone-files"]).unwrap();
+    match cli.command {
+        Commands::GoneFiles {
+            query,
+            restore,
+            limit,
+        } => {
+            assert_eq!(query, None);
+            assert!(!restore);
+            assert_eq!(limit, 20);
+        }
+        _ => panic!("Expected GoneFiles command"),
+    }
+
+    let cli = Cli::try_parse_from(["git-x", "gone-files", "parser", "--restore"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::GoneFiles { query: Some(ref q), restore: true, .. } if q == "parser"
+    ));
+}
+
+#[test]
+fn test_cli_parse_branch_deps() {
+    let cli = Cli::try_parse_from(["git-x", "branch-deps"]).unwrap();
+    assert!(matches!(cli.command, Commands::BranchDeps));
+}
+
+#[test]
+fn test_cli_parse_ci_status() {
+    let cli = Cli::try_parse_from(["git-x", "ci", "status"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Ci {
+            action: CiAction::Status {
+                watch: false,
+                interval: 10
+            }
+        }
+    ));
+
+    let cli = Cli::try_parse_from(["git-x", "ci", "status", "--watch", "--interval", "5"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Ci {
+            action: CiAction::Status {
+                watch: true,
+                interval: 5
+            }
+        }
+    ));
+}
+
+#[test]
+fn test_cli_parse_perf() {
+    let cli = Cli::try_parse_from(["git-x", "perf"]).unwrap();
+    assert!(matches!(cli.command, Commands::Perf { apply: false }));
+
+    let cli = Cli::try_parse_from(["git-x", "perf", "--apply"]).unwrap();
+    assert!(matches!(cli.command, Commands::Perf { apply: true }));
+}
+
+#[test]
+fn test_cli_parse_whoami() {
+    let cli = Cli::try_parse_from(["git-x", "whoami"]).unwrap();
+    assert!(matches!(cli.command, Commands::Whoami));
+}
+
+#[test]
+fn test_cli_parse_fixup_auto() {
+    let cli = Cli::try_parse_from(["git-x", "fixup", "--auto", "--rebase"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Fixup {
+            commit_hash: None,
+            rebase: true,
+            auto: true
+        }
+    ));
+
+    assert!(Cli::try_parse_from(["git-x", "fixup"]).is_err());
+    assert!(Cli::try_parse_from(["git-x", "fixup", "abc123", "--auto"]).is_err());
+}
+
+#[test]
+fn test_cli_parse_stash_interactive_sort() {
+    let cli =
+        Cli::try_parse_from(["git-x", "stash-branch", "interactive", "--sort", "branch"]).unwrap();
+    match cli.command {
+        Commands::StashBranch {
+            action: git_x::cli::StashBranchAction::Interactive { sort },
+        } => assert_eq!(sort, "branch"),
+        _ => panic!("Expected stash-branch interactive"),
+    }
+
+    assert!(
+        Cli::try_parse_from(["git-x", "stash-branch", "interactive", "--sort", "size"]).is_err()
+    );
+}
+
+#[test]
+fn test_cli_parse_init_hooks() {
+    let cli = Cli::try_parse_from(["git-x", "init-hooks", "--force"]).unwrap();
+    match cli.command {
+        Commands::InitHooks { force, check } => {
+            assert!(force);
+            assert_eq!(check, None);
+        }
+        _ => panic!("Expected init-hooks command"),
+    }
+
+    let cli =
+        Cli::try_parse_from(["git-x", "init-hooks", "--check", ".git/COMMIT_EDITMSG"]).unwrap();
+    match cli.command {
+        Commands::InitHooks { check, .. } => {
+            assert_eq!(check, Some(".git/COMMIT_EDITMSG".into()));
+        }
+        _ => panic!("Expected init-hooks command"),
+    }
+}
+
+#[test]
+fn test_cli_parse_remotes_migrate() {
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "remotes",
+        "migrate",
+        "--from",
+        "git@gitlab.com:org",
+        "--to",
+        "git@github.com:org",
+        "--dry-run",
+        "../api",
+        "../web",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Remotes {
+            action:
+                RemotesAction::Migrate {
+                    from,
+                    to,
+                    dry_run,
+                    no_verify,
+                    repos,
+                },
+        } => {
+            assert_eq!(from, "git@gitlab.com:org");
+            assert_eq!(to, "git@github.com:org");
+            assert!(dry_run);
+            assert!(!no_verify);
+            assert_eq!(repos, ["../api", "../web"]);
+        }
+        _ => panic!("Expected remotes migrate command"),
+    }
+
+    assert!(Cli::try_parse_from(["git-x", "remotes", "migrate", "--from", "a"]).is_err());
+}
+
+#[test]
+fn test_cli_parse_scan_staged() {
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "scan-staged",
+        "--fail-on",
+        "high",
+        "--install-hook",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::ScanStaged {
+            fail_on,
+            install_hook,
+        } => {
+            assert_eq!(fail_on.as_deref(), Some("high"));
+            assert!(install_hook);
+        }
+        _ => panic!("Expected scan-staged command"),
+    }
+
+    assert!(Cli::try_parse_from(["git-x", "scan-staged", "--fail-on", "critical"]).is_err());
+}
+
+#[test]
+fn test_cli_parse_mirror() {
+    let cli = Cli::try_parse_from(["git-x", "mirror", "push", "backup"]).unwrap();
+    match cli.command {
+        Commands::Mirror {
+            action: MirrorAction::Push { remote },
+        } => assert_eq!(remote.as_deref(), Some("backup")),
+        _ => panic!("Expected mirror push"),
+    }
+
+    let cli = Cli::try_parse_from(["git-x", "mirror", "schedule", "--unregister"]).unwrap();
+    match cli.command {
+        Commands::Mirror {
+            action: MirrorAction::Schedule { remote, unregister },
+        } => {
+            assert_eq!(remote, None);
+            assert!(unregister);
+        }
+        _ => panic!("Expected mirror schedule"),
+    }
+}
+
+#[test]
+fn test_cli_parse_heatmap() {
+    let cli = Cli::try_parse_from([
+        "git-x",
+        "heatmap",
+        "--since",
+        "6 months ago",
+        "--author",
+        "alice",
+        "--all",
+        "src",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Heatmap {
+            since,
+            author,
+            branch,
+            all,
+            path,
+        } => {
+            assert_eq!(since.as_deref(), Some("6 months ago"));
+            assert_eq!(author.as_deref(), Some("alice"));
+            assert_eq!(branch, None);
+            assert!(all);
+            assert_eq!(path.as_deref(), Some("src"));
+        }
+        _ => panic!("Expected heatmap command"),
+    }
+
+    assert!(Cli::try_parse_from(["git-x", "heatmap", "--all", "--branch", "main"]).is_err());
+}
+
+#[test]
+fn test_cli_parse_standup_team() {
+    let teams = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+        Commands::Standup { teams, .. } => teams,
+        _ => panic!("Expected Standup command"),
+    };
+
+    assert_eq!(teams(&["git-x", "standup"]), None);
+    assert_eq!(teams(&["git-x", "standup", "--team"]), Some(Vec::new()));
+    assert_eq!(
+        teams(&["git-x", "standup", "--team", "api", "--team", "web"]),
+        Some(vec!["api".to_string(), "web".to_string()])
+    );
+    assert_eq!(
+        teams(&["git-x", "standup", "--team", "--since", "monday"]),
+        Some(Vec::new())
+    );
+}
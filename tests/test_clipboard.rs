@@ -0,0 +1,63 @@
+use assert_cmd::Command as GitX;
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::process::Command;
+
+#[test]
+#[serial]
+fn test_copy_pipes_plain_output_to_the_configured_command() {
+    let repo = repo_with_branch("main");
+    repo.add_commit("a.txt", "a\n", "Add a");
+    let copied = repo.path().join(".git").join("copied.txt");
+    Command::new("git")
+        .args([
+            "config",
+            "git-x.clipboard",
+            &format!("cat > '{}'", copied.display()),
+        ])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["since", "HEAD~1", "--copy"])
+        .success()
+        .stdout(contains("Add a"))
+        .stderr(contains("📋 Copied to the clipboard"));
+
+    let text = std::fs::read_to_string(&copied).unwrap();
+    assert!(text.contains("Add a"));
+    assert!(!text.contains('\u{1b}'));
+}
+
+#[test]
+#[serial]
+fn test_copy_without_a_clipboard_still_prints() {
+    let repo = repo_with_branch("main");
+    repo.add_commit("a.txt", "a\n", "Add a");
+
+    let mut cmd = GitX::cargo_bin("git-x").unwrap();
+    cmd.args(["since", "HEAD~1", "--copy"])
+        .current_dir(repo.path())
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .env_remove("DISPLAY")
+        .env_remove("WAYLAND_DISPLAY")
+        .env_remove("WSL_DISTRO_NAME")
+        .env("SSH_CONNECTION", "10.0.0.1 22 10.0.0.2 22");
+    let expected = if cfg!(any(target_os = "macos", windows)) {
+        "📋"
+    } else {
+        "⚠️  Not copied: No clipboard available (this SSH session has no display)"
+    };
+    cmd.assert()
+        .success()
+        .stdout(contains("Add a"))
+        .stderr(contains(expected));
+
+    repo.run_git_x(&["since", "HEAD~1"])
+        .success()
+        .stderr(contains("Copied").not());
+}
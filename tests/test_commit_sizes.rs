@@ -0,0 +1,132 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::analysis::{
+    CommitAnomaly, CommitSize, SizeMetric, is_generated_file, size_histogram,
+};
+use git_x::core::dependencies::{is_lockfile, is_vendored};
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::collections::HashSet;
+
+fn sized(insertions: usize, files: usize) -> CommitSize {
+    CommitSize {
+        hash: "abc1234".to_string(),
+        subject: "change".to_string(),
+        files,
+        insertions,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_generated_and_vendored_paths() {
+    assert!(is_lockfile("Cargo.lock"));
+    assert!(is_lockfile("web/yarn.lock"));
+    // requirements.txt is written by hand
+    assert!(!is_lockfile("requirements.txt"));
+    assert!(is_generated_file("static/app.min.js"));
+    assert!(is_generated_file("api/service.pb.go"));
+    assert!(is_generated_file("web/dist/bundle.js"));
+    assert!(!is_generated_file("src/distance.rs"));
+    assert!(is_vendored("vendor/github.com/pkg/errors/errors.go"));
+    assert!(!is_vendored("src/main.rs"));
+}
+
+#[test]
+fn test_parse_log() {
+    let log = "\x1eaaa\x1fVendor errors\n\n900\t0\tvendor/errors.go\n100\t0\tgo.mod\n\x1ebbb\x1fAdd logo\n\n-\t-\tlogo.png\n3\t1\tapi/schema.gen.ts\n";
+    let marked: HashSet<String> = ["api/schema.gen.ts".to_string()].into();
+    let commits = CommitSize::parse_log(log, &marked);
+
+    assert_eq!(commits.len(), 2);
+    assert_eq!(commits[0].subject, "Vendor errors");
+    assert_eq!(commits[0].files, 2);
+    assert_eq!(commits[0].lines(), 1000);
+    assert_eq!(commits[0].vendored_lines, 900);
+    assert_eq!(commits[1].files, 2);
+    assert_eq!(commits[1].lines(), 4);
+    assert_eq!(commits[1].generated_lines, 4);
+}
+
+#[test]
+fn test_anomalies() {
+    assert_eq!(
+        sized(1200, 3).anomaly(1000, 100),
+        Some(CommitAnomaly::Giant)
+    );
+    assert_eq!(
+        sized(20, 150).anomaly(1000, 100),
+        Some(CommitAnomaly::Giant)
+    );
+    assert_eq!(sized(900, 3).anomaly(1000, 100), None);
+
+    let vendored = CommitSize {
+        vendored_lines: 450,
+        ..sized(500, 40)
+    };
+    assert_eq!(
+        vendored.anomaly(1000, 100),
+        Some(CommitAnomaly::VendoredDrop)
+    );
+    let lockfile = CommitSize {
+        generated_lines: 4000,
+        ..sized(4100, 2)
+    };
+    assert_eq!(lockfile.anomaly(1000, 100), Some(CommitAnomaly::Generated));
+    // A small lockfile bump is routine
+    let bump = CommitSize {
+        generated_lines: 12,
+        ..sized(12, 1)
+    };
+    assert_eq!(bump.anomaly(1000, 100), None);
+}
+
+#[test]
+fn test_size_histogram() {
+    let commits = [sized(0, 1), sized(10, 1), sized(11, 2), sized(5000, 4)];
+    let histogram = size_histogram(&commits, SizeMetric::Lines);
+    let labels: Vec<&str> = histogram.iter().map(|(label, _)| label.as_str()).collect();
+    assert_eq!(
+        labels,
+        ["0-10", "11-50", "51-200", "201-500", "501-1000", "1001+"]
+    );
+    let counts: Vec<usize> = histogram.iter().map(|(_, count)| *count).collect();
+    assert_eq!(counts, [2, 1, 0, 0, 0, 1]);
+
+    let by_files = size_histogram(&commits, SizeMetric::Files);
+    assert_eq!(by_files[0], ("0-1".to_string(), 2));
+    assert_eq!(by_files[1], ("2-3".to_string(), 1));
+    assert_eq!(by_files[2], ("4-10".to_string(), 1));
+    assert!("bytes".parse::<SizeMetric>().is_err());
+}
+
+#[test]
+#[serial]
+fn test_commit_sizes_command() {
+    let repo = basic_repo();
+    repo.add_commit("small.txt", "one\ntwo\n", "Small change");
+    let vendored: String = (0..300).map(|i| format!("line {i}\n")).collect();
+    std::fs::create_dir_all(repo.path().join("vendor/lib")).unwrap();
+    repo.add_commit("vendor/lib/lib.js", &vendored, "Vendor lib");
+
+    repo.run_git_x(&["commit-sizes"])
+        .success()
+        .stdout(contains("Commit sizes in"))
+        .stdout(contains("by lines changed"))
+        .stdout(contains("0-10 lines"))
+        .stdout(contains("1 anomalous commit(s)"))
+        .stdout(contains("likely vendored code drop"))
+        .stdout(contains("Vendor lib"));
+
+    repo.run_git_x(&["commit-sizes", "HEAD~1", "--by", "files"])
+        .success()
+        .stdout(contains("by files changed"))
+        .stdout(contains("No anomalies"))
+        .stdout(contains("Vendor lib").not());
+
+    repo.run_git_x(&["commit-sizes", "HEAD..HEAD"])
+        .success()
+        .stdout(contains("No commits in"));
+}
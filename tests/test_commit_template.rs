@@ -0,0 +1,237 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::commit::CommitTemplates;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+const CONFIG: &str = r#"
+[commit.templates]
+"*" = "templates/default.txt"
+"docs/" = "templates/docs.txt"
+"services/payments" = "templates/payments.txt"
+"services/*" = "templates/services.txt"
+"#;
+
+fn paths(paths: &[&str]) -> Vec<String> {
+    paths.iter().map(|path| path.to_string()).collect()
+}
+
+fn selected(templates: &CommitTemplates, staged: &[&str]) -> Option<String> {
+    templates
+        .select(&paths(staged))
+        .map(|template| template.file.clone())
+}
+
+/// A repository with the templates from [`CONFIG`] committed
+fn templated_repo() -> common::TestRepo {
+    let repo = basic_repo();
+    let root = repo.path();
+    for dir in ["templates", "docs", "services/payments", "services/search"] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    for (name, content) in [
+        ("default", "chore: \n"),
+        ("docs", "docs: \n\nWhy this change:\n"),
+        ("payments", "feat(payments): \n\nTicket: PAY-\n"),
+        ("services", "feat(services): \n"),
+    ] {
+        std::fs::write(root.join(format!("templates/{name}.txt")), content).unwrap();
+    }
+    std::fs::write(root.join(".git-x.toml"), CONFIG).unwrap();
+    repo.add_commit("templates/.keep", "", "Add commit templates");
+    repo
+}
+
+fn stage(repo: &common::TestRepo, file: &str) {
+    std::fs::write(repo.path().join(file), "change\n").unwrap();
+    std::process::Command::new("git")
+        .args(["add", file])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+}
+
+#[test]
+fn test_commit_templates_precedence() {
+    let templates = CommitTemplates::parse(CONFIG).unwrap();
+    assert_eq!(templates.templates.len(), 4);
+
+    // The most specific pattern wins, whatever the file order
+    assert_eq!(
+        selected(&templates, &["docs/guide.md"]).as_deref(),
+        Some("templates/docs.txt")
+    );
+    assert_eq!(
+        selected(&templates, &["services/payments/api.rs"]).as_deref(),
+        Some("templates/payments.txt")
+    );
+    assert_eq!(
+        selected(&templates, &["services/search/index.rs"]).as_deref(),
+        Some("templates/services.txt")
+    );
+    // A pattern covering every staged path beats a longer one covering only some
+    assert_eq!(
+        selected(
+            &templates,
+            &["services/payments/api.rs", "services/search/index.rs"]
+        )
+        .as_deref(),
+        Some("templates/services.txt")
+    );
+    assert_eq!(
+        selected(&templates, &["docs/guide.md", "services/payments/api.rs"]).as_deref(),
+        Some("templates/default.txt")
+    );
+    // A directory pattern only matches whole path components
+    assert_eq!(
+        selected(&templates, &["docsite/index.html"]).as_deref(),
+        Some("templates/default.txt")
+    );
+    assert_eq!(selected(&templates, &[]), None);
+
+    // Equally specific patterns fall back to file order
+    let tied =
+        CommitTemplates::parse("[commit.templates]\n\"a/\" = \"one\"\n\"b/\" = \"two\"\n").unwrap();
+    assert_eq!(selected(&tied, &["b/x", "a/y"]).as_deref(), Some("one"));
+}
+
+#[test]
+fn test_commit_templates_parse_errors() {
+    assert!(
+        CommitTemplates::parse("[hooks.switch]\n\"*\" = \"true\"\n")
+            .unwrap()
+            .templates
+            .is_empty()
+    );
+    assert!(
+        CommitTemplates::parse("[commit.templates]\n\"docs/\" = 3\n")
+            .unwrap_err()
+            .to_string()
+            .contains("needs a template file")
+    );
+    assert!(
+        CommitTemplates::parse("[commit]\ntemplates = \"x\"\n")
+            .unwrap_err()
+            .to_string()
+            .contains("must be a table")
+    );
+}
+
+#[test]
+fn test_commit_templates_fill_keeps_comments() {
+    let prepared = "\n# Please enter the commit message\n#\n# On branch main\n";
+    assert_eq!(
+        CommitTemplates::fill(prepared, "docs: \n\nWhy:\n"),
+        "docs: \n\nWhy:\n\n# Please enter the commit message\n#\n# On branch main\n"
+    );
+    // Git's own commit.template is replaced
+    assert_eq!(
+        CommitTemplates::fill("Generic template\n\n# comment\n", "docs: "),
+        "docs:\n\n# comment\n"
+    );
+}
+
+#[test]
+#[serial]
+fn test_commit_template_preview() {
+    let repo = templated_repo();
+
+    repo.run_git_x(&["commit-template"])
+        .success()
+        .stdout(contains("Nothing staged"));
+
+    stage(&repo, "services/payments/api.rs");
+    repo.run_git_x(&["commit-template"])
+        .success()
+        .stdout(contains(
+            "📝 templates/payments.txt (pattern 'services/payments', 1 of 1 staged path(s))",
+        ))
+        .stdout(contains("  Ticket: PAY-"))
+        .stdout(contains("commit-template --install"));
+
+    let repo = basic_repo();
+    repo.run_git_x(&["commit-template"])
+        .success()
+        .stdout(contains("No commit templates configured"));
+}
+
+#[test]
+#[serial]
+fn test_commit_template_hook_fills_plain_commits_only() {
+    let repo = templated_repo();
+    repo.run_git_x(&["commit-template", "--install"])
+        .success()
+        .stdout(contains("Installed prepare-commit-msg hook"));
+    let hook = std::fs::read_to_string(repo.path().join(".git/hooks/prepare-commit-msg")).unwrap();
+    assert!(hook.contains("git x commit-template --fill \"$1\" --source \"$2\""));
+    repo.run_git_x(&["commit-template", "--install"])
+        .success()
+        .stdout(contains("already installed"));
+
+    let message = repo.path().join(".git/MSG");
+    stage(&repo, "docs/guide.md");
+    std::fs::write(&message, "\n# On branch main\n").unwrap();
+    repo.run_git_x(&["commit-template", "--fill", ".git/MSG"])
+        .success()
+        .stdout(predicate::str::is_empty());
+    assert_eq!(
+        std::fs::read_to_string(&message).unwrap(),
+        "docs: \n\nWhy this change:\n\n# On branch main\n"
+    );
+
+    // Messages given with -m are left alone
+    std::fs::write(&message, "fix: typo\n").unwrap();
+    repo.run_git_x(&[
+        "commit-template",
+        "--fill",
+        ".git/MSG",
+        "--source",
+        "message",
+    ])
+    .success();
+    assert_eq!(std::fs::read_to_string(&message).unwrap(), "fix: typo\n");
+
+    // A missing template file warns without blocking the commit
+    std::fs::remove_file(repo.path().join("templates/docs.txt")).unwrap();
+    repo.run_git_x(&["commit-template", "--fill", ".git/MSG"])
+        .success()
+        .stderr(contains("Commit template not applied"));
+}
+
+#[test]
+#[serial]
+fn test_commit_template_hook_runs_through_git() {
+    let repo = templated_repo();
+    repo.run_git_x(&["commit-template", "--install"]).success();
+    stage(&repo, "docs/guide.md");
+
+    // The hook calls `git x`, so the built binary has to be on PATH
+    let bin_dir = assert_cmd::cargo::cargo_bin("git-x")
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    assert_cmd::Command::new("git")
+        .args(["commit", "-q"])
+        .current_dir(repo.path())
+        .env("PATH", &path)
+        .env("GIT_EDITOR", "true")
+        .assert()
+        .success();
+
+    let output = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "docs:\n\nWhy this change:"
+    );
+}
@@ -0,0 +1,109 @@
+use serial_test::serial;
+mod common;
+
+use clap_complete::Shell;
+use common::repo_with_branch;
+use git_x::commands::completion::{CompleteCommand, CompletionInstallCommand, DynamicValues};
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::process::Command;
+
+fn words(line: &[&str]) -> Vec<String> {
+    line.iter().map(|word| word.to_string()).collect()
+}
+
+fn position(line: &[&str]) -> Option<(String, String, String)> {
+    CompleteCommand::position(&words(line))
+}
+
+#[test]
+fn test_complete_position() {
+    let at = |path: &str, arg: &str, partial: &str| {
+        Some((path.to_string(), arg.to_string(), partial.to_string()))
+    };
+    assert_eq!(
+        position(&["what", "--target", "ma"]),
+        at("what", "target", "ma")
+    );
+    assert_eq!(
+        position(&["what", "--target=ma"]),
+        at("what", "target", "ma")
+    );
+    assert_eq!(position(&["-q", "jump", ""]), at("jump", "branch", ""));
+    assert_eq!(
+        position(&["stash-branch", "apply-by-branch", "--pop", "fe"]),
+        at("stash-branch apply-by-branch", "branch_name", "fe")
+    );
+    assert_eq!(
+        position(&["merge-queue", "a", "b", ""]),
+        at("merge-queue", "branches", "")
+    );
+    assert_eq!(position(&["what", "--ta"]), None);
+    assert_eq!(position(&["branch-diff", "main", ""]), None);
+
+    assert_eq!(
+        DynamicValues::for_arg("what", "target"),
+        Some(DynamicValues::Branches)
+    );
+    assert_eq!(
+        DynamicValues::for_arg("stash-branch apply-by-branch", "branch_name"),
+        Some(DynamicValues::StashBranches)
+    );
+    assert_eq!(DynamicValues::for_arg("new", "branch_name"), None);
+}
+
+#[test]
+fn test_completion_scripts_ask_for_dynamic_values() {
+    let bash = CompletionInstallCommand::with_dynamic_values(
+        Shell::Bash,
+        "_git-x() {\n    local i cur\n}\ncomplete -F _git-x git-x\n",
+    );
+    assert!(bash.starts_with("_git_x_dynamic() {"));
+    assert!(bash.contains("_git-x() {\n    _git_x_dynamic && return 0\n    local i cur"));
+
+    let fish = CompletionInstallCommand::with_dynamic_values(Shell::Fish, "complete -c git-x\n");
+    assert!(fish.ends_with(
+        "complete -c git-x -a '(git-x __complete -- (commandline -opc)[2..-1] (commandline -ct))'\n"
+    ));
+}
+
+#[test]
+#[serial]
+fn test_complete_command() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature/login");
+    repo.add_commit("wip.txt", "one", "Add wip");
+    std::fs::write(repo.path().join("wip.txt"), "two").unwrap();
+    Command::new("git")
+        .args(["stash", "push", "-q"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    repo.create_branch("feature/signup");
+
+    repo.run_git_x(&["__complete", "--", "what", "--target", "feature/"])
+        .success()
+        .stdout("feature/login\nfeature/signup\n");
+    repo.run_git_x(&["__complete", "--", "what", "--target=m"])
+        .success()
+        .stdout("--target=main\n");
+    repo.run_git_x(&["__complete", "--", "stash-branch", "apply-by-branch", ""])
+        .success()
+        .stdout("feature/login\n");
+    repo.run_git_x(&["__complete", "--", "new", ""])
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn test_complete_outside_repository() {
+    let dir = tempfile::TempDir::new().unwrap();
+    assert_cmd::Command::cargo_bin("git-x")
+        .unwrap()
+        .args(["__complete", "--", "jump", ""])
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(contains("❌").not());
+}
@@ -0,0 +1,40 @@
+use git_x::core::config::Config;
+use serial_test::serial;
+use std::process::Command;
+
+mod common;
+
+#[test]
+#[serial]
+fn test_config_reads_namespaced_values() {
+    let repo = common::basic_repo();
+    let git_config = |args: &[&str]| {
+        Command::new("git")
+            .arg("config")
+            .args(args)
+            .current_dir(repo.path())
+            .output()
+            .unwrap();
+    };
+    git_config(&["git-x.hotfix.versionFile", "VERSION"]);
+    git_config(&["git-x.info.baselines", "main, origin/main"]);
+    git_config(&["--add", "git-x.info.baselines", "release/current"]);
+    git_config(&["git-x.flag", "yes"]);
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let version_file = Config::get("hotfix.versionFile");
+    let baselines = Config::get_list("info.baselines");
+    let flag = Config::get_bool("flag");
+    let missing = Config::get("does.not.exist");
+    let missing_list = Config::get_list("does.not.exist");
+
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert_eq!(version_file, Some("VERSION".to_string()));
+    assert_eq!(baselines, vec!["main", "origin/main", "release/current"]);
+    assert_eq!(flag, Some(true));
+    assert_eq!(missing, None);
+    assert!(missing_list.is_empty());
+}
@@ -0,0 +1,132 @@
+use serial_test::serial;
+mod common;
+
+use assert_cmd::Command;
+use common::{TestRepo, basic_repo};
+use git_x::commands::repository::{ConfigOrigin, ConfigPolicy, PolicyCheck, config_values_match};
+use predicates::str::contains;
+
+fn git_x(repo: &TestRepo, global: &std::path::Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("git-x")
+        .unwrap()
+        .args(args)
+        .current_dir(repo.path())
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .env("GIT_CONFIG_GLOBAL", global)
+        .assert()
+}
+
+fn git_config(repo: &TestRepo, args: &[&str]) -> String {
+    repo.git(&[&["config"], args].concat())
+}
+
+#[test]
+fn test_parse_policy() {
+    let policy = ConfigPolicy::parse(
+        "pull.rebase=true\ncore.autocrlf=input\ncommit.gpgsign\npull.rebase=merges\n",
+    );
+    assert_eq!(
+        policy.settings,
+        [
+            ("pull.rebase".to_string(), "merges".to_string()),
+            ("core.autocrlf".to_string(), "input".to_string()),
+            ("commit.gpgsign".to_string(), "true".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_values_and_apply_scope() {
+    assert!(config_values_match("true", "yes"));
+    assert!(config_values_match("false", "0"));
+    assert!(config_values_match("input", "input"));
+    assert!(!config_values_match("true", "false"));
+    assert!(!config_values_match("input", "true"));
+
+    let check = |scope: Option<&str>| PolicyCheck {
+        key: "pull.rebase".to_string(),
+        expected: "true".to_string(),
+        actual: scope.map(|scope| ConfigOrigin {
+            scope: scope.to_string(),
+            origin: "file:.git/config".to_string(),
+            value: "false".to_string(),
+        }),
+    };
+    assert_eq!(check(None).apply_scope(false), Some("global"));
+    assert_eq!(check(None).apply_scope(true), Some("local"));
+    assert_eq!(check(Some("system")).apply_scope(false), Some("global"));
+    assert_eq!(check(Some("local")).apply_scope(false), Some("local"));
+    assert_eq!(check(Some("command")).apply_scope(false), None);
+    assert!(!check(Some("local")).compliant());
+    assert!(!check(None).compliant());
+}
+
+#[test]
+#[serial]
+fn test_config_check_reports_and_applies() {
+    let repo = basic_repo();
+    let home = tempfile::tempdir().unwrap();
+    let global = home.path().join(".gitconfig");
+    std::fs::write(&global, "[core]\n\tautocrlf = input\n").unwrap();
+    std::fs::write(
+        repo.path().join(".git-x-policy"),
+        "[pull]\n\trebase = true\n[core]\n\tautocrlf = input\n[commit]\n\tgpgsign = true\n",
+    )
+    .unwrap();
+    git_config(&repo, &["pull.rebase", "false"]);
+
+    git_x(&repo, &global, &["config-check"])
+        .failure()
+        .stdout(contains("✅ core.autocrlf = input (global:"))
+        .stdout(contains(
+            "❌ pull.rebase = false, policy wants true (local:",
+        ))
+        .stdout(contains("❌ commit.gpgsign is not set, policy wants true"))
+        .stdout(contains("2 of 3 setting(s) drift from the policy"))
+        .stdout(contains("--apply"));
+
+    git_x(&repo, &global, &["config-check", "--apply"])
+        .success()
+        .stdout(contains("🔧 pull.rebase = true (local)"))
+        .stdout(contains("🔧 commit.gpgsign = true (global)"))
+        .stdout(contains("Applied 2 setting(s)"));
+    assert_eq!(git_config(&repo, &["--local", "pull.rebase"]), "true");
+    assert!(
+        std::fs::read_to_string(&global)
+            .unwrap()
+            .contains("gpgsign = true")
+    );
+
+    git_x(&repo, &global, &["config-check"])
+        .success()
+        .stdout(contains("Config matches the policy"));
+}
+
+#[test]
+#[serial]
+fn test_config_check_policy_option() {
+    let repo = basic_repo();
+    let home = tempfile::tempdir().unwrap();
+    let global = home.path().join(".gitconfig");
+
+    git_x(&repo, &global, &["config-check"])
+        .failure()
+        .stderr(contains("No policy file at"));
+
+    let policy = home.path().join("team.gitconfig");
+    std::fs::write(&policy, "[fetch]\n\tprune = true\n").unwrap();
+    git_x(
+        &repo,
+        &global,
+        &[
+            "config-check",
+            "--policy",
+            policy.to_str().unwrap(),
+            "--apply",
+            "--local",
+        ],
+    )
+    .success()
+    .stdout(contains("🔧 fetch.prune = true (local)"));
+    assert_eq!(git_config(&repo, &["--local", "fetch.prune"]), "true");
+}
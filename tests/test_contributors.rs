@@ -432,3 +432,188 @@ fn test_contributors_command_direct() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+#[test]
+#[serial]
+fn test_contributors_csv_export() {
+    let repo = common::repo_with_commits(2);
+
+    repo.run_git_x(&["contributors", "--csv"])
+        .success()
+        .stdout(predicate::str::contains(
+            "name,email,commits,first_commit,last_commit,percentage",
+        ))
+        .stdout(predicate::str::contains("Test User,test@example.com,2,"))
+        .stdout(predicate::str::contains(",100"))
+        .stdout(predicate::str::contains("📊").not());
+}
+
+#[test]
+#[serial]
+fn test_contributors_tsv_export() {
+    let repo = common::repo_with_commits(1);
+
+    repo.run_git_x(&["contributors", "--tsv"])
+        .success()
+        .stdout(predicate::str::contains(
+            "name\temail\tcommits\tfirst_commit\tlast_commit\tpercentage",
+        ))
+        .stdout(predicate::str::contains("Test User\ttest@example.com\t1\t"));
+}
+
+#[test]
+#[serial]
+fn test_contributors_csv_and_tsv_conflict() {
+    let repo = common::basic_repo();
+
+    repo.run_git_x(&["contributors", "--csv", "--tsv"])
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+#[serial]
+fn test_contributors_multi_format_direct() {
+    use git_x::commands::analysis::ContributorsCommand;
+    use git_x::core::traits::MultiFormat;
+
+    let repo = common::repo_with_commits(2);
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let cmd = ContributorsCommand::new(None);
+    assert_eq!(cmd.supported_formats(), vec!["text", "csv", "tsv", "json"]);
+
+    let json = cmd.execute_with_format("json").unwrap();
+    assert!(json.starts_with("[{\"name\":\"Test User\""));
+    assert!(json.contains("\"commits\":2"));
+    assert!(json.contains("\"percentage\":100"));
+
+    let text = cmd.execute_with_format("text").unwrap();
+    assert!(text.contains("Repository Contributors"));
+
+    assert!(cmd.execute_with_format("yaml").is_err());
+
+    // Restore original directory
+    let _ = std::env::set_current_dir(&original_dir);
+}
+
+/// Commit `file` as `name`, dated `date`
+fn commit_as(repo: &common::TestRepo, name: &str, file: &str, date: &str) {
+    fs::write(repo.path().join(file), name).unwrap();
+    StdCommand::new("git")
+        .args(["add", "."])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    StdCommand::new("git")
+        .args(["commit", "-m", &format!("{name} edits {file}")])
+        .env("GIT_AUTHOR_NAME", name)
+        .env(
+            "GIT_AUTHOR_EMAIL",
+            format!("{}@example.com", name.to_lowercase()),
+        )
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+}
+
+fn scoped_repo() -> common::TestRepo {
+    let repo = common::basic_repo();
+    std::fs::create_dir(repo.path().join("src")).unwrap();
+    commit_as(&repo, "Alice", "src/a.rs", "2024-01-10T12:00:00");
+    commit_as(&repo, "Alice", "src/b.rs", "2024-02-10T12:00:00");
+    commit_as(&repo, "Bob", "notes.txt", "2024-03-10T12:00:00");
+    commit_as(&repo, "Carol", "src/a.rs", "2024-04-10T12:00:00");
+    repo
+}
+
+#[test]
+#[serial]
+fn test_contributors_limit_and_offset() {
+    let repo = scoped_repo();
+
+    repo.run_git_x(&["contributors", "--limit", "2"])
+        .success()
+        .stdout(predicate::str::contains("🥇 Alice 2 commits"))
+        .stdout(predicate::str::contains(
+            "… 2 more of 4 contributors (use --offset 2",
+        ));
+
+    repo.run_git_x(&["contributors", "--limit", "2", "--offset", "2"])
+        .success()
+        .stdout(predicate::str::contains("Alice").not())
+        .stdout(predicate::str::contains("🥉 Carol 1 commits"))
+        .stdout(predicate::str::contains("👤 Test User 1 commits"))
+        .stdout(predicate::str::contains("more of").not());
+
+    repo.run_git_x(&["contributors", "--csv", "--limit", "1"])
+        .success()
+        .stdout(predicate::str::contains("Alice"))
+        .stdout(predicate::str::contains("Bob").not());
+}
+
+#[test]
+#[serial]
+fn test_contributors_since_until_and_paths() {
+    let repo = scoped_repo();
+
+    repo.run_git_x(&[
+        "contributors",
+        "--since",
+        "2024-02-01",
+        "--until",
+        "2024-03-31",
+    ])
+    .success()
+    .stdout(predicate::str::contains(
+        "Contributors (2024-02-01 to 2024-03-31)",
+    ))
+    .stdout(predicate::str::contains("Alice 1 commits"))
+    .stdout(predicate::str::contains("Bob 1 commits"))
+    .stdout(predicate::str::contains("Carol").not());
+
+    repo.run_git_x(&["contributors", "src"])
+        .success()
+        .stdout(predicate::str::contains("Contributors (all time in src)"))
+        .stdout(predicate::str::contains("Alice 2 commits"))
+        .stdout(predicate::str::contains("Carol 1 commits"))
+        .stdout(predicate::str::contains("Bob").not());
+}
+
+#[test]
+#[serial]
+fn test_contributors_fast_mode() {
+    let repo = scoped_repo();
+
+    repo.run_git_x(&["contributors", "--fast", "src"])
+        .success()
+        .stdout(predicate::str::contains("🥇 Alice 2 commits"))
+        .stdout(predicate::str::contains("   📧 alice@example.com\n"))
+        .stdout(predicate::str::contains("📅").not());
+
+    repo.run_git_x(&["contributors", "--fast", "--csv"])
+        .success()
+        .stdout(predicate::str::contains("Alice,alice@example.com,2,,,"));
+}
+
+#[test]
+fn test_parse_shortlog() {
+    use git_x::commands::analysis::ParallelContributorsCommand;
+
+    let output = "    12\tAlice Smith <alice@example.com>\n     3\tBob <bob@example.com>\n";
+    assert_eq!(
+        ParallelContributorsCommand::parse_shortlog(output),
+        [
+            (
+                "Alice Smith".to_string(),
+                "alice@example.com".to_string(),
+                12
+            ),
+            ("Bob".to_string(), "bob@example.com".to_string(), 3),
+        ]
+    );
+}
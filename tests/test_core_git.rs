@@ -5,7 +5,17 @@ use serial_test::serial;
 // When outside a git repo, we test that appropriate errors are returned.
 // When inside a git repo, we test that the operations work correctly.
 
-use git_x::core::git::{BranchOperations, CommitOperations, GitOperations, RemoteOperations};
+use git_x::core::git::{
+    BranchOperations, CommitOperations, GitOperations, RemoteOperations, shell_quote,
+};
+
+mod common;
+
+#[test]
+fn test_shell_quote() {
+    assert_eq!(shell_quote("/tmp/my dir/todo"), "'/tmp/my dir/todo'");
+    assert_eq!(shell_quote("it's"), "'it'\\''s'");
+}
 
 // Tests for GitOperations
 
@@ -247,6 +257,49 @@ fn test_git_operations_merged_branches() {
     }
 }
 
+#[test]
+#[serial]
+fn test_git_operations_default_branch_fallbacks() {
+    let repo = common::repo_with_branch("trunk");
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(repo.path())
+            .output()
+            .unwrap();
+    };
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    // The only local branch
+    let only = GitOperations::default_branch();
+    git(&["checkout", "-q", "-b", "feature"]);
+    git(&["config", "init.defaultBranch", "none"]);
+    let ambiguous = GitOperations::require_default_branch();
+    git(&["config", "init.defaultBranch", "trunk"]);
+    let configured = GitOperations::default_branch();
+    git(&["branch", "main", "trunk"]);
+    git(&["update-ref", "refs/remotes/origin/develop", "trunk"]);
+    git(&[
+        "symbolic-ref",
+        "refs/remotes/origin/HEAD",
+        "refs/remotes/origin/develop",
+    ]);
+    let remote = GitOperations::default_branch();
+
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert_eq!(only.as_deref(), Some("trunk"));
+    assert!(
+        ambiguous
+            .unwrap_err()
+            .to_string()
+            .contains("No default branch found")
+    );
+    assert_eq!(configured.as_deref(), Some("trunk"));
+    assert_eq!(remote.as_deref(), Some("origin/develop"));
+}
+
 // Tests for BranchOperations
 
 #[test]
@@ -420,3 +473,49 @@ fn test_git_operations_ahead_behind_counts() {
 fn is_in_git_repo() -> bool {
     GitOperations::repo_root().is_ok()
 }
+
+// Tests for BaselineComparison
+
+#[test]
+fn test_baseline_comparison_parse_and_status() {
+    use git_x::core::git::BaselineComparison;
+
+    let diverged = BaselineComparison::parse("main", "3\t2").unwrap();
+    assert_eq!(diverged.baseline, "main");
+    assert_eq!(diverged.behind, 3);
+    assert_eq!(diverged.ahead, 2);
+    assert_eq!(diverged.status(), "diverged");
+
+    assert_eq!(
+        BaselineComparison::parse("main", "0 0").unwrap().status(),
+        "up to date"
+    );
+    assert_eq!(
+        BaselineComparison::parse("main", "0 4").unwrap().status(),
+        "ahead"
+    );
+    assert_eq!(
+        BaselineComparison::parse("main", "1 0").unwrap().status(),
+        "behind"
+    );
+    assert!(BaselineComparison::parse("main", "garbage").is_none());
+}
+
+// Tests for BranchUpstream
+
+#[test]
+fn test_branch_upstream_parse() {
+    use git_x::core::git::BranchUpstream;
+
+    let parsed = BranchUpstream::parse(
+        "main\torigin/main\t\nfeature\torigin/feature\t[gone]\nlocal\t\t\nahead\torigin/ahead\t[ahead 2]",
+    );
+
+    assert_eq!(parsed.len(), 4);
+    assert_eq!(parsed[0].upstream.as_deref(), Some("origin/main"));
+    assert!(!parsed[0].gone);
+    assert!(parsed[1].gone);
+    assert_eq!(parsed[2].upstream, None);
+    assert!(!parsed[2].gone);
+    assert!(!parsed[3].gone);
+}
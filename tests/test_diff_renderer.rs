@@ -0,0 +1,107 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::core::diff_renderer::DiffRenderer;
+
+fn words(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Render `args` with `renderer` from inside a repository whose last commit changed a file
+fn render(renderer: &DiffRenderer, args: &[&str]) -> String {
+    let repo = repo_with_branch("main");
+    repo.add_commit("file.txt", "changed\n", "Change file");
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+    let result = renderer.render(args);
+    let _ = std::env::set_current_dir(&original_dir);
+    result.unwrap()
+}
+
+#[test]
+fn test_diff_renderer_parse() {
+    assert_eq!(DiffRenderer::parse("auto"), None);
+    assert_eq!(DiffRenderer::parse(""), None);
+    assert_eq!(DiffRenderer::parse("plain"), Some(DiffRenderer::Plain));
+    assert_eq!(
+        DiffRenderer::parse("delta"),
+        Some(DiffRenderer::Filter(words(&["delta"])))
+    );
+    assert_eq!(
+        DiffRenderer::parse("difftastic"),
+        Some(DiffRenderer::External("difft".to_string()))
+    );
+    assert_eq!(
+        DiffRenderer::parse("difft --display side-by-side"),
+        Some(DiffRenderer::External(
+            "difft --display side-by-side".to_string()
+        ))
+    );
+    // Anything else runs through the shell, so options and pipes work
+    assert_eq!(
+        DiffRenderer::parse("delta --side-by-side"),
+        Some(DiffRenderer::Filter(words(&[
+            "sh",
+            "-c",
+            "delta --side-by-side"
+        ])))
+    );
+}
+
+#[test]
+fn test_diff_renderer_git_args() {
+    let args = ["stash", "show", "-p", "stash@{0}"];
+    assert_eq!(DiffRenderer::Plain.git_args(&args), words(&args));
+    assert_eq!(
+        DiffRenderer::Filter(words(&["delta"])).git_args(&args),
+        words(&[
+            "stash",
+            "show",
+            "--color=always",
+            "--no-ext-diff",
+            "-p",
+            "stash@{0}"
+        ])
+    );
+    assert_eq!(
+        DiffRenderer::External("difft".to_string()).git_args(&["show", "abc123"]),
+        words(&["-c", "diff.external=difft", "show", "abc123", "--ext-diff"])
+    );
+}
+
+#[test]
+#[serial]
+fn test_diff_renderer_pipes_through_filter() {
+    let renderer = DiffRenderer::Filter(words(&["sh", "-c", "echo RENDERED; cat"]));
+    let output = render(&renderer, &["show", "--format=", "HEAD"]);
+
+    assert!(output.starts_with("RENDERED\n"));
+    // Git colored it for the filter
+    assert!(output.contains("\u{1b}[32mchanged"));
+}
+
+#[test]
+#[serial]
+fn test_diff_renderer_runs_external_diff() {
+    let renderer = DiffRenderer::External("echo EXTERNAL".to_string());
+    let output = render(&renderer, &["show", "--format=", "HEAD"]);
+
+    assert!(output.starts_with("EXTERNAL file.txt"));
+    assert!(!output.contains("+changed"));
+}
+
+#[test]
+#[serial]
+fn test_diff_renderer_falls_back_to_plain() {
+    for renderer in [
+        DiffRenderer::Plain,
+        DiffRenderer::Filter(words(&["false"])),
+        DiffRenderer::Filter(words(&["git-x-no-such-renderer"])),
+    ] {
+        let output = render(&renderer, &["show", "--format=", "HEAD"]);
+        assert!(output.starts_with("diff --git a/file.txt b/file.txt"));
+        assert!(output.contains("+changed"));
+    }
+}
@@ -0,0 +1,94 @@
+use serial_test::serial;
+mod common;
+
+use git_x::commands::analysis::DiffStatCommand;
+use git_x::core::traits::{Command, MultiFormat};
+use predicates::str::contains;
+
+/// Repo with a `base` tag followed by changes spread across nested directories
+fn repo_with_tree_changes() -> common::TestRepo {
+    let repo = common::basic_repo();
+    std::process::Command::new("git")
+        .args(["tag", "base"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    std::fs::create_dir_all(repo.path().join("src/core")).unwrap();
+    std::fs::create_dir_all(repo.path().join("docs")).unwrap();
+    std::fs::write(repo.path().join("src/core/a.rs"), "a\nb\nc\n").unwrap();
+    std::fs::write(repo.path().join("src/lib.rs"), "lib\n").unwrap();
+    std::fs::write(repo.path().join("docs/guide.md"), "guide\n").unwrap();
+    std::fs::write(repo.path().join("top.txt"), "top\n").unwrap();
+    repo.add_commit("top.txt", "top\n", "spread changes");
+    repo
+}
+
+#[test]
+#[serial]
+fn test_diff_stat_rolls_up_top_level_directories() {
+    let repo = repo_with_tree_changes();
+
+    repo.run_git_x(&["diff-stat", "base"])
+        .success()
+        .stdout(contains("📊 Diff stat"))
+        .stdout(contains("4 files, +6 -0, depth 1"))
+        .stdout(contains("src/"))
+        .stdout(contains("docs/"))
+        .stdout(contains("./"));
+}
+
+#[test]
+#[serial]
+fn test_diff_stat_depth_expands_directories() {
+    let repo = repo_with_tree_changes();
+
+    repo.run_git_x(&["diff-stat", "base", "HEAD", "--depth", "2"])
+        .success()
+        .stdout(contains("src/core/"))
+        .stdout(contains("depth 2"));
+}
+
+#[test]
+#[serial]
+fn test_diff_stat_json_output() {
+    let repo = repo_with_tree_changes();
+
+    repo.run_git_x(&["diff-stat", "base", "--json"])
+        .success()
+        .stdout(contains(
+            "{\"directory\":\"src\",\"files\":2,\"insertions\":4,\"deletions\":0,\"binary_files\":0}",
+        ));
+}
+
+#[test]
+#[serial]
+fn test_diff_stat_no_changes() {
+    let repo = common::basic_repo();
+
+    repo.run_git_x(&["diff-stat", "HEAD"])
+        .success()
+        .stdout(contains("✅ No changes in HEAD..HEAD"));
+}
+
+#[test]
+#[serial]
+fn test_diff_stat_command_direct() {
+    let repo = repo_with_tree_changes();
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let cmd = DiffStatCommand::new("base".to_string(), None, 1);
+    assert_eq!(cmd.name(), "diff-stat");
+    let csv = cmd.execute_with_format("csv");
+    let invalid = DiffStatCommand::new("no-such-ref".to_string(), None, 1).execute();
+
+    // Restore original directory
+    let _ = std::env::set_current_dir(&original_dir);
+
+    let csv = csv.unwrap();
+    assert!(csv.starts_with("directory,files,insertions,deletions,binary_files"));
+    assert!(csv.contains("docs,1,1,0,0"));
+    assert!(invalid.is_err());
+}
@@ -49,6 +49,11 @@ fn create_repo_with_stash() -> (TempDir, PathBuf) {
         .current_dir(&repo_path)
         .output()
         .expect("Failed to set git user.email");
+    StdCommand::new("git")
+        .args(["config", "git-x.language", "en"])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to set git-x.language");
 
     // Create initial commit
     fs::write(repo_path.join("README.md"), "# Test Repo").expect("Failed to write file");
@@ -113,6 +118,11 @@ fn create_repo_with_remote() -> (TempDir, PathBuf) {
         .current_dir(&repo_path)
         .output()
         .expect("Failed to set git user.email");
+    StdCommand::new("git")
+        .args(["config", "git-x.language", "en"])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to set git-x.language");
 
     // Create initial commit and push
     fs::write(repo_path.join("README.md"), "# Test Repo").expect("Failed to write file");
@@ -776,6 +786,11 @@ fn test_e2e_commands_handle_empty_repo_gracefully() {
         .current_dir(&repo_path)
         .output()
         .expect("Failed to set git user.email");
+    StdCommand::new("git")
+        .args(["config", "git-x.language", "en"])
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to set git-x.language");
 
     let repo = TestRepo {
         _temp_dir: temp_dir,
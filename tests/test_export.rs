@@ -0,0 +1,109 @@
+use git_x::core::export::{ExportFormat, ExportValue, Exporter};
+
+#[test]
+fn test_export_format_from_str() {
+    assert_eq!("csv".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+    assert_eq!("TSV".parse::<ExportFormat>().unwrap(), ExportFormat::Tsv);
+    assert_eq!("json".parse::<ExportFormat>().unwrap(), ExportFormat::Json);
+    assert!("yaml".parse::<ExportFormat>().is_err());
+    assert_eq!(ExportFormat::Csv.to_string(), "csv");
+}
+
+#[test]
+fn test_exporter_csv_quotes_special_fields() {
+    let mut exporter = Exporter::new(&["name", "note"]);
+    exporter.add_row(vec!["Doe, Jane".into(), "said \"hi\"".into()]);
+    exporter.add_row(vec!["plain".into(), ExportValue::Null]);
+
+    assert_eq!(
+        exporter.render(ExportFormat::Csv),
+        "name,note\n\"Doe, Jane\",\"said \"\"hi\"\"\"\nplain,"
+    );
+}
+
+#[test]
+fn test_exporter_tsv_replaces_tabs_and_newlines() {
+    let mut exporter = Exporter::new(&["name", "count"]);
+    exporter.add_row(vec!["a\tb\nc".into(), 3usize.into()]);
+
+    assert_eq!(exporter.render(ExportFormat::Tsv), "name\tcount\na b c\t3");
+}
+
+#[test]
+fn test_exporter_json_records() {
+    let mut exporter = Exporter::new(&["name", "commits", "share", "active"]);
+    exporter.add_row(vec![
+        "Jane \"JD\"".into(),
+        4usize.into(),
+        12.5.into(),
+        true.into(),
+    ]);
+
+    assert_eq!(exporter.len(), 1);
+    assert_eq!(
+        exporter.render(ExportFormat::Json),
+        "[{\"name\":\"Jane \\\"JD\\\"\",\"commits\":4,\"share\":12.5,\"active\":true}]"
+    );
+}
+
+#[test]
+fn test_export_value_nested_json() {
+    let value = ExportValue::record(vec![
+        ("tags", vec!["a", "b"].into()),
+        ("missing", ExportValue::from(None::<String>)),
+        ("nan", f64::NAN.into()),
+    ]);
+
+    assert_eq!(
+        value.to_json(),
+        "{\"tags\":[\"a\",\"b\"],\"missing\":null,\"nan\":null}"
+    );
+    assert_eq!(ExportValue::from(vec!["a", "b"]).to_plain(), "a;b");
+}
+
+#[test]
+fn test_exporter_empty_renders_headers_only() {
+    let exporter = Exporter::new(&["name", "email"]);
+
+    assert!(exporter.is_empty());
+    assert_eq!(exporter.render(ExportFormat::Csv), "name,email");
+    assert_eq!(exporter.render(ExportFormat::Json), "[]");
+}
+
+#[test]
+fn test_export_value_parse_json() {
+    let value = ExportValue::parse_json(
+        r#" {"id": 42, "name": "CI \"main\"", "ok": true, "ratio": 0.5,
+             "tags": ["a", "é"], "none": null, "nested": {}} "#,
+    )
+    .unwrap();
+
+    assert_eq!(value.get("id"), Some(&ExportValue::Int(42)));
+    assert_eq!(
+        value.get("name").and_then(|v| v.as_str()),
+        Some("CI \"main\"")
+    );
+    assert_eq!(value.get("ok"), Some(&ExportValue::Bool(true)));
+    assert_eq!(value.get("ratio"), Some(&ExportValue::Float(0.5)));
+    assert_eq!(value.get("tags").unwrap().items().len(), 2);
+    assert_eq!(value.get("tags").unwrap().items()[1].as_str(), Some("é"));
+    assert_eq!(value.get("none"), Some(&ExportValue::Null));
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(
+        ExportValue::parse_json(r#""\ud83d\ude00""#).unwrap(),
+        ExportValue::from("😀")
+    );
+
+    // Round-trips through the writer
+    let json = value.to_json();
+    assert_eq!(ExportValue::parse_json(&json).unwrap(), value);
+}
+
+#[test]
+fn test_export_value_parse_json_rejects_invalid_input() {
+    assert!(ExportValue::parse_json("").is_err());
+    assert!(ExportValue::parse_json("[1, 2").is_err());
+    assert!(ExportValue::parse_json(r#"{"a" 1}"#).is_err());
+    assert!(ExportValue::parse_json("[1] trailing").is_err());
+    assert!(ExportValue::parse_json(r#""\ud83d\u0041""#).is_err());
+}
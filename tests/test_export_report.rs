@@ -0,0 +1,98 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_commits;
+use git_x::core::report::{Report, ReportFormat};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+fn sample() -> Report {
+    let mut report = Report::new("Repository report: demo");
+    report.add_intro("Generated today.");
+    report.add_section("Summary", "\u{1b}[1mbold\u{1b}[0m line\n");
+    report.add_section("Technical debt", "a < b & c");
+    report
+}
+
+#[test]
+fn test_report_format_parsing() {
+    assert_eq!(
+        "md".parse::<ReportFormat>().unwrap(),
+        ReportFormat::Markdown
+    );
+    assert_eq!("HTML".parse::<ReportFormat>().unwrap(), ReportFormat::Html);
+    assert!("pdf".parse::<ReportFormat>().is_err());
+
+    assert_eq!(
+        ReportFormat::from_path("q3/report.html"),
+        Some(ReportFormat::Html)
+    );
+    assert_eq!(
+        ReportFormat::from_path("report.MD"),
+        Some(ReportFormat::Markdown)
+    );
+    assert_eq!(ReportFormat::from_path("report.txt"), None);
+    assert_eq!(ReportFormat::from_path("report"), None);
+}
+
+#[test]
+fn test_report_markdown_has_contents_and_sections() {
+    let markdown = sample().render(ReportFormat::Markdown);
+
+    assert!(markdown.starts_with("# Repository report: demo\n\nGenerated today.\n"));
+    assert!(markdown.contains("- [Summary](#summary)\n- [Technical debt](#technical-debt)"));
+    assert!(markdown.contains("## Technical debt\n\n```text\na < b & c\n```"));
+    // Terminal styling doesn't leak into the document
+    assert!(markdown.contains("bold line"));
+    assert!(!markdown.contains('\u{1b}'));
+}
+
+#[test]
+fn test_report_html_escapes_and_links() {
+    let html = sample().render(ReportFormat::Html);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>Repository report: demo</title>"));
+    assert!(html.contains("<li><a href=\"#technical-debt\">Technical debt</a></li>"));
+    assert!(html.contains("<h2 id=\"technical-debt\">Technical debt</h2>"));
+    assert!(html.contains("<pre>a &lt; b &amp; c</pre>"));
+}
+
+#[test]
+#[serial]
+fn test_export_report_to_stdout_and_file() {
+    let repo = repo_with_commits(2);
+
+    repo.run_git_x(&["export-report"])
+        .success()
+        .stdout(contains("# Repository report:"))
+        .stdout(contains("- [Large files](#large-files)"))
+        .stdout(contains("## Health"))
+        .stdout(contains("Repository Contributors"));
+
+    repo.run_git_x(&["export-report", "--output", "report.html"])
+        .success()
+        .stdout(contains("Report written to report.html (5 sections, html)"));
+    let html = std::fs::read_to_string(repo.path().join("report.html")).unwrap();
+    assert!(html.contains("<h2 id=\"contributors\">Contributors</h2>"));
+
+    repo.run_git_x(&["export-report", "-o", "report.txt", "--format", "md"])
+        .success()
+        .stdout(contains("markdown"));
+    let markdown = std::fs::read_to_string(repo.path().join("report.txt")).unwrap();
+    assert!(markdown.contains("## Technical debt"));
+}
+
+#[test]
+#[serial]
+fn test_export_report_outside_repository() {
+    let outside = tempfile::TempDir::new().unwrap();
+    assert_cmd::Command::cargo_bin("git-x")
+        .unwrap()
+        .arg("export-report")
+        .current_dir(outside.path())
+        .assert()
+        .success()
+        .stderr(contains("not a git repository"))
+        .stdout(contains("#").not());
+}
@@ -0,0 +1,96 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::analysis::{CoupledPair, coupled_clusters, coupled_pairs};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+fn commit(files: &[&str]) -> Vec<String> {
+    files.iter().map(|file| file.to_string()).collect()
+}
+
+#[test]
+fn test_coupled_pairs_counts_and_confidence() {
+    let commits = [
+        commit(&["a.rs", "b.rs"]),
+        commit(&["a.rs", "b.rs"]),
+        commit(&["b.rs", "a.rs", "a.rs"]),
+        commit(&["a.rs", "c.rs"]),
+        commit(&["b.rs"]),
+    ];
+
+    let pairs = coupled_pairs(&commits, 2);
+    assert_eq!(
+        pairs,
+        [CoupledPair {
+            first: "a.rs".to_string(),
+            second: "b.rs".to_string(),
+            shared: 3,
+            first_changes: 4,
+            second_changes: 4,
+        }]
+    );
+    assert_eq!(pairs[0].first_confidence(), 75);
+    assert_eq!(pairs[0].confidence(), 75);
+
+    // The single shared change of a.rs and c.rs is all of c.rs's history
+    let pairs = coupled_pairs(&commits, 1);
+    assert_eq!(pairs[0].second, "c.rs");
+    assert_eq!(pairs[0].second_confidence(), 100);
+    assert!(pairs[1].involves("b.rs"));
+}
+
+#[test]
+fn test_coupled_clusters() {
+    let commits = [
+        commit(&["a", "b"]),
+        commit(&["b", "c"]),
+        commit(&["x", "y"]),
+        commit(&["a", "z"]),
+        commit(&["a", "q"]),
+        commit(&["a", "r"]),
+    ];
+    let pairs = coupled_pairs(&commits, 1);
+
+    assert_eq!(
+        coupled_clusters(&pairs, 50),
+        [commit(&["a", "b", "c", "q", "r", "z"])]
+    );
+    // a and b share one of b's two changes, so a stricter cut splits them apart
+    assert_eq!(
+        coupled_clusters(&pairs, 100),
+        [commit(&["a", "q", "r", "z"])]
+    );
+}
+
+#[test]
+#[serial]
+fn test_files_changed_together_command() {
+    let repo = basic_repo();
+    for i in 0..3 {
+        std::fs::write(repo.path().join("api.rs"), format!("api {i}")).unwrap();
+        repo.add_commit("client.rs", &format!("client {i}"), "change api and client");
+    }
+    repo.add_commit("notes.txt", "notes", "notes");
+
+    repo.run_git_x(&["files-changed-together"])
+        .success()
+        .stdout(contains("Files that change together (all history)"))
+        .stdout(contains("api.rs ⇄ client.rs"))
+        .stdout(contains("3 shared commits · 100% of api.rs changes"))
+        .stdout(contains("notes.txt").not());
+
+    repo.run_git_x(&["files-changed-together", "client.rs"])
+        .success()
+        .stdout(contains("Files that change with client.rs"))
+        .stdout(contains("100%  api.rs (3 of 3 commits)"));
+
+    repo.run_git_x(&["files-changed-together", "--min-shared", "4"])
+        .success()
+        .stdout(contains("No files changed together in 4 or more commits"));
+
+    repo.run_git_x(&["files-changed-together", "--max-files", "1"])
+        .success()
+        .stdout(contains("3 touching more than 1 files skipped"));
+}
@@ -0,0 +1,66 @@
+use serial_test::serial;
+mod common;
+
+use common::{git, repo_with_branch};
+use git_x::commands::analysis::Landing;
+use predicates::str::contains;
+
+#[test]
+fn test_landing_find() {
+    // main: c1 - m(c1, b2) - c3, with the side branch b1 - b2 forked from c1
+    let mainline = "c3 m\nm c1 b2\n";
+    assert_eq!(
+        Landing::find("b1", mainline, "c3\nm\nb2\n"),
+        Landing::Merge("m".to_string())
+    );
+    // c1 is the first parent of the merge, so it was already on main
+    assert_eq!(Landing::find("c1", mainline, "c3\nm\n"), Landing::Direct);
+    // The tip itself
+    assert_eq!(Landing::find("c3", "", ""), Landing::Direct);
+}
+
+#[test]
+#[serial]
+fn test_find_merge_command() {
+    let repo = repo_with_branch("main");
+    let root = repo.path();
+
+    git(root, &["checkout", "-q", "-b", "feature/login"]);
+    repo.add_commit("login.txt", "form", "Add login form");
+    let feature_commit = git(root, &["rev-parse", "--short", "HEAD"]);
+    git(root, &["checkout", "-q", "main"]);
+    repo.add_commit("readme.txt", "docs", "Update docs");
+    let direct_commit = git(root, &["rev-parse", "--short", "HEAD"]);
+    git(
+        root,
+        &[
+            "merge",
+            "-q",
+            "--no-ff",
+            "feature/login",
+            "-m",
+            "Merge pull request #42 from acme/feature/login",
+        ],
+    );
+    let merge = git(root, &["rev-parse", "--short", "HEAD"]);
+
+    repo.run_git_x(&["find-merge", &feature_commit])
+        .success()
+        .stdout(contains(format!("reached main in merge {merge}")))
+        .stdout(contains("Merge pull request #42"))
+        .stdout(contains("Add login form"));
+
+    repo.run_git_x(&["find-merge", &direct_commit, "--into", "main"])
+        .success()
+        .stdout(contains("committed directly on main"));
+
+    git(root, &["checkout", "-q", "-b", "unmerged"]);
+    repo.add_commit("wip.txt", "wip", "Work in progress");
+    repo.run_git_x(&["find-merge", "HEAD", "--into", "main"])
+        .success()
+        .stderr(contains("is not on main"));
+
+    repo.run_git_x(&["find-merge", "no-such-commit"])
+        .success()
+        .stderr(contains("is not a commit"));
+}
@@ -18,7 +18,7 @@ fn test_fixup_run_function_outside_git_repo() {
 
     std::env::set_current_dir(temp_dir.path()).unwrap();
 
-    let cmd = FixupCommand::new("abc123".to_string(), false);
+    let cmd = FixupCommand::new("abc123".parse().unwrap(), false);
     let result = cmd.execute();
 
     // Should fail because commit doesn't exist and we're not in a git repo
@@ -43,7 +43,7 @@ fn test_fixup_command_direct() {
 
     std::env::set_current_dir(repo.path()).unwrap();
 
-    let cmd = FixupCommand::new("HEAD".to_string(), false);
+    let cmd = FixupCommand::new("HEAD".parse().unwrap(), false);
     let result = cmd.execute();
 
     // The fixup command may fail due to no staged changes, which is acceptable
@@ -133,3 +133,150 @@ fn test_fixup_command_help() {
             "Create fixup commits for easier interactive rebasing",
         ));
 }
+
+const ABSORB_DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3 +3 @@ fn main() {
+-    old();
++    new();
+@@ -10,0 +11,2 @@ fn helper() {
++    extra();
++    more();
+diff --git a/notes.md b/notes.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/notes.md
+@@ -0,0 +1 @@
++notes";
+
+#[test]
+#[serial]
+fn test_staged_hunk_parse_diff() {
+    use git_x::commands::commit::StagedHunk;
+
+    let (hunks, skipped) = StagedHunk::parse_diff(ABSORB_DIFF);
+
+    assert_eq!(skipped, vec!["notes.md"]);
+    assert_eq!(hunks.len(), 2);
+    assert_eq!(hunks[0].path, "src/lib.rs");
+    assert_eq!((hunks[0].old_start, hunks[0].old_count), (3, 1));
+    assert_eq!(hunks[0].blame_lines(), vec![3]);
+    assert_eq!(hunks[1].lines, vec!["+    extra();", "+    more();"]);
+    // Pure additions are blamed on the lines around them
+    assert_eq!(hunks[1].blame_lines(), vec![10, 11]);
+    assert_eq!(hunks[1].delta(), 2);
+}
+
+#[test]
+#[serial]
+fn test_staged_hunk_build_patch_shifts_for_applied_hunks() {
+    use git_x::commands::commit::StagedHunk;
+
+    let (hunks, _) = StagedHunk::parse_diff(ABSORB_DIFF);
+    let mut earlier = hunks[0].clone();
+    earlier.old_start = 1;
+    earlier.old_count = 0;
+    earlier.new_count = 3;
+
+    let patch = StagedHunk::build_patch(&[&hunks[1]], &[&earlier]);
+    assert!(patch.starts_with("diff --git a/src/lib.rs b/src/lib.rs\n"));
+    assert!(patch.contains("+++ b/src/lib.rs\n@@ -13,0 +14,2 @@\n+    extra();\n"));
+
+    let patch = StagedHunk::build_patch(&[&hunks[0], &hunks[1]], &[]);
+    assert!(patch.contains("@@ -3,1 +3,1 @@\n-    old();\n+    new();\n@@ -10,0 +11,2 @@"));
+}
+
+#[test]
+#[serial]
+fn test_staged_hunk_pick_target() {
+    use git_x::commands::commit::StagedHunk;
+
+    let candidates = vec!["aaa".to_string(), "bbb".to_string()];
+    let blamed = |shas: &[&str]| shas.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        StagedHunk::pick_target(&blamed(&["aaa", "aaa"]), &candidates),
+        Some("aaa".to_string())
+    );
+    assert_eq!(
+        StagedHunk::pick_target(&blamed(&["aaa", "bbb"]), &candidates),
+        None
+    );
+    assert_eq!(
+        StagedHunk::pick_target(&blamed(&["ccc"]), &candidates),
+        None
+    );
+    assert_eq!(StagedHunk::pick_target(&[], &candidates), None);
+}
+
+/// Two commits touching different files, then staged edits to both plus a new file
+fn repo_with_absorbable_changes() -> common::TestRepo {
+    let repo = basic_repo();
+    repo.add_commit("a.txt", "1\n2\n3\n4\n5\n6\n7\n8\n", "Add a");
+    repo.add_commit("b.txt", "x\ny\nz\n", "Add b");
+
+    fs::write(
+        repo.path().join("a.txt"),
+        "1\n2\nTHREE\n4\n5\n6\n7\nEIGHT\n9",
+    )
+    .unwrap();
+    fs::write(repo.path().join("b.txt"), "x\nY\nz").unwrap();
+    fs::write(repo.path().join("c.txt"), "new\n").unwrap();
+    repo.git(&["add", "."]);
+    repo
+}
+
+#[test]
+#[serial]
+fn test_fixup_auto_creates_fixups_per_commit() {
+    let repo = repo_with_absorbable_changes();
+
+    repo.run_git_x(&["fixup", "--auto"])
+        .success()
+        .stdout(predicate::str::contains("into 2 fixup commit(s)"))
+        .stdout(predicate::str::contains("fixup! Add a (2 hunk(s))"))
+        .stdout(predicate::str::contains("fixup! Add b (1 hunk(s))"))
+        .stdout(predicate::str::contains("c.txt (added"));
+
+    let log = repo.git(&["log", "--format=%s", "-2"]);
+    assert_eq!(log, "fixup! Add b\nfixup! Add a");
+    // The new file is still staged and nothing else is
+    assert_eq!(repo.git(&["diff", "--cached", "--name-only"]), "c.txt");
+}
+
+#[test]
+#[serial]
+fn test_fixup_auto_rebase_squashes() {
+    let repo = repo_with_absorbable_changes();
+
+    repo.run_git_x(&["fixup", "--auto", "--rebase"])
+        .success()
+        .stdout(predicate::str::contains("✅ Autosquash rebase completed"));
+
+    assert_eq!(repo.git(&["log", "--format=%s"]), "Add b\nAdd a\ninitial");
+    assert_eq!(
+        repo.git(&["show", "HEAD~1:a.txt"]),
+        "1\n2\nTHREE\n4\n5\n6\n7\nEIGHT\n9"
+    );
+    assert_eq!(repo.git(&["show", "HEAD:b.txt"]), "x\nY\nz");
+}
+
+#[test]
+#[serial]
+fn test_fixup_auto_ignores_pushed_commits() {
+    let repo = basic_repo();
+    repo.add_commit("a.txt", "1\n2\n", "Add a");
+    let branch = repo.git(&["branch", "--show-current"]);
+    let _remote = repo.setup_remote(branch.trim());
+
+    fs::write(repo.path().join("a.txt"), "1\nTWO\n").unwrap();
+    repo.git(&["add", "a.txt"]);
+
+    repo.run_git_x(&["fixup", "--auto"])
+        .success()
+        .stderr(predicate::str::contains("No staged hunk could be matched"));
+    assert_eq!(repo.git(&["diff", "--cached", "--name-only"]), "a.txt");
+}
@@ -0,0 +1,138 @@
+use serial_test::serial;
+mod common;
+
+use common::{git, repo_with_branch};
+use git_x::commands::repository::{ForkSetupCommand, ForkStep};
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn test_sibling_url() {
+    assert_eq!(
+        ForkSetupCommand::sibling_url("git@github.com:me/tool.git", "acme/tool").as_deref(),
+        Some("git@github.com:acme/tool.git")
+    );
+    assert_eq!(
+        ForkSetupCommand::sibling_url("https://github.com/me/tool", "acme/tool-rs").as_deref(),
+        Some("https://github.com/acme/tool-rs")
+    );
+    assert_eq!(
+        ForkSetupCommand::sibling_url("ssh://git@example.com/me/tool.git/", "acme/tool").as_deref(),
+        Some("ssh://git@example.com/acme/tool.git")
+    );
+    assert_eq!(ForkSetupCommand::sibling_url("tool", "acme/tool"), None);
+}
+
+#[test]
+fn test_fork_steps() {
+    let url = "https://github.com/acme/tool.git";
+    assert_eq!(ForkSetupCommand::remote_step(Some(url), url), None);
+    let add = ForkSetupCommand::remote_step(None, url).unwrap();
+    assert_eq!(add.args, ["remote", "add", "upstream", url]);
+    let repoint = ForkSetupCommand::remote_step(Some("old"), url).unwrap();
+    assert_eq!(repoint.args, ["remote", "set-url", "upstream", url]);
+    assert!(repoint.description.contains("(was old)"));
+
+    let steps: Vec<ForkStep> = ForkSetupCommand::config_steps("develop");
+    let keys: Vec<&str> = steps.iter().map(|step| step.args[1].as_str()).collect();
+    assert_eq!(
+        keys,
+        ["pull.rebase", "remote.pushDefault", "alias.sync-fork"]
+    );
+    assert_eq!(steps[1].args[2], "origin");
+    let alias = ForkSetupCommand::sync_alias("develop");
+    assert!(alias.starts_with("!git fetch upstream && "));
+    assert!(alias.contains("git merge --ff-only upstream/develop"));
+    assert!(alias.contains("git fetch . upstream/develop:develop"));
+}
+
+#[test]
+#[serial]
+fn test_fork_setup_command() {
+    let original = repo_with_branch("main");
+    let dir = TempDir::new().unwrap();
+    let fork = dir.path().join("fork.git");
+    let local = dir.path().join("local");
+    let original_path = original.path().to_str().unwrap();
+    git(
+        dir.path(),
+        &[
+            "clone",
+            "-q",
+            "--bare",
+            original_path,
+            fork.to_str().unwrap(),
+        ],
+    );
+    git(
+        dir.path(),
+        &[
+            "clone",
+            "-q",
+            fork.to_str().unwrap(),
+            local.to_str().unwrap(),
+        ],
+    );
+    git(&local, &["config", "user.name", "Test User"]);
+    git(&local, &["config", "user.email", "test@example.com"]);
+
+    let git_x = |args: &[&str]| {
+        assert_cmd::Command::cargo_bin("git-x")
+            .unwrap()
+            .args(args)
+            .current_dir(&local)
+            .env("GIT_X_NON_INTERACTIVE", "1")
+            .assert()
+    };
+
+    git_x(&["fork-setup", original_path, "--dry-run"])
+        .success()
+        .stdout(contains("Would run: git remote add upstream"))
+        .stdout(contains("Would run: git config pull.rebase true"));
+    assert_eq!(git(&local, &["remote"]), "origin");
+
+    git_x(&["fork-setup", original_path])
+        .success()
+        .stdout(contains("Added remote 'upstream'"))
+        .stdout(contains("fast-forward main"));
+    assert_eq!(
+        git(&local, &["remote", "get-url", "upstream"]),
+        original_path
+    );
+    assert_eq!(git(&local, &["config", "pull.rebase"]), "true");
+    assert_eq!(git(&local, &["config", "remote.pushDefault"]), "origin");
+
+    // Checked out: merged fast-forward
+    original.add_commit("one.txt", "1", "Upstream change one");
+    git(&local, &["sync-fork"]);
+    assert_eq!(
+        git(&local, &["rev-parse", "main"]),
+        git(original.path(), &["rev-parse", "HEAD"])
+    );
+
+    // Not checked out: updated in place
+    git(&local, &["checkout", "-q", "-b", "feature"]);
+    original.add_commit("two.txt", "2", "Upstream change two");
+    git(&local, &["sync-fork"]);
+    assert_eq!(
+        git(&local, &["rev-parse", "main"]),
+        git(original.path(), &["rev-parse", "HEAD"])
+    );
+
+    git_x(&["fork-setup", original_path])
+        .success()
+        .stdout(contains("already points at"));
+    git_x(&["fork-setup", fork.to_str().unwrap()])
+        .success()
+        .stderr(contains("is origin itself"));
+}
+
+#[test]
+#[serial]
+fn test_fork_setup_without_origin() {
+    let repo = repo_with_branch("main");
+
+    repo.run_git_x(&["fork-setup", "https://example.com/acme/tool.git"])
+        .success()
+        .stderr(contains("No remote named 'origin'"));
+}
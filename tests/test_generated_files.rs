@@ -0,0 +1,73 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::core::generated::GeneratedFiles;
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::process::Command;
+
+#[test]
+fn test_generated_files_rules() {
+    let files =
+        GeneratedFiles::with_patterns(vec!["*.gen.ts".to_string(), "/api/schema/*".to_string()]);
+    let unspecified = |path: &str| files.is_excluded(path, "unspecified", "unspecified");
+
+    assert!(unspecified("Cargo.lock"));
+    assert!(unspecified("web/package-lock.json"));
+    assert!(unspecified("web/node_modules/react/index.js"));
+    assert!(unspecified("static/app.min.js"));
+    assert!(unspecified("client/types.gen.ts"));
+    assert!(unspecified("api/schema/v1.rs"));
+    assert!(!unspecified("src/api/schema/v1.rs"));
+    assert!(!unspecified("src/main.rs"));
+
+    assert!(files.is_excluded("src/models.rs", "set", "unspecified"));
+    assert!(files.is_excluded("src/models.rs", "unspecified", "true"));
+    assert!(!files.is_excluded("static/app.min.js", "false", "unspecified"));
+    assert!(!files.is_excluded("vendor/patched.c", "unspecified", "unset"));
+}
+
+#[test]
+#[serial]
+fn test_analysis_leaves_out_generated_files() {
+    let repo = repo_with_branch("main");
+    std::fs::create_dir(repo.path().join("src")).unwrap();
+    repo.add_commit(
+        ".gitattributes",
+        "src/schema.rs linguist-generated\nsrc/app.min.js -linguist-generated\n",
+        "Mark generated code",
+    );
+    repo.add_commit("Cargo.lock", &"lock\n".repeat(2000), "Add lockfile");
+    repo.add_commit("src/schema.rs", &"struct A;\n".repeat(2000), "Add schema");
+    repo.add_commit("src/app.min.js", &"a();\n".repeat(2000), "Add bundle");
+    repo.add_commit("src/main.rs", &"fn x() {}\n".repeat(2000), "Add main");
+
+    repo.run_git_x(&["large-files", "--threshold", "0.001"])
+        .success()
+        .stdout(contains("src/main.rs"))
+        .stdout(contains("src/app.min.js"))
+        .stdout(contains("Cargo.lock").not())
+        .stdout(contains("src/schema.rs").not())
+        .stdout(contains(
+            "🙈 2 generated or vendored file(s) left out (--include-generated to show them)",
+        ));
+
+    repo.run_git_x(&["large-files", "--threshold", "0.001", "--include-generated"])
+        .success()
+        .stdout(contains("Cargo.lock"))
+        .stdout(contains("src/schema.rs"))
+        .stdout(contains("🙈").not());
+
+    Command::new("git")
+        .args(["config", "git-x.analysis.generated", "main.rs"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    repo.run_git_x(&["summary", "--json"])
+        .success()
+        .stdout(contains(r#""generated_files":3"#));
+    repo.run_git_x(&["summary", "--include-generated", "--json"])
+        .success()
+        .stdout(contains(r#""generated_files":0"#));
+}
@@ -0,0 +1,99 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::analysis::DeletedFile;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// Repo where `src/legacy/parser.rs` and `docs/old.md` were deleted in separate commits
+fn repo_with_deletions() -> common::TestRepo {
+    let repo = basic_repo();
+    std::fs::create_dir_all(repo.path().join("src/legacy")).unwrap();
+    std::fs::create_dir_all(repo.path().join("docs")).unwrap();
+    repo.add_commit(
+        "src/legacy/parser.rs",
+        "fn parse() {}\n",
+        "Add legacy parser",
+    );
+    repo.add_commit("docs/old.md", "# Old docs\n", "Add old docs");
+
+    repo.git(&["rm", "-q", "src/legacy/parser.rs"]);
+    repo.git(&["commit", "-q", "-m", "Drop legacy parser"]);
+    repo.git(&["rm", "-q", "docs/old.md"]);
+    repo.git(&["commit", "-q", "-m", "Remove outdated docs"]);
+    repo
+}
+
+#[test]
+#[serial]
+fn test_deleted_file_parse_log_keeps_latest_deletion() {
+    let log = "\x1eaaaaaaaaaa\tAlice\t2025-02-01\tRemove again\n\nsrc/a.rs\n\
+               \x1ebbbbbbbbbb\tBob\t2025-01-01\tRemove files\n\nsrc/a.rs\nsrc/b.rs\n";
+
+    let files = DeletedFile::parse_log(log);
+
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].path, "src/a.rs");
+    assert_eq!(files[0].author, "Alice");
+    assert_eq!(files[0].short_hash(), "aaaaaaa");
+    assert_eq!(files[1].path, "src/b.rs");
+    assert_eq!(files[1].subject, "Remove files");
+}
+
+#[test]
+#[serial]
+fn test_gone_files_lists_deletions() {
+    let repo = repo_with_deletions();
+
+    repo.run_git_x(&["gone-files"])
+        .success()
+        .stdout(contains("Deleted files (2)"))
+        .stdout(contains("src/legacy/parser.rs"))
+        .stdout(contains("Drop legacy parser"))
+        .stdout(contains("docs/old.md"));
+}
+
+#[test]
+#[serial]
+fn test_gone_files_fuzzy_query() {
+    let repo = repo_with_deletions();
+
+    repo.run_git_x(&["gone-files", "lgcyprs"])
+        .success()
+        .stdout(contains("src/legacy/parser.rs"))
+        .stdout(contains("docs/old.md").not());
+
+    repo.run_git_x(&["gone-files", "nothing-like-this"])
+        .success()
+        .stdout(contains("No deleted files match"));
+}
+
+#[test]
+#[serial]
+fn test_gone_files_restore() {
+    let repo = repo_with_deletions();
+
+    repo.run_git_x(&["gone-files", "parser", "--restore"])
+        .success()
+        .stdout(contains("Restored"));
+
+    let restored = std::fs::read_to_string(repo.path().join("src/legacy/parser.rs")).unwrap();
+    assert_eq!(restored, "fn parse() {}\n");
+
+    // Restored files are no longer reported as gone
+    repo.run_git_x(&["gone-files"])
+        .success()
+        .stdout(contains("Deleted files (1)"))
+        .stdout(contains("src/legacy/parser.rs").not());
+}
+
+#[test]
+#[serial]
+fn test_gone_files_restore_requires_unique_match() {
+    let repo = repo_with_deletions();
+
+    repo.run_git_x(&["gone-files", "s", "--restore"])
+        .success()
+        .stderr(contains("be more specific"));
+}
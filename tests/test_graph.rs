@@ -2,6 +2,7 @@ use serial_test::serial;
 mod common;
 
 use common::basic_repo;
+use predicates::prelude::*;
 
 #[test]
 #[serial]
@@ -79,3 +80,100 @@ fn test_graph_run_function_in_non_git_directory() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+/// `main` with `feature` merged in as GitHub would, and an unmerged `other` branch
+fn merged_repo() -> common::TestRepo {
+    let repo = common::repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("login.rs", "form", "Work on the login form");
+    repo.checkout_branch("main");
+    repo.add_commit("main.txt", "main", "Mainline change");
+    std::process::Command::new("git")
+        .args([
+            "merge",
+            "--no-ff",
+            "feature",
+            "-m",
+            "Merge pull request #12 from acme/feature",
+            "-m",
+            "Add login form",
+        ])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    repo.create_branch("other");
+    repo.add_commit("other.txt", "other", "Unmerged experiment");
+    repo.checkout_branch("main");
+    repo
+}
+
+#[test]
+fn test_merge_entry_parse() {
+    use git_x::commands::analysis::MergeEntry;
+
+    let github = MergeEntry::parse(
+        "a1b2c3d",
+        "2026-10-01",
+        "Merge pull request #12 from acme/feature/login",
+        "Add login form\n",
+    );
+    assert_eq!(github.request.as_deref(), Some("#12"));
+    assert_eq!(github.title, "Add login form");
+    assert_eq!(github.describe(), "#12 Add login form (feature/login)");
+
+    let gitlab = MergeEntry::parse(
+        "d4e5f6a",
+        "2026-10-02",
+        "Merge branch 'fix-crash' into 'main'",
+        "Fix crash on startup\n\nSee merge request group/project!34",
+    );
+    assert_eq!(gitlab.describe(), "!34 Fix crash on startup (fix-crash)");
+
+    // Without a request the subject is kept, and the branch it names isn't repeated
+    let plain = MergeEntry::parse("b7c8d9e", "2026-10-03", "Merge branch 'hotfix'", "");
+    assert_eq!(plain.request, None);
+    assert_eq!(plain.describe(), "Merge branch 'hotfix'");
+}
+
+#[test]
+#[serial]
+fn test_graph_first_parent_hides_feature_commits() {
+    let repo = merged_repo();
+
+    repo.run_git_x(&["graph"])
+        .success()
+        .stdout(predicates::str::contains("Work on the login form"))
+        .stdout(predicates::str::contains("Unmerged experiment"));
+
+    repo.run_git_x(&["graph", "--first-parent"])
+        .success()
+        .stdout(predicates::str::contains("Merge pull request #12"))
+        .stdout(predicates::str::contains("Mainline change"))
+        .stdout(predicates::str::contains("Work on the login form").not())
+        .stdout(predicates::str::contains("Unmerged experiment").not());
+
+    repo.run_git_x(&["color-graph", "--first-parent", "--branch", "other"])
+        .success()
+        .stdout(predicates::str::contains("Unmerged experiment"))
+        .stdout(predicates::str::contains("Work on the login form").not());
+}
+
+#[test]
+#[serial]
+fn test_graph_merges_only_lists_pull_requests() {
+    let repo = merged_repo();
+
+    repo.run_git_x(&["graph", "--merges-only"])
+        .success()
+        .stdout(predicates::str::contains("Merges into main"))
+        .stdout(predicates::str::contains("#12 Add login form (feature)"))
+        .stdout(predicates::str::contains("Mainline change").not());
+
+    repo.run_git_x(&["graph", "--merges-only", "--branch", "feature"])
+        .success()
+        .stdout(predicates::str::contains("No merges"));
+
+    repo.run_git_x(&["graph", "--branch=-x"])
+        .success()
+        .stderr(predicates::str::contains("❌"));
+}
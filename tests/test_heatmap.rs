@@ -0,0 +1,73 @@
+use serial_test::serial;
+mod common;
+
+use chrono::NaiveDate;
+use common::basic_repo;
+use git_x::commands::analysis::ActivityHeatmap;
+use predicates::str::contains;
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn test_heatmap_counts_days_in_range() {
+    let dates = "2025-03-03\n2025-03-03\n2025-03-04\n2025-03-06\n2025-02-01\nnot a date\n";
+    let heatmap = ActivityHeatmap::from_dates(dates, date("2025-03-01"), date("2025-03-31"));
+
+    assert_eq!(heatmap.total(), 4);
+    assert_eq!(heatmap.counts.get(&date("2025-03-03")), Some(&2));
+    assert_eq!(heatmap.busiest(), Some((date("2025-03-03"), 2)));
+    assert_eq!(heatmap.longest_streak(), 2);
+}
+
+#[test]
+fn test_heatmap_levels() {
+    assert_eq!(ActivityHeatmap::level(0, 10), '·');
+    assert_eq!(ActivityHeatmap::level(1, 10), '░');
+    assert_eq!(ActivityHeatmap::level(5, 10), '▒');
+    assert_eq!(ActivityHeatmap::level(7, 10), '▓');
+    assert_eq!(ActivityHeatmap::level(10, 10), '█');
+}
+
+#[test]
+fn test_heatmap_render_grid() {
+    // 2025-03-03 is a Monday, the range covers exactly two weeks
+    let dates = "2025-03-03\n2025-03-03\n2025-03-12\n";
+    let heatmap = ActivityHeatmap::from_dates(dates, date("2025-03-03"), date("2025-03-16"));
+    let rendered = heatmap.render();
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!(lines[0], "    Mar");
+    assert_eq!(lines[1], "Mon █·");
+    assert_eq!(lines[3], "Wed ·▒");
+    assert_eq!(lines[7], "Sun ··");
+    assert!(lines[8].contains("Less · ░ ▒ ▓ █ More"));
+}
+
+#[test]
+#[serial]
+fn test_heatmap_command_filters() {
+    let repo = basic_repo();
+    std::fs::create_dir_all(repo.path().join("docs")).unwrap();
+    repo.add_commit("docs/guide.md", "# Guide\n", "Add guide");
+
+    repo.run_git_x(&["heatmap"])
+        .success()
+        .stdout(contains("Commit activity on HEAD since 1 year ago"))
+        .stdout(contains("2 commit(s)"))
+        .stdout(contains("Less · ░ ▒ ▓ █ More"));
+
+    repo.run_git_x(&["heatmap", "--since", "1 month ago", "docs"])
+        .success()
+        .stdout(contains("in docs"))
+        .stdout(contains("1 commit(s)"));
+
+    repo.run_git_x(&["heatmap", "--author", "Somebody Else"])
+        .success()
+        .stdout(contains("No commits"));
+
+    repo.run_git_x(&["heatmap", "--branch", "no-such-branch"])
+        .success()
+        .stderr(contains("Unknown branch 'no-such-branch'"));
+}
@@ -0,0 +1,139 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::hooks::{CheckOutcome, CiManifest, RunCiCommand};
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::time::Duration;
+
+#[test]
+fn test_ci_manifest_parse() {
+    let manifest = CiManifest::parse(
+        r#"
+[checks]
+fmt = "cargo fmt --check"
+test = { run = "cargo test", dir = "crates/core" }
+"#,
+    )
+    .unwrap();
+    let names: Vec<&str> = manifest.checks.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["fmt", "test"]);
+    assert_eq!(manifest.checks[1].run, "cargo test");
+    assert_eq!(manifest.checks[1].dir.as_deref(), Some("crates/core"));
+
+    let selected = manifest.clone().select(&["test".to_string()]).unwrap();
+    assert_eq!(selected.checks.len(), 1);
+    let unknown = manifest.select(&["lint".to_string()]).unwrap_err();
+    assert!(unknown.to_string().contains("it defines fmt, test"));
+
+    assert!(CiManifest::parse("[other]\na = 1").is_err());
+    assert!(CiManifest::parse("[checks]").is_err());
+    assert!(
+        CiManifest::parse("[checks]\nfmt = 3")
+            .unwrap_err()
+            .to_string()
+            .contains("Check 'fmt' needs a command")
+    );
+}
+
+#[test]
+fn test_run_ci_render() {
+    let outcome = |name: &str, code: Option<i32>, secs: u64, output: &str| CheckOutcome {
+        name: name.to_string(),
+        code,
+        duration: Duration::from_secs(secs),
+        output: output.to_string(),
+    };
+    let long_output: String = (1..=30).map(|i| format!("line {i}\n")).collect();
+    let rendered = RunCiCommand::render(
+        ".git-x/ci.toml",
+        &[
+            outcome("fmt", Some(0), 1, "ignored"),
+            outcome("clippy", Some(101), 4, &long_output),
+        ],
+        Duration::from_secs(4),
+    );
+
+    assert!(rendered.contains("🧪 Ran 2 check(s) from .git-x/ci.toml"));
+    assert!(rendered.contains("✅ fmt"));
+    assert!(rendered.contains("❌ clippy     4.0s  (exit status 101)"));
+    assert!(rendered.contains("── clippy ──"));
+    assert!(rendered.contains("… 10 earlier line(s)"));
+    assert!(rendered.contains("line 30"));
+    assert!(!rendered.contains("line 10\n"));
+    assert!(!rendered.contains("ignored"));
+    assert!(
+        rendered
+            .contains("❌ 1 of 2 check(s) failed in 4.0s (5.0s of checks run in parallel): clippy")
+    );
+}
+
+#[test]
+#[serial]
+fn test_hooks_run_ci_command() {
+    let repo = repo_with_branch("main");
+    std::fs::create_dir(repo.path().join(".git-x")).unwrap();
+    std::fs::create_dir(repo.path().join("sub")).unwrap();
+    std::fs::write(
+        repo.path().join(".git-x/ci.toml"),
+        "[checks]\nok = \"echo all good\"\nwhere = { run = \"pwd\", dir = \"sub\" }\n",
+    )
+    .unwrap();
+
+    repo.run_git_x(&["hooks", "run-ci"])
+        .success()
+        .stdout(contains("✅ All 2 check(s) passed"));
+
+    std::fs::write(
+        repo.path().join(".git-x/ci.toml"),
+        "[checks]\nok = \"true\"\nbroken = \"echo something broke >&2; exit 3\"\n",
+    )
+    .unwrap();
+    repo.run_git_x(&["hooks", "run-ci"])
+        .failure()
+        .stdout(contains("(exit status 3)"))
+        .stdout(contains("something broke"))
+        .stdout(contains("1 of 2 check(s) failed"));
+    repo.run_git_x(&["hooks", "run-ci", "ok"])
+        .success()
+        .stdout(contains("Ran 1 check(s)"));
+    repo.run_git_x(&["hooks", "run-ci", "missing"])
+        .failure()
+        .stderr(contains("No check named 'missing'"));
+}
+
+#[test]
+#[serial]
+fn test_hooks_run_ci_without_manifest() {
+    let repo = repo_with_branch("main");
+
+    repo.run_git_x(&["hooks", "run-ci"])
+        .failure()
+        .stderr(contains("No CI manifest at"));
+}
+
+#[test]
+#[serial]
+fn test_hooks_run_ci_install_hook() {
+    let repo = repo_with_branch("main");
+    let hook = repo.path().join(".git/hooks/pre-push");
+    std::fs::write(&hook, "#!/bin/sh\necho existing\n").unwrap();
+
+    repo.run_git_x(&["hooks", "run-ci", "--install-hook"])
+        .success()
+        .stdout(contains("Installed pre-push hook"));
+    let content = std::fs::read_to_string(&hook).unwrap();
+    assert!(content.starts_with("#!/bin/sh\n# Added by git-x"));
+    assert!(content.contains("git x hooks run-ci || exit 1"));
+    assert!(content.contains("echo existing"));
+
+    repo.run_git_x(&["hooks", "run-ci", "--install-hook"])
+        .success()
+        .stdout(contains("already run by the pre-push hook"));
+    assert!(
+        predicate::str::contains("Added by git-x")
+            .count(1)
+            .eval(&std::fs::read_to_string(&hook).unwrap())
+    );
+}
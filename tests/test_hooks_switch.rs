@@ -0,0 +1,97 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::hooks::SwitchHooks;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+#[test]
+fn test_switch_hooks_parse() {
+    let hooks = SwitchHooks::parse(
+        r#"
+[hooks.switch]
+"*" = "direnv allow"
+"release/*" = ["nvm use 18", "echo migrate"]
+"#,
+    )
+    .unwrap();
+    assert_eq!(hooks.hooks.len(), 2);
+    assert_eq!(
+        hooks.commands_for("release/1.2"),
+        [
+            ("*", "direnv allow"),
+            ("release/*", "nvm use 18"),
+            ("release/*", "echo migrate")
+        ]
+    );
+    assert_eq!(hooks.commands_for("main"), [("*", "direnv allow")]);
+
+    assert_eq!(
+        SwitchHooks::parse("[checks]\nfmt = \"cargo fmt\"").unwrap(),
+        SwitchHooks::default()
+    );
+    assert!(
+        SwitchHooks::parse("[hooks.switch]\nmain = 1")
+            .unwrap_err()
+            .to_string()
+            .contains("Switch hook 'main' in .git-x.toml needs a command")
+    );
+    assert!(SwitchHooks::parse("[hooks]\nswitch = \"echo\"").is_err());
+}
+
+#[test]
+#[serial]
+fn test_switch_hooks_run_on_switch() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("release/1.0");
+    repo.checkout_branch("main");
+    std::fs::write(
+        repo.path().join(".git-x.toml"),
+        r#"
+[hooks.switch]
+"release/*" = ["echo \"node for $GIT_X_BRANCH\"", "exit 3"]
+"#,
+    )
+    .unwrap();
+
+    repo.run_git_x(&["jump", "release/1.0"])
+        .success()
+        .stdout(contains("🪝 echo \"node for $GIT_X_BRANCH\""))
+        .stdout(contains("   node for release/1.0"))
+        .stdout(contains("⚠️  Switch hook 'exit 3' failed (exit status 3)"));
+
+    repo.run_git_x(&["jump", "main", "--no-hooks"])
+        .success()
+        .stdout(contains("🪝").not());
+
+    repo.run_git_x(&["new", "release/2.0"])
+        .success()
+        .stdout(contains("node for release/2.0"));
+
+    repo.run_git_x(&["hooks", "switch", "release/3.0", "--dry-run"])
+        .success()
+        .stdout(contains(
+            "Switching to 'release/3.0' would run 2 command(s)",
+        ))
+        .stdout(contains("   exit 3  (release/*)"))
+        .stdout(contains("node for release/3.0").not());
+
+    repo.run_git_x(&["hooks", "switch", "main"])
+        .success()
+        .stdout(contains("No switch hooks in .git-x.toml match 'main'"));
+}
+
+#[test]
+#[serial]
+fn test_switch_hooks_broken_config() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.checkout_branch("main");
+    std::fs::write(repo.path().join(".git-x.toml"), "[hooks.switch\n").unwrap();
+
+    repo.run_git_x(&["jump", "feature"])
+        .success()
+        .stdout(contains("🔀 Switched to 'feature'"))
+        .stdout(contains("⚠️  Switch hooks not run"));
+}
@@ -0,0 +1,147 @@
+use serial_test::serial;
+mod common;
+
+use git_x::commands::repository::HotfixCommand;
+use git_x::core::traits::Command as CommandTrait;
+use predicates::str::contains;
+
+/// Repo with a `v1.0.0` release tag and a fix committed after it
+fn released_repo() -> (common::TestRepo, String) {
+    let repo = common::basic_repo();
+    repo.add_commit("VERSION", "1.0.0\n", "release 1.0.0");
+    repo.git(&["tag", "v1.0.0"]);
+    repo.add_commit("app.txt", "fixed", "fix crash on startup");
+    let fix = repo.git(&["rev-parse", "HEAD"]);
+    (repo, fix)
+}
+
+#[test]
+#[serial]
+fn test_hotfix_from_latest_release_tag() {
+    let (repo, fix) = released_repo();
+
+    repo.run_git_x(&["hotfix", "crash", &fix])
+        .success()
+        .stdout(contains("hotfix/crash"))
+        .stdout(contains("latest release tag"))
+        .stdout(contains("🍒 Cherry-picked"))
+        .stdout(contains("git merge --no-ff hotfix/crash"));
+
+    assert_eq!(
+        repo.git(&["rev-parse", "--abbrev-ref", "HEAD"]),
+        "hotfix/crash"
+    );
+    assert_eq!(
+        repo.git(&["rev-parse", "HEAD~1"]),
+        repo.git(&["rev-parse", "v1.0.0"])
+    );
+    assert!(repo.path().join("app.txt").exists());
+}
+
+#[test]
+#[serial]
+fn test_hotfix_bumps_configured_version_file() {
+    let (repo, fix) = released_repo();
+    repo.git(&["config", "git-x.hotfix.versionFile", "VERSION"]);
+
+    repo.run_git_x(&["hotfix", "crash", &fix])
+        .success()
+        .stdout(contains("🔖 Bumped version in VERSION: 1.0.0 → 1.0.1"))
+        .stdout(contains("git tag v1.0.1"));
+
+    let content = std::fs::read_to_string(repo.path().join("VERSION")).unwrap();
+    assert_eq!(content, "1.0.1\n");
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"]),
+        "Bump version to 1.0.1"
+    );
+}
+
+#[test]
+#[serial]
+fn test_hotfix_falls_back_to_production_branch() {
+    let repo = common::basic_repo();
+    repo.create_branch("production");
+    repo.add_commit("prod.txt", "prod", "production only");
+    repo.git(&["config", "git-x.hotfix.base", "production"]);
+
+    repo.run_git_x(&["hotfix", "urgent"])
+        .success()
+        .stdout(contains("production branch"));
+
+    assert!(repo.path().join("prod.txt").exists());
+}
+
+#[test]
+#[serial]
+fn test_hotfix_rejects_existing_branch() {
+    let (repo, _) = released_repo();
+    repo.git(&["branch", "hotfix/crash"]);
+
+    repo.run_git_x(&["hotfix", "crash"])
+        .success()
+        .stderr(contains("already exists"));
+}
+
+#[test]
+#[serial]
+fn test_hotfix_command_direct() {
+    let (repo, _) = released_repo();
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let cmd = HotfixCommand::new(
+        "direct".parse().unwrap(),
+        vec![],
+        Some("v1.0.0".parse().unwrap()),
+    );
+    assert_eq!(cmd.name(), "hotfix");
+    let result = cmd.execute();
+
+    // Restore original directory
+    let _ = std::env::set_current_dir(&original_dir);
+
+    let output = result.unwrap();
+    assert!(output.contains("requested base"));
+    assert!(output.contains("Hotfix branch 'hotfix/direct' is ready"));
+}
+
+#[test]
+#[serial]
+fn test_hotfix_conflict_rolls_back() {
+    let repo = common::basic_repo();
+    repo.add_commit("app.txt", "one\n", "release 1.0.0");
+    repo.git(&["tag", "v1.0.0"]);
+    repo.add_commit("app.txt", "two\n", "rework app");
+    repo.add_commit("app.txt", "three\n", "fix app");
+    let fix = repo.git(&["rev-parse", "HEAD"]);
+    let branch = repo.git(&["branch", "--show-current"]);
+
+    repo.run_git_x(&["hotfix", "app", &fix])
+        .success()
+        .stderr(contains("hotfix failed, rolled back"))
+        .stderr(contains("Ran 'git cherry-pick --abort'"))
+        .stderr(contains(format!("Switched back to '{branch}'")))
+        .stderr(contains("Deleted branch 'hotfix/app'"))
+        .stderr(contains("Cherry-pick of"));
+
+    assert_eq!(repo.git(&["branch", "--show-current"]), branch);
+    assert_eq!(repo.git(&["rev-parse", "HEAD"]), fix);
+    assert_eq!(repo.git(&["branch", "--list", "hotfix/app"]), "");
+    assert_eq!(repo.git(&["status", "--porcelain"]), "");
+}
+
+#[test]
+#[serial]
+fn test_hotfix_merges_back_into_configured_default_branch() {
+    let (repo, fix) = released_repo();
+    repo.git(&["branch", "-m", "trunk"]);
+    repo.git(&["config", "init.defaultBranch", "trunk"]);
+
+    repo.run_git_x(&["hotfix", "crash", &fix])
+        .success()
+        .stdout(contains(
+            "git checkout trunk && git merge --no-ff hotfix/crash",
+        ));
+}
@@ -0,0 +1,161 @@
+use git_x::core::export::ExportValue;
+use git_x::core::i18n::{Language, Message};
+use predicates::prelude::*;
+use predicates::str::contains;
+use serial_test::serial;
+use std::collections::BTreeSet;
+use std::process::Command;
+
+mod common;
+
+fn placeholders(template: &str) -> BTreeSet<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[test]
+fn test_catalogs_match_english() {
+    let english = Language::English.catalog();
+    for language in Language::ALL {
+        for (key, template) in language.catalog() {
+            let source = english
+                .iter()
+                .find(|(english_key, _)| english_key == key)
+                .unwrap_or_else(|| panic!("{} has unknown key {key}", language.code()));
+            assert_eq!(
+                placeholders(template),
+                placeholders(source.1),
+                "{} placeholders differ for {key}",
+                language.code()
+            );
+        }
+    }
+
+    let german: BTreeSet<&str> = Language::German.catalog().iter().map(|(k, _)| *k).collect();
+    let missing: Vec<&str> = english
+        .iter()
+        .map(|(key, _)| *key)
+        .filter(|key| *key != "verbatim" && !german.contains(key))
+        .collect();
+    assert!(missing.is_empty(), "untranslated keys: {missing:?}");
+}
+
+#[test]
+fn test_language_from_locale() {
+    assert_eq!(Language::from_locale("de"), Some(Language::German));
+    assert_eq!(Language::from_locale("de_AT.UTF-8"), Some(Language::German));
+    assert_eq!(Language::from_locale("en-US"), Some(Language::English));
+    assert_eq!(Language::from_locale("C.UTF-8"), Some(Language::English));
+    assert_eq!(Language::from_locale("POSIX"), Some(Language::English));
+    assert_eq!(Language::from_locale("fr_FR.UTF-8"), None);
+    assert_eq!(Language::from_locale(""), None);
+}
+
+#[test]
+fn test_message_render() {
+    let message = Message::new("health.drifted")
+        .arg("branch", "feature/{count}")
+        .nested(
+            "drift",
+            Message::new("drift")
+                .arg("behind", 3)
+                .arg("days", 20)
+                .arg("baseline", "main"),
+        );
+    assert_eq!(
+        message.english(),
+        "⚠️  feature/{count} has drifted: 3 commit(s) and 20 day(s) behind main"
+    );
+    assert_eq!(
+        message.render(Language::German),
+        "⚠️  feature/{count} ist veraltet: 3 Commit(s) und 20 Tag(e) hinter main"
+    );
+
+    assert_eq!(Message::verbatim("a.txt").render(Language::German), "a.txt");
+    assert_eq!(Message::new("no.such.key").english(), "no.such.key");
+    assert_eq!(
+        Message::new("info.staged").english(),
+        "📋 Staged files: {count} file(s)"
+    );
+}
+
+#[test]
+fn test_message_value_round_trip() {
+    let message = Message::new("health.drifted")
+        .arg("branch", "feature")
+        .nested("drift", Message::new("drift").arg("behind", 3));
+    let stored = ExportValue::parse_json(&message.to_value().to_json()).unwrap();
+    assert_eq!(Message::from_value(&stored), Some(message));
+
+    // Keys and placeholders this version doesn't know aren't read back
+    let unknown = Message::new("no.such.key").to_value();
+    assert_eq!(Message::from_value(&unknown), None);
+    let renamed = Message::new("info.staged").arg("files", 2).to_value();
+    assert_eq!(Message::from_value(&renamed), None);
+}
+
+#[test]
+#[serial]
+fn test_language_resolve() {
+    let repo = common::basic_repo();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    Command::new("git")
+        .args(["config", "git-x.language", "de"])
+        .output()
+        .unwrap();
+    let configured = Language::resolve();
+    Command::new("git")
+        .args(["config", "git-x.language", "klingon"])
+        .output()
+        .unwrap();
+    let unsupported = Language::resolve();
+
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert_eq!(configured, Language::German);
+    assert!(Language::ALL.contains(&unsupported));
+}
+
+#[test]
+#[serial]
+fn test_localized_commands() {
+    let repo = common::repo_with_commits(2);
+
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("Current branch:"));
+
+    Command::new("git")
+        .args(["config", "git-x.language", "de"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("📍 Aktueller Branch:"))
+        .stdout(contains("Arbeitsverzeichnis: Sauber"));
+    repo.run_git_x(&["health"])
+        .success()
+        .stdout(contains("🏥 Zustand des Repositorys"))
+        .stdout(contains("✅ Git-Konfiguration: OK"));
+    repo.run_git_x(&["summary"])
+        .success()
+        .stdout(contains("📊 Zusammenfassung des Repositorys"))
+        .stdout(contains("Top-Mitwirkende (gesamt)"));
+
+    // Machine-readable output stays English
+    repo.run_git_x(&["health", "--json"])
+        .success()
+        .stdout(contains(r#""check":"Git configuration""#))
+        .stdout(contains("⚠️  No remotes configured"))
+        .stdout(contains("Zustand").not());
+    repo.run_git_x(&["summary", "--json"])
+        .success()
+        .stdout(contains("\"commits\":2"));
+}
@@ -0,0 +1,180 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::commit::SourceCommit;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// A repository with `svc/pay` touched by three commits, one of them on a merged branch,
+/// and `other` touched by two more
+fn source_repo() -> common::TestRepo {
+    let repo = repo_with_branch("main");
+    std::fs::create_dir_all(repo.path().join("svc/pay")).unwrap();
+    std::fs::create_dir(repo.path().join("other")).unwrap();
+    std::fs::write(repo.path().join("svc/pay/a.txt"), "a\n").unwrap();
+    repo.git(&["add", "-A"]);
+    std::process::Command::new("git")
+        .args(["commit", "-q", "-m", "Add payments"])
+        .current_dir(repo.path())
+        .env("GIT_AUTHOR_NAME", "Alice")
+        .env("GIT_AUTHOR_EMAIL", "alice@example.com")
+        .env("GIT_AUTHOR_DATE", "2020-02-03T04:05:06+0000")
+        .output()
+        .unwrap();
+    repo.add_commit("other/o.txt", "o\n", "Unrelated change");
+    repo.create_branch("feature");
+    repo.add_commit("svc/pay/b.txt", "b\n", "Add b");
+    repo.checkout_branch("main");
+    repo.add_commit("svc/pay/c.txt", "c\n", "Add c");
+    repo.git(&["merge", "-q", "--no-ff", "feature", "-m", "Merge feature"]);
+    repo.add_commit("other/x.txt", "x\n", "Another unrelated change");
+    repo
+}
+
+#[test]
+fn test_source_commit_parse_log() {
+    let log = "aaa\x00\x00Alice\x00a@x\x001580702706 +0000\x00Add payments\n\nBody\n\x1e\n\
+               bbb\x00aaa\x00Bob\x00b@x\x001580702800 +0100\x00Add b\n\x1e\n\
+               ccc\x00aaa bbb\x00Bob\x00b@x\x001580702900 +0100\x00Merge\n\x1e\n";
+    let commits = SourceCommit::parse_log(log);
+
+    assert_eq!(commits.len(), 3);
+    assert_eq!(commits[0].sha, "aaa");
+    assert!(commits[0].parents.is_empty());
+    assert_eq!(commits[0].author_name, "Alice");
+    assert_eq!(commits[0].author_date, "1580702706 +0000");
+    assert_eq!(commits[0].message, "Add payments\n\nBody");
+    assert_eq!(commits[0].subject(), "Add payments");
+    assert_eq!(commits[2].parents, vec!["aaa", "bbb"]);
+}
+
+#[test]
+#[serial]
+fn test_import_commits_dry_run_lists_commits_touching_the_path() {
+    let source = source_repo();
+    let target = repo_with_branch("main");
+    let head = target.git(&["rev-parse", "HEAD"]);
+
+    target
+        .run_git_x(&[
+            "import-commits",
+            &source.path().display().to_string(),
+            "--path",
+            "svc/pay",
+            "--into",
+            "services/payments",
+            "--dry-run",
+        ])
+        .success()
+        .stdout(contains(
+            "Would create 4 commit(s) (1 merge(s)) in services/payments/",
+        ))
+        .stdout(contains("Alice — Add payments"))
+        .stdout(contains("Merge feature"))
+        .stdout(contains("Unrelated").not());
+
+    assert_eq!(target.git(&["rev-parse", "HEAD"]), head);
+    assert!(target.git(&["for-each-ref", "refs/git-x"]).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_import_commits_transplants_history_with_authorship() {
+    let source = source_repo();
+    let target = repo_with_branch("main");
+    let head = target.git(&["rev-parse", "HEAD"]);
+
+    target
+        .run_git_x(&[
+            "import-commits",
+            &source.path().display().to_string(),
+            "--path",
+            "svc/pay/",
+            "--into",
+            "services/payments",
+        ])
+        .success()
+        .stdout(contains("Imported 4 commit(s) into services/payments/"));
+
+    for file in ["a.txt", "b.txt", "c.txt"] {
+        assert!(target.path().join("services/payments").join(file).exists());
+    }
+    assert!(!target.path().join("services/payments/other").exists());
+    assert!(target.git(&["status", "--porcelain"]).is_empty());
+
+    let first = target.git(&[
+        "log",
+        "--reverse",
+        "--format=%an <%ae> %aI",
+        &format!("{head}..HEAD"),
+    ]);
+    assert_eq!(
+        first.lines().next(),
+        Some("Alice <alice@example.com> 2020-02-03T04:05:06+00:00")
+    );
+    assert_eq!(
+        target.git(&["rev-list", "--count", "--merges", "HEAD"]),
+        "1"
+    );
+    assert!(
+        std::process::Command::new("git")
+            .args(["merge-base", "--is-ancestor", &head, "HEAD"])
+            .current_dir(target.path())
+            .status()
+            .unwrap()
+            .success()
+    );
+    assert!(
+        target
+            .git(&["log", "-1", "--format=%B", "HEAD"])
+            .contains("Imported-from: ")
+    );
+    assert!(target.git(&["for-each-ref", "refs/git-x"]).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_import_commits_whole_repository_defaults_into_its_name() {
+    let source = source_repo();
+    let target = repo_with_branch("main");
+    let name = source
+        .path()
+        .canonicalize()
+        .unwrap()
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+
+    target
+        .run_git_x(&["import-commits", &source.path().display().to_string()])
+        .success()
+        .stdout(contains(format!("into {name}/")));
+
+    assert!(target.path().join(&name).join("other/x.txt").exists());
+    assert!(target.path().join(&name).join("svc/pay/b.txt").exists());
+}
+
+#[test]
+#[serial]
+fn test_import_commits_refuses_existing_destination_and_missing_path() {
+    let source = source_repo();
+    let target = repo_with_branch("main");
+    std::fs::create_dir(target.path().join("svc")).unwrap();
+    target.add_commit("svc/readme.txt", "taken\n", "Add svc");
+    let source = source.path().display().to_string();
+
+    target
+        .run_git_x(&["import-commits", &source, "--path", "svc"])
+        .stderr(contains("'svc' already exists"));
+    target
+        .run_git_x(&["import-commits", &source, "--path", "nope", "--into", "x"])
+        .stderr(contains("'nope' is not a directory"));
+    target
+        .run_git_x(&["import-commits", "/nonexistent/repo", "--into", "x"])
+        .stderr(contains("is not a git repository"));
+    target
+        .run_git_x(&["import-commits", &source, "--into", "../outside"])
+        .stderr(contains("Invalid destination"));
+}
@@ -1,10 +1,11 @@
 use serial_test::serial;
 mod common;
 
-use common::{repo_with_branch, repo_with_remote_ahead};
+use common::{basic_repo, repo_with_branch, repo_with_remote_ahead};
 use git_x::commands::repository::InfoCommand;
 use git_x::core::output::Format;
 use git_x::core::traits::Command;
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 
 #[test]
@@ -167,3 +168,114 @@ fn test_info_command_with_details() {
     assert!(output.contains("Working directory:"));
     assert!(output.contains("Staged files:"));
 }
+
+#[test]
+#[serial]
+fn test_info_compares_all_configured_baselines() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("release/current");
+    repo.add_commit("release.txt", "release", "Release commit");
+    repo.checkout_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("feature.txt", "feature", "Feature commit");
+
+    std::process::Command::new("git")
+        .args([
+            "config",
+            "git-x.info.baselines",
+            "main,release/current,origin/missing",
+        ])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("📊 Baselines:"))
+        .stdout(contains("Baseline"))
+        .stdout(contains("main                 1       0  ahead"))
+        .stdout(contains("release/current      1       1  diverged"))
+        .stdout(contains("origin/missing").not());
+}
+
+#[test]
+#[serial]
+fn test_info_command_baseline_table_direct() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("feature.txt", "feature", "Feature commit");
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+    let result = InfoCommand::new().execute();
+    let _ = std::env::set_current_dir(&original_dir);
+
+    let output = result.unwrap();
+    assert!(output.contains("📊 Baselines:"));
+    assert!(output.contains("ahead"));
+}
+
+#[test]
+#[serial]
+fn test_info_json() {
+    let repo = repo_with_branch("test-branch");
+    std::fs::write(repo.path().join("staged.txt"), "new").unwrap();
+    std::process::Command::new("git")
+        .args(["add", "staged.txt"])
+        .current_dir(repo.path())
+        .status()
+        .unwrap();
+
+    repo.run_git_x(&["info", "--json"])
+        .success()
+        .stdout(contains(
+            r#""branch":"test-branch","detached_at":null,"upstream":null"#,
+        ))
+        .stdout(contains(r#""clean":false,"staged_files":["staged.txt"]"#))
+        .stdout(contains("Repository:").not());
+}
+
+#[test]
+#[serial]
+fn test_info_flags_drift_from_default_branch() {
+    let repo = basic_repo();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(repo.path())
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    let base = git(&["rev-parse", "--abbrev-ref", "HEAD"]);
+    repo.create_branch("feature");
+    repo.checkout_branch(&base);
+    for i in 0..3 {
+        repo.add_commit("base.txt", &format!("{i}"), &format!("base {i}"));
+    }
+    repo.checkout_branch("feature");
+
+    // Within the default limits
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("Drifting").not());
+
+    git(&["config", "git-x.drift.maxCommits", "2"]);
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains(format!(
+            "Drifting: 3 commit(s) and 0 day(s) behind {base} (limit: 2 commits or 14 days)"
+        )))
+        .stdout(contains(format!("git rebase {base}")));
+    repo.run_git_x(&["info", "--json"])
+        .success()
+        .stdout(contains(format!(
+            r#""drift":{{"baseline":"{base}","behind":3,"days":0,"drifting":true}}"#
+        )));
+
+    // The default branch itself never drifts
+    repo.checkout_branch(&base);
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("Drifting").not());
+}
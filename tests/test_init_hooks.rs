@@ -0,0 +1,175 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::core::conventional::{CommitLinter, ConventionalHeader, clean_message};
+use predicates::str::contains;
+use std::process::Command;
+
+fn messages(message: &str) -> Vec<String> {
+    CommitLinter::default()
+        .lint(message)
+        .into_iter()
+        .map(|violation| violation.message)
+        .collect()
+}
+
+#[test]
+fn test_parse_conventional_header() {
+    let header = ConventionalHeader::parse("feat(parser)!: support arrays").unwrap();
+    assert_eq!(header.kind, "feat");
+    assert_eq!(header.scope.as_deref(), Some("parser"));
+    assert!(header.breaking);
+    assert_eq!(header.description, "support arrays");
+
+    let plain = ConventionalHeader::parse("fix: handle empty input").unwrap();
+    assert_eq!(plain.scope, None);
+    assert!(!plain.breaking);
+
+    assert!(ConventionalHeader::parse("Added stuff").is_none());
+    assert!(ConventionalHeader::parse("feat(): empty scope").is_none());
+    assert!(ConventionalHeader::parse("Feat: capitalised type").is_none());
+    assert!(ConventionalHeader::parse("feat:missing space").is_none());
+}
+
+#[test]
+fn test_lint_accepts_valid_messages() {
+    assert!(messages("feat: add heatmap").is_empty());
+    assert!(messages("fix(cli)!: rename flag\n\nBREAKING CHANGE: --old is gone").is_empty());
+    // Messages git writes itself are left alone
+    assert!(messages("Merge branch 'main' into feature").is_empty());
+    assert!(messages("fixup! feat: add heatmap").is_empty());
+    // So are WIP commits
+    assert!(messages("WIP: work in progress on feature\n\nGit-X-WIP: true").is_empty());
+    // Nothing left after comments: git aborts on its own
+    assert!(messages("# Please enter the commit message\n").is_empty());
+}
+
+#[test]
+fn test_lint_reports_each_rule() {
+    assert_eq!(
+        messages("Added stuff"),
+        ["header must look like 'type(scope): description'"]
+    );
+    assert_eq!(messages("feature: add thing"), ["unknown type 'feature'"]);
+    assert_eq!(
+        messages("docs: update readme."),
+        ["description should not end with a period"]
+    );
+    assert_eq!(
+        messages("feat: add thing\nmore detail"),
+        ["leave a blank line between the header and the body"]
+    );
+
+    let long = format!("feat: {}", "x".repeat(80));
+    assert_eq!(
+        messages(&long),
+        ["header is 86 characters, the limit is 72"]
+    );
+}
+
+#[test]
+fn test_lint_extra_types() {
+    let linter = CommitLinter::new(vec!["deps".to_string()]);
+    assert!(linter.lint("deps: bump chrono").is_empty());
+    assert!(linter.types().contains(&"feat".to_string()));
+}
+
+#[test]
+fn test_clean_message_drops_comments_and_verbose_diff() {
+    let raw = "\nfeat: add thing\n\nbody  \n# comment\n\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+    assert_eq!(clean_message(raw), ["feat: add thing", "", "body"]);
+}
+
+#[test]
+#[serial]
+fn test_init_hooks_installs_commit_msg_hook() {
+    let repo = basic_repo();
+    let hook_path = repo.path().join(".git/hooks/commit-msg");
+
+    repo.run_git_x(&["init-hooks"])
+        .success()
+        .stdout(contains("Installed commit-msg hook"));
+    let hook = std::fs::read_to_string(&hook_path).unwrap();
+    assert!(hook.contains("git x init-hooks --check \"$1\""));
+
+    repo.run_git_x(&["init-hooks"])
+        .success()
+        .stdout(contains("already installed"));
+
+    // Someone else's hook is only replaced with --force, and kept as a backup
+    std::fs::write(&hook_path, "#!/bin/sh\nmake lint\n").unwrap();
+    repo.run_git_x(&["init-hooks"])
+        .failure()
+        .stderr(contains("rerun with --force"));
+    repo.run_git_x(&["init-hooks", "--force"])
+        .success()
+        .stdout(contains("Moved the existing hook"));
+    let backup = std::fs::read_to_string(repo.path().join(".git/hooks/commit-msg.backup")).unwrap();
+    assert!(backup.contains("make lint"));
+}
+
+#[test]
+#[serial]
+fn test_init_hooks_check_message_file() {
+    let repo = basic_repo();
+    let message = repo.path().join("MSG");
+
+    std::fs::write(&message, "feat: add thing\n").unwrap();
+    repo.run_git_x(&["init-hooks", "--check", "MSG"])
+        .success()
+        .stdout(contains("follows Conventional Commits"));
+
+    std::fs::write(&message, "Added thing\n").unwrap();
+    repo.run_git_x(&["init-hooks", "--check", "MSG"])
+        .failure()
+        .stderr(contains("line 1: \"Added thing\""))
+        .stderr(contains("Allowed types: feat, fix"))
+        .stderr(contains("GIT_X_SKIP_COMMIT_LINT=1"));
+
+    Command::new("git")
+        .args(["config", "git-x.lint.types", "wip"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    std::fs::write(&message, "wip: halfway there\n").unwrap();
+    repo.run_git_x(&["init-hooks", "--check", "MSG"]).success();
+}
+
+#[test]
+#[serial]
+fn test_init_hooks_rejects_commit_through_git() {
+    let repo = basic_repo();
+    repo.run_git_x(&["init-hooks"]).success();
+
+    // The hook calls `git x`, so the built binary has to be on PATH
+    let bin_dir = assert_cmd::cargo::cargo_bin("git-x")
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let commit = |message: &str, skip: bool| {
+        let mut cmd = assert_cmd::Command::new("git");
+        cmd.args(["commit", "--allow-empty", "-m", message])
+            .current_dir(repo.path())
+            .env("PATH", &path)
+            .env_remove("GIT_X_SKIP_COMMIT_LINT");
+        if skip {
+            cmd.env("GIT_X_SKIP_COMMIT_LINT", "1");
+        }
+        cmd.assert()
+    };
+
+    commit("bad message", false)
+        .failure()
+        .stderr(contains("does not follow Conventional Commits"));
+    commit("chore: empty commit", false).success();
+    commit("bad message", true)
+        .success()
+        // git forwards hook output to stderr
+        .stderr(contains("check skipped"));
+}
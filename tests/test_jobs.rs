@@ -0,0 +1,56 @@
+use git_x::cli::Cli;
+use git_x::core::jobs::Jobs;
+use predicates::str::contains;
+use serial_test::serial;
+use std::process::Command;
+
+mod common;
+
+#[test]
+fn test_jobs_flag_is_global() {
+    use clap::Parser;
+
+    let cli = Cli::try_parse_from(["git-x", "-j", "3", "info"]).unwrap();
+    assert_eq!(cli.jobs, Some(3));
+    let cli = Cli::try_parse_from(["git-x", "summary", "--jobs", "2"]).unwrap();
+    assert_eq!(cli.jobs, Some(2));
+    let cli = Cli::try_parse_from(["git-x", "info"]).unwrap();
+    assert_eq!(cli.jobs, None);
+}
+
+#[test]
+#[serial]
+fn test_jobs_resolve() {
+    let repo = common::basic_repo();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let from_flag = Jobs::resolve(Some(3));
+    let unset = Jobs::resolve(None);
+    let zero = Jobs::resolve(Some(0));
+    Command::new("git")
+        .args(["config", "git-x.jobs", "5"])
+        .output()
+        .unwrap();
+    let from_config = Jobs::resolve(None);
+    let flag_wins = Jobs::resolve(Some(2));
+
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert_eq!(from_flag, 3);
+    assert_eq!(unset, Jobs::available());
+    assert_eq!(zero, Jobs::available());
+    assert_eq!(from_config, 5);
+    assert_eq!(flag_wins, 2);
+}
+
+#[test]
+#[serial]
+fn test_jobs_limit_commands() {
+    let repo = common::repo_with_commits(3);
+
+    repo.run_git_x(&["--jobs", "1", "summary"])
+        .success()
+        .stdout(contains("Repository Summary"));
+    repo.run_git_x(&["-j", "1", "info"]).success();
+}
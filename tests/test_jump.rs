@@ -0,0 +1,96 @@
+use serial_test::serial;
+mod common;
+
+use common::{TestRepo, git, repo_with_branch};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// `main` and `feature` sharing `app.txt`, with `main` checked out
+fn two_branch_repo() -> TestRepo {
+    let repo = repo_with_branch("main");
+    repo.add_commit("app.txt", "app\n", "Add app");
+    git(repo.path(), &["branch", "feature"]);
+    repo
+}
+
+fn read(repo: &TestRepo, file: &str) -> String {
+    std::fs::read_to_string(repo.path().join(file)).unwrap()
+}
+
+#[test]
+#[serial]
+fn test_jump_round_trip() {
+    let repo = two_branch_repo();
+    std::fs::write(repo.path().join("app.txt"), "main wip\n").unwrap();
+
+    repo.run_git_x(&["jump", "feature"])
+        .success()
+        .stdout(contains("Stashed WIP on 'main' (1 file(s))"))
+        .stdout(contains("Switched to 'feature'"))
+        .stdout(contains("Restored").not());
+    assert_eq!(read(&repo, "app.txt"), "app\n");
+    assert!(git(repo.path(), &["stash", "list"]).contains("op=jump branch=main"));
+
+    std::fs::write(repo.path().join("app.txt"), "feature wip\n").unwrap();
+    repo.run_git_x(&["jump", "-"])
+        .success()
+        .stdout(contains("Stashed WIP on 'feature'"))
+        .stdout(contains("Switched to 'main'"))
+        .stdout(contains("Restored WIP stashed on 'main'"));
+    assert_eq!(read(&repo, "app.txt"), "main wip\n");
+
+    // Committed on main in the meantime: feature's WIP is still restored
+    git(repo.path(), &["commit", "--quiet", "-am", "Main work"]);
+    repo.run_git_x(&["jump", "feature"])
+        .success()
+        .stdout(contains("Stashed").not())
+        .stdout(contains("Restored WIP stashed on 'feature'"));
+    assert_eq!(read(&repo, "app.txt"), "feature wip\n");
+    assert!(git(repo.path(), &["stash", "list"]).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_jump_leaves_other_stashes_alone() {
+    let repo = two_branch_repo();
+    repo.checkout_branch("feature");
+    std::fs::write(repo.path().join("app.txt"), "manual\n").unwrap();
+    git(repo.path(), &["stash", "push", "--quiet", "-m", "my own"]);
+    std::fs::write(repo.path().join("app.txt"), "feature wip\n").unwrap();
+    repo.run_git_x(&["jump", "main"]).success();
+
+    repo.run_git_x(&["jump", "feature", "--no-restore"])
+        .success()
+        .stdout(contains("'git stash pop stash@{0}' brings it back"));
+    assert_eq!(read(&repo, "app.txt"), "app\n");
+    assert_eq!(git(repo.path(), &["stash", "list"]).lines().count(), 2);
+
+    repo.run_git_x(&["jump", "main"]).success();
+    repo.run_git_x(&["jump", "feature"])
+        .success()
+        .stdout(contains("Restored WIP stashed on 'feature'"));
+    assert_eq!(read(&repo, "app.txt"), "feature wip\n");
+    assert!(git(repo.path(), &["stash", "list"]).contains("my own"));
+}
+
+#[test]
+#[serial]
+fn test_jump_untracked_and_errors() {
+    let repo = two_branch_repo();
+    std::fs::write(repo.path().join("notes.txt"), "notes\n").unwrap();
+
+    repo.run_git_x(&["jump", "feature", "--include-untracked"])
+        .success()
+        .stdout(contains("Stashed WIP on 'main'"));
+    assert!(!repo.path().join("notes.txt").exists());
+    repo.run_git_x(&["jump", "main"]).success();
+    assert_eq!(read(&repo, "notes.txt"), "notes\n");
+
+    repo.run_git_x(&["jump", "main"])
+        .success()
+        .stdout(contains("Already on 'main'"));
+    repo.run_git_x(&["jump", "missing"])
+        .success()
+        .stderr(contains("Branch 'missing' does not exist"));
+    assert_eq!(read(&repo, "notes.txt"), "notes\n");
+}
@@ -0,0 +1,127 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::repository::{LfsAction, LfsCommand};
+use git_x::core::lfs::{LARGE_FILE_BYTES, Lfs, LfsStatus, POINTER_HEADER};
+use git_x::core::traits::Command;
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+
+const LFS_ATTRIBUTES: &str = "# assets\n*.psd filter=lfs diff=lfs merge=lfs -text\n*.txt text\nmodels/** filter=lfs diff=lfs merge=lfs -text\n";
+
+/// Binary content just over the large-file threshold
+fn large_binary() -> Vec<u8> {
+    vec![0u8; LARGE_FILE_BYTES as usize + 1]
+}
+
+#[test]
+#[serial]
+fn test_lfs_patterns_from_attributes() {
+    assert_eq!(
+        Lfs::patterns_from(LFS_ATTRIBUTES),
+        vec!["*.psd".to_string(), "models/**".to_string()]
+    );
+    assert!(Lfs::patterns_from("*.png binary\n").is_empty());
+}
+
+#[test]
+#[serial]
+fn test_lfs_content_sniffing() {
+    let pointer = format!("{POINTER_HEADER}\noid sha256:abc\nsize 12\n");
+    assert!(Lfs::is_pointer(pointer.as_bytes()));
+    assert!(!Lfs::is_pointer(b"plain text"));
+    assert!(Lfs::is_binary(&[0x89, b'P', b'N', b'G', 0]));
+    assert!(!Lfs::is_binary(b"plain text"));
+    // Only the start of the file is inspected
+    let mut late_nul = vec![b'a'; 9000];
+    late_nul.push(0);
+    assert!(!Lfs::is_binary(&late_nul));
+}
+
+#[test]
+#[serial]
+fn test_lfs_status_issues() {
+    let status = LfsStatus {
+        installed: false,
+        patterns: vec!["*.psd".to_string()],
+        uncovered: vec![("video.mp4".to_string(), 2_500_000)],
+        not_downloaded: vec!["art.psd".to_string()],
+        pending_pushes: Some(vec!["abc => art.psd".to_string()]),
+    };
+
+    let issues = status.issues().join("\n");
+    assert!(issues.contains("git-lfs is not installed"));
+    assert!(issues.contains("1 large binary file(s) not covered by LFS"));
+    assert!(issues.contains("video.mp4 (2.5 MB)"));
+    assert!(issues.contains("1 LFS file(s) not downloaded"));
+    assert!(issues.contains("1 LFS object(s) waiting to be pushed"));
+
+    assert!(LfsStatus::default().issues().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_lfs_status_reports_uncovered_binaries() {
+    let repo = basic_repo();
+    std::fs::write(repo.path().join("video.bin"), large_binary()).unwrap();
+    repo.add_commit("notes.txt", "notes", "Add video");
+
+    repo.run_git_x(&["lfs", "status"])
+        .success()
+        .stdout(contains("🗄️  Git LFS Status"))
+        .stdout(contains("No patterns tracked by LFS"))
+        .stdout(contains("1 large binary file(s) not covered by LFS"))
+        .stdout(contains("video.bin"));
+}
+
+#[test]
+#[serial]
+fn test_lfs_status_with_tracked_patterns() {
+    let repo = basic_repo();
+    std::fs::create_dir_all(repo.path().join("models")).unwrap();
+    std::fs::write(repo.path().join("models/weights.bin"), large_binary()).unwrap();
+    std::fs::write(
+        repo.path().join("art.psd"),
+        format!("{POINTER_HEADER}\noid sha256:abc\nsize 12\n"),
+    )
+    .unwrap();
+    repo.add_commit(".gitattributes", LFS_ATTRIBUTES, "Track assets with LFS");
+
+    repo.run_git_x(&["lfs", "status"])
+        .success()
+        .stdout(contains("Tracked patterns (2)"))
+        .stdout(contains("• models/**"))
+        .stdout(contains("1 LFS file(s) not downloaded"))
+        .stdout(contains("• art.psd"))
+        .stdout(contains("No large binary files outside LFS"));
+}
+
+#[test]
+#[serial]
+fn test_health_includes_lfs_section_only_when_used() {
+    let repo = basic_repo();
+
+    repo.run_git_x(&["health"])
+        .success()
+        .stdout(contains("Git LFS").not());
+
+    repo.add_commit(".gitattributes", LFS_ATTRIBUTES, "Track assets with LFS");
+
+    repo.run_git_x(&["health"])
+        .success()
+        .stdout(contains("Git LFS:"));
+}
+
+#[test]
+#[serial]
+fn test_lfs_command_direct() {
+    let repo = basic_repo();
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+    let result = LfsCommand::new(LfsAction::Status).execute();
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert!(result.unwrap().contains("Git LFS Status"));
+}
@@ -1,4 +1,4 @@
-use git_x::{GitXError, Result};
+use git_x::{GitFailure, GitXError, Result};
 use serial_test::serial;
 use std::io;
 
@@ -9,6 +9,43 @@ fn test_gitx_error_git_command() {
     assert_eq!(format!("{error}"), "Git command failed: git status failed");
 }
 
+#[test]
+fn test_git_failure_concise_and_verbose() {
+    let error = GitXError::git_failure(
+        &["push", "origin", "main"],
+        Some(1),
+        "error: failed to push some refs\nhint: Updates were rejected\n",
+    );
+    assert_eq!(
+        format!("{error}"),
+        "Git command failed: error: failed to push some refs"
+    );
+    assert_eq!(
+        format!("{error:#}"),
+        "Git command failed: git push origin main\n  exit status 1\n  stderr:\n    error: failed to push some refs\n    hint: Updates were rejected"
+    );
+
+    match error {
+        GitXError::GitProcess(failure) => {
+            assert_eq!(failure.args, ["push", "origin", "main"]);
+            assert_eq!(failure.status, Some(1));
+            assert_eq!(failure.command(), "git push origin main");
+        }
+        _ => panic!("Expected GitXError::GitProcess"),
+    }
+}
+
+#[test]
+fn test_git_failure_without_stderr() {
+    let failure = GitFailure::new(&["merge", "topic"], None, "  \n");
+    assert_eq!(failure.stderr, "");
+    assert_eq!(failure.summary(), "git merge topic (terminated by signal)");
+    assert_eq!(
+        format!("{failure:#}"),
+        "git merge topic\n  terminated by signal"
+    );
+}
+
 #[test]
 #[serial]
 fn test_gitx_error_io() {
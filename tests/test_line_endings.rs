@@ -0,0 +1,136 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::core::line_endings::{FileEol, LineEndingStatus};
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+
+const LS_FILES_EOL: &str = "i/lf    w/lf    attr/text=auto eol=lf \tsrc/main.rs\0i/crlf  w/crlf  attr/                 \tdocs/notes.txt\0i/mixed w/mixed attr/                 \tREADME.md\0i/-text w/-text attr/-text           \tlogo.png\0";
+
+#[test]
+fn test_file_eol_parse() {
+    let files = FileEol::parse(LS_FILES_EOL);
+
+    assert_eq!(files.len(), 4);
+    assert_eq!(files[0].path, "src/main.rs");
+    assert_eq!(files[0].attributes, "text=auto eol=lf");
+    assert!(files[0].has_eol_attributes());
+    assert_eq!(files[1].index, "crlf");
+    assert_eq!(files[1].attributes, "");
+    assert!(!files[1].has_eol_attributes());
+    assert!(files[3].is_binary());
+}
+
+#[test]
+fn test_line_ending_status_from_files() {
+    let status = LineEndingStatus::from_files(&FileEol::parse(LS_FILES_EOL));
+
+    assert_eq!(status.text_files, 3);
+    assert_eq!(status.mixed, vec!["README.md"]);
+    assert_eq!(status.renormalize, vec!["docs/notes.txt", "README.md"]);
+    assert_eq!(status.unattributed, 2);
+    assert!(!status.is_clean());
+
+    let issues: Vec<String> = status.messages().iter().map(|m| m.english()).collect();
+    assert!(issues[0].contains("1 file(s) mix LF and CRLF"));
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue.contains("2 text file(s) without"))
+    );
+    assert!(issues.last().unwrap().contains("git x line-endings --fix"));
+}
+
+#[test]
+fn test_missing_attributes_alone_are_not_a_health_issue() {
+    let status = LineEndingStatus::from_files(&FileEol::parse(
+        "i/lf    w/lf    attr/                 \tsrc/main.rs\0",
+    ));
+
+    assert_eq!(status.unattributed, 1);
+    assert!(!status.is_clean());
+    assert!(status.messages().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_line_endings_reports_committed_crlf() {
+    let repo = basic_repo();
+    repo.add_commit("windows.txt", "one\r\ntwo\r\n", "Add CRLF file");
+    repo.add_commit("mixed.txt", "one\r\ntwo\n", "Add mixed file");
+
+    repo.run_git_x(&["line-endings"])
+        .success()
+        .stdout(contains("📏 Line Endings"))
+        .stdout(contains("1 file(s) mix LF and CRLF"))
+        .stdout(contains("• mixed.txt"))
+        .stdout(contains(
+            "2 file(s) committed with CRLF would be renormalized",
+        ))
+        .stdout(contains("• windows.txt"))
+        .stdout(contains("no text or eol setting in .gitattributes"))
+        .stdout(contains("git x line-endings --fix"));
+
+    repo.run_git_x(&["health"])
+        .success()
+        .stdout(contains("Line endings: CRLF committed"))
+        .stdout(contains("windows.txt"));
+}
+
+#[test]
+#[serial]
+fn test_line_endings_consistent_with_attributes() {
+    let repo = basic_repo();
+    repo.add_commit(".gitattributes", "* text=auto eol=lf\n", "Add attributes");
+
+    repo.run_git_x(&["line-endings"])
+        .success()
+        .stdout(contains("No CRLF line endings committed"))
+        .stdout(contains("Line endings are consistent"));
+
+    repo.run_git_x(&["line-endings", "--fix"])
+        .success()
+        .stdout(contains("nothing to fix"));
+
+    repo.run_git_x(&["health"])
+        .success()
+        .stdout(contains("Line endings: Consistent"));
+}
+
+#[test]
+#[serial]
+fn test_line_endings_fix_writes_attributes_and_renormalizes() {
+    let repo = basic_repo();
+    repo.add_commit("windows.txt", "one\r\ntwo\r\n", "Add CRLF file");
+
+    repo.run_git_x(&["line-endings", "--fix"])
+        .success()
+        .stdout(contains("Added eol settings to .gitattributes"))
+        .stdout(contains("* text=auto eol=lf"))
+        .stdout(contains("git diff --cached --stat"));
+
+    let attributes = std::fs::read_to_string(repo.path().join(".gitattributes")).unwrap();
+    assert!(attributes.contains("* text=auto eol=lf"));
+    assert!(attributes.contains("*.bat text eol=crlf"));
+
+    let staged = repo.git(&["diff", "--cached", "--name-only"]);
+    assert!(staged.contains(".gitattributes"));
+    assert!(staged.contains("windows.txt"));
+    let eol = repo.git(&["ls-files", "--eol", "--cached", "windows.txt"]);
+    assert!(eol.starts_with("i/lf"), "{eol}");
+}
+
+#[test]
+#[serial]
+fn test_line_endings_fix_requires_clean_working_directory() {
+    let repo = basic_repo();
+    repo.add_commit("windows.txt", "one\r\ntwo\r\n", "Add CRLF file");
+    std::fs::write(repo.path().join("README.md"), "changed").unwrap();
+
+    repo.run_git_x(&["line-endings", "--fix"])
+        .success()
+        .stderr(contains("Working directory is not clean"))
+        .stdout(contains("Staged").not());
+    assert!(!repo.path().join(".gitattributes").exists());
+}
@@ -0,0 +1,78 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::core::loc::{LinesOfCode, count_lines, language, thousands};
+use predicates::str::contains;
+
+#[test]
+fn test_language_detection() {
+    assert_eq!(language("src/main.rs"), "Rust");
+    assert_eq!(language("web/App.TSX"), "TypeScript");
+    assert_eq!(language("docker/Dockerfile"), "Dockerfile");
+    assert_eq!(language("data/table.xyz"), ".xyz");
+    assert_eq!(language("LICENSE"), "Other");
+    // A leading dot names the file, it isn't an extension
+    assert_eq!(language(".gitignore"), "Other");
+}
+
+#[test]
+fn test_count_lines() {
+    assert_eq!(count_lines(b""), (0, 0));
+    assert_eq!(count_lines(b"one"), (1, 0));
+    assert_eq!(count_lines(b"one\n"), (1, 0));
+    assert_eq!(count_lines(b"one\n\n  \ntwo"), (4, 2));
+    assert_eq!(count_lines(b"one\r\n\r\ntwo\r\n"), (3, 1));
+}
+
+#[test]
+fn test_count_groups_by_language() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "fn a() {}\n\nfn b() {}\n").unwrap();
+    std::fs::write(dir.path().join("b.rs"), "fn c() {}\n").unwrap();
+    std::fs::write(dir.path().join("README.md"), "# Title\n").unwrap();
+    std::fs::write(dir.path().join("logo.png"), b"\x89PNG\0\0").unwrap();
+
+    let loc = LinesOfCode::count(
+        dir.path(),
+        &["a.rs", "b.rs", "README.md", "logo.png", "deleted.rs"],
+    );
+
+    assert_eq!(loc.binary_files, 1);
+    assert_eq!(loc.languages.len(), 2);
+    assert_eq!(loc.languages[0].language, "Rust");
+    assert_eq!(loc.languages[0].files, 2);
+    assert_eq!(loc.languages[0].lines, 4);
+    assert_eq!(loc.languages[0].code(), 3);
+    assert_eq!(loc.total_lines(), 5);
+    assert_eq!(loc.total_code(), 4);
+    assert_eq!(loc.describe_top(1), "Rust 4 (80%)");
+}
+
+#[test]
+fn test_thousands() {
+    assert_eq!(thousands(0), "0");
+    assert_eq!(thousands(999), "999");
+    assert_eq!(thousands(1000), "1,000");
+    assert_eq!(thousands(1234567), "1,234,567");
+}
+
+#[test]
+#[serial]
+fn test_summary_shows_languages() {
+    let repo = basic_repo();
+    repo.add_commit("lib.rs", "fn main() {}\n\nfn other() {}\n", "add lib");
+
+    repo.run_git_x(&["summary"])
+        .success()
+        .stdout(contains("📁 Files: 2 total, 4 lines (3 non-blank)"))
+        .stdout(contains("🗣️  Languages: Rust 3 (75%), Markdown 1 (25%)"));
+
+    repo.run_git_x(&["summary", "--json"])
+        .success()
+        .stdout(contains(r#""files":2"#))
+        .stdout(contains(r#""lines":4"#))
+        .stdout(contains(
+            r#"{"language":"Rust","files":1,"lines":3,"code":2,"blank":1}"#,
+        ));
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use assert_cmd::Command;
+use common::git;
+use git_x::core::locks::FileLock;
+use predicates::prelude::*;
+use predicates::str::contains;
+use serial_test::serial;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A shared bare remote with clones for alice and bob, both holding `art/hero.psd`
+fn team() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    git(dir.path(), &["init", "-q", "--bare", "remote.git"]);
+    for user in ["alice", "bob"] {
+        git(dir.path(), &["clone", "-q", "remote.git", user]);
+        let clone = dir.path().join(user);
+        git(&clone, &["config", "user.name", user]);
+        git(
+            &clone,
+            &["config", "user.email", &format!("{user}@example.com")],
+        );
+    }
+    let alice = dir.path().join("alice");
+    std::fs::create_dir(alice.join("art")).unwrap();
+    std::fs::write(alice.join("art/hero.psd"), "binary").unwrap();
+    git(&alice, &["add", "-A"]);
+    git(&alice, &["commit", "-q", "-m", "Add hero"]);
+    git(&alice, &["push", "-q", "origin", "HEAD:main"]);
+    git(&dir.path().join("bob"), &["pull", "-q", "origin", "main"]);
+    dir
+}
+
+fn lock(dir: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("git-x")
+        .unwrap()
+        .arg("lock")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .assert()
+}
+
+fn sample(path: &str, ours: bool) -> FileLock {
+    FileLock {
+        path: path.to_string(),
+        owner: "Alice".to_string(),
+        email: "alice@example.com".to_string(),
+        locked_at: 1_700_000_000,
+        ours,
+    }
+}
+
+#[test]
+fn test_file_lock_list_round_trip() {
+    let locks = vec![sample("b.psd", true), sample("a.psd", true)];
+    let content = FileLock::render_list(&locks);
+
+    assert_eq!(
+        content,
+        "a.psd\talice@example.com\tAlice\t1700000000\nb.psd\talice@example.com\tAlice\t1700000000\n"
+    );
+    let parsed = FileLock::parse_list(&content, "bob@example.com");
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].path, "a.psd");
+    assert!(!parsed[0].ours);
+    assert!(FileLock::parse_list(&content, "alice@example.com")[0].ours);
+    assert!(FileLock::parse_list("garbage\n", "x").is_empty());
+}
+
+#[test]
+fn test_file_lock_parse_lfs() {
+    let verify = r#"{"ours":[{"id":"1","path":"a.psd","owner":{"name":"me"},"locked_at":"2026-01-02T03:04:05Z"}],
+        "theirs":[{"id":"2","path":"b.psd","owner":{"name":"Alice"},"locked_at":"2026-01-02T03:04:05Z"}]}"#;
+    let locks = FileLock::parse_lfs(verify, "whoever").unwrap();
+    assert_eq!(locks.len(), 2);
+    assert!(locks[0].ours);
+    assert_eq!(locks[1].owner, "Alice");
+    assert!(!locks[1].ours);
+    assert_eq!(locks[1].locked_at, 1_767_323_045);
+
+    let plain = r#"[{"id":"2","path":"b.psd","owner":{"name":"Alice"},"locked_at":"2026-01-02T03:04:05Z"}]"#;
+    assert!(FileLock::parse_lfs(plain, "Alice").unwrap()[0].ours);
+    assert!(!FileLock::parse_lfs(plain, "Bob").unwrap()[0].ours);
+}
+
+#[test]
+fn test_file_lock_conflicts_ignore_our_locks() {
+    let locks = vec![sample("a.psd", true), sample("b.psd", false)];
+    let paths = vec![
+        "a.psd".to_string(),
+        "b.psd".to_string(),
+        "c.psd".to_string(),
+    ];
+
+    let conflicts = FileLock::conflicts(&locks, &paths);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].path, "b.psd");
+}
+
+#[test]
+#[serial]
+fn test_lock_take_status_and_release() {
+    let dir = team();
+    let alice = dir.path().join("alice");
+    let bob = dir.path().join("bob");
+
+    lock(&alice.join("art"), &["take", "hero.psd"])
+        .success()
+        .stdout(contains("Locked art/hero.psd"))
+        .stdout(contains("pre-commit hook"));
+    assert!(
+        git(
+            dir.path(),
+            &["--git-dir=remote.git", "for-each-ref", "refs/git-x"]
+        )
+        .contains("refs/git-x/locks")
+    );
+    lock(&alice, &["take", "art/hero.psd"])
+        .success()
+        .stdout(contains("already locked by you"));
+
+    lock(&bob, &["status"])
+        .success()
+        .stdout(contains("1 locked file(s)"))
+        .stdout(contains("art/hero.psd\talice"));
+    lock(&bob, &["take", "art/hero.psd"]).stderr(contains("locked by alice"));
+    lock(&bob, &["release", "art/hero.psd"]).stderr(contains("--force"));
+
+    lock(&alice, &["release"])
+        .success()
+        .stdout(contains("Released art/hero.psd"));
+    lock(&bob, &["status"])
+        .success()
+        .stdout(contains("No locked files"));
+    lock(&bob, &["take", "art/hero.psd"])
+        .success()
+        .stdout(contains("Locked art/hero.psd"));
+}
+
+#[test]
+#[serial]
+fn test_lock_release_force_breaks_someone_elses_lock() {
+    let dir = team();
+    let alice = dir.path().join("alice");
+    let bob = dir.path().join("bob");
+
+    lock(&alice, &["take", "art/hero.psd"]).success();
+    lock(&bob, &["release", "art/hero.psd", "--force"])
+        .success()
+        .stdout(contains("Released art/hero.psd"));
+    lock(&alice, &["status"])
+        .success()
+        .stdout(contains("No locked files"));
+}
+
+#[test]
+#[serial]
+fn test_lock_take_rejects_missing_files_and_directories() {
+    let dir = team();
+    let alice = dir.path().join("alice");
+
+    lock(&alice, &["take", "art/missing.psd"]).stderr(contains("does not exist"));
+    lock(&alice, &["take", "art"]).stderr(contains("is a directory"));
+}
+
+#[test]
+#[serial]
+fn test_lock_warns_about_changes_to_files_locked_by_others() {
+    let dir = team();
+    let alice = dir.path().join("alice");
+    let bob = dir.path().join("bob");
+    lock(&alice, &["take", "art/hero.psd"]).success();
+
+    lock(&bob, &["status"])
+        .success()
+        .stdout(contains("pre-commit hook").and(contains("changes to files").not()));
+    let hook = std::fs::read_to_string(bob.join(".git/hooks/pre-commit")).unwrap();
+    assert!(hook.contains("git x lock check commit || true"));
+
+    std::fs::write(bob.join("art/hero.psd"), "changed").unwrap();
+    lock(&bob, &["status"])
+        .success()
+        .stdout(contains("You have changes to files locked by someone else"));
+
+    git(&bob, &["add", "-A"]);
+    lock(&bob, &["check", "commit"])
+        .success()
+        .stderr(contains("You're committing changes"))
+        .stderr(contains("art/hero.psd — locked by alice"));
+    git(&bob, &["commit", "-q", "--no-verify", "-m", "Change hero"]);
+    lock(&bob, &["check", "push"])
+        .success()
+        .stderr(contains("You're pushing changes"));
+
+    lock(&alice, &["check", "commit"])
+        .success()
+        .stderr(predicate::str::is_empty());
+}
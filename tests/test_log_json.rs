@@ -0,0 +1,146 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_commits;
+use git_x::commands::analysis::{LogCursor, LoggedCommit};
+use git_x::core::export::ExportValue;
+use predicates::str::contains;
+
+fn records(output: &[u8]) -> Vec<ExportValue> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .map(|line| ExportValue::parse_json(line).unwrap())
+        .collect()
+}
+
+fn field<'a>(record: &'a ExportValue, key: &str) -> &'a str {
+    record.get(key).and_then(ExportValue::as_str).unwrap()
+}
+
+#[test]
+fn test_logged_commit_parse() {
+    let record = "abc123\x1fp1 p2\x1fAda\x1fada@example.com\x1f2024-01-01T10:00:00+00:00\x1f\
+                  Bob\x1fbob@example.com\x1f2024-01-02T10:00:00+00:00\x1fAdd parser\x1fLonger\nbody\n\x1f\
+                  \n3\t1\tsrc/parse.rs\n-\t-\tlogo.png\n";
+    let commit = LoggedCommit::parse(record).unwrap();
+
+    assert_eq!(commit.hash, "abc123");
+    assert_eq!(commit.parents, vec!["p1", "p2"]);
+    assert_eq!(commit.author.1, "ada@example.com");
+    assert_eq!(commit.committer.0, "Bob");
+    assert_eq!(commit.body, "Longer\nbody");
+    assert_eq!(commit.files.len(), 2);
+    assert_eq!(commit.files[0].additions, Some(3));
+    assert_eq!(commit.files[1].additions, None);
+
+    let value = commit.to_value("1:abc123", true);
+    let stats = value.get("stats").unwrap();
+    assert_eq!(stats.get("additions"), Some(&ExportValue::from(3i64)));
+    assert_eq!(stats.get("files"), Some(&ExportValue::from(2usize)));
+    assert!(commit.to_value("1:abc123", false).get("files").is_none());
+
+    assert!(LoggedCommit::parse("abc123\x1fnot enough fields").is_none());
+}
+
+#[test]
+fn test_cursor_round_trip_and_validation() {
+    let cursor: LogCursor = "5:abcdef1234,^1234567,--max-age=1700000000"
+        .parse()
+        .unwrap();
+    assert_eq!(cursor.offset, 5);
+    assert_eq!(cursor.revisions.len(), 3);
+    assert_eq!(
+        cursor.advance(2).to_string(),
+        "7:abcdef1234,^1234567,--max-age=1700000000"
+    );
+
+    assert!("abcdef1234".parse::<LogCursor>().is_err());
+    assert!("x:abcdef1234".parse::<LogCursor>().is_err());
+    assert!("0:--upload-pack=evil".parse::<LogCursor>().is_err());
+    assert!("0:main".parse::<LogCursor>().is_err());
+    assert!("0:--max-age=1".parse::<LogCursor>().is_err());
+}
+
+#[test]
+#[serial]
+fn test_log_json_exports_and_resumes() {
+    let repo = repo_with_commits(4);
+
+    let output = repo.run_git_x(&["log-json"]).success().get_output().clone();
+    let all = records(&output.stdout);
+    assert_eq!(all.len(), 4);
+    assert_eq!(field(&all[0], "subject"), "commit 4");
+    assert_eq!(field(all[0].get("author").unwrap(), "name"), "Test User");
+    assert_eq!(
+        all[0].get("files").unwrap().items()[0]
+            .get("path")
+            .and_then(ExportValue::as_str),
+        Some("file.txt")
+    );
+
+    let output = repo
+        .run_git_x(&["log-json", "--limit", "2"])
+        .success()
+        .stderr(contains("--cursor"))
+        .get_output()
+        .clone();
+    let first = records(&output.stdout);
+    assert_eq!(first.len(), 2);
+
+    // A commit made after the first page doesn't shift the resumed export
+    repo.add_commit("other.txt", "new", "later commit");
+    let cursor = field(&first[1], "cursor").to_string();
+    let output = repo
+        .run_git_x(&["log-json", "--cursor", &cursor, "--no-files"])
+        .success()
+        .get_output()
+        .clone();
+    let rest = records(&output.stdout);
+    assert_eq!(rest.len(), 2);
+    assert_eq!(field(&rest[0], "subject"), "commit 2");
+    assert!(rest[0].get("files").is_none());
+    assert_eq!(field(&rest[0], "hash"), field(&all[2], "hash"));
+}
+
+#[test]
+#[serial]
+fn test_log_json_range_and_errors() {
+    let repo = repo_with_commits(3);
+
+    let output = repo
+        .run_git_x(&["log-json", "HEAD~1..HEAD"])
+        .success()
+        .get_output()
+        .clone();
+    assert_eq!(records(&output.stdout).len(), 1);
+
+    repo.run_git_x(&["log-json", "--cursor", "0:--output=/tmp/x"])
+        .success()
+        .stderr(contains("Invalid cursor"));
+    repo.run_git_x(&["log-json", "no-such-branch"])
+        .success()
+        .stderr(contains("❌"));
+    repo.run_git_x(&["log-json", "--since", "whenever"])
+        .success()
+        .stderr(contains("Invalid time 'whenever'"));
+}
+
+#[test]
+#[serial]
+fn test_log_json_since_and_until() {
+    let repo = repo_with_commits(2);
+
+    let output = repo
+        .run_git_x(&["log-json", "--since", "1w", "--until", "now"])
+        .success()
+        .get_output()
+        .clone();
+    assert_eq!(records(&output.stdout).len(), 2);
+
+    let output = repo
+        .run_git_x(&["log-json", "--until", "2000-01-01"])
+        .success()
+        .get_output()
+        .clone();
+    assert!(records(&output.stdout).is_empty());
+}
@@ -0,0 +1,125 @@
+use serial_test::serial;
+mod common;
+
+use common::{basic_repo, git};
+use git_x::commands::branch::{ConflictWith, MergeQueueCommand, QueueEntry, QueueOutcome};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+#[test]
+fn test_parse_merge_tree_dedups_conflicted_paths() {
+    let clean = "4b825dc642cb6eb9a060e54bf8d69288fbee4904\n";
+    assert_eq!(
+        MergeQueueCommand::parse_merge_tree(clean),
+        (
+            "4b825dc642cb6eb9a060e54bf8d69288fbee4904".to_string(),
+            vec![]
+        )
+    );
+
+    let conflicted = "abc123\nsrc/lib.rs\nsrc/lib.rs\nREADME.md\n\nAuto-merging src/lib.rs\n";
+    let (tree, files) = MergeQueueCommand::parse_merge_tree(conflicted);
+    assert_eq!(tree, "abc123");
+    assert_eq!(files, ["src/lib.rs", "README.md"]);
+}
+
+#[test]
+fn test_parse_pull_requests_oldest_first() {
+    let json = r#"[{"headRefName":"feature/b","number":12,"title":"Add login"},{"headRefName":"feature/a","number":7,"title":"Fix typo"}]"#;
+    let prs = MergeQueueCommand::parse_pull_requests(json).unwrap();
+    assert_eq!(
+        prs,
+        [
+            ("feature/a".to_string(), "#7 Fix typo".to_string()),
+            ("feature/b".to_string(), "#12 Add login".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_queue_entry_summary() {
+    let entry = QueueEntry {
+        branch: "feature/c".to_string(),
+        pull_request: Some("#3 Tweak".to_string()),
+        outcome: QueueOutcome::Conflict {
+            with: ConflictWith::Branch("feature/a".to_string()),
+            files: vec!["f.txt".to_string()],
+        },
+    };
+    assert_eq!(
+        entry.summary(3),
+        "  3. ❌ feature/c (#3 Tweak) — conflicts with feature/a: f.txt"
+    );
+}
+
+#[test]
+#[serial]
+fn test_merge_queue_reports_first_conflicting_pair() {
+    let repo = basic_repo();
+    let base = git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+    repo.add_commit("shared.txt", "a\nb\nc\n", "add shared file");
+    git(repo.path(), &["branch", "merged-already"]);
+
+    repo.create_branch("docs");
+    repo.add_commit("docs.txt", "docs\n", "add docs");
+    repo.checkout_branch(&base);
+    repo.create_branch("first");
+    repo.add_commit("shared.txt", "first\nb\nc\n", "first edit");
+    repo.checkout_branch(&base);
+    repo.create_branch("second");
+    repo.add_commit("shared.txt", "second\nb\nc\n", "second edit");
+    repo.checkout_branch(&base);
+    let head = git(repo.path(), &["rev-parse", "HEAD"]);
+
+    repo.run_git_x(&[
+        "merge-queue",
+        "docs",
+        "first",
+        "second",
+        "merged-already",
+        "missing",
+        "--base",
+        &base,
+    ])
+    .success()
+    .stdout(contains(format!("Merge queue onto {base} (5 branch(es))")))
+    .stdout(contains("1. ✅ docs — merges cleanly"))
+    .stdout(contains("3. ❌ second — conflicts with first: shared.txt"))
+    .stdout(contains("4. ➖ merged-already — already merged"))
+    .stdout(contains("5. ❓ missing — not found"))
+    .stdout(contains("First conflict: first + second (shared.txt)"));
+
+    // Nothing was merged for real
+    assert_eq!(git(repo.path(), &["rev-parse", "HEAD"]), head);
+    assert_eq!(git(repo.path(), &["status", "--porcelain"]), "");
+
+    repo.run_git_x(&["merge-queue", "docs", "first", "--base", &base])
+        .success()
+        .stdout(contains("2 branch(es) merge cleanly"))
+        .stdout(contains("First conflict").not());
+}
+
+#[test]
+#[serial]
+fn test_merge_queue_needs_branches() {
+    let repo = basic_repo();
+    repo.run_git_x(&["merge-queue", "--base", "HEAD"])
+        .success()
+        .stderr(contains("Nothing to queue"));
+}
+
+#[test]
+#[serial]
+fn test_merge_queue_defaults_to_configured_default_branch() {
+    let repo = basic_repo();
+    git(repo.path(), &["branch", "-m", "trunk"]);
+    git(repo.path(), &["config", "init.defaultBranch", "trunk"]);
+    repo.create_branch("docs");
+    repo.add_commit("docs.txt", "docs\n", "add docs");
+    repo.checkout_branch("trunk");
+
+    repo.run_git_x(&["merge-queue", "docs"])
+        .success()
+        .stdout(contains("Merge queue onto trunk (1 branch(es))"))
+        .stdout(contains("1. ✅ docs — merges cleanly"));
+}
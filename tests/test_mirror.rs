@@ -0,0 +1,120 @@
+use serial_test::serial;
+mod common;
+
+use common::{basic_repo, git};
+use git_x::commands::repository::MirrorDiff;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+/// Repo with a tag and a note, plus an empty bare repository added as `backup`
+fn repo_with_backup() -> (common::TestRepo, TempDir) {
+    let repo = basic_repo();
+    let backup = TempDir::new().unwrap();
+    git(backup.path(), &["init", "-q", "--bare"]);
+    git(
+        repo.path(),
+        &["remote", "add", "backup", backup.path().to_str().unwrap()],
+    );
+    git(repo.path(), &["tag", "-a", "v1.0.0", "-m", "Release"]);
+    git(repo.path(), &["notes", "add", "-m", "Reviewed"]);
+    (repo, backup)
+}
+
+#[test]
+fn test_mirror_diff_compare() {
+    let local =
+        MirrorDiff::parse_refs("aaa refs/heads/main\nbbb refs/tags/v1\nccc refs/notes/commits\n");
+    let remote = MirrorDiff::parse_refs(
+        "aaa\tHEAD\naaa\trefs/heads/main\nddd\trefs/tags/v1\nfff\trefs/tags/v1^{}\neee\trefs/heads/old\n",
+    );
+
+    let diff = MirrorDiff::compare(&local, &remote);
+
+    assert_eq!(diff.matched, 1);
+    assert_eq!(diff.missing, ["refs/notes/commits"]);
+    assert_eq!(diff.outdated, ["refs/tags/v1"]);
+    assert_eq!(diff.extra, ["refs/heads/old"]);
+    assert!(!diff.is_complete());
+    assert!(MirrorDiff::compare(&local, &local).is_complete());
+}
+
+#[test]
+#[serial]
+fn test_mirror_push_and_verify() {
+    let (repo, backup) = repo_with_backup();
+
+    repo.run_git_x(&["mirror", "push"])
+        .success()
+        .stdout(contains("verified with ls-remote"));
+    assert_eq!(
+        git(backup.path(), &["rev-parse", "refs/notes/commits"]),
+        git(repo.path(), &["rev-parse", "refs/notes/commits"])
+    );
+    assert!(!git(backup.path(), &["tag", "--list", "v1.0.0"]).is_empty());
+
+    repo.run_git_x(&["mirror", "verify", "backup"])
+        .success()
+        .stdout(contains("Mirror 'backup' is complete"));
+
+    // New local work shows up until the next push
+    git(repo.path(), &["branch", "feature"]);
+    repo.run_git_x(&["mirror", "verify"])
+        .success()
+        .stdout(contains("is incomplete"))
+        .stdout(contains("Missing on mirror (1)"))
+        .stdout(contains("refs/heads/feature"));
+
+    // Deleted refs are pruned from the mirror
+    git(repo.path(), &["tag", "-d", "v1.0.0"]);
+    repo.run_git_x(&["mirror", "push"])
+        .success()
+        .stdout(contains("verified with ls-remote"));
+    assert!(git(backup.path(), &["tag", "--list", "v1.0.0"]).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_mirror_requires_remote() {
+    let repo = basic_repo();
+
+    repo.run_git_x(&["mirror", "push", "offsite"])
+        .success()
+        .stderr(contains("No remote named 'offsite'"));
+}
+
+#[test]
+#[serial]
+fn test_mirror_schedule_registers_repository() {
+    let (repo, _backup) = repo_with_backup();
+    let home = TempDir::new().unwrap();
+    let global = home.path().join(".gitconfig");
+    let run = |args: &[&str]| {
+        assert_cmd::Command::cargo_bin("git-x")
+            .unwrap()
+            .args(args)
+            .current_dir(repo.path())
+            .env("HOME", home.path())
+            .env("GIT_CONFIG_GLOBAL", &global)
+            .assert()
+    };
+
+    run(&["mirror", "schedule", "backup"])
+        .success()
+        .stdout(contains("Scheduled mirroring"))
+        .stdout(contains(
+            "git for-each-repo --config=git-x.mirror.repo x mirror push",
+        ));
+    let config = std::fs::read_to_string(&global).unwrap();
+    assert_eq!(config.matches("[git-x \"mirror\"]").count(), 1);
+    assert_eq!(
+        git(repo.path(), &["config", "git-x.mirror.remote"]),
+        "backup"
+    );
+
+    run(&["mirror", "schedule", "--unregister"])
+        .success()
+        .stdout(contains("Stopped scheduled mirroring"));
+    run(&["mirror", "schedule", "--unregister"])
+        .success()
+        .stdout(contains("is not scheduled"));
+}
@@ -0,0 +1,173 @@
+use serial_test::serial;
+mod common;
+
+use common::{TestRepo, basic_repo, git};
+use git_x::commands::branch::{MovePlan, PlannedCommit};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+fn commit(sha: &str, subject: &str) -> PlannedCommit {
+    PlannedCommit {
+        sha: sha.to_string(),
+        subject: subject.to_string(),
+    }
+}
+
+/// `stacked` with two commits built on `feature-a`, and a newer commit on the base branch
+fn stacked_repo() -> (TestRepo, String) {
+    let repo = basic_repo();
+    let base = git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+    repo.create_branch("feature-a");
+    repo.add_commit("a.txt", "a\n", "A work");
+    repo.create_branch("stacked");
+    repo.add_commit("b.txt", "b\n", "B one");
+    repo.add_commit("c.txt", "c\n", "B two");
+    repo.checkout_branch(&base);
+    repo.add_commit("main.txt", "main\n", "base moves on");
+    (repo, base)
+}
+
+#[test]
+fn test_parse_log() {
+    let output = "1111111111\0First commit\n2222222222\0Second: with\0nul\nbroken line\n";
+    assert_eq!(
+        PlannedCommit::parse_log(output),
+        [
+            commit("1111111111", "First commit"),
+            commit("2222222222", "Second: with\0nul"),
+        ]
+    );
+}
+
+#[test]
+fn test_move_plan_shape() {
+    let plan = MovePlan {
+        branch: "stacked".to_string(),
+        commits: vec![commit("cccccccccc", "B two"), commit("bbbbbbbbbb", "B one")],
+        old_base: commit("aaaaaaaaaa", "A work"),
+        old_base_name: Some("feature-a".to_string()),
+        onto: "main".to_string(),
+        new_base: commit("mmmmmmmmmm", "base moves on"),
+    };
+
+    assert_eq!(
+        plan.shape(),
+        [
+            "Before:",
+            "   ● ccccccc B two  (stacked)",
+            "   ● bbbbbbb B one",
+            "   ○ aaaaaaa A work  (feature-a)",
+            "",
+            "After:",
+            "   ● ccccccc' B two  (stacked)",
+            "   ● bbbbbbb' B one",
+            "   ○ mmmmmmm base moves on  (main)",
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn test_move_dry_run_changes_nothing() {
+    let (repo, base) = stacked_repo();
+    let tip = git(repo.path(), &["rev-parse", "stacked"]);
+
+    repo.run_git_x(&["move", "stacked", "--onto", &base, "--dry-run"])
+        .success()
+        .stdout(contains(format!(
+            "Move 2 commit(s) of 'stacked' onto '{base}'"
+        )))
+        .stdout(contains("A work  (feature-a)"))
+        .stdout(contains("' B two  (stacked)"))
+        .stdout(contains(format!("base moves on  ({base})")))
+        .stdout(contains("Dry run: nothing was moved"));
+
+    assert_eq!(git(repo.path(), &["rev-parse", "stacked"]), tip);
+}
+
+#[test]
+#[serial]
+fn test_move_unique_commits_onto_new_base() {
+    let (repo, base) = stacked_repo();
+
+    repo.run_git_x(&["move", "stacked", "--onto", &base])
+        .success()
+        .stdout(contains(format!(
+            "Moved 2 commit(s) of 'stacked' onto '{base}'"
+        )))
+        .stdout(contains("git branch -f stacked"));
+
+    // Only the stacked commits moved, and the user stays where they were
+    assert_eq!(
+        git(
+            repo.path(),
+            &["log", "--format=%s", &format!("{base}..stacked")]
+        ),
+        "B two\nB one"
+    );
+    assert_eq!(
+        git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]),
+        base
+    );
+    assert_eq!(
+        git(repo.path(), &["log", "-1", "--format=%s", "feature-a"]),
+        "A work"
+    );
+}
+
+#[test]
+#[serial]
+fn test_move_from_and_errors() {
+    let (repo, base) = stacked_repo();
+    repo.checkout_branch("stacked");
+
+    // Move only the newest commit
+    repo.run_git_x(&["move", "--onto", &base, "--from", "stacked~1"])
+        .success()
+        .stdout(contains("Moved 1 commit(s) of 'stacked'"))
+        .stdout(contains("git reset --hard"));
+    assert_eq!(
+        git(
+            repo.path(),
+            &["log", "--format=%s", &format!("{base}..HEAD")]
+        ),
+        "B two"
+    );
+
+    repo.run_git_x(&["move"])
+        .success()
+        .stderr(contains("Pass --onto <base>"));
+
+    repo.run_git_x(&["move", "missing", "--onto", &base])
+        .success()
+        .stderr(contains("Branch 'missing' does not exist"));
+
+    repo.run_git_x(&["move", "--onto", &base, "--from", "feature-a"])
+        .success()
+        .stderr(contains("'feature-a' is not in the history of 'stacked'"));
+}
+
+#[test]
+#[serial]
+fn test_move_conflict_rolls_back() {
+    let repo = basic_repo();
+    let base = git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+    repo.create_branch("topic");
+    repo.add_commit("shared.txt", "topic\n", "topic edit");
+    repo.checkout_branch(&base);
+    repo.add_commit("shared.txt", "base\n", "base edit");
+    repo.checkout_branch("topic");
+    let tip = git(repo.path(), &["rev-parse", "HEAD"]);
+
+    repo.run_git_x(&["move", "--onto", &base])
+        .success()
+        .stderr(contains("conflicts in shared.txt"))
+        .stderr(contains("rolled back"));
+
+    assert_eq!(git(repo.path(), &["rev-parse", "HEAD"]), tip);
+    assert_eq!(git(repo.path(), &["status", "--porcelain"]), "");
+    assert!(!repo.path().join(".git/rebase-merge").exists());
+    repo.run_git_x(&["move", "--onto", &base, "--dry-run"])
+        .success()
+        .stdout(contains("Moved").not());
+}
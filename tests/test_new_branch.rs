@@ -5,6 +5,8 @@ use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
+mod common;
+
 use git_x::commands::repository::NewBranchCommand;
 use git_x::core::traits::Command as CommandTrait;
 
@@ -67,7 +69,7 @@ fn test_new_branch_run_function_outside_git_repo() {
 
     std::env::set_current_dir(temp_dir.path()).unwrap();
 
-    let cmd = NewBranchCommand::new("test-branch".to_string(), None);
+    let cmd = NewBranchCommand::new("test-branch".parse().unwrap(), None);
     let result = cmd.execute();
 
     // Should fail because we're not in a git repository
@@ -267,7 +269,7 @@ fn test_new_branch_command_direct() {
         .as_nanos();
     let unique_branch = format!("feature/test-{timestamp}");
 
-    let cmd = NewBranchCommand::new(unique_branch.clone(), None);
+    let cmd = NewBranchCommand::new(unique_branch.parse().unwrap(), None);
     let result = cmd.execute();
 
     // Should succeed and return formatted output
@@ -298,7 +300,10 @@ fn test_new_branch_command_with_from() {
     let unique_branch = format!("feature/test-{timestamp}");
 
     // Test with specific base branch
-    let cmd = NewBranchCommand::new(unique_branch, Some(default_branch));
+    let cmd = NewBranchCommand::new(
+        unique_branch.parse().unwrap(),
+        Some(default_branch.parse().unwrap()),
+    );
     let result = cmd.execute();
 
     assert!(result.is_ok(), "Command failed: {:?}", result.err());
@@ -308,3 +313,66 @@ fn test_new_branch_command_with_from() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+#[test]
+#[serial]
+fn test_new_branch_fresh_warns_about_stale_base() {
+    let (repo, _remote) = common::repo_with_remote_ahead("trunk");
+    let before = repo.git(&["rev-parse", "trunk"]);
+
+    repo.run_git_x(&["new", "feature/stale", "--fresh"])
+        .success()
+        .stdout(predicate::str::contains(
+            "'trunk' is 1 commit(s) behind origin/trunk",
+        ))
+        .stdout(predicate::str::contains("use --update to fast-forward"))
+        .stdout(predicate::str::contains("Successfully created"));
+    assert_eq!(repo.git(&["rev-parse", "trunk"]), before);
+
+    // Within the allowed lag nothing is flagged
+    repo.run_git_x(&[
+        "new",
+        "feature/lagging",
+        "--from",
+        "trunk",
+        "--max-behind",
+        "3",
+    ])
+    .success()
+    .stdout(predicate::str::contains("within the limit of 3"));
+}
+
+#[test]
+#[serial]
+fn test_new_branch_update_fast_forwards_base() {
+    let (repo, _remote) = common::repo_with_remote_ahead("trunk");
+    let upstream = repo.git(&["rev-parse", "origin/trunk"]);
+
+    // The base isn't checked out here, so its ref is moved directly
+    repo.git(&["checkout", "-b", "elsewhere"]);
+    repo.run_git_x(&["new", "feature/fresh", "--from", "trunk", "--update"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Fast-forwarded 'trunk' by 1 commit(s)",
+        ));
+    assert_eq!(repo.git(&["rev-parse", "trunk"]), upstream);
+    assert_eq!(repo.git(&["rev-parse", "feature/fresh"]), upstream);
+
+    repo.run_git_x(&["new", "feature/again", "--from", "trunk", "--fresh"])
+        .success()
+        .stdout(predicate::str::contains(
+            "'trunk' is up to date with origin/trunk",
+        ));
+}
+
+#[test]
+#[serial]
+fn test_new_branch_freshness_from_config_and_without_upstream() {
+    let repo = common::basic_repo();
+    repo.git(&["config", "git-x.new.checkFreshness", "true"]);
+
+    repo.run_git_x(&["new", "feature/local"])
+        .success()
+        .stdout(predicate::str::contains("has no upstream"))
+        .stdout(predicate::str::contains("Successfully created"));
+}
@@ -0,0 +1,141 @@
+use serial_test::serial;
+mod common;
+
+use common::{basic_repo, repo_with_remote_ahead};
+use git_x::GitXError;
+use git_x::core::git::GitOperations;
+use git_x::core::network::Network;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// A repository whose `main` is one fetched commit behind an origin that can't be reached
+fn unreachable_remote_ahead() -> (common::TestRepo, common::TestRepo) {
+    let (repo, remote) = repo_with_remote_ahead("main");
+    repo.git(&[
+        "remote",
+        "set-url",
+        "origin",
+        "https://git.invalid/repo.git",
+    ]);
+    (repo, remote)
+}
+
+#[test]
+fn test_network_commands() {
+    assert!(Network::is_network_command(&["fetch", "--prune"]));
+    assert!(Network::is_network_command(&[
+        "fetch", "--quiet", "origin", "main"
+    ]));
+    assert!(Network::is_network_command(&["push", "--mirror", "backup"]));
+    assert!(Network::is_network_command(&[
+        "-C",
+        "repo",
+        "ls-remote",
+        "--heads",
+        "origin"
+    ]));
+    assert!(Network::is_network_command(&["lfs", "locks", "--json"]));
+    assert!(Network::is_network_command(&["remote", "update"]));
+
+    // Local repositories and local commands don't count
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().to_str().unwrap();
+    assert!(!Network::is_network_command(&[
+        "fetch", "--quiet", path, "HEAD"
+    ]));
+    assert!(!Network::is_network_command(&[
+        "fetch",
+        ".",
+        "refs/remotes/origin/main:refs/heads/main"
+    ]));
+    assert!(!Network::is_network_command(&[
+        "push",
+        "file:///srv/backup.git"
+    ]));
+    assert!(!Network::is_network_command(&["lfs", "ls-files"]));
+    assert!(!Network::is_network_command(&[
+        "remote", "get-url", "origin"
+    ]));
+    assert!(!Network::is_network_command(&["log", "--oneline"]));
+    assert!(!Network::is_network_command(&[]));
+}
+
+#[test]
+#[serial]
+fn test_offline_refuses_network_git_commands() {
+    let repo = basic_repo();
+    let original = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    Network::configure(true);
+    let fetch = GitOperations::run(&["fetch", "origin"]);
+    let log = GitOperations::run(&["log", "--oneline"]);
+    let ls_remote =
+        GitOperations::run_with_env(&["ls-remote", "origin"], &[("GIT_TERMINAL_PROMPT", "0")]);
+    let streamed = GitOperations::run_streaming(&["fetch", "origin"], b'\n', |_| Ok(true));
+    Network::configure(false);
+    std::env::set_current_dir(original).unwrap();
+
+    let error = fetch.unwrap_err();
+    assert!(matches!(error, GitXError::Offline(_)));
+    assert_eq!(
+        error.to_string(),
+        "Skipped git fetch origin: offline mode is on"
+    );
+    assert!(log.is_ok());
+    assert!(matches!(ls_remote, Err(GitXError::Offline(_))));
+    assert!(matches!(streamed, Err(GitXError::Offline(_))));
+}
+
+#[test]
+#[serial]
+fn test_offline_sync_uses_last_fetched_upstream() {
+    let (repo, _remote) = unreachable_remote_ahead();
+
+    repo.run_git_x(&["--offline", "sync", "--merge"])
+        .success()
+        .stdout(contains("Offline: nothing fetched"))
+        .stdout(contains("Merged 1 commits from origin/main"));
+    assert!(repo.path().join("remote_file.txt").exists());
+}
+
+#[test]
+#[serial]
+fn test_offline_upstream_sync_all_fast_forwards_locally() {
+    let (repo, _remote) = unreachable_remote_ahead();
+    repo.create_branch("feature");
+
+    repo.run_git_x(&["upstream", "sync-all"])
+        .success()
+        .stdout(contains("main: fetch failed"));
+
+    assert_cmd::Command::cargo_bin("git-x")
+        .unwrap()
+        .args(["upstream", "sync-all"])
+        .current_dir(repo.path())
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .env("GIT_X_OFFLINE", "1")
+        .assert()
+        .success()
+        .stdout(contains("Offline: nothing fetched"))
+        .stdout(contains("main: fast-forwarded 1 commit(s)"));
+}
+
+#[test]
+#[serial]
+fn test_offline_marks_unavailable_sections() {
+    let (repo, _remote) = unreachable_remote_ahead();
+    repo.git(&["config", "git-x.offline", "true"]);
+
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("Pull request status: unavailable offline"));
+    repo.run_git_x(&["health"])
+        .success()
+        .stdout(contains("Offline: remote branches are as last fetched"));
+    repo.run_git_x(&["doctor", "connection"])
+        .success()
+        .stdout(contains("Connection checks: unavailable offline"))
+        .stdout(contains("Offline: only local remotes were checked"))
+        .stdout(contains("DNS").not());
+}
@@ -1,5 +1,9 @@
 use console::Color;
-use git_x::core::output::{BufferedOutput, Format, ProgressIndicator, TableFormatter};
+use git_x::core::export::ExportValue;
+use git_x::core::output::{
+    BufferedOutput, Format, JsonSink, OutputSink, ProgressIndicator, TableFormatter, TableLayout,
+    truncate_middle,
+};
 use serial_test::serial;
 
 // Helper function to strip ANSI escape codes for testing
@@ -436,6 +440,78 @@ fn test_table_formatter_single_column() {
     assert!(result.contains("-"));
 }
 
+fn branch_table() -> TableFormatter {
+    let mut table = TableFormatter::new(vec![
+        "Branch".to_string(),
+        "Commits".to_string(),
+        "Author".to_string(),
+    ]);
+    table.add_row(vec![
+        "feature/JIRA-1234-retry-declined-payments".to_string(),
+        "7".to_string(),
+        "Alice".to_string(),
+    ]);
+    table.add_row(vec![
+        "main".to_string(),
+        "1204".to_string(),
+        "Bob".to_string(),
+    ]);
+    table
+}
+
+#[test]
+fn test_table_formatter_right_aligns_numbers() {
+    let result = branch_table().format();
+    let lines: Vec<&str> = result.lines().collect();
+
+    assert!(lines[0].starts_with("Branch"));
+    assert!(lines[0].contains("  Commits  Author"));
+    assert!(lines[2].contains("        7  Alice"));
+    assert!(lines[3].contains("     1204  Bob"));
+}
+
+#[test]
+fn test_table_formatter_fits_the_width() {
+    let result = branch_table()
+        .with_layout(TableLayout::Aligned { width: Some(30) })
+        .render();
+    let lines: Vec<&str> = result.lines().collect();
+
+    assert!(lines.iter().all(|line| line.chars().count() <= 30));
+    assert!(lines[2].starts_with("featur…yments  "));
+    assert!(lines[2].contains("7"));
+    assert!(lines[3].contains("1204"));
+}
+
+#[test]
+fn test_table_formatter_tabs() {
+    let mut table = branch_table().with_layout(TableLayout::Tabs);
+    table.add_row(vec![
+        Format::bold("styled"),
+        "3".to_string(),
+        "a\tb".to_string(),
+    ]);
+
+    assert_eq!(
+        table.render(),
+        "Branch\tCommits\tAuthor\n\
+         feature/JIRA-1234-retry-declined-payments\t7\tAlice\n\
+         main\t1204\tBob\n\
+         styled\t3\ta b\n"
+    );
+}
+
+#[test]
+fn test_truncate_middle() {
+    assert_eq!(truncate_middle("main", 10), "main");
+    assert_eq!(
+        truncate_middle("feature/JIRA-1234-retry", 12),
+        "featur…retry"
+    );
+    assert_eq!(truncate_middle("abcdef", 1), "…");
+    assert_eq!(truncate_middle("abcdef", 0), "");
+}
+
 // Tests for ProgressIndicator
 
 #[test]
@@ -709,3 +785,27 @@ fn test_progress_indicator_edge_cases() {
     progress.increment(); // One beyond total
     progress.finish();
 }
+
+// Tests for OutputSink
+
+#[test]
+fn test_buffered_output_as_sink() {
+    let mut output = BufferedOutput::new();
+    output.line("first".to_string());
+    output.record(ExportValue::from("ignored"));
+    output.line("second".to_string());
+    assert_eq!(output.content(), "first\nsecond");
+}
+
+#[test]
+fn test_json_sink_collects_records_only() {
+    let mut sink = JsonSink::new();
+    sink.line("not part of the JSON".to_string());
+    sink.record(ExportValue::record(vec![
+        ("check", ExportValue::from("Remotes")),
+        ("ok", ExportValue::from(true)),
+    ]));
+    assert_eq!(sink.records().len(), 1);
+    assert_eq!(sink.to_json(), r#"[{"check":"Remotes","ok":true}]"#);
+    assert_eq!(JsonSink::new().to_json(), "[]");
+}
@@ -0,0 +1,101 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::analysis::DirectoryOwnership;
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::process::Command;
+
+/// Commit `path` as `author`, creating its directories
+fn commit_as(repo: &common::TestRepo, author: &str, path: &str, content: &str) {
+    let file = repo.path().join(path);
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, content).unwrap();
+    for args in [
+        vec!["add", "."],
+        vec![
+            "commit",
+            "-q",
+            "-m",
+            "change",
+            "--author",
+            &format!("{author} <{}@example.com>", author.to_lowercase()),
+        ],
+    ] {
+        Command::new("git")
+            .args(&args)
+            .current_dir(repo.path())
+            .output()
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_directory_ownership_from_log() {
+    let log = "\x1eAlice\n\nservices/auth/login.rs\nservices/auth/token.rs\n\
+               \x1eBob\n\nservices/auth/login.rs\nservices/billing/invoice.rs\n\
+               \x1eAlice\n\nREADME.md\n";
+    let ownership = DirectoryOwnership::from_log(log, 2);
+
+    assert_eq!(ownership[0].directory, "services/auth");
+    assert_eq!(ownership[0].changes, 3);
+    assert_eq!(
+        ownership[0].owners,
+        [("Alice".to_string(), 2), ("Bob".to_string(), 1)]
+    );
+    assert_eq!(ownership[0].owners_summary(3), "67% Alice, 33% Bob");
+    assert_eq!(ownership[0].owners_summary(1), "67% Alice, +1 more");
+
+    let directories: Vec<&str> = ownership.iter().map(|o| o.directory.as_str()).collect();
+    assert!(directories.contains(&"."));
+    assert!(directories.contains(&"services/billing"));
+
+    let shallow = DirectoryOwnership::from_log(log, 1);
+    assert_eq!(shallow[0].directory, "services");
+    assert_eq!(shallow[0].changes, 4);
+}
+
+#[test]
+#[serial]
+fn test_ownership_report() {
+    let repo = basic_repo();
+    commit_as(&repo, "Alice", "services/auth/login.rs", "a");
+    commit_as(&repo, "Alice", "services/auth/login.rs", "b");
+    commit_as(&repo, "Bob", "services/auth/token.rs", "c");
+    commit_as(&repo, "Bob", "docs/guide.md", "d");
+
+    repo.run_git_x(&["ownership", "--depth", "2"])
+        .success()
+        .stdout(contains(
+            "Directory ownership since 6 months ago (5 changes, depth 2)",
+        ))
+        .stdout(contains("services/auth/ — 67% Alice, 33% Bob — 3 changes"))
+        .stdout(contains("100% Bob — 1 changes"))
+        .stdout(contains("100% Test User — 1 changes"));
+
+    repo.run_git_x(&["ownership", "--top", "1"])
+        .success()
+        .stdout(contains("services/ — 67% Alice, +1 more — 3 changes"));
+
+    repo.run_git_x(&["ownership", "--json"])
+        .success()
+        .stdout(contains("\"directory\":\"services\""))
+        .stdout(contains("\"author\":\"Alice\""))
+        .stdout(contains("\"percentage\":67"));
+
+    repo.run_git_x(&["ownership", "--since", "1 week ago", "--csv"])
+        .success()
+        .stdout(contains("directory,author,changes,percentage"))
+        .stdout(contains("docs,Bob,1,100"));
+}
+
+#[test]
+#[serial]
+fn test_ownership_with_no_changes_in_period() {
+    let repo = basic_repo();
+    repo.run_git_x(&["ownership", "--since", "2099-01-01"])
+        .success()
+        .stdout(contains("No changes since 2099-01-01"))
+        .stdout(contains("—").not());
+}
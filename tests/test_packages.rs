@@ -0,0 +1,258 @@
+use serial_test::serial;
+mod common;
+
+use common::{TestRepo, git, repo_with_branch};
+use git_x::core::dependencies::Ecosystem;
+use git_x::core::packages::{Package, Packages, dir_matches};
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::collections::HashMap;
+
+fn detect(files: &[(&str, &str)]) -> Vec<Package> {
+    let contents: HashMap<&str, &str> = files.iter().copied().collect();
+    let tracked: Vec<&str> = files.iter().map(|(path, _)| *path).collect();
+    Packages::from_files(&tracked, |path| {
+        contents.get(path).map(|content| content.to_string())
+    })
+}
+
+fn package(name: &str, path: &str, ecosystem: Ecosystem) -> Package {
+    Package {
+        name: name.to_string(),
+        path: path.to_string(),
+        ecosystem,
+    }
+}
+
+#[test]
+fn test_dir_matches() {
+    assert!(dir_matches("crates/*", "crates/core"));
+    assert!(!dir_matches("crates/*", "crates/core/nested"));
+    assert!(dir_matches("apps/**", "apps/web/admin"));
+    assert!(dir_matches("./tools/cli/", "tools/cli"));
+    assert!(!dir_matches("crates/*", "other/core"));
+}
+
+#[test]
+fn test_detect_cargo_workspace() {
+    let packages = detect(&[
+        (
+            "Cargo.toml",
+            "[workspace]\nmembers = [\n    \"crates/*\", # libraries\n    \"cli\",\n]\nexclude = [\"crates/scratch\"]\n\n[workspace.dependencies]\nserde = \"1\"\n",
+        ),
+        (
+            "cli/Cargo.toml",
+            "[package]\nname = \"shop-cli\"\nversion = \"0.1.0\"\n",
+        ),
+        ("crates/core/Cargo.toml", "[package]\nname = 'shop-core'\n"),
+        (
+            "crates/scratch/Cargo.toml",
+            "[package]\nname = \"scratch\"\n",
+        ),
+        ("examples/demo/Cargo.toml", "[package]\nname = \"demo\"\n"),
+    ]);
+    assert_eq!(
+        packages,
+        vec![
+            package("shop-cli", "cli", Ecosystem::Cargo),
+            package("shop-core", "crates/core", Ecosystem::Cargo),
+        ]
+    );
+}
+
+#[test]
+fn test_detect_cargo_workspace_with_dotted_keys_and_inline_tables() {
+    let packages = detect(&[
+        (
+            "Cargo.toml",
+            "workspace.members = [\"tools/*\"] # all tools\nworkspace.exclude = [\n  # not shipped\n  \"tools/old\",\n]\n\n[package]\nname = \"root\"\n",
+        ),
+        (
+            "tools/lint/Cargo.toml",
+            "package = { name = \"shop-lint\", version = \"0.1.0\" }\n",
+        ),
+        (
+            "tools/fmt/Cargo.toml",
+            "[package]\nmetadata = { name = \"not-this\" }\nname = \"shop-fmt\"\n",
+        ),
+        ("tools/old/Cargo.toml", "[package]\nname = \"old\"\n"),
+    ]);
+    assert_eq!(
+        packages,
+        vec![
+            package("shop-fmt", "tools/fmt", Ecosystem::Cargo),
+            package("shop-lint", "tools/lint", Ecosystem::Cargo),
+        ]
+    );
+}
+
+#[test]
+fn test_detect_npm_pnpm_and_go() {
+    let packages = detect(&[
+        (
+            "package.json",
+            r#"{"name": "shop", "workspaces": {"packages": ["apps/*"]}}"#,
+        ),
+        (
+            "pnpm-workspace.yaml",
+            "packages:\n  - 'packages/*'\n  - \"!packages/legacy\"\ncatalog:\n  react: ^18\n",
+        ),
+        ("apps/web/package.json", r#"{"name": "@shop/web"}"#),
+        ("packages/ui/package.json", r#"{"private": true}"#),
+        ("packages/legacy/package.json", r#"{"name": "legacy"}"#),
+        (
+            "apps/web/node_modules/left-pad/package.json",
+            r#"{"name": "left-pad"}"#,
+        ),
+        ("go.mod", "module github.com/acme/shop\n"),
+        (
+            "services/api/go.mod",
+            "module github.com/acme/shop/services/api // api\n\ngo 1.22\n",
+        ),
+    ]);
+    assert_eq!(
+        packages,
+        vec![
+            package("@shop/web", "apps/web", Ecosystem::Npm),
+            package("ui", "packages/ui", Ecosystem::Npm),
+            package(
+                "github.com/acme/shop/services/api",
+                "services/api",
+                Ecosystem::Go
+            ),
+        ]
+    );
+
+    assert_eq!(
+        Packages::select(packages.clone(), "web").unwrap().path,
+        "apps/web"
+    );
+    assert_eq!(
+        Packages::select(packages.clone(), "services/api/")
+            .unwrap()
+            .name,
+        "github.com/acme/shop/services/api"
+    );
+    assert!(
+        Packages::select(packages, "missing")
+            .unwrap_err()
+            .to_string()
+            .contains("git x packages")
+    );
+    assert!(Packages::select(Vec::new(), "web").is_err());
+}
+
+#[test]
+fn test_select_reports_ambiguous_names() {
+    let packages = vec![
+        package("@shop/api", "apps/api", Ecosystem::Npm),
+        package("github.com/acme/api", "services/api", Ecosystem::Go),
+    ];
+    let error = Packages::select(packages.clone(), "api")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("matches several packages"), "{error}");
+    assert_eq!(
+        Packages::select(packages, "@shop/api").unwrap().path,
+        "apps/api"
+    );
+}
+
+/// A Cargo workspace with `core` and `cli` crates, each with its own author
+fn workspace_repo() -> TestRepo {
+    let repo = repo_with_branch("main");
+    let root = repo.path();
+    std::fs::create_dir_all(root.join("crates/core")).unwrap();
+    std::fs::create_dir_all(root.join("crates/cli")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("crates/core/Cargo.toml"),
+        "[package]\nname = \"core\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("crates/cli/Cargo.toml"),
+        "[package]\nname = \"cli\"\n",
+    )
+    .unwrap();
+    git(root, &["add", "."]);
+    git(root, &["commit", "--quiet", "-m", "Set up workspace"]);
+
+    git(root, &["checkout", "--quiet", "-b", "feature"]);
+    std::fs::write(root.join("crates/core/lib.rs"), "pub fn core() {}\n").unwrap();
+    git(root, &["add", "."]);
+    git(
+        root,
+        &[
+            "-c",
+            "user.name=Core Dev",
+            "-c",
+            "user.email=core@example.com",
+            "commit",
+            "--quiet",
+            "-m",
+            "Add core library",
+        ],
+    );
+    std::fs::write(root.join("crates/cli/main.rs"), "fn main() {}\n").unwrap();
+    git(root, &["add", "."]);
+    git(
+        root,
+        &[
+            "-c",
+            "user.name=Cli Dev",
+            "-c",
+            "user.email=cli@example.com",
+            "commit",
+            "--quiet",
+            "-m",
+            "Add cli binary",
+        ],
+    );
+    repo
+}
+
+#[test]
+#[serial]
+fn test_packages_command_and_scoping() {
+    let repo = workspace_repo();
+
+    repo.run_git_x(&["packages"])
+        .success()
+        .stdout(contains("2 workspace package(s)"))
+        .stdout(contains("cli   crates/cli   Cargo"))
+        .stdout(contains("core  crates/core  Cargo"));
+
+    repo.run_git_x(&["contributors", "--package", "core"])
+        .success()
+        .stdout(contains("Core Dev"))
+        .stdout(contains("Cli Dev").not())
+        .stdout(contains("crates/core"));
+
+    repo.run_git_x(&["summary", "--since", "1 year ago", "--package", "cli"])
+        .success()
+        .stdout(contains("Add cli binary"))
+        .stdout(contains("Add core library").not())
+        .stdout(contains("package: cli (crates/cli)"));
+
+    repo.run_git_x(&["summary", "--package", "core"])
+        .success()
+        .stdout(contains("Files: 2 total"))
+        .stdout(contains("Core Dev"))
+        .stdout(contains("Cli Dev").not());
+
+    repo.run_git_x(&["what", "--package", "core"])
+        .success()
+        .stdout(contains("Package: core (crates/core)"))
+        .stdout(contains("1 commits ahead"))
+        .stdout(contains("crates/core/lib.rs"))
+        .stdout(contains("crates/cli/main.rs").not());
+
+    repo.run_git_x(&["what", "--package", "web"])
+        .success()
+        .stderr(contains("No package named 'web'"));
+}
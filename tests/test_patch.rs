@@ -0,0 +1,123 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::commit::{PatchAction, PatchCommand};
+use git_x::core::traits::Command;
+use predicates::str::contains;
+
+/// `main` plus a `feature` branch two commits ahead of it
+fn repo_with_feature_series() -> common::TestRepo {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("one.txt", "one\n", "Add one");
+    repo.add_commit("two.txt", "two\n", "Add two");
+    repo
+}
+
+#[test]
+#[serial]
+fn test_patch_resolve_range() {
+    assert_eq!(PatchCommand::resolve_range("3"), "HEAD~3..HEAD");
+    assert_eq!(
+        PatchCommand::resolve_range("main..feature"),
+        "main..feature"
+    );
+    assert_eq!(
+        PatchCommand::resolve_range("origin/main"),
+        "origin/main..HEAD"
+    );
+}
+
+#[test]
+#[serial]
+fn test_patch_send_writes_series_with_cover_letter() {
+    let repo = repo_with_feature_series();
+
+    repo.run_git_x(&["patch", "send", "main..feature"])
+        .success()
+        .stdout(contains(
+            "📧 Wrote 2 patch(es) to patches/ with a cover letter",
+        ))
+        .stdout(contains("0000-cover-letter.patch"))
+        .stdout(contains("✅ Series applies cleanly onto main"));
+
+    assert!(repo.path().join("patches/0000-cover-letter.patch").exists());
+    assert!(repo.path().join("patches/0001-Add-one.patch").exists());
+    assert!(repo.path().join("patches/0002-Add-two.patch").exists());
+}
+
+#[test]
+#[serial]
+fn test_patch_send_reports_conflicting_target() {
+    let repo = repo_with_feature_series();
+    repo.checkout_branch("main");
+    repo.create_branch("conflicting");
+    repo.add_commit("one.txt", "different\n", "Conflicting one");
+    repo.checkout_branch("feature");
+
+    repo.run_git_x(&["patch", "send", "main..feature", "--onto", "conflicting"])
+        .success()
+        .stdout(contains(
+            "⚠️  Series does not apply cleanly onto conflicting",
+        ))
+        .stdout(contains("0001-Add-one.patch failed"));
+}
+
+#[test]
+#[serial]
+fn test_patch_send_empty_range() {
+    let repo = repo_with_branch("main");
+
+    repo.run_git_x(&["patch", "send", "main"])
+        .success()
+        .stderr(contains("No commits in main..HEAD"));
+}
+
+#[test]
+#[serial]
+fn test_patch_apply_round_trip() {
+    let repo = repo_with_feature_series();
+    repo.run_git_x(&["patch", "send", "main..feature", "-o", "series"])
+        .success();
+    repo.checkout_branch("main");
+
+    repo.run_git_x(&["patch", "apply", "series"])
+        .success()
+        .stdout(contains("✅ Applied 2 patch(es) onto main"));
+
+    assert_eq!(repo.git(&["log", "-1", "--format=%s"]), "Add two");
+    assert!(repo.path().join("one.txt").exists());
+}
+
+#[test]
+#[serial]
+fn test_patch_apply_conflict_leaves_am_in_progress() {
+    let repo = repo_with_feature_series();
+    repo.run_git_x(&["patch", "send", "main..feature"])
+        .success();
+    repo.checkout_branch("main");
+    repo.add_commit("one.txt", "different\n", "Conflicting one");
+
+    repo.run_git_x(&["patch", "apply", "patches"])
+        .success()
+        .stderr(contains("did not apply"))
+        .stderr(contains("git am --abort"));
+}
+
+#[test]
+#[serial]
+fn test_patch_apply_missing_source_direct() {
+    let repo = repo_with_branch("main");
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+    let result = PatchCommand::new(PatchAction::Apply {
+        source: "nowhere".to_string(),
+        three_way: true,
+    })
+    .execute();
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert!(result.unwrap_err().to_string().contains("does not exist"));
+}
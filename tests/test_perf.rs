@@ -0,0 +1,111 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::analysis::{PerfCommand, PerfFacts, PerfFix};
+use predicates::str::contains;
+use std::process::Command;
+
+fn tuned_facts() -> PerfFacts {
+    PerfFacts {
+        commit_graph: true,
+        write_commit_graph_on_fetch: Some(true),
+        untracked_cache: Some(true),
+        tracked_files: 100,
+        packs: 2,
+        ..Default::default()
+    }
+}
+
+#[test]
+#[serial]
+fn test_perf_evaluate_tuned_repo() {
+    let checks = PerfCommand::evaluate(&tuned_facts());
+
+    assert!(checks.iter().all(|check| check.ok));
+    assert!(checks.iter().all(|check| check.fix.is_none()));
+}
+
+#[test]
+#[serial]
+fn test_perf_evaluate_recommends_fixes() {
+    let facts = PerfFacts {
+        commit_graph_stale: true,
+        write_commit_graph_on_fetch: None,
+        tracked_files: 60_000,
+        fsmonitor_supported: true,
+        packs: 120,
+        ..tuned_facts()
+    };
+    let checks = PerfCommand::evaluate(&facts);
+    let fixes: Vec<PerfFix> = checks
+        .iter()
+        .filter_map(|check| check.fix.clone())
+        .collect();
+
+    assert!(fixes.contains(&PerfFix::Run(vec!["commit-graph", "write", "--reachable"])));
+    assert!(fixes.contains(&PerfFix::Config {
+        key: "fetch.writeCommitGraph",
+        value: "true"
+    }));
+    assert!(fixes.contains(&PerfFix::Config {
+        key: "core.fsmonitor",
+        value: "true"
+    }));
+    assert!(fixes.contains(&PerfFix::Config {
+        key: "core.splitIndex",
+        value: "true"
+    }));
+    // Repacking is advice only
+    let packs = checks.iter().find(|check| check.name == "Packs").unwrap();
+    assert!(!packs.ok);
+    assert!(packs.fix.is_none());
+}
+
+#[test]
+#[serial]
+fn test_perf_fsmonitor_needs_platform_support() {
+    let facts = PerfFacts {
+        tracked_files: 20_000,
+        fsmonitor_supported: false,
+        ..tuned_facts()
+    };
+    let checks = PerfCommand::evaluate(&facts);
+    let fsmonitor = checks
+        .iter()
+        .find(|check| check.name == "File system monitor")
+        .unwrap();
+
+    assert!(!fsmonitor.ok);
+    assert!(fsmonitor.fix.is_none());
+}
+
+#[test]
+#[serial]
+fn test_perf_command_reports_and_applies() {
+    let repo = basic_repo();
+
+    repo.run_git_x(&["perf"])
+        .success()
+        .stdout(contains("⚠️  Commit-graph: missing"))
+        .stdout(contains("git config core.untrackedCache true"))
+        .stdout(contains("git x perf --apply"));
+
+    repo.run_git_x(&["perf", "--apply"])
+        .success()
+        .stdout(contains(
+            "🔧 Applied: git config fetch.writeCommitGraph true",
+        ));
+
+    let config = Command::new("git")
+        .args(["config", "--get", "core.untrackedCache"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&config.stdout).trim(), "true");
+
+    repo.run_git_x(&["perf"])
+        .success()
+        .stdout(contains("✅ Commit-graph: present and up to date"))
+        .stdout(contains("🎉 Nothing to tune"));
+}
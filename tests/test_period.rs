@@ -0,0 +1,120 @@
+use serial_test::serial;
+mod common;
+
+use chrono::{Local, TimeZone};
+use common::repo_with_branch;
+use git_x::core::period::{Age, TimeSpec};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+#[test]
+fn test_age_parse() {
+    let age = |text: &str| text.parse::<Age>().unwrap();
+
+    assert_eq!(age("6m").seconds(), 6 * 30 * 86_400);
+    assert_eq!(age("6m").to_string(), "6 months");
+    assert_eq!(age("2w").seconds(), 14 * 86_400);
+    assert_eq!(age("1 week").to_string(), "1 week");
+    assert_eq!(age("30d").seconds(), age("30 days").seconds());
+    assert_eq!(age("12h").seconds(), 12 * 3_600);
+    assert_eq!(age("1Y").seconds(), 365 * 86_400);
+    assert_eq!(age("1d").before(100_000), 100_000 - 86_400);
+
+    for invalid in ["", "6", "m", "6 fortnights", "-1d", "1.5d"] {
+        let error = invalid.parse::<Age>().unwrap_err().to_string();
+        assert!(error.contains("Invalid age"), "{invalid}: {error}");
+    }
+
+    // Too many seconds for an i64 is an invalid age, not an overflow panic
+    let error = "999999999999999y".parse::<Age>().unwrap_err().to_string();
+    assert!(error.contains("Invalid age"), "{error}");
+}
+
+#[test]
+fn test_time_spec_parse_at() {
+    let now = Local.with_ymd_and_hms(2025, 7, 10, 15, 30, 0).unwrap();
+    let at = |text: &str| TimeSpec::parse_at(text, now).unwrap();
+    let local = |y, mo, d, h, mi| {
+        Local
+            .with_ymd_and_hms(y, mo, d, h, mi, 0)
+            .unwrap()
+            .timestamp()
+    };
+
+    assert_eq!(at("2w").timestamp(), now.timestamp() - 14 * 86_400);
+    assert_eq!(at("2w").to_string(), "2 weeks ago");
+    assert_eq!(at("3 months ago").timestamp(), at("3 months").timestamp());
+    assert_eq!(at("1 week ago").to_string(), "1 week ago");
+    assert_eq!(at("now").timestamp(), now.timestamp());
+    assert_eq!(at("today").timestamp(), local(2025, 7, 10, 0, 0));
+    assert_eq!(at("Yesterday").timestamp(), local(2025, 7, 9, 0, 0));
+    assert_eq!(at("2025-07-01").timestamp(), local(2025, 7, 1, 0, 0));
+    assert_eq!(at("2025-07-01").to_string(), "2025-07-01");
+    assert_eq!(at("2025-07-01T09:30").timestamp(), local(2025, 7, 1, 9, 30));
+    assert_eq!(
+        at("2025-07-01 09:30:00").timestamp(),
+        local(2025, 7, 1, 9, 30)
+    );
+    assert_eq!(at("2025-07-01T09:30:00Z").timestamp(), 1_751_362_200);
+    assert_eq!(at("2w").git_arg(), format!("@{}", at("2w").timestamp()));
+    assert_eq!(at("2w").reference(), None);
+
+    for invalid in ["", "soon", "2025-13-01", "last week", "3 fortnights ago"] {
+        let error = TimeSpec::parse_at(invalid, now).unwrap_err().to_string();
+        assert!(error.contains("Invalid time"), "{invalid}: {error}");
+        assert!(error.contains("last-tag"), "{invalid}: {error}");
+    }
+}
+
+fn tag(repo: &common::TestRepo, name: &str) {
+    std::process::Command::new("git")
+        .args(["tag", name])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+}
+
+#[test]
+#[serial]
+fn test_time_spec_from_tags() {
+    let repo = repo_with_branch("main");
+    repo.run_git_x(&["summary", "--since", "last-tag"])
+        .stderr(contains("No tag reachable from HEAD"));
+
+    repo.add_commit("a.txt", "a\n", "Release work");
+    tag(&repo, "v1.0.0");
+    repo.add_commit("b.txt", "b\n", "After the release");
+    tag(&repo, "nightly");
+    repo.add_commit("c.txt", "c\n", "After nightly");
+
+    repo.run_git_x(&["since", "last-release"])
+        .success()
+        .stdout(contains("Commits since v1.0.0"))
+        .stdout(contains("After the release"))
+        .stdout(contains("After nightly"));
+    repo.run_git_x(&["since", "last-tag"])
+        .success()
+        .stdout(contains("Commits since nightly"))
+        .stdout(contains("After the release").not());
+    repo.run_git_x(&["contributors", "--since", "last-release"])
+        .success()
+        .stdout(contains("v1.0.0"));
+}
+
+#[test]
+#[serial]
+fn test_time_spec_errors_name_the_value() {
+    let repo = repo_with_branch("main");
+
+    repo.run_git_x(&["summary", "--since", "soon"])
+        .stderr(contains("Invalid time 'soon'"));
+    repo.run_git_x(&["heatmap", "--since", "whenever"])
+        .stderr(contains("Invalid time 'whenever'"));
+    repo.run_git_x(&["audit-merges", "--since", "a while", "--branch", "main"])
+        .stderr(contains("Invalid time 'a while'"));
+    repo.run_git_x(&["since", "nope"])
+        .stderr(contains("'nope' is neither a reference nor a time"));
+    repo.run_git_x(&["since", "2w"])
+        .success()
+        .stdout(contains("Commits since '2 weeks ago'"));
+}
@@ -0,0 +1,150 @@
+use serial_test::serial;
+mod common;
+
+use common::{TestRepo, basic_repo, git};
+use git_x::commands::branch::PullRequest;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// A repository whose `origin` has pull request 7 under `refs/pull/7/head`
+fn repo_with_pull_request() -> (TestRepo, TestRepo, String) {
+    let repo = basic_repo();
+    let base = git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+    let remote = repo.setup_remote(&base);
+    repo.create_branch("contribution");
+    repo.add_commit("fix.txt", "fix\n", "Fix the parser");
+    git(
+        repo.path(),
+        &["push", "--quiet", "origin", "contribution:refs/pull/7/head"],
+    );
+    repo.checkout_branch(&base);
+    git(repo.path(), &["branch", "-D", "contribution"]);
+    (repo, remote, base)
+}
+
+#[test]
+fn test_parse_pull_request() {
+    let json = r#"{"headRefName":"fix-parser","headRepository":{"id":"R_1","name":"app"},"headRepositoryOwner":{"id":"U_1","login":"octocat"},"isCrossRepository":true,"maintainerCanModify":true,"number":42,"state":"OPEN","title":"Fix the parser"}"#;
+    let pr = PullRequest::parse(json).unwrap();
+    assert_eq!(pr.number, 42);
+    assert_eq!(pr.title, "Fix the parser");
+    assert_eq!(pr.head_branch, "fix-parser");
+    assert_eq!(pr.fork, Some(("octocat".to_string(), "app".to_string())));
+    assert!(pr.maintainer_can_modify);
+
+    let same_repo = r#"{"headRefName":"feature","headRepository":{"name":"app"},"headRepositoryOwner":{"login":"org"},"isCrossRepository":false,"maintainerCanModify":false,"number":3,"state":"MERGED","title":"Feature"}"#;
+    let pr = PullRequest::parse(same_repo).unwrap();
+    assert_eq!(pr.fork, None);
+    assert_eq!(pr.state, "MERGED");
+
+    assert!(PullRequest::parse(r#"{"state":"OPEN"}"#).is_err());
+    assert_eq!(PullRequest::local_branch(42), "pr/42");
+}
+
+#[test]
+fn test_fork_url_keeps_url_style() {
+    assert_eq!(
+        PullRequest::fork_url("git@github.com:org/app.git", "octocat", "app").as_deref(),
+        Some("git@github.com:octocat/app.git")
+    );
+    assert_eq!(
+        PullRequest::fork_url("https://github.com/org/app", "octocat", "app-fork").as_deref(),
+        Some("https://github.com/octocat/app-fork")
+    );
+    assert_eq!(PullRequest::fork_url("/srv/app", "octocat", "app"), None);
+}
+
+#[test]
+#[serial]
+fn test_pr_checkout_and_update() {
+    let (repo, remote, base) = repo_with_pull_request();
+
+    repo.run_git_x(&["pr", "checkout", "7"])
+        .success()
+        .stdout(contains("Checked out #7 as 'pr/7'"))
+        .stdout(contains("git x pr clean"));
+    assert_eq!(
+        git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]),
+        "pr/7"
+    );
+    assert_eq!(
+        git(repo.path(), &["log", "-1", "--format=%s"]),
+        "Fix the parser"
+    );
+
+    // New commits on the pull request fast-forward the branch
+    let contributor = tempfile::tempdir().unwrap();
+    git(
+        contributor.path(),
+        &["clone", "--quiet", remote.path().to_str().unwrap(), "."],
+    );
+    git(
+        contributor.path(),
+        &["fetch", "--quiet", "origin", "refs/pull/7/head"],
+    );
+    git(contributor.path(), &["checkout", "--quiet", "FETCH_HEAD"]);
+    git(
+        contributor.path(),
+        &[
+            "-c",
+            "user.name=Contributor",
+            "-c",
+            "user.email=c@example.com",
+            "commit",
+            "--quiet",
+            "--allow-empty",
+            "-m",
+            "Address review",
+        ],
+    );
+    git(
+        contributor.path(),
+        &["push", "--quiet", "origin", "HEAD:refs/pull/7/head"],
+    );
+
+    repo.checkout_branch(&base);
+    repo.run_git_x(&["pr", "checkout", "7"]).success();
+    assert_eq!(
+        git(repo.path(), &["log", "-1", "--format=%s"]),
+        "Address review"
+    );
+
+    repo.run_git_x(&["pr", "checkout", "99"])
+        .success()
+        .stderr(contains("Pull request #99 was not found on 'origin'"));
+}
+
+#[test]
+#[serial]
+fn test_pr_clean() {
+    let (repo, _remote, base) = repo_with_pull_request();
+    repo.run_git_x(&["pr", "checkout", "7"]).success();
+
+    repo.run_git_x(&["pr", "clean", "--all"])
+        .success()
+        .stdout(contains("pr/7 (#7) is checked out"));
+
+    repo.checkout_branch(&base);
+    repo.run_git_x(&["pr", "clean"])
+        .success()
+        .stdout(contains("pr/7 (#7) is still open"));
+
+    // Merged into the base's default branch: gone without --all
+    git(repo.path(), &["merge", "--quiet", "pr/7"]);
+    git(repo.path(), &["push", "--quiet", "origin", &base]);
+    git(repo.path(), &["remote", "set-head", "origin", "--auto"]);
+    repo.run_git_x(&["pr", "clean", "--dry-run"])
+        .success()
+        .stdout(contains("pr/7 (#7, merged into origin/HEAD)"))
+        .stdout(contains("Run without --dry-run"));
+    assert!(!git(repo.path(), &["branch", "--list", "pr/7"]).is_empty());
+
+    repo.run_git_x(&["pr", "clean"])
+        .success()
+        .stdout(contains("Deleted 1 branch(es)"));
+    assert!(git(repo.path(), &["branch", "--list", "pr/7"]).is_empty());
+    repo.run_git_x(&["pr", "clean"])
+        .success()
+        .stdout(contains("No pull request branches to clean"))
+        .stdout(contains("still open").not());
+}
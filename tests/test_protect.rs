@@ -0,0 +1,139 @@
+use serial_test::serial;
+mod common;
+
+use common::{TestRepo, basic_repo, git};
+use git_x::commands::branch::{ProtectCheck, ProtectedBranches};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// `git` with the built binary on PATH, since the installed hooks call `git x`
+fn git_with_hooks(repo: &TestRepo, args: &[&str]) -> assert_cmd::assert::Assert {
+    let bin_dir = assert_cmd::cargo::cargo_bin("git-x")
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    assert_cmd::Command::new("git")
+        .args(args)
+        .current_dir(repo.path())
+        .env("PATH", path)
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .assert()
+}
+
+#[test]
+fn test_protected_branches_match_patterns() {
+    let protected = ProtectedBranches::new(vec!["main".to_string(), "release/*".to_string()]);
+
+    assert!(protected.matches("main"));
+    assert!(protected.matches("release/1.2"));
+    assert!(!protected.matches("mainline"));
+    assert!(!protected.matches("feature/release/1.2"));
+}
+
+#[test]
+fn test_blocked_pushes() {
+    let protected = ProtectedBranches::new(vec!["main".to_string()]);
+    let zero = "0".repeat(40);
+    let sha = "a".repeat(40);
+    let updates = format!(
+        "refs/heads/feature {sha} refs/heads/feature {zero}\n\
+         refs/heads/feature {sha} refs/heads/main {sha}\n\
+         (delete) {zero} refs/heads/main {sha}\n\
+         refs/tags/v1 {sha} refs/tags/main {zero}\n"
+    );
+
+    assert_eq!(protected.blocked_pushes(&updates), ["main"]);
+    assert!(protected.blocked_pushes("").is_empty());
+    assert_eq!("push".parse::<ProtectCheck>().unwrap(), ProtectCheck::Push);
+    assert!("merge".parse::<ProtectCheck>().is_err());
+}
+
+#[test]
+#[serial]
+fn test_protect_registers_branches_and_installs_hooks() {
+    let repo = basic_repo();
+
+    repo.run_git_x(&["protect"])
+        .success()
+        .stdout(contains("No protected branches"));
+
+    // An existing shell hook keeps running after the guard
+    let pre_commit = repo.path().join(".git/hooks/pre-commit");
+    std::fs::write(&pre_commit, "#!/bin/sh\nmake lint\n").unwrap();
+
+    repo.run_git_x(&["protect", "main", "release/*"])
+        .success()
+        .stdout(contains("Protected 'main'"))
+        .stdout(contains("Protected 'release/*'"))
+        .stdout(contains("Installed pre-commit guard"))
+        .stdout(contains("Installed pre-push guard"));
+    assert_eq!(
+        git(
+            repo.path(),
+            &["config", "--get-all", "git-x.protect.branch"]
+        ),
+        "main\nrelease/*"
+    );
+    let hook = std::fs::read_to_string(&pre_commit).unwrap();
+    assert!(hook.starts_with("#!/bin/sh\n"));
+    assert!(hook.contains("git x protect --check commit || exit 1"));
+    assert!(hook.ends_with("make lint\n"));
+    let hook = std::fs::read_to_string(repo.path().join(".git/hooks/pre-push")).unwrap();
+    assert!(hook.contains("git x protect --check push || exit 1"));
+
+    repo.run_git_x(&["protect", "main"])
+        .success()
+        .stdout(contains("'main' is already protected"))
+        .stdout(contains("pre-commit guard already installed"));
+
+    repo.run_git_x(&["protect", "--remove", "release/*", "develop"])
+        .success()
+        .stdout(contains("'release/*' is no longer protected"))
+        .stdout(contains("'develop' was not protected"));
+    repo.run_git_x(&["protect"])
+        .success()
+        .stdout(contains("  main"))
+        .stdout(contains("release/*").not());
+}
+
+#[test]
+#[serial]
+fn test_protect_blocks_commits_and_pushes() {
+    let repo = basic_repo();
+    let base = git(repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+    let remote = repo.setup_remote(&base);
+    repo.run_git_x(&["protect", &base]).success();
+
+    std::fs::write(repo.path().join("change.txt"), "change\n").unwrap();
+    git(repo.path(), &["add", "change.txt"]);
+    git_with_hooks(&repo, &["commit", "-m", "direct commit"])
+        .failure()
+        .stderr(contains(format!("'{base}' is protected")))
+        .stderr(contains("git switch -c <feature-branch>"));
+
+    // Commits on other branches go through, but pushing them to the protected branch does not
+    git(repo.path(), &["switch", "-c", "feature"]);
+    git_with_hooks(&repo, &["commit", "-m", "feature commit"]).success();
+    git_with_hooks(&repo, &["push", "origin", &format!("feature:{base}")])
+        .failure()
+        .stderr(contains(format!(
+            "Pushing to protected branch '{base}' is blocked"
+        )));
+    git_with_hooks(&repo, &["push", "origin", "feature"]).success();
+    assert_eq!(
+        git(remote.path(), &["log", "-1", "--format=%s", &base]),
+        "initial"
+    );
+
+    git(repo.path(), &["switch", &base]);
+    git_with_hooks(
+        &repo,
+        &["commit", "--allow-empty", "--no-verify", "-m", "bypass"],
+    )
+    .success();
+}
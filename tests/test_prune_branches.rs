@@ -1,7 +1,7 @@
 use serial_test::serial;
 mod common;
 
-use common::repo_with_merged_branch;
+use common::{repo_with_branch, repo_with_merged_branch};
 use git_x::commands::branch::PruneBranchesCommand;
 use git_x::core::traits::Command;
 use predicates::boolean::PredicateBooleanExt;
@@ -43,6 +43,7 @@ fn test_prune_branches_respects_exclude() {
         .success()
         .stdout(contains("Deleted branch feature/delete-me"))
         .stdout(contains("✅ No merged branches to prune").not());
+    assert!(git(&repo, &["branch"]).contains("feature/keep-me"));
 }
 
 #[test]
@@ -126,3 +127,201 @@ fn test_prune_branches_dry_run_with_except() {
         .stdout(contains("🧪 (dry run)"))
         .stdout(contains("feature/delete-me"));
 }
+
+#[test]
+#[serial]
+fn test_prune_branches_many_requires_yes() {
+    let repo = repo_with_merged_branch("feature/one", "main");
+    repo.create_branch("feature/two");
+    repo.checkout_branch("main");
+    repo.merge_branch("feature/two");
+    std::process::Command::new("git")
+        .args(["config", "git-x.safety.typeToConfirm", "1"])
+        .current_dir(repo.path())
+        .status()
+        .unwrap();
+
+    repo.run_git_x(&["prune-branches"])
+        .success()
+        .stderr(contains("Refusing to delete 2 branches"));
+    repo.run_git_x(&["clean-branches"])
+        .success()
+        .stderr(contains("Refusing to delete 2 branches"));
+
+    repo.run_git_x(&["prune-branches", "--yes"])
+        .success()
+        .stdout(contains("Deleted 2 merged branches"));
+}
+
+#[test]
+#[serial]
+fn test_prune_branches_uses_default_branch_as_baseline() {
+    let repo = repo_with_branch("trunk");
+    std::process::Command::new("git")
+        .args(["config", "init.defaultBranch", "trunk"])
+        .current_dir(repo.path())
+        .status()
+        .unwrap();
+    repo.create_branch("feature/done");
+    repo.add_commit("done.txt", "done", "Finish feature");
+    repo.checkout_branch("trunk");
+    repo.merge_branch("feature/done");
+    // Merged into trunk, not into the branch being worked on
+    repo.create_branch("feature/wip");
+    std::process::Command::new("git")
+        .args(["reset", "-q", "--hard", "HEAD~1"])
+        .current_dir(repo.path())
+        .status()
+        .unwrap();
+
+    repo.run_git_x(&["prune-branches", "--dry-run"])
+        .success()
+        .stdout(contains("Would delete: feature/done"))
+        .stdout(contains("Would delete: trunk").not());
+    repo.run_git_x(&["prune-branches", "--yes"])
+        .success()
+        .stdout(contains("feature/done"));
+    repo.run_git_x(&["what"])
+        .success()
+        .stdout(contains("trunk"));
+}
+
+#[test]
+#[serial]
+fn test_prune_branches_without_baseline() {
+    let repo = repo_with_branch("feature/a");
+    repo.create_branch("feature/b");
+    std::process::Command::new("git")
+        .args(["config", "init.defaultBranch", "none"])
+        .current_dir(repo.path())
+        .status()
+        .unwrap();
+
+    repo.run_git_x(&["prune-branches"])
+        .success()
+        .stderr(contains("No default branch found"));
+    repo.run_git_x(&["clean-branches", "--dry-run"])
+        .success()
+        .stderr(contains("No default branch found"));
+    repo.run_git_x(&["what"])
+        .success()
+        .stderr(contains("pass the branch to compare against"));
+}
+
+fn git(repo: &common::TestRepo, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(repo.path())
+        .env("GIT_COMMITTER_DATE", "2020-01-01T00:00:00+0000")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// `main` with `old-merged` (merged, last commit in 2020), `new-merged` (merged today)
+/// and `old-unmerged` (not merged, last commit in 2020)
+fn repo_with_aged_branches() -> common::TestRepo {
+    let repo = repo_with_branch("main");
+    for (branch, old) in [
+        ("old-merged", true),
+        ("new-merged", false),
+        ("old-unmerged", true),
+    ] {
+        repo.create_branch(branch);
+        std::fs::write(repo.path().join(format!("{branch}.txt")), branch).unwrap();
+        git(&repo, &["add", "-A"]);
+        if old {
+            git(&repo, &["commit", "-q", "-m", branch]);
+        } else {
+            repo.add_commit(&format!("{branch}-2.txt"), branch, branch);
+        }
+        repo.checkout_branch("main");
+        if branch != "old-unmerged" {
+            repo.merge_branch(branch);
+        }
+    }
+    repo
+}
+
+#[test]
+#[serial]
+fn test_prune_branches_older_than() {
+    let repo = repo_with_aged_branches();
+
+    repo.run_git_x(&["prune-branches", "--older-than", "6m", "--dry-run"])
+        .success()
+        .stdout(contains("Would delete: old-merged"))
+        .stdout(contains("Would delete: new-merged").not())
+        .stdout(contains("Would delete: old-unmerged").not());
+    repo.run_git_x(&["prune-branches", "--older-than", "100y", "--dry-run"])
+        .success()
+        .stdout(contains("No merged branches older than 100 years to prune"));
+
+    repo.run_git_x(&["prune-branches", "--older-than", "6 months", "--yes"])
+        .success()
+        .stdout(contains("Deleted 1 merged branches"));
+    let branches = git(&repo, &["branch", "--format=%(refname:short)"]);
+    assert!(!branches.contains("old-merged"));
+    assert!(branches.contains("new-merged"));
+    assert!(branches.contains("old-unmerged"));
+}
+
+#[test]
+#[serial]
+fn test_prune_branches_allow_unmerged_archives_and_needs_confirmation() {
+    let repo = repo_with_aged_branches();
+
+    repo.run_git_x(&[
+        "prune-branches",
+        "--older-than",
+        "6m",
+        "--allow-unmerged",
+        "--dry-run",
+    ])
+    .success()
+    .stdout(contains("Would delete: old-merged\n"))
+    .stdout(contains(
+        "Would delete: old-unmerged (unmerged, archived as archive/old-unmerged-",
+    ))
+    .stdout(contains("Would delete: new-merged").not());
+
+    // Unmerged work needs someone to confirm, even with --yes
+    repo.run_git_x(&[
+        "prune-branches",
+        "--older-than",
+        "6m",
+        "--allow-unmerged",
+        "--yes",
+    ])
+    .success()
+    .stderr(contains(
+        "Refusing to delete 1 unmerged branch(es) without interactive confirmation",
+    ));
+    assert!(git(&repo, &["branch"]).contains("old-unmerged"));
+    assert!(git(&repo, &["tag"]).is_empty());
+
+    // Excepted branches survive, unmerged or not
+    repo.run_git_x(&[
+        "prune-branches",
+        "--older-than",
+        "6m",
+        "--allow-unmerged",
+        "--except",
+        "old-unmerged, other",
+        "--yes",
+    ])
+    .success()
+    .stdout(contains("Deleted 1 branches"));
+    let branches = git(&repo, &["branch", "--format=%(refname:short)"]);
+    assert!(!branches.contains("old-merged"));
+    assert!(branches.contains("old-unmerged"));
+    assert!(git(&repo, &["tag"]).is_empty());
+
+    repo.run_git_x(&["prune-branches", "--allow-unmerged"])
+        .failure()
+        .stderr(contains("--older-than"));
+    repo.run_git_x(&["prune-branches", "--older-than", "soon"])
+        .success()
+        .stderr(contains("Invalid age 'soon'"));
+}
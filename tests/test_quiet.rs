@@ -0,0 +1,96 @@
+use serial_test::serial;
+mod common;
+
+use common::{basic_repo, repo_with_branch};
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::process::Command;
+
+#[test]
+#[serial]
+fn test_quiet_new_prints_branch_name() {
+    let repo = basic_repo();
+
+    repo.run_git_x(&["new", "feature/quiet", "--quiet"])
+        .success()
+        .stdout("feature/quiet\n")
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+#[serial]
+fn test_quiet_flag_before_subcommand() {
+    let repo = basic_repo();
+
+    repo.run_git_x(&["-q", "new", "feature/early"])
+        .success()
+        .stdout("feature/early\n");
+}
+
+#[test]
+#[serial]
+fn test_quiet_error_exits_non_zero() {
+    let repo = basic_repo();
+    repo.run_git_x(&["new", "existing"]).success();
+
+    repo.run_git_x(&["new", "existing", "-q"])
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(contains("error: "))
+        .stderr(contains("already exists"));
+}
+
+#[test]
+#[serial]
+fn test_quiet_prune_lists_deleted_branches() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("merged-one");
+    repo.checkout_branch("main");
+
+    repo.run_git_x(&["prune-branches", "--dry-run", "-q"])
+        .success()
+        .stdout("merged-one\n");
+
+    repo.run_git_x(&["prune-branches", "-q"])
+        .success()
+        .stdout("merged-one\n");
+
+    // Nothing left to prune prints nothing at all
+    repo.run_git_x(&["prune-branches", "-q"])
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+#[serial]
+fn test_quiet_sync_prints_nothing_on_success() {
+    let repo = repo_with_branch("main");
+    let _remote = repo.setup_remote("main");
+
+    repo.run_git_x(&["sync", "--quiet"])
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+#[serial]
+fn test_quiet_fixup_prints_commit_hash() {
+    let repo = basic_repo();
+    std::fs::write(repo.path().join("README.md"), "changed").unwrap();
+    Command::new("git")
+        .args(["add", "README.md"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    let assert = repo.run_git_x(&["fixup", "HEAD", "-q"]).success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+
+    let head = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    assert_eq!(stdout.trim(), String::from_utf8_lossy(&head.stdout).trim());
+}
@@ -0,0 +1,103 @@
+use serial_test::serial;
+mod common;
+
+use common::{TestRepo, repo_with_branch};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// `feature` has commits A and B editing the same line, then C; `main` squash-merged
+/// A and B and changed that line again, so rebasing A and B conflicts
+fn squash_merged_repo() -> (TestRepo, TestRepo) {
+    let repo = repo_with_branch("main");
+    repo.add_commit("notes.txt", "base\nend\n", "Add notes");
+    let remote = repo.setup_remote("main");
+
+    repo.create_branch("feature");
+    repo.add_commit("notes.txt", "first draft\nend\n", "A");
+    repo.add_commit("notes.txt", "second draft\nend\n", "B");
+    repo.add_commit("other.txt", "c\n", "C");
+
+    repo.checkout_branch("main");
+    repo.add_commit("notes.txt", "second draft\nend\n", "Feature (#12)");
+    repo.add_commit("notes.txt", "final\nend\n", "Polish notes");
+    repo.git(&["push", "-q", "origin", "main"]);
+
+    repo.checkout_branch("feature");
+    repo.git(&["branch", "-q", "--set-upstream-to", "origin/main"]);
+    (repo, remote)
+}
+
+#[test]
+#[serial]
+fn test_sync_skips_commits_squashed_upstream() {
+    let (repo, _remote) = squash_merged_repo();
+
+    repo.run_git_x(&["sync"])
+        .success()
+        .stdout(contains("✅ Rebased 1 commits onto origin/main"))
+        .stdout(contains("⏭️  Skipped"))
+        .stdout(contains("A (one of 2 commits squashed upstream into"))
+        .stdout(contains("B (one of 2 commits squashed upstream into"))
+        .stdout(contains("'Feature (#12)'"));
+
+    assert_eq!(
+        repo.git(&["log", "--format=%s", "-4"]),
+        "C\nPolish notes\nFeature (#12)\nAdd notes"
+    );
+}
+
+#[test]
+#[serial]
+fn test_sync_rolls_back_on_a_real_conflict() {
+    let (repo, _remote) = squash_merged_repo();
+    repo.add_commit("notes.txt", "my own take\nend\n", "D");
+    let before = repo.git(&["rev-parse", "HEAD"]);
+
+    repo.run_git_x(&["sync"])
+        .success()
+        .stderr(contains(
+            "D, which conflicts in notes.txt and isn't upstream yet",
+        ))
+        .stderr(contains("git x rebase continue-smart"));
+
+    assert_eq!(repo.git(&["rev-parse", "HEAD"]), before);
+    assert_eq!(repo.git(&["status", "--porcelain"]), "");
+}
+
+#[test]
+#[serial]
+fn test_rebase_continue_smart() {
+    let (repo, _remote) = squash_merged_repo();
+    repo.checkout_branch("main");
+    repo.add_commit("other.txt", "picked\n", "Pick C");
+    repo.checkout_branch("feature");
+    repo.add_commit("other.txt", "picked\n", "C2");
+    repo.add_commit("notes.txt", "my own take\nend\n", "D");
+
+    // Stops on the conflict in A
+    let rebase = std::process::Command::new("git")
+        .args(["rebase", "main"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    assert!(!rebase.status.success());
+    repo.run_git_x(&["rebase", "continue-smart"])
+        .success()
+        .stdout(contains("A (one of 2 commits squashed upstream"))
+        .stdout(contains("⚠️  Stopped on"))
+        .stdout(contains(
+            "D: it isn't upstream yet and conflicts in:\n   notes.txt",
+        ))
+        .stdout(contains("✅").not());
+
+    std::fs::write(repo.path().join("notes.txt"), "merged\nend\n").unwrap();
+    repo.git(&["add", "notes.txt"]);
+    repo.run_git_x(&["rebase", "continue-smart"])
+        .success()
+        .stdout(contains("✅ Rebase finished"));
+    assert_eq!(repo.git(&["log", "-1", "--format=%s"]), "D");
+
+    repo.run_git_x(&["rebase", "continue-smart"])
+        .success()
+        .stderr(contains("No rebase in progress"));
+}
@@ -0,0 +1,112 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::domain::{Branch, CommitId, RefName, RemoteBranch};
+
+#[test]
+fn test_branch_accepts_valid_names() {
+    let branch: Branch = "feature/login".parse().unwrap();
+    assert_eq!(branch.as_str(), "feature/login");
+    assert_eq!(branch.to_string(), "feature/login");
+    assert_eq!(String::from(branch), "feature/login");
+}
+
+#[test]
+fn test_branch_rejects_invalid_names() {
+    let empty = Branch::new("").unwrap_err().to_string();
+    assert!(empty.contains("Branch name cannot be empty"));
+
+    assert!(Branch::new("has space").is_err());
+    assert!(Branch::new("-leading-dash").is_err());
+    assert!(Branch::new("HEAD").is_err());
+}
+
+#[test]
+fn test_ref_name_allows_revision_syntax() {
+    for rev in [
+        "HEAD~2",
+        "main",
+        "v1.0.0",
+        "abc123",
+        "main..feature",
+        "HEAD@{1}",
+    ] {
+        assert!(RefName::new(rev).is_ok(), "{rev} should be accepted");
+    }
+}
+
+#[test]
+fn test_ref_name_rejects_invalid_references() {
+    assert!(
+        RefName::new("")
+            .unwrap_err()
+            .to_string()
+            .contains("Reference cannot be empty")
+    );
+    assert!(
+        RefName::new("--all")
+            .unwrap_err()
+            .to_string()
+            .contains("cannot start with '-'")
+    );
+    assert!(RefName::new("two words").is_err());
+}
+
+#[test]
+fn test_commit_id_validation() {
+    let commit = CommitId::new("0123456789abcdef").unwrap();
+    assert_eq!(commit.short(), "0123456");
+    assert_eq!(CommitId::new("abcd").unwrap().short(), "abcd");
+
+    assert!(CommitId::new("abc").is_err());
+    assert!(CommitId::new("not-a-hash").is_err());
+}
+
+#[test]
+fn test_remote_branch_splits_at_first_slash() {
+    let upstream: RemoteBranch = "origin/feature/login".parse().unwrap();
+    assert_eq!(upstream.remote(), "origin");
+    assert_eq!(upstream.branch().as_str(), "feature/login");
+    assert_eq!(upstream.to_string(), "origin/feature/login");
+}
+
+#[test]
+fn test_remote_branch_rejects_missing_parts() {
+    let no_slash = "main".parse::<RemoteBranch>().unwrap_err().to_string();
+    assert!(no_slash.contains("<remote>/<branch>"));
+
+    let no_remote = "/main".parse::<RemoteBranch>().unwrap_err().to_string();
+    assert!(no_remote.contains("Remote name cannot be empty"));
+
+    let no_branch = "origin/".parse::<RemoteBranch>().unwrap_err().to_string();
+    assert!(no_branch.contains("Branch name cannot be empty"));
+}
+
+#[test]
+fn test_conversions_into_ref_name() {
+    let branch = Branch::new("main").unwrap();
+    assert_eq!(RefName::from(branch).as_str(), "main");
+
+    let commit = CommitId::new("abcdef1").unwrap();
+    assert_eq!(RefName::from(commit).as_str(), "abcdef1");
+
+    let upstream: RemoteBranch = "origin/main".parse().unwrap();
+    assert_eq!(RefName::from(upstream).as_str(), "origin/main");
+}
+
+#[test]
+#[serial]
+fn test_commit_id_resolve() {
+    let repo = basic_repo();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let head = CommitId::resolve(&"HEAD".parse().unwrap());
+    let missing = CommitId::resolve(&"no-such-ref".parse().unwrap());
+
+    std::env::set_current_dir(original_dir).unwrap();
+
+    assert_eq!(head.unwrap().as_str().len(), 40);
+    assert!(missing.is_err());
+}
@@ -0,0 +1,191 @@
+use serial_test::serial;
+mod common;
+
+use common::{basic_repo, git};
+use git_x::commands::repository::RemoteUrlChange;
+use predicates::prelude::*;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+/// Bare repositories at `<hosts>/old/app.git` and `<hosts>/new/app.git`
+fn hosts() -> TempDir {
+    let hosts = TempDir::new().unwrap();
+    for host in ["old", "new"] {
+        let dir = hosts.path().join(host).join("app.git");
+        std::fs::create_dir_all(&dir).unwrap();
+        git(&dir, &["init", "-q", "--bare"]);
+    }
+    hosts
+}
+
+/// Run git-x from a directory that isn't a repository
+fn run_outside_repo(args: &[&str]) -> assert_cmd::assert::Assert {
+    let outside = TempDir::new().unwrap();
+    assert_cmd::Command::cargo_bin("git-x")
+        .unwrap()
+        .args(args)
+        .current_dir(outside.path())
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .assert()
+}
+
+#[test]
+fn test_rewrite_url_respects_boundaries() {
+    let from = "git@gitlab.com:org";
+    let to = "git@github.com:org";
+    assert_eq!(
+        RemoteUrlChange::rewrite_url("git@gitlab.com:org/app.git", from, to).as_deref(),
+        Some("git@github.com:org/app.git")
+    );
+    assert_eq!(
+        RemoteUrlChange::rewrite_url("git@gitlab.com:org-archive/app.git", from, to),
+        None
+    );
+    assert_eq!(
+        RemoteUrlChange::rewrite_url("https://gitlab.com/org/app.git", from, to),
+        None
+    );
+    // A prefix ending in a separator matches anything after it
+    assert_eq!(
+        RemoteUrlChange::rewrite_url(
+            "https://gitlab.com/org/app.git",
+            "https://gitlab.com/",
+            "https://github.com/"
+        )
+        .as_deref(),
+        Some("https://github.com/org/app.git")
+    );
+}
+
+#[test]
+fn test_plan_from_config_output() {
+    let config = "remote.origin.url git@gitlab.com:org/app.git\n\
+                  remote.origin.pushurl git@gitlab.com:org/app.git\n\
+                  remote.upstream.url https://example.com/app.git\n\
+                  branch.main.remote origin\n\
+                  branch.topic.remote git@gitlab.com:org/app.git";
+    let changes = RemoteUrlChange::plan(config, "git@gitlab.com:org", "git@github.com:org");
+
+    let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        [
+            "remote.origin.url",
+            "remote.origin.pushurl",
+            "branch.topic.remote"
+        ]
+    );
+    assert_eq!(changes[0].new, "git@github.com:org/app.git");
+    assert_eq!(changes[0].remote(), Some("origin"));
+    assert_eq!(changes[2].remote(), None);
+}
+
+#[test]
+#[serial]
+fn test_remotes_migrate_rewrites_and_verifies() {
+    let repo = basic_repo();
+    let hosts = hosts();
+    let old_prefix = hosts.path().join("old").display().to_string();
+    let new_prefix = hosts.path().join("new").display().to_string();
+    let old_url = format!("{old_prefix}/app.git");
+    let new_url = format!("{new_prefix}/app.git");
+
+    git(repo.path(), &["remote", "add", "origin", &old_url]);
+    git(repo.path(), &["config", "branch.topic.remote", &old_url]);
+
+    repo.run_git_x(&[
+        "remotes",
+        "migrate",
+        "--from",
+        &old_prefix,
+        "--to",
+        &new_prefix,
+        "--dry-run",
+    ])
+    .success()
+    .stdout(contains(format!(
+        "remote.origin.url: {old_url} → {new_url}"
+    )))
+    .stdout(contains("2 setting(s) across 1 repo(s)"))
+    .stdout(contains("Run without --dry-run"));
+    assert_eq!(git(repo.path(), &["remote", "get-url", "origin"]), old_url);
+
+    repo.run_git_x(&[
+        "remotes",
+        "migrate",
+        "--from",
+        &old_prefix,
+        "--to",
+        &new_prefix,
+    ])
+    .success()
+    .stdout(contains("Updated 2 setting(s)"))
+    .stdout(contains("'origin' is reachable"));
+    assert_eq!(git(repo.path(), &["remote", "get-url", "origin"]), new_url);
+    assert_eq!(
+        git(repo.path(), &["config", "branch.topic.remote"]),
+        new_url
+    );
+
+    repo.run_git_x(&[
+        "remotes",
+        "migrate",
+        "--from",
+        &old_prefix,
+        "--to",
+        &new_prefix,
+    ])
+    .success()
+    .stdout(contains("Nothing to rewrite"));
+}
+
+#[test]
+#[serial]
+fn test_remotes_migrate_across_repos() {
+    let first = basic_repo();
+    let second = basic_repo();
+    let not_a_repo = TempDir::new().unwrap();
+    let hosts = hosts();
+    let old_prefix = hosts.path().join("old").display().to_string();
+    let new_prefix = hosts.path().join("new").display().to_string();
+
+    for repo in [&first, &second] {
+        git(
+            repo.path(),
+            &["remote", "add", "origin", &format!("{old_prefix}/app.git")],
+        );
+    }
+
+    run_outside_repo(&[
+        "remotes",
+        "migrate",
+        "--from",
+        &old_prefix,
+        "--to",
+        &new_prefix,
+        "--no-verify",
+        first.path().to_str().unwrap(),
+        second.path().to_str().unwrap(),
+        not_a_repo.path().to_str().unwrap(),
+    ])
+    .success()
+    .stdout(contains("2 setting(s) across 2 repo(s)"))
+    .stdout(contains("is not a git repository"))
+    .stdout(contains("reachable").not());
+
+    for repo in [&first, &second] {
+        assert_eq!(
+            git(repo.path(), &["remote", "get-url", "origin"]),
+            format!("{new_prefix}/app.git")
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_remotes_migrate_rejects_identical_prefixes() {
+    let repo = basic_repo();
+    repo.run_git_x(&["remotes", "migrate", "--from", "a", "--to", "a"])
+        .success()
+        .stderr(contains("nothing to rewrite"));
+}
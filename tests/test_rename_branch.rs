@@ -170,7 +170,7 @@ fn test_rename_branch_run_function_successful_case() {
 
     std::env::set_current_dir(repo.path()).expect("Failed to change directory");
 
-    let cmd = RenameBranchCommand::new("test-branch".to_string());
+    let cmd = RenameBranchCommand::new("test-branch".parse().unwrap());
     let result = cmd.execute();
     // The command may fail if the branch already has this name, which is acceptable
     match &result {
@@ -195,7 +195,7 @@ fn test_rename_branch_run_function_same_name() {
 
     std::env::set_current_dir(repo.path()).expect("Failed to change directory");
 
-    let cmd = RenameBranchCommand::new("test-branch".to_string());
+    let cmd = RenameBranchCommand::new("test-branch".parse().unwrap());
     let result = cmd.execute();
     // The command may fail if the branch already has this name, which is acceptable
     match &result {
@@ -0,0 +1,192 @@
+use assert_cmd::Command;
+use git_x::core::repos::RepoRegistry;
+use predicates::prelude::*;
+use predicates::str::contains;
+use serial_test::serial;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A home directory holding a/payments, b/payments and web-app, with throwaway global config
+fn workspace() -> TempDir {
+    let home = TempDir::new().unwrap();
+    for repo in ["a/payments", "b/payments", "web-app"] {
+        std::fs::create_dir_all(home.path().join(repo)).unwrap();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(home.path().join(repo))
+            .output()
+            .unwrap();
+    }
+    home
+}
+
+fn run_git_x(home: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("git-x")
+        .unwrap()
+        .args(args)
+        .current_dir(home)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .assert()
+}
+
+fn stdout(assert: assert_cmd::assert::Assert) -> String {
+    String::from_utf8_lossy(&assert.get_output().stdout)
+        .trim()
+        .to_string()
+}
+
+fn root(home: &Path, repo: &str) -> String {
+    home.join(repo)
+        .canonicalize()
+        .unwrap()
+        .display()
+        .to_string()
+}
+
+fn repos(paths: &[&str]) -> Vec<PathBuf> {
+    paths.iter().map(PathBuf::from).collect()
+}
+
+#[test]
+fn test_repo_registry_find_prefers_exact_then_prefix_then_fuzzy() {
+    let known = repos(&["/src/payments", "/src/payments-api", "/src/web-app"]);
+
+    assert_eq!(
+        RepoRegistry::find("payments", &known).unwrap(),
+        PathBuf::from("/src/payments")
+    );
+    assert_eq!(
+        RepoRegistry::find("web", &known).unwrap(),
+        PathBuf::from("/src/web-app")
+    );
+    assert_eq!(
+        RepoRegistry::find("WBAP", &known).unwrap(),
+        PathBuf::from("/src/web-app")
+    );
+}
+
+#[test]
+fn test_repo_registry_find_uses_the_path_to_tell_apart_same_names() {
+    let known = repos(&["/work/a/payments", "/work/b/payments"]);
+
+    let err = RepoRegistry::find("payments", &known).unwrap_err();
+    assert!(err.to_string().contains("matches 2 repositories"));
+    assert!(err.to_string().contains("e.g. a/payments"));
+    assert_eq!(
+        RepoRegistry::find("b/payments", &known).unwrap(),
+        PathBuf::from("/work/b/payments")
+    );
+    assert_eq!(
+        RepoRegistry::find("b/pay", &known).unwrap(),
+        PathBuf::from("/work/b/payments")
+    );
+}
+
+#[test]
+fn test_repo_registry_find_without_a_match() {
+    let known = repos(&["/src/web-app"]);
+
+    let err = RepoRegistry::find("zzz", &known).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("No known repository matches 'zzz'")
+    );
+    assert!(RepoRegistry::find("web", &[]).is_err());
+}
+
+#[test]
+#[serial]
+fn test_repos_scan_registers_repositories_once() {
+    let home = workspace();
+
+    run_git_x(home.path(), &["repos", "scan", "."])
+        .success()
+        .stdout(contains("Found 3 repositories").and(contains("registered 3 new")));
+    run_git_x(home.path(), &["repos", "scan", "."])
+        .success()
+        .stdout(contains("registered 0 new"));
+
+    let list = stdout(run_git_x(home.path(), &["repos", "list"]).success());
+    assert!(list.contains("3 known repositories"));
+    assert!(list.contains(&root(home.path(), "web-app")));
+}
+
+#[test]
+#[serial]
+fn test_repos_add_registers_the_repository_root() {
+    let home = workspace();
+    std::fs::create_dir(home.path().join("web-app/src")).unwrap();
+
+    run_git_x(home.path(), &["repos", "add", "web-app/src"])
+        .success()
+        .stdout(contains("Registered").and(contains("as 'web-app'")));
+    run_git_x(home.path(), &["repos", "add", "web-app"])
+        .success()
+        .stdout(contains("already registered"));
+    run_git_x(home.path(), &["repos", "add", "."]).stderr(contains("not in a git repository"));
+}
+
+#[test]
+#[serial]
+fn test_goto_prints_only_the_path() {
+    let home = workspace();
+    run_git_x(home.path(), &["repos", "scan", "."]).success();
+
+    let path = stdout(run_git_x(home.path(), &["goto", "web"]).success());
+    assert_eq!(path, root(home.path(), "web-app"));
+
+    run_git_x(home.path(), &["goto", "payments"])
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(contains("matches 2 repositories"));
+    run_git_x(home.path(), &["goto", "nothing-like-it"])
+        .failure()
+        .stderr(contains("No known repository"));
+}
+
+#[test]
+#[serial]
+fn test_goto_skips_repositories_that_are_gone() {
+    let home = workspace();
+    run_git_x(home.path(), &["repos", "scan", "."]).success();
+    std::fs::remove_dir_all(home.path().join("b/payments")).unwrap();
+
+    let path = stdout(run_git_x(home.path(), &["goto", "payments"]).success());
+    assert_eq!(path, root(home.path(), "a/payments"));
+    run_git_x(home.path(), &["repos", "list"])
+        .success()
+        .stdout(contains("missing"));
+}
+
+#[test]
+#[serial]
+fn test_repos_list_json_and_remove() {
+    let home = workspace();
+    run_git_x(home.path(), &["repos", "scan", "."]).success();
+    run_git_x(home.path(), &["repos", "remove", "web"])
+        .success()
+        .stdout(contains("Forgot"));
+
+    let json = stdout(run_git_x(home.path(), &["repos", "list", "--json"]).success());
+    assert_eq!(json.matches("\"name\":\"payments\"").count(), 2);
+    assert!(json.contains(&format!("\"path\":\"{}\"", root(home.path(), "a/payments"))));
+    assert!(json.contains("\"exists\":true"));
+    assert!(!json.contains("web-app"));
+}
+
+#[test]
+#[serial]
+fn test_repos_list_when_empty() {
+    let home = workspace();
+
+    run_git_x(home.path(), &["repos", "list"])
+        .success()
+        .stdout(contains("No known repositories"));
+    assert_eq!(
+        stdout(run_git_x(home.path(), &["repos", "list", "--json"]).success()),
+        "[]"
+    );
+}
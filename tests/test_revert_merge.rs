@@ -0,0 +1,135 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::commit::RevertMergeCommand;
+use predicates::str::contains;
+
+/// `main` with `feature` (two commits) merged into it with `--no-ff`
+fn merged_repo() -> common::TestRepo {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("f1.txt", "one\n", "Add f1");
+    repo.add_commit("f2.txt", "two\n", "Add f2");
+    repo.checkout_branch("main");
+    repo.add_commit("main.txt", "main\n", "Main change");
+    repo.git(&[
+        "merge",
+        "-q",
+        "--no-ff",
+        "feature",
+        "-m",
+        "Merge branch 'feature'",
+    ]);
+    repo
+}
+
+#[test]
+fn test_merged_branch_from_subject() {
+    let branch = |subject: &str| RevertMergeCommand::merged_branch(subject);
+
+    assert_eq!(branch("Merge branch 'feature'").as_deref(), Some("feature"));
+    assert_eq!(
+        branch("Merge branch 'feat/x' into main").as_deref(),
+        Some("feat/x")
+    );
+    assert_eq!(
+        branch("Merge remote-tracking branch 'origin/feature'").as_deref(),
+        Some("origin/feature")
+    );
+    assert_eq!(
+        branch("Merge pull request #12 from owner/feat/login").as_deref(),
+        Some("feat/login")
+    );
+    assert_eq!(branch("Add login"), None);
+}
+
+#[test]
+#[serial]
+fn test_revert_merge_and_re_merge() {
+    let repo = merged_repo();
+    let merge = repo.git(&["rev-parse", "HEAD"]);
+
+    repo.run_git_x(&["revert-merge", "HEAD"])
+        .success()
+        .stdout(contains("Reverted merge"))
+        .stdout(contains("keeping parent 1"))
+        .stdout(contains(
+            "Git still counts the 2 commit(s) from 'feature' as merged",
+        ))
+        .stdout(contains("git x re-merge feature"));
+    assert!(!repo.path().join("f1.txt").exists());
+    assert!(repo.path().join("main.txt").exists());
+    assert_eq!(
+        repo.git(&["rev-parse", "HEAD^"]),
+        merge,
+        "the revert is a new commit on top"
+    );
+    assert!(
+        !repo
+            .git(&["for-each-ref", "refs/git-x/reverted/feature"])
+            .is_empty()
+    );
+
+    repo.run_git_x(&["revert-merge", &merge])
+        .stderr(contains("already reverted"));
+    repo.run_git_x(&["re-merge"])
+        .success()
+        .stdout(contains("feature"))
+        .stdout(contains("Revert \"Merge branch 'feature'\""));
+
+    // The branch moved on after the revert
+    repo.checkout_branch("feature");
+    repo.add_commit("f3.txt", "three\n", "Add f3");
+    repo.checkout_branch("main");
+
+    repo.run_git_x(&["re-merge", "feature"])
+        .success()
+        .stdout(contains("the changes from 'feature' are back"))
+        .stdout(contains("Merged 1 newer commit(s) from 'feature'"));
+    for file in ["f1.txt", "f2.txt", "f3.txt", "main.txt"] {
+        assert!(repo.path().join(file).exists(), "{file} is missing");
+    }
+    assert!(repo.git(&["for-each-ref", "refs/git-x"]).is_empty());
+    repo.run_git_x(&["re-merge"])
+        .success()
+        .stdout(contains("No reverted merges recorded"));
+}
+
+#[test]
+#[serial]
+fn test_revert_merge_refuses_non_merges() {
+    let repo = merged_repo();
+
+    repo.run_git_x(&["revert-merge", "HEAD^2"])
+        .stderr(contains("is not a merge commit"));
+    repo.run_git_x(&["revert-merge", "nope"])
+        .stderr(contains("'nope' is not a commit"));
+    repo.run_git_x(&["revert-merge", "HEAD", "--mainline", "3"])
+        .stderr(contains("--mainline 3 is out of range"));
+    repo.run_git_x(&["re-merge", "other"])
+        .stderr(contains("No reverted merge recorded for 'other'"));
+
+    std::fs::write(repo.path().join("main.txt"), "dirty\n").unwrap();
+    repo.run_git_x(&["revert-merge", "HEAD"])
+        .stderr(contains("uncommitted changes"));
+}
+
+#[test]
+#[serial]
+fn test_revert_merge_needs_mainline_off_the_first_parent_history() {
+    let repo = merged_repo();
+    // `main` is merged into `topic`, so its merge is reached through topic's second parent
+    repo.create_branch("topic");
+    repo.git(&["reset", "-q", "--hard", "HEAD^"]);
+    repo.add_commit("topic.txt", "topic\n", "Topic change");
+    repo.git(&["merge", "-q", "--no-ff", "main", "-m", "Merge main"]);
+    let inner = repo.git(&["rev-parse", "main"]);
+
+    repo.run_git_x(&["revert-merge", &inner])
+        .stderr(contains("pick the parent to keep with --mainline"));
+    repo.run_git_x(&["revert-merge", &inner, "-m", "1"])
+        .success()
+        .stdout(contains("keeping parent 1"));
+    assert!(!repo.path().join("f1.txt").exists());
+}
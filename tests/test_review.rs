@@ -0,0 +1,116 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::analysis::{
+    CommitReview, DiffHunk, ReviewCommand, ReviewSummary, ReviewVerdict,
+};
+use git_x::core::traits::Command;
+use predicates::str::contains;
+
+const SAMPLE_DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,2 @@
+-old
++new
+ same
+@@ -10,1 +10,2 @@ fn main()
+ keep
++added
+diff --git a/README.md b/README.md
+new file mode 100644
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1 @@
++hello";
+
+#[test]
+#[serial]
+fn test_diff_hunk_parse_all_tracks_files() {
+    let hunks = DiffHunk::parse_all(SAMPLE_DIFF);
+
+    assert_eq!(hunks.len(), 3);
+    assert_eq!(hunks[0].file, "src/lib.rs");
+    assert_eq!(hunks[0].header, "@@ -1,2 +1,2 @@");
+    assert_eq!(hunks[0].lines, vec!["-old", "+new", " same"]);
+    assert_eq!(hunks[1].label(), "src/lib.rs @@ -10,1 +10,2 @@ fn main()");
+    assert_eq!(hunks[2].file, "README.md");
+    assert_eq!(hunks[2].lines, vec!["+hello"]);
+}
+
+#[test]
+#[serial]
+fn test_review_summary_renders_flagged_hunks_and_notes() {
+    let hunks = DiffHunk::parse_all(SAMPLE_DIFF);
+    let mut summary = ReviewSummary::new("main..feature", 3);
+
+    let mut accepted = CommitReview::new("aaaaaaaaaaaa", "Add parser");
+    accepted.verdict = ReviewVerdict::Accepted;
+    let mut flagged = CommitReview::new("bbbbbbbbbbbb", "Tweak output");
+    flagged.verdict = ReviewVerdict::Flagged;
+    flagged.flagged_hunks.push(hunks[0].clone());
+    flagged.notes.push("Why was this renamed?".to_string());
+    summary.reviews.push(accepted);
+    summary.reviews.push(flagged);
+
+    let markdown = summary.render_markdown();
+    assert!(markdown.starts_with("# Review of main..feature"));
+    assert!(markdown.contains("Reviewed 2 of 3 commits: 1 accepted, 1 flagged, 0 skipped"));
+    assert!(markdown.contains("## ✅ aaaaaaa Add parser"));
+    assert!(markdown.contains("## 🚩 bbbbbbb Tweak output"));
+    assert!(markdown.contains("- Why was this renamed?"));
+    assert!(markdown.contains("```diff\n@@ -1,2 +1,2 @@\n-old\n+new\n same\n```"));
+}
+
+#[test]
+#[serial]
+fn test_review_range_resolution() {
+    let branch = ReviewCommand::new("feature".to_string(), None, None);
+    assert_eq!(branch.range(), "main..feature");
+
+    let based = ReviewCommand::new("feature".to_string(), Some("develop".to_string()), None);
+    assert_eq!(based.range(), "develop..feature");
+
+    let range = ReviewCommand::new("abc..def".to_string(), Some("develop".to_string()), None);
+    assert_eq!(range.range(), "abc..def");
+}
+
+#[test]
+#[serial]
+fn test_review_empty_range() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature");
+
+    repo.run_git_x(&["review", "feature"])
+        .success()
+        .stdout(contains("✅ No commits to review in main..feature"));
+}
+
+#[test]
+#[serial]
+fn test_review_requires_interactive_terminal() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("feature.txt", "feature", "Feature commit");
+
+    repo.run_git_x(&["review", "feature"])
+        .success()
+        .stderr(contains("Review needs an interactive terminal"));
+
+    assert!(!repo.path().join("git-x-review.md").exists());
+}
+
+#[test]
+#[serial]
+fn test_review_invalid_range_direct() {
+    let repo = repo_with_branch("main");
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+    let result = ReviewCommand::new("does-not-exist".to_string(), None, None).execute();
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,143 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::commands::analysis::{ChangedFile, ReviewSizeCommand, is_test_file};
+use git_x::core::dependencies::Ecosystem;
+use git_x::core::packages::Package;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+#[test]
+fn test_is_test_file() {
+    for path in [
+        "tests/test_cli.rs",
+        "src/__tests__/App.tsx",
+        "pkg/parser_test.go",
+        "web/src/App.test.tsx",
+        "web/src/App.spec.js",
+        "test_models.py",
+        "src/main/java/FooTest.java",
+    ] {
+        assert!(is_test_file(path), "{path}");
+    }
+    for path in [
+        "src/main.rs",
+        "src/testing.rs",
+        "latest.txt",
+        "contest/main.c",
+    ] {
+        assert!(!is_test_file(path), "{path}");
+    }
+}
+
+#[test]
+fn test_parse_numstat_with_renames_and_binaries() {
+    let output = "3\t1\tsrc/lib.rs\0-\t-\tlogo.png\x005\t0\t\0old/name.rs\0new/name.rs\0";
+    let files = ChangedFile::parse_numstat(output);
+    assert_eq!(
+        files
+            .iter()
+            .map(|file| (file.path.as_str(), file.lines()))
+            .collect::<Vec<_>>(),
+        vec![("src/lib.rs", 4), ("logo.png", 0), ("new/name.rs", 5)]
+    );
+}
+
+#[test]
+fn test_review_size_splits() {
+    assert_eq!(ReviewSizeCommand::size_label(100, 400), "S");
+    assert_eq!(ReviewSizeCommand::size_label(200, 400), "M");
+    assert_eq!(ReviewSizeCommand::size_label(400, 400), "L");
+    assert_eq!(ReviewSizeCommand::size_label(401, 400), "XL");
+
+    let commits: Vec<(String, usize)> = [("a", 150), ("b", 200), ("c", 500), ("d", 30)]
+        .iter()
+        .map(|(commit, lines)| (commit.to_string(), *lines))
+        .collect();
+    let splits = ReviewSizeCommand::split_commits(&commits, 400);
+    assert_eq!(
+        splits
+            .iter()
+            .map(|split| (split.first.as_str(), split.last.as_str(), split.lines))
+            .collect::<Vec<_>>(),
+        vec![("a", "b", 350), ("c", "c", 500), ("d", "d", 30)]
+    );
+
+    let packages = vec![Package {
+        name: "api".to_string(),
+        path: "services/api".to_string(),
+        ecosystem: Ecosystem::Cargo,
+    }];
+    assert_eq!(
+        ReviewSizeCommand::area("services/api/src/db/pool.rs", &packages, 0),
+        "services/api"
+    );
+    assert_eq!(
+        ReviewSizeCommand::area("services/api/src/db/pool.rs", &packages, 1),
+        "services/api/src"
+    );
+    assert_eq!(ReviewSizeCommand::area("web/app.ts", &packages, 0), "web");
+    assert_eq!(ReviewSizeCommand::area("web/ui/app.ts", &[], 1), "web/ui");
+    assert_eq!(ReviewSizeCommand::area("README.md", &[], 0), ".");
+}
+
+#[test]
+#[serial]
+fn test_review_size_of_a_large_branch() {
+    let repo = repo_with_branch("main");
+    repo.add_commit("README.md", "# Shop\n", "Initial");
+    repo.create_branch("feature");
+    for dir in ["api", "web", "db", "cli"] {
+        std::fs::create_dir(repo.path().join(dir)).unwrap();
+        repo.add_commit(
+            &format!("{dir}/main.rs"),
+            &"fn x() {}\n".repeat(150),
+            &format!("Add {dir}"),
+        );
+    }
+    repo.add_commit("Cargo.lock", &"lock\n".repeat(500), "Lock");
+
+    repo.run_git_x(&["review-size", "--target", "main"])
+        .success()
+        .stdout(contains("📏 Review size of 'feature' against main: XL"))
+        .stdout(contains("Lines:       600 (+600 -0, limit 400)  ⚠️"))
+        .stdout(contains("Directories: 4 (limit 3)  ⚠️"))
+        .stdout(contains("Tests:       0% of the code lines changed"))
+        .stdout(contains("🙈 1 generated or vendored file(s) not counted"))
+        .stdout(contains("⚠️  Too large to review comfortably"))
+        .stdout(contains(
+            "✂️  Split by directory:\n   api  1 file(s), 150 lines",
+        ))
+        .stdout(contains("✂️  Split by commit:"))
+        .stdout(contains("2 commit(s), 300 lines"));
+
+    repo.run_git_x(&["review-size", "--json"])
+        .success()
+        .stdout(contains(
+            r#""size":"XL","too_large":true,"files":4,"lines":600"#,
+        ))
+        .stdout(contains(
+            r#""areas":4,"area_kind":"directory","test_ratio":0"#,
+        ))
+        .stdout(contains(r#""generated_files":1"#));
+}
+
+#[test]
+#[serial]
+fn test_review_size_of_a_small_branch() {
+    let repo = repo_with_branch("main");
+    repo.add_commit("README.md", "# Shop\n", "Initial");
+    repo.create_branch("fix");
+    std::fs::create_dir(repo.path().join("tests")).unwrap();
+    repo.add_commit("lib.rs", "fn fixed() {}\n", "Fix");
+    repo.add_commit("tests/fix.rs", "#[test]\nfn t() {}\n", "Test fix");
+
+    repo.run_git_x(&["review-size", "--target", "main"])
+        .success()
+        .stdout(contains(": S"))
+        .stdout(contains("Tests:       200% of the code lines changed"))
+        .stdout(contains("✅ Within the review-size guidelines"))
+        .stdout(contains("✂️").not())
+        .stdout(contains("⚠️").not());
+}
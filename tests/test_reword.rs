@@ -0,0 +1,85 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_commits;
+use git_x::commands::commit::RewordCommand;
+use git_x::core::traits::Command as CommandTrait;
+use predicates::str::contains;
+
+#[test]
+#[serial]
+fn test_reword_middle_commit() {
+    let repo = repo_with_commits(4);
+    let trees_before = repo.git(&["log", "--format=%T"]);
+
+    repo.run_git_x(&["reword", "HEAD~2", "-m", "better message"])
+        .success()
+        .stdout(contains("✅ Reworded"))
+        .stdout(contains("Verified 3 commit(s): only messages changed"));
+
+    assert_eq!(
+        repo.git(&["log", "--format=%s"]),
+        "commit 4\ncommit 3\nbetter message\ninitial"
+    );
+    assert_eq!(repo.git(&["log", "--format=%T"]), trees_before);
+}
+
+#[test]
+#[serial]
+fn test_reword_root_commit() {
+    let repo = repo_with_commits(2);
+
+    repo.run_git_x(&["reword", "HEAD~1", "-m", "project start"])
+        .success()
+        .stdout(contains("✅ Reworded"));
+
+    assert_eq!(repo.git(&["log", "--format=%s"]), "commit 2\nproject start");
+}
+
+#[test]
+#[serial]
+fn test_reword_refuses_pushed_commit() {
+    let repo = repo_with_commits(2);
+    repo.create_branch("feature");
+    let _remote = repo.setup_remote("feature");
+
+    repo.run_git_x(&["reword", "HEAD", "-m", "nope"])
+        .success()
+        .stderr(contains("already been pushed"));
+
+    assert_eq!(repo.git(&["log", "-1", "--format=%s"]), "commit 2");
+}
+
+#[test]
+#[serial]
+fn test_reword_refuses_dirty_working_directory() {
+    let repo = repo_with_commits(2);
+    std::fs::write(repo.path().join("file.txt"), "dirty").unwrap();
+
+    repo.run_git_x(&["reword", "HEAD", "-m", "nope"])
+        .success()
+        .stderr(contains("Working directory is not clean"));
+}
+
+#[test]
+#[serial]
+fn test_reword_command_direct() {
+    let repo = repo_with_commits(3);
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let cmd = RewordCommand::new("HEAD".parse().unwrap(), "direct reword".to_string());
+    assert_eq!(cmd.name(), "reword");
+    let result = cmd.execute();
+    let empty = RewordCommand::new("HEAD".parse().unwrap(), "  ".to_string()).execute();
+    let invalid = RewordCommand::new("no-such-ref".parse().unwrap(), "msg".to_string()).execute();
+
+    // Restore original directory
+    let _ = std::env::set_current_dir(&original_dir);
+
+    assert!(result.unwrap().contains("Reworded"));
+    assert!(empty.is_err());
+    assert!(invalid.is_err());
+    assert_eq!(repo.git(&["log", "-1", "--format=%s"]), "direct reword");
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "serve")]
+
+mod common;
+
+use common::basic_repo;
+use git_x::commands::serve::{Route, dashboard_html, http_response};
+use serial_test::serial;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+#[test]
+fn test_route_resolve() {
+    assert_eq!(Route::resolve("GET / HTTP/1.1"), Route::Dashboard);
+    assert_eq!(Route::resolve("GET /index.html HTTP/1.1"), Route::Dashboard);
+    assert_eq!(Route::resolve("GET /api/info HTTP/1.1"), Route::Info);
+    assert_eq!(Route::resolve("GET /api/summary/ HTTP/1.1"), Route::Summary);
+    assert_eq!(
+        Route::resolve("GET /api/health?t=1 HTTP/1.1"),
+        Route::Health
+    );
+    assert_eq!(
+        Route::resolve("GET /api/contributors HTTP/1.1"),
+        Route::Contributors
+    );
+    assert_eq!(Route::resolve("GET /api/undo HTTP/1.1"), Route::NotFound);
+    assert_eq!(Route::resolve(""), Route::NotFound);
+    // Nothing can change the repository through the dashboard
+    assert_eq!(
+        Route::resolve("POST /api/info HTTP/1.1"),
+        Route::MethodNotAllowed
+    );
+}
+
+#[test]
+fn test_http_response() {
+    let response = http_response("200 OK", "application/json", "{\"a\":\"é\"}");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Content-Type: application/json\r\n"));
+    // Length is in bytes, not characters
+    assert!(response.contains("Content-Length: 10\r\n"));
+    assert!(response.ends_with("\r\n\r\n{\"a\":\"é\"}"));
+}
+
+#[test]
+fn test_dashboard_html_refresh() {
+    let html = dashboard_html(15);
+    assert!(html.contains("const REFRESH_SECONDS = 15;"));
+    for panel in ["info", "summary", "health", "contributors"] {
+        assert!(html.contains(&format!("<div id=\"{panel}\">")));
+    }
+}
+
+#[test]
+fn test_route_from_request_checks_host() {
+    let request = |host: &str| format!("GET /api/info HTTP/1.1\r\nHost: {host}\r\n\r\n");
+
+    assert_eq!(
+        Route::from_request(&request("127.0.0.1:8080"), 8080),
+        Route::Info
+    );
+    assert_eq!(
+        Route::from_request(&request("LocalHost:8080"), 8080),
+        Route::Info
+    );
+    // DNS rebinding: a foreign name resolving to 127.0.0.1
+    assert_eq!(
+        Route::from_request(&request("evil.example:8080"), 8080),
+        Route::Forbidden
+    );
+    assert_eq!(
+        Route::from_request(&request("localhost:9090"), 8080),
+        Route::Forbidden
+    );
+    assert_eq!(
+        Route::from_request("GET /api/info HTTP/1.1\r\n\r\n", 8080),
+        Route::Forbidden
+    );
+}
+
+#[test]
+#[serial]
+fn test_serve_rejects_foreign_host() {
+    let repo = basic_repo();
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let mut server = std::process::Command::new(env!("CARGO_BIN_EXE_git-x"))
+        .args(["serve", "--port", &port.to_string()])
+        .current_dir(repo.path())
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let get = |host: &str| {
+        let mut stream = (0..50)
+            .find_map(|_| {
+                TcpStream::connect(("127.0.0.1", port)).ok().or_else(|| {
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                    None
+                })
+            })
+            .expect("server did not start");
+        write!(stream, "GET /api/info HTTP/1.1\r\nHost: {host}\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+    let foreign = get("attacker.example");
+    let local = get(&format!("localhost:{port}"));
+    server.kill().unwrap();
+    server.wait().unwrap();
+
+    assert!(foreign.starts_with("HTTP/1.1 403 Forbidden"), "{foreign}");
+    assert!(!foreign.contains("branch"));
+    assert!(local.starts_with("HTTP/1.1 200 OK"), "{local}");
+}
@@ -0,0 +1,146 @@
+use assert_cmd::Command;
+use clap_complete::Shell;
+use git_x::commands::setup::{PullStrategy, SetupCommand};
+use predicates::str::contains;
+use serial_test::serial;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Run `git x setup --yes` against an isolated home directory
+fn run_setup(home: &Path, shell: &str) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("git-x")
+        .unwrap()
+        .args(["setup", "--yes"])
+        .current_dir(home)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("SHELL", shell)
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .assert()
+}
+
+fn global_config(home: &Path, key: &str) -> String {
+    let output = std::process::Command::new("git")
+        .args(["config", "--global", "--get", key])
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn set_global_config(home: &Path, key: &str, value: &str) {
+    std::process::Command::new("git")
+        .args(["config", "--global", key, value])
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .output()
+        .unwrap();
+}
+
+#[test]
+#[serial]
+fn test_setup_parse_git_version() {
+    assert_eq!(
+        SetupCommand::parse_git_version("git version 2.43.0"),
+        Some((2, 43, 0))
+    );
+    assert_eq!(
+        SetupCommand::parse_git_version("git version 2.39.3 (Apple Git-146)"),
+        Some((2, 39, 3))
+    );
+    assert_eq!(
+        SetupCommand::parse_git_version("git version 2.45.1.windows.1"),
+        Some((2, 45, 1))
+    );
+    assert_eq!(SetupCommand::parse_git_version("not git"), None);
+}
+
+#[test]
+#[serial]
+fn test_setup_detect_shell() {
+    assert_eq!(SetupCommand::detect_shell("/bin/zsh"), Some(Shell::Zsh));
+    assert_eq!(
+        SetupCommand::detect_shell("/usr/local/bin/fish"),
+        Some(Shell::Fish)
+    );
+    assert_eq!(SetupCommand::detect_shell("bash"), Some(Shell::Bash));
+    assert_eq!(SetupCommand::detect_shell("/bin/tcsh"), None);
+    assert_eq!(SetupCommand::detect_shell(""), None);
+}
+
+#[test]
+#[serial]
+fn test_pull_strategy_from_config() {
+    assert_eq!(PullStrategy::from_config(None, None), None);
+    assert_eq!(
+        PullStrategy::from_config(Some("true"), None),
+        Some(PullStrategy::Rebase)
+    );
+    assert_eq!(
+        PullStrategy::from_config(Some("merges"), None),
+        Some(PullStrategy::Rebase)
+    );
+    assert_eq!(
+        PullStrategy::from_config(Some("false"), None),
+        Some(PullStrategy::Merge)
+    );
+    assert_eq!(
+        PullStrategy::from_config(None, Some("only")),
+        Some(PullStrategy::FastForwardOnly)
+    );
+}
+
+#[test]
+#[serial]
+fn test_setup_applies_defaults_on_fresh_machine() {
+    let home = TempDir::new().unwrap();
+
+    run_setup(home.path(), "/usr/bin/fish")
+        .success()
+        .stdout(contains("✅ git"))
+        .stdout(contains("user.name not set"))
+        .stdout(contains("🌿 Default branch: main"))
+        .stdout(contains("🔀 Pull strategy: rebase"))
+        .stdout(contains("git xs → git x sync"))
+        .stdout(contains("Completion installed for fish"))
+        .stdout(contains("📝 Settings saved to"));
+
+    assert_eq!(global_config(home.path(), "init.defaultBranch"), "main");
+    assert_eq!(global_config(home.path(), "pull.rebase"), "true");
+    assert_eq!(global_config(home.path(), "alias.xs"), "x sync");
+    assert_eq!(
+        global_config(home.path(), "git-x.setup.version"),
+        env!("CARGO_PKG_VERSION")
+    );
+    assert!(
+        home.path()
+            .join(".config/fish/completions/git-x.fish")
+            .exists()
+    );
+}
+
+#[test]
+#[serial]
+fn test_setup_keeps_existing_settings() {
+    let home = TempDir::new().unwrap();
+    set_global_config(home.path(), "user.name", "Jane Doe");
+    set_global_config(home.path(), "user.email", "jane@example.com");
+    set_global_config(home.path(), "init.defaultBranch", "trunk");
+    set_global_config(home.path(), "pull.ff", "only");
+    set_global_config(home.path(), "alias.xs", "status --short");
+
+    run_setup(home.path(), "/bin/tcsh")
+        .success()
+        .stdout(contains("👤 Identity: Jane Doe <jane@example.com>"))
+        .stdout(contains("🌿 Default branch: trunk (unchanged)"))
+        .stdout(contains("🔀 Pull strategy: fast-forward only (unchanged)"))
+        .stdout(contains("Skipping alias 'xs'"))
+        .stdout(contains("shell not detected"));
+
+    assert_eq!(global_config(home.path(), "init.defaultBranch"), "trunk");
+    assert_eq!(global_config(home.path(), "pull.ff"), "only");
+    assert_eq!(global_config(home.path(), "alias.xs"), "status --short");
+    assert_eq!(global_config(home.path(), "alias.xi"), "x info");
+}
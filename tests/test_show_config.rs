@@ -0,0 +1,94 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::repository::{ConfigOrigin, ShowConfigCommand};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+#[test]
+fn test_config_origin_parse_list() {
+    let entries = ConfigOrigin::parse_list(
+        "global\tfile:/home/jane/.gitconfig\tuser.name=Jane Doe\n\
+         local\tfile:.git/config\tcore.bare=false\n\
+         local\tfile:.git/config\tgit-x.url=https://example.com/?a=b\n\
+         local\tfile:.git/config\tcore.flag\n",
+    );
+
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0].0, "user.name");
+    assert_eq!(entries[0].1.value, "Jane Doe");
+    assert_eq!(entries[0].1.scope, "global");
+    assert_eq!(entries[2].1.value, "https://example.com/?a=b");
+    assert_eq!(entries[3].0, "core.flag");
+    assert_eq!(entries[3].1.value, "true");
+}
+
+#[test]
+fn test_git_x_settings_merge_known_and_unknown_keys() {
+    let entries = ConfigOrigin::parse_list(
+        "global\tfile:/g\tgit-x.mirror.remote=backup-global\n\
+         local\tfile:.git/config\tgit-x.mirror.remote=backup-local\n\
+         local\tfile:.git/config\tgit-x.team.web=alice@example.com\n\
+         local\tfile:.git/config\tgit-x.team.api=bob@example.com\n\
+         local\tfile:.git/config\tgit-x.team.web=carol@example.com\n\
+         local\tfile:.git/config\tgit-x.debt.lockfilemonths=3\n\
+         local\tfile:.git/config\tgit-x.made.up=1\n",
+    );
+    let settings = ShowConfigCommand::git_x_settings(&entries);
+    let find = |key: &str| settings.iter().find(|s| s.key == key).unwrap();
+
+    let mirror = find("git-x.mirror.remote");
+    assert_eq!(mirror.values.len(), 2);
+    assert_eq!(mirror.values.last().unwrap().value, "backup-local");
+
+    // git lowercases variable names; the known spelling is kept
+    assert_eq!(find("git-x.debt.lockfileMonths").values[0].value, "3");
+
+    let web = find("git-x.team.web");
+    assert!(web.list);
+    assert_eq!(web.values.len(), 2);
+    assert_eq!(find("git-x.team.api").values.len(), 1);
+    assert!(settings.iter().all(|s| s.key != "git-x.team.<name>"));
+
+    assert_eq!(find("git-x.made.up").values[0].value, "1");
+    assert!(find("git-x.scan.allow").values.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_show_config_reports_values_and_origins() {
+    let repo = basic_repo();
+    repo.git(&["config", "git-x.mirror.remote", "offsite"]);
+    repo.git(&["config", "git-x.ci.gitlabToken", "glpat-secret"]);
+    repo.git(&["config", "pull.rebase", "true"]);
+
+    repo.run_git_x(&["show-config"])
+        .success()
+        .stdout(contains(
+            "git-x.mirror.remote = offsite (local: .git/config)",
+        ))
+        .stdout(contains("git-x.scan.failOn = medium (default)"))
+        .stdout(contains("git-x.ci.gitlabToken = ********"))
+        .stdout(contains("glpat-secret").not())
+        .stdout(contains("pull.rebase = true (local: .git/config)"))
+        .stdout(contains("GIT_X_NON_INTERACTIVE = 1"))
+        .stdout(contains("--quiet = false"));
+}
+
+#[test]
+#[serial]
+fn test_show_config_json() {
+    let repo = basic_repo();
+    repo.git(&["config", "--add", "git-x.lint.types", "deps"]);
+    repo.git(&["config", "--add", "git-x.lint.types", "wip"]);
+
+    repo.run_git_x(&["--quiet", "show-config", "--json"])
+        .success()
+        .stdout(contains(r#"{"git_x":[{"key":"git-x.analysis.generated""#))
+        .stdout(contains(r#""key":"git-x.lint.types","#))
+        .stdout(contains(r#""effective":["deps","wip"]"#))
+        .stdout(contains(r#""effective":["main","master","develop"]"#))
+        .stdout(contains(r#"{"name":"GIT_X_NON_INTERACTIVE","value":"1"}"#))
+        .stdout(contains(r#""flags":{"quiet":true}"#));
+}
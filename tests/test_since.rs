@@ -4,6 +4,7 @@ mod common;
 use common::repo_with_commits;
 use git_x::commands::analysis::SinceCommand;
 use git_x::core::traits::Command;
+use predicates::prelude::*;
 use predicates::str::contains;
 
 #[test]
@@ -67,3 +68,75 @@ fn test_since_command_no_commits() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+#[test]
+#[serial]
+fn test_since_defaults_to_upstream() {
+    let repo = common::repo_with_branch("main");
+    let _remote = repo.setup_remote("main");
+    repo.add_commit("local.txt", "local", "local work");
+
+    repo.run_git_x(&["since"])
+        .success()
+        .stdout(contains("using the upstream 'origin/main'"))
+        .stdout(contains("local work"));
+}
+
+#[test]
+#[serial]
+fn test_since_defaults_to_latest_tag() {
+    let repo = repo_with_commits(2);
+    repo.git(&["tag", "v1.0.0", "HEAD~1"]);
+
+    repo.run_git_x(&["since"])
+        .success()
+        .stdout(contains("using the latest tag 'v1.0.0'"))
+        .stdout(contains("🔍 Commits since v1.0.0:"))
+        .stdout(contains("commit 2"));
+}
+
+#[test]
+#[serial]
+fn test_since_defaults_to_last_fetch() {
+    let repo = common::basic_repo();
+    let now = std::time::SystemTime::now();
+    let hour = std::time::Duration::from_secs(3600);
+
+    // Fetched an hour from now, so only a commit dated after that is new
+    let fetch_head = repo.path().join(".git/FETCH_HEAD");
+    std::fs::write(&fetch_head, "").unwrap();
+    std::fs::File::options()
+        .write(true)
+        .open(&fetch_head)
+        .unwrap()
+        .set_modified(now + hour)
+        .unwrap();
+    let later = (now + 2 * hour)
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    std::fs::write(repo.path().join("new.txt"), "new").unwrap();
+    repo.git(&["add", "new.txt"]);
+    std::process::Command::new("git")
+        .args(["commit", "-q", "-m", "after fetch"])
+        .env("GIT_COMMITTER_DATE", format!("{later} +0000"))
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["since"])
+        .success()
+        .stdout(contains("using the last fetch ("))
+        .stdout(contains("after fetch"))
+        .stdout(contains("initial").not());
+}
+
+#[test]
+#[serial]
+fn test_since_without_any_default() {
+    let repo = common::basic_repo();
+
+    repo.run_git_x(&["since"])
+        .success()
+        .stderr(contains("No upstream, tag or previous fetch"));
+}
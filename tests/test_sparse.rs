@@ -0,0 +1,108 @@
+use serial_test::serial;
+mod common;
+
+use common::{TestRepo, basic_repo};
+use git_x::core::sparse::SparseCheckout;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// `app/` and `docs/` directories next to the root README
+fn monorepo() -> TestRepo {
+    let repo = basic_repo();
+    std::fs::create_dir_all(repo.path().join("app")).unwrap();
+    std::fs::create_dir_all(repo.path().join("docs")).unwrap();
+    repo.add_commit("app/main.rs", "fn main() {}\n", "Add app");
+    repo.add_commit("docs/guide.md", "# Guide\n", "Add docs");
+    repo
+}
+
+#[test]
+fn test_scan_warning() {
+    let sparse = SparseCheckout {
+        enabled: true,
+        cone: true,
+        patterns: vec!["app".to_string()],
+        tracked_files: 10,
+        skipped_files: 4,
+    };
+    assert_eq!(sparse.checked_out_files(), 6);
+    assert!(
+        sparse
+            .scan_warning()
+            .unwrap()
+            .contains("only 6 of 10 tracked files are on disk")
+    );
+
+    let full = SparseCheckout {
+        skipped_files: 0,
+        ..sparse.clone()
+    };
+    assert_eq!(full.scan_warning(), None);
+    assert_eq!(SparseCheckout::default().scan_warning(), None);
+}
+
+#[test]
+#[serial]
+fn test_sparse_add_status_disable() {
+    let repo = monorepo();
+
+    repo.run_git_x(&["sparse", "status"])
+        .success()
+        .stdout(contains("Sparse checkout: disabled"));
+
+    repo.run_git_x(&["sparse", "add", "app"])
+        .success()
+        .stdout(contains("Enabled sparse checkout (cone mode) with: app"));
+    assert!(repo.path().join("app/main.rs").exists());
+    assert!(!repo.path().join("docs/guide.md").exists());
+
+    repo.run_git_x(&["sparse", "status"])
+        .success()
+        .stdout(contains("enabled (cone mode)"))
+        .stdout(contains("   app"))
+        .stdout(contains("of 3 tracked files checked out"));
+
+    // File-based scans say they only saw part of the tree
+    repo.run_git_x(&["large-files"])
+        .success()
+        .stdout(contains("Sparse checkout: only"));
+    repo.run_git_x(&["technical-debt"])
+        .success()
+        .stdout(contains("Sparse checkout: only"));
+
+    repo.run_git_x(&["sparse", "add", "docs"])
+        .success()
+        .stdout(contains("Added to the sparse checkout: docs"));
+    assert!(repo.path().join("docs/guide.md").exists());
+
+    repo.run_git_x(&["sparse", "add", "--no-cone", "*.md"])
+        .success()
+        .stderr(contains("cone mode"));
+
+    repo.run_git_x(&["sparse", "disable"])
+        .success()
+        .stdout(contains("all 3 tracked files are checked out"));
+    repo.run_git_x(&["sparse", "disable"])
+        .success()
+        .stdout(contains("not enabled"));
+    repo.run_git_x(&["large-files"])
+        .success()
+        .stdout(contains("Sparse checkout").not());
+}
+
+#[test]
+#[serial]
+fn test_sparse_add_pattern_mode() {
+    let repo = monorepo();
+
+    repo.run_git_x(&["sparse", "add", "--no-cone", "/docs/"])
+        .success()
+        .stdout(contains("pattern mode"));
+    assert!(repo.path().join("docs/guide.md").exists());
+    assert!(!repo.path().join("app/main.rs").exists());
+
+    repo.run_git_x(&["sparse", "status"])
+        .success()
+        .stdout(contains("enabled (pattern mode)"))
+        .stdout(contains("/docs/"));
+}
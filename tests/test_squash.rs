@@ -0,0 +1,142 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_commits;
+use git_x::commands::commit::SquashCommand;
+use git_x::core::traits::Command as CommandTrait;
+use predicates::str::contains;
+
+#[test]
+#[serial]
+fn test_squash_last_n_commits_with_message() {
+    let repo = repo_with_commits(4);
+
+    repo.run_git_x(&["squash", "3", "-m", "implement login"])
+        .success()
+        .stdout(contains("✅ Squashed 3 commits into"))
+        .stdout(contains("💾 Backup branch: squash/"));
+
+    let log = repo.git(&["log", "--format=%s"]);
+    assert_eq!(
+        log.lines().collect::<Vec<_>>(),
+        vec!["implement login", "initial"]
+    );
+
+    // Content of the newest commit is preserved
+    let content = std::fs::read_to_string(repo.path().join("file.txt")).unwrap();
+    assert_eq!(content, "content 4");
+
+    let branches = repo.git(&["branch", "--list", "squash/*"]);
+    assert!(!branches.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_squash_combines_messages_by_default() {
+    let repo = repo_with_commits(3);
+
+    repo.run_git_x(&["squash", "2"]).success();
+
+    let message = repo.git(&["log", "-1", "--format=%B"]);
+    assert_eq!(message, "commit 2\n\ncommit 3");
+}
+
+#[test]
+#[serial]
+fn test_squash_range_to_head() {
+    let repo = repo_with_commits(4);
+
+    repo.run_git_x(&["squash", "HEAD~2..HEAD", "-m", "range"])
+        .success()
+        .stdout(contains("Squashed 2 commits"));
+
+    assert_eq!(repo.git(&["rev-list", "--count", "HEAD"]), "3");
+}
+
+#[test]
+#[serial]
+fn test_squash_rejects_single_commit() {
+    let repo = repo_with_commits(3);
+
+    repo.run_git_x(&["squash", "1"])
+        .success()
+        .stderr(contains("Need at least 2 commits to squash"));
+}
+
+#[test]
+#[serial]
+fn test_squash_rejects_root_commit() {
+    let repo = repo_with_commits(2);
+
+    repo.run_git_x(&["squash", "2"])
+        .success()
+        .stderr(contains("root commit"));
+}
+
+#[test]
+#[serial]
+fn test_squash_rejects_unrelated_base() {
+    let repo = repo_with_commits(3);
+    let branch = repo.git(&["branch", "--show-current"]);
+    repo.git(&["checkout", "-q", "--orphan", "unrelated"]);
+    repo.git(&["commit", "-q", "-m", "unrelated root"]);
+    repo.git(&["checkout", "-q", &branch]);
+    let head = repo.git(&["rev-parse", "HEAD"]);
+
+    for target in ["unrelated..", "unrelated"] {
+        repo.run_git_x(&["squash", target, "-m", "nope"])
+            .success()
+            .stderr(contains("not in the history of the current branch"));
+    }
+
+    assert_eq!(repo.git(&["rev-parse", "HEAD"]), head);
+}
+
+#[test]
+#[serial]
+fn test_squash_refuses_staged_changes() {
+    let repo = repo_with_commits(3);
+    std::fs::write(repo.path().join("staged.txt"), "staged").unwrap();
+    repo.git(&["add", "staged.txt"]);
+
+    repo.run_git_x(&["squash", "2"])
+        .success()
+        .stderr(contains("Staged changes"));
+
+    assert_eq!(repo.git(&["rev-list", "--count", "HEAD"]), "3");
+}
+
+#[test]
+#[serial]
+fn test_squash_warns_about_pushed_commits() {
+    let repo = repo_with_commits(3);
+    repo.create_branch("feature");
+    let _remote = repo.setup_remote("feature");
+
+    repo.run_git_x(&["squash", "2", "-m", "squashed"])
+        .success()
+        .stdout(contains("2 of 2 commits are already pushed"))
+        .stdout(contains("✅ Squashed 2 commits"));
+}
+
+#[test]
+#[serial]
+fn test_squash_command_direct() {
+    let repo = repo_with_commits(3);
+    let original_dir = std::env::current_dir().unwrap();
+
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let cmd = SquashCommand::new("2".to_string(), Some("direct".to_string()));
+    assert_eq!(cmd.name(), "squash");
+    let result = cmd.execute();
+
+    assert!(result.is_ok());
+    assert!(result.unwrap().contains("Squashed 2 commits"));
+
+    let invalid = SquashCommand::new("no-such-ref..".to_string(), None).execute();
+    assert!(invalid.is_err());
+
+    // Restore original directory
+    let _ = std::env::set_current_dir(&original_dir);
+}
@@ -1,7 +1,11 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use git_x::commands::stash::{
-    StashBranchAction as StashAction, StashCommand, StashCommands, StashInfo, utils::*,
+    StashBranchAction as StashAction, StashCommand, StashCommands, StashInfo, StashSort, utils::*,
 };
+use git_x::core::stash_meta::StashMeta;
 use git_x::core::traits::Command as NewCommand;
 use predicates::prelude::*;
 use serial_test::serial;
@@ -61,7 +65,9 @@ fn test_stash_interactive_command_exists() {
 
     std::env::set_current_dir(&repo_path).expect("Failed to change directory");
 
-    let interactive_cmd = StashCommand::new(StashAction::Interactive);
+    let interactive_cmd = StashCommand::new(StashAction::Interactive {
+        sort: StashSort::Age,
+    });
 
     // The command should exist and handle empty stash list gracefully
     match NewCommand::execute(&interactive_cmd) {
@@ -289,6 +295,121 @@ fn test_stash_branch_clean_with_age_filter() {
         .success();
 }
 
+#[test]
+fn test_stash_meta_roundtrip() {
+    let meta = StashMeta {
+        operation: "sync".to_string(),
+        branch: "feature/x".to_string(),
+        created: "2026-10-17T09:30:00Z".to_string(),
+        version: "1.1.0".to_string(),
+    };
+    let message = format!("On feature/x: {}", meta.message("git-x sync autostash"));
+
+    assert_eq!(StashMeta::parse(&message), Some(meta));
+    assert_eq!(
+        StashMeta::strip(&message),
+        "On feature/x: git-x sync autostash"
+    );
+
+    // User stashes, even ones mentioning git-x, carry no metadata
+    assert_eq!(StashMeta::parse("On main: try [git-x later]"), None);
+    assert_eq!(StashMeta::parse("WIP on main: abc123 work"), None);
+    assert_eq!(StashMeta::strip("On main: wip"), "On main: wip");
+}
+
+#[test]
+#[serial]
+fn test_stash_branch_list_mine() {
+    let (_temp_dir, repo_path, default_branch) = create_test_repo();
+    create_stash(&repo_path, "user.txt", "user content", "User stash");
+    let tagged = StashMeta {
+        operation: "checkpoint".to_string(),
+        branch: default_branch.clone(),
+        created: "2026-10-17T09:30:00Z".to_string(),
+        version: "1.1.0".to_string(),
+    }
+    .message("before rebase");
+    create_stash(&repo_path, "tool.txt", "tool content", &tagged);
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args(["stash-branch", "list"])
+        .current_dir(&repo_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 stash(es)"))
+        .stdout(predicate::str::contains("User stash"))
+        .stdout(predicate::str::contains(
+            "🤖 checkpoint (git-x 1.1.0)  before rebase",
+        ));
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args(["stash-branch", "list", "--mine"])
+        .current_dir(&repo_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 stash(es) created by git-x"))
+        .stdout(predicate::str::contains(format!("[{default_branch}]")))
+        .stdout(predicate::str::contains("User stash").not());
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args(["stash-branch", "clean", "--mine", "--dry-run"])
+        .current_dir(&repo_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would clean 1 stash(es)"))
+        .stdout(predicate::str::contains("User stash").not());
+}
+
+#[test]
+#[serial]
+fn test_stash_branch_clean_many_requires_yes() {
+    let (_temp_dir, repo_path, _) = create_test_repo();
+    for i in 0..3 {
+        create_stash(
+            &repo_path,
+            &format!("f{i}.txt"),
+            "content",
+            &format!("Stash {i}"),
+        );
+    }
+    Command::new("git")
+        .args(["config", "git-x.safety.typeToConfirm", "2"])
+        .current_dir(&repo_path)
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args(["stash-branch", "clean"])
+        .current_dir(&repo_path)
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Refusing to delete 3 stashes"))
+        .stderr(predicate::str::contains("--yes"));
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args(["stash-branch", "clean", "--yes"])
+        .current_dir(&repo_path)
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Successfully deleted 3 stashes"));
+}
+
+#[test]
+#[serial]
+fn test_stash_branch_list_mine_empty() {
+    let (_temp_dir, repo_path, _default_branch) = create_test_repo();
+    create_stash(&repo_path, "user.txt", "user content", "User stash");
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args(["stash-branch", "list", "--mine"])
+        .current_dir(&repo_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No stashes created by git-x"));
+}
+
 #[test]
 #[serial]
 fn test_stash_branch_apply_by_branch_no_stashes() {
@@ -655,6 +776,8 @@ fn test_stash_branch_run_clean_function() {
     let cmd = StashCommand::new(StashAction::Clean {
         older_than: None,
         dry_run: true,
+        mine: false,
+        yes: false,
     });
 
     let _ = cmd.execute();
@@ -683,6 +806,8 @@ fn test_stash_branch_run_clean_function_with_age() {
     let cmd = StashCommand::new(StashAction::Clean {
         older_than: Some("7d".to_string()),
         dry_run: false,
+        mine: false,
+        yes: false,
     });
 
     let _ = cmd.execute();
@@ -706,6 +831,8 @@ fn test_stash_branch_run_apply_function() {
     let cmd = StashCommand::new(StashAction::ApplyByBranch {
         branch_name: "nonexistent".to_string(),
         list_only: true,
+        pop: false,
+        newest_only: false,
     });
 
     let _ = cmd.execute();
@@ -724,6 +851,8 @@ fn test_stash_branch_run_apply_function_no_list() {
     let cmd = StashCommand::new(StashAction::ApplyByBranch {
         branch_name: "main".to_string(),
         list_only: false,
+        pop: false,
+        newest_only: false,
     });
 
     let _ = cmd.execute();
@@ -810,6 +939,8 @@ fn test_stash_command_direct_no_stashes() {
     let cmd = StashCommand::new(StashAction::Clean {
         older_than: None,
         dry_run: true,
+        mine: false,
+        yes: false,
     });
     let result = cmd.execute();
 
@@ -838,6 +969,8 @@ fn test_stash_command_apply_by_branch_no_stashes() {
     let cmd = StashCommand::new(StashAction::ApplyByBranch {
         branch_name: "nonexistent-branch".to_string(),
         list_only: true,
+        pop: false,
+        newest_only: false,
     });
     let result = cmd.execute();
 
@@ -929,3 +1062,173 @@ fn create_stash(repo_path: &PathBuf, filename: &str, content: &str, message: &st
         .assert()
         .success();
 }
+
+fn stash_at(index: usize, branch: &str, timestamp: i64) -> StashInfo {
+    StashInfo {
+        name: format!("stash@{{{index}}}"),
+        message: format!("WIP on {branch}: work {index}"),
+        branch: branch.to_string(),
+        timestamp: timestamp.to_string(),
+    }
+}
+
+#[test]
+fn test_stash_sort_from_str() {
+    assert_eq!("age".parse::<StashSort>().unwrap(), StashSort::Age);
+    assert_eq!("branch".parse::<StashSort>().unwrap(), StashSort::Branch);
+    assert!("size".parse::<StashSort>().is_err());
+}
+
+#[test]
+fn test_relative_age() {
+    let now = 1_700_000_000;
+    assert_eq!(relative_age(now - 10, now), "just now");
+    assert_eq!(relative_age(now - 60, now), "1 minute ago");
+    assert_eq!(relative_age(now - 3 * 3_600, now), "3 hours ago");
+    assert_eq!(relative_age(now - 2 * 86_400, now), "2 days ago");
+    assert_eq!(relative_age(now - 14 * 86_400, now), "2 weeks ago");
+    assert_eq!(relative_age(now - 400 * 86_400, now), "1 year ago");
+    // Clock skew never produces a negative age
+    assert_eq!(relative_age(now + 100, now), "just now");
+}
+
+#[test]
+fn test_sort_stashes_by_age_and_branch() {
+    let mut stashes = vec![
+        stash_at(0, "main", 100),
+        stash_at(1, "feature", 50),
+        stash_at(2, "main", 200),
+    ];
+
+    sort_stashes(&mut stashes, StashSort::Age);
+    let names: Vec<&str> = stashes.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["stash@{2}", "stash@{0}", "stash@{1}"]);
+
+    sort_stashes(&mut stashes, StashSort::Branch);
+    let names: Vec<&str> = stashes.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["stash@{1}", "stash@{2}", "stash@{0}"]);
+    assert_eq!(stashes[0].branch, "feature");
+}
+
+#[test]
+fn test_format_stash_entry_and_index() {
+    let stash = stash_at(3, "feature/login", 1_000);
+    let entry = format_stash_entry(&stash, 1_000 + 2 * 86_400);
+
+    assert_eq!(
+        entry,
+        "stash@{3}  2 days ago  [feature/login]  WIP on feature/login: work 3"
+    );
+    assert_eq!(stash_index(&stash.name), Some(3));
+    assert_eq!(stash_index("not-a-stash"), None);
+}
+
+#[test]
+fn test_apply_failure_message() {
+    let stash = stash_at(2, "feature", 0);
+    let error = git_x::GitXError::GitCommand("exit status 1".to_string());
+
+    let conflict = apply_failure(
+        &stash,
+        true,
+        &error,
+        &["README.md", "src/lib.rs"],
+        &["stash@{3}"],
+        &["stash@{0}"],
+    )
+    .to_string();
+    assert!(
+        conflict.contains("stash@{2} (WIP on feature: work 2) conflicts in README.md, src/lib.rs")
+    );
+    assert!(conflict.contains("git stash drop stash@{2}"));
+    assert!(conflict.contains("Popped before it: stash@{3}"));
+    assert!(conflict.contains("Not applied yet: stash@{0}"));
+
+    let failure = apply_failure(&stash, false, &error, &[], &[], &[]).to_string();
+    assert!(failure.contains("Failed to apply stash@{2}"));
+    assert!(!failure.contains("git stash drop"));
+    assert!(!failure.contains("Not applied yet"));
+}
+
+#[test]
+#[serial]
+fn test_stash_branch_apply_by_branch_pop_and_newest_only() {
+    let (_temp_dir, repo_path, _default_branch) = create_test_repo();
+    Command::new("git")
+        .args(["checkout", "-b", "feature"])
+        .current_dir(&repo_path)
+        .assert()
+        .success();
+    create_stash(&repo_path, "first.txt", "first", "first change");
+    create_stash(&repo_path, "second.txt", "second", "second change");
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args([
+        "stash-branch",
+        "apply-by-branch",
+        "feature",
+        "--newest-only",
+        "--pop",
+    ])
+    .current_dir(&repo_path)
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Popping 1 stash(es)"))
+    .stdout(predicate::str::contains("second change"));
+    assert!(repo_path.join("second.txt").exists());
+    assert!(!repo_path.join("first.txt").exists());
+    assert_eq!(git(&repo_path, &["stash", "list"]).lines().count(), 1);
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args(["stash-branch", "apply-by-branch", "feature", "--pop"])
+        .current_dir(&repo_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Popped stash@{0}"));
+    assert!(repo_path.join("first.txt").exists());
+    assert_eq!(git(&repo_path, &["stash", "list"]), "");
+}
+
+#[test]
+#[serial]
+fn test_stash_branch_apply_by_branch_stops_at_conflict() {
+    let (_temp_dir, repo_path, _default_branch) = create_test_repo();
+    Command::new("git")
+        .args(["checkout", "-b", "feature"])
+        .current_dir(&repo_path)
+        .assert()
+        .success();
+    create_stash(&repo_path, "README.md", "stashed readme", "readme change");
+    create_stash(&repo_path, "notes.txt", "notes", "notes change");
+    fs::write(repo_path.join("README.md"), "committed readme").expect("Failed to write file");
+    Command::new("git")
+        .args(["commit", "-qam", "Change readme"])
+        .current_dir(&repo_path)
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args(["stash-branch", "apply-by-branch", "feature", "--pop"])
+        .current_dir(&repo_path)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("conflicts in README.md"))
+        .stderr(predicate::str::contains("git stash drop stash@{1}"))
+        .stderr(predicate::str::contains("Not applied yet: stash@{0}"));
+
+    // The conflicting stash is kept and nothing after it was touched
+    assert_eq!(git(&repo_path, &["stash", "list"]).lines().count(), 2);
+    assert!(!repo_path.join("notes.txt").exists());
+
+    let mut cmd = Command::cargo_bin("git-x").expect("Failed to find binary");
+    cmd.args([
+        "stash-branch",
+        "apply-by-branch",
+        "feature",
+        "--list",
+        "--pop",
+    ])
+    .current_dir(&repo_path)
+    .assert()
+    .failure();
+}
@@ -1,9 +1,11 @@
 use serial_test::serial;
 mod common;
 
+use chrono::NaiveDate;
 use common::repo_with_conventional_commits;
-use git_x::commands::analysis::SummaryCommand;
+use git_x::commands::analysis::{StandupCommand, StandupCommit, SummaryCommand, SummaryFilter};
 use git_x::core::traits::Command;
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 
 #[test]
@@ -58,3 +60,195 @@ fn test_summary_command_no_since() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+#[test]
+#[serial]
+fn test_summary_author_filter() {
+    let repo = repo_with_conventional_commits();
+
+    repo.run_git_x(&["summary", "--since", "3 days ago", "--author", "Alice"])
+        .success()
+        .stdout(contains("feat: initial commit"))
+        .stdout(contains("fix: bug fix").not())
+        .stdout(contains("🔎 Filters: authors: Alice"));
+}
+
+#[test]
+#[serial]
+fn test_summary_team_filter_from_config() {
+    let repo = repo_with_conventional_commits();
+    std::process::Command::new("git")
+        .args(["config", "--add", "git-x.team.backend", "bob@example.com"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["summary", "--since", "3 days ago", "--team", "backend"])
+        .success()
+        .stdout(contains("fix: bug fix"))
+        .stdout(contains("feat: initial commit").not());
+
+    repo.run_git_x(&["summary", "--team", "frontend"])
+        .success()
+        .stderr(contains("Team 'frontend' is not defined"));
+}
+
+#[test]
+#[serial]
+fn test_summary_by_author_breakdown() {
+    let repo = repo_with_conventional_commits();
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let output = SummaryCommand::new(None)
+        .with_filter(SummaryFilter {
+            by_author: true,
+            ..SummaryFilter::default()
+        })
+        .execute();
+
+    let _ = std::env::set_current_dir(&original_dir);
+
+    let output = output.unwrap();
+    assert!(output.contains("by author"));
+    assert!(output.contains("👤 Alice (1 commits)"));
+    assert!(output.contains("👤 Bob (1 commits)"));
+    assert!(output.contains("✨ feat: initial commit"));
+}
+
+#[test]
+#[serial]
+fn test_summary_exclude_merges() {
+    let repo = common::repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("feature.txt", "feature", "Add feature");
+    repo.checkout_branch("main");
+    std::process::Command::new("git")
+        .args(["merge", "--no-ff", "feature", "-m", "Merge branch feature"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["summary", "--since", "1 day ago"])
+        .success()
+        .stdout(contains("Merge branch feature"));
+
+    repo.run_git_x(&["summary", "--since", "1 day ago", "--exclude-merges"])
+        .success()
+        .stdout(contains("Add feature"))
+        .stdout(contains("Merge branch feature").not())
+        .stdout(contains("🔎 Filters: no merges"));
+}
+
+fn standup_commit(hash: &str, author: &str, email: &str, subject: &str) -> StandupCommit {
+    StandupCommit {
+        hash: hash.to_string(),
+        author: author.to_string(),
+        email: email.to_string(),
+        when: "2 hours ago".to_string(),
+        subject: subject.to_string(),
+    }
+}
+
+fn git_config(repo: &common::TestRepo, key: &str, value: &str) {
+    repo.git(&["config", "--add", key, value]);
+}
+
+#[test]
+fn test_standup_previous_working_day() {
+    let day = |d| NaiveDate::from_ymd_opt(2025, 7, d).unwrap();
+    // 2025-07-07 is a Monday
+    assert_eq!(StandupCommand::previous_working_day(day(7)), day(4));
+    assert_eq!(StandupCommand::previous_working_day(day(8)), day(7));
+    assert_eq!(StandupCommand::previous_working_day(day(6)), day(4));
+}
+
+#[test]
+fn test_standup_commit_parse_and_membership() {
+    let commit = StandupCommit::parse(
+        "abc1234\x1fAlice Smith\x1falice@example.com\x1f3 hours ago\x1ffeat: x",
+    )
+    .unwrap();
+    assert_eq!(commit.author, "Alice Smith");
+    assert_eq!(commit.subject, "feat: x");
+    assert!(commit.is_by("ALICE@example.com"));
+    assert!(commit.is_by("Alice Smith"));
+    assert!(!commit.is_by("bob@example.com"));
+    assert!(StandupCommit::parse("abc1234\x1fAlice").is_none());
+}
+
+#[test]
+fn test_standup_render_groups_by_team_then_author() {
+    let commits = vec![
+        standup_commit("aaa1111", "Alice", "alice@example.com", "feat: api"),
+        standup_commit("bbb2222", "Bob", "bob@example.com", "fix: login"),
+        standup_commit("ccc3333", "Alice", "alice@example.com", "fix: api"),
+        standup_commit("ddd4444", "Dana", "dana@example.com", "docs: readme"),
+    ];
+    let teams = vec![
+        ("backend".to_string(), vec!["alice@example.com".to_string()]),
+        ("web".to_string(), vec!["bob@example.com".to_string()]),
+        ("mobile".to_string(), vec!["erin@example.com".to_string()]),
+    ];
+
+    let output = StandupCommand::new(None)
+        .with_teams(Some(Vec::new()))
+        .render("yesterday", &commits, Some(&teams));
+
+    let backend = output.find("🏷️  backend (2 commits, 1 authors)").unwrap();
+    let web = output.find("🏷️  web (1 commits, 1 authors)").unwrap();
+    assert!(backend < web);
+    assert!(output.contains(
+        " 👤 Alice (2 commits)\n   - aaa1111 feat: api (2 hours ago)\n   - ccc3333 fix: api"
+    ));
+    assert!(output.contains("🏷️  mobile: no commits"));
+    assert!(output.contains("🏷️  Not in a team (1 commits, 1 authors)\n 👤 Dana (1 commits)"));
+    assert!(output.contains("📊 4 commits by 3 authors"));
+
+    // Named teams leave everyone else out
+    let output = StandupCommand::new(None)
+        .with_teams(Some(vec!["web".to_string()]))
+        .render("yesterday", &commits, Some(&teams[1..2]));
+    assert!(output.contains("Bob"));
+    assert!(!output.contains("Not in a team"));
+}
+
+#[test]
+#[serial]
+fn test_standup_cli() {
+    let repo = repo_with_conventional_commits();
+
+    repo.run_git_x(&["standup"])
+        .success()
+        .stdout(contains("🗓️  Standup since"))
+        .stdout(contains("👤 Alice (1 commits)"))
+        .stdout(contains("feat: initial commit"))
+        .stdout(contains("🏷️").not());
+
+    repo.run_git_x(&["standup", "--team"])
+        .success()
+        .stderr(contains("No teams are defined"));
+
+    git_config(&repo, "git-x.team.backend", "bob@example.com");
+    git_config(&repo, "git-x.team.Web", "alice@example.com");
+
+    repo.run_git_x(&["standup", "--team"])
+        .success()
+        .stdout(contains("🏷️  backend (1 commits, 1 authors)"))
+        .stdout(contains("🏷️  web (1 commits, 1 authors)"))
+        .stdout(contains("Not in a team").not());
+
+    repo.run_git_x(&["standup", "--team", "backend", "--since", "1 week ago"])
+        .success()
+        .stdout(contains("Standup since 1 week ago"))
+        .stdout(contains("fix: bug fix"))
+        .stdout(contains("feat: initial commit").not());
+
+    repo.run_git_x(&["standup", "--team", "mobile"])
+        .success()
+        .stderr(contains("Team 'mobile' is not defined"));
+
+    repo.run_git_x(&["standup", "--since", "monday"])
+        .success()
+        .stderr(contains("Invalid time 'monday'"));
+}
@@ -157,3 +157,65 @@ fn test_sync_command_with_rebase_strategy() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+#[test]
+#[serial]
+fn test_sync_reports_gone_upstream() {
+    let (repo, _remote) = common::repo_with_gone_upstream("feature/shipped");
+
+    repo.run_git_x(&["sync"])
+        .success()
+        .stderr(predicate::str::contains(
+            "Upstream 'origin/feature/shipped' for branch 'feature/shipped' no longer exists on the remote",
+        ));
+}
+
+#[test]
+#[serial]
+fn test_sync_autostash_keeps_local_changes() {
+    let (repo, _remote) = common::repo_with_remote_ahead("main");
+    std::fs::write(repo.path().join("README.md"), "local edit").unwrap();
+
+    repo.run_git_x(&["sync"])
+        .success()
+        .stderr(predicate::str::contains("rolled back").not());
+    assert!(!repo.path().join("remote_file.txt").exists());
+
+    repo.run_git_x(&["sync", "--autostash"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Stashed local changes and put them back",
+        ));
+    assert!(repo.path().join("remote_file.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("README.md")).unwrap(),
+        "local edit"
+    );
+    assert_eq!(repo.git(&["stash", "list"]), "");
+}
+
+#[test]
+#[serial]
+fn test_sync_conflict_rolls_back() {
+    let (repo, _remote) = common::repo_with_remote_ahead("main");
+    repo.add_commit("remote_file.txt", "local content", "local commit");
+    let head = repo.git(&["rev-parse", "HEAD"]);
+    std::fs::write(repo.path().join("README.md"), "local edit").unwrap();
+
+    repo.run_git_x(&["sync", "--autostash"])
+        .success()
+        .stderr(predicate::str::contains("sync failed, rolled back"))
+        .stderr(predicate::str::contains("Ran 'git rebase --abort'"))
+        .stderr(predicate::str::contains(
+            "Put stashed changes back in the working tree",
+        ));
+
+    assert_eq!(repo.git(&["rev-parse", "HEAD"]), head);
+    assert_eq!(repo.git(&["branch", "--show-current"]), "main");
+    assert!(!repo.path().join(".git/rebase-merge").exists());
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("README.md")).unwrap(),
+        "local edit"
+    );
+    assert_eq!(repo.git(&["stash", "list"]), "");
+}
@@ -8,6 +8,7 @@ use tempfile::TempDir;
 mod common;
 
 use git_x::commands::analysis::TechnicalDebtCommand;
+use git_x::core::dependencies::{DependencyManifest, Ecosystem, describe_age};
 use git_x::core::traits::Command as CommandTrait;
 
 #[test]
@@ -156,3 +157,139 @@ fn test_technical_debt_command_outside_git_repo() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+#[test]
+fn test_ecosystem_from_manifest_path() {
+    assert_eq!(Ecosystem::from_path("Cargo.toml"), Some(Ecosystem::Cargo));
+    assert_eq!(
+        Ecosystem::from_path("web/package.json"),
+        Some(Ecosystem::Npm)
+    );
+    assert_eq!(Ecosystem::from_path("svc/go.mod"), Some(Ecosystem::Go));
+    assert_eq!(
+        Ecosystem::from_path("requirements.txt"),
+        Some(Ecosystem::Python)
+    );
+    assert_eq!(Ecosystem::from_path("Cargo.lock"), None);
+    assert_eq!(Ecosystem::from_path("docs/package.json.md"), None);
+}
+
+#[test]
+fn test_count_outdated_per_tool() {
+    let cargo = "Name   Project  Compat  Latest  Kind    Platform\n\
+                 ----   -------  ------  ------  ----    --------\n\
+                 clap   4.0.0    4.5.0   4.5.0   Normal  ---\n\
+                 tokio  1.0.0    1.47.0  1.47.0  Normal  ---\n";
+    assert_eq!(Ecosystem::Cargo.count_outdated(cargo, ""), 2);
+    assert_eq!(
+        Ecosystem::Cargo.count_outdated("All dependencies are up to date, yay!", ""),
+        0
+    );
+
+    let npm = "/app/node_modules/react:react@18.3.1:react@18.2.0:react@19.0.0:app\n";
+    assert_eq!(Ecosystem::Npm.count_outdated(npm, ""), 1);
+
+    let go = "example.com/app\ngolang.org/x/text v0.3.0 [v0.14.0]\ngithub.com/pkg/errors v0.9.1\n";
+    assert_eq!(Ecosystem::Go.count_outdated(go, ""), 1);
+
+    // pip lists the whole environment; only required packages count
+    let pip = "Requests==2.0.0\nblack==22.1.0\ntyping_extensions==4.0.0\n";
+    let requirements = "# pinned\nrequests==2.0.0\ntyping-extensions>=4\n-r dev.txt\n";
+    assert_eq!(Ecosystem::Python.count_outdated(pip, requirements), 2);
+}
+
+#[test]
+fn test_dependency_manifest_summary() {
+    let manifest = DependencyManifest {
+        ecosystem: Ecosystem::Cargo,
+        manifest: "Cargo.toml".to_string(),
+        lockfile: Some("Cargo.lock".to_string()),
+        lock_age_days: Some(12),
+        outdated: None,
+    };
+    assert!(!manifest.needs_attention(180));
+    assert_eq!(
+        manifest.summary(180),
+        "   ✅ Cargo.toml (Cargo): Cargo.lock updated 12 days ago"
+    );
+
+    let stale = DependencyManifest {
+        lock_age_days: Some(250),
+        outdated: Some(3),
+        ..manifest.clone()
+    };
+    assert!(stale.needs_attention(180));
+    assert_eq!(
+        stale.summary(180),
+        "   ⚠️  Cargo.toml (Cargo): Cargo.lock last updated 8 months ago, 3 outdated"
+    );
+
+    let unlocked = DependencyManifest {
+        ecosystem: Ecosystem::Npm,
+        manifest: "web/package.json".to_string(),
+        lockfile: None,
+        lock_age_days: None,
+        outdated: None,
+    };
+    assert!(unlocked.needs_attention(180));
+    assert!(unlocked.summary(180).ends_with("no lockfile committed"));
+
+    assert_eq!(describe_age(0), "today");
+    assert_eq!(describe_age(1), "1 day ago");
+    assert_eq!(describe_age(59), "59 days ago");
+    assert_eq!(describe_age(400), "13 months ago");
+}
+
+#[test]
+#[serial]
+fn test_technical_debt_reports_dependency_manifests() {
+    let repo = common::basic_repo();
+    let commit = |message: &str, date: &str| {
+        StdCommand::new("git")
+            .args(["add", "."])
+            .current_dir(repo.path())
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .args(["commit", "-q", "-m", message])
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .current_dir(repo.path())
+            .output()
+            .unwrap();
+    };
+
+    fs::write(repo.path().join("Cargo.toml"), "[package]\n").unwrap();
+    fs::write(repo.path().join("Cargo.lock"), "version = 3\n").unwrap();
+    commit("Add crate", "2020-01-01T00:00:00");
+
+    fs::create_dir_all(repo.path().join("web/node_modules/left-pad")).unwrap();
+    fs::write(repo.path().join("web/package.json"), "{}").unwrap();
+    fs::write(
+        repo.path().join("web/node_modules/left-pad/package.json"),
+        "{}",
+    )
+    .unwrap();
+    commit("Add web app", "2020-01-02T00:00:00");
+
+    repo.run_git_x(&["technical-debt"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Dependencies (lockfiles older than 6 months are flagged)",
+        ))
+        .stdout(predicate::str::contains(
+            "⚠️  Cargo.toml (Cargo): Cargo.lock last updated",
+        ))
+        .stdout(predicate::str::contains(
+            "web/package.json (npm): no lockfile committed",
+        ))
+        .stdout(predicate::str::contains("left-pad/package.json (npm)").not())
+        .stdout(predicate::str::contains("No significant technical debt").not());
+
+    // A long enough window makes the old lockfile acceptable
+    repo.run_git_x(&["technical-debt", "--lockfile-months", "1200"])
+        .success()
+        .stdout(predicate::str::contains(
+            "✅ Cargo.toml (Cargo): Cargo.lock updated",
+        ));
+}
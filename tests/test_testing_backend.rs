@@ -0,0 +1,173 @@
+#![cfg(feature = "testing")]
+
+use serial_test::serial;
+mod common;
+
+use git_x::commands::analysis::WhatCommand;
+use git_x::core::git::{AsyncGitOperations, GitOperations};
+use git_x::core::testing::{self, Fixture, RecordingGitBackend, ReplayGitBackend};
+use git_x::core::traits::Command;
+use std::sync::Arc;
+
+#[test]
+#[serial]
+fn test_fixture_round_trip() {
+    let mut fixture = Fixture::new();
+    fixture.push(
+        &["log", "--since=3 days ago", "it's"],
+        Ok("a1b2c3d Fix".to_string()),
+    );
+    fixture.push(&["status", "--porcelain"], Ok(String::new()));
+    fixture.push(
+        &["show", "HEAD"],
+        Ok("$ git looks like markup\n!bang\n\\slash".to_string()),
+    );
+    fixture.push(&["config", "user.name"], Err(String::new()));
+    fixture.push(
+        &["rev-parse", "nope"],
+        Err("fatal: bad revision\nhint".to_string()),
+    );
+
+    let rendered = fixture.render();
+    assert!(rendered.contains("$ git log '--since=3 days ago' 'it''s'"));
+    assert_eq!(Fixture::parse(&rendered).unwrap(), fixture);
+}
+
+#[test]
+#[serial]
+fn test_fixture_parse_errors() {
+    assert!(Fixture::parse("main\n").is_err());
+    assert!(Fixture::parse("$ git log 'unterminated\n").is_err());
+    assert!(
+        Fixture::parse("# just a comment\n")
+            .unwrap()
+            .entries
+            .is_empty()
+    );
+}
+
+#[test]
+#[serial]
+fn test_replay_backend_answers_git_operations() {
+    let backend = Arc::new(
+        ReplayGitBackend::new()
+            .with_output(&["rev-parse", "--abbrev-ref", "HEAD"], "first")
+            .with_output(&["rev-parse", "--abbrev-ref", "HEAD"], "second")
+            .with_error(&["config", "user.name"], "not set"),
+    );
+    let _guard = testing::install(backend.clone());
+
+    assert_eq!(GitOperations::current_branch().unwrap(), "first");
+    assert_eq!(GitOperations::current_branch().unwrap(), "second");
+    // The last response repeats once the queue is drained
+    assert_eq!(GitOperations::current_branch().unwrap(), "second");
+    assert!(GitOperations::run(&["config", "user.name"]).is_err());
+
+    let unknown = GitOperations::run(&["stash", "list"]).unwrap_err();
+    assert!(
+        unknown
+            .to_string()
+            .contains("No recorded output for: git stash list")
+    );
+
+    assert_eq!(backend.calls().len(), 5);
+    assert_eq!(backend.calls()[4], "git stash list");
+}
+
+#[test]
+#[serial]
+fn test_replay_backend_drives_command_from_fixture_file() {
+    let backend = ReplayGitBackend::load("tests/fixtures/what_feature.fixture").unwrap();
+    let _guard = testing::install(Arc::new(backend));
+
+    let output = WhatCommand::new(Some("main".parse().unwrap()))
+        .execute()
+        .unwrap();
+
+    assert!(output.contains("📈 2 commits ahead"));
+    assert!(output.contains("📉 0 commits behind"));
+    assert!(output.contains("➕ src/login.rs"));
+    assert!(output.contains("🔄 README.md"));
+}
+
+#[test]
+#[serial]
+fn test_replay_backend_answers_async_git_operations() {
+    let _guard = testing::install(Arc::new(
+        ReplayGitBackend::new().with_output(&["rev-parse", "--abbrev-ref", "HEAD"], "async"),
+    ));
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let branch = runtime
+        .block_on(AsyncGitOperations::current_branch())
+        .unwrap();
+    assert_eq!(branch, "async");
+}
+
+#[test]
+#[serial]
+fn test_replay_backend_answers_env_input_and_streaming_runs() {
+    let backend = Arc::new(
+        ReplayGitBackend::new()
+            .with_output(&["commit-tree", "abc", "-m", "import"], "def")
+            .with_output(&["mktree", "-z"], "tree")
+            .with_output(&["merge-tree", "--write-tree", "a", "b"], "merged")
+            .with_output(&["log", "--format=%s"], "one\ntwo\nthree"),
+    );
+    let _guard = testing::install(backend.clone());
+
+    let commit = GitOperations::run_with_env(
+        &["commit-tree", "abc", "-m", "import"],
+        &[("GIT_AUTHOR_NAME", "Jane")],
+    );
+    assert_eq!(commit.unwrap(), "def");
+    let tree = GitOperations::run_with_input(&["mktree", "-z"], "100644 blob x\tf\0");
+    assert_eq!(tree.unwrap(), "tree");
+    let merged =
+        GitOperations::run_with_exit_codes(&["merge-tree", "--write-tree", "a", "b"], &[0, 1]);
+    assert_eq!(merged.unwrap(), "merged");
+
+    let mut subjects = Vec::new();
+    GitOperations::run_streaming(&["log", "--format=%s"], b'\n', |record| {
+        subjects.push(String::from_utf8_lossy(record).to_string());
+        Ok(subjects.len() < 2)
+    })
+    .unwrap();
+    assert_eq!(subjects, ["one", "two"]);
+    assert_eq!(backend.calls().len(), 4);
+}
+
+#[test]
+#[serial]
+fn test_guard_restores_system_backend() {
+    {
+        let _guard = testing::install(Arc::new(ReplayGitBackend::new()));
+        assert!(testing::installed_backend().is_some());
+    }
+    assert!(testing::installed_backend().is_none());
+}
+
+#[test]
+#[serial]
+fn test_recording_backend_output_replays() {
+    let repo = common::repo_with_branch("recorded");
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+
+    let recorder = Arc::new(RecordingGitBackend::new());
+    let recorded_branch = {
+        let _guard = testing::install(recorder.clone());
+        let branch = GitOperations::current_branch().unwrap();
+        let _ = GitOperations::run(&["rev-parse", "--verify", "missing-ref"]);
+        branch
+    };
+    let _ = std::env::set_current_dir(&original_dir);
+
+    let path = repo.path().join("recorded.fixture");
+    recorder.save(&path).unwrap();
+
+    let _guard = testing::install(Arc::new(ReplayGitBackend::load(&path).unwrap()));
+    assert_eq!(recorded_branch, "recorded");
+    assert_eq!(GitOperations::current_branch().unwrap(), "recorded");
+    assert!(GitOperations::run(&["rev-parse", "--verify", "missing-ref"]).is_err());
+}
@@ -0,0 +1,192 @@
+use serial_test::serial;
+mod common;
+
+use common::{basic_repo, repo_with_branch};
+use git_x::commands::commit::TicketsCommand;
+use git_x::core::tickets::{DEFAULT_PATTERN, TicketPattern, ticket_url};
+use predicates::prelude::*;
+use predicates::str::contains;
+
+fn commit(hash: &str, message: &str) -> (String, String, String) {
+    let subject = message.lines().next().unwrap_or_default();
+    (hash.to_string(), subject.to_string(), message.to_string())
+}
+
+#[test]
+fn test_ticket_pattern_finds_ids() {
+    let pattern = TicketPattern::new(DEFAULT_PATTERN).unwrap();
+    assert_eq!(
+        pattern.find("PROJ-12: fix login\n\nAlso touches OPS-7 and PROJ-12"),
+        ["PROJ-12", "OPS-7"]
+    );
+    assert!(pattern.find("Bump utf-8 handling to v2-3").is_empty());
+
+    // A capture group is the ID
+    let github = TicketPattern::new(r"#([0-9]+)").unwrap();
+    assert_eq!(github.find("Fix crash (#42, #7)"), ["42", "7"]);
+
+    assert!(TicketPattern::new("[A-Z").is_err());
+}
+
+#[test]
+fn test_ticket_url_template() {
+    assert_eq!(
+        ticket_url("https://jira.example.com/browse/{id}", "PROJ-12").unwrap(),
+        "https://jira.example.com/browse/PROJ-12"
+    );
+    assert!(ticket_url("https://jira.example.com/browse/", "PROJ-12").is_err());
+}
+
+#[test]
+fn test_group_commits_by_ticket() {
+    let pattern = TicketPattern::new(DEFAULT_PATTERN).unwrap();
+    let commits = [
+        commit("c3", "PROJ-2 wire the form"),
+        commit("c2", "Fix typo"),
+        commit("c1", "PROJ-1 add form\n\nRefs PROJ-2"),
+    ];
+    let (tickets, untracked) = TicketsCommand::group(&commits, &pattern);
+    assert_eq!(
+        tickets,
+        [
+            (
+                "PROJ-2".to_string(),
+                vec![
+                    "c3 PROJ-2 wire the form".to_string(),
+                    "c1 PROJ-1 add form".to_string()
+                ]
+            ),
+            ("PROJ-1".to_string(), vec!["c1 PROJ-1 add form".to_string()]),
+        ]
+    );
+    assert_eq!(untracked, ["c2 Fix typo"]);
+}
+
+#[test]
+#[serial]
+fn test_tickets_lists_branch_commits() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature/PROJ-12");
+    repo.add_commit("a.txt", "a", "PROJ-12 add a");
+    repo.add_commit("b.txt", "b", "Tidy up");
+
+    repo.run_git_x(&["tickets"])
+        .success()
+        .stdout(contains("1 ticket(s) across 2 commit(s) in main..HEAD"))
+        .stdout(contains("PROJ-12 (1 commit(s))"))
+        .stdout(contains("No ticket (1 commit(s))"))
+        .stdout(contains("Tidy up"))
+        .stdout(contains("initial").not())
+        .stdout(contains("git-x.tickets.url"));
+
+    repo.git(&[
+        "config",
+        "git-x.tickets.url",
+        "https://jira.example.com/browse/{id}",
+    ]);
+    repo.run_git_x(&["tickets", "HEAD"])
+        .success()
+        .stdout(contains("in HEAD"))
+        .stdout(contains(
+            "PROJ-12 (1 commit(s)) → https://jira.example.com/browse/PROJ-12",
+        ))
+        .stdout(contains("initial"));
+}
+
+#[test]
+#[serial]
+fn test_tickets_open_uses_configured_browser() {
+    let repo = basic_repo();
+    repo.run_git_x(&["tickets", "--open", "PROJ-1"])
+        .success()
+        .stderr(contains("No ticket URL configured"));
+
+    repo.git(&[
+        "config",
+        "git-x.tickets.url",
+        "https://jira.example.com/browse/{id}",
+    ]);
+    repo.git(&["config", "git-x.browser", "echo >opened"]);
+    repo.run_git_x(&["tickets", "--open", "PROJ-1"])
+        .success()
+        .stdout(contains("Opened https://jira.example.com/browse/PROJ-1"));
+    let opened = std::fs::read_to_string(repo.path().join("opened")).unwrap();
+    assert_eq!(opened.trim(), "https://jira.example.com/browse/PROJ-1");
+
+    repo.git(&["config", "git-x.browser", "false"]);
+    repo.run_git_x(&["tickets", "--open", "PROJ-1"])
+        .success()
+        .stderr(contains(
+            "The ticket is at https://jira.example.com/browse/PROJ-1",
+        ));
+}
+
+#[test]
+#[serial]
+fn test_tickets_check_only_warns() {
+    let repo = repo_with_branch("feature/PROJ-9-login");
+    let message = repo.path().join("MSG");
+
+    std::fs::write(&message, "PROJ-9 add login\n").unwrap();
+    repo.run_git_x(&["tickets", "--check", "MSG"])
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    std::fs::write(&message, "Add login\n# PROJ-9 in a comment doesn't count\n").unwrap();
+    repo.run_git_x(&["tickets", "--check", "MSG"])
+        .success()
+        .stderr(contains("references no ticket"))
+        .stderr(contains("The branch name mentions PROJ-9"));
+
+    std::fs::write(&message, "Merge branch 'main'\n").unwrap();
+    repo.run_git_x(&["tickets", "--check", "MSG"])
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    // A broken pattern never blocks the commit
+    repo.git(&["config", "git-x.tickets.pattern", "[A-Z"]);
+    std::fs::write(&message, "Add login\n").unwrap();
+    repo.run_git_x(&["tickets", "--check", "MSG"])
+        .success()
+        .stderr(contains("Ticket check skipped"));
+}
+
+#[test]
+#[serial]
+fn test_tickets_hook_shares_commit_msg_with_init_hooks() {
+    let repo = basic_repo();
+    let hook_path = repo.path().join(".git/hooks/commit-msg");
+
+    repo.run_git_x(&["tickets", "--install-hook"])
+        .success()
+        .stdout(contains("Installed commit-msg hook"));
+    repo.run_git_x(&["tickets", "--install-hook"])
+        .success()
+        .stdout(contains("already installed"));
+
+    repo.run_git_x(&["init-hooks"])
+        .success()
+        .stdout(contains("Added the Conventional Commits check"));
+    let hook = std::fs::read_to_string(&hook_path).unwrap();
+    assert!(hook.contains("git x tickets --check \"$1\"\nexec git x init-hooks --check \"$1\""));
+    repo.run_git_x(&["tickets", "--install-hook"])
+        .success()
+        .stdout(contains("already installed"));
+
+    // The other way round: the ticket check runs ahead of the lint
+    std::fs::remove_file(&hook_path).unwrap();
+    repo.run_git_x(&["init-hooks"]).success();
+    repo.run_git_x(&["tickets", "--install-hook"])
+        .success()
+        .stdout(contains("Added the ticket check"));
+    let hook = std::fs::read_to_string(&hook_path).unwrap();
+    assert!(hook.contains("git x tickets --check \"$1\"\nexec git x init-hooks --check \"$1\""));
+
+    std::fs::write(&hook_path, "#!/bin/sh\nmake lint\n").unwrap();
+    repo.run_git_x(&["tickets", "--install-hook"])
+        .success()
+        .stderr(contains("rerun with --force"));
+    repo.run_git_x(&["tickets", "--install-hook", "--force"])
+        .success()
+        .stdout(contains("Moved the existing hook"));
+}
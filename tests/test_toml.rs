@@ -0,0 +1,117 @@
+use git_x::core::export::ExportValue;
+use git_x::core::toml;
+
+#[test]
+fn test_toml_parse() {
+    let value = toml::parse(
+        r#"
+# Checks CI runs
+title = "CI \"main\"" # trailing comment
+retries = 3
+ratio = 0.5
+strict = false
+
+[checks]
+fmt = 'cargo fmt --check'
+"unit tests" = { run = "cargo test", dir = "crates/core" }
+
+[servers.alpha]
+ports = [
+    8001,
+    8002,
+]
+
+[[jobs]]
+name = "a"
+[[jobs]]
+name = "b"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        value.get("title").and_then(|v| v.as_str()),
+        Some("CI \"main\"")
+    );
+    assert_eq!(value.get("retries"), Some(&ExportValue::Int(3)));
+    assert_eq!(value.get("ratio"), Some(&ExportValue::Float(0.5)));
+    assert_eq!(value.get("strict"), Some(&ExportValue::Bool(false)));
+
+    let checks = value.get("checks").unwrap();
+    assert_eq!(
+        checks.get("fmt").and_then(|v| v.as_str()),
+        Some("cargo fmt --check")
+    );
+    assert_eq!(
+        checks
+            .get("unit tests")
+            .and_then(|v| v.get("dir"))
+            .and_then(|v| v.as_str()),
+        Some("crates/core")
+    );
+    let ports = value
+        .get("servers")
+        .unwrap()
+        .get("alpha")
+        .unwrap()
+        .get("ports");
+    assert_eq!(ports.unwrap().items().len(), 2);
+    let jobs = value.get("jobs").unwrap().items();
+    assert_eq!(jobs[1].get("name").and_then(|v| v.as_str()), Some("b"));
+}
+
+#[test]
+fn test_toml_parse_rejects_invalid_input() {
+    let error = |toml: &str| toml::parse(toml).unwrap_err().to_string();
+
+    assert!(error("a = 1\na = 2").contains("line 2"));
+    assert!(error("a = 1\n[a]").contains("already defined"));
+    assert!(error("a = \"open").contains("unterminated"));
+    assert!(error("a = \"\"\"never closed").contains("unterminated"));
+    assert!(error("a = '''never closed").contains("unterminated"));
+    assert!(error("when = 2024-01-01").contains("unsupported value"));
+    assert!(error("a = 1 b = 2").contains("unexpected"));
+    assert!(error("= 1").contains("expected a key"));
+}
+
+#[test]
+fn test_toml_parse_multi_line_strings() {
+    let value = toml::parse(
+        r#"
+[checks]
+build = """
+cargo build
+cargo test"""
+docs = '''
+echo 'C:\docs'
+'''
+folded = """\
+    one \
+    two"""
+quoted = """say "hi"""""
+raw = '''''quoted'''''
+tab = """a\tb"""
+after = 1
+"#,
+    )
+    .unwrap();
+
+    let checks = value.get("checks").unwrap();
+    let text = |key: &str| checks.get(key).and_then(|v| v.as_str()).unwrap();
+    // The line break right after the opening quotes is dropped
+    assert_eq!(text("build"), "cargo build\ncargo test");
+    assert_eq!(text("docs"), "echo 'C:\\docs'\n");
+    // A backslash at the end of a line joins it with the next
+    assert_eq!(text("folded"), "one two");
+    // Up to two quotes right before the closing ones belong to the string
+    assert_eq!(text("quoted"), "say \"hi\"\"");
+    assert_eq!(text("raw"), "''quoted''");
+    assert_eq!(text("tab"), "a\tb");
+    assert_eq!(checks.get("after"), Some(&ExportValue::Int(1)));
+
+    // Line numbers keep counting inside multi-line strings
+    let error = toml::parse("a = \"\"\"\none\ntwo\"\"\"\na = 2")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("line 4"), "{error}");
+}
@@ -0,0 +1,146 @@
+use serial_test::serial;
+mod common;
+
+use common::git;
+use git_x::commands::repository::RepoState;
+use git_x::core::repos::RepoDiscovery;
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn init_repo(dir: &Path) {
+    std::fs::create_dir_all(dir).unwrap();
+    git(dir, &["init", "-q"]);
+    git(dir, &["config", "user.name", "Test User"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+    std::fs::write(dir.join("README.md"), "hello").unwrap();
+    git(dir, &["add", "README.md"]);
+    git(dir, &["commit", "-q", "-m", "Initial commit"]);
+}
+
+/// A clean repository: its only commit is on a remote-tracking branch too
+fn init_pushed_repo(dir: &Path) {
+    init_repo(dir);
+    git(dir, &["update-ref", "refs/remotes/origin/main", "HEAD"]);
+}
+
+fn git_x(dir: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    assert_cmd::Command::cargo_bin("git-x")
+        .unwrap()
+        .args(args)
+        .current_dir(dir)
+        .assert()
+}
+
+#[test]
+fn test_repo_discovery() {
+    let root = TempDir::new().unwrap();
+    init_repo(&root.path().join("api"));
+    init_repo(&root.path().join("clients/web"));
+    init_repo(&root.path().join("api/vendor/lib"));
+    init_repo(&root.path().join("node_modules/pkg"));
+    init_repo(&root.path().join(".cache/repo"));
+    init_repo(&root.path().join("a/b/c/deep"));
+
+    let found = RepoDiscovery::new(vec![root.path().to_path_buf()]).discover();
+    let relative: Vec<PathBuf> = found
+        .iter()
+        .map(|repo| repo.strip_prefix(root.path()).unwrap().to_path_buf())
+        .collect();
+    assert_eq!(
+        relative,
+        vec![PathBuf::from("api"), PathBuf::from("clients/web")]
+    );
+
+    let deeper = RepoDiscovery::new(vec![root.path().to_path_buf()])
+        .with_depth(4)
+        .discover();
+    assert!(deeper.contains(&root.path().join("a/b/c/deep")));
+
+    let root_is_repo = RepoDiscovery::new(vec![root.path().join("api")]).discover();
+    assert_eq!(root_is_repo, vec![root.path().join("api")]);
+}
+
+#[test]
+fn test_repo_state() {
+    let root = TempDir::new().unwrap();
+    let repo = root.path().join("repo");
+    init_pushed_repo(&repo);
+
+    let clean = RepoState::collect(&repo);
+    assert!(!clean.is_stranded());
+    assert!(clean.branch.is_some());
+
+    std::fs::write(repo.join("README.md"), "changed").unwrap();
+    std::fs::write(repo.join("notes.txt"), "new").unwrap();
+    let dirty = RepoState::collect(&repo);
+    assert_eq!(dirty.uncommitted, 1);
+    assert_eq!(dirty.untracked, 1);
+    assert_eq!(dirty.unpushed, 0);
+    assert_eq!(dirty.describe(), "1 uncommitted, 1 untracked");
+
+    git(&repo, &["stash", "push", "-q"]);
+    git(
+        &repo,
+        &["commit", "-q", "--allow-empty", "-m", "Local only"],
+    );
+    let local = RepoState::collect(&repo);
+    assert_eq!(
+        local.describe(),
+        "1 untracked, 1 unpushed commit(s), 1 stash(es)"
+    );
+
+    let missing = RepoState::collect(&root.path().join("missing"));
+    assert!(missing.is_stranded());
+    assert!(missing.describe().starts_with("could not read"));
+}
+
+#[test]
+#[serial]
+fn test_uncommitted_command() {
+    let root = TempDir::new().unwrap();
+    init_pushed_repo(&root.path().join("clean"));
+    init_pushed_repo(&root.path().join("dirty"));
+    init_repo(&root.path().join("nested/local"));
+    std::fs::write(root.path().join("dirty/notes.txt"), "new").unwrap();
+
+    git_x(root.path(), &["uncommitted"])
+        .success()
+        .stdout(contains("🔎 Scanned 3 repositories under ."))
+        .stdout(contains("dirty"))
+        .stdout(contains("1 untracked"))
+        .stdout(contains("1 unpushed commit(s)"))
+        .stdout(contains("clean").not())
+        .stdout(contains(
+            "❌ 2 of 3 repositories have work that exists only on this machine",
+        ));
+
+    git_x(root.path(), &["uncommitted", "--all", "--json"])
+        .success()
+        .stdout(contains(r#""untracked":1"#))
+        .stdout(contains(r#""unpushed":1"#))
+        .stdout(contains("clean"));
+
+    git_x(root.path(), &["uncommitted", "clean"])
+        .success()
+        .stdout(contains(
+            "✅ Nothing uncommitted, untracked, unpushed or stashed",
+        ));
+
+    git_x(
+        root.path(),
+        &["uncommitted", "--depth", "1", "nested/local/.."],
+    )
+    .success()
+    .stdout(contains("Scanned 1 repositories"));
+}
+
+#[test]
+#[serial]
+fn test_uncommitted_without_repositories() {
+    let root = TempDir::new().unwrap();
+    git_x(root.path(), &["uncommitted", "--depth", "1"])
+        .success()
+        .stderr(contains("No git repositories under ."));
+}
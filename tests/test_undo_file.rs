@@ -0,0 +1,155 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::branch::StashBranchCommand;
+use git_x::commands::commit::UndoFileCommand;
+use git_x::core::checkpoints::{FileCheckpoint, FileCheckpoints, MAX_CHECKPOINTS_PER_FILE};
+use git_x::core::traits::Command;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn test_checkpoint_index_line_roundtrip() {
+    let checkpoint = FileCheckpoint::parse("7\t1700000000\tbefore sync\tsrc/with\ttab.rs").unwrap();
+    assert_eq!(checkpoint.id, 7);
+    assert_eq!(checkpoint.time, 1_700_000_000);
+    assert_eq!(checkpoint.operation, "before sync");
+    assert_eq!(checkpoint.path, "src/with\ttab.rs");
+    assert!(FileCheckpoint::parse("not a checkpoint").is_none());
+}
+
+#[test]
+fn test_record_skips_unchanged_and_missing_files() {
+    let worktree = TempDir::new().unwrap();
+    let store = TempDir::new().unwrap();
+    let checkpoints = FileCheckpoints::new(store.path());
+    let paths = ["a.txt".to_string(), "gone.txt".to_string()];
+
+    std::fs::write(worktree.path().join("a.txt"), "one").unwrap();
+    let first = checkpoints
+        .record(worktree.path(), &paths, "before sync")
+        .unwrap();
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].id, 1);
+
+    // Same content again is not a new checkpoint
+    assert!(
+        checkpoints
+            .record(worktree.path(), &paths, "before sync")
+            .unwrap()
+            .is_empty()
+    );
+
+    std::fs::write(worktree.path().join("a.txt"), "two").unwrap();
+    checkpoints
+        .record(worktree.path(), &paths, "before stash apply")
+        .unwrap();
+
+    let history = checkpoints.history("a.txt").unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].operation, "before stash apply");
+    assert_eq!(checkpoints.content(&history[1]).unwrap(), b"one");
+}
+
+#[test]
+fn test_record_keeps_newest_checkpoints_per_file() {
+    let worktree = TempDir::new().unwrap();
+    let store = TempDir::new().unwrap();
+    let checkpoints = FileCheckpoints::new(store.path());
+    let paths = ["a.txt".to_string()];
+
+    for n in 0..MAX_CHECKPOINTS_PER_FILE + 3 {
+        std::fs::write(worktree.path().join("a.txt"), n.to_string()).unwrap();
+        checkpoints
+            .record(worktree.path(), &paths, "before sync")
+            .unwrap();
+    }
+
+    let history = checkpoints.history("a.txt").unwrap();
+    assert_eq!(history.len(), MAX_CHECKPOINTS_PER_FILE);
+    assert_eq!(history.last().unwrap().id, 4);
+    assert!(!store.path().join("1").exists());
+}
+
+#[test]
+fn test_restore_saves_replaced_content() {
+    let worktree = TempDir::new().unwrap();
+    let store = TempDir::new().unwrap();
+    let checkpoints = FileCheckpoints::new(store.path());
+    let file = worktree.path().join("a.txt");
+
+    std::fs::write(&file, "precious").unwrap();
+    checkpoints
+        .record(worktree.path(), &["a.txt".to_string()], "before sync")
+        .unwrap();
+    std::fs::write(&file, "clobbered").unwrap();
+
+    let (restored, replaced) = checkpoints.restore(worktree.path(), 1).unwrap();
+    assert_eq!(restored.id, 1);
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "precious");
+    let replaced = replaced.unwrap();
+    assert_eq!(checkpoints.content(&replaced).unwrap(), b"clobbered");
+
+    assert!(checkpoints.restore(worktree.path(), 42).is_err());
+}
+
+#[test]
+fn test_repo_path_resolves_relative_paths() {
+    assert_eq!(UndoFileCommand::repo_path("", "src/a.rs"), "src/a.rs");
+    assert_eq!(UndoFileCommand::repo_path("src/", "a.rs"), "src/a.rs");
+    assert_eq!(
+        UndoFileCommand::repo_path("src/", "./../docs/b.md"),
+        "docs/b.md"
+    );
+}
+
+#[test]
+#[serial]
+fn test_undo_file_restores_changes_lost_by_stash_branch() {
+    let repo = basic_repo();
+    std::fs::write(repo.path().join("README.md"), "uncommitted work").unwrap();
+
+    let original_dir = std::env::current_dir().unwrap();
+    std::env::set_current_dir(repo.path()).unwrap();
+    let result = StashBranchCommand::new("rescue".parse().unwrap()).execute();
+    std::env::set_current_dir(original_dir).unwrap();
+    assert!(result.is_ok());
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("README.md")).unwrap(),
+        "# test"
+    );
+
+    repo.run_git_x(&["undo-file"])
+        .success()
+        .stdout(contains("README.md — 1 checkpoint(s)"));
+    repo.run_git_x(&["undo-file", "README.md"])
+        .success()
+        .stdout(contains("#1"))
+        .stdout(contains("before stash-branch"));
+
+    repo.run_git_x(&["undo-file", "README.md", "--restore", "1"])
+        .success()
+        .stdout(contains("Restored README.md from checkpoint #1"))
+        .stdout(contains("saved as checkpoint #2"));
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("README.md")).unwrap(),
+        "uncommitted work"
+    );
+
+    repo.run_git_x(&["undo-file", "other.txt", "--restore", "1"])
+        .success()
+        .stderr(contains("not a checkpoint of other.txt"));
+}
+
+#[test]
+#[serial]
+fn test_undo_file_without_checkpoints() {
+    let repo = basic_repo();
+    repo.run_git_x(&["undo-file"])
+        .success()
+        .stdout(contains("No file checkpoints yet"));
+    repo.run_git_x(&["undo-file", "README.md"])
+        .success()
+        .stderr(contains("No checkpoints for README.md"));
+}
@@ -3,7 +3,9 @@ mod common;
 
 use assert_cmd::Command;
 use common::basic_repo;
-use git_x::commands::repository::{UpstreamAction as RepoUpstreamAction, UpstreamCommand};
+use git_x::commands::repository::{
+    UpstreamAction as RepoUpstreamAction, UpstreamCommand, UpstreamMatch,
+};
 use git_x::core::traits::Command as NewCommand;
 use predicates::prelude::*;
 use tempfile::TempDir;
@@ -18,8 +20,7 @@ fn test_upstream_run_set_function() {
     std::env::set_current_dir(repo.path()).expect("Failed to change directory");
 
     let action = RepoUpstreamAction::Set {
-        remote: "origin".to_string(),
-        branch: "main".to_string(),
+        upstream: "origin/main".parse().unwrap(),
     };
     let cmd = UpstreamCommand::new(action);
     let _ = cmd.execute();
@@ -96,21 +97,21 @@ fn test_upstream_set_invalid_format() {
 
     repo.run_git_x(&["upstream", "set", ""])
         .success()
-        .stderr(predicate::str::contains("Git command failed"));
+        .stderr(predicate::str::contains("Branch name cannot be empty"));
 
     // Test upstream without slash
     repo.run_git_x(&["upstream", "set", "origin"])
         .success()
         .stderr(predicate::str::contains("Git command failed"));
 
-    // Test upstream with empty parts
+    // Empty parts are rejected before git runs
     repo.run_git_x(&["upstream", "set", "/main"])
         .success()
-        .stderr(predicate::str::contains("Git command failed"));
+        .stderr(predicate::str::contains("Remote name cannot be empty"));
 
     repo.run_git_x(&["upstream", "set", "origin/"])
         .success()
-        .stderr(predicate::str::contains("Git command failed"));
+        .stderr(predicate::str::contains("Branch name cannot be empty"));
 }
 
 #[test]
@@ -198,3 +199,199 @@ fn test_upstream_command_direct() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+#[test]
+#[serial]
+fn test_upstream_status_flags_gone_upstream() {
+    let (repo, _remote) = common::repo_with_gone_upstream("feature/shipped");
+
+    repo.run_git_x(&["upstream", "status"])
+        .success()
+        .stdout(predicate::str::contains(
+            "feature/shipped: ⚠️  origin/feature/shipped is gone",
+        ))
+        .stdout(predicate::str::contains("main: ❌ No upstream configured"))
+        .stdout(predicate::str::contains("git x clean-branches --gone"));
+}
+
+/// `behind` trails its upstream, `diverged` has local and remote commits, `local-only` has no upstream
+fn repo_with_sync_targets() -> (common::TestRepo, common::TestRepo) {
+    let repo = common::repo_with_branch("main");
+    let remote = repo.setup_remote("main");
+
+    for branch in ["behind", "diverged"] {
+        repo.git(&["checkout", "-q", "-b", branch, "main"]);
+        repo.add_commit(
+            &format!("{branch}.txt"),
+            "remote",
+            &format!("Remote {branch}"),
+        );
+        repo.git(&["push", "-q", "-u", "origin", branch]);
+        repo.git(&["reset", "-q", "--hard", "HEAD~1"]);
+    }
+    repo.add_commit("local.txt", "local", "Local diverged work");
+    repo.git(&["checkout", "-q", "-b", "local-only", "main"]);
+    repo.git(&["checkout", "-q", "main"]);
+
+    (repo, remote)
+}
+
+#[test]
+fn test_branch_sync_outcome_from_counts() {
+    use git_x::commands::repository::BranchSyncOutcome;
+
+    assert_eq!(
+        BranchSyncOutcome::from_counts(0, 0),
+        BranchSyncOutcome::UpToDate
+    );
+    assert_eq!(
+        BranchSyncOutcome::from_counts(0, 3),
+        BranchSyncOutcome::FastForwarded(3)
+    );
+    assert_eq!(
+        BranchSyncOutcome::from_counts(2, 0),
+        BranchSyncOutcome::Ahead(2)
+    );
+    assert_eq!(
+        BranchSyncOutcome::from_counts(1, 4),
+        BranchSyncOutcome::Diverged {
+            ahead: 1,
+            behind: 4
+        }
+    );
+}
+
+#[test]
+fn test_sync_target_parse() {
+    use git_x::commands::repository::SyncTarget;
+
+    let targets = SyncTarget::parse(
+        "main\torigin/main\torigin\trefs/heads/main\t\n\
+         shipped\torigin/shipped\torigin\trefs/heads/shipped\t[gone]\n\
+         local\t\t\t\t\n",
+    );
+
+    assert_eq!(targets.len(), 3);
+    assert_eq!(targets[0].remote, "origin");
+    assert_eq!(targets[0].remote_ref, "refs/heads/main");
+    assert!(targets[1].gone);
+    assert_eq!(targets[2].upstream, None);
+}
+
+#[test]
+#[serial]
+fn test_upstream_sync_all_fast_forwards_without_checkout() {
+    let (repo, _remote) = repo_with_sync_targets();
+    let behind_before = repo.git(&["rev-parse", "behind"]);
+    let diverged_before = repo.git(&["rev-parse", "diverged"]);
+    std::fs::write(repo.path().join("wip.txt"), "uncommitted").unwrap();
+
+    repo.run_git_x(&["upstream", "sync-all", "--dry-run"])
+        .success()
+        .stdout(predicate::str::contains(
+            "behind: would fast-forward 1 commit(s)",
+        ));
+    assert_eq!(repo.git(&["rev-parse", "behind"]), behind_before);
+
+    repo.run_git_x(&["upstream", "sync-all"])
+        .success()
+        .stdout(predicate::str::contains(
+            "behind: fast-forwarded 1 commit(s)",
+        ))
+        .stdout(predicate::str::contains(
+            "diverged: diverged (1 ahead, 1 behind)",
+        ))
+        .stdout(predicate::str::contains("local-only: no upstream"))
+        .stdout(predicate::str::contains("main: current branch"))
+        .stdout(predicate::str::contains("1 fast-forwarded, 1 diverged"));
+
+    assert_eq!(
+        repo.git(&["rev-parse", "behind"]),
+        repo.git(&["rev-parse", "origin/behind"])
+    );
+    assert_eq!(repo.git(&["rev-parse", "diverged"]), diverged_before);
+    // The working tree and checked-out branch are left alone
+    assert_eq!(repo.git(&["branch", "--show-current"]), "main");
+    assert!(repo.path().join("wip.txt").exists());
+}
+
+#[test]
+fn test_match_upstreams() {
+    let owned = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    let remote_branches: Vec<(String, String)> = [
+        ("fork", "shared"),
+        ("origin", "shared"),
+        ("fork", "mine"),
+        ("fork", "both"),
+        ("upstream", "both"),
+    ]
+    .iter()
+    .map(|(remote, branch)| (remote.to_string(), branch.to_string()))
+    .collect();
+    let branches = owned(&["shared", "mine", "both", "local"]);
+
+    assert_eq!(
+        UpstreamCommand::match_upstreams(&branches, &remote_branches, None),
+        vec![
+            (
+                "shared".to_string(),
+                UpstreamMatch::Found("origin".to_string())
+            ),
+            ("mine".to_string(), UpstreamMatch::Found("fork".to_string())),
+            (
+                "both".to_string(),
+                UpstreamMatch::Ambiguous(owned(&["fork", "upstream"]))
+            ),
+            ("local".to_string(), UpstreamMatch::Missing),
+        ]
+    );
+    // A chosen remote settles ties and rules out the others
+    let on_fork = UpstreamCommand::match_upstreams(&branches, &remote_branches, Some("fork"));
+    assert_eq!(on_fork[0].1, UpstreamMatch::Found("fork".to_string()));
+    assert_eq!(on_fork[2].1, UpstreamMatch::Found("fork".to_string()));
+    let on_origin = UpstreamCommand::match_upstreams(&branches, &remote_branches, Some("origin"));
+    assert_eq!(on_origin[1].1, UpstreamMatch::Missing);
+}
+
+#[test]
+#[serial]
+fn test_upstream_adopt_sets_matching_upstreams() {
+    let repo = common::repo_with_branch("main");
+    let _remote = repo.setup_remote("main");
+    for branch in ["feature-a", "feature-b", "local-only"] {
+        repo.git(&["branch", branch]);
+    }
+    // Pushed without -u, as after an import or a remote switch
+    repo.git(&["push", "-q", "origin", "feature-a", "feature-b"]);
+    let upstream = |branch: &str| {
+        repo.git(&[
+            "for-each-ref",
+            "--format=%(upstream:short)",
+            &format!("refs/heads/{branch}"),
+        ])
+    };
+
+    repo.run_git_x(&["upstream", "adopt", "--dry-run"])
+        .success()
+        .stdout(predicate::str::contains("2 branch(es) without an upstream"))
+        .stdout(predicate::str::contains("feature-a → origin/feature-a"))
+        .stdout(predicate::str::contains(
+            "No remote branch of the same name: local-only",
+        ))
+        .stdout(predicate::str::contains("Run without --dry-run"));
+    assert_eq!(upstream("feature-a"), "");
+
+    repo.run_git_x(&["upstream", "adopt", "--exclude", "feature-b"])
+        .success()
+        .stdout(predicate::str::contains("Excluded: feature-b"))
+        .stdout(predicate::str::contains("Set 1 upstream(s)"));
+    assert_eq!(upstream("feature-a"), "origin/feature-a");
+    assert_eq!(upstream("feature-b"), "");
+
+    repo.run_git_x(&["upstream", "adopt", "--remote", "fork"])
+        .stderr(predicate::str::contains("No remote named 'fork'"));
+    repo.run_git_x(&["upstream", "adopt"])
+        .success()
+        .stdout(predicate::str::contains("Set 1 upstream(s)"));
+    assert_eq!(upstream("feature-b"), "origin/feature-b");
+}
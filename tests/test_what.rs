@@ -4,6 +4,7 @@ mod common;
 use common::repo_with_feature_ahead;
 use git_x::commands::analysis::WhatCommand;
 use git_x::core::traits::Command;
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 
 // Helper function to strip ANSI escape codes for testing
@@ -69,3 +70,97 @@ fn test_what_command_direct() {
     // Restore original directory
     let _ = std::env::set_current_dir(&original_dir);
 }
+
+#[test]
+#[serial]
+fn test_what_format_diff_line_statuses() {
+    let cmd = WhatCommand::new(None);
+
+    assert_eq!(
+        cmd.format_diff_line("A\tsrc/new file.rs"),
+        Some(" ➕ src/new file.rs".to_string())
+    );
+    assert_eq!(
+        cmd.format_diff_line("R087\tsrc/old.rs\tsrc/new.rs"),
+        Some(" 🚚 src/old.rs → src/new.rs (87%)".to_string())
+    );
+    assert_eq!(
+        cmd.format_diff_line("R100\ta.txt\tb.txt"),
+        Some(" 🚚 a.txt → b.txt".to_string())
+    );
+    assert_eq!(
+        cmd.format_diff_line("C075\ttemplate.rs\tcopy.rs"),
+        Some(" 📋 template.rs → copy.rs (75%)".to_string())
+    );
+    assert_eq!(
+        cmd.format_diff_line("T\tlink"),
+        Some(" 🔧 link".to_string())
+    );
+    assert_eq!(cmd.format_diff_line(""), None);
+}
+
+#[test]
+#[serial]
+fn test_git_what_shows_renames() {
+    let repo = common::repo_with_branch("main");
+    repo.add_commit(
+        "old_name.txt",
+        "line one\nline two\nline three\nline four\n",
+        "Add file",
+    );
+    repo.create_branch("feature/rename");
+    std::process::Command::new("git")
+        .args(["mv", "old_name.txt", "new_name.txt"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    std::process::Command::new("git")
+        .args(["commit", "-m", "Rename file"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+
+    repo.run_git_x(&["what"])
+        .success()
+        .stdout(contains("🚚 old_name.txt → new_name.txt"))
+        .stdout(contains("❓").not())
+        .stdout(contains("➖").not());
+}
+
+#[test]
+#[serial]
+fn test_git_what_shows_copies() {
+    let repo = common::repo_with_branch("main");
+    let content = "line one\nline two\nline three\nline four\nline five\n";
+    repo.add_commit("original.txt", content, "Add file");
+    repo.create_branch("feature/copy");
+    // Without --find-copies-harder, git only looks for copies of modified files
+    std::fs::write(repo.path().join("copy.txt"), content).unwrap();
+    repo.add_commit(
+        "original.txt",
+        &format!("{content}line six\n"),
+        "Copy and extend file",
+    );
+
+    repo.run_git_x(&["what"])
+        .success()
+        .stdout(contains("📋 original.txt → copy.txt"))
+        .stdout(contains("➕").not());
+}
+
+#[test]
+#[serial]
+fn test_git_what_unknown_target_verbose_error() {
+    let repo = repo_with_feature_ahead("feature/test", "main");
+
+    repo.run_git_x(&["what", "--target", "no-such-ref"])
+        .success()
+        .stderr(contains("Git command failed: fatal: ambiguous argument"))
+        .stderr(contains("exit status").not());
+
+    repo.run_git_x(&["--verbose", "what", "--target", "no-such-ref"])
+        .success()
+        .stderr(contains("Git command failed: git rev-list"))
+        .stderr(contains("exit status 128"))
+        .stderr(contains("    fatal: ambiguous argument"));
+}
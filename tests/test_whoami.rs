@@ -0,0 +1,68 @@
+use serial_test::serial;
+mod common;
+
+use common::basic_repo;
+use git_x::commands::repository::{ConfigOrigin, WhoamiCommand};
+use predicates::str::contains;
+
+#[test]
+#[serial]
+fn test_config_origin_parse() {
+    let origins = ConfigOrigin::parse(
+        "global\tfile:/home/jane/.gitconfig\tJane Doe\nlocal\tfile:.git/config\tJane D.\n",
+    );
+
+    assert_eq!(origins.len(), 2);
+    assert_eq!(origins[0].scope, "global");
+    assert_eq!(origins[0].value, "Jane Doe");
+    assert_eq!(origins[1].describe(), "local: .git/config");
+}
+
+#[test]
+#[serial]
+fn test_whoami_parse_ident() {
+    assert_eq!(
+        WhoamiCommand::parse_ident("Jane Doe <jane@example.com> 1700000000 +0100"),
+        Some("Jane Doe <jane@example.com>".to_string())
+    );
+    assert_eq!(WhoamiCommand::parse_ident("garbage"), None);
+}
+
+#[test]
+#[serial]
+fn test_whoami_shows_identity_and_origin() {
+    let repo = basic_repo();
+    repo.git(&["config", "user.signingkey", "ABC123"]);
+    repo.git(&["config", "commit.gpgsign", "yes"]);
+    repo.git(&["config", "credential.helper", "cache"]);
+
+    repo.run_git_x(&["whoami"])
+        .success()
+        .stdout(contains("👤 Name: Test User (local: .git/config)"))
+        .stdout(contains("📧 Email: test@example.com (local: .git/config)"))
+        .stdout(contains("Next commit author: Test User <test@example.com>"))
+        .stdout(contains("🔏 Commit signing: on (openpgp)"))
+        .stdout(contains("🔑 Signing key: ABC123"))
+        .stdout(contains("• cache (local: .git/config)"));
+}
+
+#[test]
+#[serial]
+fn test_whoami_reports_environment_overrides() {
+    let repo = basic_repo();
+
+    assert_cmd::Command::cargo_bin("git-x")
+        .unwrap()
+        .arg("whoami")
+        .current_dir(repo.path())
+        .env("GIT_X_NON_INTERACTIVE", "1")
+        .env("GIT_AUTHOR_NAME", "Release Bot")
+        .assert()
+        .success()
+        .stdout(contains(
+            "Next commit author: Release Bot <test@example.com> (from GIT_AUTHOR_NAME)",
+        ))
+        .stdout(contains(
+            "Next commit committer: Test User <test@example.com>",
+        ));
+}
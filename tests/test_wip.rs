@@ -0,0 +1,118 @@
+use serial_test::serial;
+mod common;
+
+use common::repo_with_branch;
+use git_x::core::wip::{WIP_TRAILER, is_wip, wip_message};
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::contains;
+
+#[test]
+fn test_is_wip() {
+    assert!(is_wip("WIP"));
+    assert!(is_wip("WIP: halfway there"));
+    assert!(is_wip("wip! try again"));
+    assert!(is_wip(&format!("Save state\n\n{WIP_TRAILER}")));
+    assert!(!is_wip("Wipe the cache on logout"));
+    assert!(!is_wip("feat: add WIP badge"));
+
+    let message = wip_message(None, "feature");
+    assert!(message.starts_with("WIP: work in progress on feature\n"));
+    assert!(is_wip(&message));
+    assert!(wip_message(Some("parser"), "feature").starts_with("WIP: parser\n"));
+}
+
+#[test]
+#[serial]
+fn test_wip_and_unwip_round_trip() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("done.txt", "done\n", "feat: done");
+    std::fs::write(repo.path().join("README.md"), "# changed").unwrap();
+    std::fs::write(repo.path().join("new.txt"), "new\n").unwrap();
+
+    repo.run_git_x(&["wip"])
+        .success()
+        .stdout(contains("Committed 2 file(s) as WIP"))
+        .stdout(contains("on 'feature'"));
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"]),
+        "WIP: work in progress on feature"
+    );
+    assert!(
+        repo.git(&["log", "-1", "--format=%B"])
+            .contains(WIP_TRAILER)
+    );
+    assert_eq!(repo.git(&["status", "--porcelain"]), "");
+
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("🚧 1 WIP commit(s) on this branch"));
+
+    repo.run_git_x(&["unwip"])
+        .success()
+        .stdout(contains("Uncommitted WIP"))
+        .stdout(contains("2 file(s) back in the working tree"));
+    assert_eq!(repo.git(&["log", "-1", "--format=%s"]), "feat: done");
+    let status = repo.git(&["status", "--porcelain"]);
+    assert!(status.contains("README.md"));
+    assert!(status.contains("new.txt"));
+
+    repo.run_git_x(&["info"])
+        .success()
+        .stdout(contains("WIP commit").not());
+}
+
+#[test]
+#[serial]
+fn test_wip_skips_hooks_and_uses_message() {
+    let repo = repo_with_branch("main");
+    let hook = repo.path().join(".git/hooks/pre-commit");
+    std::fs::write(&hook, "#!/bin/sh\nexit 1\n").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    std::fs::write(repo.path().join("README.md"), "# changed").unwrap();
+
+    repo.run_git_x(&["wip", "parser halfway"])
+        .success()
+        .stdout(contains("as WIP"));
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"]),
+        "WIP: parser halfway"
+    );
+
+    repo.run_git_x(&["wip"])
+        .success()
+        .stdout(contains("Nothing to commit"));
+}
+
+#[test]
+#[serial]
+fn test_unwip_refuses_regular_commit() {
+    let repo = repo_with_branch("main");
+    repo.add_commit("a.txt", "a\n", "feat: add a");
+
+    repo.run_git_x(&["unwip"])
+        .success()
+        .stderr(contains("HEAD is not a WIP commit: 'feat: add a'"));
+    assert_eq!(repo.git(&["log", "-1", "--format=%s"]), "feat: add a");
+}
+
+#[test]
+#[serial]
+fn test_ready_flags_wip_commits() {
+    let repo = repo_with_branch("main");
+    repo.create_branch("feature");
+    repo.add_commit("b.txt", "b\n", "feat: add b");
+    std::fs::write(repo.path().join("b.txt"), "more\n").unwrap();
+    repo.run_git_x(&["wip"]).success();
+
+    repo.run_git_x(&["ready", "--target", "main"])
+        .code(1)
+        .stdout(contains("1 of 2 commit message(s) need work"))
+        .stdout(contains(
+            "WIP: work in progress on feature: work in progress; finish it with 'git x unwip'",
+        ));
+}